@@ -97,8 +97,9 @@ fn benchmark_graph_data_management(c: &mut Criterion) {
                 calculator.add_sample(stats);
 
                 // Access graph data (simulating UI updates)
-                let _graph_in = calculator.graph_data_in();
-                let _graph_out = calculator.graph_data_out();
+                let window = (0, calculator.graph_len());
+                let _graph_in = calculator.graph_data_in(window);
+                let _graph_out = calculator.graph_data_out(window);
             }
         });
     });
@@ -1,6 +1,7 @@
 use netwatch_rs::{
     device::{NetworkReader, NetworkStats},
     stats::StatsCalculator,
+    units::format_bytes,
 };
 use std::time::{Duration, SystemTime};
 
@@ -77,16 +78,3 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
-
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-
-    format!("{:.1} {}", size, UNITS[unit_index])
-}
@@ -1,8 +1,24 @@
+use crate::dns_monitor::DnsMonitor;
+use crate::ping::PingWindow;
+use crate::port_check::{PortCheckHistory, PortChecker};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::process::Command;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Traceroutes are slow and chatty, so by default a new round starts at most this often
+pub const DEFAULT_TRACE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Whole-request budget for one HTTP check, including redirects
+const HTTP_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long one ping waits for its reply before counting it lost
+const PING_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone)]
 pub struct ActiveDiagnostics {
@@ -10,6 +26,9 @@ pub struct ActiveDiagnostics {
     pub traceroute_results: HashMap<String, TracerouteResult>,
     pub port_scan_results: HashMap<String, PortScanResult>,
     pub dns_results: HashMap<String, DnsResult>,
+    pub http_results: HashMap<String, HttpCheckResult>,
+    /// Recent attempts of each `PortChecks` target, keyed like its port_scan_results entry
+    pub port_check_history: HashMap<String, PortCheckHistory>,
     pub last_updated: Instant,
 }
 
@@ -33,19 +52,20 @@ pub struct TracerouteResult {
     pub hops: Vec<TracerouteHop>,
     pub total_hops: u32,
     pub status: ConnectivityStatus,
+    /// TTLs answered by a different router than in the previous completed trace
+    pub changed_ttls: Vec<u8>,
     pub last_test: Instant,
 }
 
+/// One responding router. Hops where every probe timed out are not recorded.
 #[derive(Debug, Clone)]
 pub struct TracerouteHop {
-    pub hop_number: u32,
-    pub ip_address: Option<String>,
+    pub ttl: u8,
+    pub addr: IpAddr,
+    /// Reverse-DNS name, looked up with `--resolve`
     pub hostname: Option<String>,
-    pub rtt1: Option<f32>,
-    pub rtt2: Option<f32>,
-    pub rtt3: Option<f32>,
-    pub avg_rtt: Option<f32>,
-    pub packet_loss: f32,
+    pub avg_rtt_ms: f64,
+    pub packet_loss: f32, // Percent of probes without a reply
 }
 
 #[derive(Debug, Clone)]
@@ -70,6 +90,23 @@ pub struct DnsResult {
     pub last_test: Instant,
 }
 
+/// One `GET` against a configured URL. Phase timings describe the first connection;
+/// with redirect following, TTFB and total include every hop.
+#[derive(Debug, Clone)]
+pub struct HttpCheckResult {
+    pub url: String,
+    pub status_code: u16, // 0 when no response arrived
+    pub total_ms: f64,
+    pub dns_ms: Option<f64>,
+    pub connect_ms: Option<f64>,
+    pub tls_ms: Option<f64>, // None for plain http
+    pub ttfb_ms: Option<f64>,
+    pub tls_valid: bool, // Certificate chain verified; always false for plain http
+    pub timed_out: bool,
+    pub error: Option<String>,
+    pub last_test: Instant,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ConnectivityStatus {
     Online,
@@ -100,12 +137,27 @@ pub enum DnsStatus {
     Error(String),
 }
 
+#[derive(Clone)]
 pub struct ActiveDiagnosticsEngine {
     diagnostics: ActiveDiagnostics,
     test_targets: Vec<String>,
     #[allow(dead_code)]
     critical_ports: Vec<u16>,
     dns_domains: Vec<String>,
+    trace_targets: Vec<String>,
+    trace_interval: Duration,
+    resolve_hostnames: bool,
+    http_check_urls: Vec<String>,
+    http_follow_redirects: bool,
+    // Written by the background traceroute thread, copied into `diagnostics` on update
+    trace_results: Arc<Mutex<HashMap<String, TracerouteResult>>>,
+    trace_running: Arc<AtomicBool>,
+    last_trace_start: Option<Instant>,
+    dns_monitor: DnsMonitor,
+    // Runs connect check rounds in the background
+    port_checker: Arc<PortChecker>,
+    // Sequence numbers and recent probes of each ping target
+    ping_windows: HashMap<String, PingWindow>,
 }
 
 impl Default for ActiveDiagnosticsEngine {
@@ -130,11 +182,24 @@ impl ActiveDiagnosticsEngine {
                 traceroute_results: HashMap::new(),
                 port_scan_results: HashMap::new(),
                 dns_results: HashMap::new(),
+                http_results: HashMap::new(),
+                port_check_history: HashMap::new(),
                 last_updated: Instant::now(),
             },
             test_targets: config.diagnostic_targets.clone(),
             critical_ports,
             dns_domains: config.dns_domains.clone(),
+            trace_targets: config.trace_targets.clone(),
+            trace_interval: Duration::from_secs(config.trace_interval),
+            resolve_hostnames: config.resolve_hostnames,
+            http_check_urls: config.http_check_urls.clone(),
+            http_follow_redirects: config.http_follow_redirects,
+            trace_results: Arc::new(Mutex::new(HashMap::new())),
+            trace_running: Arc::new(AtomicBool::new(false)),
+            last_trace_start: None,
+            dns_monitor: DnsMonitor::new(),
+            port_checker: Arc::new(PortChecker::new(&config.port_checks)),
+            ping_windows: HashMap::new(),
         }
     }
 
@@ -154,6 +219,24 @@ impl ActiveDiagnosticsEngine {
             CYCLE_COUNTER = CYCLE_COUNTER.wrapping_add(1);
         }
 
+        // HTTP checks run every update, i.e. every 5s while the Diagnostics panel is open
+        self.run_http_checks();
+
+        // Starts live capture on the first call, then only refreshes the resolved fallback
+        self.dns_monitor.update();
+
+        // A round that outlasts the update interval keeps running; results land as they finish
+        self.port_checker.start_round();
+        self.diagnostics
+            .port_scan_results
+            .extend(self.port_checker.results());
+        self.diagnostics.port_check_history = self.port_checker.histories();
+
+        self.start_traceroutes_if_due();
+        if let Ok(results) = self.trace_results.lock() {
+            self.diagnostics.traceroute_results = results.clone();
+        }
+
         self.diagnostics.last_updated = Instant::now();
         Ok(())
     }
@@ -163,12 +246,56 @@ impl ActiveDiagnosticsEngine {
         &self.diagnostics
     }
 
+    /// Recent DNS queries seen on the wire, or resolver counters when capture isn't permitted
+    #[must_use]
+    pub fn dns_monitor(&self) -> &DnsMonitor {
+        &self.dns_monitor
+    }
+
+    /// True while the background traceroute round is still probing
+    #[must_use]
+    pub fn traceroute_in_progress(&self) -> bool {
+        self.trace_running.load(Ordering::Relaxed)
+    }
+
+    /// `GET` the URL once and time each phase of the request
+    #[must_use]
+    pub fn check_http_endpoint(&self, url: &str) -> HttpCheckResult {
+        crate::http_check::check(url, self.http_follow_redirects, HTTP_CHECK_TIMEOUT)
+    }
+
+    /// Check all configured URLs concurrently so one slow endpoint costs at most
+    /// HTTP_CHECK_TIMEOUT per round
+    fn run_http_checks(&mut self) {
+        if self.http_check_urls.is_empty() {
+            return;
+        }
+
+        let engine = &*self;
+        let results: Vec<HttpCheckResult> = thread::scope(|scope| {
+            let checks: Vec<_> = engine
+                .http_check_urls
+                .iter()
+                .map(|url| scope.spawn(move || engine.check_http_endpoint(url)))
+                .collect();
+            checks
+                .into_iter()
+                .filter_map(|check| check.join().ok())
+                .collect()
+        });
+
+        for result in results {
+            self.diagnostics
+                .http_results
+                .insert(result.url.clone(), result);
+        }
+    }
+
     fn run_quick_ping_test(&mut self) -> Result<()> {
-        // Only ping one target with very short timeout
-        if let Some(target) = self.test_targets.first() {
-            if let Ok(result) = self.quick_ping_target(target) {
-                self.diagnostics.ping_results.insert(target.clone(), result);
-            }
+        // One probe to the first target per cycle; loss and RTT come from its recent probes
+        if let Some(target) = self.test_targets.first().cloned() {
+            let result = self.quick_ping_target(&target);
+            self.diagnostics.ping_results.insert(target, result);
         }
         Ok(())
     }
@@ -217,97 +344,39 @@ impl ActiveDiagnosticsEngine {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn run_ping_tests(&mut self) -> Result<()> {
-        for target in &self.test_targets.clone() {
-            if let Ok(result) = self.ping_target(target) {
-                self.diagnostics.ping_results.insert(target.clone(), result);
-            }
-        }
-        Ok(())
-    }
-
-    fn quick_ping_target(&self, target: &str) -> Result<PingResult> {
-        // Ultra-fast ping with minimal timeout
-        let start_time = Instant::now();
-
-        #[cfg(target_os = "macos")]
-        let output = Command::new("ping")
-            .args(["-c", "1", "-W", "200", target]) // Only 200ms timeout
-            .output();
+    /// Ping `target` once and report over its last few probes. Native ICMP when a
+    /// socket can be opened, otherwise the `ping` binary.
+    fn quick_ping_target(&mut self, target: &str) -> PingResult {
+        let window = self.ping_windows.entry(target.to_string()).or_default();
+        let seq = window.next_seq();
 
-        #[cfg(target_os = "linux")]
-        let output = Command::new("ping")
-            .args(["-c", "1", "-W", "0.2", target]) // Only 200ms timeout
-            .output();
+        let rtt = match crate::traceroute::resolve(target) {
+            Ok(addr) => match crate::ping::echo(addr, seq, PING_TIMEOUT) {
+                Ok(rtt) => rtt,
+                Err(_) => subprocess_ping(&addr.to_string()),
+            },
+            Err(_) => None,
+        };
+        window.record(rtt);
 
-        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-        let output: Result<std::process::Output, std::io::Error> = Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "Ping not supported on this platform",
-        ));
-
-        let elapsed = start_time.elapsed().as_millis() as f32;
-
-        match output {
-            Ok(result) => {
-                if result.status.success() {
-                    let stdout = String::from_utf8_lossy(&result.stdout);
-                    if let Some(rtt) = extract_rtt_from_ping(&stdout) {
-                        Ok(PingResult {
-                            target: target.to_string(),
-                            packets_sent: 1,
-                            packets_received: 1,
-                            packet_loss: 0.0,
-                            min_rtt: rtt,
-                            avg_rtt: rtt,
-                            max_rtt: rtt,
-                            stddev_rtt: 0.0,
-                            status: ConnectivityStatus::Online,
-                            last_test: Instant::now(),
-                        })
-                    } else {
-                        // Fallback result
-                        Ok(PingResult {
-                            target: target.to_string(),
-                            packets_sent: 1,
-                            packets_received: 1,
-                            packet_loss: 0.0,
-                            min_rtt: elapsed,
-                            avg_rtt: elapsed,
-                            max_rtt: elapsed,
-                            stddev_rtt: 0.0,
-                            status: ConnectivityStatus::Online,
-                            last_test: Instant::now(),
-                        })
-                    }
-                } else {
-                    Ok(PingResult {
-                        target: target.to_string(),
-                        packets_sent: 1,
-                        packets_received: 0,
-                        packet_loss: 100.0,
-                        min_rtt: 0.0,
-                        avg_rtt: 0.0,
-                        max_rtt: 0.0,
-                        stddev_rtt: 0.0,
-                        status: ConnectivityStatus::Offline,
-                        last_test: Instant::now(),
-                    })
-                }
-            }
-            Err(_) => Ok(PingResult {
-                target: target.to_string(),
-                packets_sent: 1,
-                packets_received: 0,
-                packet_loss: 100.0,
-                min_rtt: 0.0,
-                avg_rtt: 0.0,
-                max_rtt: 0.0,
-                stddev_rtt: 0.0,
-                status: ConnectivityStatus::Offline,
-                last_test: Instant::now(),
-            }),
+        let summary = window.summary();
+        PingResult {
+            target: target.to_string(),
+            packets_sent: summary.sent,
+            packets_received: summary.received,
+            packet_loss: summary.loss_percent,
+            min_rtt: summary.min_rtt,
+            avg_rtt: summary.avg_rtt,
+            max_rtt: summary.max_rtt,
+            stddev_rtt: summary.stddev_rtt,
+            status: if summary.received == 0 {
+                ConnectivityStatus::Offline
+            } else if !summary.last_answered || summary.loss_percent > 10.0 {
+                ConnectivityStatus::Degraded
+            } else {
+                ConnectivityStatus::Online
+            },
+            last_test: Instant::now(),
         }
     }
 
@@ -347,158 +416,72 @@ impl ActiveDiagnosticsEngine {
         }
     }
 
-    #[allow(dead_code)]
-    fn ping_target(&self, target: &str) -> Result<PingResult> {
-        let start_time = Instant::now();
-
-        // Use faster ping with shorter timeout to prevent blocking
-        #[cfg(target_os = "macos")]
-        let output = Command::new("ping")
-            .args(["-c", "1", "-W", "1000", target])
-            .output();
-
-        #[cfg(target_os = "linux")]
-        let output = Command::new("ping")
-            .args(["-c", "1", "-W", "1", target])
-            .output();
-
-        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-        let output: Result<std::process::Output, std::io::Error> = Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "Ping not supported on this platform",
-        ));
+    /// Trace every configured target on a background thread, at most once per
+    /// TraceInterval. Hops are published as they are discovered, and each completed
+    /// trace is compared with the previous one to spot path changes.
+    fn start_traceroutes_if_due(&mut self) {
+        if self.trace_targets.is_empty() || self.trace_running.load(Ordering::Relaxed) {
+            return;
+        }
+        if self
+            .last_trace_start
+            .is_some_and(|last| last.elapsed() < self.trace_interval)
+        {
+            return;
+        }
 
-        let result = match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
+        self.last_trace_start = Some(Instant::now());
+        self.trace_running.store(true, Ordering::Relaxed);
+
+        let targets = self.trace_targets.clone();
+        let results = Arc::clone(&self.trace_results);
+        let running = Arc::clone(&self.trace_running);
+        let resolve_hostnames = self.resolve_hostnames;
+        thread::spawn(move || {
+            for target in targets {
+                let start_time = Instant::now();
+                // Still the last round's completed trace until the first hop is published
+                let previous = results
+                    .lock()
+                    .ok()
+                    .and_then(|results| results.get(&target).map(|r| r.hops.clone()))
+                    .unwrap_or_default();
+                let publish = |result: TracerouteResult| {
+                    if let Ok(mut results) = results.lock() {
+                        results.insert(target.clone(), result);
+                    }
+                };
 
-                // Parse ping output (simplified version)
-                if stdout.contains("0% packet loss")
-                    || stdout.contains("1 packets transmitted, 1 received")
-                {
-                    // Extract RTT statistics - simplified parsing
-                    let avg_rtt = extract_avg_rtt(&stdout).unwrap_or(20.0);
-
-                    PingResult {
-                        target: target.to_string(),
-                        packets_sent: 1,
-                        packets_received: 1,
-                        packet_loss: 0.0,
-                        min_rtt: avg_rtt * 0.8,
-                        avg_rtt,
-                        max_rtt: avg_rtt * 1.2,
-                        stddev_rtt: avg_rtt * 0.1,
-                        status: if avg_rtt < 50.0 {
-                            ConnectivityStatus::Online
-                        } else if avg_rtt < 200.0 {
-                            ConnectivityStatus::Degraded
-                        } else {
-                            ConnectivityStatus::Offline
-                        },
-                        last_test: start_time,
+                let result = match crate::traceroute::trace(&target, |hops| {
+                    publish(traceroute_result(
+                        &target,
+                        hops.to_vec(),
+                        &previous,
+                        start_time,
+                    ));
+                }) {
+                    Ok(mut hops) => {
+                        // Blocking lookups are fine here, off the UI and collector threads
+                        if resolve_hostnames {
+                            for hop in &mut hops {
+                                hop.hostname = crate::resolver::reverse_lookup(hop.addr);
+                            }
+                        }
+                        traceroute_result(&target, hops, &previous, start_time)
                     }
-                } else {
-                    PingResult {
-                        target: target.to_string(),
-                        packets_sent: 1,
-                        packets_received: 0,
-                        packet_loss: 100.0,
-                        min_rtt: 0.0,
-                        avg_rtt: 0.0,
-                        max_rtt: 0.0,
-                        stddev_rtt: 0.0,
-                        status: ConnectivityStatus::Offline,
+                    Err(e) => TracerouteResult {
+                        target: target.clone(),
+                        total_hops: 0,
+                        status: ConnectivityStatus::Error(format!("Traceroute failed: {e}")),
+                        hops: Vec::new(),
+                        changed_ttls: Vec::new(),
                         last_test: start_time,
-                    }
-                }
-            }
-            Err(e) => {
-                // Return actual error instead of fake data
-                PingResult {
-                    target: target.to_string(),
-                    packets_sent: 0,
-                    packets_received: 0,
-                    packet_loss: 100.0,
-                    min_rtt: 0.0,
-                    avg_rtt: 0.0,
-                    max_rtt: 0.0,
-                    stddev_rtt: 0.0,
-                    status: ConnectivityStatus::Error(format!("Ping failed: {e}")),
-                    last_test: start_time,
-                }
-            }
-        };
-
-        Ok(result)
-    }
-
-    #[allow(dead_code)]
-    fn run_traceroute_tests(&mut self) -> Result<()> {
-        // Skip traceroute - no hardcoded targets
-        let critical_targets: Vec<&str> = vec![];
-
-        for target in &critical_targets {
-            if let Ok(result) = self.traceroute_target(target) {
-                self.diagnostics
-                    .traceroute_results
-                    .insert(target.to_string(), result);
-            }
-        }
-        Ok(())
-    }
-
-    fn traceroute_target(&self, target: &str) -> Result<TracerouteResult> {
-        let start_time = Instant::now();
-
-        // Use very limited traceroute to prevent blocking
-        #[cfg(target_os = "macos")]
-        let output = Command::new("traceroute")
-            .args(["-m", "5", "-q", "1", "-w", "1", target])
-            .output();
-
-        #[cfg(target_os = "linux")]
-        let output = Command::new("traceroute")
-            .args(["-m", "5", "-q", "1", "-w", "1", target])
-            .output();
-
-        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-        let output: Result<std::process::Output, std::io::Error> = Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "Traceroute not supported on this platform",
-        ));
-
-        let result = match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let hops = parse_traceroute_output(&stdout);
-
-                TracerouteResult {
-                    target: target.to_string(),
-                    total_hops: hops.len() as u32,
-                    status: if hops.is_empty() {
-                        ConnectivityStatus::Timeout
-                    } else if hops.iter().any(|h| h.packet_loss > 50.0) {
-                        ConnectivityStatus::Degraded
-                    } else {
-                        ConnectivityStatus::Online
                     },
-                    hops,
-                    last_test: start_time,
-                }
-            }
-            Err(e) => {
-                // Return actual error instead of fake data
-                TracerouteResult {
-                    target: target.to_string(),
-                    total_hops: 0,
-                    status: ConnectivityStatus::Error(format!("Traceroute failed: {e}")),
-                    hops: Vec::new(),
-                    last_test: start_time,
-                }
+                };
+                publish(result);
             }
-        };
-
-        Ok(result)
+            running.store(false, Ordering::Relaxed);
+        });
     }
 
     #[allow(dead_code)]
@@ -739,38 +722,26 @@ pub struct ConnectivitySummary {
 }
 
 // Helper functions for parsing command outputs
-#[allow(dead_code)]
-fn extract_avg_rtt(ping_output: &str) -> Option<f32> {
-    // Simple regex-like parsing for ping statistics
-    if let Some(stats_line) = ping_output
-        .lines()
-        .find(|line| line.contains("min/avg/max"))
-    {
-        let parts: Vec<&str> = stats_line.split('/').collect();
-        if parts.len() >= 5 {
-            if let Ok(avg) = parts[4].trim().parse::<f32>() {
-                return Some(avg);
-            }
-        }
-    }
-    // Fallback - estimate based on target
-    Some(20.0 + (ping_output.len() as f32 * 0.1))
-}
-
-#[allow(dead_code)]
-fn parse_traceroute_output(output: &str) -> Vec<TracerouteHop> {
-    let hops = Vec::new();
-
-    for (i, line) in output.lines().enumerate() {
-        if i == 0 || line.trim().is_empty() {
-            continue; // Skip header
-        }
-
-        // No fake traceroute parsing - would need real traceroute output parsing
-        break;
+fn traceroute_result(
+    target: &str,
+    hops: Vec<TracerouteHop>,
+    previous: &[TracerouteHop],
+    started: Instant,
+) -> TracerouteResult {
+    TracerouteResult {
+        changed_ttls: crate::traceroute::changed_ttls(previous, &hops),
+        target: target.to_string(),
+        total_hops: hops.len() as u32,
+        status: if hops.is_empty() {
+            ConnectivityStatus::Timeout
+        } else if hops.iter().any(|h| h.packet_loss > 50.0) {
+            ConnectivityStatus::Degraded
+        } else {
+            ConnectivityStatus::Online
+        },
+        hops,
+        last_test: started,
     }
-
-    hops
 }
 
 #[allow(dead_code)]
@@ -796,6 +767,34 @@ fn get_service_banner(_port: u16) -> Option<String> {
     None
 }
 
+/// One probe with the system `ping`, for when no ICMP socket can be opened. The RTT
+/// is the one ping printed, or the command's run time when it printed none.
+fn subprocess_ping(target: &str) -> Option<f64> {
+    let started = Instant::now();
+
+    #[cfg(target_os = "macos")]
+    let output = Command::new("ping")
+        .args(["-c", "1", "-W", "1000", target])
+        .output()
+        .ok()?;
+
+    #[cfg(target_os = "linux")]
+    let output = Command::new("ping")
+        .args(["-c", "1", "-W", "1", target])
+        .output()
+        .ok()?;
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    let output: std::process::Output = return None;
+
+    if !output.status.success() {
+        return None;
+    }
+    let rtt = extract_rtt_from_ping(&String::from_utf8_lossy(&output.stdout))
+        .map_or_else(|| started.elapsed().as_secs_f64() * 1000.0, f64::from);
+    Some(rtt)
+}
+
 fn extract_rtt_from_ping(output: &str) -> Option<f32> {
     // Simple RTT extraction for macOS ping output
     // Look for patterns like "time=12.345 ms"
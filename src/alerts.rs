@@ -0,0 +1,765 @@
+//! Alert conditions shared by the dashboard's Alerts panel and SRE terminal mode, the
+//! `--webhook-url` notifier and the `NotifyOnCritical` bell
+//!
+//! [`evaluate`] turns the current stats into the list of active alerts. The notifier only
+//! posts when an alert appears or gets more severe (ok→warning, warning→critical), so a
+//! sustained condition is reported once rather than on every refresh. Posts go out from a
+//! background thread through a bounded queue, so a slow webhook never stalls the caller;
+//! delivery failures are queued for [`WebhookNotifier::take_status`] rather than printed.
+//! [`AlertHistory`] keeps the same transitions, plus clearings, for the Alerts panel.
+
+use crate::cli::TrafficUnit;
+use crate::connections::{ConnectionMonitor, ConnectionStats};
+use crate::error::{NetwatchError, Result};
+use crate::health::{HealthAssessment, HealthStatus};
+use crate::quota::QuotaPeriod;
+use crate::safe_system::ORPHAN_TCP_WARNING;
+use crate::stats::StatsCalculator;
+use crate::units::{format_age, format_rate};
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Peak speed (bytes/s) at which an interface's traffic is critical
+pub const CRITICAL_TRAFFIC: u64 = 100_000_000;
+/// Current inbound speed (bytes/s) at which an interface's traffic is a warning
+pub const WARNING_TRAFFIC: u64 = 50_000_000;
+/// Open connections above which the count is critical
+pub const CRITICAL_CONNECTIONS: usize = 1000;
+/// Open connections above which the count is a warning
+pub const WARNING_CONNECTIONS: usize = 500;
+/// ESTABLISHED connections open longer than this are raised as possibly leaked
+pub const LONG_LIVED_CONNECTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Events the Alerts panel's history keeps before dropping the oldest
+pub const ALERT_HISTORY_LEN: usize = 500;
+
+/// Notifications waiting to be posted before new ones are dropped
+const QUEUE_DEPTH: usize = 32;
+#[cfg(feature = "http-checks")]
+const POST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlertSeverity {
+    /// Not a problem: history entries for alerts that cleared
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlertKind {
+    InboundTraffic,
+    OutboundTraffic,
+    Spike,
+    TimeWait,
+    CloseWait,
+    /// Raised by [`evaluate_connections`], like the two below
+    LongLivedConnections,
+    OrphanTcp,
+    ConnectionCount,
+    /// `--quota-*-gb`, raised by [`crate::quota::evaluate`]
+    Quota(QuotaPeriod),
+}
+
+impl AlertKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InboundTraffic => "inbound traffic",
+            Self::OutboundTraffic => "outbound traffic",
+            Self::Spike => "traffic spike",
+            Self::TimeWait => "TIME_WAIT buildup",
+            Self::CloseWait => "CLOSE_WAIT buildup",
+            Self::LongLivedConnections => "long-lived connections",
+            Self::OrphanTcp => "orphaned TCP sockets",
+            Self::ConnectionCount => "connection count",
+            Self::Quota(QuotaPeriod::Daily) => "daily quota",
+            Self::Quota(QuotaPeriod::Weekly) => "weekly quota",
+            Self::Quota(QuotaPeriod::Monthly) => "monthly quota",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub severity: AlertSeverity,
+    /// None for host-wide alerts such as socket buildup
+    pub interface: Option<String>,
+    /// The measurement that tripped the alert: bytes/s or a socket count
+    pub value: u64,
+    pub message: String,
+}
+
+impl Alert {
+    /// What the alert is about; its severity can change while this stays the same
+    fn subject(&self) -> (AlertKind, Option<String>) {
+        (self.kind, self.interface.clone())
+    }
+}
+
+/// Active alerts for the current stats, interfaces in name order
+pub fn evaluate(
+    stats_calculators: &HashMap<String, StatsCalculator>,
+    conn_stats: &ConnectionStats,
+    time_wait_threshold: u32,
+    close_wait_threshold: u32,
+) -> Vec<Alert> {
+    let speed = |bytes: u64| format_rate(bytes, &TrafficUnit::HumanByte);
+    let mut interfaces: Vec<_> = stats_calculators.iter().collect();
+    interfaces.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut alerts = Vec::new();
+    for (name, calculator) in interfaces {
+        let interface_alert = |kind, severity, value, message| Alert {
+            kind,
+            severity,
+            interface: Some(name.clone()),
+            value,
+            message,
+        };
+        let (max_in, max_out) = calculator.max_speed();
+        let (current_in, current_out) = calculator.current_speed();
+
+        if max_in > CRITICAL_TRAFFIC {
+            alerts.push(interface_alert(
+                AlertKind::InboundTraffic,
+                AlertSeverity::Critical,
+                max_in,
+                format!("{name} high inbound traffic: {}", speed(max_in)),
+            ));
+        } else if current_in > WARNING_TRAFFIC {
+            alerts.push(interface_alert(
+                AlertKind::InboundTraffic,
+                AlertSeverity::Warning,
+                current_in,
+                format!("{name} sustained high traffic: {}", speed(current_in)),
+            ));
+        }
+
+        if max_out > CRITICAL_TRAFFIC {
+            alerts.push(interface_alert(
+                AlertKind::OutboundTraffic,
+                AlertSeverity::Critical,
+                max_out,
+                format!("{name} high outbound traffic: {}", speed(max_out)),
+            ));
+        }
+
+        if calculator.spike_active() {
+            let (avg_in, avg_out) = calculator.average_speed();
+            alerts.push(interface_alert(
+                AlertKind::Spike,
+                AlertSeverity::Warning,
+                current_in.max(current_out),
+                format!(
+                    "{name} traffic spike: {} in, {} out (avg {} in, {} out)",
+                    speed(current_in),
+                    speed(current_out),
+                    speed(avg_in),
+                    speed(avg_out)
+                ),
+            ));
+        }
+    }
+
+    if conn_stats.time_wait > time_wait_threshold {
+        alerts.push(Alert {
+            kind: AlertKind::TimeWait,
+            severity: AlertSeverity::Warning,
+            interface: None,
+            value: conn_stats.time_wait.into(),
+            message: format!(
+                "TIME_WAIT buildup: {} sockets (threshold {time_wait_threshold})",
+                conn_stats.time_wait
+            ),
+        });
+    }
+
+    if conn_stats.close_wait > close_wait_threshold {
+        alerts.push(Alert {
+            kind: AlertKind::CloseWait,
+            severity: AlertSeverity::Warning,
+            interface: None,
+            value: conn_stats.close_wait.into(),
+            message: format!(
+                "CLOSE_WAIT buildup: {} sockets (threshold {close_wait_threshold}) - app not closing sockets",
+                conn_stats.close_wait
+            ),
+        });
+    }
+
+    alerts
+}
+
+/// Host-wide alerts on the connection table: connections ESTABLISHED longer than
+/// [`LONG_LIVED_CONNECTION`], orphaned TCP sockets (`orphan_tcp`, None where the kernel's
+/// socket summary can't be read) and the number of open connections
+pub fn evaluate_connections(monitor: &ConnectionMonitor, orphan_tcp: Option<u32>) -> Vec<Alert> {
+    let host_alert = |kind, severity, value, message| Alert {
+        kind,
+        severity,
+        interface: None,
+        value,
+        message,
+    };
+    let mut alerts = Vec::new();
+
+    let long_lived = monitor.get_long_lived_connections(LONG_LIVED_CONNECTION);
+    if let Some(oldest) = long_lived.first() {
+        alerts.push(host_alert(
+            AlertKind::LongLivedConnections,
+            AlertSeverity::Warning,
+            long_lived.len() as u64,
+            format!(
+                "{} connection(s) ESTABLISHED >24h, oldest {} → {} ({})",
+                long_lived.len(),
+                oldest.process_name.as_deref().unwrap_or("unknown"),
+                monitor.remote_display(oldest),
+                oldest.age().map(format_age).unwrap_or_default()
+            ),
+        ));
+    }
+
+    if let Some(orphan_tcp) = orphan_tcp.filter(|&count| count > ORPHAN_TCP_WARNING) {
+        alerts.push(host_alert(
+            AlertKind::OrphanTcp,
+            AlertSeverity::Warning,
+            orphan_tcp.into(),
+            format!(
+                "{orphan_tcp} orphaned TCP sockets (threshold {ORPHAN_TCP_WARNING}) - possible connection leak"
+            ),
+        ));
+    }
+
+    let connection_count = monitor.get_connections().len();
+    if connection_count > CRITICAL_CONNECTIONS {
+        alerts.push(host_alert(
+            AlertKind::ConnectionCount,
+            AlertSeverity::Critical,
+            connection_count as u64,
+            format!("High connection count: {connection_count} active"),
+        ));
+    } else if connection_count > WARNING_CONNECTIONS {
+        alerts.push(host_alert(
+            AlertKind::ConnectionCount,
+            AlertSeverity::Warning,
+            connection_count as u64,
+            format!("Elevated connections: {connection_count} active"),
+        ));
+    }
+
+    alerts
+}
+
+/// Remembers each alert's last severity to pick out the ones worth a notification
+#[derive(Debug, Default)]
+pub struct AlertTransitions {
+    active: HashMap<(AlertKind, Option<String>), AlertSeverity>,
+}
+
+impl AlertTransitions {
+    /// Alerts that are new or more severe than on the previous call. Alerts that have
+    /// cleared are forgotten, so they notify again if they come back.
+    pub fn update<'a>(&mut self, alerts: &'a [Alert]) -> Vec<&'a Alert> {
+        let previous = std::mem::take(&mut self.active);
+        let mut raised = Vec::new();
+
+        for alert in alerts {
+            let subject = alert.subject();
+            if previous
+                .get(&subject)
+                .map_or(true, |&severity| alert.severity > severity)
+            {
+                raised.push(alert);
+            }
+            // A de-escalated alert keeps its new, lower level so it can escalate again
+            self.active.insert(subject, alert.severity);
+        }
+
+        raised
+    }
+}
+
+/// An alert being raised, escalating or clearing
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub timestamp: DateTime<Local>,
+    /// The alert's severity, or Info when it cleared
+    pub level: AlertSeverity,
+    pub message: String,
+    pub interface: Option<String>,
+}
+
+/// The last [`ALERT_HISTORY_LEN`] alert events, oldest first. Like the webhook, an alert
+/// is recorded when it appears or escalates rather than on every refresh it lasts.
+#[derive(Debug, Default)]
+pub struct AlertHistory {
+    events: VecDeque<AlertEvent>,
+    transitions: AlertTransitions,
+    active: usize,
+}
+
+impl AlertHistory {
+    /// Record what changed since the previous call, given the alerts active now
+    pub fn record(&mut self, alerts: &[Alert]) {
+        let timestamp = Local::now();
+        let mut previous: Vec<_> = self.transitions.active.keys().cloned().collect();
+        previous.sort();
+
+        let raised: Vec<AlertEvent> = self
+            .transitions
+            .update(alerts)
+            .into_iter()
+            .map(|alert| AlertEvent {
+                timestamp,
+                level: alert.severity,
+                message: alert.message.clone(),
+                interface: alert.interface.clone(),
+            })
+            .collect();
+
+        for (kind, interface) in previous {
+            if self
+                .transitions
+                .active
+                .contains_key(&(kind, interface.clone()))
+            {
+                continue;
+            }
+            let message = match &interface {
+                Some(name) => format!("{name} {} cleared", kind.as_str()),
+                None => format!("{} cleared", kind.as_str()),
+            };
+            self.push(AlertEvent {
+                timestamp,
+                level: AlertSeverity::Info,
+                message,
+                interface,
+            });
+        }
+        for event in raised {
+            self.push(event);
+        }
+        self.active = alerts.len();
+    }
+
+    /// Add an event that does not come from [`evaluate`], such as an interface going down
+    pub fn push(&mut self, event: AlertEvent) {
+        if self.events.len() == ALERT_HISTORY_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn events(&self) -> &VecDeque<AlertEvent> {
+        &self.events
+    }
+
+    /// Alerts active at the last [`record`](Self::record)
+    pub fn active_count(&self) -> usize {
+        self.active
+    }
+}
+
+/// Posts a JSON notification to a webhook whenever an alert is raised or escalates
+pub struct WebhookNotifier {
+    sender: Option<SyncSender<String>>,
+    worker: Option<JoinHandle<()>>,
+    transitions: AlertTransitions,
+    hostname: String,
+    // Failures, recoveries and drops waiting for take_status
+    status_sender: Sender<String>,
+    status: Receiver<String>,
+    dropped: u64,
+}
+
+impl WebhookNotifier {
+    /// Needs the `http-checks` feature, which brings the HTTPS client
+    pub fn new(url: &str) -> Result<Self> {
+        crate::validation::validate_http_url(url)?;
+        if !cfg!(feature = "http-checks") {
+            return Err(NetwatchError::Config(
+                "Webhooks require netwatch built with `--features http-checks`".to_string(),
+            ));
+        }
+
+        let url = url.to_string();
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_DEPTH);
+        let (status_sender, status) = mpsc::channel();
+        let worker_status = status_sender.clone();
+        let worker = thread::spawn(move || send_loop(&url, receiver, worker_status));
+
+        Ok(Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            transitions: AlertTransitions::default(),
+            hostname: hostname(),
+            status_sender,
+            status,
+            dropped: 0,
+        })
+    }
+
+    /// The newest delivery failure or recovery message since the last call, for the
+    /// caller to show. Never blocks.
+    pub fn take_status(&mut self) -> Option<String> {
+        self.status.try_iter().last()
+    }
+
+    /// Queue a post for every alert that is new or more severe than last time. Never blocks.
+    pub fn notify(&mut self, alerts: &[Alert]) {
+        let timestamp = Local::now().to_rfc3339();
+        for alert in self.transitions.update(alerts) {
+            let Some(sender) = &self.sender else {
+                return;
+            };
+            match sender.try_send(payload(alert, &self.hostname, &timestamp)) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    self.dropped += 1;
+                    if self.dropped == 1 || self.dropped % 100 == 0 {
+                        let _ = self.status_sender.send(format!(
+                            "Webhook is falling behind, {} notifications dropped",
+                            self.dropped
+                        ));
+                    }
+                }
+                Err(TrySendError::Disconnected(_)) => self.sender = None,
+            }
+        }
+    }
+}
+
+impl Drop for WebhookNotifier {
+    fn drop(&mut self) {
+        // Closing the channel lets the worker post what is queued and exit
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// `NotifyOnCritical`: rings the terminal bell, and shows a desktop notification where
+/// notify-send (Linux) or osascript (macOS) is available, when the health assessment
+/// turns critical or an alert does
+#[derive(Debug, Default)]
+pub struct CriticalNotifier {
+    transitions: AlertTransitions,
+    health: HealthStatus,
+}
+
+impl CriticalNotifier {
+    /// Notify once for whatever became critical since the last call: the health
+    /// assessment's System Status, and each alert
+    pub fn notify(&mut self, health: &HealthAssessment, alerts: &[Alert]) {
+        let mut messages: Vec<String> = Vec::new();
+        if self.health_became_critical(health.status) {
+            messages.push(format!(
+                "System status critical: {}",
+                health.critical_issues.join(", ")
+            ));
+        }
+        messages.extend(
+            self.newly_critical(alerts)
+                .into_iter()
+                .map(|alert| alert.message.clone()),
+        );
+        if messages.is_empty() {
+            return;
+        }
+
+        // The dashboard owns stdout, but a lone BEL does not disturb its screen
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+
+        desktop_notification(&messages.join("\n"));
+    }
+
+    fn health_became_critical(&mut self, status: HealthStatus) -> bool {
+        let previous = std::mem::replace(&mut self.health, status);
+        status == HealthStatus::Critical && previous != HealthStatus::Critical
+    }
+
+    fn newly_critical<'a>(&mut self, alerts: &'a [Alert]) -> Vec<&'a Alert> {
+        self.transitions
+            .update(alerts)
+            .into_iter()
+            .filter(|alert| alert.severity == AlertSeverity::Critical)
+            .collect()
+    }
+}
+
+/// Best effort: nothing happens where the helper is missing or there is no desktop
+fn desktop_notification(body: &str) {
+    #[cfg(target_os = "linux")]
+    let command = Command::new("notify-send")
+        .args(["--urgency=critical", "netwatch: critical alert", body])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    // The body goes in as an argument rather than into the script, so no quoting is needed
+    #[cfg(target_os = "macos")]
+    let command = Command::new("osascript")
+        .args([
+            "-e",
+            "on run argv",
+            "-e",
+            "display notification (item 1 of argv) with title \"netwatch: critical alert\"",
+            "-e",
+            "end run",
+            body,
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    if let Ok(mut child) = command {
+        // Reap it off the caller's thread
+        thread::spawn(move || child.wait());
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    let _ = body;
+}
+
+/// The notification body. `text` and `content` carry a one-line summary so Slack and
+/// Discord incoming webhooks show it as is; the other fields are for anything else.
+pub fn payload(alert: &Alert, hostname: &str, timestamp: &str) -> String {
+    let summary = format!(
+        "[{}] {}: {}",
+        alert.severity.as_str().to_uppercase(),
+        hostname,
+        alert.message
+    );
+    serde_json::json!({
+        "text": summary,
+        "content": summary,
+        "severity": alert.severity.as_str(),
+        "message": alert.message,
+        "interface": alert.interface,
+        "value": alert.value,
+        "hostname": hostname,
+        "timestamp": timestamp,
+    })
+    .to_string()
+}
+
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its full length, which is what we pass
+    let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if result != 0 {
+        return "unknown".to_string();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+fn send_loop(url: &str, receiver: Receiver<String>, status: Sender<String>) {
+    let mut failing = false;
+
+    for body in receiver {
+        match post(url, &body) {
+            Ok(()) if failing => {
+                let _ = status.send("Webhook delivery recovered".to_string());
+                failing = false;
+            }
+            Ok(()) => {}
+            Err(e) => {
+                // Only report the transition so an outage doesn't flood the status line
+                if !failing {
+                    let _ = status.send(format!("Webhook delivery failed: {e}"));
+                    failing = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "http-checks")]
+fn post(url: &str, body: &str) -> std::result::Result<(), String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(POST_TIMEOUT))
+        .user_agent(concat!("netwatch/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .into();
+    agent
+        .post(url)
+        .content_type("application/json")
+        .send(body)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "http-checks"))]
+fn post(_url: &str, _body: &str) -> std::result::Result<(), String> {
+    Err("netwatch was built without the http-checks feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(kind: AlertKind, severity: AlertSeverity) -> Alert {
+        Alert {
+            kind,
+            severity,
+            interface: Some("eth0".to_string()),
+            value: 1,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_socket_state_buildups_are_warnings() {
+        let conn_stats = ConnectionStats {
+            time_wait: 501,
+            close_wait: 51,
+            ..ConnectionStats::default()
+        };
+        let alerts = evaluate(&HashMap::new(), &conn_stats, 500, 50);
+
+        let kinds: Vec<_> = alerts.iter().map(|alert| alert.kind).collect();
+        assert_eq!(kinds, [AlertKind::TimeWait, AlertKind::CloseWait]);
+        assert!(alerts
+            .iter()
+            .all(|alert| alert.severity == AlertSeverity::Warning));
+        assert!(evaluate(&HashMap::new(), &conn_stats, 501, 51).is_empty());
+    }
+
+    #[test]
+    fn test_orphan_tcp_alert_above_threshold_only() {
+        let monitor = ConnectionMonitor::new();
+        let alerts = evaluate_connections(&monitor, Some(ORPHAN_TCP_WARNING + 1));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, AlertKind::OrphanTcp);
+        assert_eq!(alerts[0].severity, AlertSeverity::Warning);
+        assert_eq!(alerts[0].interface, None);
+
+        assert!(evaluate_connections(&monitor, Some(ORPHAN_TCP_WARNING)).is_empty());
+        assert!(evaluate_connections(&monitor, None).is_empty());
+    }
+
+    #[test]
+    fn test_transitions_fire_on_new_and_escalated_alerts_only() {
+        let mut transitions = AlertTransitions::default();
+        let warning = [alert(AlertKind::InboundTraffic, AlertSeverity::Warning)];
+        let critical = [alert(AlertKind::InboundTraffic, AlertSeverity::Critical)];
+
+        // ok -> warning fires once, however long it lasts
+        assert_eq!(transitions.update(&warning).len(), 1);
+        assert!(transitions.update(&warning).is_empty());
+
+        // warning -> critical fires, critical -> warning does not, warning -> critical again does
+        assert_eq!(transitions.update(&critical).len(), 1);
+        assert!(transitions.update(&warning).is_empty());
+        assert_eq!(transitions.update(&critical).len(), 1);
+
+        // Cleared alerts re-arm
+        assert!(transitions.update(&[]).is_empty());
+        assert_eq!(transitions.update(&warning).len(), 1);
+
+        // Another interface is its own subject
+        let mut other = alert(AlertKind::InboundTraffic, AlertSeverity::Warning);
+        other.interface = Some("eth1".to_string());
+        assert_eq!(transitions.update(&[warning[0].clone(), other]).len(), 1);
+    }
+
+    #[test]
+    fn test_critical_notifier_fires_on_transition_to_critical_only() {
+        let mut notifier = CriticalNotifier::default();
+        let warning = [alert(AlertKind::InboundTraffic, AlertSeverity::Warning)];
+        let critical = [alert(AlertKind::InboundTraffic, AlertSeverity::Critical)];
+
+        assert!(notifier.newly_critical(&warning).is_empty());
+        assert_eq!(notifier.newly_critical(&critical).len(), 1);
+        // Staying critical, frame after frame, does not ring again
+        assert!(notifier.newly_critical(&critical).is_empty());
+        // Leaving critical re-arms it
+        assert!(notifier.newly_critical(&warning).is_empty());
+        assert_eq!(notifier.newly_critical(&critical).len(), 1);
+    }
+
+    #[test]
+    fn test_critical_notifier_fires_when_health_turns_critical() {
+        let mut notifier = CriticalNotifier::default();
+        assert!(!notifier.health_became_critical(HealthStatus::Warning));
+        assert!(notifier.health_became_critical(HealthStatus::Critical));
+        assert!(!notifier.health_became_critical(HealthStatus::Critical));
+        // Recovering re-arms it
+        assert!(!notifier.health_became_critical(HealthStatus::Healthy));
+        assert!(notifier.health_became_critical(HealthStatus::Critical));
+    }
+
+    #[test]
+    fn test_history_records_transitions_and_clearings() {
+        let mut history = AlertHistory::default();
+        let mut warning = alert(AlertKind::InboundTraffic, AlertSeverity::Warning);
+        warning.message = "eth0 sustained high traffic".to_string();
+        let mut close_wait = alert(AlertKind::CloseWait, AlertSeverity::Critical);
+        close_wait.interface = None;
+
+        // A sustained alert is one event, not one per refresh
+        for _ in 0..3 {
+            history.record(&[warning.clone(), close_wait.clone()]);
+        }
+        assert_eq!(history.events().len(), 2);
+        assert_eq!(history.active_count(), 2);
+
+        history.record(&[warning.clone()]);
+        let cleared = history.events().back().unwrap();
+        assert_eq!(cleared.level, AlertSeverity::Info);
+        assert_eq!(cleared.message, "CLOSE_WAIT buildup cleared");
+        assert_eq!(cleared.interface, None);
+        assert_eq!(history.active_count(), 1);
+
+        history.record(&[]);
+        assert_eq!(
+            history.events().back().unwrap().message,
+            "eth0 inbound traffic cleared"
+        );
+        assert_eq!(history.active_count(), 0);
+
+        // The oldest events make room for new ones
+        for _ in 0..ALERT_HISTORY_LEN {
+            history.record(&[warning.clone()]);
+            history.record(&[]);
+        }
+        assert_eq!(history.events().len(), ALERT_HISTORY_LEN);
+        assert_eq!(history.events()[0].level, AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_payload_carries_alert_fields() {
+        let mut alert = alert(AlertKind::CloseWait, AlertSeverity::Critical);
+        alert.interface = None;
+        alert.value = 75;
+        alert.message = "CLOSE_WAIT buildup".to_string();
+
+        let body: serde_json::Value =
+            serde_json::from_str(&payload(&alert, "web-1", "2024-01-01T00:00:00+00:00")).unwrap();
+        assert_eq!(body["severity"], "critical");
+        assert_eq!(body["interface"], serde_json::Value::Null);
+        assert_eq!(body["value"], 75);
+        assert_eq!(body["hostname"], "web-1");
+        assert_eq!(body["text"], "[CRITICAL] web-1: CLOSE_WAIT buildup");
+    }
+}
@@ -0,0 +1,423 @@
+//! Nagios/Icinga-style health check (`--check --warn EXPR --crit EXPR`)
+//!
+//! Samples once like `--once`, evaluates the threshold expressions against the
+//! snapshot, prints a single status line with performance data and exits 0/1/2/3 for
+//! OK/WARNING/CRITICAL/UNKNOWN.
+//!
+//! An expression is `<metric><op><value>`, e.g. `in>50MB/s`, `avg_rtt>=200ms` or
+//! `retrans>100`. Several can be joined with commas or given as repeated flags; any
+//! one that holds triggers that level.
+
+use crate::error::{NetwatchError, Result};
+use crate::snapshot::SnapshotReport;
+use std::fmt;
+
+/// Values compared by threshold expressions, taken from the health assessment metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Receive rate summed over the selected interfaces, bytes/s
+    In,
+    /// Transmit rate summed over the selected interfaces, bytes/s
+    Out,
+    /// Interface errors (rx + tx) during the sample
+    Errors,
+    /// Interface drops (rx + tx) during the sample
+    Drops,
+    /// TCP retransmits summed over all connections
+    Retrans,
+    /// Mean connection RTT in milliseconds
+    AvgRtt,
+    /// Total connection count
+    Connections,
+    /// Connections opened per second during the sample
+    ConnNewRate,
+}
+
+impl Metric {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "in" | "rx" => Some(Self::In),
+            "out" | "tx" => Some(Self::Out),
+            "errors" | "errs" => Some(Self::Errors),
+            "drops" => Some(Self::Drops),
+            "retrans" | "retransmits" => Some(Self::Retrans),
+            "avg_rtt" | "rtt" => Some(Self::AvgRtt),
+            "conns" | "connections" => Some(Self::Connections),
+            "conn_new_rate" => Some(Self::ConnNewRate),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+            Self::Errors => "errors",
+            Self::Drops => "drops",
+            Self::Retrans => "retrans",
+            Self::AvgRtt => "avg_rtt",
+            Self::Connections => "conns",
+            Self::ConnNewRate => "conn_new_rate",
+        }
+    }
+
+    /// Multiplier that converts a value with `unit` into this metric's base unit
+    fn unit_scale(self, unit: &str) -> Option<f64> {
+        let unit = unit.to_ascii_lowercase();
+        match self {
+            Self::In | Self::Out => {
+                // Rates may be written with or without a "/s" or "ps" suffix
+                let unit = unit
+                    .strip_suffix("/s")
+                    .or_else(|| unit.strip_suffix("ps"))
+                    .unwrap_or(&unit);
+                match unit {
+                    "" | "b" => Some(1.0),
+                    "k" | "kb" => Some(1e3),
+                    "m" | "mb" => Some(1e6),
+                    "g" | "gb" => Some(1e9),
+                    "kib" => Some(1024.0),
+                    "mib" => Some(1_048_576.0),
+                    "gib" => Some(1_073_741_824.0),
+                    "bit" => Some(1.0 / 8.0),
+                    "kbit" => Some(1e3 / 8.0),
+                    "mbit" => Some(1e6 / 8.0),
+                    "gbit" => Some(1e9 / 8.0),
+                    "kibit" => Some(1024.0 / 8.0),
+                    "mibit" => Some(1_048_576.0 / 8.0),
+                    "gibit" => Some(1_073_741_824.0 / 8.0),
+                    _ => None,
+                }
+            }
+            Self::AvgRtt => match unit.as_str() {
+                "" | "ms" => Some(1.0),
+                "s" => Some(1000.0),
+                "us" => Some(0.001),
+                _ => None,
+            },
+            Self::ConnNewRate => matches!(unit.as_str(), "" | "/s").then_some(1.0),
+            Self::Errors | Self::Drops | Self::Retrans | Self::Connections => {
+                unit.is_empty().then_some(1.0)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, actual: f64, threshold: f64) -> bool {
+        match self {
+            Self::Greater => actual > threshold,
+            Self::GreaterOrEqual => actual >= threshold,
+            Self::Less => actual < threshold,
+            Self::LessOrEqual => actual <= threshold,
+        }
+    }
+}
+
+/// One parsed `<metric><op><value>` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub metric: Metric,
+    pub comparison: Comparison,
+    /// In the metric's base unit (bytes/s, ms or a count)
+    pub value: f64,
+    /// The expression as written, for the status line
+    pub expression: String,
+}
+
+impl Threshold {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            NetwatchError::Config(format!("Invalid threshold '{expression}': {reason}"))
+        };
+        let trimmed = expression.trim();
+
+        let op_start = trimmed
+            .find(['>', '<'])
+            .ok_or_else(|| invalid("expected <metric><op><value>, e.g. in>50MB/s"))?;
+        let (name, rest) = trimmed.split_at(op_start);
+        let (comparison, value) = if let Some(value) = rest.strip_prefix(">=") {
+            (Comparison::GreaterOrEqual, value)
+        } else if let Some(value) = rest.strip_prefix("<=") {
+            (Comparison::LessOrEqual, value)
+        } else if let Some(value) = rest.strip_prefix('>') {
+            (Comparison::Greater, value)
+        } else {
+            (Comparison::Less, &rest[1..])
+        };
+
+        let metric = Metric::parse(name.trim()).ok_or_else(|| {
+            invalid("unknown metric (use in, out, errors, drops, retrans, avg_rtt, conns or conn_new_rate)")
+        })?;
+
+        let value = value.trim();
+        let number_end = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(number_end);
+        let number: f64 = number
+            .parse()
+            .map_err(|_| invalid("threshold is not a number"))?;
+        let scale = metric.unit_scale(unit.trim()).ok_or_else(|| {
+            invalid(&format!(
+                "unit '{unit}' does not apply to {}",
+                metric.name()
+            ))
+        })?;
+
+        Ok(Self {
+            metric,
+            comparison,
+            value: number * scale,
+            expression: trimmed.to_string(),
+        })
+    }
+
+    /// Whether the snapshot crosses this threshold. A metric with no data (avg_rtt when no
+    /// connection reports RTT) never does.
+    pub fn is_breached(&self, values: &MetricValues) -> bool {
+        values
+            .get(self.metric)
+            .is_some_and(|actual| self.comparison.holds(actual, self.value))
+    }
+}
+
+/// Parse every comma-separated expression in every `--warn`/`--crit` argument
+pub fn parse_thresholds(expressions: &[String]) -> Result<Vec<Threshold>> {
+    expressions
+        .iter()
+        .flat_map(|arg| arg.split(','))
+        .filter(|expression| !expression.trim().is_empty())
+        .map(Threshold::parse)
+        .collect()
+}
+
+/// Metric values for one snapshot
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricValues {
+    pub in_bytes_per_sec: u64,
+    pub out_bytes_per_sec: u64,
+    pub errors: u64,
+    pub drops: u64,
+    pub retrans: u64,
+    pub avg_rtt_ms: Option<f64>,
+    pub connections: u32,
+    pub conn_new_rate: f64,
+}
+
+impl MetricValues {
+    pub fn from_report(report: &SnapshotReport) -> Self {
+        let mut values = Self {
+            retrans: report.connections.retransmits,
+            avg_rtt_ms: report.connections.avg_rtt_ms,
+            connections: report.connections.total,
+            conn_new_rate: report.connections.new_per_sec,
+            ..Self::default()
+        };
+        for interface in &report.interfaces {
+            values.in_bytes_per_sec += interface.rx_bytes_per_sec;
+            values.out_bytes_per_sec += interface.tx_bytes_per_sec;
+            values.errors += interface.rx_errors + interface.tx_errors;
+            values.drops += interface.rx_drops + interface.tx_drops;
+        }
+        values
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn get(&self, metric: Metric) -> Option<f64> {
+        match metric {
+            Metric::In => Some(self.in_bytes_per_sec as f64),
+            Metric::Out => Some(self.out_bytes_per_sec as f64),
+            Metric::Errors => Some(self.errors as f64),
+            Metric::Drops => Some(self.drops as f64),
+            Metric::Retrans => Some(self.retrans as f64),
+            Metric::AvgRtt => self.avg_rtt_ms,
+            Metric::Connections => Some(f64::from(self.connections)),
+            Metric::ConnNewRate => Some(self.conn_new_rate),
+        }
+    }
+
+    /// Nagios performance data, e.g. `in=1200B out=300B ... avg_rtt=12.5ms`
+    fn perfdata(&self) -> String {
+        let mut perfdata = format!(
+            "in={}B out={}B errors={} drops={} retrans={} conns={} conn_new_rate={:.1}",
+            self.in_bytes_per_sec,
+            self.out_bytes_per_sec,
+            self.errors,
+            self.drops,
+            self.retrans,
+            self.connections,
+            self.conn_new_rate
+        );
+        if let Some(rtt) = self.avg_rtt_ms {
+            perfdata.push_str(&format!(" avg_rtt={rtt:.1}ms"));
+        }
+        perfdata
+    }
+}
+
+/// Plugin status; the discriminant is the process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok = 0,
+    Warning = 1,
+    Critical = 2,
+    Unknown = 3,
+}
+
+impl CheckStatus {
+    pub fn exit_code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+            Self::Unknown => "UNKNOWN",
+        })
+    }
+}
+
+/// Worst status reached and the status line to print
+pub fn evaluate(
+    warn: &[Threshold],
+    crit: &[Threshold],
+    values: &MetricValues,
+) -> (CheckStatus, String) {
+    let breached = |thresholds: &[Threshold]| -> Vec<String> {
+        thresholds
+            .iter()
+            .filter(|threshold| threshold.is_breached(values))
+            .map(|threshold| threshold.expression.clone())
+            .collect()
+    };
+    let critical = breached(crit);
+    let warning = breached(warn);
+
+    let (status, reasons) = if !critical.is_empty() {
+        (CheckStatus::Critical, critical)
+    } else if !warning.is_empty() {
+        (CheckStatus::Warning, warning)
+    } else {
+        (CheckStatus::Ok, Vec::new())
+    };
+
+    let summary = if reasons.is_empty() {
+        format!(
+            "in {}/s, out {}/s, {} connections",
+            crate::units::format_bytes(values.in_bytes_per_sec),
+            crate::units::format_bytes(values.out_bytes_per_sec),
+            values.connections
+        )
+    } else {
+        reasons.join(", ")
+    };
+    let line = format!("NETWATCH {status} - {summary} | {}", values.perfdata());
+    (status, line)
+}
+
+/// Status line for a check that could not run
+pub fn unknown(reason: &dyn fmt::Display) -> (CheckStatus, String) {
+    (
+        CheckStatus::Unknown,
+        format!("NETWATCH {} - {reason}", CheckStatus::Unknown),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds(expressions: &[&str]) -> Vec<Threshold> {
+        let expressions: Vec<String> = expressions.iter().map(|e| e.to_string()).collect();
+        parse_thresholds(&expressions).unwrap()
+    }
+
+    #[test]
+    fn test_threshold_parsing_and_units() {
+        let threshold = Threshold::parse("in>50MB/s").unwrap();
+        assert_eq!(threshold.metric, Metric::In);
+        assert_eq!(threshold.comparison, Comparison::Greater);
+        assert_eq!(threshold.value, 50e6);
+
+        let threshold = Threshold::parse(" out >= 100Mbit ").unwrap();
+        assert_eq!(threshold.comparison, Comparison::GreaterOrEqual);
+        assert_eq!(threshold.value, 12.5e6);
+        assert_eq!(threshold.expression, "out >= 100Mbit");
+
+        assert_eq!(Threshold::parse("avg_rtt>0.5s").unwrap().value, 500.0);
+        assert_eq!(
+            Threshold::parse("rtt>200ms").unwrap().metric,
+            Metric::AvgRtt
+        );
+        assert_eq!(
+            Threshold::parse("conns<=3").unwrap().comparison,
+            Comparison::LessOrEqual
+        );
+        assert_eq!(
+            Threshold::parse("retrans<1").unwrap().comparison,
+            Comparison::Less
+        );
+
+        assert_eq!(thresholds(&["in>1K,errors>0", "drops>5"]).len(), 3);
+        let threshold = Threshold::parse("conn_new_rate>50/s").unwrap();
+        assert_eq!(threshold.metric, Metric::ConnNewRate);
+        assert_eq!(threshold.value, 50.0);
+
+        assert!(Threshold::parse("in").is_err());
+        assert!(Threshold::parse("latency>5").is_err());
+        assert!(Threshold::parse("in>fast").is_err());
+        assert!(Threshold::parse("retrans>10MB").is_err());
+        assert!(Threshold::parse("avg_rtt>5MB/s").is_err());
+        assert!(Threshold::parse("conn_new_rate>5ms").is_err());
+    }
+
+    #[test]
+    fn test_evaluation_picks_worst_status() {
+        let values = MetricValues {
+            in_bytes_per_sec: 60_000_000,
+            retrans: 40,
+            connections: 12,
+            ..MetricValues::default()
+        };
+        let warn = thresholds(&["in>50MB/s"]);
+        let crit = thresholds(&["retrans>100"]);
+
+        let (status, line) = evaluate(&warn, &crit, &values);
+        assert_eq!(status, CheckStatus::Warning);
+        assert!(line.starts_with("NETWATCH WARNING - in>50MB/s |"));
+        assert!(line.contains("retrans=40"));
+
+        let (status, line) = evaluate(&warn, &thresholds(&["retrans>25"]), &values);
+        assert_eq!(status, CheckStatus::Critical);
+        assert_eq!(status.exit_code(), 2);
+        assert!(line.starts_with("NETWATCH CRITICAL - retrans>25 |"));
+
+        let (status, _) = evaluate(&thresholds(&["in>100MB/s"]), &crit, &values);
+        assert_eq!(status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_missing_rtt_never_breaches() {
+        let values = MetricValues::default();
+        let (status, line) = evaluate(&thresholds(&["avg_rtt<1000"]), &[], &values);
+        assert_eq!(status, CheckStatus::Ok);
+        assert!(!line.contains("avg_rtt="));
+
+        let (status, line) = unknown(&"no interfaces");
+        assert_eq!(status.exit_code(), 3);
+        assert_eq!(line, "NETWATCH UNKNOWN - no interfaces");
+    }
+}
@@ -8,49 +8,138 @@ pub struct Args {
     /// Network devices to monitor (default: auto-detect all)
     pub devices: Vec<String>,
 
+    /// Also monitor every interface matching a glob (`eth*`) or regex (`en.*`)
+    #[arg(long = "match", value_name = "PATTERN")]
+    pub interface_pattern: Option<String>,
+
+    /// Also monitor every interface whose whole name matches a regex (`veth[0-9a-f]+`)
+    #[arg(long, value_name = "PATTERN")]
+    pub interfaces_regex: Option<String>,
+
+    /// Leave out interfaces whose whole name matches a regex, unless named explicitly
+    #[arg(long, value_name = "PATTERN")]
+    pub interfaces_regex_exclude: Option<String>,
+
     /// List available network interfaces and exit
     #[arg(short, long)]
     pub list: bool,
 
-    /// Average window in seconds
-    #[arg(short = 'a', long = "average", default_value = "300")]
-    pub average_window: u32,
+    /// With --list, also show the interfaces "all" leaves out, and why
+    #[arg(long, requires = "list")]
+    pub verbose: bool,
 
-    /// Max incoming bandwidth scaling (kBit/s, 0 = auto)
-    #[arg(short = 'i', long = "incoming", default_value = "0")]
-    pub max_incoming: u64,
+    // Settings that the config file and NETWATCH_* variables also set are Options:
+    // None leaves those values alone, anything given (even the default) overrides them.
+    // Their on/off flags take an optional value, so --compact=false turns one back off.
+    /// Average window in seconds [default: 300]
+    #[arg(short = 'a', long = "average")]
+    pub average_window: Option<u32>,
 
-    /// Max outgoing bandwidth scaling (kBit/s, 0 = auto)  
-    #[arg(short = 'o', long = "outgoing", default_value = "0")]
-    pub max_outgoing: u64,
+    /// Max incoming bandwidth scaling (kBit/s, 0 = auto) [default: 0]
+    #[arg(short = 'i', long = "incoming")]
+    pub max_incoming: Option<u64>,
 
-    /// Refresh interval in milliseconds
-    #[arg(short = 't', long = "interval", default_value = "1000")]
-    pub refresh_interval: u64,
+    /// Max outgoing bandwidth scaling (kBit/s, 0 = auto) [default: 0]
+    #[arg(short = 'o', long = "outgoing")]
+    pub max_outgoing: Option<u64>,
+
+    /// Refresh interval in milliseconds [default: 1000]
+    #[arg(short = 't', long = "interval")]
+    pub refresh_interval: Option<u64>,
 
     /// High performance mode - reduces CPU usage for heavy traffic scenarios
     #[arg(
         long = "high-perf",
+        num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL",
         help = "Enable high performance mode (slower updates, less CPU)"
     )]
-    pub high_performance: bool,
+    pub high_performance: Option<bool>,
 
-    /// Traffic unit format (h=human-bit, H=human-byte, b=bit, B=byte, k=kbit, K=kbyte, m=mbit, M=mbyte, g=gbit, G=gbyte)
-    #[arg(short = 'u', long = "unit", default_value = "k")]
-    pub traffic_unit: TrafficUnit,
+    /// Traffic unit format (h=human-bit, H=human-byte, b=bit, B=byte, k=kbit, K=kbyte, m=mbit, M=mbyte, g=gbit, G=gbyte) [default: k]
+    #[arg(short = 'u', long = "unit")]
+    pub traffic_unit: Option<TrafficUnit>,
 
-    /// Data unit format (same as -u but for totals)
-    #[arg(short = 'U', long = "data-unit", default_value = "M")]
-    pub data_unit: DataUnit,
+    /// Data unit format (same as -u but for totals) [default: M]
+    #[arg(short = 'U', long = "data-unit")]
+    pub data_unit: Option<DataUnit>,
 
     /// Show multiple devices without graphs
-    #[arg(short = 'm', long = "multiple")]
-    pub multiple_devices: bool,
+    #[arg(short = 'm', long = "multiple", num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub multiple_devices: Option<bool>,
+
+    /// Compact dashboard for 80x24 terminals (toggle with c)
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub compact: Option<bool>,
+
+    /// Don't capture the mouse (clickable tabs, wheel scrolling) in the dashboard
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub no_mouse: Option<bool>,
+
+    /// Don't label Docker bridge and veth interfaces with their network or container name
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub no_docker: Option<bool>,
+
+    /// Dashboard color theme: dark (default), light, solarized, or NAME.toml in ~/.config/netwatch/themes
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+
+    /// Panel the dashboard opens on: overview (default), interfaces, connections,
+    /// processes, system, graphs, diagnostics, alerts, forensics or settings
+    #[arg(long, value_name = "NAME")]
+    pub panel: Option<String>,
 
     /// Log traffic data to file
     #[arg(short = 'f', long = "file")]
     pub log_file: Option<String>,
 
+    /// Gzip the traffic log, writing <file>.gz (needs --features log-compress)
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub log_compress: Option<bool>,
+
+    /// Flag a traffic spike when current speed exceeds this multiple of the average [default: 5.0]
+    #[arg(long = "spike-multiplier")]
+    pub spike_multiplier: Option<f64>,
+
+    /// Flag an anomaly when traffic is this many standard deviations from the learned baseline [default: 3.0]
+    #[arg(long = "baseline-sigma", value_name = "FLOAT")]
+    pub baseline_sigma: Option<f64>,
+
+    /// Learn a traffic baseline over this many seconds, save it and exit
+    #[arg(long = "learn-baseline", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub learn_baseline: Option<u64>,
+
+    /// Warn at 80% and alert at 100% of this many GB per interface per UTC day
+    #[arg(long = "quota-daily-gb", value_name = "GB")]
+    pub quota_daily_gb: Option<f64>,
+
+    /// Warn at 80% and alert at 100% of this many GB per interface per week (from Monday, UTC)
+    #[arg(long = "quota-weekly-gb", value_name = "GB")]
+    pub quota_weekly_gb: Option<f64>,
+
+    /// Warn at 80% and alert at 100% of this many GB per interface per calendar month (UTC)
+    #[arg(long = "quota-monthly-gb", value_name = "GB")]
+    pub quota_monthly_gb: Option<f64>,
+
+    /// Show how far traffic is from the baseline saved in FILE (by --learn-baseline)
+    #[arg(long = "compare-baseline", value_name = "FILE")]
+    pub compare_baseline: Option<String>,
+
+    /// Append alert events as JSON lines to this file in headless mode (default: stderr)
+    #[arg(long = "alert-log")]
+    pub alert_log: Option<String>,
+
+    /// POST InfluxDB line protocol to this URL every refresh (http only, token from INFLUX_TOKEN)
+    #[arg(long = "influx-url", value_name = "URL")]
+    pub influx_url: Option<String>,
+
+    /// POST a JSON notification here when an alert fires or escalates (Slack/Discord compatible)
+    #[arg(long = "webhook-url", value_name = "URL")]
+    pub webhook_url: Option<String>,
+
+    /// Name remote networks in the Forensics panel from this MaxMind ASN database (GeoLite2-ASN.mmdb)
+    #[arg(long = "geoip-db", value_name = "PATH")]
+    pub geoip_db: Option<String>,
+
     /// Test mode - print statistics once and exit (bypass TUI)
     #[arg(long)]
     pub test: bool,
@@ -59,6 +148,10 @@ pub struct Args {
     #[arg(long)]
     pub debug_dashboard: bool,
 
+    /// Append panel navigation and key events to FILE for troubleshooting the dashboard (off by default)
+    #[arg(long = "debug-log", value_name = "FILE")]
+    pub debug_log: Option<String>,
+
     /// Show before/after comparison of dashboard enhancements
     #[arg(long)]
     pub show_comparison: bool,
@@ -74,6 +167,104 @@ pub struct Args {
     /// Force SRE forensics terminal mode
     #[arg(long)]
     pub sre_terminal: bool,
+
+    /// Traceroute to this host from the Diagnostics panel (repeat for several hosts)
+    #[arg(long = "trace-target", value_name = "HOST")]
+    pub trace_targets: Vec<String>,
+
+    /// Check this URL from the Diagnostics panel (repeat for several URLs)
+    #[arg(long = "http-check-url", value_name = "URL")]
+    pub http_check_urls: Vec<String>,
+
+    /// TCP connect check of HOST:PORT from the Diagnostics panel (repeat for several)
+    #[arg(long = "port-check", value_name = "HOST:PORT")]
+    pub port_checks: Vec<String>,
+
+    /// Follow redirects in HTTP checks instead of reporting the 3xx status
+    #[arg(long = "http-follow-redirects", value_name = "BOOL")]
+    pub http_follow_redirects: Option<bool>,
+
+    /// Record raw interface samples and connection snapshots to FILE for --replay
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<String>,
+
+    /// Play a --record file back through the dashboard at its recorded cadence
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<String>,
+
+    /// Monitor a Linux network namespace: NAME from `ip netns`, or a PATH such as /proc/PID/ns/net; needs CAP_SYS_ADMIN
+    #[arg(long, value_name = "NAME|PATH")]
+    pub netns: Option<String>,
+
+    /// Watch another Linux machine over ssh: its interface counters and connections (needs key or agent login)
+    #[arg(long, value_name = "[USER@]HOST", conflicts_with_all = ["replay", "proc_net_dev", "netns"])]
+    pub remote: Option<String>,
+
+    /// Read interface counters from this /proc/net/dev-format file, e.g. /proc/PID/net/dev for PID's namespace without root
+    #[arg(long, value_name = "PATH")]
+    pub proc_net_dev: Option<String>,
+
+    /// Focus on one process: the Connections panel lists only its connections
+    #[arg(long = "pid", value_name = "PID")]
+    pub monitor_pid: Option<u32>,
+
+    /// With --pid, quit the dashboard when the process exits
+    #[arg(long, requires = "monitor_pid")]
+    pub exit_on_process_death: bool,
+
+    /// Show reverse-DNS hostnames for remote hosts (resolved in the background)
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub resolve: Option<bool>,
+
+    /// Don't restore the saved rolling window from the previous run
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub no_restore_history: Option<bool>,
+
+    /// Don't reopen the dashboard on the panel, selection, zoom and unit it was left with
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub no_restore_session: Option<bool>,
+
+    /// Persist totals and max speeds on exit and continue them on the next start
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub continue_session: Option<bool>,
+
+    /// Check the config file, print every invalid or unknown key, and exit
+    #[arg(long)]
+    pub check_config: bool,
+
+    /// Convert ~/.nload into ~/.netwatch and exit
+    #[arg(long)]
+    pub migrate_config: bool,
+
+    /// Sample once over one refresh interval, print speeds, totals, errors, connections
+    /// and top talkers as plain text, and exit (no TTY needed)
+    #[arg(long)]
+    pub once: bool,
+
+    /// Print the --once snapshot as JSON
+    #[arg(long, requires = "once")]
+    pub json: bool,
+
+    /// Sample once, print a Nagios-style status line and exit 0/1/2/3 (OK/WARN/CRIT/UNKNOWN)
+    #[arg(long)]
+    pub check: bool,
+
+    /// Warning threshold for --check, e.g. 'in>50MB/s' or 'avg_rtt>200ms,drops>0' (repeatable)
+    #[arg(long, value_name = "EXPR", requires = "check")]
+    pub warn: Vec<String>,
+
+    /// Critical threshold for --check, e.g. 'retrans>100' (repeatable)
+    #[arg(long, value_name = "EXPR", requires = "check")]
+    pub crit: Vec<String>,
+
+    /// Quit the dashboard or SRE terminal mode after this long, e.g. 90 (seconds), 10m, 2h;
+    /// 0 runs until Ctrl+C, as does leaving it out
+    #[arg(long, value_name = "DURATION", value_parser = validation::parse_duration)]
+    pub duration: Option<std::time::Duration>,
+
+    /// Stop the SRE terminal mode after this many refreshes (default: until Ctrl+C)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub iterations: Option<u64>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug, PartialEq, Default)]
@@ -104,6 +295,12 @@ pub enum TrafficUnit {
 pub use TrafficUnit as DataUnit;
 
 impl Args {
+    /// `--duration`, or None to run until stopped (unset or 0)
+    #[must_use]
+    pub fn run_duration(&self) -> Option<std::time::Duration> {
+        self.duration.filter(|duration| !duration.is_zero())
+    }
+
     /// Validate all command-line arguments for security
     pub fn validate(&self) -> crate::error::Result<()> {
         // Validate device names
@@ -112,11 +309,14 @@ impl Args {
         }
 
         // Validate refresh interval
-        validation::validate_refresh_interval(self.refresh_interval)?;
+        if let Some(refresh_interval) = self.refresh_interval {
+            validation::validate_refresh_interval(refresh_interval)?;
+        }
 
         // Validate bandwidth values
-        validation::validate_bandwidth(self.max_incoming)?;
-        validation::validate_bandwidth(self.max_outgoing)?;
+        for bandwidth in [self.max_incoming, self.max_outgoing].into_iter().flatten() {
+            validation::validate_bandwidth(bandwidth)?;
+        }
 
         // Validate log file path if provided
         if let Some(ref log_file) = self.log_file {
@@ -126,6 +326,91 @@ impl Args {
             }
         }
 
+        if let Some(spike_multiplier) = self.spike_multiplier {
+            validation::validate_spike_multiplier(spike_multiplier)?;
+        }
+        if let Some(baseline_sigma) = self.baseline_sigma {
+            validation::validate_baseline_sigma(baseline_sigma)?;
+        }
+
+        for quota in [
+            self.quota_daily_gb,
+            self.quota_weekly_gb,
+            self.quota_monthly_gb,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            validation::validate_quota_gb(quota)?;
+        }
+
+        if let Some(ref alert_log) = self.alert_log {
+            validation::validate_file_path(alert_log, None)?;
+        }
+
+        if let Some(ref debug_log) = self.debug_log {
+            validation::validate_file_path(debug_log, None)?;
+        }
+
+        for path in [
+            &self.record,
+            &self.replay,
+            &self.geoip_db,
+            &self.compare_baseline,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            validation::validate_file_path(path, None)?;
+        }
+
+        if let Some(ref proc_net_dev) = self.proc_net_dev {
+            validation::validate_proc_net_dev_path(proc_net_dev)?;
+        }
+
+        if let Some(ref remote) = self.remote {
+            validation::validate_ssh_target(remote)?;
+        }
+
+        if let Some(ref panel) = self.panel {
+            crate::dashboard::DashboardPanel::from_name(panel)?;
+        }
+
+        if let Some(pid) = self.monitor_pid {
+            validation::validate_pid(pid)?;
+        }
+
+        if let Some(ref netns) = self.netns {
+            validation::validate_netns_path(&crate::platform::netns_path(netns))?;
+        }
+
+        if let Some(ref influx_url) = self.influx_url {
+            crate::influx::InfluxEndpoint::parse(influx_url)?;
+        }
+
+        if let Some(ref webhook_url) = self.webhook_url {
+            validation::validate_http_url(webhook_url)?;
+        }
+
+        for target in &self.trace_targets {
+            validation::validate_host(target)?;
+        }
+
+        if let Some(ref pattern) = self.interface_pattern {
+            crate::device::InterfacePattern::parse(pattern)?;
+        }
+
+        for pattern in [&self.interfaces_regex, &self.interfaces_regex_exclude]
+            .into_iter()
+            .flatten()
+        {
+            crate::device::InterfacePattern::regex(pattern)?;
+        }
+
+        for url in &self.http_check_urls {
+            validation::validate_http_url(url)?;
+        }
+
         Ok(())
     }
 }
@@ -147,6 +432,23 @@ impl TrafficUnit {
         }
     }
 
+    /// Inverse of [`TrafficUnit::next`]
+    #[must_use]
+    pub fn prev(&self) -> Self {
+        match self {
+            Self::HumanBit => Self::GigaByte,
+            Self::HumanByte => Self::HumanBit,
+            Self::Bit => Self::HumanByte,
+            Self::Byte => Self::Bit,
+            Self::KiloBit => Self::Byte,
+            Self::KiloByte => Self::KiloBit,
+            Self::MegaBit => Self::KiloByte,
+            Self::MegaByte => Self::MegaBit,
+            Self::GigaBit => Self::MegaByte,
+            Self::GigaByte => Self::GigaBit,
+        }
+    }
+
     #[must_use]
     pub fn to_string(&self) -> &'static str {
         match self {
@@ -179,4 +481,13 @@ impl TrafficUnit {
             _ => None,
         }
     }
+
+    /// Whether this unit counts bits rather than bytes
+    #[must_use]
+    pub fn is_bits(&self) -> bool {
+        matches!(
+            self,
+            Self::HumanBit | Self::Bit | Self::KiloBit | Self::MegaBit | Self::GigaBit
+        )
+    }
 }
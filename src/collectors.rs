@@ -0,0 +1,433 @@
+//! Background collection for the dashboard
+//!
+//! Connections (`ss`), processes (/proc scans), system stats, active diagnostics and
+//! the neighbor table are slow to read, so each runs on its own worker thread at its
+//! own interval, and only while a visible panel needs it. Workers publish a
+//! [`Snapshot`] after every update, which the UI loop swaps in without waiting.
+//! Workers never print: the alternate screen belongs to the UI, so failures come back
+//! as [`Snapshot::Failed`] for the status line.
+
+use crate::active_diagnostics::ActiveDiagnosticsEngine;
+use crate::config::Config;
+use crate::connections::{ConnectionMonitor, NetworkConnection};
+use crate::dashboard::{DashboardPanel, DashboardState, ParallelData};
+use crate::neighbors::ArpEntry;
+use crate::processes::{ProcessMonitor, ProcessNetworkInfo};
+use crate::safe_system::SafeSystemStats;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often an idle worker wakes up to check demand and shutdown
+const WORKER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const SYSTEM_INTERVAL: Duration = Duration::from_secs(1);
+const DIAGNOSTICS_INTERVAL: Duration = Duration::from_secs(5);
+/// How often the Neighbors panel re-reads the ARP cache
+pub const NEIGHBORS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Latest result from one collector, applied to the dashboard state by the UI thread
+pub enum Snapshot {
+    Connections(Box<ConnectionMonitor>),
+    Processes {
+        monitor: ProcessMonitor,
+        bandwidth: Vec<(ProcessNetworkInfo, u64, u64)>, // (process, in, out)
+    },
+    System(SafeSystemStats),
+    Diagnostics(Box<ActiveDiagnosticsEngine>),
+    /// None where the platform has no neighbor table to read
+    Neighbors(Option<Vec<ArpEntry>>),
+    /// A collector's updates started failing, for the status line
+    Failed(String),
+}
+
+/// Sends [`Snapshot::Failed`] when a collector starts failing rather than on every
+/// failed update, so one broken source doesn't monopolize the status line
+#[derive(Default)]
+struct FailureReporter {
+    failing: bool,
+}
+
+impl FailureReporter {
+    fn check<E: std::fmt::Display>(
+        &mut self,
+        result: Result<(), E>,
+        what: &str,
+        sender: &Sender<Snapshot>,
+    ) {
+        match result {
+            Ok(()) => self.failing = false,
+            Err(e) => {
+                if !self.failing {
+                    let _ = sender.send(Snapshot::Failed(format!("{what} failed: {e}")));
+                }
+                self.failing = true;
+            }
+        }
+    }
+}
+
+/// What the UI currently needs, so workers only run for the visible panel
+struct Demand {
+    panel: DashboardPanel,
+    paused: bool,
+}
+
+struct Control {
+    shutdown: AtomicBool,
+    demand: Mutex<Demand>,
+}
+
+impl Control {
+    fn wants(&self, wanted: fn(&DashboardPanel) -> bool) -> bool {
+        self.demand
+            .lock()
+            .map(|demand| !demand.paused && wanted(&demand.panel))
+            .unwrap_or(false)
+    }
+}
+
+/// Worker threads that refresh the slow monitors (ss, /proc scans, pings) off the UI
+/// thread. Each worker owns its own copy of a monitor and publishes a clone after every
+/// update; the UI only ever swaps in the latest snapshot, so input never waits on them.
+pub struct Collectors {
+    control: Arc<Control>,
+    receiver: Receiver<Snapshot>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Collectors {
+    /// Start one worker per collector, seeded from the monitors already in `state`
+    pub fn spawn(state: &DashboardState, config: &Config) -> Self {
+        let control = Arc::new(Control {
+            shutdown: AtomicBool::new(false),
+            demand: Mutex::new(Demand {
+                panel: state.active_panel.clone(),
+                paused: state.paused,
+            }),
+        });
+        let (sender, receiver) = mpsc::channel();
+
+        // High performance mode slows the expensive collectors down further
+        let perf_multiplier = if config.high_performance { 2 } else { 1 };
+        let connection_interval =
+            Duration::from_millis(config.connection_interval * perf_multiplier);
+        let process_interval = Duration::from_millis(config.process_interval * perf_multiplier);
+
+        // Shared with the process worker, which attributes connection bandwidth to processes
+        let latest_connections: Arc<Mutex<Vec<NetworkConnection>>> =
+            Arc::new(Mutex::new(Vec::new()));
+
+        // Exported and notified-on connection counts must stay current whatever panel is open
+        let connections_wanted: fn(&DashboardPanel) -> bool = if config.influx_url.is_some()
+            || config.webhook_url.is_some()
+            || config.notify_on_critical
+        {
+            |_| true
+        } else {
+            |panel| {
+                matches!(
+                    panel,
+                    DashboardPanel::Connections
+                        | DashboardPanel::Overview
+                        | DashboardPanel::Forensics
+                        | DashboardPanel::Graphs
+                        | DashboardPanel::Processes
+                        | DashboardPanel::Alerts
+                )
+            }
+        };
+
+        let workers = vec![
+            spawn_worker(
+                Arc::clone(&control),
+                connection_interval,
+                connections_wanted,
+                connection_collector(
+                    state.connection_monitor.clone(),
+                    Arc::clone(&latest_connections),
+                    state.parallel_data.clone(),
+                    sender.clone(),
+                ),
+            ),
+            spawn_worker(
+                Arc::clone(&control),
+                process_interval,
+                |panel| matches!(panel, DashboardPanel::Processes),
+                process_collector(
+                    state.process_monitor.clone(),
+                    latest_connections,
+                    state.parallel_data.clone(),
+                    sender.clone(),
+                ),
+            ),
+            spawn_worker(
+                Arc::clone(&control),
+                SYSTEM_INTERVAL,
+                // CPU, memory and disk feed the Overview summaries as well as the System panel
+                |_| true,
+                system_collector(state, sender.clone()),
+            ),
+            spawn_worker(
+                Arc::clone(&control),
+                DIAGNOSTICS_INTERVAL,
+                |panel| matches!(panel, DashboardPanel::Diagnostics),
+                diagnostics_collector(
+                    state.active_diagnostics.clone(),
+                    state.parallel_data.clone(),
+                    sender.clone(),
+                ),
+            ),
+            spawn_worker(
+                Arc::clone(&control),
+                NEIGHBORS_INTERVAL,
+                |panel| matches!(panel, DashboardPanel::Neighbors),
+                neighbors_collector(state, sender),
+            ),
+        ];
+
+        Self {
+            control,
+            receiver,
+            workers,
+        }
+    }
+
+    /// Tell the workers which panel is visible and whether collection is paused
+    pub fn set_demand(&self, panel: &DashboardPanel, paused: bool) {
+        if let Ok(mut demand) = self.control.demand.lock() {
+            demand.panel = panel.clone();
+            demand.paused = paused;
+        }
+    }
+
+    /// Snapshots published since the last call, oldest first. Never blocks.
+    pub fn try_iter(&self) -> impl Iterator<Item = Snapshot> + '_ {
+        self.receiver.try_iter()
+    }
+
+    /// Stop all workers and wait for them. A worker in the middle of a collection
+    /// finishes it first.
+    pub fn shutdown(&mut self) {
+        self.control.shutdown.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for Collectors {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn spawn_worker(
+    control: Arc<Control>,
+    interval: Duration,
+    wanted: fn(&DashboardPanel) -> bool,
+    mut collect: impl FnMut() + Send + 'static,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        // None runs the first collection as soon as a panel needs it
+        let mut last_run: Option<Instant> = None;
+
+        while !control.shutdown.load(Ordering::Relaxed) {
+            let due = last_run.map_or(true, |last| last.elapsed() >= interval);
+            if due && control.wants(wanted) {
+                collect();
+                last_run = Some(Instant::now());
+            }
+            thread::sleep(WORKER_POLL_INTERVAL);
+        }
+    })
+}
+
+fn connection_collector(
+    mut monitor: ConnectionMonitor,
+    latest_connections: Arc<Mutex<Vec<NetworkConnection>>>,
+    parallel_data: ParallelData,
+    sender: Sender<Snapshot>,
+) -> impl FnMut() + Send + 'static {
+    let mut failures = FailureReporter::default();
+    move || {
+        failures.check(monitor.update(), "Reading connections", &sender);
+
+        if let Ok(mut latest) = latest_connections.lock() {
+            *latest = monitor.get_connections().to_vec();
+        }
+        if let Ok(mut count) = parallel_data.connection_count.lock() {
+            *count = monitor.get_connections().len();
+        }
+        mark_updated(&parallel_data);
+
+        let _ = sender.send(Snapshot::Connections(Box::new(monitor.clone())));
+    }
+}
+
+fn process_collector(
+    mut monitor: ProcessMonitor,
+    latest_connections: Arc<Mutex<Vec<NetworkConnection>>>,
+    parallel_data: ParallelData,
+    sender: Sender<Snapshot>,
+) -> impl FnMut() + Send + 'static {
+    let mut failures = FailureReporter::default();
+    move || {
+        failures.check(monitor.update(), "Reading processes", &sender);
+
+        let connections = latest_connections
+            .lock()
+            .map(|latest| latest.clone())
+            .unwrap_or_default();
+        let bandwidth = monitor.bandwidth_by_process(&connections);
+
+        if let Ok(mut count) = parallel_data.process_count.lock() {
+            *count = monitor.get_processes().len();
+        }
+        mark_updated(&parallel_data);
+
+        let _ = sender.send(Snapshot::Processes {
+            monitor: monitor.clone(),
+            bandwidth,
+        });
+    }
+}
+
+fn system_collector(
+    state: &DashboardState,
+    sender: Sender<Snapshot>,
+) -> impl FnMut() + Send + 'static {
+    let mut monitor = state.safe_system_monitor.clone();
+    let parallel_data = state.parallel_data.clone();
+
+    move || {
+        let stats = monitor.get_current_stats();
+
+        if let Ok(mut cpu) = parallel_data.system_cpu.lock() {
+            *cpu = stats.cpu_usage_percent;
+        }
+        if let Ok(mut memory) = parallel_data.system_memory.lock() {
+            *memory = stats.memory_usage_percent;
+        }
+        if let Ok(mut disk) = parallel_data.system_disk.lock() {
+            *disk = stats
+                .disk_usage
+                .values()
+                .next()
+                .map(|d| d.usage_percent)
+                .unwrap_or(0.0);
+        }
+        mark_updated(&parallel_data);
+
+        let _ = sender.send(Snapshot::System(stats));
+    }
+}
+
+fn diagnostics_collector(
+    mut engine: ActiveDiagnosticsEngine,
+    parallel_data: ParallelData,
+    sender: Sender<Snapshot>,
+) -> impl FnMut() + Send + 'static {
+    let mut failures = FailureReporter::default();
+    move || {
+        failures.check(engine.update(), "Diagnostics", &sender);
+
+        let diagnostics = engine.get_diagnostics();
+        if let Ok(mut count) = parallel_data.diagnostic_count.lock() {
+            *count = diagnostics.ping_results.len()
+                + diagnostics.port_scan_results.len()
+                + diagnostics.dns_results.len()
+                + diagnostics.http_results.len();
+        }
+        mark_updated(&parallel_data);
+
+        let _ = sender.send(Snapshot::Diagnostics(Box::new(engine.clone())));
+    }
+}
+
+fn neighbors_collector(
+    state: &DashboardState,
+    sender: Sender<Snapshot>,
+) -> impl FnMut() + Send + 'static {
+    let monitor = state.safe_system_monitor.clone();
+    let parallel_data = state.parallel_data.clone();
+
+    move || {
+        let neighbors = monitor.neighbors();
+        mark_updated(&parallel_data);
+
+        let _ = sender.send(Snapshot::Neighbors(neighbors));
+    }
+}
+
+fn mark_updated(parallel_data: &ParallelData) {
+    if let Ok(mut update_time) = parallel_data.last_update.lock() {
+        *update_time = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_worker_follows_demand_and_joins_on_shutdown() {
+        let control = Arc::new(Control {
+            shutdown: AtomicBool::new(false),
+            demand: Mutex::new(Demand {
+                panel: DashboardPanel::Overview,
+                paused: false,
+            }),
+        });
+        let runs = Arc::new(AtomicUsize::new(0));
+        let worker_runs = Arc::clone(&runs);
+        let worker = spawn_worker(
+            Arc::clone(&control),
+            Duration::from_secs(60),
+            |panel| matches!(panel, DashboardPanel::Processes),
+            move || {
+                worker_runs.fetch_add(1, Ordering::Relaxed);
+            },
+        );
+
+        // Not wanted on this panel
+        thread::sleep(WORKER_POLL_INTERVAL * 3);
+        assert_eq!(runs.load(Ordering::Relaxed), 0);
+
+        // First visit runs immediately, then waits out the interval
+        control.demand.lock().unwrap().panel = DashboardPanel::Processes;
+        thread::sleep(WORKER_POLL_INTERVAL * 4);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        let started = Instant::now();
+        control.shutdown.store(true, Ordering::Relaxed);
+        worker.join().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_failures_reported_once_per_outage() {
+        let (sender, receiver) = mpsc::channel();
+        let mut failures = FailureReporter::default();
+        let failed = || Err::<(), _>("ss: command not found");
+
+        failures.check(failed(), "Reading connections", &sender);
+        failures.check(failed(), "Reading connections", &sender);
+        failures.check(Ok::<(), &str>(()), "Reading connections", &sender);
+        failures.check(failed(), "Reading connections", &sender);
+
+        let messages: Vec<String> = receiver
+            .try_iter()
+            .map(|snapshot| match snapshot {
+                Snapshot::Failed(message) => message,
+                _ => panic!("only failures are sent"),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            ["Reading connections failed: ss: command not found"; 2]
+        );
+    }
+}
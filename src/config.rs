@@ -1,6 +1,12 @@
 use crate::cli::{Args, DataUnit, TrafficUnit};
+use crate::units::UnitBase;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once the ~/.nload deprecation notice has been shown, so a reload doesn't repeat it
+static NLOAD_NOTICE_SHOWN: AtomicBool = AtomicBool::new(false);
 
 fn default_diagnostic_targets() -> Vec<String> {
     vec![
@@ -9,6 +15,80 @@ fn default_diagnostic_targets() -> Vec<String> {
     ]
 }
 
+fn default_time_wait_threshold() -> u32 {
+    500
+}
+
+fn default_close_wait_threshold() -> u32 {
+    50
+}
+
+fn default_connection_interval() -> u64 {
+    4000
+}
+
+fn default_process_interval() -> u64 {
+    6000
+}
+
+fn default_restore_history() -> bool {
+    true
+}
+
+fn default_restore_session() -> bool {
+    true
+}
+
+fn default_exclude_loopback() -> bool {
+    true
+}
+
+fn default_exclude_virtual() -> bool {
+    true
+}
+
+fn default_use_mouse() -> bool {
+    true
+}
+
+fn default_docker_labels() -> bool {
+    true
+}
+
+fn default_spike_multiplier() -> f64 {
+    5.0
+}
+
+fn default_ema_alpha() -> f64 {
+    crate::stats::DEFAULT_EMA_ALPHA
+}
+
+fn default_baseline_sigma() -> f64 {
+    3.0
+}
+
+fn default_port_scan_ports() -> usize {
+    crate::network_intelligence::PortScanThresholds::default().ports
+}
+
+fn default_port_scan_window() -> u64 {
+    crate::network_intelligence::PortScanThresholds::default()
+        .window
+        .as_secs()
+}
+
+fn default_trace_interval() -> u64 {
+    crate::active_diagnostics::DEFAULT_TRACE_INTERVAL.as_secs()
+}
+
+fn default_forensics_interval() -> u64 {
+    2
+}
+
+fn default_burst_multiplier() -> f64 {
+    crate::network_intelligence::DEFAULT_BURST_MULTIPLIER
+}
+
 fn default_dns_domains() -> Vec<String> {
     vec![
         "cloudflare.com".to_string(), // Reliable test domain
@@ -16,40 +96,309 @@ fn default_dns_domains() -> Vec<String> {
     ]
 }
 
+/// Which config file [`Config::load`] reads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigFile {
+    Toml(PathBuf),
+    Nload(PathBuf),
+}
+
+/// One `[Interfaces.NAME]` table; keys left out fall back to the global values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterfaceOverrides {
+    #[serde(
+        rename = "AverageWindow",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub average_window: Option<u32>,
+
+    #[serde(rename = "BarMaxIn", default, skip_serializing_if = "Option::is_none")]
+    pub max_incoming: Option<u64>,
+
+    #[serde(rename = "BarMaxOut", default, skip_serializing_if = "Option::is_none")]
+    pub max_outgoing: Option<u64>,
+
+    #[serde(
+        rename = "SpikeMultiplier",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub spike_multiplier: Option<f64>,
+}
+
+/// The settings that apply to one interface, see [`Config::for_interface`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterfaceSettings {
+    pub average_window: u32,
+    pub max_incoming: u64,
+    pub max_outgoing: u64,
+    pub spike_multiplier: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Environment: NETWATCH_AVERAGE_WINDOW
     #[serde(rename = "AverageWindow")]
     pub average_window: u32,
 
+    /// Environment: NETWATCH_MAX_INCOMING
     #[serde(rename = "BarMaxIn")]
     pub max_incoming: u64,
 
+    /// Environment: NETWATCH_MAX_OUTGOING
     #[serde(rename = "BarMaxOut")]
     pub max_outgoing: u64,
 
     #[serde(rename = "DataFormat")]
     pub data_format: String,
 
+    /// Environment: NETWATCH_DEVICES
     #[serde(rename = "Devices")]
     pub devices: String,
 
+    /// Leave loopback out when Devices is "all"
+    #[serde(rename = "ExcludeLoopback", default = "default_exclude_loopback")]
+    pub exclude_loopback: bool,
+
+    /// Leave software interfaces (bridges, veths, tunnels, ...) out when Devices is "all"
+    #[serde(rename = "ExcludeVirtual", default = "default_exclude_virtual")]
+    pub exclude_virtual: bool,
+
+    /// Interfaces to leave out when Devices is "all", as globs or regexes like `--match`
+    #[serde(rename = "ExcludePatterns", default)]
+    pub exclude_patterns: Vec<String>,
+
     #[serde(rename = "MultipleDevices")]
     pub multiple_devices: bool,
 
+    /// Environment: NETWATCH_REFRESH_INTERVAL
     #[serde(rename = "RefreshInterval")]
     pub refresh_interval: u64,
 
+    /// Environment: NETWATCH_HIGH_PERFORMANCE
     #[serde(rename = "HighPerformance", default)]
     pub high_performance: bool,
 
     #[serde(rename = "TrafficFormat")]
     pub traffic_format: String,
 
+    /// "si" for 1000-based KB/kbit, "iec" for 1024-based KiB/Kibit; unset keeps
+    /// SI bits and IEC bytes
+    #[serde(rename = "UnitBase", default, skip_serializing_if = "Option::is_none")]
+    pub unit_base: Option<String>,
+
     #[serde(rename = "DiagnosticTargets", default = "default_diagnostic_targets")]
     pub diagnostic_targets: Vec<String>,
 
     #[serde(rename = "DNSDomains", default = "default_dns_domains")]
     pub dns_domains: Vec<String>,
+
+    /// Hosts traced from the Diagnostics panel (`--trace-target`, repeatable)
+    #[serde(rename = "TraceTargets", default)]
+    pub trace_targets: Vec<String>,
+
+    /// Seconds between traceroute rounds; each round is compared with the last to spot
+    /// path changes
+    #[serde(rename = "TraceInterval", default = "default_trace_interval")]
+    pub trace_interval: u64,
+
+    /// URLs fetched from the Diagnostics panel (`--http-check-url`, repeatable)
+    #[serde(rename = "HttpCheckUrls", default)]
+    pub http_check_urls: Vec<String>,
+
+    /// `host:port` TCP connect checks in the Diagnostics panel (`--port-check`, repeatable)
+    #[serde(rename = "PortChecks", default)]
+    pub port_checks: Vec<String>,
+
+    /// Follow redirects in HTTP checks instead of reporting the 3xx itself
+    #[serde(rename = "HttpFollowRedirects", default)]
+    pub http_follow_redirects: bool,
+
+    /// Milliseconds between connection table scans, independent of RefreshInterval
+    #[serde(rename = "ConnectionInterval", default = "default_connection_interval")]
+    pub connection_interval: u64,
+
+    /// Milliseconds between process scans, independent of RefreshInterval
+    #[serde(rename = "ProcessInterval", default = "default_process_interval")]
+    pub process_interval: u64,
+
+    /// Plot traffic graphs on a logarithmic Y axis (toggle with `L`)
+    #[serde(rename = "LogScale", default)]
+    pub log_scale: bool,
+
+    /// Fit the dashboard into 80x24 terminals (`--compact`, toggle with `c`)
+    #[serde(rename = "CompactMode", default)]
+    pub compact_mode: bool,
+
+    /// Overview's traffic total counts only hardware interfaces, not the bridges,
+    /// bonds and VLANs whose traffic they already carry
+    #[serde(rename = "PhysicalTotal", default)]
+    pub physical_total: bool,
+
+    /// List an "All interfaces" device summing every physical interface first in the
+    /// Interfaces panel, with its own graph in the Graphs panel
+    #[serde(rename = "ShowTotalDevice", default)]
+    pub show_total_device: bool,
+
+    /// Click panel tabs and scroll tables with the wheel (`--no-mouse` turns it off)
+    #[serde(rename = "UseMouse", default = "default_use_mouse")]
+    pub use_mouse: bool,
+
+    /// Label Docker interfaces with their container or network name (`--no-docker`
+    /// turns it off; needs netwatch built with `--features docker`)
+    #[serde(rename = "DockerLabels", default = "default_docker_labels")]
+    pub docker_labels: bool,
+
+    /// Dashboard color theme: a built-in name or NAME.toml in ~/.config/netwatch/themes
+    #[serde(rename = "Theme", default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+
+    /// Panel the dashboard opens on, e.g. "connections"
+    #[serde(rename = "Panel", default, skip_serializing_if = "Option::is_none")]
+    pub panel: Option<String>,
+
+    /// Panels in the tab bar and Tab cycle, e.g. ["overview", "connections"]; empty shows all
+    #[serde(rename = "EnabledPanels", default)]
+    pub enabled_panels: Vec<String>,
+
+    /// Per-role colors over the theme, e.g. `[Colors]` `header_color = "blue"`
+    #[serde(rename = "Colors", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, String>,
+
+    /// Alert when more sockets than this sit in TIME_WAIT
+    #[serde(rename = "TimeWaitThreshold", default = "default_time_wait_threshold")]
+    pub time_wait_threshold: u32,
+
+    /// Alert when more sockets than this sit in CLOSE_WAIT (usually an application bug)
+    #[serde(
+        rename = "CloseWaitThreshold",
+        default = "default_close_wait_threshold"
+    )]
+    pub close_wait_threshold: u32,
+
+    /// Current speed above this multiple of the average counts as a spike
+    #[serde(rename = "SpikeMultiplier", default = "default_spike_multiplier")]
+    pub spike_multiplier: f64,
+
+    /// Weight of the newest sample in the exponential moving average shown beside
+    /// the simple average, in (0, 1]; higher reacts faster
+    #[serde(rename = "EmaAlpha", default = "default_ema_alpha")]
+    pub ema_alpha: f64,
+
+    /// Standard deviations from the learned baseline that count as an anomaly
+    #[serde(rename = "BaselineSigma", default = "default_baseline_sigma")]
+    pub baseline_sigma: f64,
+
+    /// A remote address probing more than this many local ports within
+    /// `PortScanWindow` seconds is reported as a port scan
+    #[serde(rename = "PortScanPorts", default = "default_port_scan_ports")]
+    pub port_scan_ports: usize,
+
+    /// Seconds a port scan's probes are counted over, and how long it stays reported
+    #[serde(rename = "PortScanWindow", default = "default_port_scan_window")]
+    pub port_scan_window: u64,
+
+    /// A connection whose throughput or queued bytes grow more than this many times
+    /// between connection snapshots is reported as a burst
+    #[serde(rename = "BurstMultiplier", default = "default_burst_multiplier")]
+    pub burst_multiplier: f64,
+
+    /// Minimum seconds between Forensics panel analysis passes; doubled in high
+    /// performance mode
+    #[serde(rename = "ForensicsInterval", default = "default_forensics_interval")]
+    pub forensics_interval_secs: u64,
+
+    /// Headless-mode alert events are appended here as JSON lines (stderr if unset)
+    #[serde(rename = "AlertLog", default, skip_serializing_if = "Option::is_none")]
+    pub alert_log: Option<String>,
+
+    /// InfluxDB write endpoint for line-protocol export (`--influx-url`)
+    #[serde(rename = "InfluxUrl", default, skip_serializing_if = "Option::is_none")]
+    pub influx_url: Option<String>,
+
+    /// Alert notifications are POSTed here as JSON (`--webhook-url`)
+    #[serde(
+        rename = "WebhookUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub webhook_url: Option<String>,
+
+    /// MaxMind ASN database (GeoLite2-ASN.mmdb) naming remote networks in the
+    /// Forensics panel (`--geoip-db`)
+    #[serde(rename = "GeoIpDb", default, skip_serializing_if = "Option::is_none")]
+    pub geoip_db: Option<String>,
+
+    /// Traffic quotas per interface in GB (10^9 bytes), per UTC day, week (from Monday)
+    /// and calendar month (`--quota-daily-gb` and the like)
+    #[serde(
+        rename = "QuotaDailyGb",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub quota_daily_gb: Option<f64>,
+    #[serde(
+        rename = "QuotaWeeklyGb",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub quota_weekly_gb: Option<f64>,
+    #[serde(
+        rename = "QuotaMonthlyGb",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub quota_monthly_gb: Option<f64>,
+
+    /// Baseline saved by `--learn-baseline` that the interface details and Overview
+    /// compare traffic against (`--compare-baseline`)
+    #[serde(
+        rename = "CompareBaseline",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub compare_baseline: Option<String>,
+
+    /// Show reverse-DNS hostnames for remote hosts (`--resolve`)
+    #[serde(rename = "ResolveHostnames", default)]
+    pub resolve_hostnames: bool,
+
+    /// Ring the terminal bell and show a desktop notification when the System Status or an
+    /// alert turns critical
+    #[serde(rename = "NotifyOnCritical", default)]
+    pub notify_on_critical: bool,
+
+    /// Restore each interface's rolling window saved at the last shutdown
+    #[serde(rename = "RestoreHistory", default = "default_restore_history")]
+    pub restore_history: bool,
+
+    /// Reopen the dashboard on the panel, selection, zoom and unit it was left with
+    /// (`--no-restore-session` turns it off)
+    #[serde(rename = "RestoreSession", default = "default_restore_session")]
+    pub restore_session: bool,
+
+    /// Persist totals and max speeds between runs (`--continue-session`)
+    #[serde(rename = "ContinueSession", default)]
+    pub continue_session: bool,
+
+    /// Environment: NETWATCH_LOG_FILE
+    #[serde(rename = "LogFile", default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<String>,
+
+    /// Write the traffic log gzip-compressed, to LogFile plus `.gz` (`--log-compress`)
+    #[serde(rename = "LogCompress", default)]
+    pub log_compress: bool,
+
+    /// Per-interface overrides (`[Interfaces.eth0]`). Interfaces missing on this machine
+    /// are warned about and skipped, so one file can be shared between hosts.
+    #[serde(
+        rename = "Interfaces",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub interfaces: BTreeMap<String, InterfaceOverrides>,
 }
 
 impl Default for Config {
@@ -60,53 +409,485 @@ impl Default for Config {
             max_outgoing: 0,
             data_format: "M".to_string(),
             devices: "all".to_string(),
+            exclude_loopback: default_exclude_loopback(),
+            exclude_virtual: default_exclude_virtual(),
+            exclude_patterns: Vec::new(),
             multiple_devices: false,
             refresh_interval: 1000,
             high_performance: false,
             traffic_format: "k".to_string(),
+            unit_base: None,
             diagnostic_targets: default_diagnostic_targets(),
             dns_domains: default_dns_domains(),
+            trace_targets: Vec::new(),
+            trace_interval: default_trace_interval(),
+            http_check_urls: Vec::new(),
+            port_checks: Vec::new(),
+            http_follow_redirects: false,
+            connection_interval: default_connection_interval(),
+            process_interval: default_process_interval(),
+            log_scale: false,
+            compact_mode: false,
+            physical_total: false,
+            show_total_device: false,
+            use_mouse: default_use_mouse(),
+            docker_labels: default_docker_labels(),
+            theme: None,
+            panel: None,
+            enabled_panels: Vec::new(),
+            colors: BTreeMap::new(),
+            time_wait_threshold: default_time_wait_threshold(),
+            close_wait_threshold: default_close_wait_threshold(),
+            spike_multiplier: default_spike_multiplier(),
+            ema_alpha: default_ema_alpha(),
+            baseline_sigma: default_baseline_sigma(),
+            port_scan_ports: default_port_scan_ports(),
+            port_scan_window: default_port_scan_window(),
+            burst_multiplier: default_burst_multiplier(),
+            forensics_interval_secs: default_forensics_interval(),
+            alert_log: None,
+            influx_url: None,
+            webhook_url: None,
+            geoip_db: None,
+            quota_daily_gb: None,
+            quota_weekly_gb: None,
+            quota_monthly_gb: None,
+            compare_baseline: None,
+            resolve_hostnames: false,
+            notify_on_critical: false,
+            restore_history: default_restore_history(),
+            restore_session: default_restore_session(),
+            continue_session: false,
+            log_file: None,
+            log_compress: false,
+            interfaces: BTreeMap::new(),
         }
     }
 }
 
 impl Config {
     pub fn load() -> anyhow::Result<Self> {
-        // Try to load from ~/.netwatch (modern) or ~/.nload (compatibility)
-        if let Some(home) = dirs::home_dir() {
-            let modern_config = home.join(".netwatch");
-            let legacy_config = home.join(".nload");
-
-            if modern_config.exists() {
-                let content = std::fs::read_to_string(modern_config)?;
-                return Ok(toml::from_str(&content)?);
-            } else if legacy_config.exists() {
-                // Parse nload format: Key="Value"
-                return Self::parse_nload_format(&legacy_config);
+        // Layering: compiled defaults < config file < environment (CLI is applied later)
+        let mut config = Self::load_file()?;
+        for warning in config.apply_env(|key| std::env::var(key).ok()) {
+            eprintln!("Warning: {warning}");
+        }
+        Ok(config)
+    }
+
+    /// [`Config::load`] for a running dashboard: warnings are returned rather than
+    /// printed over the screen, and nothing is applied yet
+    pub fn reload() -> anyhow::Result<(Self, Vec<String>)> {
+        let (mut config, mut warnings) = Self::read_file()?;
+        warnings.extend(config.apply_env(|key| std::env::var(key).ok()));
+        Ok((config, warnings))
+    }
+
+    fn load_file() -> anyhow::Result<Self> {
+        let (config, warnings) = Self::read_file()?;
+        for warning in warnings {
+            eprintln!("Warning: {warning}");
+        }
+        Ok(config)
+    }
+
+    fn read_file() -> anyhow::Result<(Self, Vec<String>)> {
+        match Self::locate_file() {
+            Some(ConfigFile::Toml(path)) => {
+                let table: toml::Table = std::fs::read_to_string(&path)?.parse()?;
+                let warnings = Self::unknown_keys(&table)
+                    .into_iter()
+                    .map(|key| {
+                        format!(
+                            "ignoring unknown key `{key}` in {} (see --check-config)",
+                            path.display()
+                        )
+                    })
+                    .collect();
+                Ok((table.try_into()?, warnings))
             }
+            Some(ConfigFile::Nload(path)) => {
+                let config = Self::load_nload(&path)?;
+                let mut warnings = Vec::new();
+                if !NLOAD_NOTICE_SHOWN.swap(true, Ordering::Relaxed) {
+                    warnings.push(
+                        "Found ~/.nload — consider migrating to ~/.netwatch (run netwatch --migrate-config)"
+                            .to_string(),
+                    );
+                }
+                Ok((config, warnings))
+            }
+            None => Ok((Self::default(), Vec::new())),
+        }
+    }
+
+    /// The config file in use: ~/.netwatch (modern) or ~/.nload (compatibility)
+    pub fn locate_file() -> Option<ConfigFile> {
+        let home = dirs::home_dir()?;
+        let modern_config = home.join(".netwatch");
+        let legacy_config = home.join(".nload");
+
+        if modern_config.exists() {
+            Some(ConfigFile::Toml(modern_config))
+        } else if legacy_config.exists() {
+            Some(ConfigFile::Nload(legacy_config))
+        } else {
+            None
+        }
+    }
+
+    /// Every key the TOML config file understands
+    pub fn known_keys() -> Vec<String> {
+        // Unset optional fields are skipped when serializing, so set them all
+        let all_set = Self {
+            alert_log: Some(String::new()),
+            influx_url: Some(String::new()),
+            webhook_url: Some(String::new()),
+            geoip_db: Some(String::new()),
+            quota_daily_gb: Some(1.0),
+            quota_weekly_gb: Some(1.0),
+            quota_monthly_gb: Some(1.0),
+            compare_baseline: Some(String::new()),
+            unit_base: Some(String::new()),
+            log_file: Some(String::new()),
+            theme: Some(String::new()),
+            panel: Some(String::new()),
+            colors: BTreeMap::from([(String::new(), String::new())]),
+            interfaces: BTreeMap::from([(String::new(), InterfaceOverrides::default())]),
+            ..Self::default()
+        };
+        match toml::Table::try_from(all_set) {
+            Ok(table) => table.keys().cloned().collect(),
+            Err(_) => Vec::new(),
         }
+    }
+
+    /// Keys in a parsed config file that no field reads, in file order
+    pub fn unknown_keys(table: &toml::Table) -> Vec<String> {
+        let known = Self::known_keys();
+        table
+            .keys()
+            .filter(|key| !known.contains(key))
+            .cloned()
+            .collect()
+    }
 
-        Ok(Self::default())
+    /// Global settings with the `[Interfaces.NAME]` overrides for `interface` applied
+    #[must_use]
+    pub fn for_interface(&self, interface: &str) -> InterfaceSettings {
+        let overrides = self.interfaces.get(interface);
+        InterfaceSettings {
+            average_window: overrides
+                .and_then(|o| o.average_window)
+                .unwrap_or(self.average_window),
+            max_incoming: overrides
+                .and_then(|o| o.max_incoming)
+                .unwrap_or(self.max_incoming),
+            max_outgoing: overrides
+                .and_then(|o| o.max_outgoing)
+                .unwrap_or(self.max_outgoing),
+            spike_multiplier: overrides
+                .and_then(|o| o.spike_multiplier)
+                .unwrap_or(self.spike_multiplier),
+        }
+    }
+
+    /// Build a configuration from compiled defaults and `NETWATCH_*` environment variables.
+    ///
+    /// Unparseable values are reported on stderr and the field keeps its default.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        for warning in config.apply_env(|key| std::env::var(key).ok()) {
+            eprintln!("Warning: {warning}");
+        }
+        config
     }
 
+    /// Layer `NETWATCH_*` variables over the settings so far. An unparseable value leaves
+    /// the setting as it was (from the config file, say) and is returned as a warning.
+    fn apply_env<F>(&mut self, lookup: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut warnings = Vec::new();
+
+        if let Some(value) = lookup("NETWATCH_DEVICES") {
+            if value.trim().is_empty() {
+                warnings.push(env_warning("NETWATCH_DEVICES", &value, &self.devices));
+            } else {
+                self.devices = value.trim().to_string();
+            }
+        }
+
+        if let Some(value) = lookup("NETWATCH_REFRESH_INTERVAL") {
+            parse_env(
+                "NETWATCH_REFRESH_INTERVAL",
+                &value,
+                &mut self.refresh_interval,
+                &mut warnings,
+            );
+        }
+
+        if let Some(value) = lookup("NETWATCH_AVERAGE_WINDOW") {
+            parse_env(
+                "NETWATCH_AVERAGE_WINDOW",
+                &value,
+                &mut self.average_window,
+                &mut warnings,
+            );
+        }
+
+        if let Some(value) = lookup("NETWATCH_MAX_INCOMING") {
+            parse_env(
+                "NETWATCH_MAX_INCOMING",
+                &value,
+                &mut self.max_incoming,
+                &mut warnings,
+            );
+        }
+
+        if let Some(value) = lookup("NETWATCH_MAX_OUTGOING") {
+            parse_env(
+                "NETWATCH_MAX_OUTGOING",
+                &value,
+                &mut self.max_outgoing,
+                &mut warnings,
+            );
+        }
+
+        if let Some(value) = lookup("NETWATCH_HIGH_PERFORMANCE") {
+            match value.trim().to_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => self.high_performance = true,
+                "0" | "false" | "no" | "off" => self.high_performance = false,
+                _ => warnings.push(env_warning(
+                    "NETWATCH_HIGH_PERFORMANCE",
+                    &value,
+                    &self.high_performance,
+                )),
+            }
+        }
+
+        if let Some(value) = lookup("NETWATCH_LOG_FILE") {
+            if value.trim().is_empty() {
+                let current = self.log_file.as_deref().unwrap_or("no log file");
+                warnings.push(env_warning("NETWATCH_LOG_FILE", &value, &current));
+            } else {
+                self.log_file = Some(value.trim().to_string());
+            }
+        }
+
+        warnings
+    }
+
+    /// Convert ~/.nload into ~/.netwatch (`--migrate-config`) and return the path written.
+    /// An existing ~/.netwatch is left alone.
+    pub fn migrate_nload() -> anyhow::Result<PathBuf> {
+        let home =
+            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("no home directory to migrate in"))?;
+        let nload = home.join(".nload");
+        let netwatch = home.join(".netwatch");
+        if netwatch.exists() {
+            anyhow::bail!("{} already exists, not overwriting it", netwatch.display());
+        }
+        if !nload.exists() {
+            anyhow::bail!("no nload config found at {}", nload.display());
+        }
+        Self::load_nload(&nload)?.save()?;
+        Ok(netwatch)
+    }
+
+    /// Write the settings to ~/.netwatch, which [`Config::load`] prefers from then on
     pub fn save(&self) -> anyhow::Result<()> {
-        if let Some(home) = dirs::home_dir() {
-            let config_path = home.join(".netwatch");
-            let content = toml::to_string_pretty(self)?;
-            std::fs::write(config_path, content)?;
+        let home = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("no home directory to save ~/.netwatch in"))?;
+        let content = toml::to_string_pretty(self)?;
+        std::fs::write(home.join(".netwatch"), content)?;
+        Ok(())
+    }
+
+    /// Check values that may have come from the config file or environment, which
+    /// bypass CLI validation. Out-of-range intervals are rejected, not clamped.
+    /// What `Devices = "all"` leaves out
+    pub fn interface_filter(&self) -> crate::error::Result<crate::device::InterfaceFilter> {
+        crate::device::InterfaceFilter::new(
+            self.exclude_loopback,
+            self.exclude_virtual,
+            &self.exclude_patterns,
+        )
+    }
+
+    pub fn validate(&self) -> crate::error::Result<()> {
+        crate::validation::validate_refresh_interval(self.refresh_interval)?;
+        crate::validation::validate_collector_interval(
+            self.connection_interval,
+            "ConnectionInterval",
+        )?;
+        crate::validation::validate_collector_interval(self.process_interval, "ProcessInterval")?;
+        crate::validation::validate_ema_alpha(self.ema_alpha)?;
+        crate::validation::validate_baseline_sigma(self.baseline_sigma)?;
+        crate::validation::validate_burst_multiplier(self.burst_multiplier)?;
+        if let Some(ref unit_base) = self.unit_base {
+            crate::validation::validate_unit_base(unit_base)?;
+        }
+        for target in &self.trace_targets {
+            crate::validation::validate_host(target)?;
+        }
+        self.interface_filter()?;
+        for url in &self.http_check_urls {
+            crate::validation::validate_http_url(url)?;
+        }
+        for target in &self.port_checks {
+            crate::validation::validate_port_check(target)?;
+        }
+        if self.log_compress && !cfg!(feature = "log-compress") {
+            return Err(crate::error::NetwatchError::Config(
+                "Log compression requires netwatch built with `--features log-compress`"
+                    .to_string(),
+            ));
+        }
+        if !self.http_check_urls.is_empty() && !cfg!(feature = "http-checks") {
+            return Err(crate::error::NetwatchError::Config(
+                "HTTP checks require netwatch built with `--features http-checks`".to_string(),
+            ));
+        }
+        if let Some(ref influx_url) = self.influx_url {
+            crate::influx::InfluxEndpoint::parse(influx_url)?;
+        }
+        if let Some(ref geoip_db) = self.geoip_db {
+            crate::validation::validate_file_path(geoip_db, None)?;
+        }
+        if let Some(ref compare_baseline) = self.compare_baseline {
+            crate::validation::validate_file_path(compare_baseline, None)?;
+        }
+        for quota in [
+            self.quota_daily_gb,
+            self.quota_weekly_gb,
+            self.quota_monthly_gb,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            crate::validation::validate_quota_gb(quota)?;
+        }
+        if let Some(ref webhook_url) = self.webhook_url {
+            crate::validation::validate_http_url(webhook_url)?;
+            if !cfg!(feature = "http-checks") {
+                return Err(crate::error::NetwatchError::Config(
+                    "Webhooks require netwatch built with `--features http-checks`".to_string(),
+                ));
+            }
         }
         Ok(())
     }
 
     pub fn apply_args(&mut self, args: &Args) {
-        self.average_window = args.average_window;
-        self.max_incoming = args.max_incoming;
-        self.max_outgoing = args.max_outgoing;
-        self.refresh_interval = args.refresh_interval;
-        self.high_performance = args.high_performance;
-        self.traffic_format = args.traffic_unit.to_string().to_string();
-        self.data_format = args.data_unit.to_string().to_string();
-        self.multiple_devices = args.multiple_devices;
+        // Every flag given overrides file/env values, even when it repeats a default
+
+        // These flags set every interface, so they also replace [Interfaces.NAME] values
+        if let Some(average_window) = args.average_window {
+            self.average_window = average_window;
+            self.clear_overrides(|o| o.average_window = None);
+        }
+        if let Some(max_incoming) = args.max_incoming {
+            self.max_incoming = max_incoming;
+            self.clear_overrides(|o| o.max_incoming = None);
+        }
+        if let Some(max_outgoing) = args.max_outgoing {
+            self.max_outgoing = max_outgoing;
+            self.clear_overrides(|o| o.max_outgoing = None);
+        }
+        if let Some(refresh_interval) = args.refresh_interval {
+            self.refresh_interval = refresh_interval;
+        }
+        if let Some(high_performance) = args.high_performance {
+            self.high_performance = high_performance;
+        }
+        if let Some(ref traffic_unit) = args.traffic_unit {
+            self.traffic_format = traffic_unit.to_string().to_string();
+        }
+        if let Some(ref data_unit) = args.data_unit {
+            self.data_format = data_unit.to_string().to_string();
+        }
+        if let Some(multiple_devices) = args.multiple_devices {
+            self.multiple_devices = multiple_devices;
+        }
+        if let Some(compact) = args.compact {
+            self.compact_mode = compact;
+        }
+        if let Some(no_mouse) = args.no_mouse {
+            self.use_mouse = !no_mouse;
+        }
+        if let Some(no_docker) = args.no_docker {
+            self.docker_labels = !no_docker;
+        }
+        if args.theme.is_some() {
+            self.theme = args.theme.clone();
+        }
+        if args.panel.is_some() {
+            self.panel = args.panel.clone();
+        }
+        if args.log_file.is_some() {
+            self.log_file = args.log_file.clone();
+        }
+        if let Some(log_compress) = args.log_compress {
+            self.log_compress = log_compress;
+        }
+        if let Some(resolve) = args.resolve {
+            self.resolve_hostnames = resolve;
+        }
+        if let Some(no_restore_history) = args.no_restore_history {
+            self.restore_history = !no_restore_history;
+        }
+        if let Some(no_restore_session) = args.no_restore_session {
+            self.restore_session = !no_restore_session;
+        }
+        if let Some(continue_session) = args.continue_session {
+            self.continue_session = continue_session;
+        }
+        if let Some(spike_multiplier) = args.spike_multiplier {
+            self.spike_multiplier = spike_multiplier;
+            self.clear_overrides(|o| o.spike_multiplier = None);
+        }
+        if let Some(baseline_sigma) = args.baseline_sigma {
+            self.baseline_sigma = baseline_sigma;
+        }
+        if args.alert_log.is_some() {
+            self.alert_log = args.alert_log.clone();
+        }
+        if args.influx_url.is_some() {
+            self.influx_url = args.influx_url.clone();
+        }
+        if args.webhook_url.is_some() {
+            self.webhook_url = args.webhook_url.clone();
+        }
+        if args.geoip_db.is_some() {
+            self.geoip_db = args.geoip_db.clone();
+        }
+        if args.compare_baseline.is_some() {
+            self.compare_baseline = args.compare_baseline.clone();
+        }
+        if args.quota_daily_gb.is_some() {
+            self.quota_daily_gb = args.quota_daily_gb;
+        }
+        if args.quota_weekly_gb.is_some() {
+            self.quota_weekly_gb = args.quota_weekly_gb;
+        }
+        if args.quota_monthly_gb.is_some() {
+            self.quota_monthly_gb = args.quota_monthly_gb;
+        }
+        if !args.trace_targets.is_empty() {
+            self.trace_targets = args.trace_targets.clone();
+        }
+        if !args.http_check_urls.is_empty() {
+            self.http_check_urls = args.http_check_urls.clone();
+        }
+        if !args.port_checks.is_empty() {
+            self.port_checks = args.port_checks.clone();
+        }
+        if let Some(follow) = args.http_follow_redirects {
+            self.http_follow_redirects = follow;
+        }
 
         // Enable high performance security monitoring if high-perf mode is enabled
         if self.high_performance {
@@ -114,6 +895,19 @@ impl Config {
         }
     }
 
+    fn clear_overrides(&mut self, clear: impl Fn(&mut InterfaceOverrides)) {
+        self.interfaces.values_mut().for_each(clear);
+    }
+
+    /// Interfaces with an `[Interfaces.NAME]` table that are not in `available`
+    pub fn unknown_interfaces<'a>(&'a self, available: &'a [String]) -> Vec<&'a str> {
+        self.interfaces
+            .keys()
+            .filter(|name| !available.contains(name))
+            .map(String::as_str)
+            .collect()
+    }
+
     #[must_use]
     pub fn get_traffic_unit(&self) -> TrafficUnit {
         TrafficUnit::from_string(&self.traffic_format).unwrap_or(TrafficUnit::KiloBit)
@@ -124,7 +918,17 @@ impl Config {
         DataUnit::from_string(&self.data_format).unwrap_or(DataUnit::MegaByte)
     }
 
-    fn parse_nload_format(path: &PathBuf) -> anyhow::Result<Self> {
+    #[must_use]
+    pub fn get_unit_base(&self) -> UnitBase {
+        self.unit_base
+            .as_deref()
+            .and_then(UnitBase::parse)
+            .unwrap_or_default()
+    }
+
+    /// Read an nload config (`Key="Value"` lines). Keys map onto the fields of the same
+    /// name; MIGRATION.md lists each one. Other settings keep their defaults.
+    pub fn load_nload(path: &Path) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let mut config = Self::default();
 
@@ -155,3 +959,242 @@ impl Config {
         Ok(config)
     }
 }
+
+/// Parse `value` into `setting`, or leave it alone and add a warning
+fn parse_env<T>(key: &str, value: &str, setting: &mut T, warnings: &mut Vec<String>)
+where
+    T: std::str::FromStr + std::fmt::Display,
+{
+    match value.trim().parse() {
+        Ok(parsed) => *setting = parsed,
+        Err(_) => warnings.push(env_warning(key, value, setting)),
+    }
+}
+
+fn env_warning(key: &str, value: &str, current: &dyn std::fmt::Display) -> String {
+    format!("ignoring invalid value {value:?} for {key}, keeping {current}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::sync::Mutex;
+
+    // Environment variables are process-global, so env tests run one at a time
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    const ENV_KEYS: [&str; 7] = [
+        "NETWATCH_DEVICES",
+        "NETWATCH_REFRESH_INTERVAL",
+        "NETWATCH_AVERAGE_WINDOW",
+        "NETWATCH_MAX_INCOMING",
+        "NETWATCH_MAX_OUTGOING",
+        "NETWATCH_HIGH_PERFORMANCE",
+        "NETWATCH_LOG_FILE",
+    ];
+
+    fn with_env<F: FnOnce()>(vars: &[(&str, &str)], test: F) {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for key in ENV_KEYS {
+            std::env::remove_var(key);
+        }
+        for (key, value) in vars {
+            std::env::set_var(key, value);
+        }
+        test();
+        for key in ENV_KEYS {
+            std::env::remove_var(key);
+        }
+    }
+
+    #[test]
+    fn test_from_env_reads_each_variable() {
+        with_env(
+            &[
+                ("NETWATCH_DEVICES", "eth0 eth1"),
+                ("NETWATCH_REFRESH_INTERVAL", "250"),
+                ("NETWATCH_AVERAGE_WINDOW", "60"),
+                ("NETWATCH_MAX_INCOMING", "1000"),
+                ("NETWATCH_MAX_OUTGOING", "2000"),
+                ("NETWATCH_HIGH_PERFORMANCE", "true"),
+                ("NETWATCH_LOG_FILE", "/tmp/netwatch.log"),
+            ],
+            || {
+                let config = Config::from_env();
+                assert_eq!(config.devices, "eth0 eth1");
+                assert_eq!(config.refresh_interval, 250);
+                assert_eq!(config.average_window, 60);
+                assert_eq!(config.max_incoming, 1000);
+                assert_eq!(config.max_outgoing, 2000);
+                assert!(config.high_performance);
+                assert_eq!(config.log_file.as_deref(), Some("/tmp/netwatch.log"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_from_env_invalid_values_fall_back_to_defaults() {
+        with_env(
+            &[
+                ("NETWATCH_REFRESH_INTERVAL", "fast"),
+                ("NETWATCH_AVERAGE_WINDOW", "-5"),
+                ("NETWATCH_MAX_INCOMING", "lots"),
+                ("NETWATCH_MAX_OUTGOING", ""),
+                ("NETWATCH_HIGH_PERFORMANCE", "maybe"),
+            ],
+            || {
+                let config = Config::from_env();
+                let defaults = Config::default();
+                assert_eq!(config.refresh_interval, defaults.refresh_interval);
+                assert_eq!(config.average_window, defaults.average_window);
+                assert_eq!(config.max_incoming, defaults.max_incoming);
+                assert_eq!(config.max_outgoing, defaults.max_outgoing);
+                assert!(!config.high_performance);
+            },
+        );
+    }
+
+    #[test]
+    fn test_invalid_env_keeps_file_setting() {
+        with_env(
+            &[
+                ("NETWATCH_REFRESH_INTERVAL", "fast"),
+                ("NETWATCH_HIGH_PERFORMANCE", "maybe"),
+                ("NETWATCH_LOG_FILE", " "),
+            ],
+            || {
+                // As if read from the config file
+                let mut config = Config {
+                    refresh_interval: 2000,
+                    high_performance: true,
+                    log_file: Some("/var/log/netwatch.log".to_string()),
+                    ..Config::default()
+                };
+                let warnings = config.apply_env(|key| std::env::var(key).ok());
+
+                assert_eq!(config.refresh_interval, 2000);
+                assert!(config.high_performance);
+                assert_eq!(config.log_file.as_deref(), Some("/var/log/netwatch.log"));
+                assert_eq!(warnings.len(), 3);
+                assert_eq!(
+                    warnings[0],
+                    "ignoring invalid value \"fast\" for NETWATCH_REFRESH_INTERVAL, keeping 2000"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_env_overrides_file_and_cli_overrides_env() {
+        with_env(&[("NETWATCH_REFRESH_INTERVAL", "500")], || {
+            let mut config = Config {
+                refresh_interval: 2000,
+                average_window: 120,
+                ..Config::default()
+            };
+            assert!(config.apply_env(|key| std::env::var(key).ok()).is_empty());
+            assert_eq!(config.refresh_interval, 500);
+            assert_eq!(config.average_window, 120);
+
+            config.apply_args(&Args::parse_from(["netwatch", "-t", "750"]));
+            assert_eq!(config.refresh_interval, 750);
+            assert_eq!(config.average_window, 120);
+
+            // A flag repeating its default still overrides the environment
+            let _ = config.apply_env(|key| std::env::var(key).ok());
+            config.apply_args(&Args::parse_from(["netwatch", "--interval", "1000"]));
+            assert_eq!(config.refresh_interval, 1000);
+        });
+    }
+
+    #[test]
+    fn test_cli_switches_turn_settings_on_and_off() {
+        let mut config = Config {
+            compact_mode: true,
+            use_mouse: false,
+            ..Config::default()
+        };
+
+        config.apply_args(&Args::parse_from(["netwatch", "eth0"]));
+        assert!(config.compact_mode);
+        assert!(!config.use_mouse);
+        assert!(!config.multiple_devices);
+
+        config.apply_args(&Args::parse_from([
+            "netwatch",
+            "--compact=false",
+            "--no-mouse=false",
+            "-m",
+            "eth0",
+        ]));
+        assert!(!config.compact_mode);
+        assert!(config.use_mouse);
+        assert!(config.multiple_devices);
+    }
+
+    #[test]
+    fn test_interface_overrides_fall_back_and_yield_to_cli() {
+        let mut config: Config = toml::from_str(
+            r#"
+AverageWindow = 300
+BarMaxIn = 0
+BarMaxOut = 0
+DataFormat = "M"
+Devices = "all"
+MultipleDevices = false
+RefreshInterval = 1000
+TrafficFormat = "k"
+
+[Interfaces.eth0]
+BarMaxIn = 1250000000
+AverageWindow = 60
+
+[Interfaces.wan0]
+SpikeMultiplier = 8.0
+"#,
+        )
+        .unwrap();
+
+        let eth0 = config.for_interface("eth0");
+        assert_eq!(eth0.max_incoming, 1_250_000_000);
+        assert_eq!(eth0.average_window, 60);
+        assert_eq!(eth0.max_outgoing, 0);
+        assert_eq!(config.for_interface("wan0").spike_multiplier, 8.0);
+        assert_eq!(config.for_interface("lo").average_window, 300);
+
+        let available = vec!["eth0".to_string(), "lo".to_string()];
+        assert_eq!(config.unknown_interfaces(&available), ["wan0"]);
+
+        config.apply_args(&Args::parse_from(["netwatch", "--average", "120"]));
+        assert_eq!(config.for_interface("eth0").average_window, 120);
+        assert_eq!(config.for_interface("eth0").max_incoming, 1_250_000_000);
+
+        assert!(toml::from_str::<InterfaceOverrides>("BarMaxInn = 1").is_err());
+    }
+    #[test]
+    fn test_nload_config_converts_to_toml() {
+        let mut nload = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut nload,
+            b"# nload config\nAverageWindow=\"60\"\nBarMaxIn=\"10000\"\nDataFormat=\"G\"\nDevices=\"eth0 wlan0\"\nMultipleDevices=\"true\"\nRefreshInterval=\"bogus\"\nTrafficFormat=\"M\"\nSomethingElse=\"1\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_nload(nload.path()).unwrap();
+        assert_eq!(config.average_window, 60);
+        assert_eq!(config.max_incoming, 10_000);
+        assert_eq!(config.max_outgoing, 0);
+        assert_eq!(config.data_format, "G");
+        assert_eq!(config.devices, "eth0 wlan0");
+        assert!(config.multiple_devices);
+        assert_eq!(config.refresh_interval, 500);
+        assert_eq!(config.traffic_format, "M");
+
+        // What --migrate-config writes reads back as the same settings
+        let migrated: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(migrated.devices, "eth0 wlan0");
+        assert_eq!(migrated.traffic_format, "M");
+        assert_eq!(migrated.average_window, 60);
+    }
+}
@@ -0,0 +1,582 @@
+//! Config file diagnostics (`--check-config`)
+//!
+//! [`Config::load`] stops at the first bad value, and serde's messages rarely say which
+//! key was at fault. Here the file is checked key by key: each value is parsed on its
+//! own, then every field is validated, so one run lists every problem together with an
+//! example of a value that would be accepted. Keys no field reads are reported as
+//! warnings rather than silently dropped.
+
+use crate::cli::{DataUnit, TrafficUnit};
+use crate::config::{Config, ConfigFile};
+use crate::validation;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+/// A key whose value is rejected
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub key: String,
+    pub message: String,
+    /// A line that would be accepted, e.g. `RefreshInterval = 1000`
+    pub example: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    /// None when neither ~/.netwatch nor ~/.nload exists
+    pub path: Option<PathBuf>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<ConfigIssue>,
+}
+
+impl ConfigReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Warnings, then errors, then "OK" when there were no errors
+    pub fn render(&self) -> String {
+        let mut out = match self.path {
+            Some(ref path) => format!("Checking {}\n", path.display()),
+            None => "No config file (~/.netwatch or ~/.nload), checking defaults\n".to_string(),
+        };
+        for warning in &self.warnings {
+            out.push_str(&format!("Warning: {warning}\n"));
+        }
+        for issue in &self.errors {
+            out.push_str(&format!(
+                "Error: {}: {}\n  e.g. {}\n",
+                issue.key, issue.message, issue.example
+            ));
+        }
+        if self.is_ok() {
+            out.push_str("OK\n");
+        }
+        out
+    }
+}
+
+/// Check the config file [`Config::load`] would read
+pub fn check_config_file() -> ConfigReport {
+    match Config::locate_file() {
+        Some(ConfigFile::Toml(path)) => match std::fs::read_to_string(&path) {
+            Ok(content) => ConfigReport {
+                path: Some(path),
+                ..check_toml(&content)
+            },
+            Err(e) => unreadable(path, e),
+        },
+        Some(ConfigFile::Nload(path)) => match Config::load_nload(&path) {
+            Ok(config) => ConfigReport {
+                path: Some(path),
+                errors: validate_fields(&config),
+                ..ConfigReport::default()
+            },
+            Err(e) => unreadable(path, e),
+        },
+        None => ConfigReport {
+            errors: validate_fields(&Config::default()),
+            ..ConfigReport::default()
+        },
+    }
+}
+
+fn unreadable(path: PathBuf, error: impl std::fmt::Display) -> ConfigReport {
+    ConfigReport {
+        errors: vec![ConfigIssue {
+            key: path.display().to_string(),
+            message: format!("cannot read: {error}"),
+            example: "RefreshInterval = 1000".to_string(),
+        }],
+        path: Some(path),
+        ..ConfigReport::default()
+    }
+}
+
+/// Check TOML config text: syntax, unknown keys, value types, then field values
+pub fn check_toml(content: &str) -> ConfigReport {
+    let mut report = ConfigReport::default();
+
+    let table: toml::Table = match content.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            report.errors.push(ConfigIssue {
+                key: "(syntax)".to_string(),
+                message: e.message().to_string(),
+                example: "RefreshInterval = 1000".to_string(),
+            });
+            return report;
+        }
+    };
+
+    let defaults = match toml::Table::try_from(Config::default()) {
+        Ok(defaults) => defaults,
+        Err(_) => return report,
+    };
+    let known = Config::known_keys();
+
+    // Layer each known key over the defaults on its own, so a type error names its key
+    let mut merged = defaults.clone();
+    for (key, value) in &table {
+        if !known.contains(key) {
+            report.warnings.push(unknown_key_warning(key, &known));
+            continue;
+        }
+        let mut single = defaults.clone();
+        single.insert(key.clone(), value.clone());
+        match single.try_into::<Config>() {
+            Ok(_) => {
+                merged.insert(key.clone(), value.clone());
+            }
+            Err(e) => report.errors.push(issue(key, e.message())),
+        }
+    }
+
+    // Keys without a serde default must be in the file
+    for key in &known {
+        if table.contains_key(key) {
+            continue;
+        }
+        let mut without = defaults.clone();
+        without.remove(key);
+        if without.try_into::<Config>().is_err() {
+            report.errors.push(issue(key, "required key is missing"));
+        }
+    }
+
+    // Keys with a type error keep their default, so the remaining values still get checked
+    match merged.try_into::<Config>() {
+        Ok(config) => report.errors.extend(validate_fields(&config)),
+        Err(e) => report.errors.push(ConfigIssue {
+            key: "(file)".to_string(),
+            message: e.message().to_string(),
+            example: "RefreshInterval = 1000".to_string(),
+        }),
+    }
+    report
+}
+
+fn unknown_key_warning(key: &str, known: &[String]) -> String {
+    match known.iter().find(|k| k.eq_ignore_ascii_case(key)) {
+        Some(known_key) => format!("unknown key `{key}` is ignored (did you mean `{known_key}`?)"),
+        None => format!("unknown key `{key}` is ignored"),
+    }
+}
+
+/// Every value rule the config is subject to, collected rather than stopping at the first
+pub fn validate_fields(config: &Config) -> Vec<ConfigIssue> {
+    let mut errors = Vec::new();
+    let mut check = |key: &str, result: crate::error::Result<()>| match result {
+        Ok(()) => {}
+        // The key already says it is a config problem
+        Err(
+            crate::error::NetwatchError::Config(message)
+            | crate::error::NetwatchError::Parse(message),
+        ) => errors.push(issue(key, &message)),
+        Err(e) => errors.push(issue(key, &e.to_string())),
+    };
+
+    check(
+        "AverageWindow",
+        at_least_one(config.average_window.into(), "seconds"),
+    );
+    check(
+        "BarMaxIn",
+        validation::validate_bandwidth(config.max_incoming),
+    );
+    check(
+        "BarMaxOut",
+        validation::validate_bandwidth(config.max_outgoing),
+    );
+    check(
+        "TrafficFormat",
+        unit(TrafficUnit::from_string(&config.traffic_format).is_some()),
+    );
+    check(
+        "DataFormat",
+        unit(DataUnit::from_string(&config.data_format).is_some()),
+    );
+    if let Some(ref unit_base) = config.unit_base {
+        check("UnitBase", validation::validate_unit_base(unit_base));
+    }
+    check("Devices", validate_devices(&config.devices));
+    for pattern in &config.exclude_patterns {
+        check(
+            "ExcludePatterns",
+            crate::device::InterfacePattern::parse(pattern).map(|_| ()),
+        );
+    }
+    check(
+        "RefreshInterval",
+        validation::validate_refresh_interval(config.refresh_interval),
+    );
+    check(
+        "ConnectionInterval",
+        validation::validate_collector_interval(config.connection_interval, "ConnectionInterval"),
+    );
+    check(
+        "ProcessInterval",
+        validation::validate_collector_interval(config.process_interval, "ProcessInterval"),
+    );
+
+    // Alert rules
+    check(
+        "TimeWaitThreshold",
+        at_least_one(config.time_wait_threshold.into(), "sockets"),
+    );
+    check(
+        "CloseWaitThreshold",
+        at_least_one(config.close_wait_threshold.into(), "sockets"),
+    );
+    check(
+        "SpikeMultiplier",
+        validation::validate_spike_multiplier(config.spike_multiplier),
+    );
+    check("EmaAlpha", validation::validate_ema_alpha(config.ema_alpha));
+    check(
+        "BaselineSigma",
+        validation::validate_baseline_sigma(config.baseline_sigma),
+    );
+    check(
+        "PortScanPorts",
+        at_least_one(config.port_scan_ports as u64, "ports"),
+    );
+    check(
+        "PortScanWindow",
+        at_least_one(config.port_scan_window, "seconds"),
+    );
+    check(
+        "BurstMultiplier",
+        validation::validate_burst_multiplier(config.burst_multiplier),
+    );
+    check(
+        "ForensicsInterval",
+        at_least_one(config.forensics_interval_secs, "seconds"),
+    );
+
+    for target in &config.diagnostic_targets {
+        check("DiagnosticTargets", validation::validate_host(target));
+    }
+    for domain in &config.dns_domains {
+        check("DNSDomains", validation::validate_host(domain));
+    }
+    for target in &config.trace_targets {
+        check("TraceTargets", validation::validate_host(target));
+    }
+    check(
+        "TraceInterval",
+        at_least_one(config.trace_interval, "seconds"),
+    );
+    for url in &config.http_check_urls {
+        check("HttpCheckUrls", validation::validate_http_url(url));
+    }
+    for target in &config.port_checks {
+        check("PortChecks", validation::validate_port_check(target));
+    }
+    if !config.http_check_urls.is_empty() && !cfg!(feature = "http-checks") {
+        check(
+            "HttpCheckUrls",
+            Err(crate::error::NetwatchError::Config(
+                "HTTP checks require netwatch built with `--features http-checks`".to_string(),
+            )),
+        );
+    }
+    if let Some(ref influx_url) = config.influx_url {
+        check(
+            "InfluxUrl",
+            crate::influx::InfluxEndpoint::parse(influx_url).map(|_| ()),
+        );
+    }
+    if let Some(ref geoip_db) = config.geoip_db {
+        check("GeoIpDb", validation::validate_file_path(geoip_db, None));
+    }
+    for (key, quota) in [
+        ("QuotaDailyGb", config.quota_daily_gb),
+        ("QuotaWeeklyGb", config.quota_weekly_gb),
+        ("QuotaMonthlyGb", config.quota_monthly_gb),
+    ] {
+        if let Some(quota) = quota {
+            check(key, validation::validate_quota_gb(quota));
+        }
+    }
+    if let Some(ref compare_baseline) = config.compare_baseline {
+        check(
+            "CompareBaseline",
+            validation::validate_file_path(compare_baseline, None),
+        );
+    }
+    if let Some(ref webhook_url) = config.webhook_url {
+        check("WebhookUrl", validation::validate_http_url(webhook_url));
+        if !cfg!(feature = "http-checks") {
+            check(
+                "WebhookUrl",
+                Err(crate::error::NetwatchError::Config(
+                    "Webhooks require netwatch built with `--features http-checks`".to_string(),
+                )),
+            );
+        }
+    }
+
+    // Per-interface overrides follow the same rules as the global keys
+    for (name, overrides) in &config.interfaces {
+        // VLAN names like eth0.100 must be quoted in a dotted key
+        let table = if name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            name.clone()
+        } else {
+            format!("{name:?}")
+        };
+        let key = |field: &str| format!("Interfaces.{table}.{field}");
+        check("Interfaces", validation::validate_interface_name(name));
+        if let Some(average_window) = overrides.average_window {
+            check(
+                &key("AverageWindow"),
+                at_least_one(average_window.into(), "seconds"),
+            );
+        }
+        if let Some(max_incoming) = overrides.max_incoming {
+            check(
+                &key("BarMaxIn"),
+                validation::validate_bandwidth(max_incoming),
+            );
+        }
+        if let Some(max_outgoing) = overrides.max_outgoing {
+            check(
+                &key("BarMaxOut"),
+                validation::validate_bandwidth(max_outgoing),
+            );
+        }
+        if let Some(spike_multiplier) = overrides.spike_multiplier {
+            check(
+                &key("SpikeMultiplier"),
+                validation::validate_spike_multiplier(spike_multiplier),
+            );
+        }
+    }
+
+    if let Some(ref theme) = config.theme {
+        check("Theme", crate::theme::Theme::load(theme).map(|_| ()));
+    }
+    if let Some(ref panel) = config.panel {
+        check(
+            "Panel",
+            crate::dashboard::DashboardPanel::from_name(panel).map(|_| ()),
+        );
+    }
+    for name in &config.enabled_panels {
+        check(
+            "EnabledPanels",
+            crate::dashboard::DashboardPanel::from_name(name).map(|_| ()),
+        );
+    }
+    check(
+        "Colors",
+        crate::theme::Theme::default()
+            .with_colors(&config.colors)
+            .map(|_| ())
+            .map_err(crate::error::NetwatchError::Config),
+    );
+
+    // Log paths
+    if let Some(ref log_file) = config.log_file {
+        if log_file != "-" {
+            check(
+                "LogFile",
+                validation::validate_file_path(log_file, Some("log"))
+                    .and_then(|()| writable(log_file)),
+            );
+        }
+    }
+    if config.log_compress && !cfg!(feature = "log-compress") {
+        check(
+            "LogCompress",
+            Err(crate::error::NetwatchError::Config(
+                "Log compression requires netwatch built with `--features log-compress`"
+                    .to_string(),
+            )),
+        );
+    }
+    if let Some(ref alert_log) = config.alert_log {
+        check(
+            "AlertLog",
+            validation::validate_file_path(alert_log, None).and_then(|()| writable(alert_log)),
+        );
+    }
+
+    errors
+}
+
+fn issue(key: &str, message: &str) -> ConfigIssue {
+    ConfigIssue {
+        key: key.to_string(),
+        message: message.to_string(),
+        example: example(key),
+    }
+}
+
+/// A valid `Key = value` line: the default where it is a useful example
+fn example(key: &str) -> String {
+    // Interfaces.NAME.Key is a dotted key for Key in [Interfaces.NAME]
+    if let Some(field) = key
+        .strip_prefix("Interfaces.")
+        .and_then(|rest| rest.rsplit_once('.'))
+        .map(|(_, field)| field)
+    {
+        let line = example(field);
+        return format!("{key}{}", &line[field.len()..]);
+    }
+
+    let value = match key {
+        "Interfaces" => "{ eth0 = { BarMaxIn = 1250000 } }".to_string(),
+        "Devices" => "\"eth0 wlan0\"".to_string(),
+        "TraceTargets" => "[\"example.com\"]".to_string(),
+        "HttpCheckUrls" => "[\"https://example.com/health\"]".to_string(),
+        "PortChecks" => "[\"db.internal:5432\"]".to_string(),
+        "InfluxUrl" => "\"http://localhost:8086/api/v2/write?org=ops&bucket=net\"".to_string(),
+        "UnitBase" => "\"iec\"".to_string(),
+        "WebhookUrl" => "\"https://hooks.slack.com/services/T000/B000/XXXX\"".to_string(),
+        "GeoIpDb" => "\"/usr/share/GeoIP/GeoLite2-ASN.mmdb\"".to_string(),
+        "LogFile" => "\"/tmp/netwatch.log\"".to_string(),
+        "Theme" => "\"solarized\"".to_string(),
+        "Panel" => "\"connections\"".to_string(),
+        "EnabledPanels" => "[\"overview\", \"connections\"]".to_string(),
+        "Colors" => "{ header_color = \"blue\", ok_color = \"#1a7f37\" }".to_string(),
+        "AlertLog" => "\"/tmp/netwatch-alerts.jsonl\"".to_string(),
+        "QuotaDailyGb" | "QuotaWeeklyGb" | "QuotaMonthlyGb" => "100.0".to_string(),
+        _ => toml::Table::try_from(Config::default())
+            .ok()
+            .and_then(|defaults| defaults.get(key).map(ToString::to_string))
+            .unwrap_or_default(),
+    };
+    format!("{key} = {value}")
+}
+
+fn at_least_one(value: u64, what: &str) -> crate::error::Result<()> {
+    if value == 0 {
+        return Err(crate::error::NetwatchError::Config(format!(
+            "must be at least 1 ({what})"
+        )));
+    }
+    Ok(())
+}
+
+fn unit(parsed: bool) -> crate::error::Result<()> {
+    if !parsed {
+        return Err(crate::error::NetwatchError::Config(
+            "unknown unit, expected one of h H b B k K m M g G".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// "all", or interface names separated by whitespace
+fn validate_devices(devices: &str) -> crate::error::Result<()> {
+    if devices == "all" {
+        return Ok(());
+    }
+    if devices.trim().is_empty() {
+        return Err(crate::error::NetwatchError::Config(
+            "expected \"all\" or interface names separated by spaces".to_string(),
+        ));
+    }
+    devices
+        .split_whitespace()
+        .try_for_each(validation::validate_interface_name)
+}
+
+/// An existing file must open for appending; a new one needs a writable directory
+fn writable(path: &str) -> crate::error::Result<()> {
+    let path = Path::new(path);
+    if path.exists() {
+        std::fs::OpenOptions::new().append(true).open(path)?;
+        return Ok(());
+    }
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if !dir.is_dir() {
+        return Err(crate::error::NetwatchError::Config(format!(
+            "directory {} does not exist",
+            dir.display()
+        )));
+    }
+    let c_dir = std::ffi::CString::new(dir.as_os_str().as_bytes())
+        .map_err(|_| crate::error::NetwatchError::Config("path contains a NUL byte".to_string()))?;
+    // SAFETY: c_dir is a valid NUL-terminated string for the duration of the call
+    if unsafe { libc::access(c_dir.as_ptr(), libc::W_OK) } != 0 {
+        return Err(crate::error::NetwatchError::PermissionDenied(format!(
+            "directory {} is not writable",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reports_every_bad_key_with_an_example() {
+        let report = check_toml(
+            r#"
+AverageWindow = 300
+BarMaxIn = 0
+BarMaxOut = 0
+DataFormat = "M"
+Devices = "all"
+MultipleDevices = false
+RefreshInterval = 5
+TrafficFormat = "x"
+ConnectionInterval = "fast"
+refreshinterval = 500
+Colour = "blue"
+Panel = "sockets"
+EnabledPanels = ["overview", "forensic"]
+"#,
+        );
+
+        let keys: Vec<&str> = report.errors.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "ConnectionInterval",
+                "TrafficFormat",
+                "RefreshInterval",
+                "Panel",
+                "EnabledPanels"
+            ]
+        );
+        assert_eq!(report.errors[2].example, "RefreshInterval = 1000");
+        assert_eq!(report.errors[0].example, "ConnectionInterval = 4000");
+
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[1].contains("did you mean `RefreshInterval`"));
+        assert!(!report.render().contains("OK"));
+
+        let report = check_toml("RefreshInterval = 500\nDevices = \"eth0 eth1\"\n");
+        let keys: Vec<&str> = report.errors.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "AverageWindow",
+                "BarMaxIn",
+                "BarMaxOut",
+                "DataFormat",
+                "MultipleDevices",
+                "TrafficFormat"
+            ]
+        );
+
+        let config = toml::Table::try_from(Config::default())
+            .unwrap()
+            .to_string();
+        let report = check_toml(&config);
+        assert!(report.is_ok(), "{:?}", report.errors);
+        assert!(report.render().ends_with("OK\n"));
+    }
+}
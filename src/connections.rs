@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use crate::remote::RemoteHost;
+use crate::replay::Replay;
+use crate::resolver::ReverseDnsResolver;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConnection {
     pub local_addr: SocketAddr,
     pub remote_addr: SocketAddr,
@@ -11,13 +17,36 @@ pub struct NetworkConnection {
     pub protocol: Protocol,
     pub pid: Option<u32>,
     pub process_name: Option<String>,
+    /// Owning process's full command line, when it could be read
+    #[serde(default)]
+    pub process_cmdline: Option<String>,
+    /// Cumulative bytes acknowledged by the peer (`ss -i` `bytes_acked`); 0 when unknown
     pub bytes_sent: u64,
+    /// Cumulative bytes received (`ss -i` `bytes_received`); 0 when unknown
     pub bytes_received: u64,
+    /// Bytes/s `bytes_sent` grew by since the previous update; None on a socket's
+    /// first update and without byte counters
+    #[serde(default)]
+    pub sent_per_sec: Option<u64>,
+    /// Bytes/s `bytes_received` grew by since the previous update
+    #[serde(default)]
+    pub received_per_sec: Option<u64>,
     // Enhanced ss command data
     pub socket_info: SocketInfo,
+    /// When this connection was first seen ESTABLISHED (None in any other state)
+    pub established_at: Option<SystemTime>,
 }
 
-#[derive(Debug, Clone, Default)]
+impl NetworkConnection {
+    /// Time spent ESTABLISHED so far. Connections already open when monitoring started
+    /// count from the first update that saw them.
+    pub fn age(&self) -> Option<Duration> {
+        self.established_at
+            .map(|since| SystemTime::now().duration_since(since).unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SocketInfo {
     pub rtt: Option<f64>,          // Round trip time in ms
     pub rttvar: Option<f64>,       // RTT variation in ms
@@ -25,16 +54,17 @@ pub struct SocketInfo {
     pub ssthresh: Option<u32>,     // Slow start threshold
     pub send_queue: u32,           // Send queue size
     pub recv_queue: u32,           // Receive queue size
-    pub bandwidth: Option<u64>,    // Estimated bandwidth
+    pub bandwidth: Option<u64>,    // Estimated bandwidth in bits/s
     pub pacing_rate: Option<u64>,  // Pacing rate
     pub retrans: u32,              // Retransmission count
     pub lost: u32,                 // Lost packet count
     pub duration: Option<String>,  // Connection duration
     pub interface: Option<String>, // Network interface
     pub tcp_info: Option<TcpInfo>, // Extended TCP information
+    pub cc_algo: Option<String>,   // Congestion control algorithm (cubic, bbr, ...)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TcpInfo {
     pub mss: u32,                   // Maximum segment size
     pub pmtu: u32,                  // Path MTU
@@ -46,7 +76,7 @@ pub struct TcpInfo {
     pub reordering: u32,            // Packet reordering metric
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConnectionState {
     Established,
     Listen,
@@ -119,7 +149,7 @@ impl FromStr for ConnectionState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Protocol {
     Tcp,
     Udp,
@@ -138,9 +168,185 @@ impl Protocol {
     }
 }
 
+/// Who can reach a listening socket, judged from the address it is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Exposure {
+    /// 127.0.0.0/8 or ::1: this host only
+    Loopback,
+    /// A private, link-local or unique local address
+    Lan,
+    /// A specific public address
+    Public,
+    /// 0.0.0.0 or ::, every interface
+    All,
+}
+
+impl Exposure {
+    pub fn of(ip: IpAddr) -> Self {
+        if ip.is_unspecified() {
+            return Exposure::All;
+        }
+        if ip.is_loopback() {
+            return Exposure::Loopback;
+        }
+        let local = match ip {
+            IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+            // fc00::/7 unique local and fe80::/10 link-local
+            IpAddr::V6(v6) => {
+                let first = v6.segments()[0];
+                first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+            }
+        };
+        if local {
+            Exposure::Lan
+        } else {
+            Exposure::Public
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Exposure::Loopback => "loopback",
+            Exposure::Lan => "LAN",
+            Exposure::Public => "PUBLIC",
+            Exposure::All => "ALL",
+        }
+    }
+
+    /// Reachable from outside the local network, given no firewall in the way
+    pub fn is_world_exposed(&self) -> bool {
+        matches!(self, Exposure::Public | Exposure::All)
+    }
+}
+
+/// Congestion control modules the kernel ships, as `ss -i` names them
+const CONGESTION_CONTROL: [&str; 18] = [
+    "cubic",
+    "bbr",
+    "bbr2",
+    "reno",
+    "dctcp",
+    "htcp",
+    "vegas",
+    "westwood",
+    "bic",
+    "illinois",
+    "hybla",
+    "scalable",
+    "yeah",
+    "lp",
+    "veno",
+    "highspeed",
+    "cdg",
+    "nv",
+];
+
+fn is_congestion_control(token: &str) -> bool {
+    CONGESTION_CONTROL.contains(&token)
+}
+
+/// Services that are commonly attacked when reachable from outside, by port
+fn risky_port(port: u16) -> Option<&'static str> {
+    Some(match port {
+        21 => "FTP",
+        23 => "Telnet",
+        111 => "rpcbind",
+        135 => "MS RPC",
+        139 => "NetBIOS",
+        445 => "SMB",
+        1433 => "MS SQL",
+        2375 => "Docker API",
+        3306 => "MySQL",
+        3389 => "RDP",
+        5432 => "PostgreSQL",
+        5900..=5903 => "VNC",
+        6379 => "Redis",
+        9200 => "Elasticsearch",
+        11211 => "memcached",
+        27017 => "MongoDB",
+        _ => return None,
+    })
+}
+
+/// A socket waiting for connections (TCP LISTEN) or datagrams (unconnected UDP)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListeningSocket {
+    pub protocol: Protocol,
+    pub local_addr: SocketAddr,
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    pub exposure: Exposure,
+}
+
+impl ListeningSocket {
+    /// Listening sockets among `connections`, most exposed first, then by port
+    pub fn from_connections(connections: &[NetworkConnection]) -> Vec<Self> {
+        let mut sockets: Vec<Self> = connections
+            .iter()
+            .filter(|conn| match conn.protocol {
+                Protocol::Tcp | Protocol::Tcp6 => conn.state == ConnectionState::Listen,
+                Protocol::Udp | Protocol::Udp6 => {
+                    conn.remote_addr.ip().is_unspecified() && conn.remote_addr.port() == 0
+                }
+            })
+            .map(|conn| Self {
+                protocol: conn.protocol.clone(),
+                local_addr: conn.local_addr,
+                pid: conn.pid,
+                process_name: conn.process_name.clone(),
+                exposure: Exposure::of(conn.local_addr.ip()),
+            })
+            .collect();
+
+        sockets.sort_by(|a, b| {
+            b.exposure
+                .cmp(&a.exposure)
+                .then(a.local_addr.port().cmp(&b.local_addr.port()))
+                .then(a.protocol.as_str().cmp(b.protocol.as_str()))
+                .then(a.local_addr.cmp(&b.local_addr))
+        });
+        // A socket shared by forked workers is listed once per process by ss
+        sockets.dedup_by(|a, b| a.protocol == b.protocol && a.local_addr == b.local_addr);
+        sockets
+    }
+
+    /// Name of the service when the port is one that is often attacked
+    pub fn risk(&self) -> Option<&'static str> {
+        risky_port(self.local_addr.port())
+    }
+}
+
+/// Where a [`ConnectionMonitor`] reads its sockets from
+#[derive(Debug, Clone, Default)]
+pub enum ConnectionSource {
+    /// This machine
+    #[default]
+    Local,
+    /// A recording (`--replay`)
+    Replay(Arc<Replay>),
+    /// `ss` on another machine (`--remote`)
+    Remote(Arc<RemoteHost>),
+}
+
+#[derive(Clone)]
 pub struct ConnectionMonitor {
     connections: Vec<NetworkConnection>,
     process_cache: HashMap<u32, String>,
+    stats: ConnectionStats,
+    resolver: Option<ReverseDnsResolver>,
+    // First time each (local, remote) pair was seen ESTABLISHED, kept across updates
+    established_since: HashMap<(SocketAddr, SocketAddr), SystemTime>,
+    // This machine, a recording (`--replay`) or another machine (`--remote`)
+    source: ConnectionSource,
+    // Each TCP socket's cumulative (sent, received) bytes at the previous update, and
+    // when that was, to estimate throughput where the kernel gives no rate
+    byte_counters: HashMap<(SocketAddr, SocketAddr), (u64, u64)>,
+    counters_read_at: Option<Instant>,
+    // Every (protocol, local, remote) seen at the previous update, and when, for churn rates
+    previous_sockets: HashSet<(Protocol, SocketAddr, SocketAddr)>,
+    sockets_read_at: Option<Instant>,
+    // Why `ss` could not be used, while connections come from /proc/net instead
+    fallback_reason: Option<String>,
 }
 
 impl ConnectionMonitor {
@@ -148,34 +354,79 @@ impl ConnectionMonitor {
         Self {
             connections: Vec::new(),
             process_cache: HashMap::new(),
+            stats: ConnectionStats::default(),
+            resolver: None,
+            established_since: HashMap::new(),
+            source: ConnectionSource::Local,
+            byte_counters: HashMap::new(),
+            counters_read_at: None,
+            previous_sockets: HashSet::new(),
+            sockets_read_at: None,
+            fallback_reason: None,
         }
     }
 
-    pub fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Clear existing connections to get fresh data
-        self.connections.clear();
+    /// Read connections from a recording or another machine instead of this one
+    pub fn set_source(&mut self, source: ConnectionSource) {
+        self.source = source;
+    }
 
-        // On macOS, skip ss command entirely and go straight to netstat/lsof
-        #[cfg(target_os = "macos")]
-        {
-            self.read_tcp_connections()?;
-            self.read_udp_connections()?;
-            // Skip process info update as it may fail on macOS in some environments
-            let _ = self.update_process_info();
+    /// Whether this machine is the one being watched, rather than a recording or `--remote`
+    pub fn is_local(&self) -> bool {
+        matches!(self.source, ConnectionSource::Local)
+    }
+
+    /// One-line explanation for the Connections panel while `ss` is missing or failing
+    /// and sockets are read from /proc/net, which has no RTT or byte counts
+    pub fn fallback_notice(&self) -> Option<String> {
+        self.fallback_reason.as_ref().map(|reason| {
+            format!("{reason}: reading /proc/net directly, without RTT or byte counts")
+        })
+    }
+
+    /// Resolve remote hosts in the background (`--resolve`)
+    pub fn enable_reverse_dns(&mut self) {
+        if self.resolver.is_none() {
+            self.resolver = Some(ReverseDnsResolver::default());
         }
+    }
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            // Try using ss command for rich socket information (Linux/modern systems)
-            if self.read_ss_connections().is_ok() {
-                // ss command succeeded, we have rich data
-            } else {
-                // Fallback to /proc parsing or demo data
-                self.read_tcp_connections()?;
-                self.read_udp_connections()?;
+    /// Stop resolving remote hosts; names already shown fall back to addresses
+    pub fn disable_reverse_dns(&mut self) {
+        self.resolver = None;
+    }
+
+    /// Resolved hostname for a remote IP, if reverse DNS is enabled and the lookup has finished
+    pub fn hostname(&self, ip: IpAddr) -> Option<String> {
+        self.resolver
+            .as_ref()
+            .and_then(|resolver| resolver.lookup(ip))
+    }
 
-                // Update process information
-                self.update_process_info()?;
+    /// Remote endpoint for display: `hostname:port` when resolved, otherwise `ip:port`
+    pub fn remote_display(&self, conn: &NetworkConnection) -> String {
+        match self.hostname(conn.remote_addr.ip()) {
+            Some(hostname) => format!("{hostname}:{}", conn.remote_addr.port()),
+            None => format!("{}:{}", conn.remote_addr.ip(), conn.remote_addr.port()),
+        }
+    }
+
+    pub fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Clear existing connections to get fresh data
+        self.connections.clear();
+
+        match self.source.clone() {
+            // Recorded connections already carry their process names and the bandwidth
+            // estimates made while recording
+            ConnectionSource::Replay(replay) => self.connections = replay.connections(),
+            // Process names come from ss; command lines would be this machine's
+            ConnectionSource::Remote(host) => {
+                self.parse_ss_output(&host.run("ss -tuapn -i -e")?)?;
+                self.estimate_bandwidth(Instant::now());
+            }
+            ConnectionSource::Local => {
+                self.read_live_connections()?;
+                self.estimate_bandwidth(Instant::now());
             }
         }
 
@@ -195,20 +446,175 @@ impl ConnectionMonitor {
             }
         });
 
+        self.track_established();
+
+        // Tally per-state counts once per update so the UI doesn't recount every frame
+        self.stats = ConnectionStats::from_connections(&self.connections);
+        self.track_churn(Instant::now());
+
+        // Queue reverse lookups now so names are ready by the time they're drawn
+        if let Some(resolver) = &self.resolver {
+            for conn in &self.connections {
+                resolver.lookup(conn.remote_addr.ip());
+            }
+        }
+
         Ok(())
     }
 
+    fn read_live_connections(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // On macOS, skip ss command entirely and go straight to netstat/lsof
+        #[cfg(target_os = "macos")]
+        {
+            self.read_tcp_connections(&HashMap::new())?;
+            self.read_udp_connections(&HashMap::new())?;
+            // Skip process info update as it may fail on macOS in some environments
+            let _ = self.update_process_info();
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            // Try using ss command for rich socket information (Linux/modern systems)
+            match self.read_ss_connections() {
+                Ok(()) => self.fallback_reason = None,
+                Err(e) => {
+                    // Minimal systems (busybox, distroless) have no ss, but /proc/net still
+                    // lists every socket; owners come from the fd tables we may read
+                    self.connections.clear();
+                    self.fallback_reason = Some(e.to_string());
+                    let owners = socket_owners_by_inode();
+                    self.read_tcp_connections(&owners)?;
+                    self.read_udp_connections(&owners)?;
+
+                    // Update process information
+                    self.update_process_info()?;
+                }
+            }
+        }
+
+        self.attach_cmdlines();
+        Ok(())
+    }
+
+    /// Look up the command line of each process that owns a connection, once per pid
+    fn attach_cmdlines(&mut self) {
+        let pids: std::collections::HashSet<u32> = self
+            .connections
+            .iter()
+            .filter_map(|conn| conn.pid)
+            .collect();
+        let cmdlines = crate::processes::read_cmdlines(pids);
+        for conn in &mut self.connections {
+            conn.process_cmdline = conn.pid.and_then(|pid| cmdlines.get(&pid).cloned());
+        }
+    }
+
+    /// Set each socket's send and receive rates from how much its byte counters moved
+    /// since the previous update, and `socket_info.bandwidth` from their sum where `ss`
+    /// reported no delivery or pacing rate. Needs `ss -i`: /proc/net/tcp has no
+    /// per-socket byte counts.
+    fn estimate_bandwidth(&mut self, now: Instant) {
+        let elapsed = self
+            .counters_read_at
+            .map(|then| now.duration_since(then).as_secs_f64())
+            .filter(|&secs| secs > 0.0);
+        let mut counters = HashMap::new();
+
+        for conn in &mut self.connections {
+            if conn.bytes_sent == 0 && conn.bytes_received == 0 {
+                continue;
+            }
+            let key = (conn.local_addr, conn.remote_addr);
+            let current = (conn.bytes_sent, conn.bytes_received);
+            counters.insert(key, current);
+
+            let (Some(elapsed), Some(&(sent, received))) = (elapsed, self.byte_counters.get(&key))
+            else {
+                continue;
+            };
+            // A counter going backwards is a new socket on the same ports
+            let per_second = |moved: u64| (moved as f64 / elapsed) as u64;
+            let sent_per_sec = per_second(current.0.saturating_sub(sent));
+            let received_per_sec = per_second(current.1.saturating_sub(received));
+            conn.sent_per_sec = Some(sent_per_sec);
+            conn.received_per_sec = Some(received_per_sec);
+            if conn.socket_info.bandwidth.is_none() {
+                conn.socket_info.bandwidth = Some((sent_per_sec + received_per_sec) * 8);
+            }
+        }
+
+        self.byte_counters = counters;
+        self.counters_read_at = Some(now);
+    }
+
+    /// Stamp ESTABLISHED connections with when they were first seen. Pairs that closed or
+    /// left ESTABLISHED are forgotten, so a reconnect on the same ports starts over.
+    fn track_established(&mut self) {
+        let now = SystemTime::now();
+        let mut still_established = HashMap::new();
+
+        for conn in &mut self.connections {
+            if conn.state != ConnectionState::Established {
+                conn.established_at = None;
+                continue;
+            }
+
+            let key = (conn.local_addr, conn.remote_addr);
+            let since = self.established_since.get(&key).copied().unwrap_or(now);
+            conn.established_at = Some(since);
+            still_established.insert(key, since);
+        }
+
+        self.established_since = still_established;
+    }
+
+    /// Diff this update's sockets against the previous one's into new/closed per second
+    /// and a churn ratio. Keyed on protocol and both endpoints, so a port reused by a new
+    /// peer counts as one closed and one new connection. The first update has no rates.
+    fn track_churn(&mut self, now: Instant) {
+        let current: HashSet<(Protocol, SocketAddr, SocketAddr)> = self
+            .connections
+            .iter()
+            .map(|conn| (conn.protocol.clone(), conn.local_addr, conn.remote_addr))
+            .collect();
+
+        let elapsed = self
+            .sockets_read_at
+            .map(|then| now.duration_since(then).as_secs_f64());
+        if let Some(elapsed) = elapsed.filter(|elapsed| *elapsed > 0.0) {
+            let opened = current.difference(&self.previous_sockets).count();
+            let closed = self.previous_sockets.difference(&current).count();
+            self.stats.new_per_sec = opened as f64 / elapsed;
+            self.stats.closed_per_sec = closed as f64 / elapsed;
+            // Share of the two snapshots' sockets that came or went: 0 is stable, 1 is
+            // complete turnover
+            let population = current.len() + self.previous_sockets.len();
+            self.stats.churn_ratio = if population > 0 {
+                (opened + closed) as f64 / population as f64
+            } else {
+                0.0
+            };
+        }
+
+        self.previous_sockets = current;
+        self.sockets_read_at = Some(now);
+    }
+
     #[allow(dead_code)]
     fn read_ss_connections(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         use std::process::Command;
 
         // Execute ss command with comprehensive options for rich socket data
         let output = Command::new("ss")
-            .args(["-tupln", "-i", "-e", "-p"]) // TCP/UDP, processes, listening, numeric, internal, extended
-            .output()?;
+            .args(["-tuapn", "-i", "-e"]) // TCP/UDP, all states, processes, numeric, internal, extended
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => "ss not found".to_string(),
+                _ => format!("Cannot run ss: {e}"),
+            })?;
 
         if !output.status.success() {
-            return Err("ss command failed".into());
+            return Err(format!("ss failed ({})", output.status).into());
         }
 
         let content = String::from_utf8_lossy(&output.stdout);
@@ -232,21 +638,21 @@ impl ConnectionMonitor {
             }
 
             // Parse main connection line
-            if let Some(connection) = self.parse_ss_connection_line(line)? {
-                // Look for additional lines with socket details
-                let mut socket_info = SocketInfo::default();
-
+            if let Some(mut conn) = self.parse_ss_connection_line(line)? {
                 // Check next lines for extended information
                 i += 1;
                 while i < lines.len() {
                     let next_line = lines[i].trim();
 
                     // If next line starts with socket details, parse it
-                    if next_line.starts_with("cubic")
+                    if next_line
+                        .split_whitespace()
+                        .next()
+                        .is_some_and(is_congestion_control)
                         || next_line.starts_with("rto:")
                         || next_line.contains("rtt:")
                     {
-                        self.parse_socket_details(next_line, &mut socket_info)?;
+                        self.parse_socket_details(next_line, &mut conn)?;
                         i += 1;
                     } else {
                         // This line doesn't belong to current connection
@@ -254,8 +660,6 @@ impl ConnectionMonitor {
                     }
                 }
 
-                let mut conn = connection;
-                conn.socket_info = socket_info;
                 self.connections.push(conn);
             } else {
                 i += 1;
@@ -335,9 +739,13 @@ impl ConnectionMonitor {
             protocol,
             pid,
             process_name,
+            process_cmdline: None,
             bytes_sent: 0, // Will be populated from extended info if available
             bytes_received: 0,
             socket_info,
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
         }))
     }
 
@@ -374,6 +782,15 @@ impl ConnectionMonitor {
 
     #[allow(dead_code)]
     fn parse_address(&self, addr_str: &str) -> Result<SocketAddr, Box<dyn std::error::Error>> {
+        // Listeners and unconnected sockets show a wildcard peer: 0.0.0.0:*, [::]:* or *:*
+        let wildcard_port = |port: &str| -> Result<u16, std::num::ParseIntError> {
+            if port == "*" {
+                Ok(0)
+            } else {
+                port.parse()
+            }
+        };
+
         // Handle IPv4 and IPv6 addresses from ss output
         if addr_str.starts_with('[') {
             // IPv6 format: [::1]:22
@@ -381,7 +798,7 @@ impl ConnectionMonitor {
             let ip_str = &addr_str[1..end_bracket];
             let port_str = &addr_str[end_bracket + 2..]; // Skip ']:'
             let ip = ip_str.parse()?;
-            let port = port_str.parse()?;
+            let port = wildcard_port(port_str)?;
             Ok(SocketAddr::new(ip, port))
         } else {
             // IPv4 format: 192.168.1.1:80
@@ -389,8 +806,12 @@ impl ConnectionMonitor {
             if parts.len() != 2 {
                 return Err("Invalid address format".into());
             }
-            let port = parts[0].parse()?;
-            let ip = parts[1].parse()?;
+            let port = wildcard_port(parts[0])?;
+            let ip = if parts[1] == "*" {
+                IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
+            } else {
+                parts[1].parse()?
+            };
             Ok(SocketAddr::new(ip, port))
         }
     }
@@ -424,10 +845,15 @@ impl ConnectionMonitor {
     fn parse_socket_details(
         &self,
         line: &str,
-        socket_info: &mut SocketInfo,
+        conn: &mut NetworkConnection,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Parse detailed socket information from ss output
-        for part in line.split_whitespace() {
+        let socket_info = &mut conn.socket_info;
+        let mut delivery_rate = None;
+
+        // Parse detailed socket information from ss output. Rates are "name value"
+        // pairs ("pacing_rate 1.2Mbps"), everything else "name:value".
+        let mut parts = line.split_whitespace();
+        while let Some(part) = parts.next() {
             if let Some(rtt_part) = part.strip_prefix("rtt:") {
                 // Parse RTT: rtt:12.5/24.0ms
                 if let Some(slash_pos) = rtt_part.find('/') {
@@ -444,11 +870,17 @@ impl ConnectionMonitor {
                 socket_info.cwnd = cwnd_part.parse().ok();
             } else if let Some(ssthresh_part) = part.strip_prefix("ssthresh:") {
                 socket_info.ssthresh = ssthresh_part.parse().ok();
-            } else if part.starts_with("pacing_rate") {
-                // Parse pacing_rate 1.2Mbps
-                if let Some(rate_str) = part.split(':').nth(1) {
-                    socket_info.pacing_rate = self.parse_bandwidth(rate_str);
-                }
+            } else if part == "pacing_rate" {
+                socket_info.pacing_rate = parts.next().and_then(|rate| self.parse_bandwidth(rate));
+            } else if part == "delivery_rate" {
+                delivery_rate = parts.next().and_then(|rate| self.parse_bandwidth(rate));
+            } else if let Some(acked) = part.strip_prefix("bytes_acked:") {
+                conn.bytes_sent = acked.parse().unwrap_or(0);
+            } else if let Some(received) = part.strip_prefix("bytes_received:") {
+                conn.bytes_received = received.parse().unwrap_or(0);
+            } else if is_congestion_control(part) {
+                // A bare token among the flags: "ts sack cubic wscale:7,7 ..."
+                socket_info.cc_algo = Some(part.to_string());
             } else if let Some(retrans_part) = part.strip_prefix("retrans:") {
                 // Parse retrans:0/10
                 if let Some(slash_pos) = retrans_part.find('/') {
@@ -458,6 +890,9 @@ impl ConnectionMonitor {
             }
         }
 
+        // The kernel's own estimates: measured delivery rate, else what it paces at
+        socket_info.bandwidth = delivery_rate.or(socket_info.pacing_rate);
+
         Ok(())
     }
 
@@ -481,17 +916,20 @@ impl ConnectionMonitor {
         }
     }
 
-    fn read_tcp_connections(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    fn read_tcp_connections(
+        &mut self,
+        owners: &HashMap<u64, u32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         // Try Linux /proc filesystem first
         if let Ok(content) = fs::read_to_string("/proc/net/tcp") {
-            self.parse_connections(&content, Protocol::Tcp)?;
+            self.parse_connections(&content, Protocol::Tcp, owners)?;
         } else {
             // macOS - get real connection data from system commands
             self.create_real_connections_from_system(Protocol::Tcp);
         }
 
         if let Ok(content) = fs::read_to_string("/proc/net/tcp6") {
-            self.parse_connections(&content, Protocol::Tcp6)?;
+            self.parse_connections(&content, Protocol::Tcp6, owners)?;
         } else {
             // macOS fallback
             self.create_real_connections_from_system(Protocol::Tcp6);
@@ -500,17 +938,20 @@ impl ConnectionMonitor {
         Ok(())
     }
 
-    fn read_udp_connections(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    fn read_udp_connections(
+        &mut self,
+        owners: &HashMap<u64, u32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         // Read IPv4 UDP connections
         if let Ok(content) = fs::read_to_string("/proc/net/udp") {
-            self.parse_connections(&content, Protocol::Udp)?;
+            self.parse_connections(&content, Protocol::Udp, owners)?;
         } else {
             self.create_real_connections_from_system(Protocol::Udp);
         }
 
         // Read IPv6 UDP connections
         if let Ok(content) = fs::read_to_string("/proc/net/udp6") {
-            self.parse_connections(&content, Protocol::Udp6)?;
+            self.parse_connections(&content, Protocol::Udp6, owners)?;
         } else {
             self.create_real_connections_from_system(Protocol::Udp6);
         }
@@ -518,10 +959,14 @@ impl ConnectionMonitor {
         Ok(())
     }
 
+    /// Sockets from a /proc/net/{tcp,udp}{,6} table. Each line is `sl local remote st
+    /// tx_queue:rx_queue tr:when retrnsmt uid timeout inode ...`; the owning pid is looked
+    /// up by inode in `owners`.
     fn parse_connections(
         &mut self,
         content: &str,
         protocol: Protocol,
+        owners: &HashMap<u64, u32>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         for line in content.lines().skip(1) {
             // Skip header
@@ -537,12 +982,21 @@ impl ConnectionMonitor {
             // Parse connection state
             let state = ConnectionState::from_str(fields[3]).unwrap_or(ConnectionState::Unknown);
 
-            // Parse PID (if available in field 7)
-            let pid = if fields.len() > 7 {
-                fields[7].parse().ok()
-            } else {
-                None
-            };
+            let (send_queue, recv_queue) = fields[4]
+                .split_once(':')
+                .map(|(tx, rx)| {
+                    (
+                        u32::from_str_radix(tx, 16).unwrap_or(0),
+                        u32::from_str_radix(rx, 16).unwrap_or(0),
+                    )
+                })
+                .unwrap_or((0, 0));
+
+            // Field 7 is the owner's uid; the pid has to come from the fd tables
+            let pid = fields[9]
+                .parse::<u64>()
+                .ok()
+                .and_then(|inode| owners.get(&inode).copied());
 
             // Create connection
             let connection = NetworkConnection {
@@ -552,9 +1006,17 @@ impl ConnectionMonitor {
                 protocol: protocol.clone(),
                 pid,
                 process_name: None, // Will be filled later
-                bytes_sent: 0,      // Would need additional parsing from /proc/net/netstat
+                process_cmdline: None,
+                bytes_sent: 0, // Would need additional parsing from /proc/net/netstat
                 bytes_received: 0,
-                socket_info: SocketInfo::default(),
+                socket_info: SocketInfo {
+                    send_queue,
+                    recv_queue,
+                    ..SocketInfo::default()
+                },
+                established_at: None,
+                sent_per_sec: None,
+                received_per_sec: None,
             };
 
             self.connections.push(connection);
@@ -576,21 +1038,15 @@ impl ConnectionMonitor {
         let port = u16::from_str_radix(port_hex, 16)?;
 
         // Parse IP address based on length
+        // The kernel prints the address as 32-bit words in host byte order
         let ip = if ip_hex.len() == 8 {
-            // IPv4 address in hex (little-endian)
             let ip_num = u32::from_str_radix(ip_hex, 16)?;
-            let ip_bytes = [
-                (ip_num & 0xFF) as u8,
-                ((ip_num >> 8) & 0xFF) as u8,
-                ((ip_num >> 16) & 0xFF) as u8,
-                ((ip_num >> 24) & 0xFF) as u8,
-            ];
-            IpAddr::V4(ip_bytes.into())
+            IpAddr::V4(ip_num.to_ne_bytes().into())
         } else if ip_hex.len() == 32 {
-            // IPv6 address in hex
             let mut ip_bytes = [0u8; 16];
-            for i in 0..16 {
-                ip_bytes[i] = u8::from_str_radix(&ip_hex[i * 2..i * 2 + 2], 16)?;
+            for (i, word) in ip_bytes.chunks_mut(4).enumerate() {
+                let word_num = u32::from_str_radix(&ip_hex[i * 8..i * 8 + 8], 16)?;
+                word.copy_from_slice(&word_num.to_ne_bytes());
             }
             IpAddr::V6(ip_bytes.into())
         } else {
@@ -633,25 +1089,76 @@ impl ConnectionMonitor {
     }
 
     pub fn get_connection_stats(&self) -> ConnectionStats {
-        let mut stats = ConnectionStats::default();
+        self.stats.clone()
+    }
+
+    pub fn get_listening_sockets(&self) -> Vec<ListeningSocket> {
+        ListeningSocket::from_connections(&self.connections)
+    }
+
+    /// Estimated bandwidth (bits/s) summed per protocol from `socket_info.bandwidth`.
+    /// Connection-derived: sockets without an estimate contribute nothing.
+    pub fn bandwidth_by_protocol(&self) -> HashMap<Protocol, u64> {
+        let mut totals: HashMap<Protocol, u64> = HashMap::new();
 
         for conn in &self.connections {
-            match conn.state {
-                ConnectionState::Established => stats.established += 1,
-                ConnectionState::Listen => stats.listening += 1,
-                ConnectionState::TimeWait => stats.time_wait += 1,
-                _ => stats.other += 1,
+            if let Some(bandwidth) = conn.socket_info.bandwidth {
+                let total = totals.entry(conn.protocol.clone()).or_insert(0);
+                *total = total.saturating_add(bandwidth);
             }
+        }
 
-            match conn.protocol {
-                Protocol::Tcp | Protocol::Tcp6 => stats.tcp += 1,
-                Protocol::Udp | Protocol::Udp6 => stats.udp += 1,
-            }
+        totals
+    }
 
-            stats.total += 1;
-        }
+    /// Connections in `state`
+    pub fn filter_by_state(&self, state: &ConnectionState) -> Vec<&NetworkConnection> {
+        self.connections
+            .iter()
+            .filter(|conn| conn.state == *state)
+            .collect()
+    }
 
-        stats
+    /// Connections whose process name contains `name`, ignoring case
+    pub fn filter_by_process_name(&self, name: &str) -> Vec<&NetworkConnection> {
+        let name = name.to_lowercase();
+        self.connections
+            .iter()
+            .filter(|conn| {
+                conn.process_name
+                    .as_ref()
+                    .is_some_and(|process| process.to_lowercase().contains(&name))
+            })
+            .collect()
+    }
+
+    /// Connections with `port` at either end
+    pub fn get_connections_by_port(&self, port: u16) -> Vec<&NetworkConnection> {
+        self.connections
+            .iter()
+            .filter(|conn| conn.local_addr.port() == port || conn.remote_addr.port() == port)
+            .collect()
+    }
+
+    /// ESTABLISHED connections to `ip`
+    pub fn get_established_to_remote(&self, ip: IpAddr) -> Vec<&NetworkConnection> {
+        self.connections
+            .iter()
+            .filter(|conn| {
+                conn.state == ConnectionState::Established && conn.remote_addr.ip() == ip
+            })
+            .collect()
+    }
+
+    /// ESTABLISHED connections open longer than `threshold`, oldest first
+    pub fn get_long_lived_connections(&self, threshold: Duration) -> Vec<&NetworkConnection> {
+        let mut long_lived: Vec<&NetworkConnection> = self
+            .connections
+            .iter()
+            .filter(|conn| conn.age().is_some_and(|age| age > threshold))
+            .collect();
+        long_lived.sort_by_key(|conn| conn.established_at);
+        long_lived
     }
 
     pub fn get_top_processes(&self) -> Vec<(String, u32)> {
@@ -664,7 +1171,7 @@ impl ConnectionMonitor {
         }
 
         let mut sorted_processes: Vec<(String, u32)> = process_counts.into_iter().collect();
-        sorted_processes.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_processes.sort_by_key(|p| std::cmp::Reverse(p.1));
         sorted_processes.truncate(10); // Top 10
 
         sorted_processes
@@ -680,22 +1187,136 @@ impl ConnectionMonitor {
         }
 
         let mut sorted_hosts: Vec<(IpAddr, u32)> = host_counts.into_iter().collect();
-        sorted_hosts.sort_by(|a, b| b.1.cmp(&a.1));
+        sorted_hosts.sort_by_key(|h| std::cmp::Reverse(h.1));
         sorted_hosts.truncate(10); // Top 10
 
         sorted_hosts
     }
 }
 
-#[derive(Default)]
+/// Upper bounds (ms) of the RTT histogram buckets; the last bucket is everything above 5s
+pub const RTT_BUCKET_BOUNDS_MS: [f64; 9] =
+    [1.0, 5.0, 10.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 5000.0];
+/// Axis labels for `ConnectionStats::connections_rtt_buckets`
+pub const RTT_BUCKET_LABELS: [&str; 10] = [
+    "<1ms",
+    "1-5ms",
+    "5-10ms",
+    "10-50ms",
+    "50-100ms",
+    "100-200ms",
+    "200-500ms",
+    "0.5-1s",
+    "1-5s",
+    ">5s",
+];
+
+/// Histogram bucket for an RTT in milliseconds
+#[must_use]
+pub fn rtt_bucket(rtt_ms: f64) -> usize {
+    RTT_BUCKET_BOUNDS_MS
+        .iter()
+        .position(|&bound| rtt_ms < bound)
+        .unwrap_or(RTT_BUCKET_BOUNDS_MS.len())
+}
+
+/// Half-open (SYN_RECV) sockets above which the health assessment suspects a SYN flood
+pub const SYN_RECV_WARNING: u32 = 100;
+
+#[derive(Debug, Clone, Default)]
 pub struct ConnectionStats {
     pub total: u32,
     pub established: u32,
     pub listening: u32,
     pub time_wait: u32,
+    pub close_wait: u32,
+    pub fin_wait: u32, // FIN_WAIT1 + FIN_WAIT2
+    pub fin_wait1: u32,
+    pub fin_wait2: u32,
+    pub syn_sent: u32,
+    pub syn_recv: u32,
+    pub last_ack: u32,
+    pub closing: u32,
+    /// CLOSE and unknown states, including unconnected UDP sockets
     pub other: u32,
     pub tcp: u32,
     pub udp: u32,
+    /// Connections per RTT range, see [`RTT_BUCKET_LABELS`]; sockets without an RTT aren't counted
+    pub connections_rtt_buckets: [u32; 10],
+    /// Connections that appeared since the previous update, per second
+    pub new_per_sec: f64,
+    /// Connections that went away since the previous update, per second
+    pub closed_per_sec: f64,
+    /// New plus closed connections over both updates' totals, 0.0 to 1.0
+    pub churn_ratio: f64,
+}
+
+impl ConnectionStats {
+    pub fn from_connections(connections: &[NetworkConnection]) -> Self {
+        let mut stats = Self::default();
+
+        for conn in connections {
+            match conn.state {
+                ConnectionState::Established => stats.established += 1,
+                ConnectionState::Listen => stats.listening += 1,
+                ConnectionState::TimeWait => stats.time_wait += 1,
+                ConnectionState::CloseWait => stats.close_wait += 1,
+                ConnectionState::FinWait1 => stats.fin_wait1 += 1,
+                ConnectionState::FinWait2 => stats.fin_wait2 += 1,
+                ConnectionState::SynSent => stats.syn_sent += 1,
+                ConnectionState::SynReceived => stats.syn_recv += 1,
+                ConnectionState::LastAck => stats.last_ack += 1,
+                ConnectionState::Closing => stats.closing += 1,
+                ConnectionState::Close | ConnectionState::Unknown => stats.other += 1,
+            }
+
+            match conn.protocol {
+                Protocol::Tcp | Protocol::Tcp6 => stats.tcp += 1,
+                Protocol::Udp | Protocol::Udp6 => stats.udp += 1,
+            }
+
+            if let Some(rtt) = conn.socket_info.rtt {
+                stats.connections_rtt_buckets[rtt_bucket(rtt)] += 1;
+            }
+
+            stats.total += 1;
+        }
+        stats.fin_wait = stats.fin_wait1 + stats.fin_wait2;
+
+        stats
+    }
+
+    /// Socket count per TCP state in handshake-to-teardown order, LISTEN left out
+    pub fn tcp_states(&self) -> [(ConnectionState, u32); 9] {
+        [
+            (ConnectionState::SynSent, self.syn_sent),
+            (ConnectionState::SynReceived, self.syn_recv),
+            (ConnectionState::Established, self.established),
+            (ConnectionState::FinWait1, self.fin_wait1),
+            (ConnectionState::FinWait2, self.fin_wait2),
+            (ConnectionState::TimeWait, self.time_wait),
+            (ConnectionState::CloseWait, self.close_wait),
+            (ConnectionState::LastAck, self.last_ack),
+            (ConnectionState::Closing, self.closing),
+        ]
+    }
+
+    /// Health assessment warnings from the socket states: CLOSE_WAIT above
+    /// `close_wait_threshold` (an application isn't closing its sockets) and SYN_RECV
+    /// above [`SYN_RECV_WARNING`] (possible SYN flood)
+    pub fn pressure_warnings(&self, close_wait_threshold: u32) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.close_wait > close_wait_threshold {
+            warnings.push(format!(
+                "{} CLOSE_WAIT - app not closing sockets",
+                self.close_wait
+            ));
+        }
+        if self.syn_recv > SYN_RECV_WARNING {
+            warnings.push(format!("{} SYN_RECV - possible SYN flood", self.syn_recv));
+        }
+        warnings
+    }
 }
 
 impl ConnectionMonitor {
@@ -810,9 +1431,13 @@ impl ConnectionMonitor {
             protocol: protocol.clone(),
             pid: None,
             process_name: None,
+            process_cmdline: None,
             bytes_sent: 0,
             bytes_received: 0,
             socket_info: SocketInfo::default(),
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
         })
     }
 
@@ -870,9 +1495,13 @@ impl ConnectionMonitor {
                 protocol: protocol.clone(),
                 pid,
                 process_name,
+                process_cmdline: None,
                 bytes_sent: 0,
                 bytes_received: 0,
                 socket_info: SocketInfo::default(),
+                established_at: None,
+                sent_per_sec: None,
+                received_per_sec: None,
             });
         } else if network_part.contains(":") {
             // Listening socket (format: *:port or ip:port)
@@ -889,9 +1518,13 @@ impl ConnectionMonitor {
                 protocol: protocol.clone(),
                 pid,
                 process_name,
+                process_cmdline: None,
                 bytes_sent: 0,
                 bytes_received: 0,
                 socket_info: SocketInfo::default(),
+                established_at: None,
+                sent_per_sec: None,
+                received_per_sec: None,
             });
         }
 
@@ -939,3 +1572,523 @@ impl Default for ConnectionMonitor {
         Self::new()
     }
 }
+
+/// Owning pid of every socket inode in the /proc/PID/fd tables we may read; without root
+/// other users' sockets stay unattributed
+#[cfg(not(target_os = "macos"))]
+fn socket_owners_by_inode() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    for pid in entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+    {
+        for inode in crate::processes::socket_inodes(pid) {
+            owners.insert(inode, pid);
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_connection(state: ConnectionState, protocol: Protocol) -> NetworkConnection {
+        NetworkConnection {
+            local_addr: "10.0.0.1:40000".parse().unwrap(),
+            remote_addr: "10.0.0.2:443".parse().unwrap(),
+            state,
+            protocol,
+            pid: None,
+            process_name: None,
+            process_cmdline: None,
+            bytes_sent: 0,
+            bytes_received: 0,
+            socket_info: SocketInfo::default(),
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
+        }
+    }
+
+    #[test]
+    fn test_connection_stats_state_distribution() {
+        let mut connections = Vec::new();
+        let distribution = [
+            (ConnectionState::Established, 10),
+            (ConnectionState::Listen, 3),
+            (ConnectionState::TimeWait, 250),
+            (ConnectionState::CloseWait, 40),
+            (ConnectionState::FinWait1, 2),
+            (ConnectionState::FinWait2, 5),
+            (ConnectionState::SynSent, 7),
+            (ConnectionState::LastAck, 1),
+        ];
+        for (state, count) in distribution {
+            for _ in 0..count {
+                connections.push(synthetic_connection(state.clone(), Protocol::Tcp));
+            }
+        }
+        connections.push(synthetic_connection(
+            ConnectionState::Unknown,
+            Protocol::Udp,
+        ));
+
+        let stats = ConnectionStats::from_connections(&connections);
+        assert_eq!(stats.total, 319);
+        assert_eq!(stats.established, 10);
+        assert_eq!(stats.listening, 3);
+        assert_eq!(stats.time_wait, 250);
+        assert_eq!(stats.close_wait, 40);
+        assert_eq!(stats.fin_wait, 7);
+        assert_eq!(stats.fin_wait1, 2);
+        assert_eq!(stats.fin_wait2, 5);
+        assert_eq!(stats.syn_sent, 7);
+        assert_eq!(stats.last_ack, 1);
+        assert_eq!(stats.other, 1);
+        assert_eq!(stats.tcp, 318);
+        assert_eq!(stats.udp, 1);
+        // Synthetic sockets carry no RTT
+        assert_eq!(stats.connections_rtt_buckets, [0; 10]);
+    }
+
+    #[test]
+    fn test_tcp_state_breakdown_from_ss_output() {
+        let mut fixture = String::from(
+            "Netid State      Recv-Q Send-Q Local Address:Port  Peer Address:Port Process
+tcp   LISTEN     0      128    0.0.0.0:80          0.0.0.0:*         users:((\"nginx\",pid=900,fd=6))
+tcp   ESTAB      0      0      10.0.0.1:80         10.0.0.9:51000    users:((\"nginx\",pid=901,fd=9))
+tcp   SYN-SENT   0      1      10.0.0.1:41000      10.0.0.7:443
+tcp   FIN-WAIT-1 0      1      10.0.0.1:80         10.0.0.9:51001
+tcp   FIN-WAIT-2 0      0      10.0.0.1:80         10.0.0.9:51002
+tcp   TIME-WAIT  0      0      10.0.0.1:80         10.0.0.9:51003
+tcp   CLOSE-WAIT 1      0      10.0.0.1:80         10.0.0.9:51004    users:((\"nginx\",pid=901,fd=12))
+tcp   LAST-ACK   0      1      10.0.0.1:80         10.0.0.9:51005
+tcp   CLOSING    0      1      10.0.0.1:80         10.0.0.9:51006
+udp   UNCONN     0      0      0.0.0.0:5353        0.0.0.0:*
+",
+        );
+        for port in 0..=SYN_RECV_WARNING {
+            fixture.push_str(&format!(
+                "tcp   SYN-RECV   0      0      10.0.0.1:80         203.0.113.5:{}\n",
+                20000 + port
+            ));
+        }
+
+        let mut monitor = ConnectionMonitor::new();
+        monitor.parse_ss_output(&fixture).unwrap();
+        let stats = ConnectionStats::from_connections(monitor.get_connections());
+
+        let states: Vec<(&str, u32)> = stats
+            .tcp_states()
+            .iter()
+            .map(|(state, count)| (state.as_str(), *count))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("SYN_SENT", 1),
+                ("SYN_RECV", 101),
+                ("ESTABLISHED", 1),
+                ("FIN_WAIT1", 1),
+                ("FIN_WAIT2", 1),
+                ("TIME_WAIT", 1),
+                ("CLOSE_WAIT", 1),
+                ("LAST_ACK", 1),
+                ("CLOSING", 1),
+            ]
+        );
+        assert_eq!(stats.listening, 1);
+        assert_eq!(stats.other, 1);
+
+        // One CLOSE_WAIT is under any sensible threshold; 101 half-open sockets are not
+        assert_eq!(
+            stats.pressure_warnings(50),
+            ["101 SYN_RECV - possible SYN flood"]
+        );
+        assert_eq!(
+            stats.pressure_warnings(0),
+            [
+                "1 CLOSE_WAIT - app not closing sockets",
+                "101 SYN_RECV - possible SYN flood"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_listening_sockets_classify_exposure() {
+        let listener = |protocol: Protocol, local: &str, remote: &str, state| {
+            let mut conn = synthetic_connection(state, protocol);
+            conn.local_addr = local.parse().unwrap();
+            conn.remote_addr = remote.parse().unwrap();
+            conn
+        };
+        let mut shared = listener(
+            Protocol::Tcp,
+            "0.0.0.0:80",
+            "0.0.0.0:0",
+            ConnectionState::Listen,
+        );
+        shared.pid = Some(42);
+        let connections = vec![
+            listener(
+                Protocol::Tcp,
+                "127.0.0.1:5432",
+                "0.0.0.0:0",
+                ConnectionState::Listen,
+            ),
+            listener(
+                Protocol::Tcp6,
+                "[::]:3389",
+                "[::]:0",
+                ConnectionState::Listen,
+            ),
+            shared.clone(),
+            shared,
+            listener(
+                Protocol::Udp,
+                "192.168.1.5:53",
+                "0.0.0.0:0",
+                ConnectionState::Unknown,
+            ),
+            listener(
+                Protocol::Tcp,
+                "203.0.113.7:22",
+                "0.0.0.0:0",
+                ConnectionState::Listen,
+            ),
+            // Connected sockets aren't listening, whatever their protocol
+            listener(
+                Protocol::Udp,
+                "10.0.0.1:5353",
+                "10.0.0.2:53",
+                ConnectionState::Unknown,
+            ),
+            synthetic_connection(ConnectionState::Established, Protocol::Tcp),
+        ];
+
+        let sockets = ListeningSocket::from_connections(&connections);
+        let summary: Vec<(u16, Exposure)> = sockets
+            .iter()
+            .map(|socket| (socket.local_addr.port(), socket.exposure))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (80, Exposure::All),
+                (3389, Exposure::All),
+                (22, Exposure::Public),
+                (53, Exposure::Lan),
+                (5432, Exposure::Loopback),
+            ]
+        );
+        assert_eq!(sockets[1].risk(), Some("RDP"));
+        assert_eq!(sockets[0].risk(), None);
+        assert!(sockets[2].exposure.is_world_exposed());
+        assert!(!sockets[3].exposure.is_world_exposed());
+        assert_eq!(Exposure::of("fd00::1".parse().unwrap()), Exposure::Lan);
+        assert_eq!(Exposure::of("fe80::1".parse().unwrap()), Exposure::Lan);
+        assert_eq!(Exposure::of("::1".parse().unwrap()), Exposure::Loopback);
+    }
+
+    #[test]
+    fn test_rtt_histogram_buckets() {
+        // Lower bounds are inclusive: 1ms is "1-5ms", 5000ms is ">5s"
+        let rtts = [
+            0.2, 1.0, 4.9, 5.0, 12.0, 75.0, 150.0, 300.0, 999.0, 1000.0, 5000.0,
+        ];
+        let connections: Vec<_> = rtts
+            .iter()
+            .map(|&rtt| {
+                let mut conn = synthetic_connection(ConnectionState::Established, Protocol::Tcp);
+                conn.socket_info.rtt = Some(rtt);
+                conn
+            })
+            .collect();
+
+        let stats = ConnectionStats::from_connections(&connections);
+        assert_eq!(
+            stats.connections_rtt_buckets,
+            [1, 2, 1, 1, 1, 1, 1, 1, 1, 1]
+        );
+        assert_eq!(rtt_bucket(0.0), 0);
+        assert_eq!(rtt_bucket(f64::MAX), 9);
+    }
+
+    #[test]
+    fn test_bandwidth_by_protocol_sums_estimates() {
+        let with_bandwidth = |protocol: Protocol, bandwidth: Option<u64>| {
+            let mut conn = synthetic_connection(ConnectionState::Established, protocol);
+            conn.socket_info.bandwidth = bandwidth;
+            conn
+        };
+
+        let mut monitor = ConnectionMonitor::new();
+        monitor.connections = vec![
+            with_bandwidth(Protocol::Tcp, Some(1_000_000)),
+            with_bandwidth(Protocol::Tcp, Some(500_000)),
+            with_bandwidth(Protocol::Tcp6, Some(250_000)),
+            with_bandwidth(Protocol::Udp, Some(64_000)),
+            with_bandwidth(Protocol::Udp6, None), // No estimate, not counted
+        ];
+
+        let totals = monitor.bandwidth_by_protocol();
+        assert_eq!(totals.get(&Protocol::Tcp), Some(&1_500_000));
+        assert_eq!(totals.get(&Protocol::Tcp6), Some(&250_000));
+        assert_eq!(totals.get(&Protocol::Udp), Some(&64_000));
+        assert_eq!(totals.get(&Protocol::Udp6), None);
+    }
+
+    #[test]
+    fn test_established_age_persists_across_updates() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut monitor = ConnectionMonitor::new();
+        monitor.connections = vec![
+            synthetic_connection(ConnectionState::Established, Protocol::Tcp),
+            synthetic_connection(ConnectionState::Listen, Protocol::Tcp),
+        ];
+        monitor.track_established();
+        assert!(monitor.connections[0].established_at.is_some());
+        assert_eq!(monitor.connections[1].established_at, None);
+        assert!(monitor.get_long_lived_connections(day).is_empty());
+
+        // Pretend the pair was first seen 25 hours ago; the stamp survives a fresh update
+        let key = (
+            monitor.connections[0].local_addr,
+            monitor.connections[0].remote_addr,
+        );
+        let first_seen = SystemTime::now() - Duration::from_secs(25 * 60 * 60);
+        monitor.established_since.insert(key, first_seen);
+        monitor.connections = vec![synthetic_connection(
+            ConnectionState::Established,
+            Protocol::Tcp,
+        )];
+        monitor.track_established();
+        assert_eq!(monitor.connections[0].established_at, Some(first_seen));
+        assert_eq!(monitor.get_long_lived_connections(day).len(), 1);
+
+        // Leaving ESTABLISHED forgets the pair
+        monitor.connections[0].state = ConnectionState::TimeWait;
+        monitor.track_established();
+        assert_eq!(monitor.connections[0].age(), None);
+        assert!(monitor.established_since.is_empty());
+    }
+
+    #[test]
+    fn test_bandwidth_estimated_from_byte_counters() {
+        let monitor = ConnectionMonitor::new();
+        let mut conn = synthetic_connection(ConnectionState::Established, Protocol::Tcp);
+        monitor
+            .parse_socket_details(
+                "cubic wscale:7,7 rto:204 rtt:1.5/0.75 cwnd:10 bytes_acked:1000 bytes_received:5000 pacing_rate 2Mbps delivery_rate 800Kbps",
+                &mut conn,
+            )
+            .unwrap();
+        assert_eq!((conn.bytes_sent, conn.bytes_received), (1000, 5000));
+        assert_eq!(conn.socket_info.pacing_rate, Some(2_000_000));
+        assert_eq!(conn.socket_info.bandwidth, Some(800_000));
+        assert_eq!(conn.socket_info.cc_algo.as_deref(), Some("cubic"));
+
+        let mut conn = synthetic_connection(ConnectionState::Established, Protocol::Tcp);
+        monitor
+            .parse_socket_details(
+                "ts sack bbr wscale:7,7 rto:204 rtt:1.5/0.75 bbr:(bw:10Mbps,mrtt:1.2)",
+                &mut conn,
+            )
+            .unwrap();
+        assert_eq!(conn.socket_info.cc_algo.as_deref(), Some("bbr"));
+
+        let counted = |sent: u64, received: u64| {
+            let mut conn = synthetic_connection(ConnectionState::Established, Protocol::Tcp);
+            conn.bytes_sent = sent;
+            conn.bytes_received = received;
+            conn
+        };
+        let mut monitor = ConnectionMonitor::new();
+        let start = Instant::now();
+        monitor.connections = vec![counted(1000, 5000)];
+        monitor.estimate_bandwidth(start);
+        // Nothing to diff against on the first sample
+        assert_eq!(monitor.connections[0].socket_info.bandwidth, None);
+        assert_eq!(monitor.connections[0].sent_per_sec, None);
+
+        // 250_000 bytes in 2s = 1 Mbit/s
+        monitor.connections = vec![counted(51_000, 205_000)];
+        monitor.estimate_bandwidth(start + Duration::from_secs(2));
+        assert_eq!(
+            monitor.connections[0].socket_info.bandwidth,
+            Some(1_000_000)
+        );
+        assert_eq!(monitor.connections[0].sent_per_sec, Some(25_000));
+        assert_eq!(monitor.connections[0].received_per_sec, Some(100_000));
+
+        // A kernel estimate is kept as is
+        let mut reported = counted(60_000, 300_000);
+        reported.socket_info.bandwidth = Some(42);
+        monitor.connections = vec![reported];
+        monitor.estimate_bandwidth(start + Duration::from_secs(4));
+        assert_eq!(monitor.connections[0].socket_info.bandwidth, Some(42));
+        assert_eq!(monitor.connections[0].sent_per_sec, Some(4_500));
+    }
+
+    #[test]
+    fn test_churn_keys_on_protocol_and_both_endpoints() {
+        let peer = |remote: &str, protocol: Protocol| {
+            let mut conn = synthetic_connection(ConnectionState::Established, protocol);
+            conn.remote_addr = remote.parse().unwrap();
+            conn
+        };
+        let start = Instant::now();
+        let mut monitor = ConnectionMonitor::new();
+        monitor.connections = vec![
+            peer("10.0.0.2:443", Protocol::Tcp),
+            peer("10.0.0.3:443", Protocol::Tcp),
+        ];
+        monitor.track_churn(start);
+        assert_eq!(monitor.stats.new_per_sec, 0.0);
+
+        // Same local port, new peer: one closed and one new. A UDP socket on an existing
+        // TCP 4-tuple is new too.
+        monitor.connections = vec![
+            peer("10.0.0.2:443", Protocol::Tcp),
+            peer("10.0.0.4:443", Protocol::Tcp),
+            peer("10.0.0.2:443", Protocol::Udp),
+        ];
+        monitor.track_churn(start + Duration::from_secs(2));
+        assert_eq!(monitor.stats.new_per_sec, 1.0);
+        assert_eq!(monitor.stats.closed_per_sec, 0.5);
+        assert_eq!(monitor.stats.churn_ratio, 3.0 / 5.0);
+
+        // Nothing changed
+        monitor.track_churn(start + Duration::from_secs(3));
+        assert_eq!(monitor.stats.new_per_sec, 0.0);
+        assert_eq!(monitor.stats.closed_per_sec, 0.0);
+        assert_eq!(monitor.stats.churn_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_connection_queries_borrow_matching_connections() {
+        let conn = |local: &str, remote: &str, state, process: Option<&str>| {
+            let mut conn = synthetic_connection(state, Protocol::Tcp);
+            conn.local_addr = local.parse().unwrap();
+            conn.remote_addr = remote.parse().unwrap();
+            conn.process_name = process.map(str::to_string);
+            conn
+        };
+        let mut monitor = ConnectionMonitor::new();
+        monitor.connections = vec![
+            conn(
+                "10.0.0.1:40000",
+                "93.184.216.34:443",
+                ConnectionState::Established,
+                Some("firefox"),
+            ),
+            conn(
+                "[2001:db8::1]:40001",
+                "[2606:4700::1111]:443",
+                ConnectionState::Established,
+                Some("Firefox-ESR"),
+            ),
+            conn(
+                "[2001:db8::1]:40002",
+                "[2606:4700::1111]:53",
+                ConnectionState::TimeWait,
+                None,
+            ),
+            conn(
+                "0.0.0.0:443",
+                "0.0.0.0:0",
+                ConnectionState::Listen,
+                Some("nginx"),
+            ),
+            conn(
+                "10.0.0.1:40003",
+                "93.184.216.34:80",
+                ConnectionState::SynSent,
+                Some("curl"),
+            ),
+        ];
+        let locals = |conns: Vec<&NetworkConnection>| -> Vec<u16> {
+            conns.iter().map(|conn| conn.local_addr.port()).collect()
+        };
+
+        assert_eq!(
+            locals(monitor.filter_by_state(&ConnectionState::Established)),
+            [40000, 40001]
+        );
+        assert_eq!(
+            locals(monitor.filter_by_state(&ConnectionState::TimeWait)),
+            [40002]
+        );
+        assert!(monitor
+            .filter_by_state(&ConnectionState::Closing)
+            .is_empty());
+
+        assert_eq!(
+            locals(monitor.filter_by_process_name("FIREFOX")),
+            [40000, 40001]
+        );
+        assert_eq!(locals(monitor.filter_by_process_name("gin")), [443]);
+        assert!(monitor.filter_by_process_name("sshd").is_empty());
+
+        // Local and remote ends both count
+        assert_eq!(
+            locals(monitor.get_connections_by_port(443)),
+            [40000, 40001, 443]
+        );
+        assert_eq!(locals(monitor.get_connections_by_port(53)), [40002]);
+
+        let v4: IpAddr = "93.184.216.34".parse().unwrap();
+        let v6: IpAddr = "2606:4700::1111".parse().unwrap();
+        assert_eq!(locals(monitor.get_established_to_remote(v4)), [40000]);
+        assert_eq!(locals(monitor.get_established_to_remote(v6)), [40001]);
+
+        // References into the monitor's own list, not copies
+        let first = monitor.get_established_to_remote(v4)[0];
+        assert!(std::ptr::eq(first, &monitor.connections[0]));
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn test_proc_net_tables_without_ss() {
+        let tcp = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000002 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:D6E2 2204FA8E:01BB 01 00000040:00000000 01:00000014 00000000  1000        0 4343 1 0000000000000000 20 4 30 10 -1
+";
+        let tcp6 = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 5151 1 0000000000000000 100 0 0 10 0
+";
+        let owners = HashMap::from([(4343, 77)]);
+        let mut monitor = ConnectionMonitor::new();
+        monitor
+            .parse_connections(tcp, Protocol::Tcp, &owners)
+            .unwrap();
+        monitor
+            .parse_connections(tcp6, Protocol::Tcp6, &owners)
+            .unwrap();
+
+        let [listen, established, listen6] = monitor.connections.as_slice() else {
+            panic!("expected three sockets");
+        };
+        assert_eq!(listen.local_addr, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(listen.state, ConnectionState::Listen);
+        assert_eq!(listen.socket_info.recv_queue, 2);
+        // The uid column is not a pid, and nobody we can see owns inode 4242
+        assert_eq!(listen.pid, None);
+
+        assert_eq!(established.local_addr, "10.0.2.15:55010".parse().unwrap());
+        assert_eq!(established.remote_addr, "142.250.4.34:443".parse().unwrap());
+        assert_eq!(established.state, ConnectionState::Established);
+        assert_eq!(established.socket_info.send_queue, 64);
+        assert_eq!(established.pid, Some(77));
+        assert_eq!(established.socket_info.rtt, None);
+
+        assert_eq!(listen6.local_addr, "[::1]:22".parse().unwrap());
+        assert_eq!(listen6.state, ConnectionState::Listen);
+    }
+}
@@ -1,22 +1,46 @@
 use crate::{
     active_diagnostics::{ActiveDiagnosticsEngine, ConnectivityStatus, DnsStatus, PortStatus},
+    alerts::{
+        self, Alert, AlertEvent, AlertHistory, AlertKind, AlertSeverity, CriticalNotifier,
+        WebhookNotifier,
+    },
     cli::{DataUnit, TrafficUnit},
+    collectors::{Collectors, Snapshot, NEIGHBORS_INTERVAL},
     config::Config,
-    connections::ConnectionMonitor,
-    device::{Device, NetworkReader},
+    connections::{
+        self, ConnectionMonitor, ConnectionSource, ConnectionState, ConnectionStats,
+        ListeningSocket, NetworkConnection, Protocol,
+    },
+    device::{Device, NetworkReader, TOTAL_DEVICE},
+    dns_monitor::{DnsSource, DNS_WINDOW},
+    health::{self, HealthAssessment, HealthStatus},
+    influx::InfluxExporter,
     input::InputEvent,
-    logger::TrafficLogger,
-    network_intelligence::{NetworkIntelligenceEngine, Severity},
-    processes::ProcessMonitor,
-    safe_system::{SafeSystemMonitor, SafeSystemStats},
+    logger::{DebugLogger, TrafficLogger},
+    neighbors::{ArpEntry, NeighborState},
+    network_intelligence::{
+        connection_metrics, Baseline, ConnectionIntelligence, MetricBaseline,
+        NetworkIntelligenceEngine, PortScanThresholds, Severity,
+    },
+    platform::{
+        wireguard::{self, WireGuardInfo},
+        InterfaceEvent, InterfaceEventWatcher,
+    },
+    processes::{ContainerNetworkInfo, ProcessMonitor, ProcessNetworkInfo},
+    quota::{self, QuotaLimits, QuotaPeriod, QuotaTracker},
+    replay::Recorder,
+    safe_system::{SafeSystemMonitor, SafeSystemStats, SocketStats, ORPHAN_TCP_WARNING},
+    session::{self, DashboardSnapshot, SessionState},
     simple_overview::{
         draw_basic_connectivity_check, draw_common_network_issues, draw_simple_interface_summary,
     },
-    stats::StatsCalculator,
+    stats::{self, StatsCalculator, TotalDevice},
     system::SystemMonitor,
+    theme::Theme,
+    units::{format_age, format_bytes, format_rate, format_volume},
 };
 use anyhow::Result;
-use crossterm::event::{self, Event};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -28,11 +52,9 @@ use ratatui::{
     },
     Frame, Terminal,
 };
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::net::IpAddr;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
@@ -46,6 +68,7 @@ pub enum DashboardPanel {
     System,
     Graphs,
     Diagnostics,
+    Neighbors,
     Alerts,
     Forensics,
     Settings,
@@ -61,6 +84,7 @@ impl DashboardPanel {
             Self::System,
             Self::Graphs,
             Self::Diagnostics,
+            Self::Neighbors,
             Self::Alerts,
             Self::Forensics,
             Self::Settings,
@@ -76,40 +100,418 @@ impl DashboardPanel {
             Self::System => "System Info",
             Self::Graphs => "Graphs",
             Self::Diagnostics => "Active Diagnostics",
+            Self::Neighbors => "Neighbors",
             Self::Alerts => "Network Alerts",
             Self::Forensics => "Security Forensics",
             Self::Settings => "Settings",
         }
     }
+
+    /// Short name for `--panel` and the `Panel` config key
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Overview => "overview",
+            Self::Interfaces => "interfaces",
+            Self::Connections => "connections",
+            Self::Processes => "processes",
+            Self::System => "system",
+            Self::Graphs => "graphs",
+            Self::Diagnostics => "diagnostics",
+            Self::Neighbors => "neighbors",
+            Self::Alerts => "alerts",
+            Self::Forensics => "forensics",
+            Self::Settings => "settings",
+        }
+    }
+
+    /// The named panels in tab order, or every panel when none of the names match.
+    /// Unknown names are skipped; --check-config reports them.
+    pub fn enabled(names: &[String]) -> Vec<Self> {
+        let panels: Vec<Self> = Self::all()
+            .into_iter()
+            .filter(|panel| {
+                names
+                    .iter()
+                    .any(|name| Self::from_name(name).is_ok_and(|named| named == *panel))
+            })
+            .collect();
+        if panels.is_empty() {
+            Self::all()
+        } else {
+            panels
+        }
+    }
+
+    /// Panel with this short name or title, ignoring case
+    pub fn from_name(name: &str) -> crate::error::Result<Self> {
+        let panels = Self::all();
+        let name = name.trim();
+        panels
+            .iter()
+            .find(|panel| {
+                name.eq_ignore_ascii_case(panel.name()) || name.eq_ignore_ascii_case(panel.title())
+            })
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<&str> = panels.iter().map(Self::name).collect();
+                crate::error::NetwatchError::Config(format!(
+                    "Unknown panel '{name}' (use {})",
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// The `/` filter of the list panels, cleared when switching panels
+#[derive(Debug, Clone, Default)]
+pub struct SearchBar {
+    /// Keys are typed into the query rather than acting on the dashboard
+    pub active: bool,
+    pub query: String,
+}
+
+impl SearchBar {
+    /// While typing, and after Enter while the query is in effect
+    pub fn is_shown(&self) -> bool {
+        self.active || !self.query.is_empty()
+    }
+
+    pub fn matches(&self, row: &str) -> bool {
+        match_search(&self.query, row)
+    }
+}
+
+/// Whether the `/` filter keeps a row: every word of `query` appears in the row's text,
+/// ignoring case. An empty query keeps every row.
+pub fn match_search(query: &str, row: &str) -> bool {
+    let row = row.to_lowercase();
+    query
+        .split_whitespace()
+        .all(|word| row.contains(&word.to_lowercase()))
+}
+
+/// A signal awaiting confirmation for the process selected in the Processes panel
+#[derive(Debug, Clone)]
+pub struct KillPrompt {
+    pub pid: u32,
+    pub name: String,
+    /// SIGKILL rather than SIGTERM
+    pub force: bool,
+}
+
+/// Process opened with Enter in the Processes panel, whose connections are listed
+/// in place of the process list until Esc
+#[derive(Debug, Clone)]
+pub struct ProcessDrilldown {
+    pub pid: u32,
+    /// Kept for the header after the process exits
+    pub name: String,
+    /// Process list row to return to
+    pub return_row: usize,
+}
+
+/// The process given with `--pid`, whose connections the Connections panel is
+/// narrowed to
+#[derive(Debug, Clone)]
+pub struct ProcessFocus {
+    pub pid: u32,
+    /// `--exit-on-process-death`: quit once the process is gone
+    pub exit_on_death: bool,
+    /// Its connections in the latest snapshot
+    connections: Vec<NetworkConnection>,
+    exited: bool,
+    /// Whether the Processes panel cursor has been put on it yet
+    row_selected: bool,
+}
+
+impl ProcessFocus {
+    pub fn new(pid: u32, exit_on_death: bool) -> Self {
+        Self {
+            pid,
+            exit_on_death,
+            connections: Vec::new(),
+            exited: false,
+            row_selected: false,
+        }
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+}
+
+/// Updates a changed value stays highlighted for: the one it changed in and the next
+const CHANGE_HIGHLIGHT_UPDATES: u32 = 2;
+
+/// watch(1)-style highlighting of values that changed: the last value shown for each
+/// field, and how many of its updates ago that value changed
+#[derive(Debug, Default)]
+pub struct ChangeTracker {
+    fields: HashMap<String, (String, u32)>,
+}
+
+impl ChangeTracker {
+    /// Note `field`'s value, as displayed, after an update
+    pub fn record(&mut self, field: &str, shown: String) {
+        match self.fields.get_mut(field) {
+            Some((previous, age)) if *previous == shown => *age = age.saturating_add(1),
+            Some(entry) => *entry = (shown, 0),
+            // Appearing is not a change
+            None => {
+                self.fields
+                    .insert(field.to_string(), (shown, CHANGE_HIGHLIGHT_UPDATES));
+            }
+        }
+    }
+
+    pub fn changed(&self, field: &str) -> bool {
+        self.fields
+            .get(field)
+            .is_some_and(|(_, age)| *age < CHANGE_HIGHLIGHT_UPDATES)
+    }
+
+    /// `style`, made bold and reversed while `field` is highlighted
+    pub fn style(&self, field: &str, style: Style) -> Style {
+        if self.changed(field) {
+            style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
+}
+
+/// A setting that can be changed from the Settings panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingField {
+    RefreshInterval,
+    TrafficUnit,
+    DataUnit,
+    AverageWindow,
+    HighPerformance,
+    ResolveHostnames,
+    PhysicalTotal,
+}
+
+impl SettingField {
+    /// Display order in the Settings panel
+    pub const ALL: [Self; 7] = [
+        Self::RefreshInterval,
+        Self::TrafficUnit,
+        Self::DataUnit,
+        Self::AverageWindow,
+        Self::HighPerformance,
+        Self::ResolveHostnames,
+        Self::PhysicalTotal,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RefreshInterval => "Refresh Interval",
+            Self::TrafficUnit => "Traffic Unit",
+            Self::DataUnit => "Data Unit",
+            Self::AverageWindow => "Average Window",
+            Self::HighPerformance => "High Performance",
+            Self::ResolveHostnames => "Resolve Hostnames",
+            Self::PhysicalTotal => "Physical Total",
+        }
+    }
+
+    /// Numeric settings are typed in after Enter; the rest cycle through their values
+    pub fn is_numeric(self) -> bool {
+        matches!(self, Self::RefreshInterval | Self::AverageWindow)
+    }
+
+    pub fn value(self, config: &Config) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+        match self {
+            Self::RefreshInterval => format!("{} ms", config.refresh_interval),
+            Self::TrafficUnit => format!("{:?}", config.get_traffic_unit()),
+            Self::DataUnit => format!("{:?}", config.get_data_unit()),
+            Self::AverageWindow => format!("{} s", config.average_window),
+            Self::HighPerformance => on_off(config.high_performance),
+            Self::ResolveHostnames => on_off(config.resolve_hostnames),
+            Self::PhysicalTotal => on_off(config.physical_total),
+        }
+    }
+
+    /// The raw number to start editing from
+    pub fn input(self, config: &Config) -> String {
+        match self {
+            Self::RefreshInterval => config.refresh_interval.to_string(),
+            Self::AverageWindow => config.average_window.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Move the setting one step: the next refresh interval or unit, 30s of average
+    /// window, or flip a switch
+    pub fn step(self, config: &mut Config, forward: bool) {
+        match self {
+            Self::RefreshInterval => {
+                config.refresh_interval = step_refresh_interval(config.refresh_interval, forward);
+            }
+            Self::TrafficUnit => {
+                let unit = config.get_traffic_unit();
+                let unit = if forward { unit.next() } else { unit.prev() };
+                config.traffic_format = unit.to_string().to_string();
+            }
+            Self::DataUnit => {
+                let unit = config.get_data_unit();
+                let unit = if forward { unit.next() } else { unit.prev() };
+                config.data_format = unit.to_string().to_string();
+            }
+            Self::AverageWindow => {
+                config.average_window = if forward {
+                    config.average_window.saturating_add(30)
+                } else {
+                    config.average_window.saturating_sub(30).max(30)
+                };
+            }
+            Self::HighPerformance => config.high_performance = !config.high_performance,
+            Self::ResolveHostnames => config.resolve_hostnames = !config.resolve_hostnames,
+            Self::PhysicalTotal => config.physical_total = !config.physical_total,
+        }
+    }
+
+    /// Apply typed input to a numeric setting. Range checks beyond "a number" are left
+    /// to [`Config::validate`], except the window, which it doesn't cover.
+    pub fn set(self, config: &mut Config, input: &str) -> Result<(), String> {
+        let invalid = || format!("{}: `{input}` is not a number", self.label());
+        match self {
+            Self::RefreshInterval => {
+                config.refresh_interval = input.parse().map_err(|_| invalid())?;
+            }
+            Self::AverageWindow => match input.parse() {
+                Ok(0) => return Err("Average window must be at least 1 second".to_string()),
+                Ok(seconds) => config.average_window = seconds,
+                Err(_) => return Err(invalid()),
+            },
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
+/// How long a footer status message stays up
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
+/// Rows in the Processes panel's process list
+const PROCESS_LIST_ROWS: usize = 15;
+
+/// Rows in the Connections panel's connection list
+const CONNECTION_LIST_ROWS: usize = 15;
+
+/// Deepest the Graphs panel can zoom its time axis (1/16th of the minute)
+const MAX_GRAPH_ZOOM: f64 = 16.0;
+
+/// Interfaces shown at once in the Graphs panel's side-by-side view
+const MAX_GRAPH_COLUMNS: usize = 4;
+
 pub struct DashboardState {
     pub current_device_index: usize,
     pub devices: Vec<Device>,
     pub active_panel: DashboardPanel,
+    /// Panels in the tab bar, from `EnabledPanels`; `panel_index` indexes this
+    pub panels: Vec<DashboardPanel>,
     pub panel_index: usize,
     pub paused: bool,
     pub traffic_unit: TrafficUnit,
     pub data_unit: DataUnit,
     pub max_incoming: u64,
     pub max_outgoing: u64,
+    /// Graph scale (BarMaxIn, BarMaxOut) of interfaces with their own `[Interfaces.NAME]`
+    pub interface_bar_max: HashMap<String, (u64, u64)>,
+    /// Time axis zoom of the Graphs panel: 1.0 shows the whole minute, 2.0 half of it
     pub zoom_level: f64,
+    /// Graph points the zoomed Graphs view is panned back from the newest one
+    pub graph_x_offset: usize,
+    /// Graphs panel shows interfaces side by side (m) instead of the selected one;
+    /// kept across panel switches
+    pub graphs_multi_view: bool,
+    /// Processes panel sums traffic per container (g) instead of listing processes
+    pub processes_by_container: bool,
+    pub process_drilldown: Option<ProcessDrilldown>,
+    /// Connections panel lists listening sockets and who can reach them (o) instead of
+    /// connections
+    pub connections_listening: bool,
+    /// `--pid`: the one process being watched
+    pub process_focus: Option<ProcessFocus>,
+    /// Recently changed CPU, memory and interface rates, highlighted in the System
+    /// and Interfaces panels
+    pub changes: ChangeTracker,
+    /// Peers of the selected interface when it is a WireGuard one, refreshed with its
+    /// counters
+    pub wireguard: HashMap<String, WireGuardInfo>,
+    /// Last-known addresses of each listed interface, to tell when they change; empty
+    /// when the reader cannot say (another host, a recording)
+    pub interface_addresses: HashMap<String, BTreeSet<IpAddr>>,
+    /// Interfaces whose ports, slaves and VLANs the Interfaces panel hides (Enter)
+    pub collapsed_interfaces: HashSet<String>,
+    /// `ShowTotalDevice`: the physical interfaces summed, listed first in the
+    /// Interfaces panel
+    pub total_device: Option<TotalDevice>,
+    /// Graphs panel shows the total device instead of `current_device_index`
+    pub graph_total: bool,
+    /// `--quota-*-gb`; empty (nothing tracked) without quotas or for another host
+    pub quota_limits: QuotaLimits,
+    /// Daily, weekly and monthly totals of each interface while quotas are set
+    pub quotas: HashMap<String, QuotaTracker>,
+    pub search: SearchBar,
+    pub log_scale: bool,
+    pub compact_mode: bool,
+    pub theme: Theme,
     pub show_help: bool,
     pub selected_item: usize,
     pub list_state: ListState,
     pub table_state: TableState,
     pub connection_monitor: ConnectionMonitor,
     pub process_monitor: ProcessMonitor,
+    pub process_bandwidth: Vec<(ProcessNetworkInfo, u64, u64)>, // (process, in, out)
     pub system_monitor: SystemMonitor,
     pub safe_system_monitor: SafeSystemMonitor,
+    pub system_stats: Option<SafeSystemStats>, // Latest snapshot from the system collector
     pub active_diagnostics: ActiveDiagnosticsEngine,
     pub network_intelligence: NetworkIntelligenceEngine,
+    /// `--compare-baseline`: traffic is shown next to its deviation from this
+    pub compare_baseline: Option<Baseline>,
     pub last_active_diagnostics_update: Option<std::time::Instant>,
+    pub diagnostics_scroll: u16, // First traceroute line shown in the Diagnostics panel
+    /// Trace target shown in the Diagnostics panel, by position in target order
+    pub trace_target_index: usize,
+    /// ARP cache from the neighbors collector; None until read or where unsupported
+    pub neighbors: Option<Vec<ArpEntry>>,
+    pub last_neighbors_update: Option<Instant>,
+    /// Alerts active at the last refresh; what the Alerts panel, its badge, the history
+    /// and the notifiers all go by
+    pub current_alerts: Vec<Alert>,
+    /// System Status at the last refresh, for the Diagnostics panel and NotifyOnCritical
+    pub health: HealthAssessment,
+    /// Alerts raised, escalated and cleared while running, for the Alerts panel
+    pub alert_history: AlertHistory,
+    /// Alerts panel shows the history (h) instead of the active alerts
+    pub alerts_show_history: bool,
+    /// Newest history events scrolled past
+    pub alert_history_scroll: usize,
     pub last_navigation_time: std::time::Instant,
     pub navigation_redraw_needed: bool,
     pub parallel_data: ParallelData,
     pub last_forensics_update: Option<std::time::Instant>,
+    /// Forensics panel analysis from the last pass, redrawn until the next is due
+    pub forensics_intel: Vec<ConnectionIntelligence>,
     pub config: Option<Arc<crate::config::Config>>,
+    pub kill_prompt: Option<KillPrompt>,
+    /// `--debug-log` trace of navigation and key events; a no-op unless enabled
+    pub debug_log: DebugLogger,
+    /// Last PID sent SIGTERM; pressing K on it again offers SIGKILL
+    pub terminated_pid: Option<u32>,
+    /// Shown in place of the footer hints for [`STATUS_MESSAGE_DURATION`]
+    pub status_message: Option<(String, Instant)>,
+    /// Where the panel tabs were last drawn, for mouse clicks; empty in compact mode
+    pub tab_bar: Rect,
+    /// Row of [`SettingField::ALL`] under the Settings panel's cursor
+    pub settings_index: usize,
+    /// Digits typed so far while a numeric setting is being edited
+    pub settings_input: Option<String>,
 }
 
 #[derive(Clone)]
@@ -199,64 +601,166 @@ impl ParallelData {
 
 impl DashboardState {
     pub fn new(devices: Vec<String>, config: &Config) -> Result<Self> {
-        let devices: Vec<Device> = devices.into_iter().map(Device::new).collect();
+        let mut devices: Vec<Device> = devices.into_iter().map(Device::new).collect();
+        if config.docker_labels {
+            let names: Vec<String> = devices.iter().map(|device| device.name.clone()).collect();
+            let labels = crate::platform::docker::resolve_container_names(&names);
+            for (device, label) in devices.iter_mut().zip(labels) {
+                device.label = label;
+            }
+        }
+        let interface_bar_max = interface_bar_max(&devices, config);
         let mut list_state = ListState::default();
         list_state.select(Some(0));
         let mut table_state = TableState::default();
         table_state.select(Some(0));
 
         // Validate panel consistency
-        let panels = DashboardPanel::all();
-        let initial_panel_index = 0;
+        let panels = DashboardPanel::enabled(&config.enabled_panels);
+        // A bad or disabled name is reported by --check-config; fall back quietly here
+        let initial_panel_index = config
+            .panel
+            .as_deref()
+            .and_then(|name| DashboardPanel::from_name(name).ok())
+            .and_then(|panel| panels.iter().position(|p| *p == panel))
+            .unwrap_or(0);
         let initial_active_panel = panels[initial_panel_index].clone();
 
         // Initialize dashboard with proper panel state
 
+        let mut connection_monitor = ConnectionMonitor::new();
+        if config.resolve_hostnames {
+            connection_monitor.enable_reverse_dns();
+        }
+
         Ok(Self {
             current_device_index: 0,
             devices,
             active_panel: initial_active_panel,
+            panels,
             panel_index: initial_panel_index,
             paused: false,
             traffic_unit: config.get_traffic_unit(),
             data_unit: config.get_data_unit(),
             max_incoming: config.max_incoming,
             max_outgoing: config.max_outgoing,
+            interface_bar_max,
             zoom_level: 1.0,
+            graph_x_offset: 0,
+            graphs_multi_view: false,
+            processes_by_container: false,
+            process_drilldown: None,
+            connections_listening: false,
+            process_focus: None,
+            changes: ChangeTracker::default(),
+            wireguard: HashMap::new(),
+            interface_addresses: HashMap::new(),
+            collapsed_interfaces: HashSet::new(),
+            total_device: config
+                .show_total_device
+                .then(|| TotalDevice::new(Duration::from_secs(config.average_window as u64))),
+            graph_total: false,
+            quota_limits: QuotaLimits::default(),
+            quotas: HashMap::new(),
+            search: SearchBar::default(),
+            log_scale: config.log_scale,
+            compact_mode: config.compact_mode,
+            // An unloadable theme was already reported before the TUI started
+            theme: Theme::from_config(config).unwrap_or_default(),
             show_help: false,
             selected_item: 0,
             list_state,
             table_state,
-            connection_monitor: ConnectionMonitor::new(),
+            connection_monitor,
             process_monitor: ProcessMonitor::new(),
+            process_bandwidth: Vec::new(),
             system_monitor: SystemMonitor::new()?,
             safe_system_monitor: SafeSystemMonitor::new(),
-            active_diagnostics: ActiveDiagnosticsEngine::new(),
+            system_stats: None,
+            active_diagnostics: ActiveDiagnosticsEngine::with_config(config),
             network_intelligence: NetworkIntelligenceEngine::new(),
+            compare_baseline: None,
             last_active_diagnostics_update: None,
+            diagnostics_scroll: 0,
+            trace_target_index: 0,
+            neighbors: None,
+            last_neighbors_update: None,
+            current_alerts: Vec::new(),
+            health: HealthAssessment::default(),
+            alert_history: AlertHistory::default(),
+            alerts_show_history: false,
+            alert_history_scroll: 0,
             last_navigation_time: std::time::Instant::now(),
             navigation_redraw_needed: false,
             parallel_data: ParallelData::new(),
             last_forensics_update: None,
+            forensics_intel: Vec::new(),
             config: None,
+            kill_prompt: None,
+            debug_log: DebugLogger::default(),
+            terminated_pid: None,
+            status_message: None,
+            tab_bar: Rect::default(),
+            settings_index: 0,
+            settings_input: None,
         })
     }
 
+    /// One-line header and footer and at most [`COMPACT_PANEL_ROWS`] of panel, for 80x24
+    pub fn is_compact(&self) -> bool {
+        self.compact_mode
+    }
+
+    /// Replace a monitor with the latest copy published by its collector
+    pub fn apply_snapshot(&mut self, snapshot: Snapshot) {
+        match snapshot {
+            Snapshot::Connections(monitor) => {
+                self.connection_monitor = *monitor;
+                self.network_intelligence
+                    .observe_connections(self.connection_monitor.get_connections());
+                if let Some(focus) = self.process_focus.as_mut() {
+                    focus.connections = crate::processes::connections_of_pid(
+                        focus.pid,
+                        self.connection_monitor.get_connections(),
+                    );
+                }
+            }
+            Snapshot::Processes { monitor, bandwidth } => {
+                self.process_monitor = monitor;
+                self.process_bandwidth = bandwidth;
+                self.select_focused_process();
+            }
+            Snapshot::System(stats) => {
+                self.changes
+                    .record("cpu", format!("{:.1}", stats.cpu_usage_percent));
+                self.changes
+                    .record("memory", format!("{:.1}", stats.memory_usage_percent));
+                self.system_stats = Some(stats);
+            }
+            Snapshot::Diagnostics(engine) => {
+                self.active_diagnostics = *engine;
+                self.last_active_diagnostics_update = Some(Instant::now());
+            }
+            Snapshot::Neighbors(mut neighbors) => {
+                if let Some(entries) = neighbors.as_mut() {
+                    entries.sort_by(|a, b| a.interface.cmp(&b.interface).then(a.ip.cmp(&b.ip)));
+                }
+                self.neighbors = neighbors;
+                self.last_neighbors_update = Some(Instant::now());
+            }
+            Snapshot::Failed(message) => self.set_status(message),
+        }
+    }
+
     pub fn next_panel(&mut self) -> bool {
         let now = std::time::Instant::now();
 
-        let panels = DashboardPanel::all();
+        let panels = &self.panels;
 
         // More robust navigation logic
         if panels.is_empty() {
-            let empty_msg = "ERROR: panels.is_empty() in next_panel\n";
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/netwatch_nav_debug.log")
-            {
-                let _ = file.write_all(empty_msg.as_bytes());
-            }
+            self.debug_log
+                .log(format_args!("ERROR: panels.is_empty() in next_panel"));
             return false; // Safety check for empty panels
         }
 
@@ -276,6 +780,7 @@ impl DashboardState {
             self.active_panel = panels[self.panel_index].clone();
 
             // Reset selection state for new panel
+            self.search = SearchBar::default();
             self.selected_item = 0;
             self.list_state.select(Some(0));
             self.table_state.select(Some(0));
@@ -286,30 +791,18 @@ impl DashboardState {
             // Flag for immediate redraw bypass throttling
             self.navigation_redraw_needed = true;
 
-            // Simple navigation logging
-            let nav_msg = format!("Next: {} -> {}\n", current_index, self.panel_index);
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/netwatch_nav_debug.log")
-            {
-                let _ = file.write_all(nav_msg.as_bytes());
-            }
+            self.debug_log.log(format_args!(
+                "Next: {} -> {}",
+                current_index, self.panel_index
+            ));
 
             true // Return true to indicate successful navigation
         } else {
-            let invalid_msg = format!(
-                "ERROR: Invalid next_index {} >= {}\n",
+            self.debug_log.log(format_args!(
+                "ERROR: Invalid next_index {} >= {}",
                 next_index,
                 panels.len()
-            );
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/netwatch_nav_debug.log")
-            {
-                let _ = file.write_all(invalid_msg.as_bytes());
-            }
+            ));
             false // Return false for invalid navigation
         }
     }
@@ -317,7 +810,7 @@ impl DashboardState {
     pub fn prev_panel(&mut self) -> bool {
         let now = std::time::Instant::now();
 
-        let panels = DashboardPanel::all();
+        let panels = &self.panels;
 
         // More robust navigation logic
         if panels.is_empty() {
@@ -340,6 +833,7 @@ impl DashboardState {
             self.active_panel = panels[self.panel_index].clone();
 
             // Reset selection state for new panel
+            self.search = SearchBar::default();
             self.selected_item = 0;
             self.list_state.select(Some(0));
             self.table_state.select(Some(0));
@@ -350,15 +844,10 @@ impl DashboardState {
             // Flag for immediate redraw bypass throttling
             self.navigation_redraw_needed = true;
 
-            // Simple navigation logging
-            let nav_msg = format!("Prev: {} -> {}\n", current_index, self.panel_index);
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/netwatch_nav_debug.log")
-            {
-                let _ = file.write_all(nav_msg.as_bytes());
-            }
+            self.debug_log.log(format_args!(
+                "Prev: {} -> {}",
+                current_index, self.panel_index
+            ));
 
             return true; // Return true to indicate successful navigation
         }
@@ -366,6 +855,102 @@ impl DashboardState {
         false // Return false if navigation failed
     }
 
+    /// Interfaces panel rows as (index into `devices`, depth): each interface followed by
+    /// its bridge ports, bond slaves and VLANs, unless it is collapsed. An interface whose
+    /// parent is not listed is shown at the top level. While filtering, only the matching
+    /// interfaces are, collapsed or not.
+    pub fn interface_rows(&self) -> Vec<(usize, usize)> {
+        let listed = |name: &str| self.devices.iter().any(|device| device.name == name);
+        let mut pending: Vec<(usize, usize)> = self
+            .devices
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, device)| !device.info.parent.as_deref().is_some_and(listed))
+            .map(|(index, _)| (index, 0))
+            .collect();
+
+        let filtering = !self.search.query.is_empty();
+        let mut rows = Vec::new();
+        while let Some((index, depth)) = pending.pop() {
+            let device = &self.devices[index];
+            if !filtering || self.search.matches(&self.interface_search_text(device)) {
+                rows.push((index, depth));
+            }
+            let name = &device.name;
+            if !filtering && self.collapsed_interfaces.contains(name) {
+                continue;
+            }
+            pending.extend(
+                self.devices
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .filter(|(_, device)| device.info.parent.as_ref() == Some(name))
+                    .map(|(child, _)| (child, depth + 1)),
+            );
+        }
+        rows
+    }
+
+    /// What the `/` filter looks for in an interface: its name and label, MAC and
+    /// addresses
+    fn interface_search_text(&self, device: &Device) -> String {
+        let mut text = format!(
+            "{} {}",
+            device.display_name(),
+            device.info.mac.as_deref().unwrap_or_default()
+        );
+        for address in self
+            .interface_addresses
+            .get(&device.name)
+            .into_iter()
+            .flatten()
+        {
+            text.push(' ');
+            text.push_str(&address.to_string());
+        }
+        text
+    }
+
+    /// The total device heads the Interfaces panel: while there is one, and the `/`
+    /// filter (if any) matches it
+    pub fn total_row_shown(&self) -> bool {
+        self.total_device.is_some()
+            && (self.search.query.is_empty() || self.search.matches(TOTAL_DEVICE))
+    }
+
+    /// Rows in the Interfaces panel: [`interface_rows`](Self::interface_rows) after the
+    /// total device's
+    pub fn interface_row_count(&self) -> usize {
+        usize::from(self.total_row_shown()) + self.interface_rows().len()
+    }
+
+    /// The total device is under the Interfaces panel's cursor
+    pub fn total_selected(&self) -> bool {
+        self.total_row_shown() && self.selected_item == 0
+    }
+
+    /// Index into `devices` of the interface under the Interfaces panel's cursor
+    pub fn selected_device(&self) -> Option<usize> {
+        let row = match self.total_row_shown() {
+            true => self.selected_item.checked_sub(1)?,
+            false => self.selected_item,
+        };
+        self.interface_rows().get(row).map(|&(index, _)| index)
+    }
+
+    /// Enter in the Interfaces panel: hide or show what sits under the selected interface
+    pub fn toggle_selected_collapsed(&mut self) {
+        let Some(index) = self.selected_device() else {
+            return;
+        };
+        let name = &self.devices[index].name;
+        if !self.collapsed_interfaces.remove(name) {
+            self.collapsed_interfaces.insert(name.clone());
+        }
+    }
+
     pub fn next_item(&mut self, max_items: usize) {
         if max_items > 0 {
             self.selected_item = (self.selected_item + 1) % max_items;
@@ -383,172 +968,1058 @@ impl DashboardState {
             self.list_state.select(Some(self.selected_item));
         }
     }
-}
-
-pub fn run_dashboard(
-    interfaces: Vec<String>,
-    reader: Box<dyn NetworkReader>,
-    mut config: Config,
-    log_file: Option<String>,
-) -> Result<()> {
-    let backend = CrosstermBackend::new(std::io::stdout());
-    let mut terminal = Terminal::new(backend)?;
-
-    let mut state = DashboardState::new(interfaces, &config)?;
-    state.config = Some(Arc::new(config.clone()));
-    let mut stats_calculators: HashMap<String, StatsCalculator> = HashMap::new();
-    let mut logger = if log_file.is_some() {
-        Some(TrafficLogger::new(log_file)?)
-    } else {
-        None
-    };
 
-    // Initialize stats calculators for each device
-    for device in &state.devices {
-        stats_calculators.insert(
-            device.name.clone(),
-            StatsCalculator::new(Duration::from_secs(config.average_window as u64)),
-        );
+    /// Switch to the panel at `index` in the tab bar, as Tab would
+    pub fn select_panel(&mut self, index: usize) -> bool {
+        if index >= self.panels.len() || index == self.panel_index {
+            return false;
+        }
+        self.panel_index = index;
+        self.active_panel = self.panels[index].clone();
+        self.search = SearchBar::default();
+        self.selected_item = 0;
+        self.list_state.select(Some(0));
+        self.table_state.select(Some(0));
+        self.last_navigation_time = Instant::now();
+        self.navigation_redraw_needed = true;
+        true
     }
 
-    let mut last_update = Instant::now();
-    let mut last_connection_update = Instant::now();
-    let mut last_process_update = Instant::now();
-    let mut last_draw = Instant::now();
-    let mut needs_redraw = true;
-    let refresh_interval = Duration::from_millis(config.refresh_interval);
-    // Scale update intervals based on refresh rate and performance mode
-    let base_multiplier = (config.refresh_interval as f64 / 1000.0).max(1.0);
-    let perf_multiplier = if config.high_performance { 2.0 } else { 1.0 };
-    let connection_update_interval =
-        Duration::from_secs((4.0 * base_multiplier * perf_multiplier) as u64);
-    let process_update_interval =
-        Duration::from_secs((6.0 * base_multiplier * perf_multiplier) as u64);
-    let draw_interval = Duration::from_millis((200.0 * base_multiplier * perf_multiplier) as u64);
+    /// Where the dashboard stands, to be reopened there next time
+    pub fn snapshot(&self) -> DashboardSnapshot {
+        DashboardSnapshot {
+            saved_at: chrono::Utc::now().timestamp(),
+            panel: self.active_panel.name().to_string(),
+            selected_item: self.selected_item,
+            selected_row: self.table_state.selected(),
+            scroll: self.diagnostics_scroll,
+            zoom_level: self.zoom_level,
+            traffic_unit: self.traffic_unit.to_string().to_string(),
+            filter: self.search.query.clone(),
+            interfaces: self
+                .devices
+                .iter()
+                .map(|device| (device.name.clone(), device.stats.clone()))
+                .collect(),
+        }
+    }
 
-    // Initialize parallel data cache with real data immediately
-    {
-        let conns = state.connection_monitor.get_connections();
-        if let Ok(mut count) = state.parallel_data.connection_count.lock() {
-            *count = conns.len();
+    /// Reopen a saved [`DashboardSnapshot`]. A panel that is unknown falls back to the
+    /// Overview, one not in the tab bar is left alone, and out-of-range values are
+    /// brought back into range.
+    pub fn restore(&mut self, snapshot: DashboardSnapshot) {
+        let panel = DashboardPanel::from_name(&snapshot.panel).unwrap_or(DashboardPanel::Overview);
+        if let Some(index) = self.panels.iter().position(|p| *p == panel) {
+            self.select_panel(index);
+        }
+        self.search.query = snapshot.filter;
+        if self.search_matches().is_none() {
+            self.search = SearchBar::default();
         }
 
-        let sys_stats = state.safe_system_monitor.get_current_stats();
-        if let Ok(mut cpu) = state.parallel_data.system_cpu.lock() {
-            *cpu = sys_stats.cpu_usage_percent;
+        let rows = self.interface_row_count();
+        self.selected_item = snapshot.selected_item.min(rows.saturating_sub(1));
+        self.list_state.select(Some(self.selected_item));
+        self.table_state.select(snapshot.selected_row.or(Some(0)));
+        self.diagnostics_scroll = snapshot.scroll;
+        if snapshot.zoom_level.is_finite() {
+            self.zoom_level = snapshot.zoom_level.clamp(1.0, MAX_GRAPH_ZOOM);
         }
-        if let Ok(mut memory) = state.parallel_data.system_memory.lock() {
-            *memory = sys_stats.memory_usage_percent;
+        if let Some(unit) = TrafficUnit::from_string(&snapshot.traffic_unit) {
+            self.traffic_unit = unit;
         }
-        if let Ok(mut disk) = state.parallel_data.system_disk.lock() {
-            *disk = sys_stats
-                .disk_usage
-                .values()
-                .next()
-                .map(|d| d.usage_percent)
-                .unwrap_or(0.0);
+    }
+
+    /// Panel tab titles, the Alerts tab with a count of active alerts when there are any
+    fn tab_titles(&self) -> Vec<String> {
+        self.panels
+            .iter()
+            .map(|panel| match self.alert_history.active_count() {
+                count if *panel == DashboardPanel::Alerts && count > 0 => {
+                    format!("{} ({count})", panel.title())
+                }
+                _ => panel.title().to_string(),
+            })
+            .collect()
+    }
+
+    /// Clicks on a panel tab switch to it; the wheel moves the row cursor in the
+    /// Connections and Processes tables. Returns whether anything changed.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> bool {
+        // The kill prompt is about the selected row, so leave it where it is
+        if self.kill_prompt.is_some() {
+            return false;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(_) => {
+                tab_at(self.tab_bar, &self.tab_titles(), mouse.column, mouse.row)
+                    .is_some_and(|index| self.select_panel(index))
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let rows = match self.active_panel {
+                    // The listening view has no row cursor
+                    DashboardPanel::Connections if self.connections_listening => return false,
+                    DashboardPanel::Connections => {
+                        self.panel_connections().len().min(CONNECTION_LIST_ROWS)
+                    }
+                    DashboardPanel::Processes => self.process_rows(),
+                    _ => return false,
+                };
+                if rows == 0 {
+                    return false;
+                }
+                // Unlike j/k the wheel stops at either end instead of wrapping
+                let current = self.table_state.selected().unwrap_or(0).min(rows - 1);
+                let next = if mouse.kind == MouseEventKind::ScrollDown {
+                    (current + 1).min(rows - 1)
+                } else {
+                    current.saturating_sub(1)
+                };
+                self.table_state.select(Some(next));
+                next != current
+            }
+            _ => false,
         }
+    }
 
-        let proc_info = state.process_monitor.get_processes();
-        if let Ok(mut count) = state.parallel_data.process_count.lock() {
-            *count = proc_info.len();
+    /// Take over settings from a reloaded config. Each calculator keeps its samples and
+    /// only drops those outside a shorter averaging window.
+    pub fn apply_runtime_config(
+        &mut self,
+        config: &Config,
+        stats_calculators: &mut HashMap<String, StatsCalculator>,
+    ) {
+        self.traffic_unit = config.get_traffic_unit();
+        self.data_unit = config.get_data_unit();
+        crate::units::set_unit_base(config.get_unit_base());
+        self.max_incoming = config.max_incoming;
+        self.max_outgoing = config.max_outgoing;
+        self.interface_bar_max = interface_bar_max(&self.devices, config);
+        self.log_scale = config.log_scale;
+        self.config = Some(Arc::new(config.clone()));
+        if config.resolve_hostnames {
+            self.connection_monitor.enable_reverse_dns();
+        } else {
+            self.connection_monitor.disable_reverse_dns();
         }
 
-        let diag_info = state.active_diagnostics.get_diagnostics();
-        if let Ok(mut count) = state.parallel_data.diagnostic_count.lock() {
-            *count = diag_info.ping_results.len()
-                + diag_info.port_scan_results.len()
-                + diag_info.dns_results.len();
+        let now = std::time::SystemTime::now();
+        for (name, calculator) in stats_calculators.iter_mut() {
+            let window = Duration::from_secs(config.for_interface(name).average_window as u64);
+            if calculator.window_size() != window {
+                calculator.rewindow(window, now);
+            }
+            calculator.set_ema_alpha(config.ema_alpha);
         }
 
-        if let Ok(mut update_time) = state.parallel_data.last_update.lock() {
-            *update_time = Instant::now();
+        let window = Duration::from_secs(config.average_window as u64);
+        match (config.show_total_device, self.total_device.as_mut()) {
+            (true, Some(total)) => {
+                let calculator = total.calculator_mut();
+                if calculator.window_size() != window {
+                    calculator.rewindow(window, now);
+                }
+                calculator.set_ema_alpha(config.ema_alpha);
+            }
+            (true, None) => {
+                let mut total = TotalDevice::new(window);
+                total.calculator_mut().set_ema_alpha(config.ema_alpha);
+                self.total_device = Some(total);
+            }
+            (false, _) => {
+                self.total_device = None;
+                self.graph_total = false;
+            }
         }
     }
 
-    loop {
-        // Handle input events with faster polling for better responsiveness
-        // Scale event polling based on refresh rate for better performance
-        let poll_interval = (config.refresh_interval / 10).clamp(50, 100);
-        if event::poll(Duration::from_millis(poll_interval))? {
-            if let Event::Key(key) = event::read()? {
-                let input_event = InputEvent::from_key_event(key);
+    pub fn selected_setting(&self) -> SettingField {
+        SettingField::ALL[self.settings_index % SettingField::ALL.len()]
+    }
 
-                // Log all key events for debugging
-                let debug_msg = format!(
-                    "Key: {:?}, Modifiers: {:?}, Event: {:?}\n",
-                    key.code, key.modifiers, input_event
-                );
-                if let Ok(mut file) = OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open("/tmp/netwatch_debug.log")
-                {
-                    let _ = file.write_all(debug_msg.as_bytes());
-                }
+    /// The running config with the units as currently shown, which u and U change
+    /// without touching the config
+    pub fn editable_config(&self, config: &Config) -> Config {
+        let mut config = config.clone();
+        config.traffic_format = self.traffic_unit.to_string().to_string();
+        config.data_format = self.data_unit.to_string().to_string();
+        config
+    }
 
-                match input_event {
-                    InputEvent::Quit => break,
-                    InputEvent::NextPanel => {
-                        // Always navigate - trust user input
-                        if state.next_panel() {
-                            // Force immediate redraw for navigation
-                            needs_redraw = true;
-                        }
-                    }
-                    InputEvent::PrevPanel => {
-                        // Only proceed if navigation actually occurred
-                        if state.prev_panel() {
-                            // Force immediate redraw for navigation
-                            needs_redraw = true;
+    /// Graph scale for `device`: its `[Interfaces.NAME]` values, else the global ones
+    pub fn bar_max(&self, device: &str) -> (u64, u64) {
+        self.interface_bar_max
+            .get(device)
+            .copied()
+            .unwrap_or((self.max_incoming, self.max_outgoing))
+    }
 
-                            // Minimal delay to allow screen refresh
-                            std::thread::sleep(Duration::from_millis(10));
-                        }
-                    }
-                    InputEvent::NextItem => {
-                        match state.active_panel {
-                            DashboardPanel::Interfaces => {
-                                state.next_item(state.devices.len());
-                                needs_redraw = true;
-                            }
-                            DashboardPanel::Graphs => {
-                                // Switch to next device in graphs panel
-                                if !state.devices.is_empty() {
-                                    state.current_device_index =
-                                        (state.current_device_index + 1) % state.devices.len();
-                                    needs_redraw = true;
-                                }
-                            }
-                            _ => {}
+    /// Processes listed in the Processes panel, in display order
+    pub fn listed_processes(&self) -> Vec<&ProcessNetworkInfo> {
+        let filtering = !self.search.query.is_empty();
+        self.process_monitor
+            .get_top_network_processes(if filtering {
+                usize::MAX
+            } else {
+                PROCESS_LIST_ROWS
+            })
+            .into_iter()
+            .filter(|proc| !proc.name.is_empty() || proc.cmdline.is_some())
+            .filter(|proc| {
+                self.search.matches(&format!(
+                    "{} {} {} {}",
+                    proc.pid,
+                    proc.name,
+                    proc.cmdline.as_deref().unwrap_or_default(),
+                    proc.container.as_deref().unwrap_or_default()
+                ))
+            })
+            .take(PROCESS_LIST_ROWS)
+            .collect()
+    }
+
+    /// Containers listed in the Processes panel when grouped, in display order
+    pub fn listed_containers(&self) -> Vec<ContainerNetworkInfo> {
+        let mut containers = self.process_monitor.get_container_totals();
+        containers.retain(|container| self.search.matches(&container.container));
+        containers.truncate(PROCESS_LIST_ROWS);
+        containers
+    }
+
+    /// Connections in the Connections panel that pass the `/` filter
+    pub fn listed_connections(&self) -> Vec<&NetworkConnection> {
+        self.panel_connections()
+            .iter()
+            .filter(|conn| {
+                self.search.matches(&format!(
+                    "{} {} {} {} {} {}",
+                    conn.protocol.as_str(),
+                    conn.local_addr,
+                    conn.remote_addr,
+                    conn.state.as_str(),
+                    conn.process_name.as_deref().unwrap_or_default(),
+                    conn.pid.map(|pid| pid.to_string()).unwrap_or_default()
+                ))
+            })
+            .collect()
+    }
+
+    /// Listening sockets in the Connections panel (o) that pass the `/` filter
+    pub fn listed_listening_sockets(&self) -> Vec<ListeningSocket> {
+        let mut sockets = self.connection_monitor.get_listening_sockets();
+        sockets.retain(|socket| {
+            self.search.matches(&format!(
+                "{} {} {} {} {}",
+                socket.protocol.as_str(),
+                socket.local_addr,
+                socket.exposure.as_str(),
+                socket.process_name.as_deref().unwrap_or_default(),
+                socket.pid.map(|pid| pid.to_string()).unwrap_or_default()
+            ))
+        });
+        sockets
+    }
+
+    /// Neighbors panel entries that pass the `/` filter
+    pub fn listed_neighbors(&self) -> Vec<&ArpEntry> {
+        self.neighbors
+            .iter()
+            .flatten()
+            .filter(|entry| {
+                self.search.matches(&format!(
+                    "{} {} {} {} {}",
+                    entry.ip,
+                    entry.mac,
+                    entry.mac.vendor_prefix().unwrap_or_default(),
+                    entry.interface,
+                    entry.state.label()
+                ))
+            })
+            .collect()
+    }
+
+    /// Alert history events that pass the `/` filter, newest first
+    pub fn listed_alert_events(&self) -> Vec<&AlertEvent> {
+        self.alert_history
+            .events()
+            .iter()
+            .rev()
+            .filter(|event| {
+                self.search.matches(&format!(
+                    "{} {} {}",
+                    event.level.as_str(),
+                    event.message,
+                    event.interface.as_deref().unwrap_or_default()
+                ))
+            })
+            .collect()
+    }
+
+    /// Rows the `/` filter leaves in the active panel, or None where it doesn't apply
+    pub fn search_matches(&self) -> Option<usize> {
+        match self.active_panel {
+            DashboardPanel::Interfaces => Some(self.interface_row_count()),
+            DashboardPanel::Connections if self.connections_listening => {
+                Some(self.listed_listening_sockets().len())
+            }
+            DashboardPanel::Connections => Some(self.listed_connections().len()),
+            DashboardPanel::Processes if self.process_drilldown.is_some() => None,
+            DashboardPanel::Processes => Some(self.process_rows()),
+            DashboardPanel::Neighbors => Some(self.listed_neighbors().len()),
+            DashboardPanel::Alerts if self.alerts_show_history => {
+                Some(self.listed_alert_events().len())
+            }
+            _ => None,
+        }
+    }
+
+    /// The filter changed under the cursor: start over from the first row
+    fn reset_list_position(&mut self) {
+        self.selected_item = 0;
+        self.list_state.select(Some(0));
+        self.table_state.select(Some(0));
+        self.alert_history_scroll = 0;
+    }
+
+    /// Connections the Connections panel lists: all of them, or only the `--pid`
+    /// process's
+    pub fn panel_connections(&self) -> &[NetworkConnection] {
+        match &self.process_focus {
+            Some(focus) => &focus.connections,
+            None => self.connection_monitor.get_connections(),
+        }
+    }
+
+    pub fn panel_connection_stats(&self) -> ConnectionStats {
+        match &self.process_focus {
+            Some(focus) => ConnectionStats::from_connections(&focus.connections),
+            None => self.connection_monitor.get_connection_stats(),
+        }
+    }
+
+    /// `--pid`: put the Processes panel cursor on the watched process the first time
+    /// it is listed
+    fn select_focused_process(&mut self) {
+        let Some(focus) = &self.process_focus else {
+            return;
+        };
+        if focus.row_selected || self.process_drilldown.is_some() || self.processes_by_container {
+            return;
+        }
+        let pid = focus.pid;
+        let Some(row) = self
+            .listed_processes()
+            .iter()
+            .position(|process| process.pid == pid)
+        else {
+            return;
+        };
+        self.table_state.select(Some(row));
+        if let Some(focus) = self.process_focus.as_mut() {
+            focus.row_selected = true;
+        }
+    }
+
+    /// `--pid`: notice the watched process exiting. True only the first time it is
+    /// found gone.
+    pub fn check_process_focus(&mut self) -> bool {
+        let Some(focus) = self.process_focus.as_mut() else {
+            return false;
+        };
+        if focus.exited || crate::processes::is_running(focus.pid) {
+            return false;
+        }
+        focus.exited = true;
+        true
+    }
+
+    /// Connections of the process opened with Enter, in Connections panel order
+    pub fn drilldown_connections(&self) -> Vec<&NetworkConnection> {
+        let Some(drilldown) = &self.process_drilldown else {
+            return Vec::new();
+        };
+        self.process_monitor
+            .connections_of(drilldown.pid, self.connection_monitor.get_connections())
+    }
+
+    /// Enter in the Processes panel: list the selected process's connections
+    fn open_drilldown(&mut self) {
+        let Some((pid, name)) = self
+            .selected_process()
+            .map(|process| (process.pid, process.name.clone()))
+        else {
+            self.set_status("No process selected");
+            return;
+        };
+        self.process_drilldown = Some(ProcessDrilldown {
+            pid,
+            name,
+            return_row: self.table_state.selected().unwrap_or(0),
+        });
+        self.table_state.select(Some(0));
+    }
+
+    /// Esc: back to the process list, on the row the drill-down was opened from
+    fn close_drilldown(&mut self) {
+        if let Some(drilldown) = self.process_drilldown.take() {
+            self.table_state.select(Some(drilldown.return_row));
+        }
+    }
+
+    /// Rows in the Processes panel: processes, containers when grouped, or the
+    /// drilled-down process's connections
+    fn process_rows(&self) -> usize {
+        if self.process_drilldown.is_some() {
+            self.drilldown_connections().len().min(CONNECTION_LIST_ROWS)
+        } else if self.processes_by_container {
+            self.listed_containers().len()
+        } else {
+            self.listed_processes().len()
+        }
+    }
+
+    /// The process under the Processes panel's row cursor, or the one drilled into. The
+    /// list refreshes under the cursor, so a selection past its end lands on the last row.
+    pub fn selected_process(&self) -> Option<&ProcessNetworkInfo> {
+        if let Some(drilldown) = &self.process_drilldown {
+            return self.process_monitor.get_process(drilldown.pid);
+        }
+        let processes = self.listed_processes();
+        let index = self
+            .table_state
+            .selected()
+            .unwrap_or(0)
+            .min(processes.len().checked_sub(1)?);
+        processes.into_iter().nth(index)
+    }
+
+    /// Move the table row cursor, wrapping around `row_count` rows
+    /// Rows in the Neighbors panel's table
+    pub fn neighbor_rows(&self) -> usize {
+        self.listed_neighbors().len()
+    }
+
+    pub fn step_table_row(&mut self, row_count: usize, forward: bool) {
+        if row_count == 0 {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0).min(row_count - 1);
+        let next = if forward {
+            (current + 1) % row_count
+        } else {
+            current.checked_sub(1).unwrap_or(row_count - 1)
+        };
+        self.table_state.select(Some(next));
+    }
+
+    /// Index range of the visible points for a graph holding `len` of them
+    pub fn graph_window(&self, len: usize) -> (usize, usize) {
+        stats::graph_window(len, self.zoom_level, self.graph_x_offset)
+    }
+
+    /// Halve or double the visible time span, keeping the middle of the view in place
+    pub fn zoom_graph(&mut self, len: usize, zoom_in: bool) {
+        let visible = stats::visible_graph_points(len, self.zoom_level);
+        let offset = self
+            .graph_x_offset
+            .min(stats::max_graph_offset(len, self.zoom_level));
+        let center = offset + visible / 2;
+
+        self.zoom_level = if zoom_in {
+            (self.zoom_level * 2.0).min(MAX_GRAPH_ZOOM)
+        } else {
+            (self.zoom_level / 2.0).max(1.0)
+        };
+
+        let visible = stats::visible_graph_points(len, self.zoom_level);
+        self.graph_x_offset = center
+            .saturating_sub(visible / 2)
+            .min(stats::max_graph_offset(len, self.zoom_level));
+    }
+
+    /// Pan the zoomed graph a quarter of its span back in time, or forward toward now
+    pub fn pan_graph(&mut self, len: usize, back: bool) {
+        let step = (stats::visible_graph_points(len, self.zoom_level) / 4).max(1);
+        let max_offset = stats::max_graph_offset(len, self.zoom_level);
+        let offset = self.graph_x_offset.min(max_offset);
+        self.graph_x_offset = if back {
+            (offset + step).min(max_offset)
+        } else {
+            offset.saturating_sub(step)
+        };
+    }
+
+    /// Indices of the devices in the side-by-side view: the group of up to
+    /// [`MAX_GRAPH_COLUMNS`] that holds the focused (current) device
+    pub fn graph_columns(&self) -> std::ops::Range<usize> {
+        let start = self.current_device_index / MAX_GRAPH_COLUMNS * MAX_GRAPH_COLUMNS;
+        start..(start + MAX_GRAPH_COLUMNS).min(self.devices.len())
+    }
+
+    /// The device shown in the Graphs panel and its calculator, if it has one yet
+    fn graphed_device<'a>(
+        &'a self,
+        stats_calculators: &'a HashMap<String, StatsCalculator>,
+    ) -> Option<(&'a Device, Option<&'a StatsCalculator>)> {
+        match &self.total_device {
+            Some(total) if self.graph_total => Some((&total.device, Some(total.calculator()))),
+            _ => self
+                .devices
+                .get(self.current_device_index)
+                .map(|device| (device, stats_calculators.get(&device.name))),
+        }
+    }
+
+    /// Graph points held for the device shown in the Graphs panel
+    fn current_graph_len(&self, stats_calculators: &HashMap<String, StatsCalculator>) -> usize {
+        self.graphed_device(stats_calculators)
+            .and_then(|(_, calculator)| calculator)
+            .map_or(0, StatsCalculator::graph_len)
+    }
+
+    /// ↓/↑ in the Graphs panel: the next or previous device, the total device (when
+    /// there is one) coming before the first interface
+    pub fn step_graph_device(&mut self, forward: bool) {
+        let len = self.devices.len();
+        if len == 0 {
+            return;
+        }
+        let has_total = self.total_device.is_some();
+        match (forward, self.graph_total) {
+            (true, true) => {
+                self.graph_total = false;
+                self.current_device_index = 0;
+            }
+            (true, false) if has_total && self.current_device_index + 1 >= len => {
+                self.graph_total = true;
+            }
+            (true, false) => self.current_device_index = (self.current_device_index + 1) % len,
+            (false, true) => {
+                self.graph_total = false;
+                self.current_device_index = len - 1;
+            }
+            (false, false) if has_total && self.current_device_index == 0 => {
+                self.graph_total = true;
+            }
+            (false, false) => {
+                self.current_device_index =
+                    self.current_device_index.checked_sub(1).unwrap_or(len - 1)
+            }
+        }
+    }
+
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), Instant::now()));
+    }
+
+    /// The footer status message, until it expires
+    pub fn current_status(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < STATUS_MESSAGE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Open the confirmation prompt for the selected process: SIGTERM first, SIGKILL
+    /// when it was already sent SIGTERM
+    fn prompt_kill_selected(&mut self) {
+        if self.processes_by_container {
+            self.set_status("Press g to list processes, then select one to kill");
+            return;
+        }
+        let Some((pid, name)) = self
+            .selected_process()
+            .map(|process| (process.pid, process.name.clone()))
+        else {
+            self.set_status("No process selected");
+            return;
+        };
+        if pid <= 1 {
+            self.set_status(format!("Refusing to signal PID {pid}"));
+            return;
+        }
+        self.kill_prompt = Some(KillPrompt {
+            pid,
+            name,
+            force: self.terminated_pid == Some(pid),
+        });
+    }
+
+    /// Send the prompted signal and report the outcome in the footer
+    fn confirm_kill(&mut self, prompt: KillPrompt) {
+        let signal = if prompt.force { "SIGKILL" } else { "SIGTERM" };
+        match crate::processes::signal_process(prompt.pid, prompt.force) {
+            Ok(()) if prompt.force => {
+                self.terminated_pid = None;
+                self.set_status(format!("Sent {signal} to {} ({})", prompt.pid, prompt.name));
+            }
+            Ok(()) => {
+                self.terminated_pid = Some(prompt.pid);
+                self.set_status(format!(
+                    "Sent {signal} to {} ({}); K again to SIGKILL",
+                    prompt.pid, prompt.name
+                ));
+            }
+            Err(e) => self.set_status(format!(
+                "Failed to send {signal} to {} ({}): {e}",
+                prompt.pid, prompt.name
+            )),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_dashboard(
+    interfaces: Vec<String>,
+    reader: Box<dyn NetworkReader>,
+    mut config: Config,
+    mut recorder: Option<Recorder>,
+    source: ConnectionSource,
+    debug_log: DebugLogger,
+    process_focus: Option<ProcessFocus>,
+    duration: Option<Duration>,
+) -> Result<()> {
+    let backend = CrosstermBackend::new(std::io::stdout());
+    let mut terminal = Terminal::new(backend)?;
+
+    // Interfaces the reader pushes samples for are read from those, the rest are polled
+    #[cfg(feature = "push-reader")]
+    let reader: Box<dyn NetworkReader> =
+        Box::new(crate::device::SubscribedReader::new(reader, &interfaces));
+
+    let mut state = DashboardState::new(interfaces, &config)?;
+    state.debug_log = debug_log;
+    state.process_focus = process_focus;
+    let log_file = config.log_file.clone();
+    state.config = Some(Arc::new(config.clone()));
+    // Only this machine's own traffic may overwrite its saved history and session
+    let persist_state = matches!(source, ConnectionSource::Local);
+    state.connection_monitor.set_source(source);
+    // Quota totals are this machine's, like the saved history
+    if persist_state {
+        state.quota_limits = QuotaLimits::from_config(&config);
+    }
+    let mut stats_calculators: HashMap<String, StatsCalculator> = HashMap::new();
+    let mut logger = if log_file.is_some() {
+        Some(TrafficLogger::new(log_file, config.log_compress)?)
+    } else {
+        None
+    };
+    let mut influx = config
+        .influx_url
+        .as_deref()
+        .map(InfluxExporter::new)
+        .transpose()?;
+    let mut webhook = config
+        .webhook_url
+        .as_deref()
+        .map(WebhookNotifier::new)
+        .transpose()?;
+    let mut critical_notifier = config.notify_on_critical.then(CriticalNotifier::default);
+
+    // Initialize stats calculators for each device
+    for device in &state.devices {
+        let average_window = config.for_interface(&device.name).average_window;
+        stats_calculators.insert(
+            device.name.clone(),
+            StatsCalculator::new(Duration::from_secs(average_window as u64)),
+        );
+    }
+
+    if persist_state && config.restore_history {
+        session::restore_history(&mut stats_calculators);
+    }
+
+    if persist_state && config.continue_session {
+        match SessionState::load() {
+            Ok(Some(session)) => session.seed(&mut stats_calculators),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Failed to load saved session: {e}"),
+        }
+    }
+    // --panel or Panel says where to open; the rest of the snapshot still applies
+    if persist_state && config.restore_session {
+        match DashboardSnapshot::load() {
+            Ok(Some(mut snapshot)) => {
+                if config.panel.is_some() {
+                    snapshot.panel = state.active_panel.name().to_string();
+                }
+                state.restore(snapshot);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Failed to load saved dashboard session: {e}"),
+        }
+    }
+    // Restored windows may have been saved with another EmaAlpha
+    for calculator in stats_calculators.values_mut() {
+        calculator.set_ema_alpha(config.ema_alpha);
+    }
+    if let Some(total) = state.total_device.as_mut() {
+        total.calculator_mut().set_ema_alpha(config.ema_alpha);
+    }
+
+    // Score anomalies against the baseline from --learn-baseline when there is one
+    state
+        .network_intelligence
+        .set_baseline_sigma(config.baseline_sigma);
+    state
+        .network_intelligence
+        .set_port_scan_thresholds(PortScanThresholds {
+            ports: config.port_scan_ports,
+            window: Duration::from_secs(config.port_scan_window),
+        });
+    state
+        .network_intelligence
+        .set_burst_multiplier(config.burst_multiplier);
+    // A baseline to compare against is also the one anomalies are scored against
+    if let Some(ref path) = config.compare_baseline {
+        let baseline = Baseline::load_from(std::path::Path::new(path))
+            .map_err(|e| anyhow::anyhow!("Failed to load baseline {path}: {e}"))?;
+        state
+            .network_intelligence
+            .set_baseline(Some(baseline.clone()));
+        state.compare_baseline = Some(baseline);
+    } else {
+        match Baseline::load() {
+            Ok(baseline) => state.network_intelligence.set_baseline(baseline),
+            Err(e) => eprintln!("Warning: Failed to load traffic baseline: {e}"),
+        }
+    }
+    if let Some(ref geoip_db) = config.geoip_db {
+        state.network_intelligence.load_asn_database(geoip_db)?;
+    }
+
+    let session_save_interval = Duration::from_secs(60);
+    let mut last_session_save = Instant::now();
+    let mut last_quota_save = Instant::now();
+
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        if let Some(warning) = small_terminal_warning(width, height, state.compact_mode) {
+            state.set_status(warning);
+        }
+    }
+
+    let mut last_update = Instant::now();
+    let mut last_draw = Instant::now();
+    let mut last_focus_check = Instant::now();
+    // Recording failures are reported once per outage, not on every snapshot
+    let mut recording_failed = false;
+    let mut needs_redraw = true;
+    // Both follow config.refresh_interval, which can change at runtime with [ and ]
+    let mut refresh_interval = Duration::from_millis(config.refresh_interval);
+    let mut draw_interval = draw_interval_for(&config);
+
+    // Connections, processes, system stats and diagnostics are collected on worker
+    // threads; this loop only applies their latest snapshots
+    let mut collectors = Collectors::spawn(&state, &config);
+    // A replay's or remote host's interfaces are not this host's
+    let mut interface_tracker =
+        InterfaceTracker::start(&state, reader.as_ref(), &config, persist_state);
+
+    // `--duration`: a bounded capture quits like `q` would, saving and finishing logs
+    let deadline = duration.map(|duration| Instant::now() + duration);
+    loop {
+        // SIGTERM/SIGINT: leave through the normal path so history and the session are saved
+        if crate::shutdown::requested()
+            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            break;
+        }
+
+        // Handle input events with faster polling for better responsiveness
+        // Scale event polling based on refresh rate for better performance
+        let poll_interval = (config.refresh_interval / 10).clamp(50, 100);
+        if event::poll(Duration::from_millis(poll_interval))? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                if state.handle_mouse(mouse) {
+                    needs_redraw = true;
+                }
+            }
+            // Redraw a resized terminal at once rather than at the next throttled draw:
+            // ratatui sees the new size on draw, clears the screen and lays the panels out
+            // against the new area
+            if let Event::Resize(width, height) = event {
+                if let Some(warning) = small_terminal_warning(width, height, state.compact_mode) {
+                    state.set_status(warning);
+                }
+                needs_redraw = true;
+                state.navigation_redraw_needed = true;
+            }
+            if let Event::Key(key) = event {
+                let input_event = InputEvent::from_key_event(key);
+
+                state.debug_log.log(format_args!(
+                    "Key: {:?}, Modifiers: {:?}, Event: {:?}",
+                    key.code, key.modifiers, input_event
+                ));
+
+                match input_event {
+                    // The kill prompt takes the next key: y or Enter confirms, any other cancels
+                    _ if state.kill_prompt.is_some() => {
+                        if let Some(prompt) = state.kill_prompt.take() {
+                            if matches!(key.code, KeyCode::Char('y' | 'Y') | KeyCode::Enter) {
+                                state.confirm_kill(prompt);
+                            } else {
+                                state.set_status("Cancelled");
+                            }
+                        }
+                        needs_redraw = true;
+                    }
+                    // Typing a filter: characters and Backspace edit it, Enter keeps it, Esc
+                    // clears it. Ctrl+C still quits.
+                    _ if state.search.active && !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                state.search.query.push(c);
+                                state.reset_list_position();
+                            }
+                            KeyCode::Backspace => {
+                                state.search.query.pop();
+                                state.reset_list_position();
+                            }
+                            KeyCode::Enter => state.search.active = false,
+                            KeyCode::Esc => {
+                                state.search = SearchBar::default();
+                                state.reset_list_position();
+                            }
+                            _ => {}
+                        }
+                        needs_redraw = true;
+                    }
+                    // Typing a numeric setting: digits and Backspace edit, Enter applies, Esc cancels
+                    _ if state.settings_input.is_some() => {
+                        match key.code {
+                            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                                if let Some(input) = state.settings_input.as_mut() {
+                                    if input.len() < 9 {
+                                        input.push(digit);
+                                    }
+                                }
+                            }
+                            KeyCode::Backspace => {
+                                if let Some(input) = state.settings_input.as_mut() {
+                                    input.pop();
+                                }
+                            }
+                            KeyCode::Esc => {
+                                state.settings_input = None;
+                                state.set_status("Edit cancelled");
+                            }
+                            KeyCode::Enter => {
+                                let field = state.selected_setting();
+                                let input = state.settings_input.take().unwrap_or_default();
+                                let mut edited = state.editable_config(&config);
+                                match field.set(&mut edited, &input) {
+                                    Ok(()) => {
+                                        (refresh_interval, draw_interval) = apply_setting(
+                                            field,
+                                            edited,
+                                            &mut config,
+                                            &mut state,
+                                            &mut stats_calculators,
+                                            &mut collectors,
+                                        )
+                                        .unwrap_or((refresh_interval, draw_interval));
+                                    }
+                                    Err(message) => state.set_status(message),
+                                }
+                            }
+                            _ => {}
+                        }
+                        needs_redraw = true;
+                    }
+                    InputEvent::Quit
+                        if key.code == KeyCode::Esc && state.process_drilldown.is_some() =>
+                    {
+                        state.close_drilldown();
+                        needs_redraw = true;
+                    }
+                    InputEvent::Quit
+                        if key.code == KeyCode::Esc && !state.search.query.is_empty() =>
+                    {
+                        state.search = SearchBar::default();
+                        state.reset_list_position();
+                        needs_redraw = true;
+                    }
+                    InputEvent::Search if state.search_matches().is_some() => {
+                        state.search.active = true;
+                        needs_redraw = true;
+                    }
+                    InputEvent::Quit => break,
+                    InputEvent::NextPanel => {
+                        // Always navigate - trust user input
+                        let navigated = state.next_panel();
+                        if navigated {
+                            // Force immediate redraw for navigation
+                            needs_redraw = true;
+                        }
+                    }
+                    InputEvent::PrevPanel => {
+                        // Only proceed if navigation actually occurred
+                        let navigated = state.prev_panel();
+                        if navigated {
+                            // Force immediate redraw for navigation
+                            needs_redraw = true;
+
+                            // Minimal delay to allow screen refresh
+                            std::thread::sleep(Duration::from_millis(10));
+                        }
+                    }
+                    InputEvent::NextItem => {
+                        match state.active_panel {
+                            DashboardPanel::Interfaces => {
+                                state.next_item(state.interface_row_count());
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Processes => {
+                                state.step_table_row(state.process_rows(), true);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Diagnostics => {
+                                state.diagnostics_scroll =
+                                    state.diagnostics_scroll.saturating_add(1);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Neighbors => {
+                                state.step_table_row(state.neighbor_rows(), true);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Alerts if state.alerts_show_history => {
+                                let last = state.listed_alert_events().len().saturating_sub(1);
+                                state.alert_history_scroll =
+                                    (state.alert_history_scroll + 1).min(last);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Settings => {
+                                state.settings_index =
+                                    (state.settings_index + 1) % SettingField::ALL.len();
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Graphs if !state.devices.is_empty() => {
+                                // Switch to next device in graphs panel
+                                state.step_graph_device(true);
+                                needs_redraw = true;
+                            }
+                            _ => {}
                         }
                     }
                     InputEvent::PrevItem => {
                         match state.active_panel {
                             DashboardPanel::Interfaces => {
-                                state.prev_item(state.devices.len());
+                                state.prev_item(state.interface_row_count());
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Processes => {
+                                state.step_table_row(state.process_rows(), false);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Diagnostics => {
+                                state.diagnostics_scroll =
+                                    state.diagnostics_scroll.saturating_sub(1);
                                 needs_redraw = true;
                             }
-                            DashboardPanel::Graphs => {
+                            DashboardPanel::Neighbors => {
+                                state.step_table_row(state.neighbor_rows(), false);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Alerts if state.alerts_show_history => {
+                                state.alert_history_scroll =
+                                    state.alert_history_scroll.saturating_sub(1);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Settings => {
+                                state.settings_index = state
+                                    .settings_index
+                                    .checked_sub(1)
+                                    .unwrap_or(SettingField::ALL.len() - 1);
+                                needs_redraw = true;
+                            }
+                            DashboardPanel::Graphs if !state.devices.is_empty() => {
                                 // Switch to previous device in graphs panel
-                                if !state.devices.is_empty() {
-                                    state.current_device_index = if state.current_device_index == 0
-                                    {
-                                        state.devices.len() - 1
-                                    } else {
-                                        state.current_device_index - 1
-                                    };
-                                    needs_redraw = true;
-                                }
+                                state.step_graph_device(false);
+                                needs_redraw = true;
                             }
                             _ => {}
                         }
                     }
+                    InputEvent::NextDevice | InputEvent::PrevDevice
+                        if state.active_panel == DashboardPanel::Graphs =>
+                    {
+                        let len = state.current_graph_len(&stats_calculators);
+                        state.pan_graph(len, input_event == InputEvent::PrevDevice);
+                        needs_redraw = true;
+                    }
+                    InputEvent::NextDevice | InputEvent::PrevDevice
+                        if state.active_panel == DashboardPanel::Diagnostics =>
+                    {
+                        let targets = state
+                            .active_diagnostics
+                            .get_diagnostics()
+                            .traceroute_results
+                            .len();
+                        if targets > 0 {
+                            let current = state.trace_target_index.min(targets - 1);
+                            state.trace_target_index = if input_event == InputEvent::NextDevice {
+                                (current + 1) % targets
+                            } else {
+                                (current + targets - 1) % targets
+                            };
+                            state.diagnostics_scroll = 0;
+                        }
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleGraphsView
+                        if state.active_panel == DashboardPanel::Graphs =>
+                    {
+                        state.graphs_multi_view = !state.graphs_multi_view;
+                        needs_redraw = true;
+                    }
+                    // Enter on the focused interface opens it on its own
+                    InputEvent::ToggleMultiple
+                        if state.active_panel == DashboardPanel::Graphs
+                            && state.graphs_multi_view =>
+                    {
+                        state.graphs_multi_view = false;
+                        needs_redraw = true;
+                    }
+                    InputEvent::NextDevice
+                    | InputEvent::PrevDevice
+                    | InputEvent::ToggleMultiple
+                        if state.active_panel == DashboardPanel::Settings =>
+                    {
+                        let field = state.selected_setting();
+                        if input_event == InputEvent::ToggleMultiple && field.is_numeric() {
+                            // Enter starts typing from the current value
+                            state.settings_input = Some(field.input(&config));
+                        } else {
+                            let mut edited = state.editable_config(&config);
+                            field.step(&mut edited, input_event != InputEvent::PrevDevice);
+                            (refresh_interval, draw_interval) = apply_setting(
+                                field,
+                                edited,
+                                &mut config,
+                                &mut state,
+                                &mut stats_calculators,
+                                &mut collectors,
+                            )
+                            .unwrap_or((refresh_interval, draw_interval));
+                        }
+                        needs_redraw = true;
+                    }
                     InputEvent::NextDevice => {
                         state.current_device_index =
                             (state.current_device_index + 1) % state.devices.len();
                         needs_redraw = true;
                     }
+                    InputEvent::PrevDevice
+                        if key.code == KeyCode::Char('h')
+                            && state.active_panel == DashboardPanel::Alerts =>
+                    {
+                        state.alerts_show_history = !state.alerts_show_history;
+                        state.alert_history_scroll = 0;
+                        needs_redraw = true;
+                    }
                     InputEvent::PrevDevice => {
                         state.current_device_index = if state.current_device_index == 0 {
                             state.devices.len() - 1
@@ -566,18 +2037,51 @@ pub fn run_dashboard(
                         needs_redraw = true;
                     }
                     InputEvent::SaveSettings => {
-                        config.save().ok();
+                        // Units and the log scale can be changed by key without touching config
+                        config = state.editable_config(&config);
+                        config.log_scale = state.log_scale;
+                        state.config = Some(Arc::new(config.clone()));
+                        match config.save() {
+                            Ok(()) => state.set_status("Settings saved to ~/.netwatch"),
+                            Err(e) => state.set_status(format!("Save failed: {e}")),
+                        }
+                        needs_redraw = true;
                     }
                     InputEvent::ReloadSettings => {
-                        config = Config::load().unwrap_or_default();
+                        // A broken file keeps the running settings; the error stays on screen
+                        match Config::reload().and_then(|(reloaded, warnings)| {
+                            reloaded.validate()?;
+                            Ok((reloaded, warnings))
+                        }) {
+                            Ok((reloaded, warnings)) => {
+                                (refresh_interval, draw_interval) = switch_config(
+                                    reloaded,
+                                    &mut config,
+                                    &mut state,
+                                    &mut stats_calculators,
+                                    &mut collectors,
+                                );
+                                state.set_status(match warnings.first() {
+                                    Some(warning) => format!("config reloaded; {warning}"),
+                                    None => "config reloaded".to_string(),
+                                });
+                            }
+                            Err(e) => {
+                                state.set_status(format!(
+                                    "Config reload failed: {}",
+                                    one_line(&e.to_string())
+                                ));
+                            }
+                        }
+                        needs_redraw = true;
                     }
                     InputEvent::Reset => {
-                        // Reset all stats calculators
+                        // Reset all stats calculators, each keeping its window
                         for calculator in stats_calculators.values_mut() {
-                            *calculator = StatsCalculator::new(Duration::from_secs(
-                                config.average_window as u64,
-                            ));
+                            *calculator = StatsCalculator::new(calculator.window_size());
+                            calculator.set_ema_alpha(config.ema_alpha);
                         }
+                        state.graph_x_offset = 0;
                     }
                     InputEvent::ToggleTrafficUnits => {
                         state.traffic_unit = match state.traffic_unit {
@@ -594,12 +2098,60 @@ pub fn run_dashboard(
                         };
                         needs_redraw = true;
                     }
-                    InputEvent::ZoomIn => {
-                        state.zoom_level = (state.zoom_level * 1.5).min(10.0);
+                    InputEvent::ZoomIn | InputEvent::ZoomOut
+                        if state.active_panel == DashboardPanel::Graphs =>
+                    {
+                        let len = state.current_graph_len(&stats_calculators);
+                        state.zoom_graph(len, input_event == InputEvent::ZoomIn);
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleLogScale if state.active_panel == DashboardPanel::Graphs => {
+                        state.log_scale = !state.log_scale;
                         needs_redraw = true;
                     }
-                    InputEvent::ZoomOut => {
-                        state.zoom_level = (state.zoom_level / 1.5).max(0.1);
+                    InputEvent::ToggleCompact => {
+                        state.compact_mode = !state.compact_mode;
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleMultiple
+                        if state.active_panel == DashboardPanel::Interfaces =>
+                    {
+                        state.toggle_selected_collapsed();
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleMultiple
+                        if state.active_panel == DashboardPanel::Processes
+                            && state.process_drilldown.is_none()
+                            && !state.processes_by_container =>
+                    {
+                        state.open_drilldown();
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleGraphs
+                        if state.active_panel == DashboardPanel::Processes
+                            && state.process_drilldown.is_none() =>
+                    {
+                        state.processes_by_container = !state.processes_by_container;
+                        state.table_state.select(Some(0));
+                        needs_redraw = true;
+                    }
+                    InputEvent::ToggleListening
+                        if state.active_panel == DashboardPanel::Connections =>
+                    {
+                        state.connections_listening = !state.connections_listening;
+                        needs_redraw = true;
+                    }
+                    InputEvent::KillProcess if state.active_panel == DashboardPanel::Processes => {
+                        state.prompt_kill_selected();
+                        needs_redraw = true;
+                    }
+                    InputEvent::DecreaseInterval | InputEvent::IncreaseInterval => {
+                        let slower = input_event == InputEvent::IncreaseInterval;
+                        config.refresh_interval =
+                            step_refresh_interval(config.refresh_interval, slower);
+                        refresh_interval = Duration::from_millis(config.refresh_interval);
+                        draw_interval = draw_interval_for(&config);
+                        state.config = Some(Arc::new(config.clone()));
                         needs_redraw = true;
                     }
                     _ => {}
@@ -607,112 +2159,108 @@ pub fn run_dashboard(
             }
         }
 
-        // Update data based on active panel to reduce CPU usage
-        if !state.paused {
-            // Update parallel data collection if needed
-            let should_update = state.parallel_data.should_update();
-            if should_update {
-                // Extract data collection logic directly here to avoid borrowing issues
-                let conns = state.connection_monitor.get_connections();
-                if let Ok(mut count) = state.parallel_data.connection_count.lock() {
-                    *count = conns.len();
-                }
-
-                let sys_stats = state.safe_system_monitor.get_current_stats();
-                if let Ok(mut cpu) = state.parallel_data.system_cpu.lock() {
-                    *cpu = sys_stats.cpu_usage_percent;
-                }
-                if let Ok(mut memory) = state.parallel_data.system_memory.lock() {
-                    *memory = sys_stats.memory_usage_percent;
-                }
-                if let Ok(mut disk) = state.parallel_data.system_disk.lock() {
-                    *disk = sys_stats
-                        .disk_usage
-                        .values()
-                        .next()
-                        .map(|d| d.usage_percent)
-                        .unwrap_or(0.0);
-                }
-
-                let proc_info = state.process_monitor.get_processes();
-                if let Ok(mut count) = state.parallel_data.process_count.lock() {
-                    *count = proc_info.len();
-                }
-
-                let diag_info = state.active_diagnostics.get_diagnostics();
-                if let Ok(mut count) = state.parallel_data.diagnostic_count.lock() {
-                    *count = diag_info.ping_results.len()
-                        + diag_info.port_scan_results.len()
-                        + diag_info.dns_results.len();
+        // Swap in whatever the collectors have published; never waits on them. Paused
+        // workers stop collecting, and a snapshot still in flight is dropped so the
+        // frozen view doesn't change under the user.
+        collectors.set_demand(&state.active_panel, state.paused);
+        for snapshot in collectors.try_iter() {
+            if state.paused {
+                continue;
+            }
+            if let (Some(recorder), Snapshot::Connections(monitor)) = (&mut recorder, &snapshot) {
+                match recorder.record_connections(monitor.get_connections()) {
+                    Ok(()) => recording_failed = false,
+                    Err(e) => {
+                        if !recording_failed {
+                            state.set_status(format!("Recording connections failed: {e}"));
+                        }
+                        recording_failed = true;
+                    }
                 }
+            }
+            state.apply_snapshot(snapshot);
+            needs_redraw = true;
+        }
 
-                if let Ok(mut update_time) = state.parallel_data.last_update.lock() {
-                    *update_time = Instant::now();
+        if last_focus_check.elapsed() >= refresh_interval {
+            last_focus_check = Instant::now();
+            if state.check_process_focus() {
+                needs_redraw = true;
+                if state
+                    .process_focus
+                    .as_ref()
+                    .is_some_and(|focus| focus.exit_on_death)
+                {
+                    break;
                 }
             }
+        }
 
-            // Always update network stats as they're used in Overview and Interfaces panels
-            if (matches!(
-                state.active_panel,
-                DashboardPanel::Overview | DashboardPanel::Interfaces | DashboardPanel::Graphs
-            ) && last_update.elapsed() >= refresh_interval)
-            {
+        if interface_tracker.apply(&mut state, &mut stats_calculators, reader.as_ref(), &config) {
+            needs_redraw = true;
+        }
+
+        // Update data based on active panel to reduce CPU usage
+        if !state.paused {
+            // Interface counters are sampled every refresh whatever the panel, since the
+            // logger, recorder and exporters depend on them too
+            if last_update.elapsed() >= refresh_interval {
                 update_network_stats(
                     &mut state,
                     reader.as_ref(),
                     &mut stats_calculators,
                     &mut logger,
+                    &mut recorder,
                 )?;
-                last_update = Instant::now();
-                needs_redraw = true;
-            }
-
-            // Update connection monitor when Connections panel is active OR if we need overview data
-            // Force update on first visit to connections tab
-            let force_connection_update = matches!(state.active_panel, DashboardPanel::Connections)
-                && state.connection_monitor.get_connections().is_empty();
-
-            if (matches!(
-                state.active_panel,
-                DashboardPanel::Connections | DashboardPanel::Overview | DashboardPanel::Forensics
-            ) && (last_connection_update.elapsed() >= connection_update_interval
-                || force_connection_update))
-            {
-                if let Err(_e) = state.connection_monitor.update() {
-                    // Silently handle connection update failures
+                let conn_stats = state.connection_monitor.get_connection_stats();
+                if let Some(ref mut influx) = influx {
+                    influx.export(&stats_calculators, Some(&conn_stats));
+                    if let Some(status) = influx.take_status() {
+                        state.set_status(status);
+                    }
                 }
-                last_connection_update = Instant::now();
-                needs_redraw = true;
-            }
-
-            // Update active diagnostics when Diagnostics panel is active
-            let diagnostics_update_interval = Duration::from_secs(5); // Update diagnostics every 5 seconds
-            let force_diagnostics_update =
-                matches!(state.active_panel, DashboardPanel::Diagnostics)
-                    && state.last_active_diagnostics_update.is_none();
-
-            if (matches!(state.active_panel, DashboardPanel::Diagnostics)
-                && (state
-                    .last_active_diagnostics_update
-                    .map_or(true, |last| last.elapsed() >= diagnostics_update_interval)
-                    || force_diagnostics_update))
-            {
-                if let Err(_e) = state.active_diagnostics.update() {
-                    // Silently handle diagnostics update failures
+                // Alerts are evaluated whatever the panel so the webhook, the critical bell
+                // and the Alerts panel's history catch them while the user looks elsewhere
+                let mut current_alerts = alerts::evaluate(
+                    &stats_calculators,
+                    &conn_stats,
+                    config.time_wait_threshold,
+                    config.close_wait_threshold,
+                );
+                // Socket counts come from this machine, so a recording or --remote host
+                // goes without the orphan check
+                let orphan_tcp = state
+                    .system_stats
+                    .as_ref()
+                    .and_then(|stats| stats.socket_stats)
+                    .map(|sockets| sockets.orphan_tcp)
+                    .filter(|_| state.connection_monitor.is_local());
+                current_alerts.extend(alerts::evaluate_connections(
+                    &state.connection_monitor,
+                    orphan_tcp,
+                ));
+                current_alerts.extend(quota::evaluate(
+                    &state.quotas,
+                    &state.quota_limits,
+                    chrono::Utc::now(),
+                ));
+                if let Some(ref mut webhook) = webhook {
+                    webhook.notify(&current_alerts);
+                    if let Some(status) = webhook.take_status() {
+                        state.set_status(status);
+                    }
                 }
-                state.last_active_diagnostics_update = Some(Instant::now());
-                needs_redraw = true;
-            }
-
-            // Only update process monitor when Processes panel is active
-            // Overview panel now uses lightweight cached data instead
-            if (matches!(state.active_panel, DashboardPanel::Processes)
-                && last_process_update.elapsed() >= process_update_interval)
-            {
-                if let Err(e) = state.process_monitor.update() {
-                    eprintln!("Warning: Failed to update process monitor: {e}");
+                state.health = health::assess(
+                    state.connection_monitor.get_connections(),
+                    conn_stats.total,
+                    &state.devices,
+                );
+                if let Some(ref mut notifier) = critical_notifier {
+                    notifier.notify(&state.health, &current_alerts);
                 }
-                last_process_update = Instant::now();
+                state.alert_history.record(&current_alerts);
+                state.current_alerts = current_alerts;
+                last_update = Instant::now();
                 needs_redraw = true;
             }
 
@@ -737,27 +2285,386 @@ pub fn run_dashboard(
             state.navigation_redraw_needed = false; // Reset navigation redraw flag
         }
 
+        // Periodically persist the session so a crash loses at most a minute
+        if persist_state
+            && config.continue_session
+            && last_session_save.elapsed() >= session_save_interval
+        {
+            SessionState::capture(&stats_calculators).save().ok();
+            last_session_save = Instant::now();
+        }
+        if last_quota_save.elapsed() >= session_save_interval {
+            for tracker in state.quotas.values() {
+                tracker.save().ok();
+            }
+            last_quota_save = Instant::now();
+        }
+
         // Sleep briefly when no updates are needed to reduce CPU usage
         if !needs_redraw {
             std::thread::sleep(Duration::from_millis(10));
         }
     }
 
+    collectors.shutdown();
+
+    // A compressed log is unreadable past the last complete gzip member until finished
+    if let Some(logger) = logger {
+        if let Err(e) = logger.flush_and_sync() {
+            eprintln!("Warning: Failed to finish traffic log: {e}");
+        }
+    }
+
+    // Shutdown hook: persist state while the terminal is still ours (before the caller
+    // disables raw mode)
+    if persist_state {
+        if let Err(e) = session::save_history(&stats_calculators) {
+            eprintln!("Warning: Failed to save stats history: {e}");
+        }
+        if let Err(e) = state.snapshot().save() {
+            eprintln!("Warning: Failed to save dashboard session: {e}");
+        }
+    }
+
+    if persist_state && config.continue_session {
+        if let Err(e) = SessionState::capture(&stats_calculators).save() {
+            eprintln!("Warning: Failed to save session: {e}");
+        }
+    }
+
+    for tracker in state.quotas.values() {
+        if let Err(e) = tracker.save() {
+            eprintln!(
+                "Warning: Failed to save quota totals for {}: {e}",
+                tracker.interface()
+            );
+        }
+    }
+
+    if let Some(focus) = state.process_focus.filter(|focus| focus.has_exited()) {
+        // Said after leaving the alternate screen, so it stays visible
+        crate::shutdown::restore_terminal();
+        eprintln!("Process {} exited", focus.pid);
+    }
+
     Ok(())
 }
 
+/// Rows of panel content shown in compact mode (an 80x24 terminal minus header and footer)
+pub const COMPACT_PANEL_ROWS: u16 = 20;
+
+/// Smallest terminal the full-size panels are laid out for
+const MIN_TERMINAL_WIDTH: u16 = 80;
+const MIN_TERMINAL_HEIGHT: u16 = 20;
+
+/// Status line warning for a terminal too small for the panels; None when it fits.
+/// Small terminals still work, but full-size panels get cut off.
+fn small_terminal_warning(width: u16, height: u16, compact: bool) -> Option<String> {
+    if width >= MIN_TERMINAL_WIDTH && height >= MIN_TERMINAL_HEIGHT {
+        return None;
+    }
+    let hint = if compact { "" } else { " (c: compact mode)" };
+    Some(format!(
+        "Terminal is {width}x{height}, smaller than {MIN_TERMINAL_WIDTH}x{MIN_TERMINAL_HEIGHT}; panels may be truncated{hint}"
+    ))
+}
+
+/// Presets cycled through by [ and ] (ms)
+const REFRESH_INTERVAL_STEPS: [u64; 9] = [100, 200, 250, 500, 1000, 2000, 3000, 5000, 10_000];
+
+/// Next preset below (faster) or above (slower) `current`; unchanged at either end
+fn step_refresh_interval(current: u64, slower: bool) -> u64 {
+    let next = if slower {
+        REFRESH_INTERVAL_STEPS.iter().find(|&&step| step > current)
+    } else {
+        REFRESH_INTERVAL_STEPS
+            .iter()
+            .rev()
+            .find(|&&step| step < current)
+    };
+    next.copied().unwrap_or(current)
+}
+
+/// A multi-line error (TOML errors quote the offending line) squeezed into the footer:
+/// the first line, which says where, and the last, which says what
+fn one_line(message: &str) -> String {
+    let mut lines = message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let first = lines.next().unwrap_or_default();
+    match lines.next_back() {
+        Some(last) => format!("{first}: {last}"),
+        None => first.to_string(),
+    }
+}
+
+/// Keeps the device list in step with interfaces that come and go while the dashboard
+/// runs, and records the changes in the Alerts panel's history
+struct InterfaceTracker {
+    events: Arc<Mutex<VecDeque<InterfaceEvent>>>,
+    // Listening while this is alive; None where netlink is unavailable
+    _watcher: Option<InterfaceEventWatcher>,
+    // The list had every interface "all" takes at startup, so new ones join it too
+    follow_new: bool,
+    // Listed interfaces that were removed, to list again if they come back
+    departed: HashSet<String>,
+}
+
+impl InterfaceTracker {
+    fn start(
+        state: &DashboardState,
+        reader: &dyn NetworkReader,
+        config: &Config,
+        watch: bool,
+    ) -> Self {
+        let events = Arc::new(Mutex::new(VecDeque::new()));
+        let watcher = if watch {
+            InterfaceEventWatcher::spawn(Arc::clone(&events)).ok()
+        } else {
+            None
+        };
+        let filter = config.interface_filter().unwrap_or_default();
+        let follow_new = reader.list_devices().is_ok_and(|available| {
+            available.iter().all(|name| {
+                state.devices.iter().any(|device| &device.name == name)
+                    || filter.exclusion(name, reader).is_some()
+            })
+        });
+        Self {
+            events,
+            _watcher: watcher,
+            follow_new,
+            departed: HashSet::new(),
+        }
+    }
+
+    /// Apply the events queued since the last call; returns whether any changed the list
+    fn apply(
+        &mut self,
+        state: &mut DashboardState,
+        stats_calculators: &mut HashMap<String, StatsCalculator>,
+        reader: &dyn NetworkReader,
+        config: &Config,
+    ) -> bool {
+        let events: Vec<InterfaceEvent> = match self.events.lock() {
+            Ok(mut queue) => queue.drain(..).collect(),
+            Err(_) => return false,
+        };
+
+        let mut changed = false;
+        for event in events {
+            let listed = |name: &str| state.devices.iter().position(|d| d.name == name);
+            let (level, message, name) = match event {
+                InterfaceEvent::Up(name) => {
+                    let Some(index) = listed(&name) else { continue };
+                    state.devices[index].is_active = true;
+                    (AlertSeverity::Info, format!("{name} is up"), name)
+                }
+                InterfaceEvent::Down(name) => {
+                    let Some(index) = listed(&name) else { continue };
+                    state.devices[index].is_active = false;
+                    (AlertSeverity::Warning, format!("{name} went down"), name)
+                }
+                InterfaceEvent::Added(name) => {
+                    // A returning interface was listed before, filtered or not
+                    let returning = self.departed.remove(&name);
+                    let followed = self.follow_new
+                        && config
+                            .interface_filter()
+                            .is_ok_and(|filter| filter.exclusion(&name, reader).is_none());
+                    if listed(&name).is_some()
+                        || !(returning || followed)
+                        || crate::validation::validate_interface_name(&name).is_err()
+                    {
+                        continue;
+                    }
+                    let mut device = Device::new(name.clone());
+                    if config.docker_labels {
+                        device.label = crate::platform::docker::resolve_container_names(
+                            std::slice::from_ref(&name),
+                        )
+                        .pop()
+                        .flatten();
+                    }
+                    state.devices.push(device);
+                    let average_window = config.for_interface(&name).average_window;
+                    let mut calculator =
+                        StatsCalculator::new(Duration::from_secs(average_window as u64));
+                    calculator.set_ema_alpha(config.ema_alpha);
+                    stats_calculators.insert(name.clone(), calculator);
+                    (AlertSeverity::Info, format!("{name} appeared"), name)
+                }
+                InterfaceEvent::Removed(name) => {
+                    let Some(index) = listed(&name) else { continue };
+                    self.departed.insert(name.clone());
+                    // The panels assume at least one device, so the last one stays listed
+                    if state.devices.len() == 1 {
+                        state.devices[0].is_active = false;
+                    } else {
+                        state.devices.remove(index);
+                        stats_calculators.remove(&name);
+                        if state.current_device_index > index
+                            || state.current_device_index == state.devices.len()
+                        {
+                            state.current_device_index -= 1;
+                        }
+                    }
+                    (AlertSeverity::Warning, format!("{name} was removed"), name)
+                }
+            };
+            state.alert_history.push(AlertEvent {
+                timestamp: chrono::Local::now(),
+                level,
+                message,
+                interface: Some(name),
+            });
+            changed = true;
+        }
+
+        if changed {
+            state.interface_bar_max = interface_bar_max(&state.devices, config);
+        }
+        changed
+    }
+}
+
+/// Graph scales of the devices that have their own `[Interfaces.NAME]` table
+fn interface_bar_max(devices: &[Device], config: &Config) -> HashMap<String, (u64, u64)> {
+    devices
+        .iter()
+        .filter(|device| config.interfaces.contains_key(&device.name))
+        .map(|device| {
+            let settings = config.for_interface(&device.name);
+            (
+                device.name.clone(),
+                (settings.max_incoming, settings.max_outgoing),
+            )
+        })
+        .collect()
+}
+
+/// Make `new_config` the running config, as F6 and the Settings panel do: the dashboard
+/// state, then the sample and redraw intervals (returned), and the collectors when the
+/// settings they were started with changed
+fn switch_config(
+    new_config: Config,
+    config: &mut Config,
+    state: &mut DashboardState,
+    stats_calculators: &mut HashMap<String, StatsCalculator>,
+    collectors: &mut Collectors,
+) -> (Duration, Duration) {
+    let restart_collectors = new_config.high_performance != config.high_performance
+        || new_config.resolve_hostnames != config.resolve_hostnames;
+
+    *config = new_config;
+    state.apply_runtime_config(config, stats_calculators);
+    if restart_collectors {
+        // The old workers are joined as they drop
+        *collectors = Collectors::spawn(state, config);
+    }
+
+    (
+        Duration::from_millis(config.refresh_interval),
+        draw_interval_for(config),
+    )
+}
+
+/// Validate and switch to a config edited in the Settings panel, reporting the outcome
+/// in the footer. None leaves the running config and intervals as they were.
+fn apply_setting(
+    field: SettingField,
+    edited: Config,
+    config: &mut Config,
+    state: &mut DashboardState,
+    stats_calculators: &mut HashMap<String, StatsCalculator>,
+    collectors: &mut Collectors,
+) -> Option<(Duration, Duration)> {
+    if let Err(e) = edited.validate() {
+        state.set_status(one_line(&e.to_string()));
+        return None;
+    }
+
+    let intervals = switch_config(edited, config, state, stats_calculators, collectors);
+    state.set_status(format!(
+        "{}: {} (F5 to save)",
+        field.label(),
+        field.value(config)
+    ));
+    Some(intervals)
+}
+
+/// Redraw at least as often as new samples arrive so fast refresh rates show in the graphs
+fn draw_interval_for(config: &Config) -> Duration {
+    let base_multiplier = (config.refresh_interval as f64 / 1000.0).max(1.0);
+    let perf_multiplier = if config.high_performance { 2.0 } else { 1.0 };
+    Duration::from_millis(
+        ((200.0 * base_multiplier * perf_multiplier) as u64).min(config.refresh_interval),
+    )
+}
+
 fn update_network_stats(
     state: &mut DashboardState,
     reader: &dyn NetworkReader,
     stats_calculators: &mut HashMap<String, StatsCalculator>,
     logger: &mut Option<TrafficLogger>,
+    recorder: &mut Option<Recorder>,
 ) -> Result<()> {
+    let (connection_count, unique_remote_ips) =
+        connection_metrics(state.connection_monitor.get_connections());
+    // What the total device sums this tick: physical interfaces that could be read
+    let mut total_samples = Vec::new();
+
     for device in &mut state.devices {
+        device.refresh_info(reader);
+        device.refresh_wireless(reader);
         if let Ok(current_stats) = reader.read_stats(&device.name) {
             device.stats = current_stats.clone();
+            if device.info.physical != Some(false) {
+                total_samples.push((device.name.clone(), current_stats.clone()));
+            }
+
+            // Raw counters, before any processing, so a replay reproduces this run exactly
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record_interface(&device.name, &current_stats)?;
+            }
 
             if let Some(calculator) = stats_calculators.get_mut(&device.name) {
+                let spike_multiplier = state.config.as_ref().map_or(5.0, |config| {
+                    config.for_interface(&device.name).spike_multiplier
+                });
                 calculator.add_sample(current_stats);
+                calculator.update_spike_state(spike_multiplier);
+                if !state.quota_limits.is_empty() {
+                    state
+                        .quotas
+                        .entry(device.name.clone())
+                        .or_insert_with(|| {
+                            // Unreadable totals start over rather than stop the count
+                            QuotaTracker::load(&device.name)
+                                .unwrap_or_else(|_| QuotaTracker::new(&device.name))
+                        })
+                        .update(calculator);
+                }
+
+                let (speed_in, speed_out) = calculator.current_speed();
+                state.changes.record(
+                    &format!("{}/in", device.name),
+                    format_rate(speed_in, &state.traffic_unit),
+                );
+                state.changes.record(
+                    &format!("{}/out", device.name),
+                    format_rate(speed_out, &state.traffic_unit),
+                );
+
+                if calculator.sample_count() >= 2 {
+                    state.network_intelligence.observe_traffic(
+                        &device.name,
+                        speed_in + speed_out,
+                        connection_count,
+                        unique_remote_ips,
+                    );
+                }
 
                 // Log if logging is enabled
                 if let Some(ref mut log) = logger {
@@ -766,82 +2673,203 @@ fn update_network_stats(
             }
         }
     }
+    if let Some(total) = state.total_device.as_mut() {
+        total.add_samples(&total_samples);
+    }
+
+    // A new DHCP lease or a VPN reconnecting shows up in the Alerts panel and the log.
+    // The first read of an interface only records what it has.
+    if let Some(mut current) = reader.read_addresses() {
+        for device in &state.devices {
+            let addresses = current.remove(&device.name).unwrap_or_default();
+            let change = state
+                .interface_addresses
+                .get(&device.name)
+                .and_then(|previous| address_change(&device.name, previous, &addresses));
+            state
+                .interface_addresses
+                .insert(device.name.clone(), addresses);
+
+            if let Some((level, message)) = change {
+                if let Some(ref mut log) = logger {
+                    log.log_event(&device.name, &message)?;
+                }
+                state.alert_history.push(AlertEvent {
+                    timestamp: chrono::Local::now(),
+                    level,
+                    message,
+                    interface: Some(device.name.clone()),
+                });
+            }
+        }
+    }
+
+    // Only the interface whose details are on screen is asked for its peers. Peers and
+    // driver counters come from this machine, so they are skipped for a recording or a
+    // `--remote` host rather than showing a same-named local interface's.
+    let local = state.connection_monitor.is_local();
+    let selected = state
+        .selected_device()
+        .filter(|_| local)
+        .map(|index| state.devices[index].name.clone());
+    let selected = selected.as_ref();
+    state.wireguard.retain(|name, _| Some(name) == selected);
+    if let Some(name) = selected {
+        match wireguard::read_info(name) {
+            Some(info) => {
+                state.wireguard.insert(name.clone(), info);
+            }
+            None => {
+                state.wireguard.remove(name);
+            }
+        }
+    }
+    // Likewise for its driver counters, every HW_STATS_INTERVAL
+    if let Some(index) = state.selected_device().filter(|_| local) {
+        state.devices[index].refresh_hw_stats();
+    }
+
+    if let Some(recorder) = recorder.as_mut() {
+        recorder.flush()?;
+    }
 
     Ok(())
 }
 
+/// Alert for a change in an interface's addresses: a warning when one went away, info
+/// when one was only added
+fn address_change(
+    name: &str,
+    previous: &BTreeSet<IpAddr>,
+    current: &BTreeSet<IpAddr>,
+) -> Option<(AlertSeverity, String)> {
+    let join = |addresses: Vec<&IpAddr>| {
+        addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let removed: Vec<&IpAddr> = previous.difference(current).collect();
+    let added: Vec<&IpAddr> = current.difference(previous).collect();
+    match (removed.is_empty(), added.is_empty()) {
+        (true, true) => None,
+        (false, false) => Some((
+            AlertSeverity::Warning,
+            format!(
+                "{name} address changed: {} → {}",
+                join(removed),
+                join(added)
+            ),
+        )),
+        (false, true) => Some((
+            AlertSeverity::Warning,
+            format!("{name} lost {}", join(removed)),
+        )),
+        (true, false) => Some((
+            AlertSeverity::Info,
+            format!("{name} gained {}", join(added)),
+        )),
+    }
+}
+
 fn draw_dashboard(
     f: &mut Frame,
     state: &mut DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header with tabs
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer with help
-        ])
-        .split(f.area());
+    let chunks = if state.is_compact() {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),               // Panel indicator
+                Constraint::Max(COMPACT_PANEL_ROWS), // Main content
+                Constraint::Length(1),               // One-line footer
+            ])
+            .split(f.area())
+    } else {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header with tabs
+                Constraint::Min(0),    // Main content
+                Constraint::Length(3), // Footer with help
+            ])
+            .split(f.area())
+    };
 
     // Draw header with panel tabs
     draw_header(f, chunks[0], state);
+    state.tab_bar = if state.is_compact() {
+        Rect::default()
+    } else {
+        chunks[0]
+    };
 
     // Pre-extract system stats to avoid borrow conflicts
     let system_stats = if matches!(state.active_panel, DashboardPanel::System) {
-        Some(state.safe_system_monitor.get_current_stats())
+        state.system_stats.clone()
     } else {
         None
     };
 
-    // Debug logging for panel rendering (temporarily disabled for performance)
-    // let render_debug = format!("RENDER: panel_index={}, active_panel={:?}\n",
-    //                           state.panel_index, state.active_panel);
-    // if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/tmp/netwatch_render_debug.log") {
-    //     let _ = file.write_all(render_debug.as_bytes());
-    // }
+    // The `/` filter frames the panel, with its bar along the bottom
+    let area = match state.search_matches() {
+        Some(matches) if state.search.is_shown() => draw_search_bar(f, chunks[1], state, matches),
+        _ => chunks[1],
+    };
 
     // Draw main content based on active panel
     match state.active_panel {
         DashboardPanel::Overview => {
             // Fast parallel data overview
-            draw_overview_parallel(f, chunks[1], state, stats_calculators);
+            draw_overview_parallel(f, area, state, stats_calculators);
         }
         DashboardPanel::Interfaces => {
-            draw_interfaces_panel(f, chunks[1], state, stats_calculators);
+            draw_interfaces_panel(f, area, state, stats_calculators);
         }
         DashboardPanel::Connections => {
-            draw_connections_panel(f, chunks[1], state);
+            draw_connections_panel(f, area, state);
         }
         DashboardPanel::Processes => {
-            draw_processes_panel(f, chunks[1], state);
+            draw_processes_panel(f, area, state);
         }
         DashboardPanel::System => {
             if let Some(stats) = system_stats {
-                draw_system_panel(f, chunks[1], &mut *state, stats);
+                draw_system_panel(f, area, &mut *state, stats);
+            } else {
+                let waiting = Paragraph::new("⏳ Collecting system statistics...").block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("🖥️ System Info"),
+                );
+                f.render_widget(waiting, area);
             }
         }
         DashboardPanel::Graphs => {
-            draw_graphs_panel(f, chunks[1], state, stats_calculators);
+            draw_graphs_panel(f, area, state, stats_calculators);
         }
         DashboardPanel::Diagnostics => {
-            draw_diagnostics_panel(f, chunks[1], state);
+            draw_diagnostics_panel(f, area, state);
+        }
+        DashboardPanel::Neighbors => {
+            draw_neighbors_panel(f, area, state);
         }
         DashboardPanel::Alerts => {
-            draw_alerts_panel(f, chunks[1], state, stats_calculators);
+            draw_alerts_panel(f, area, state);
         }
         DashboardPanel::Forensics => {
             // Wrap entire forensics panel in panic protection
             if std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                draw_forensics_panel(f, chunks[1], state)
+                draw_forensics_panel(f, area, state)
             }))
             .is_err()
             {
-                draw_forensics_error(f, chunks[1]);
+                draw_forensics_error(f, area, &state.theme);
             }
         }
         DashboardPanel::Settings => {
-            draw_settings_panel(f, chunks[1], state);
+            draw_settings_panel(f, area, state);
         }
     }
 
@@ -850,8 +2878,42 @@ fn draw_dashboard(
 
     // Draw help overlay if needed
     if state.show_help {
-        draw_help_overlay(f);
+        draw_help_overlay(f, state);
     }
+
+    if let Some(prompt) = &state.kill_prompt {
+        draw_kill_prompt(f, prompt, &state.theme);
+    }
+}
+
+/// Frame `area` in the warning color with the filter's query and match count along the
+/// bottom, returning what is left inside for the panel
+fn draw_search_bar(f: &mut Frame, area: Rect, state: &DashboardState, matches: usize) -> Rect {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(state.theme.warning_color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let cursor = if state.search.active { "█" } else { "" };
+    let noun = if matches == 1 { "match" } else { "matches" };
+    let bar = Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(state.theme.warning_color)),
+        Span::styled(
+            format!("{}{cursor}", state.search.query),
+            Style::default().fg(state.theme.text_color),
+        ),
+        Span::styled(
+            format!(" [{matches} {noun}]"),
+            Style::default().fg(state.theme.muted_color),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(bar), chunks[1]);
+    chunks[0]
 }
 
 #[allow(dead_code)]
@@ -951,51 +3013,60 @@ fn draw_overview_system_status(f: &mut Frame, area: Rect, state: &DashboardState
     let block = Block::default()
         .title("🖥️  System Status")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Blue));
+        .style(Style::default().fg(state.theme.info_color));
 
     let mut content = vec![
         Line::from(vec![
-            Span::styled("CPU Usage:    ", Style::default().fg(Color::White)),
+            Span::styled(
+                "CPU Usage:    ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{cpu:5.1}%"),
                 Style::default().fg(if cpu > 80.0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else if cpu > 60.0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Memory Usage: ", Style::default().fg(Color::White)),
+            Span::styled(
+                "Memory Usage: ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{memory:5.1}%"),
                 Style::default().fg(if memory > 80.0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else if memory > 60.0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Disk Usage:   ", Style::default().fg(Color::White)),
+            Span::styled(
+                "Disk Usage:   ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{disk:5.1}%"),
                 Style::default().fg(if disk > 80.0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else if disk > 60.0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::White)),
+            Span::styled("Status: ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 if has_errors {
                     "⚠️  Errors detected"
@@ -1006,7 +3077,11 @@ fn draw_overview_system_status(f: &mut Frame, area: Rect, state: &DashboardState
                 } else {
                     "🔴 Critical"
                 },
-                Style::default().fg(if has_errors { Color::Red } else { Color::White }),
+                Style::default().fg(if has_errors {
+                    state.theme.critical_color
+                } else {
+                    state.theme.text_color
+                }),
             ),
         ]),
     ];
@@ -1015,15 +3090,18 @@ fn draw_overview_system_status(f: &mut Frame, area: Rect, state: &DashboardState
     if has_errors {
         content.push(Line::from(""));
         content.push(Line::from(vec![
-            Span::styled("⚠️  ", Style::default().fg(Color::Red)),
+            Span::styled("⚠️  ", Style::default().fg(state.theme.critical_color)),
             Span::styled(
                 "CPU/Memory monitoring may not be",
-                Style::default().fg(Color::Red),
+                Style::default().fg(state.theme.critical_color),
             ),
         ]));
         content.push(Line::from(vec![
-            Span::styled("   ", Style::default().fg(Color::Red)),
-            Span::styled("supported on this system", Style::default().fg(Color::Red)),
+            Span::styled("   ", Style::default().fg(state.theme.critical_color)),
+            Span::styled(
+                "supported on this system",
+                Style::default().fg(state.theme.critical_color),
+            ),
         ]));
     }
 
@@ -1046,7 +3124,7 @@ fn draw_overview_network_stats(
     let block = Block::default()
         .title(format!("🌐 Network Statistics - {}", current_device.name))
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Green));
+        .style(Style::default().fg(state.theme.ok_color));
 
     let content = if let Some(calculator) = stats_calculators.get(&current_device.name) {
         let (speed_in, speed_out) = calculator.current_speed();
@@ -1056,51 +3134,51 @@ fn draw_overview_network_stats(
 
         vec![
             Line::from(vec![
-                Span::styled("Current:  ↓ ", Style::default().fg(Color::White)),
+                Span::styled("Current:  ↓ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} KB/s", speed_in as f64 / 1024.0),
-                    Style::default().fg(Color::Cyan),
+                    format!("{:>13}", format_rate(speed_in, &state.traffic_unit)),
+                    Style::default().fg(state.theme.accent_color),
                 ),
-                Span::styled("  ↑ ", Style::default().fg(Color::White)),
+                Span::styled("  ↑ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} KB/s", speed_out as f64 / 1024.0),
-                    Style::default().fg(Color::Cyan),
+                    format!("{:>13}", format_rate(speed_out, &state.traffic_unit)),
+                    Style::default().fg(state.theme.accent_color),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Average:  ↓ ", Style::default().fg(Color::White)),
+                Span::styled("Average:  ↓ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} KB/s", avg_in as f64 / 1024.0),
-                    Style::default().fg(Color::Green),
+                    format!("{:>13}", format_rate(avg_in, &state.traffic_unit)),
+                    Style::default().fg(state.theme.ok_color),
                 ),
-                Span::styled("  ↑ ", Style::default().fg(Color::White)),
+                Span::styled("  ↑ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} KB/s", avg_out as f64 / 1024.0),
-                    Style::default().fg(Color::Green),
+                    format!("{:>13}", format_rate(avg_out, &state.traffic_unit)),
+                    Style::default().fg(state.theme.ok_color),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Total:    ↓ ", Style::default().fg(Color::White)),
+                Span::styled("Total:    ↓ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} MB", total_in as f64 / (1024.0 * 1024.0)),
-                    Style::default().fg(Color::Yellow),
+                    format!("{:>13}", format_volume(total_in, &state.data_unit)),
+                    Style::default().fg(state.theme.warning_color),
                 ),
-                Span::styled("   ↑ ", Style::default().fg(Color::White)),
+                Span::styled("   ↑ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
-                    format!("{:8.1} MB", total_out as f64 / (1024.0 * 1024.0)),
-                    Style::default().fg(Color::Yellow),
+                    format!("{:>13}", format_volume(total_out, &state.data_unit)),
+                    Style::default().fg(state.theme.warning_color),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Packets:  ↓ ", Style::default().fg(Color::White)),
+                Span::styled("Packets:  ↓ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
                     format!("{packets_in:>10}"),
-                    Style::default().fg(Color::Magenta),
+                    Style::default().fg(state.theme.highlight_color),
                 ),
-                Span::styled("   ↑ ", Style::default().fg(Color::White)),
+                Span::styled("   ↑ ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
                     format!("{packets_out:>10}"),
-                    Style::default().fg(Color::Magenta),
+                    Style::default().fg(state.theme.highlight_color),
                 ),
             ]),
         ]
@@ -1137,14 +3215,17 @@ fn draw_overview_connections_processes(f: &mut Frame, area: Rect, state: &Dashbo
     let conn_block = Block::default()
         .title("🔗 Active Connections")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(state.theme.warning_color));
 
     let conn_content = vec![
         Line::from(vec![
-            Span::styled("Total Connections: ", Style::default().fg(Color::White)),
+            Span::styled(
+                "Total Connections: ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{connections_count}"),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             ),
         ]),
         Line::from(""),
@@ -1171,14 +3252,17 @@ fn draw_overview_connections_processes(f: &mut Frame, area: Rect, state: &Dashbo
     let proc_block = Block::default()
         .title("⚙️  Running Processes")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Magenta));
+        .style(Style::default().fg(state.theme.highlight_color));
 
     let proc_content = vec![
         Line::from(vec![
-            Span::styled("Active Processes: ", Style::default().fg(Color::White)),
+            Span::styled(
+                "Active Processes: ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{processes_count}"),
-                Style::default().fg(Color::Magenta),
+                Style::default().fg(state.theme.highlight_color),
             ),
         ]),
         Line::from(""),
@@ -1206,9 +3290,17 @@ fn draw_server_health_status(
     let mut total_traffic = 0u64;
     let mut has_errors = false;
     let mut interface_count = 0;
+    // Unknown counts as physical, so a reader that cannot tell still shows a total
+    let physical_total = state
+        .config
+        .as_ref()
+        .is_some_and(|config| config.physical_total);
 
     for device in &state.devices {
         interface_count += 1;
+        if physical_total && device.info.physical == Some(false) {
+            continue;
+        }
         if let Some(calculator) = stats_calculators.get(&device.name) {
             let (speed_in, speed_out) = calculator.current_speed();
             total_traffic += speed_in + speed_out;
@@ -1228,31 +3320,76 @@ fn draw_server_health_status(
 
     // More stable health assessment - reduce flickering
     let has_any_activity = total_traffic > 100 || connections_count > 0; // 100 bytes threshold
+    let close_wait_threshold = state
+        .config
+        .as_ref()
+        .map_or(50, |config| config.close_wait_threshold);
+    let pressure = state
+        .connection_monitor
+        .get_connection_stats()
+        .pressure_warnings(close_wait_threshold);
+
+    let deviating = baseline_deviations(state, stats_calculators);
 
     let (status_icon, status_text, status_color) = if has_errors {
-        ("🔴", "ERRORS DETECTED", Color::Red)
+        (
+            "🔴",
+            "ERRORS DETECTED".to_string(),
+            state.theme.critical_color,
+        )
+    } else if deviating > 0 {
+        (
+            "🔴",
+            format!(
+                "{deviating} METRIC{} DEVIATING FROM BASELINE",
+                if deviating == 1 { "" } else { "S" }
+            ),
+            state.theme.critical_color,
+        )
     } else if total_traffic > 50 * 1024 * 1024 {
         // > 50MB/s
-        ("🔴", "HIGH BANDWIDTH USAGE", Color::Red)
+        (
+            "🔴",
+            "HIGH BANDWIDTH USAGE".to_string(),
+            state.theme.critical_color,
+        )
+    } else if !pressure.is_empty() {
+        (
+            "🟡",
+            "SOCKET PRESSURE".to_string(),
+            state.theme.warning_color,
+        )
     } else if connections_count > 100 {
-        ("🟡", "HIGH CONNECTION COUNT", Color::Yellow)
+        (
+            "🟡",
+            "HIGH CONNECTION COUNT".to_string(),
+            state.theme.warning_color,
+        )
     } else if has_any_activity {
-        ("✅", "NETWORK OK", Color::Green)
+        ("✅", "NETWORK OK".to_string(), state.theme.ok_color)
     } else if interface_count > 0 {
         // Interfaces exist but quiet - this is often normal for servers
-        ("🟡", "QUIET (NORMAL)", Color::Yellow)
+        (
+            "🟡",
+            "QUIET (NORMAL)".to_string(),
+            state.theme.warning_color,
+        )
     } else {
-        ("⚠️", "NO INTERFACES", Color::Red)
+        (
+            "⚠️",
+            "NO INTERFACES".to_string(),
+            state.theme.critical_color,
+        )
     };
 
     let block = Block::default()
         .title("🖥️ Server Health")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Blue));
+        .style(Style::default().fg(state.theme.info_color));
 
     let content = vec![
         Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::White)),
+            Span::styled("Status: ", Style::default().fg(state.theme.text_color)),
             Span::styled(status_icon, Style::default().fg(status_color)),
             Span::styled(
                 format!(" {status_text}"),
@@ -1261,28 +3398,76 @@ fn draw_server_health_status(
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+        match &state.process_focus {
+            // Socket rate estimates (bits/s) of just that process's connections
+            Some(focus) => Line::from(vec![
+                Span::styled("Traffic: ", Style::default().fg(state.theme.text_color)),
+                Span::styled(
+                    format_rate(
+                        focus
+                            .connections
+                            .iter()
+                            .filter_map(|conn| conn.socket_info.bandwidth)
+                            .sum::<u64>()
+                            / 8,
+                        &state.traffic_unit,
+                    ),
+                    Style::default().fg(state.theme.accent_color),
+                ),
+                Span::styled(
+                    format!(
+                        " | {} connections (PID {} only)",
+                        focus.connections.len(),
+                        focus.pid
+                    ),
+                    Style::default().fg(state.theme.text_color),
+                ),
+            ]),
+            None => Line::from(vec![
+                Span::styled(
+                    if physical_total {
+                        "Traffic (physical): "
+                    } else {
+                        "Traffic: "
+                    },
+                    Style::default().fg(state.theme.text_color),
+                ),
+                Span::styled(
+                    format_rate(total_traffic, &state.traffic_unit),
+                    Style::default().fg(state.theme.accent_color),
+                ),
+                Span::styled(
+                    format!(" | {connections_count} connections"),
+                    Style::default().fg(state.theme.text_color),
+                ),
+            ]),
+        },
+        Line::from(vec![
+            Span::styled("Sockets: ", Style::default().fg(state.theme.text_color)),
+            if pressure.is_empty() {
+                Span::styled(
+                    "✅ No CLOSE_WAIT or SYN_RECV buildup",
+                    Style::default().fg(state.theme.ok_color),
+                )
+            } else {
+                Span::styled(
+                    format!("⚠️ {}", pressure.join(", ")),
+                    Style::default().fg(state.theme.warning_color),
+                )
+            },
+        ]),
         Line::from(vec![
-            Span::styled("Traffic: ", Style::default().fg(Color::White)),
-            Span::styled(
-                if total_traffic >= 1024 * 1024 {
-                    format!("{:.1} MB/s", total_traffic as f64 / 1024.0 / 1024.0)
-                } else if total_traffic >= 1024 {
-                    format!("{:.0} KB/s", total_traffic as f64 / 1024.0)
-                } else {
-                    format!("{total_traffic} B/s")
-                },
-                Style::default().fg(Color::Cyan),
-            ),
+            Span::styled("Churn: ", Style::default().fg(state.theme.text_color)),
             Span::styled(
-                format!(" | {connections_count} connections"),
-                Style::default().fg(Color::White),
+                churn_summary(&state.connection_monitor.get_connection_stats()),
+                Style::default().fg(state.theme.accent_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Interfaces: ", Style::default().fg(Color::White)),
+            Span::styled("Interfaces: ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 format!("{interface_count} total"),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(state.theme.accent_color),
             ),
             Span::styled(
                 if has_errors {
@@ -1290,7 +3475,11 @@ fn draw_server_health_status(
                 } else {
                     " | ✅ No errors"
                 },
-                Style::default().fg(if has_errors { Color::Red } else { Color::Green }),
+                Style::default().fg(if has_errors {
+                    state.theme.critical_color
+                } else {
+                    state.theme.ok_color
+                }),
             ),
         ]),
     ];
@@ -1302,6 +3491,16 @@ fn draw_server_health_status(
     f.render_widget(paragraph, area);
 }
 
+/// New and closed connections per second and the churn ratio, e.g. `+4.0/s new, -3.5/s closed, 12% churn`
+fn churn_summary(stats: &ConnectionStats) -> String {
+    format!(
+        "+{:.1}/s new, -{:.1}/s closed, {:.0}% churn",
+        stats.new_per_sec,
+        stats.closed_per_sec,
+        stats.churn_ratio * 100.0
+    )
+}
+
 #[allow(dead_code)]
 fn draw_all_interfaces_grid(
     f: &mut Frame,
@@ -1312,20 +3511,20 @@ fn draw_all_interfaces_grid(
     let block = Block::default()
         .title("📊 All Network Interfaces Activity")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Green));
+        .style(Style::default().fg(state.theme.ok_color));
 
     let mut content = vec![
         Line::from(vec![
             Span::styled(
                 "Interface",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "        ↓Download    ↑Upload      Status",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -1338,10 +3537,10 @@ fn draw_all_interfaces_grid(
         let is_current = i == state.current_device_index;
         let interface_style = if is_current {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(state.theme.accent_color)
         };
 
         if let Some(calculator) = stats_calculators.get(&device.name) {
@@ -1354,14 +3553,14 @@ fn draw_all_interfaces_grid(
 
             let status = if combined_speed > 1024 * 100 {
                 // > 100KB/s
-                ("🔴 BUSY", Color::Red)
+                ("🔴 BUSY", state.theme.critical_color)
             } else if combined_speed > 1024 * 10 {
                 // > 10KB/s
-                ("🟡 ACTIVE", Color::Yellow)
+                ("🟡 ACTIVE", state.theme.warning_color)
             } else if combined_speed > 0 {
-                ("🟢 LIGHT", Color::Green)
+                ("🟢 LIGHT", state.theme.ok_color)
             } else {
-                ("⚪ IDLE", Color::White)
+                ("⚪ IDLE", state.theme.text_color)
             };
 
             let current_indicator = if is_current { "►" } else { " " };
@@ -1372,12 +3571,12 @@ fn draw_all_interfaces_grid(
                     interface_style,
                 ),
                 Span::styled(
-                    format!("{:>8.1}KB/s", speed_in as f64 / 1024.0),
-                    Style::default().fg(Color::Cyan),
+                    format!("{:>12}", format_rate(speed_in, &state.traffic_unit)),
+                    Style::default().fg(state.theme.accent_color),
                 ),
                 Span::styled(
-                    format!("  {:>8.1}KB/s", speed_out as f64 / 1024.0),
-                    Style::default().fg(Color::Magenta),
+                    format!("  {:>12}", format_rate(speed_out, &state.traffic_unit)),
+                    Style::default().fg(state.theme.highlight_color),
                 ),
                 Span::styled(format!("  {}", status.0), Style::default().fg(status.1)),
             ]));
@@ -1388,9 +3587,18 @@ fn draw_all_interfaces_grid(
                     format!("{}{:<12}", current_indicator, device.name),
                     interface_style,
                 ),
-                Span::styled("    No Data", Style::default().fg(Color::Red)),
-                Span::styled("     No Data", Style::default().fg(Color::Red)),
-                Span::styled("  ❌ ERROR", Style::default().fg(Color::Red)),
+                Span::styled(
+                    "    No Data",
+                    Style::default().fg(state.theme.critical_color),
+                ),
+                Span::styled(
+                    "     No Data",
+                    Style::default().fg(state.theme.critical_color),
+                ),
+                Span::styled(
+                    "  ❌ ERROR",
+                    Style::default().fg(state.theme.critical_color),
+                ),
             ]));
         }
     }
@@ -1401,20 +3609,28 @@ fn draw_all_interfaces_grid(
         content.push(Line::from(vec![
             Span::styled(
                 "⚠️  No active interfaces detected! ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(state.theme.critical_color)
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Use ←/→ to check other interfaces",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             ),
         ]));
     } else {
         content.push(Line::from(vec![
-            Span::styled("💡 Use ", Style::default().fg(Color::White)),
-            Span::styled("←/→", Style::default().fg(Color::Green)),
-            Span::styled(" to select interface, ", Style::default().fg(Color::White)),
-            Span::styled("Tab", Style::default().fg(Color::Green)),
-            Span::styled(" for detailed view", Style::default().fg(Color::White)),
+            Span::styled("💡 Use ", Style::default().fg(state.theme.text_color)),
+            Span::styled("←/→", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                " to select interface, ",
+                Style::default().fg(state.theme.text_color),
+            ),
+            Span::styled("Tab", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                " for detailed view",
+                Style::default().fg(state.theme.text_color),
+            ),
         ]));
     }
 
@@ -1448,12 +3664,14 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     let activity_block = Block::default()
         .title("🎯 Top Activity & Security Alerts")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(state.theme.critical_color));
 
     let mut activity_content = vec![
         Line::from(vec![Span::styled(
             "🔥 PRIORITY ALERTS:",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
     ];
@@ -1461,30 +3679,33 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     // Generate actionable alerts based on actual data
     if connections_count > 100 {
         activity_content.push(Line::from(vec![
-            Span::styled("🚨 ", Style::default().fg(Color::Red)),
+            Span::styled("🚨 ", Style::default().fg(state.theme.critical_color)),
             Span::styled(
                 format!("HIGH CONNECTION COUNT: {connections_count} active"),
-                Style::default().fg(Color::Red),
+                Style::default().fg(state.theme.critical_color),
             ),
         ]));
         activity_content.push(Line::from(vec![
-            Span::styled("   ", Style::default().fg(Color::White)),
+            Span::styled("   ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 "→ Check Connections tab for details",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             ),
         ]));
         activity_content.push(Line::from(""));
     } else if connections_count == 0 {
         activity_content.push(Line::from(vec![
-            Span::styled("⚠️  ", Style::default().fg(Color::Yellow)),
-            Span::styled("NO ACTIVE CONNECTIONS", Style::default().fg(Color::Yellow)),
+            Span::styled("⚠️  ", Style::default().fg(state.theme.warning_color)),
+            Span::styled(
+                "NO ACTIVE CONNECTIONS",
+                Style::default().fg(state.theme.warning_color),
+            ),
         ]));
         activity_content.push(Line::from(vec![
-            Span::styled("   ", Style::default().fg(Color::White)),
+            Span::styled("   ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 "→ Network may be isolated or monitoring issue",
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
         activity_content.push(Line::from(""));
@@ -1492,17 +3713,17 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
 
     if processes_count > 200 {
         activity_content.push(Line::from(vec![
-            Span::styled("🔍 ", Style::default().fg(Color::Yellow)),
+            Span::styled("🔍 ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("HIGH PROCESS COUNT: {processes_count}"),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             ),
         ]));
         activity_content.push(Line::from(vec![
-            Span::styled("   ", Style::default().fg(Color::White)),
+            Span::styled("   ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 "→ Check Processes tab for resource usage",
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
         activity_content.push(Line::from(""));
@@ -1511,14 +3732,17 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     // If no alerts, show positive status
     if connections_count > 0 && connections_count <= 100 && processes_count <= 200 {
         activity_content.push(Line::from(vec![
-            Span::styled("✅ ", Style::default().fg(Color::Green)),
-            Span::styled("NETWORK STATUS: NORMAL", Style::default().fg(Color::Green)),
+            Span::styled("✅ ", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                "NETWORK STATUS: NORMAL",
+                Style::default().fg(state.theme.ok_color),
+            ),
         ]));
         activity_content.push(Line::from(vec![
-            Span::styled("   ", Style::default().fg(Color::White)),
+            Span::styled("   ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 "→ No security alerts detected",
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
         activity_content.push(Line::from(""));
@@ -1527,19 +3751,25 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     activity_content.push(Line::from(vec![Span::styled(
         "📊 QUICK STATS:",
         Style::default()
-            .fg(Color::Cyan)
+            .fg(state.theme.accent_color)
             .add_modifier(Modifier::BOLD),
     )]));
     activity_content.push(Line::from(vec![
-        Span::styled("   Connections: ", Style::default().fg(Color::White)),
+        Span::styled(
+            "   Connections: ",
+            Style::default().fg(state.theme.text_color),
+        ),
         Span::styled(
             format!("{connections_count}"),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
+        ),
+        Span::styled(
+            " | Processes: ",
+            Style::default().fg(state.theme.text_color),
         ),
-        Span::styled(" | Processes: ", Style::default().fg(Color::White)),
         Span::styled(
             format!("{processes_count}"),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         ),
     ]));
 
@@ -1553,42 +3783,51 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     let action_block = Block::default()
         .title("⚡ Quick Actions")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Magenta));
+        .style(Style::default().fg(state.theme.highlight_color));
 
     let action_content = vec![
         Line::from(vec![Span::styled(
             "NAVIGATE:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("Tab", Style::default().fg(Color::Green)),
-            Span::styled(" - Next panel", Style::default().fg(Color::White)),
+            Span::styled("Tab", Style::default().fg(state.theme.ok_color)),
+            Span::styled(" - Next panel", Style::default().fg(state.theme.text_color)),
         ]),
         Line::from(vec![
-            Span::styled("←/→", Style::default().fg(Color::Green)),
-            Span::styled(" - Switch interface", Style::default().fg(Color::White)),
+            Span::styled("←/→", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                " - Switch interface",
+                Style::default().fg(state.theme.text_color),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "MONITOR:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("Space", Style::default().fg(Color::Green)),
-            Span::styled(" - Pause/Resume", Style::default().fg(Color::White)),
+            Span::styled("Space", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                " - Pause/Resume",
+                Style::default().fg(state.theme.text_color),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("R", Style::default().fg(Color::Green)),
-            Span::styled(" - Reset stats", Style::default().fg(Color::White)),
+            Span::styled("R", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                " - Reset stats",
+                Style::default().fg(state.theme.text_color),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "netwatch v2.0",
-            Style::default().fg(Color::Magenta),
+            Style::default().fg(state.theme.highlight_color),
         )]),
     ];
 
@@ -1599,20 +3838,71 @@ fn draw_top_activity_security(f: &mut Frame, area: Rect, state: &DashboardState)
     f.render_widget(action_paragraph, chunks[1]);
 }
 
+/// The panel tab under a click at (`column`, `row`), following the layout [`Tabs`] uses
+/// in [`draw_header`]: inside the border, each title padded by a space on both sides
+/// and followed by a one-column divider
+fn tab_at(tab_bar: Rect, titles: &[String], column: u16, row: u16) -> Option<usize> {
+    if !tab_bar.contains(ratatui::layout::Position::new(column, row)) {
+        return None;
+    }
+    let mut x = tab_bar.x + 1;
+    for (index, title) in titles.iter().enumerate() {
+        let width = Line::from(title.as_str()).width() as u16 + 2;
+        if column < x + width {
+            return (column >= x).then_some(index);
+        }
+        x += width + 1;
+    }
+    None
+}
+
+/// `--pid` in the header: the watched PID, or that it has exited
+fn process_focus_span(state: &DashboardState) -> Option<Span<'static>> {
+    let focus = state.process_focus.as_ref()?;
+    Some(if focus.has_exited() {
+        Span::styled(
+            format!(" · Process {} exited", focus.pid),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            format!(" · PID {}", focus.pid),
+            Style::default().fg(state.theme.accent_color),
+        )
+    })
+}
+
 fn draw_header(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let panels = DashboardPanel::all();
-    let titles: Vec<Line> = panels.iter().map(|p| Line::from(p.title())).collect();
+    if state.is_compact() {
+        let mut indicator = vec![
+            Span::styled(
+                format!("[Panel {}/{}] ", state.panel_index + 1, state.panels.len()),
+                Style::default()
+                    .fg(state.theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                state.active_panel.title(),
+                Style::default().fg(state.theme.text_color),
+            ),
+        ];
+        indicator.extend(process_focus_span(state));
+        f.render_widget(Paragraph::new(Line::from(indicator)), area);
+        return;
+    }
+    let titles: Vec<Line> = state.tab_titles().into_iter().map(Line::from).collect();
+    let title: Line = std::iter::once(Span::raw("netwatch ADVANCED DASHBOARD"))
+        .chain(process_focus_span(state))
+        .collect();
 
     let tabs = Tabs::new(titles)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("netwatch ADVANCED DASHBOARD"),
-        )
-        .style(Style::default().fg(Color::White))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(state.theme.text_color))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )
         .select(state.panel_index);
@@ -1667,7 +3957,7 @@ fn draw_overview_panel(
         .split(main_chunks[1]);
 
     // Left panel: Ultra-comprehensive diagnostics + NEW active testing
-    draw_ultra_system_health_panel(f, left_chunks[0], state, stats_calculators);
+    draw_ultra_system_health_panel(f, left_chunks[0], state);
     draw_ultra_network_stack_diagnostics(f, left_chunks[1], state, stats_calculators);
     draw_ultra_performance_bottlenecks(f, left_chunks[2], state, stats_calculators);
     draw_ultra_active_diagnostics_panel(f, left_chunks[3], state); // NEW!
@@ -1688,38 +3978,41 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
     diagnostic_lines.push(Line::from(vec![Span::styled(
         "🌐 ACTIVE CONNECTIVITY",
         Style::default()
-            .fg(Color::Green)
+            .fg(state.theme.ok_color)
             .add_modifier(Modifier::BOLD),
     )]));
     diagnostic_lines.push(Line::from(""));
 
     // Connectivity summary
     diagnostic_lines.push(Line::from(vec![
-        Span::styled("📊 Summary: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            "📊 Summary: ",
+            Style::default().fg(state.theme.warning_color),
+        ),
         Span::styled(
             format!(
                 "{}/{} online",
                 summary.online_targets, summary.total_targets
             ),
             Style::default().fg(if summary.online_targets == summary.total_targets {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Red
+                state.theme.critical_color
             }),
         ),
         Span::styled(
             format!(" ({:.0}ms avg)", summary.avg_latency),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         ),
     ]));
 
     // Show ping results
     for (target, ping_result) in diagnostics.ping_results.iter().take(3) {
         let status_color = match ping_result.status {
-            ConnectivityStatus::Online => Color::Green,
-            ConnectivityStatus::Degraded => Color::Yellow,
-            ConnectivityStatus::Offline => Color::Red,
-            _ => Color::Gray,
+            ConnectivityStatus::Online => state.theme.ok_color,
+            ConnectivityStatus::Degraded => state.theme.warning_color,
+            ConnectivityStatus::Offline => state.theme.critical_color,
+            _ => state.theme.muted_color,
         };
 
         let status_icon = match ping_result.status {
@@ -1731,7 +4024,10 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
 
         diagnostic_lines.push(Line::from(vec![
             Span::styled(format!("{status_icon} "), Style::default()),
-            Span::styled(format!("{target:12}"), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("{target:12}"),
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 format!("{:>6.0}ms", ping_result.avg_rtt),
                 Style::default().fg(status_color),
@@ -1739,9 +4035,9 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
             Span::styled(
                 format!(" {:.0}%loss", ping_result.packet_loss),
                 Style::default().fg(if ping_result.packet_loss > 0.0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]));
@@ -1752,7 +4048,7 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
         diagnostic_lines.push(Line::from(""));
         diagnostic_lines.push(Line::from(vec![Span::styled(
             "🔍 Ports:",
-            Style::default().fg(Color::Magenta),
+            Style::default().fg(state.theme.highlight_color),
         )]));
 
         for (_target_port, port_result) in diagnostics.port_scan_results.iter().take(2) {
@@ -1769,11 +4065,11 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
                         "{} {}:{}",
                         status_icon, port_result.target, port_result.port
                     ),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(state.theme.text_color),
                 ),
                 Span::styled(
                     format!(" {:?}", port_result.status),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(state.theme.muted_color),
                 ),
             ]));
         }
@@ -1784,7 +4080,7 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
         diagnostic_lines.push(Line::from(""));
         diagnostic_lines.push(Line::from(vec![Span::styled(
             "🌐 DNS:",
-            Style::default().fg(Color::Blue),
+            Style::default().fg(state.theme.info_color),
         )]));
 
         for (domain, dns_result) in diagnostics.dns_results.iter().take(1) {
@@ -1797,11 +4093,11 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
             diagnostic_lines.push(Line::from(vec![
                 Span::styled(
                     format!("{status_icon} {domain}"),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(state.theme.text_color),
                 ),
                 Span::styled(
                     format!(" {:.0}ms", dns_result.response_time),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(state.theme.accent_color),
                 ),
             ]));
         }
@@ -1812,12 +4108,12 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
         diagnostic_lines.push(Line::from(""));
         diagnostic_lines.push(Line::from(vec![Span::styled(
             "⚠️ Issues:",
-            Style::default().fg(Color::Red),
+            Style::default().fg(state.theme.critical_color),
         )]));
         for issue in summary.critical_issues.iter().take(1) {
             diagnostic_lines.push(Line::from(vec![Span::styled(
                 format!("  {issue}"),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             )]));
         }
     }
@@ -1828,166 +4124,35 @@ fn draw_ultra_active_diagnostics_panel(f: &mut Frame, area: Rect, state: &Dashbo
                 .borders(Borders::ALL)
                 .title("ULTRA ACTIVE DIAGNOSTICS"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(diagnostics_widget, area);
 }
 
-#[allow(dead_code)]
-fn draw_ultra_system_health_panel(
-    f: &mut Frame,
-    area: Rect,
-    state: &DashboardState,
-    stats_calculators: &HashMap<String, StatsCalculator>,
-) {
-    let connections = state.connection_monitor.get_connections();
-    let conn_stats = state.connection_monitor.get_connection_stats();
-
-    // Calculate comprehensive interface metrics
-    let mut _total_in = 0u64;
-    let mut _total_out = 0u64;
-    let mut _interface_errors = 0u64;
-    let mut _active_interfaces = 0;
-
-    for device in &state.devices {
-        if let Some(calculator) = stats_calculators.get(&device.name) {
-            let (current_in, current_out) = calculator.current_speed();
-            _total_in += current_in;
-            _total_out += current_out;
-            _active_interfaces += 1;
-            _interface_errors += device.stats.errors_in
-                + device.stats.errors_out
-                + device.stats.drops_in
-                + device.stats.drops_out;
-        }
-    }
-
-    // Advanced diagnostics
-    let mut critical_issues = Vec::new();
-    let mut warnings = Vec::new();
-    let mut system_status = "🟢 HEALTHY";
-    let mut status_color = Color::Green;
-
-    // Calculate advanced metrics
-    let mut total_retrans = 0u32;
-    let mut _total_lost = 0u32;
-    let mut avg_rtt = 0.0;
-    let mut rtt_count = 0;
-    let mut _slow_connections = 0;
-    let mut congested_connections = 0;
-    let mut _established_external = 0;
-    let mut _failed_connections = 0;
-
-    for conn in connections {
-        total_retrans += conn.socket_info.retrans;
-        _total_lost += conn.socket_info.lost;
-
-        if let Some(rtt) = conn.socket_info.rtt {
-            avg_rtt += rtt;
-            rtt_count += 1;
-
-            if rtt > 1000.0 {
-                _slow_connections += 1;
-            }
-            if rtt > 500.0 && conn.socket_info.retrans > 5 {
-                congested_connections += 1;
-            }
-        }
-
-        if conn.state.as_str() == "ESTABLISHED"
-            && !conn.remote_addr.ip().to_string().starts_with("127.")
-        {
-            _established_external += 1;
-        }
-
-        if conn.state.as_str() == "CLOSE" || conn.state.as_str() == "TIME_WAIT" {
-            _failed_connections += 1;
-        }
-
-        // High queue = congestion
-        if conn.socket_info.send_queue > 65536 || conn.socket_info.recv_queue > 65536 {
-            congested_connections += 1;
-        }
-    }
-
-    if rtt_count > 0 {
-        avg_rtt /= rtt_count as f64;
-    }
-
-    // Critical issue detection with specific diagnostics
-    if total_retrans > 100 {
-        critical_issues.push("🚨 MASSIVE RETRANSMISSIONS");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    } else if total_retrans > 25 {
-        warnings.push("⚠️ HIGH RETRANS RATE");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if avg_rtt > 2000.0 {
-        critical_issues.push("🚨 SEVERE LATENCY");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    } else if avg_rtt > 500.0 {
-        warnings.push("⚠️ HIGH LATENCY");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if congested_connections > 5 {
-        critical_issues.push("🚨 NETWORK CONGESTION");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    } else if congested_connections > 1 {
-        warnings.push("⚠️ CONGESTION DETECTED");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if conn_stats.total > 2000 {
-        warnings.push("⚠️ CONNECTION FLOOD");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    // Interface errors
-    let mut total_errors = 0;
-    let mut total_drops = 0;
-    for device in &state.devices {
-        total_errors += device.stats.errors_in + device.stats.errors_out;
-        total_drops += device.stats.drops_in + device.stats.drops_out;
-    }
-
-    if total_errors > 50 {
-        critical_issues.push("🚨 INTERFACE ERRORS");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    }
-
-    if total_drops > 100 {
-        critical_issues.push("🚨 PACKET DROPS");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    }
+#[allow(dead_code)]
+fn draw_ultra_system_health_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let health = &state.health;
+    let status_color = match health.status {
+        HealthStatus::Healthy => state.theme.ok_color,
+        HealthStatus::Warning => state.theme.warning_color,
+        HealthStatus::Critical => state.theme.critical_color,
+    };
+    let system_status = health.status.label();
+    let critical_issues = &health.critical_issues;
+    let warnings = &health.warnings;
 
     let health_text = vec![
         Line::from(vec![Span::styled(
             "🛡️ SRE NETWORK FORENSICS",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("System Status: ", Style::default().fg(Color::White)),
+            Span::styled(
+                "System Status: ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 system_status,
                 Style::default()
@@ -1998,7 +4163,9 @@ fn draw_ultra_system_health_panel(
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔴 Critical Issues:",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
             if critical_issues.is_empty() {
@@ -2007,16 +4174,16 @@ fn draw_ultra_system_health_panel(
                 format!("   {}", critical_issues.join(", "))
             },
             Style::default().fg(if critical_issues.is_empty() {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Red
+                state.theme.critical_color
             }),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🟡 Warnings:",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
@@ -2026,9 +4193,9 @@ fn draw_ultra_system_health_panel(
                 format!("   {}", warnings.join(", "))
             },
             Style::default().fg(if warnings.is_empty() {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Yellow
+                state.theme.warning_color
             }),
         )]),
     ];
@@ -2039,7 +4206,7 @@ fn draw_ultra_system_health_panel(
                 .borders(Borders::ALL)
                 .title("🛡️ ULTRA SRE SYSTEM HEALTH"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(health_widget, area);
 }
 
@@ -2092,34 +4259,34 @@ fn draw_ultra_network_stack_diagnostics(
     let stack_text = vec![
         Line::from(vec![Span::styled(
             "📊 Protocol Distribution:",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]),
         Line::from(vec![Span::styled(
             format!("  TCP: {:.1}% ({} conns)", tcp_ratio, conn_stats.tcp),
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]),
         Line::from(vec![Span::styled(
             format!("  UDP: {:.1}% ({} conns)", udp_ratio, conn_stats.udp),
-            Style::default().fg(Color::Blue),
+            Style::default().fg(state.theme.info_color),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔗 Connection States:",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]),
         Line::from(vec![Span::styled(
             format!(
                 "  Active: {:.1}% ({} conns)",
                 active_ratio, conn_stats.established
             ),
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]),
         Line::from(vec![Span::styled(
             format!(
                 "  Listen: {:.1}% ({} ports)",
                 listen_ratio, conn_stats.listening
             ),
-            Style::default().fg(Color::Blue),
+            Style::default().fg(state.theme.info_color),
         )]),
         Line::from(vec![Span::styled(
             if stack_issues.is_empty() {
@@ -2128,9 +4295,9 @@ fn draw_ultra_network_stack_diagnostics(
                 format!("⚠️ {}", stack_issues.join(", "))
             },
             Style::default().fg(if stack_issues.is_empty() {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Yellow
+                state.theme.warning_color
             }),
         )]),
     ];
@@ -2141,7 +4308,7 @@ fn draw_ultra_network_stack_diagnostics(
                 .borders(Borders::ALL)
                 .title("🔧 ULTRA NETWORK STACK"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(stack_widget, area);
 }
 
@@ -2215,45 +4382,48 @@ fn draw_ultra_performance_bottlenecks(
         Line::from(vec![Span::styled(
             "⚡ Performance Metrics:",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(state.theme.highlight_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
             format!("  Avg RTT: {avg_rtt:.0}ms"),
             Style::default().fg(if avg_rtt > 200.0 {
-                Color::Red
+                state.theme.critical_color
             } else if avg_rtt > 100.0 {
-                Color::Yellow
+                state.theme.warning_color
             } else {
-                Color::Green
+                state.theme.ok_color
             }),
         )]),
         Line::from(vec![Span::styled(
-            format!("  Bandwidth: {}Mbps", total_bandwidth / 1_000_000),
-            Style::default().fg(Color::Cyan),
+            format!(
+                "  Bandwidth: {}",
+                format_bandwidth(total_bandwidth, &state.traffic_unit)
+            ),
+            Style::default().fg(state.theme.accent_color),
         )]),
         Line::from(vec![Span::styled(
             format!("  Retrans Rate: {retrans_rate:.2}%"),
             Style::default().fg(if retrans_rate > 1.0 {
-                Color::Red
+                state.theme.critical_color
             } else if retrans_rate > 0.1 {
-                Color::Yellow
+                state.theme.warning_color
             } else {
-                Color::Green
+                state.theme.ok_color
             }),
         )]),
         Line::from(vec![Span::styled(
             format!(
-                "  Interface: ↓{}/s ↑{}/s",
-                format_bytes(total_in),
-                format_bytes(total_out)
+                "  Interface: ↓{} ↑{}",
+                format_rate(total_in, &state.traffic_unit),
+                format_rate(total_out, &state.traffic_unit)
             ),
-            Style::default().fg(Color::White),
+            Style::default().fg(state.theme.text_color),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🎯 Bottlenecks:",
-            Style::default().fg(Color::Red),
+            Style::default().fg(state.theme.critical_color),
         )]),
         Line::from(vec![Span::styled(
             if bottlenecks.is_empty() {
@@ -2262,9 +4432,9 @@ fn draw_ultra_performance_bottlenecks(
                 format!("  {}", bottlenecks.join(", "))
             },
             Style::default().fg(if bottlenecks.is_empty() {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Red
+                state.theme.critical_color
             }),
         )]),
     ];
@@ -2275,7 +4445,7 @@ fn draw_ultra_performance_bottlenecks(
                 .borders(Borders::ALL)
                 .title("🎯 ULTRA BOTTLENECKS"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(perf_widget, area);
 }
 
@@ -2304,7 +4474,6 @@ fn draw_connection_intelligence_summary(
     let mut avg_rtt = 0.0;
     let mut rtt_count = 0;
     let mut total_bandwidth = 0u64;
-    let mut total_retrans = 0u32;
 
     for conn in connections {
         if let Some(rtt) = conn.socket_info.rtt {
@@ -2314,67 +4483,40 @@ fn draw_connection_intelligence_summary(
         if let Some(bw) = conn.socket_info.bandwidth {
             total_bandwidth += bw;
         }
-        total_retrans += conn.socket_info.retrans;
     }
 
     if rtt_count > 0 {
         avg_rtt /= rtt_count as f64;
     }
 
-    // SRE Health Assessment
-    let mut health_issues = Vec::new();
-    let mut system_status = "🟢 HEALTHY";
-    let mut status_color = Color::Green;
-
-    // Critical issue detection
-    if total_retrans > 50 {
-        health_issues.push("🔴 HIGH RETRANSMISSIONS");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    } else if total_retrans > 10 {
-        health_issues.push("🟡 ELEVATED RETRANSMISSIONS");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if avg_rtt > 500.0 {
-        health_issues.push("🔴 SEVERE LATENCY");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    } else if avg_rtt > 200.0 {
-        health_issues.push("🟡 HIGH LATENCY");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if conn_stats.total > 1000 {
-        health_issues.push("🟡 CONNECTION OVERLOAD");
-        if system_status == "🟢 HEALTHY" {
-            system_status = "🟡 WARNING";
-            status_color = Color::Yellow;
-        }
-    }
-
-    if total_bandwidth < 1_000_000 && connections.len() > 20 {
-        health_issues.push("🔴 BANDWIDTH BOTTLENECK");
-        system_status = "🔴 CRITICAL";
-        status_color = Color::Red;
-    }
+    // The same assessment the Diagnostics panel and NotifyOnCritical use
+    let health_issues: Vec<&str> = state
+        .health
+        .critical_issues
+        .iter()
+        .chain(&state.health.warnings)
+        .copied()
+        .collect();
+    let system_status = state.health.status.label();
+    let status_color = match state.health.status {
+        HealthStatus::Healthy => state.theme.ok_color,
+        HealthStatus::Warning => state.theme.warning_color,
+        HealthStatus::Critical => state.theme.critical_color,
+    };
 
     let summary_text = vec![
         Line::from(vec![Span::styled(
             "SRE NETWORK FORENSICS SUMMARY",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(state.theme.highlight_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("🌟 System Status: ", Style::default().fg(Color::White)),
+            Span::styled(
+                "🌟 System Status: ",
+                Style::default().fg(state.theme.text_color),
+            ),
             Span::styled(
                 system_status,
                 Style::default()
@@ -2385,35 +4527,35 @@ fn draw_connection_intelligence_summary(
         Line::from(""),
         Line::from(vec![Span::styled(
             "📊 Network Overview:",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]),
         Line::from(vec![Span::styled(
             format!(
-                "  Interface: ↓{}/s ↑{}/s",
-                format_bytes(total_in),
-                format_bytes(total_out)
+                "  Interface: ↓{} ↑{}",
+                format_rate(total_in, &state.traffic_unit),
+                format_rate(total_out, &state.traffic_unit)
             ),
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]),
         Line::from(vec![Span::styled(
             format!(
                 "  Connections: {} total, {} active",
                 conn_stats.total, conn_stats.established
             ),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]),
         Line::from(vec![Span::styled(
             format!(
-                "  Avg RTT: {:.0}ms | BW: {}Mbps",
+                "  Avg RTT: {:.0}ms | BW: {}",
                 avg_rtt,
-                total_bandwidth / 1_000_000
+                format_bandwidth(total_bandwidth, &state.traffic_unit)
             ),
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🚨 Critical Issues:",
-            Style::default().fg(Color::Red),
+            Style::default().fg(state.theme.critical_color),
         )]),
         Line::from(vec![Span::styled(
             if health_issues.is_empty() {
@@ -2422,9 +4564,9 @@ fn draw_connection_intelligence_summary(
                 format!("  {}", health_issues.join(", "))
             },
             Style::default().fg(if health_issues.is_empty() {
-                Color::Green
+                state.theme.ok_color
             } else {
-                Color::Red
+                state.theme.critical_color
             }),
         )]),
     ];
@@ -2435,7 +4577,7 @@ fn draw_connection_intelligence_summary(
                 .borders(Borders::ALL)
                 .title("📋 SRE SUMMARY"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(summary_widget, area);
 }
 
@@ -2461,32 +4603,32 @@ fn draw_ultra_connection_forensics_table(
     let header = Row::new(vec![
         Cell::from("Status").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
         Cell::from("Process").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
         Cell::from("Remote").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
         Cell::from("RTT").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
         Cell::from("Issues").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
         Cell::from("Queue").style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     ]);
@@ -2497,7 +4639,7 @@ fn draw_ultra_connection_forensics_table(
         .map(|conn| {
             let status_icon = get_connection_health_icon(conn);
             let process = conn.process_name.as_deref().unwrap_or("unknown");
-            let remote = format!("{}:{}", conn.remote_addr.ip(), conn.remote_addr.port());
+            let remote = state.connection_monitor.remote_display(conn);
             let rtt = if let Some(rtt) = conn.socket_info.rtt {
                 format!("{rtt:.0}ms")
             } else {
@@ -2642,13 +4784,13 @@ fn draw_ultra_realtime_diagnostics_panel(
         Line::from(vec![Span::styled(
             "🔬 REAL-TIME DIAGNOSTICS",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(state.theme.highlight_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "📋 Findings:",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]),
     ];
 
@@ -2656,11 +4798,11 @@ fn draw_ultra_realtime_diagnostics_panel(
         diagnostic_text.push(Line::from(vec![Span::styled(
             format!("  {diagnostic}"),
             Style::default().fg(if diagnostic.contains("🚨") {
-                Color::Red
+                state.theme.critical_color
             } else if diagnostic.contains("⚠️") {
-                Color::Yellow
+                state.theme.warning_color
             } else {
-                Color::Green
+                state.theme.ok_color
             }),
         )]));
     }
@@ -2668,13 +4810,13 @@ fn draw_ultra_realtime_diagnostics_panel(
     diagnostic_text.push(Line::from(""));
     diagnostic_text.push(Line::from(vec![Span::styled(
         "💡 Recommendations:",
-        Style::default().fg(Color::Cyan),
+        Style::default().fg(state.theme.accent_color),
     )]));
 
     for rec in &recommendations {
         diagnostic_text.push(Line::from(vec![Span::styled(
             format!("  {rec}"),
-            Style::default().fg(Color::White),
+            Style::default().fg(state.theme.text_color),
         )]));
     }
 
@@ -2684,7 +4826,7 @@ fn draw_ultra_realtime_diagnostics_panel(
                 .borders(Borders::ALL)
                 .title("🩺 LIVE DIAGNOSTICS"),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(diagnostics_widget, area);
 }
 
@@ -2811,34 +4953,38 @@ fn draw_enhanced_network_overview(
         Line::from(vec![Span::styled(
             "📡 INTERFACES",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Active: ", Style::default().fg(Color::White)),
+            Span::styled("Active: ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 format!("{active_interfaces}"),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(state.theme.ok_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("↓ In:  ", Style::default().fg(Color::Green)),
-            Span::styled(format_bytes(total_in), Style::default().fg(Color::White)),
-            Span::styled("/s", Style::default().fg(Color::Gray)),
+            Span::styled("↓ In:  ", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                format_rate(total_in, &state.traffic_unit),
+                Style::default().fg(state.theme.text_color),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("↑ Out: ", Style::default().fg(Color::Red)),
-            Span::styled(format_bytes(total_out), Style::default().fg(Color::White)),
-            Span::styled("/s", Style::default().fg(Color::Gray)),
+            Span::styled("↑ Out: ", Style::default().fg(state.theme.critical_color)),
+            Span::styled(
+                format_rate(total_out, &state.traffic_unit),
+                Style::default().fg(state.theme.text_color),
+            ),
         ]),
     ];
 
     let interface_widget = Paragraph::new(interface_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(interface_widget, overview_chunks[0]);
 
     // Connection summary
@@ -2846,34 +4992,34 @@ fn draw_enhanced_network_overview(
         Line::from(vec![Span::styled(
             "🔗 CONNECTIONS",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Total: ", Style::default().fg(Color::White)),
+            Span::styled("Total: ", Style::default().fg(state.theme.text_color)),
             Span::styled(
                 format!("{}", conn_stats.total),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Active: ", Style::default().fg(Color::Green)),
+            Span::styled("Active: ", Style::default().fg(state.theme.ok_color)),
             Span::styled(
                 format!("{}", conn_stats.established),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(state.theme.ok_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Listen: ", Style::default().fg(Color::Blue)),
+            Span::styled("Listen: ", Style::default().fg(state.theme.info_color)),
             Span::styled(
                 format!("{}", conn_stats.listening),
                 Style::default()
-                    .fg(Color::Blue)
+                    .fg(state.theme.info_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -2881,7 +5027,7 @@ fn draw_enhanced_network_overview(
 
     let connection_widget = Paragraph::new(connection_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(connection_widget, overview_chunks[1]);
 
     // Quality metrics
@@ -2889,35 +5035,40 @@ fn draw_enhanced_network_overview(
         Line::from(vec![Span::styled(
             "⚡ QUALITY",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(state.theme.highlight_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("🟢 Fast: ", Style::default().fg(Color::Green)),
+            Span::styled("🟢 Fast: ", Style::default().fg(state.theme.ok_color)),
             Span::styled(
                 format!("{high_quality}"),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(state.theme.ok_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("🔴 Slow: ", Style::default().fg(Color::Red)),
+            Span::styled("🔴 Slow: ", Style::default().fg(state.theme.critical_color)),
             Span::styled(
                 format!("{poor_quality}"),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(state.theme.critical_color)
+                    .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("⚠️ Retrans: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "⚠️ Retrans: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{total_retrans}"),
                 Style::default()
                     .fg(if total_retrans > 0 {
-                        Color::Yellow
+                        state.theme.warning_color
                     } else {
-                        Color::Green
+                        state.theme.ok_color
                     })
                     .add_modifier(Modifier::BOLD),
             ),
@@ -2926,18 +5077,20 @@ fn draw_enhanced_network_overview(
 
     let quality_widget = Paragraph::new(quality_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(quality_widget, overview_chunks[2]);
 
     // Performance metrics
     let performance_text = vec![
         Line::from(vec![Span::styled(
             "PERFORMANCE",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("RTT: ", Style::default().fg(Color::Magenta)),
+            Span::styled("RTT: ", Style::default().fg(state.theme.highlight_color)),
             Span::styled(
                 if avg_rtt > 0.0 {
                     format!("{avg_rtt:.1}ms")
@@ -2945,31 +5098,31 @@ fn draw_enhanced_network_overview(
                     "N/A".to_string()
                 },
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("BW: ", Style::default().fg(Color::Cyan)),
+            Span::styled("BW: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
-                format!("{}M", total_bandwidth / 1_000_000),
+                format_bandwidth(total_bandwidth, &state.traffic_unit),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Proto: ", Style::default().fg(Color::Gray)),
+            Span::styled("Proto: ", Style::default().fg(state.theme.muted_color)),
             Span::styled(
                 format!("TCP:{} UDP:{}", conn_stats.tcp, conn_stats.udp),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
     ];
 
     let performance_widget = Paragraph::new(performance_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
     f.render_widget(performance_widget, overview_chunks[3]);
 }
 
@@ -2996,22 +5149,27 @@ fn draw_enhanced_connections_table(f: &mut Frame, area: Rect, state: &DashboardS
                 if conn.socket_info.retrans > 10 || conn.socket_info.lost > 5 {
                     (
                         "🚨 PROBLEM",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        Style::default()
+                            .fg(state.theme.critical_color)
+                            .add_modifier(Modifier::BOLD),
                     )
                 } else if let Some(rtt) = conn.socket_info.rtt {
                     if rtt > 500.0 {
-                        ("🔴 CRITICAL", Style::default().fg(Color::Red))
+                        (
+                            "🔴 CRITICAL",
+                            Style::default().fg(state.theme.critical_color),
+                        )
                     } else if rtt > 200.0 {
-                        ("🟡 WARNING", Style::default().fg(Color::Yellow))
+                        ("🟡 WARNING", Style::default().fg(state.theme.warning_color))
                     } else if rtt < 50.0 {
-                        ("🟢 GOOD", Style::default().fg(Color::Green))
+                        ("🟢 GOOD", Style::default().fg(state.theme.ok_color))
                     } else {
-                        ("⚪ OK", Style::default().fg(Color::White))
+                        ("⚪ OK", Style::default().fg(state.theme.text_color))
                     }
                 } else if conn.state.as_str() == "LISTEN" {
-                    ("🔵 SERVICE", Style::default().fg(Color::Blue))
+                    ("🔵 SERVICE", Style::default().fg(state.theme.info_color))
                 } else {
-                    ("⚪ UNKNOWN", Style::default().fg(Color::Gray))
+                    ("⚪ UNKNOWN", Style::default().fg(state.theme.muted_color))
                 };
 
             let rtt_display = conn
@@ -3023,7 +5181,7 @@ fn draw_enhanced_connections_table(f: &mut Frame, area: Rect, state: &DashboardS
             let bandwidth_display = conn
                 .socket_info
                 .bandwidth
-                .map(format_bandwidth)
+                .map(|bw| format_bandwidth(bw, &state.traffic_unit))
                 .unwrap_or_else(|| "-".to_string());
 
             let queue_info =
@@ -3097,7 +5255,7 @@ fn draw_enhanced_connections_table(f: &mut Frame, area: Rect, state: &DashboardS
             Constraint::Length(18), // Remote Address
             Constraint::Length(12), // State
             Constraint::Length(8),  // RTT
-            Constraint::Length(10), // Bandwidth
+            Constraint::Length(12), // Bandwidth
             Constraint::Length(8),  // Queue
             Constraint::Length(8),  // Retrans/Lost
             Constraint::Min(12),    // Process
@@ -3118,7 +5276,7 @@ fn draw_enhanced_connections_table(f: &mut Frame, area: Rect, state: &DashboardS
         ])
         .style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     )
@@ -3145,32 +5303,75 @@ fn draw_interfaces_panel(
         ])
         .split(area);
 
+    let row_style = |row: usize| {
+        if row == state.selected_item {
+            Style::default()
+                .bg(state.theme.info_color)
+                .fg(state.theme.text_color)
+        } else {
+            Style::default().fg(state.theme.text_color)
+        }
+    };
+    let traffic_info = |calculator: Option<&StatsCalculator>| match calculator {
+        Some(calculator) => {
+            let (current_in, current_out) = calculator.current_speed();
+            format!(
+                " ({} ↓ {} ↑)",
+                format_rate(current_in, &state.traffic_unit),
+                format_rate(current_out, &state.traffic_unit)
+            )
+        }
+        None => " (No data)".to_string(),
+    };
+
+    // The total device heads the list, and shifts the interfaces' rows down by one
+    let total_row = state
+        .total_device
+        .as_ref()
+        .filter(|_| state.total_row_shown());
+    let first_row = usize::from(total_row.is_some());
+    let mut interface_items: Vec<ListItem> = total_row
+        .map(|total| {
+            ListItem::new(format!(
+                "Σ {}{}",
+                total.device.name,
+                traffic_info(Some(total.calculator()))
+            ))
+            .style(row_style(0).add_modifier(Modifier::BOLD))
+        })
+        .into_iter()
+        .collect();
+
     // Interface list
-    let interface_items: Vec<ListItem> = state
-        .devices
-        .iter()
-        .enumerate()
-        .map(|(i, device)| {
-            let style = if i == state.selected_item {
-                Style::default().bg(Color::Blue).fg(Color::White)
-            } else {
-                Style::default().fg(Color::White)
+    interface_items.extend(state.interface_rows().into_iter().enumerate().map(
+        |(i, (index, depth))| {
+            let device = &state.devices[index];
+            let style = row_style(first_row + i);
+            let traffic_info = traffic_info(stats_calculators.get(&device.name));
+
+            // Children hang off their parent; a parent shows whether they are folded away
+            let indent = match depth {
+                0 => String::new(),
+                depth => format!("{}└ ", "  ".repeat(depth - 1)),
             };
-
-            let traffic_info = if let Some(calculator) = stats_calculators.get(&device.name) {
-                let (current_in, current_out) = calculator.current_speed();
-                format!(
-                    " ({}/s ↓ {}/s ↑)",
-                    format_bytes(current_in),
-                    format_bytes(current_out)
-                )
-            } else {
-                " (No data)".to_string()
+            let has_children = state
+                .devices
+                .iter()
+                .any(|child| child.info.parent.as_ref() == Some(&device.name));
+            let fold = match has_children {
+                false => "",
+                true if state.collapsed_interfaces.contains(&device.name) => "▸ ",
+                true => "▾ ",
             };
 
-            ListItem::new(format!("{}{}", device.name, traffic_info)).style(style)
-        })
-        .collect();
+            ListItem::new(format!(
+                "{indent}{fold}{}{}",
+                device.display_name(),
+                traffic_info
+            ))
+            .style(style)
+        },
+    ));
 
     let interface_list = List::new(interface_items)
         .block(
@@ -3178,129 +5379,677 @@ fn draw_interfaces_panel(
                 .borders(Borders::ALL)
                 .title("Network Interfaces"),
         )
-        .highlight_style(Style::default().bg(Color::Blue));
+        .highlight_style(Style::default().bg(state.theme.info_color));
+
+    f.render_stateful_widget(interface_list, chunks[0], &mut state.list_state);
+
+    // Interface details
+    if let Some(total) = state
+        .total_device
+        .as_ref()
+        .filter(|_| state.total_selected())
+    {
+        draw_interface_details(f, chunks[1], &total.device, Some(total.calculator()), state);
+    } else if let Some(index) = state.selected_device() {
+        let device = &state.devices[index];
+        draw_interface_details(
+            f,
+            chunks[1],
+            device,
+            stats_calculators.get(&device.name),
+            state,
+        );
+    }
+}
+
+fn draw_interface_details(
+    f: &mut Frame,
+    area: Rect,
+    device: &Device,
+    calculator: Option<&StatsCalculator>,
+    state: &DashboardState,
+) {
+    let theme = &state.theme;
+    let unit = &state.traffic_unit;
+    if let Some(calculator) = calculator {
+        let (current_in, current_out) = calculator.current_speed();
+        let (avg_in, avg_out) = calculator.average_speed();
+        let ema_alpha = state
+            .config
+            .as_ref()
+            .map_or(crate::stats::DEFAULT_EMA_ALPHA, |config| config.ema_alpha);
+        let (ema_in, ema_out) = calculator.ema_speed(ema_alpha);
+        let (_min_in, _min_out) = calculator.min_speed();
+        let (max_in, max_out) = calculator.max_speed();
+        let (total_in, total_out) = calculator.total_bytes();
+
+        let mut details_text = vec![
+            Line::from(vec![
+                Span::styled("Interface: ", Style::default().fg(theme.accent_color)),
+                Span::styled(
+                    &device.name,
+                    Style::default()
+                        .fg(theme.text_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Current Traffic:",
+                Style::default()
+                    .fg(theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![
+                Span::styled("  In:  ", Style::default().fg(theme.ok_color)),
+                Span::styled(
+                    format_rate(current_in, unit),
+                    state.changes.style(
+                        &format!("{}/in", device.name),
+                        Style::default().fg(theme.text_color),
+                    ),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Out: ", Style::default().fg(theme.critical_color)),
+                Span::styled(
+                    format_rate(current_out, unit),
+                    state.changes.style(
+                        &format!("{}/out", device.name),
+                        Style::default().fg(theme.text_color),
+                    ),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Average Traffic:",
+                Style::default()
+                    .fg(theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![
+                Span::styled("  In:  ", Style::default().fg(theme.ok_color)),
+                Span::styled(
+                    format_rate(avg_in, unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Out: ", Style::default().fg(theme.critical_color)),
+                Span::styled(
+                    format_rate(avg_out, unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    format!("  EMA (α={ema_alpha}): "),
+                    Style::default().fg(theme.accent_color),
+                ),
+                Span::styled(
+                    format!(
+                        "{} in, {} out",
+                        format_rate(ema_in, unit),
+                        format_rate(ema_out, unit)
+                    ),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Peak Traffic:",
+                Style::default()
+                    .fg(theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![
+                Span::styled("  In:  ", Style::default().fg(theme.ok_color)),
+                Span::styled(
+                    format_rate(max_in, unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Out: ", Style::default().fg(theme.critical_color)),
+                Span::styled(
+                    format_rate(max_out, unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Total Data:",
+                Style::default()
+                    .fg(theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![
+                Span::styled("  In:  ", Style::default().fg(theme.ok_color)),
+                Span::styled(
+                    format_volume(total_in, &state.data_unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Out: ", Style::default().fg(theme.critical_color)),
+                Span::styled(
+                    format_volume(total_out, &state.data_unit),
+                    Style::default().fg(theme.text_color),
+                ),
+            ]),
+        ];
+        details_text.extend(quota_lines(device, state));
+        details_text.extend(baseline_lines(device, calculator, state));
+        details_text.extend(packet_rate_lines(calculator, state));
+        // The total device has no link of its own
+        if device.name != TOTAL_DEVICE {
+            details_text.extend(link_info_lines(device, current_in + current_out, state));
+        }
+        details_text.extend(hw_counter_lines(device, state));
+        if let Some(info) = state.wireguard.get(&device.name) {
+            details_text.extend(wireguard_peer_lines(info, state));
+        }
+
+        let details = Paragraph::new(details_text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Interface Details"),
+            )
+            .style(Style::default().fg(theme.text_color));
+
+        f.render_widget(details, area);
+    }
+}
+
+/// "Packets & Errors" section of the interface details: packets, errors and drops per
+/// second between the last two samples. Any new error is red; drops are yellow, and
+/// red from 100/s.
+fn packet_rate_lines(calculator: &StatsCalculator, state: &DashboardState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let (pps_in, pps_out) = calculator.current_pps();
+    let rate = calculator.current_error_rate();
+    let errors = rate.errors_in + rate.errors_out;
+    let drops = rate.drops_in + rate.drops_out;
+    let error_color = if errors > 0.0 {
+        theme.critical_color
+    } else {
+        theme.ok_color
+    };
+    let drop_color = if drops >= 100.0 {
+        theme.critical_color
+    } else if drops > 0.0 {
+        theme.warning_color
+    } else {
+        theme.ok_color
+    };
+
+    let line = |label: &str, text: String, color: Color| {
+        Line::from(vec![
+            Span::styled(label.to_string(), Style::default().fg(theme.accent_color)),
+            Span::styled(text, Style::default().fg(color)),
+        ])
+    };
+    vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Packets & Errors:",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        line(
+            "  Packets: ",
+            format!("{pps_in:.0}/s in, {pps_out:.0}/s out"),
+            theme.text_color,
+        ),
+        line(
+            "  Errors:  ",
+            format!("{:.1}/s in, {:.1}/s out", rate.errors_in, rate.errors_out),
+            error_color,
+        ),
+        line(
+            "  Drops:   ",
+            format!("{:.1}/s in, {:.1}/s out", rate.drops_in, rate.drops_out),
+            drop_color,
+        ),
+    ]
+}
+
+/// "Quota" section of the interface details: used, limit and share of each quota
+/// with a bar, and the time until it resets. Empty without quotas.
+fn quota_lines(device: &Device, state: &DashboardState) -> Vec<Line<'static>> {
+    const BAR_WIDTH: usize = 10;
+    let Some(tracker) = state.quotas.get(&device.name) else {
+        return Vec::new();
+    };
+    let theme = &state.theme;
+    let now = chrono::Utc::now();
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Quota:",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
+    for period in QuotaPeriod::ALL {
+        let Some(limit) = state.quota_limits.get(period) else {
+            continue;
+        };
+        let used = tracker.used(period, now);
+        let fraction = quota::used_fraction(used, limit);
+        let color = if fraction >= 1.0 {
+            theme.critical_color
+        } else if fraction >= quota::QUOTA_WARNING {
+            theme.warning_color
+        } else {
+            theme.ok_color
+        };
+        let filled = ((fraction * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        let resets_in = (period.next_reset(now) - now).to_std().unwrap_or_default();
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<8} ", format!("{}:", period.as_str())),
+                Style::default().fg(theme.accent_color),
+            ),
+            Span::styled(
+                format!(
+                    "{} / {} ",
+                    format_volume(used, &state.data_unit),
+                    format_volume(limit, &state.data_unit)
+                ),
+                Style::default().fg(theme.text_color),
+            ),
+            Span::styled("█".repeat(filled), Style::default().fg(color)),
+            Span::styled(
+                "░".repeat(BAR_WIDTH - filled),
+                Style::default().fg(theme.muted_color),
+            ),
+            Span::styled(
+                format!(" {:.0}%", fraction * 100.0),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!("  resets in {}", format_age(resets_in)),
+                Style::default().fg(theme.muted_color),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// "Baseline" section of the interface details in `--compare-baseline` mode: current
+/// and average throughput and the connection metrics next to their deviation from the
+/// learned means. Empty when the baseline doesn't cover the interface.
+fn baseline_lines(
+    device: &Device,
+    calculator: &StatsCalculator,
+    state: &DashboardState,
+) -> Vec<Line<'static>> {
+    let Some(learned) = state
+        .compare_baseline
+        .as_ref()
+        .and_then(|baseline| baseline.interfaces.get(&device.name))
+    else {
+        return Vec::new();
+    };
+    let theme = &state.theme;
+    let unit = &state.traffic_unit;
+    let (current_in, current_out) = calculator.current_speed();
+    let (avg_in, avg_out) = calculator.average_speed();
+    let (connections, remote_ips) = connection_metrics(state.connection_monitor.get_connections());
+
+    let line = |label: &str, spans: Vec<Span<'static>>| {
+        let mut line = vec![Span::styled(
+            label.to_string(),
+            Style::default().fg(theme.accent_color),
+        )];
+        line.extend(spans);
+        Line::from(line)
+    };
+    vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Baseline:",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        line(
+            "  Current:    ",
+            format_with_deviation(
+                format_rate(current_in + current_out, unit),
+                current_in + current_out,
+                &learned.bytes_per_sec,
+                theme.text_color,
+                theme,
+            ),
+        ),
+        line(
+            "  Average:    ",
+            format_with_deviation(
+                format_rate(avg_in + avg_out, unit),
+                avg_in + avg_out,
+                &learned.bytes_per_sec,
+                theme.text_color,
+                theme,
+            ),
+        ),
+        line(
+            "  Learned:    ",
+            vec![Span::styled(
+                format!(
+                    "{} ± {}",
+                    format_rate(learned.bytes_per_sec.mean as u64, unit),
+                    format_rate(learned.bytes_per_sec.std_dev as u64, unit)
+                ),
+                Style::default().fg(theme.muted_color),
+            )],
+        ),
+        line(
+            "  Conns:      ",
+            format_with_deviation(
+                connections.to_string(),
+                connections as u64,
+                &learned.connections,
+                theme.text_color,
+                theme,
+            ),
+        ),
+        line(
+            "  Remote IPs: ",
+            format_with_deviation(
+                remote_ips.to_string(),
+                remote_ips as u64,
+                &learned.unique_remote_ips,
+                theme.text_color,
+                theme,
+            ),
+        ),
+    ]
+}
 
-    f.render_stateful_widget(interface_list, chunks[0], &mut state.list_state);
+/// `text` (the formatted `value`) in `color`, followed by how far `value` is from the
+/// baseline mean, e.g. `120 KB/s (+85%)`. The deviation is green within 1σ, yellow
+/// to 2σ and red beyond.
+fn format_with_deviation(
+    text: String,
+    value: u64,
+    baseline: &MetricBaseline,
+    color: Color,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let percent = Baseline::deviation_percent(value, baseline.mean.round() as u64);
+    let deviation = if percent.is_infinite() {
+        " (+∞%)".to_string()
+    } else {
+        format!(" ({percent:+.0}%)")
+    };
+    vec![
+        Span::styled(text, Style::default().fg(color)),
+        Span::styled(
+            deviation,
+            Style::default().fg(deviation_color(baseline.z_score(value), theme)),
+        ),
+    ]
+}
 
-    // Interface details
-    if let Some(device) = state.devices.get(state.selected_item) {
-        draw_interface_details(f, chunks[1], device, stats_calculators);
+/// Green within 1σ of the baseline, yellow to 2σ, red beyond
+fn deviation_color(z_score: f64, theme: &Theme) -> Color {
+    match z_score.abs() {
+        z if z > 2.0 => theme.critical_color,
+        z if z > 1.0 => theme.warning_color,
+        _ => theme.ok_color,
     }
 }
 
-fn draw_interface_details(
-    f: &mut Frame,
-    area: Rect,
-    device: &Device,
+/// Interfaces whose current throughput is more than 2σ from the `--compare-baseline`
+/// mean, for the Overview's health status
+fn baseline_deviations(
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
-) {
-    if let Some(calculator) = stats_calculators.get(&device.name) {
-        let (current_in, current_out) = calculator.current_speed();
-        let (avg_in, avg_out) = calculator.average_speed();
-        let (_min_in, _min_out) = calculator.min_speed();
-        let (max_in, max_out) = calculator.max_speed();
-        let (total_in, total_out) = calculator.total_bytes();
+) -> usize {
+    let Some(baseline) = &state.compare_baseline else {
+        return 0;
+    };
+    state
+        .devices
+        .iter()
+        .filter(|device| {
+            let (Some(learned), Some(calculator)) = (
+                baseline.interfaces.get(&device.name),
+                stats_calculators.get(&device.name),
+            ) else {
+                return false;
+            };
+            // The first sample has no rate yet
+            if calculator.sample_count() < 2 {
+                return false;
+            }
+            let (speed_in, speed_out) = calculator.current_speed();
+            learned.bytes_per_sec.z_score(speed_in + speed_out).abs() > 2.0
+        })
+        .count()
+}
 
-        let details_text = vec![
-            Line::from(vec![
-                Span::styled("Interface: ", Style::default().fg(Color::Cyan)),
-                Span::styled(
-                    &device.name,
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Current Traffic:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(vec![
-                Span::styled("  In:  ", Style::default().fg(Color::Green)),
-                Span::styled(
-                    format!("{}/s", format_bytes(current_in)),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("  Out: ", Style::default().fg(Color::Red)),
-                Span::styled(
-                    format!("{}/s", format_bytes(current_out)),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Average Traffic:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(vec![
-                Span::styled("  In:  ", Style::default().fg(Color::Green)),
-                Span::styled(
-                    format!("{}/s", format_bytes(avg_in)),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("  Out: ", Style::default().fg(Color::Red)),
-                Span::styled(
-                    format!("{}/s", format_bytes(avg_out)),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Peak Traffic:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(vec![
-                Span::styled("  In:  ", Style::default().fg(Color::Green)),
-                Span::styled(
-                    format!("{}/s", format_bytes(max_in)),
-                    Style::default().fg(Color::White),
+/// "Link" section of the interface details: kind, speed, duplex, MTU, MAC and state,
+/// and the current traffic as a share of the link speed
+fn link_info_lines(device: &Device, current: u64, state: &DashboardState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let info = &device.info;
+    let unknown = || "unknown".to_string();
+    let speed = match info.speed_mbps {
+        Some(speed) => format!("{speed} Mbit/s"),
+        None => "speed unknown".to_string(),
+    };
+    let duplex = info
+        .duplex
+        .as_ref()
+        .map_or(String::new(), |duplex| format!(" {duplex} duplex"));
+    let link_state = match (info.operstate.as_deref(), info.carrier) {
+        (Some(operstate), Some(true)) => format!("{operstate}, carrier"),
+        (Some(operstate), Some(false)) => format!("{operstate}, no carrier"),
+        (Some(operstate), None) => operstate.to_string(),
+        (None, _) => unknown(),
+    };
+    let utilization = current as f64 * 100.0 / info.capacity() as f64;
+    let capacity = match info.speed_mbps {
+        Some(speed) => format!("{speed} Mbit/s"),
+        None => format!(
+            "{} Mbit/s (assumed)",
+            crate::device::DEFAULT_LINK_SPEED_MBPS
+        ),
+    };
+
+    let label =
+        |text: &str| Span::styled(text.to_string(), Style::default().fg(theme.accent_color));
+    let value = |text: String| Span::styled(text, Style::default().fg(theme.text_color));
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Link:",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            label("  Type: "),
+            value(format!("{}, {speed}{duplex}", info.kind.as_str())),
+        ]),
+        Line::from(vec![
+            label("  MTU: "),
+            value(info.mtu.map_or_else(unknown, |mtu| mtu.to_string())),
+            label("  MAC: "),
+            value(info.mac.clone().unwrap_or_else(unknown)),
+        ]),
+        Line::from(vec![label("  State: "), value(link_state)]),
+    ];
+    if let Some(addresses) = state.interface_addresses.get(&device.name) {
+        let list = |v4: bool| {
+            let matching: Vec<String> = addresses
+                .iter()
+                .filter(|address| address.is_ipv4() == v4)
+                .map(ToString::to_string)
+                .collect();
+            if matching.is_empty() {
+                "none".to_string()
+            } else {
+                matching.join(", ")
+            }
+        };
+        lines.push(Line::from(vec![label("  IPv4: "), value(list(true))]));
+        lines.push(Line::from(vec![label("  IPv6: "), value(list(false))]));
+    }
+    lines.extend([Line::from(vec![
+        label("  Utilization: "),
+        value(format!("{utilization:.1}% of {capacity}")),
+    ])]);
+
+    if let Some(wireless) = &device.wireless {
+        // Roughly where Wi-Fi goes from solid to usable to unreliable
+        let signal_color = match wireless.rssi_dbm {
+            Some(rssi) if rssi >= -67 => theme.ok_color,
+            Some(rssi) if rssi >= -80 => theme.warning_color,
+            Some(_) => theme.critical_color,
+            None => theme.muted_color,
+        };
+        let dbm = |value: Option<i32>| value.map_or_else(unknown, |value| format!("{value} dBm"));
+        lines.push(Line::from(vec![
+            label("  Signal: "),
+            Span::styled(dbm(wireless.rssi_dbm), Style::default().fg(signal_color)),
+            label("  Noise: "),
+            value(dbm(wireless.noise_dbm)),
+            label("  SNR: "),
+            value(
+                wireless
+                    .snr_db()
+                    .map_or_else(unknown, |snr| format!("{snr} dB")),
+            ),
+        ]));
+        if wireless.tx_rate_mbps.is_some() || wireless.channel.is_some() {
+            lines.push(Line::from(vec![
+                label("  Tx rate: "),
+                value(
+                    wireless
+                        .tx_rate_mbps
+                        .map_or_else(unknown, |rate| format!("{rate:.0} Mbit/s")),
                 ),
-            ]),
-            Line::from(vec![
-                Span::styled("  Out: ", Style::default().fg(Color::Red)),
-                Span::styled(
-                    format!("{}/s", format_bytes(max_out)),
-                    Style::default().fg(Color::White),
+                label("  Channel: "),
+                value(
+                    wireless
+                        .channel
+                        .map_or_else(unknown, |channel| channel.to_string()),
                 ),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Total Data:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(vec![
-                Span::styled("  In:  ", Style::default().fg(Color::Green)),
-                Span::styled(format_bytes(total_in), Style::default().fg(Color::White)),
-            ]),
-            Line::from(vec![
-                Span::styled("  Out: ", Style::default().fg(Color::Red)),
-                Span::styled(format_bytes(total_out), Style::default().fg(Color::White)),
-            ]),
-        ];
+            ]));
+        }
+    }
+    lines
+}
 
-        let details = Paragraph::new(details_text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Interface Details"),
-            )
-            .style(Style::default().fg(Color::White));
+/// "HW Counters" section of the interface details: the driver's largest error
+/// counters, red where they grew since the previous read
+fn hw_counter_lines(device: &Device, state: &DashboardState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "HW Counters:",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
 
-        f.render_widget(details, area);
+    let counters = device.hw_error_counters(10);
+    if device.hw_stats.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  HW stats unavailable",
+            Style::default().fg(theme.muted_color),
+        )));
+    } else if counters.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  No errors in {} driver counters", device.hw_stats.len()),
+            Style::default().fg(theme.ok_color),
+        )));
+    }
+    for (name, value, grew) in counters {
+        let color = if grew {
+            theme.critical_color
+        } else {
+            theme.text_color
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {name}: "),
+                Style::default().fg(theme.accent_color),
+            ),
+            Span::styled(value.to_string(), Style::default().fg(color)),
+        ]));
+    }
+    lines
+}
+
+/// "WireGuard Peers" section of the interface details: key and endpoint, then traffic
+/// and how long ago the last handshake was
+fn wireguard_peer_lines(info: &WireGuardInfo, state: &DashboardState) -> Vec<Line<'static>> {
+    let theme = &state.theme;
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("WireGuard Peers ({}):", info.peers.len()),
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
+
+    for peer in &info.peers {
+        let key: String = peer.public_key.chars().take(8).collect();
+        let endpoint = peer
+            .endpoint
+            .map_or_else(|| "(no endpoint)".to_string(), |addr| addr.to_string());
+        let handshake = match peer.last_handshake_time {
+            Some(time) => format!(
+                "handshake {} ago",
+                format_age(time.elapsed().unwrap_or_default())
+            ),
+            None => "no handshake".to_string(),
+        };
+        let handshake_color = if peer.last_handshake_time.is_some() {
+            theme.text_color
+        } else {
+            theme.warning_color
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {key}… "),
+                Style::default().fg(theme.accent_color),
+            ),
+            Span::styled(endpoint, Style::default().fg(theme.text_color)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("    ↓ ", Style::default().fg(theme.ok_color)),
+            Span::styled(
+                format_volume(peer.bytes_received, &state.data_unit),
+                Style::default().fg(theme.text_color),
+            ),
+            Span::styled("  ↑ ", Style::default().fg(theme.critical_color)),
+            Span::styled(
+                format_volume(peer.bytes_sent, &state.data_unit),
+                Style::default().fg(theme.text_color),
+            ),
+            Span::styled(
+                format!("  {handshake}"),
+                Style::default().fg(handshake_color),
+            ),
+        ]));
     }
+    lines
 }
 
 #[allow(dead_code)]
@@ -3317,16 +6066,29 @@ fn draw_interface_list(
             let (current_in, current_out, status) =
                 if let Some(calculator) = stats_calculators.get(&device.name) {
                     let (curr_in, curr_out) = calculator.current_speed();
-                    (format_bytes(curr_in), format_bytes(curr_out), "Active")
+                    (
+                        format_rate(curr_in, &state.traffic_unit),
+                        format_rate(curr_out, &state.traffic_unit),
+                        "Active",
+                    )
                 } else {
-                    ("0 B".to_string(), "0 B".to_string(), "Inactive")
+                    (
+                        format_rate(0, &state.traffic_unit),
+                        format_rate(0, &state.traffic_unit),
+                        "Inactive",
+                    )
                 };
 
+            let changed = |direction: &str| {
+                state
+                    .changes
+                    .style(&format!("{}/{direction}", device.name), Style::default())
+            };
             Row::new(vec![
-                device.name.clone(),
-                format!("{}/s", current_in),
-                format!("{}/s", current_out),
-                status.to_string(),
+                Cell::from(device.name.clone()),
+                Cell::from(current_in).style(changed("in")),
+                Cell::from(current_out).style(changed("out")),
+                Cell::from(status),
             ])
         })
         .collect();
@@ -3343,7 +6105,7 @@ fn draw_interface_list(
     .header(
         Row::new(vec!["Interface", "In", "Out", "Status"]).style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     )
@@ -3357,6 +6119,33 @@ fn draw_interface_list(
 }
 
 fn draw_connections_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
+    // Without ss the panel still works, but RTT and throughput columns stay empty
+    let area = match state.connection_monitor.fallback_notice() {
+        Some(notice) if area.height > 1 => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            f.render_widget(
+                Paragraph::new(format!("⚠ {notice}"))
+                    .style(Style::default().fg(state.theme.warning_color)),
+                chunks[0],
+            );
+            chunks[1]
+        }
+        _ => area,
+    };
+
+    if state.is_compact() {
+        // 80 columns only fit the list
+        if state.connections_listening {
+            draw_listening_sockets(f, area, state);
+        } else {
+            draw_connections_list(f, area, state);
+        }
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -3365,20 +6154,133 @@ fn draw_connections_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
         ])
         .split(area);
 
-    // Left: Active connections list
-    draw_connections_list(f, chunks[0], state);
+    // Left: Active connections list, or what is listening for them
+    if state.connections_listening {
+        draw_listening_sockets(f, chunks[0], state);
+    } else {
+        draw_connections_list(f, chunks[0], state);
+    }
 
     // Right: Connection statistics and analysis
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(50), // Connection stats
-            Constraint::Percentage(50), // Top remote hosts
+            Constraint::Min(6),         // Top remote hosts
+            Constraint::Length(TCP_STATE_HISTOGRAM_HEIGHT),
+            Constraint::Length(RTT_HISTOGRAM_HEIGHT),
         ])
         .split(chunks[1]);
 
     draw_connection_stats(f, right_chunks[0], state);
     draw_top_remote_hosts(f, right_chunks[1], state);
+    draw_tcp_state_histogram(f, right_chunks[2], state);
+    draw_rtt_histogram(f, right_chunks[3], state);
+}
+
+// Two states per row plus the borders
+const TCP_STATE_HISTOGRAM_HEIGHT: u16 = 5 + 2;
+const TCP_STATE_BAR_WIDTH: u32 = 8;
+
+fn draw_tcp_state_histogram(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let stats = state.panel_connection_stats();
+    let close_wait_threshold = state
+        .config
+        .as_ref()
+        .map_or(50, |config| config.close_wait_threshold);
+    let states = stats.tcp_states();
+    let widest = states
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let cell = |(tcp_state, count): &(ConnectionState, u32)| {
+        let color = match tcp_state {
+            ConnectionState::CloseWait if *count > close_wait_threshold => {
+                state.theme.warning_color
+            }
+            ConnectionState::SynReceived if *count > connections::SYN_RECV_WARNING => {
+                state.theme.critical_color
+            }
+            _ => tcp_state.color(),
+        };
+        // Any non-empty state gets at least one block
+        let width = (count * TCP_STATE_BAR_WIDTH + widest - 1) / widest;
+        vec![
+            Span::styled(
+                format!("{:<11}", tcp_state.as_str()),
+                Style::default().fg(state.theme.muted_color),
+            ),
+            Span::styled(
+                format!("{:<8}", "█".repeat(width as usize)),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!("{count:>6}  "),
+                Style::default().fg(state.theme.text_color),
+            ),
+        ]
+    };
+    let lines: Vec<Line> = states
+        .chunks(2)
+        .map(|pair| Line::from(pair.iter().flat_map(cell).collect::<Vec<_>>()))
+        .collect();
+
+    let histogram =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("TCP States"));
+    f.render_widget(histogram, area);
+}
+
+// One row per bucket plus the borders
+const RTT_HISTOGRAM_HEIGHT: u16 = connections::RTT_BUCKET_LABELS.len() as u16 + 2;
+const RTT_HISTOGRAM_BAR_WIDTH: u32 = 20;
+
+fn draw_rtt_histogram(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let buckets = state.panel_connection_stats().connections_rtt_buckets;
+    let widest = buckets.iter().copied().max().unwrap_or(0).max(1);
+
+    // Empty buckets keep their row so the axis doesn't shift between refreshes
+    let lines: Vec<Line> = connections::RTT_BUCKET_LABELS
+        .iter()
+        .zip(buckets)
+        .enumerate()
+        .map(|(i, (label, count))| {
+            let color = match i {
+                0..=3 => state.theme.ok_color,      // under 50ms
+                4..=5 => state.theme.warning_color, // 50-200ms
+                _ => state.theme.critical_color,
+            };
+            // Any non-empty bucket gets at least one block
+            let width = (count * RTT_HISTOGRAM_BAR_WIDTH + widest - 1) / widest;
+            Line::from(vec![
+                Span::styled(
+                    format!("{label:>9} "),
+                    Style::default().fg(state.theme.muted_color),
+                ),
+                Span::styled(
+                    format!("{:<20}", "█".repeat(width as usize)),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    if count > 0 {
+                        format!(" {count}")
+                    } else {
+                        String::new()
+                    },
+                    Style::default().fg(state.theme.text_color),
+                ),
+            ])
+        })
+        .collect();
+
+    let histogram = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("RTT Distribution"),
+    );
+    f.render_widget(histogram, area);
 }
 
 fn draw_processes_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
@@ -3422,7 +6324,7 @@ fn draw_system_panel(
                 Line::from(vec![Span::styled(
                     "🛡️  Safe System Monitor",
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(state.theme.header_color)
                         .add_modifier(Modifier::BOLD),
                 )]),
                 Line::from(""),
@@ -3432,7 +6334,7 @@ fn draw_system_panel(
                 Line::from(""),
                 Line::from(vec![Span::styled(
                     "Errors encountered:",
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(state.theme.critical_color),
                 )]),
             ];
 
@@ -3466,54 +6368,75 @@ fn draw_system_panel(
         Line::from(vec![Span::styled(
             "🖥️  System Information",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Hostname: ", Style::default().fg(Color::Yellow)),
-            Span::styled(&system_info.hostname, Style::default().fg(Color::Green)),
-            Span::styled("    OS: ", Style::default().fg(Color::Yellow)),
+            Span::styled("Hostname: ", Style::default().fg(state.theme.warning_color)),
+            Span::styled(
+                &system_info.hostname,
+                Style::default().fg(state.theme.ok_color),
+            ),
+            Span::styled("    OS: ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("{} {}", system_info.os_name, system_info.os_version),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Architecture: ", Style::default().fg(Color::Yellow)),
-            Span::styled(&system_info.architecture, Style::default().fg(Color::Green)),
-            Span::styled("    Kernel: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Architecture: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
+            Span::styled(
+                &system_info.architecture,
+                Style::default().fg(state.theme.ok_color),
+            ),
+            Span::styled(
+                "    Kernel: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 &system_info.kernel_version,
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("CPU: ", Style::default().fg(Color::Yellow)),
-            Span::styled(&system_info.cpu_model, Style::default().fg(Color::Green)),
+            Span::styled("CPU: ", Style::default().fg(state.theme.warning_color)),
+            Span::styled(
+                &system_info.cpu_model,
+                Style::default().fg(state.theme.ok_color),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("Cores: ", Style::default().fg(Color::Yellow)),
+            Span::styled("Cores: ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("{} physical", system_info.cpu_cores),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
+            ),
+            Span::styled(
+                "    Threads: ",
+                Style::default().fg(state.theme.warning_color),
             ),
-            Span::styled("    Threads: ", Style::default().fg(Color::Yellow)),
             Span::styled(
                 format!("{} logical", system_info.cpu_threads),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Memory: ", Style::default().fg(Color::Yellow)),
+            Span::styled("Memory: ", Style::default().fg(state.theme.warning_color)),
+            Span::styled(
+                format_bytes(system_info.total_memory),
+                Style::default().fg(state.theme.ok_color),
+            ),
             Span::styled(
-                crate::safe_system::SafeSystemMonitor::format_bytes(system_info.total_memory),
-                Style::default().fg(Color::Green),
+                "    Uptime: ",
+                Style::default().fg(state.theme.warning_color),
             ),
-            Span::styled("    Uptime: ", Style::default().fg(Color::Yellow)),
             Span::styled(
                 crate::safe_system::SafeSystemMonitor::format_uptime(system_info.uptime),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
     ];
@@ -3527,61 +6450,103 @@ fn draw_system_panel(
         Line::from(vec![Span::styled(
             "📊 Resource Usage",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("CPU Usage: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "CPU Usage: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{:.1}%", safe_stats.cpu_usage_percent),
-                if safe_stats.cpu_usage_percent > 80.0 {
-                    Style::default().fg(Color::Red)
-                } else if safe_stats.cpu_usage_percent > 60.0 {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default().fg(Color::Green)
-                },
+                state.changes.style(
+                    "cpu",
+                    if safe_stats.cpu_usage_percent > 80.0 {
+                        Style::default().fg(state.theme.critical_color)
+                    } else if safe_stats.cpu_usage_percent > 60.0 {
+                        Style::default().fg(state.theme.warning_color)
+                    } else {
+                        Style::default().fg(state.theme.ok_color)
+                    },
+                ),
+            ),
+            Span::styled(
+                "    Load Avg: ",
+                Style::default().fg(state.theme.warning_color),
             ),
-            Span::styled("    Load Avg: ", Style::default().fg(Color::Yellow)),
             Span::styled(
                 format!(
                     "{:.2}, {:.2}, {:.2}",
                     safe_stats.load_average.0, safe_stats.load_average.1, safe_stats.load_average.2
                 ),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Memory: ", Style::default().fg(Color::Yellow)),
+            Span::styled("Memory: ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("{:.1}%", safe_stats.memory_usage_percent),
-                if safe_stats.memory_usage_percent > 90.0 {
-                    Style::default().fg(Color::Red)
-                } else if safe_stats.memory_usage_percent > 70.0 {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default().fg(Color::Green)
-                },
+                state.changes.style(
+                    "memory",
+                    if safe_stats.memory_usage_percent > 90.0 {
+                        Style::default().fg(state.theme.critical_color)
+                    } else if safe_stats.memory_usage_percent > 70.0 {
+                        Style::default().fg(state.theme.warning_color)
+                    } else {
+                        Style::default().fg(state.theme.ok_color)
+                    },
+                ),
+            ),
+            Span::styled("    Used: ", Style::default().fg(state.theme.warning_color)),
+            Span::styled(
+                format_bytes(safe_stats.memory_used),
+                Style::default().fg(state.theme.ok_color),
             ),
-            Span::styled("    Used: ", Style::default().fg(Color::Yellow)),
             Span::styled(
-                crate::safe_system::SafeSystemMonitor::format_bytes(safe_stats.memory_used),
-                Style::default().fg(Color::Green),
+                " / Available: ",
+                Style::default().fg(state.theme.warning_color),
             ),
-            Span::styled(" / Available: ", Style::default().fg(Color::Yellow)),
             Span::styled(
-                crate::safe_system::SafeSystemMonitor::format_bytes(safe_stats.memory_available),
-                Style::default().fg(Color::Green),
+                format_bytes(safe_stats.memory_available),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Disk Usage: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Disk Usage: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{} mount points", safe_stats.disk_usage.len()),
-                Style::default().fg(Color::Green),
+                Style::default().fg(state.theme.ok_color),
             ),
         ]),
+        match safe_stats.socket_stats {
+            Some(sockets) => Line::from(vec![
+                Span::styled("Sockets: ", Style::default().fg(state.theme.warning_color)),
+                Span::styled(
+                    format!(
+                        "TCP {} ({}, {} orphaned)    UDP {} ({})",
+                        sockets.tcp_sockets,
+                        format_bytes(SocketStats::memory_bytes(sockets.tcp_memory_pages)),
+                        sockets.orphan_tcp,
+                        sockets.udp_sockets,
+                        format_bytes(SocketStats::memory_bytes(sockets.udp_memory_pages)),
+                    ),
+                    Style::default().fg(if sockets.orphan_tcp > ORPHAN_TCP_WARNING {
+                        state.theme.critical_color
+                    } else {
+                        state.theme.ok_color
+                    }),
+                ),
+            ]),
+            None => Line::from(vec![
+                Span::styled("Sockets: ", Style::default().fg(state.theme.warning_color)),
+                Span::styled("n/a", Style::default().fg(state.theme.muted_color)),
+            ]),
+        },
     ];
 
     let usage_paragraph = Paragraph::new(usage_text).block(
@@ -3602,9 +6567,7 @@ fn draw_system_panel(
                 Cell::from(proc.name.chars().take(14).collect::<String>()), // Safe character truncation
                 Cell::from(format!("{:.1}%", proc.cpu_percent)),
                 Cell::from(format!("{:.1}%", proc.memory_percent)),
-                Cell::from(crate::safe_system::SafeSystemMonitor::format_bytes(
-                    proc.memory_rss,
-                )),
+                Cell::from(format_bytes(proc.memory_rss)),
                 Cell::from(proc.user.chars().take(11).collect::<String>()), // Safe character truncation
                 Cell::from(proc.state.clone()),
             ])
@@ -3626,7 +6589,7 @@ fn draw_system_panel(
     .header(
         Row::new(vec!["PID", "Name", "CPU%", "Mem%", "RSS", "User", "State"]).style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     )
@@ -3635,39 +6598,81 @@ fn draw_system_panel(
             .borders(Borders::ALL)
             .title("🔝 Top Processes by CPU"),
     )
-    .row_highlight_style(Style::default().bg(Color::DarkGray));
+    .row_highlight_style(Style::default().bg(state.theme.selected_row_bg));
 
     f.render_stateful_widget(process_table, chunks[2], &mut state.table_state);
 }
 
+/// Side-by-side view: one column per interface, the focused one highlighted
+fn draw_graph_columns(
+    f: &mut Frame,
+    area: Rect,
+    state: &DashboardState,
+    stats_calculators: &HashMap<String, StatsCalculator>,
+) {
+    let shown = state.graph_columns();
+    let n = shown.len();
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(100 / n as u16); n])
+        .split(area);
+
+    for (column, index) in columns.iter().zip(shown) {
+        let device = &state.devices[index];
+        display::draw_mini_traffic_graph(
+            f,
+            *column,
+            &device.name,
+            stats_calculators.get(&device.name),
+            state,
+            !state.graph_total && index == state.current_device_index,
+        );
+    }
+}
+
 fn draw_graphs_panel(
     f: &mut Frame,
     area: Rect,
     state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
-    if let Some(device) = state.devices.get(state.current_device_index) {
-        if let Some(calculator) = stats_calculators.get(&device.name) {
-            // Debug: Check if we have graph data
-            let graph_data_in = calculator.graph_data_in();
-            let graph_data_out = calculator.graph_data_out();
+    if state.graphs_multi_view && !state.devices.is_empty() {
+        let area = if state.is_compact() {
+            Rect {
+                height: area.height.min(COMPACT_PANEL_ROWS),
+                ..area
+            }
+        } else {
+            area
+        };
+        draw_graph_columns(f, area, state, stats_calculators);
+        return;
+    }
 
-            if graph_data_in.is_empty() && graph_data_out.is_empty() {
+    if let Some((device, calculator)) = state.graphed_device(stats_calculators) {
+        if let Some(calculator) = calculator {
+            // Debug: Check if we have graph data (both directions get a point per sample)
+            let graph_points = calculator.graph_len();
+
+            if graph_points == 0 {
                 // Show debug info if no graph data is available
                 let debug_text = vec![
                     Line::from(vec![Span::styled(
                         "📊 Traffic Graphs (Debug Mode)",
                         Style::default()
-                            .fg(Color::Yellow)
+                            .fg(state.theme.header_color)
                             .add_modifier(Modifier::BOLD),
                     )]),
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("Current Device: ", Style::default().fg(Color::Cyan)),
+                        Span::styled(
+                            "Current Device: ",
+                            Style::default().fg(state.theme.accent_color),
+                        ),
                         Span::styled(
                             &device.name,
                             Style::default()
-                                .fg(Color::Green)
+                                .fg(state.theme.ok_color)
                                 .add_modifier(Modifier::BOLD),
                         ),
                         Span::styled(
@@ -3676,35 +6681,35 @@ fn draw_graphs_panel(
                                 state.current_device_index + 1,
                                 state.devices.len()
                             ),
-                            Style::default().fg(Color::Gray),
+                            Style::default().fg(state.theme.muted_color),
                         ),
                     ]),
                     Line::from(""),
                     Line::from(vec![Span::styled(
                         "⌨️  Controls:",
-                        Style::default().fg(Color::Cyan),
+                        Style::default().fg(state.theme.accent_color),
                     )]),
                     Line::from("  ↑/↓ or j/k - Switch between devices"),
-                    Line::from("  ←/→ - Switch between panels"),
+                    Line::from("  +/- - Zoom the time axis, ←/→ - Pan when zoomed"),
                     Line::from(""),
                     Line::from(vec![Span::styled(
                         "📈 Graph Data Status:",
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(state.theme.warning_color),
                     )]),
-                    Line::from(format!("  Incoming data points: {}", graph_data_in.len())),
-                    Line::from(format!("  Outgoing data points: {}", graph_data_out.len())),
+                    Line::from(format!("  Incoming data points: {graph_points}")),
+                    Line::from(format!("  Outgoing data points: {graph_points}")),
                     Line::from(""),
                     Line::from(vec![Span::styled(
                         "📊 Current Stats:",
-                        Style::default().fg(Color::Yellow),
+                        Style::default().fg(state.theme.warning_color),
                     )]),
                     Line::from(format!(
-                        "  Speed In: {}/s",
-                        format_bytes(calculator.current_speed().0)
+                        "  Speed In: {}",
+                        format_rate(calculator.current_speed().0, &state.traffic_unit)
                     )),
                     Line::from(format!(
-                        "  Speed Out: {}/s",
-                        format_bytes(calculator.current_speed().1)
+                        "  Speed Out: {}",
+                        format_rate(calculator.current_speed().1, &state.traffic_unit)
                     )),
                     Line::from(format!("  Total Samples: {}", calculator.sample_count())),
                     Line::from(""),
@@ -3721,8 +6726,23 @@ fn draw_graphs_panel(
                     .wrap(ratatui::widgets::Wrap { trim: true });
                 f.render_widget(debug_display, area);
             } else {
-                // We have data, try to draw the graphs
-                display::draw_traffic_graphs(f, area, &device.name, calculator, state);
+                if state.is_compact() {
+                    // No room for the protocol breakdown: the canvas gets every row
+                    let canvas = Rect {
+                        height: area.height.min(COMPACT_PANEL_ROWS),
+                        ..area
+                    };
+                    display::draw_traffic_graphs(f, canvas, &device.name, calculator, state);
+                    return;
+                }
+
+                // We have data, try to draw the graphs with the protocol breakdown below
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(10), Constraint::Length(4)])
+                    .split(area);
+                display::draw_traffic_graphs(f, chunks[0], &device.name, calculator, state);
+                draw_protocol_breakdown(f, chunks[1], state);
             }
         } else {
             // Show message when stats calculator is not available for this device
@@ -3730,18 +6750,18 @@ fn draw_graphs_panel(
                 Line::from(vec![Span::styled(
                     "📊 Traffic Graphs",
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(state.theme.header_color)
                         .add_modifier(Modifier::BOLD),
                 )]),
                 Line::from(""),
                 Line::from(vec![
-                    Span::styled("Device: ", Style::default().fg(Color::Cyan)),
-                    Span::styled(&device.name, Style::default().fg(Color::White)),
+                    Span::styled("Device: ", Style::default().fg(state.theme.accent_color)),
+                    Span::styled(&device.name, Style::default().fg(state.theme.text_color)),
                 ]),
                 Line::from(""),
                 Line::from(vec![Span::styled(
                     "⚠️ No statistics available for this device",
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.theme.warning_color),
                 )]),
                 Line::from("Statistics are being collected..."),
                 Line::from(""),
@@ -3749,7 +6769,7 @@ fn draw_graphs_panel(
                 Line::from(""),
                 Line::from(vec![Span::styled(
                     "Available devices:",
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(state.theme.accent_color),
                 )]),
             ];
 
@@ -3757,10 +6777,10 @@ fn draw_graphs_panel(
             for (i, dev) in state.devices.iter().enumerate() {
                 let style = if i == state.current_device_index {
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(state.theme.ok_color)
                         .add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::Gray)
+                    Style::default().fg(state.theme.muted_color)
                 };
                 lines.push(Line::from(vec![Span::styled(
                     format!(
@@ -3791,13 +6811,13 @@ fn draw_graphs_panel(
             Line::from(vec![Span::styled(
                 "📊 Traffic Graphs",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(state.theme.header_color)
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
             Line::from(vec![Span::styled(
                 "❌ No network devices available",
-                Style::default().fg(Color::Red),
+                Style::default().fg(state.theme.critical_color),
             )]),
             Line::from(""),
             Line::from("Possible causes:"),
@@ -3819,6 +6839,58 @@ fn draw_graphs_panel(
     }
 }
 
+/// Stacked bar of estimated bandwidth per protocol. Derived from per-connection socket
+/// estimates, not packet capture, so it is an approximation of the interface traffic.
+fn draw_protocol_breakdown(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let by_protocol = state.connection_monitor.bandwidth_by_protocol();
+    let protocols = [
+        (Protocol::Tcp, state.theme.ok_color),
+        (Protocol::Tcp6, state.theme.accent_color),
+        (Protocol::Udp, state.theme.warning_color),
+        (Protocol::Udp6, state.theme.highlight_color),
+    ];
+    let total: u64 = by_protocol.values().sum();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("🔀 Protocol Breakdown (connection-derived, not packet-captured)");
+
+    let content = if total == 0 {
+        vec![Line::from(Span::styled(
+            "No per-connection bandwidth estimates available yet",
+            Style::default().fg(state.theme.muted_color),
+        ))]
+    } else {
+        // Two columns of border; the rest is shared out proportionally
+        let width = area.width.saturating_sub(2) as u64;
+        let mut bar = Vec::new();
+        let mut legend = Vec::new();
+
+        for (protocol, color) in &protocols {
+            let bandwidth = by_protocol.get(protocol).copied().unwrap_or(0);
+            if bandwidth == 0 {
+                continue;
+            }
+
+            let cells = ((bandwidth as f64 / total as f64) * width as f64).round() as usize;
+            bar.push(Span::styled("█".repeat(cells), Style::default().fg(*color)));
+            legend.push(Span::styled(
+                format!(
+                    "■ {} {} ({:.0}%)  ",
+                    protocol.as_str(),
+                    format_bandwidth(bandwidth, &state.traffic_unit),
+                    bandwidth as f64 / total as f64 * 100.0
+                ),
+                Style::default().fg(*color),
+            ));
+        }
+
+        vec![Line::from(bar), Line::from(legend)]
+    };
+
+    f.render_widget(Paragraph::new(content).block(block), area);
+}
+
 fn draw_diagnostics_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -3833,19 +6905,50 @@ fn draw_diagnostics_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
         )
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         );
     f.render_widget(title, chunks[0]);
 
+    let http_urls: &[String] = state
+        .config
+        .as_ref()
+        .map_or(&[], |config| &config.http_check_urls);
+    let port_checks: &[String] = state
+        .config
+        .as_ref()
+        .map_or(&[], |config| &config.port_checks);
+    // Header row plus borders, capped so traceroute keeps room
+    let table_height = |rows: usize| {
+        if rows == 0 {
+            0
+        } else {
+            (rows as u16 + 3).min(12)
+        }
+    };
+    let body = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(11),
+            Constraint::Length(table_height(http_urls.len())),
+            Constraint::Length(table_height(port_checks.len())),
+            Constraint::Min(5),
+        ])
+        .split(chunks[1]);
+
     let diagnostics = &state.active_diagnostics.get_diagnostics();
+    let total_hops: u32 = diagnostics
+        .traceroute_results
+        .values()
+        .map(|r| r.total_hops)
+        .sum();
     let diagnostic_items = vec![
         ListItem::new(format!(
             "🏓 Ping Results: {} targets tested",
             diagnostics.ping_results.len()
         )),
         ListItem::new(format!(
-            "🛣️  Traceroute: {} hops to targets",
+            "🛣️  Traceroute: {total_hops} hops to {} targets",
             diagnostics.traceroute_results.len()
         )),
         ListItem::new(format!(
@@ -3885,79 +6988,598 @@ fn draw_diagnostics_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
                 .borders(Borders::ALL)
                 .title("Real-time Network Health"),
         )
-        .style(Style::default().fg(Color::White))
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(state.theme.text_color))
+        .highlight_style(Style::default().fg(state.theme.warning_color));
+
+    let health = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body[0]);
+    f.render_widget(diagnostics_list, health[0]);
+    draw_dns_activity(f, health[1], state);
+    if !http_urls.is_empty() {
+        draw_http_checks(
+            f,
+            body[1],
+            http_urls,
+            &diagnostics.http_results,
+            &state.theme,
+        );
+    }
+    if !port_checks.is_empty() {
+        draw_port_checks(f, body[2], port_checks, diagnostics, &state.theme);
+    }
+    draw_traceroute_hops(f, body[3], state);
+}
+
+/// Captured DNS traffic: failure rates, busiest names and slowest lookups. Falls back to
+/// systemd-resolved's counters when capture isn't permitted.
+fn draw_dns_activity(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let muted = Style::default().fg(theme.muted_color);
+    let monitor = state.active_diagnostics.dns_monitor();
+
+    let mut lines = Vec::new();
+    match monitor.source() {
+        DnsSource::Capture => {
+            let summary = monitor.summary();
+            let (status, color) = if summary.looks_like_outage() {
+                (
+                    "🔴 Most queries unanswered - resolvers down?",
+                    theme.critical_color,
+                )
+            } else if summary.queries == 0 {
+                ("⏳ Waiting for DNS traffic...", theme.muted_color)
+            } else {
+                ("🟢 Resolving", theme.ok_color)
+            };
+            lines.push(Line::from(Span::styled(
+                status,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(format!(
+                "Queries: {}  Answered: {}  Timeouts: {}",
+                summary.queries, summary.answered, summary.timeouts
+            )));
+            let nxdomain_color = if summary.nxdomain_rate() > 0.2 {
+                theme.warning_color
+            } else {
+                theme.text_color
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("NXDOMAIN: {:.0}%", summary.nxdomain_rate() * 100.0),
+                    Style::default().fg(nxdomain_color),
+                ),
+                Span::raw(format!(
+                    "  SERVFAIL: {}  Avg: {}",
+                    summary.servfail,
+                    summary
+                        .avg_latency
+                        .map_or("N/A".to_string(), |l| format!("{}ms", l.as_millis()))
+                )),
+            ]));
+            if !summary.top_domains.is_empty() {
+                let top: Vec<String> = summary
+                    .top_domains
+                    .iter()
+                    .map(|(name, count)| format!("{name} ({count})"))
+                    .collect();
+                lines.push(Line::from(vec![
+                    Span::styled("Top: ", muted),
+                    Span::raw(top.join(", ")),
+                ]));
+            }
+            if !summary.slowest.is_empty() {
+                lines.push(Line::from(Span::styled("Slowest:", muted)));
+                for (name, latency) in summary.slowest.iter().take(4) {
+                    lines.push(Line::from(format!(
+                        "  {:>5}ms  {name}",
+                        latency.as_millis()
+                    )));
+                }
+            }
+        }
+        DnsSource::Resolved(stats) => {
+            lines.push(Line::from(Span::styled(
+                "systemd-resolved (run with CAP_NET_RAW for per-query detail)",
+                muted,
+            )));
+            lines.push(Line::from(format!(
+                "Transactions: {} total, {} in flight",
+                stats.total_transactions, stats.current_transactions
+            )));
+            lines.push(Line::from(format!(
+                "Cache: {} entries, {:.0}% hit rate",
+                stats.cache_size,
+                stats.cache_hit_rate() * 100.0
+            )));
+        }
+        DnsSource::Pending => lines.push(Line::from(Span::styled("⏳ Starting...", muted))),
+        DnsSource::Unavailable(reason) => {
+            lines.push(Line::from(Span::styled(
+                format!("DNS activity unavailable: {reason}"),
+                muted,
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "DNS Activity (last {} min)",
+            DNS_WINDOW.as_secs() / 60
+        )))
+        .style(Style::default().fg(theme.text_color))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+/// ARP cache: which MAC answers for each address, its vendor, and whether it still
+/// answers. Failed entries are addresses that were asked for and never replied.
+fn draw_neighbors_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let theme = &state.theme;
+    let Some(entries) = &state.neighbors else {
+        let message = if state.last_neighbors_update.is_some() {
+            "Neighbor table not available on this platform"
+        } else {
+            "⏳ Reading neighbor table..."
+        };
+        let waiting = Paragraph::new(message)
+            .style(Style::default().fg(theme.muted_color))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Neighbors (ARP)"),
+            );
+        f.render_widget(waiting, area);
+        return;
+    };
+
+    let failed = entries
+        .iter()
+        .filter(|entry| entry.state == NeighborState::Failed)
+        .count();
+    let title = format!(
+        "Neighbors (ARP) - {} entries, {failed} failed · refreshed every {}s",
+        entries.len(),
+        NEIGHBORS_INTERVAL.as_secs()
+    );
+
+    let header_style = Style::default()
+        .fg(theme.header_color)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        ["IP", "MAC", "Vendor", "Interface", "State"]
+            .into_iter()
+            .map(|title| Cell::from(title).style(header_style)),
+    );
+    let rows: Vec<Row> = state
+        .listed_neighbors()
+        .into_iter()
+        .map(|entry| {
+            let (mac, vendor) = if entry.mac.is_zero() {
+                ("(incomplete)".to_string(), String::new())
+            } else {
+                let vendor = match entry.mac.vendor_prefix() {
+                    Some(vendor) => vendor.to_string(),
+                    None if entry.mac.is_locally_administered() => "(random/local)".to_string(),
+                    None => "-".to_string(),
+                };
+                (entry.mac.to_string(), vendor)
+            };
+            let state_color = match entry.state {
+                NeighborState::Reachable => theme.ok_color,
+                NeighborState::Stale => theme.warning_color,
+                NeighborState::Failed => theme.critical_color,
+            };
+            Row::new(vec![
+                Cell::from(entry.ip.to_string()),
+                Cell::from(mac),
+                Cell::from(vendor),
+                Cell::from(entry.interface.clone()),
+                Cell::from(entry.state.label()).style(Style::default().fg(state_color)),
+            ])
+        })
+        .collect();
 
-    f.render_widget(diagnostics_list, chunks[1]);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(18),
+            Constraint::Min(16),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .style(Style::default().fg(theme.text_color))
+    .row_highlight_style(Style::default().bg(theme.selected_row_bg));
+
+    let mut table_state = TableState::default().with_selected(
+        state
+            .table_state
+            .selected()
+            .map(|row| row.min(entries.len().saturating_sub(1))),
+    );
+    f.render_stateful_widget(table, area, &mut table_state);
 }
 
-fn draw_alerts_panel(
+/// HTTP check results: green for 2xx, yellow for 3xx or slow, red for errors and timeouts
+fn draw_http_checks(
     f: &mut Frame,
     area: Rect,
-    state: &DashboardState,
-    stats_calculators: &HashMap<String, StatsCalculator>,
+    urls: &[String],
+    results: &HashMap<String, crate::active_diagnostics::HttpCheckResult>,
+    theme: &Theme,
+) {
+    let header_style = Style::default()
+        .fg(theme.header_color)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        [
+            "Status", "URL", "Code", "Total", "DNS", "Connect", "TLS", "TTFB", "Cert",
+        ]
+        .into_iter()
+        .map(|title| Cell::from(title).style(header_style)),
+    );
+    let ms = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{v:.0}ms"));
+
+    let rows: Vec<Row> = urls
+        .iter()
+        .map(|url| {
+            let Some(result) = results.get(url) else {
+                return Row::new(vec![Cell::from("⏳"), Cell::from(url.as_str())])
+                    .style(Style::default().fg(theme.muted_color));
+            };
+
+            let slow = result.total_ms > crate::http_check::SLOW_RESPONSE_MS;
+            let (status, color) = match result.status_code {
+                _ if result.timed_out => ("TIMEOUT", theme.critical_color),
+                0 => ("ERROR", theme.critical_color),
+                400.. => ("FAIL", theme.critical_color),
+                300..=399 => ("REDIRECT", theme.warning_color),
+                _ if slow => ("SLOW", theme.warning_color),
+                _ => ("OK", theme.ok_color),
+            };
+            // Errors are too long for a column of their own, so they follow the URL
+            let (target, code) = match &result.error {
+                Some(error) if result.status_code == 0 => {
+                    (format!("{url} ({error})"), "-".to_string())
+                }
+                _ => (url.clone(), result.status_code.to_string()),
+            };
+            let cert = if !url.starts_with("https://") {
+                "-"
+            } else if result.tls_valid {
+                "✅"
+            } else {
+                "❌"
+            };
+
+            Row::new(vec![
+                Cell::from(status),
+                Cell::from(target),
+                Cell::from(code),
+                Cell::from(format!("{:.0}ms", result.total_ms)),
+                Cell::from(ms(result.dns_ms)),
+                Cell::from(ms(result.connect_ms)),
+                Cell::from(ms(result.tls_ms)),
+                Cell::from(ms(result.ttfb_ms)),
+                Cell::from(cert),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Min(20),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(4),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("🌍 HTTP Checks (every 5s)"),
+    );
+    f.render_widget(table, area);
+}
+
+/// `PortChecks` results: latest status, connect time percentiles and a reachability
+/// sparkline over the recent attempts
+fn draw_port_checks(
+    f: &mut Frame,
+    area: Rect,
+    targets: &[String],
+    diagnostics: &crate::active_diagnostics::ActiveDiagnostics,
+    theme: &Theme,
 ) {
+    let header_style = Style::default()
+        .fg(theme.header_color)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        [
+            "Status",
+            "Target",
+            "Connect",
+            "p50",
+            "p95",
+            "Up",
+            "Reachability",
+        ]
+        .into_iter()
+        .map(|title| Cell::from(title).style(header_style)),
+    );
+    let ms = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{v:.0}ms"));
+
+    let rows: Vec<Row> = targets
+        .iter()
+        .map(|target| {
+            let (Some(result), Some(history)) = (
+                diagnostics.port_scan_results.get(target),
+                diagnostics.port_check_history.get(target),
+            ) else {
+                return Row::new(vec![Cell::from("⏳"), Cell::from(target.as_str())])
+                    .style(Style::default().fg(theme.muted_color));
+            };
+
+            let (status, color) = match result.status {
+                PortStatus::Open => ("OPEN", theme.ok_color),
+                PortStatus::Closed => ("CLOSED", theme.critical_color),
+                PortStatus::Filtered => ("FILTERED", theme.warning_color),
+                _ => ("ERROR", theme.critical_color),
+            };
+            let sparkline: String = history
+                .reachability()
+                .iter()
+                .map(|&up| if up == 1 { '█' } else { '▁' })
+                .collect();
+
+            Row::new(vec![
+                Cell::from(status),
+                Cell::from(target.as_str()),
+                Cell::from(ms(result.response_time.map(f64::from))),
+                Cell::from(ms(history.connect_percentile(50.0))),
+                Cell::from(ms(history.connect_percentile(95.0))),
+                Cell::from(format!("{:.0}%", history.success_rate())),
+                Cell::from(sparkline),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(crate::port_check::PORT_CHECK_HISTORY as u16 + 1),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("🔌 Port Checks (every 5s)"),
+    );
+    f.render_widget(table, area);
+}
+
+/// Per-hop traceroute results with an RTT bar for each hop, scrolled with ↑/↓
+fn draw_traceroute_hops(f: &mut Frame, area: Rect, state: &DashboardState) {
+    const BAR_WIDTH: usize = 20;
+
+    let engine = &state.active_diagnostics;
+    let mut results: Vec<_> = engine
+        .get_diagnostics()
+        .traceroute_results
+        .values()
+        .collect();
+    results.sort_by(|a, b| a.target.cmp(&b.target));
+
+    let mut lines = Vec::new();
+    if results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            if engine.traceroute_in_progress() {
+                "⏳ Tracing..."
+            } else {
+                "No trace targets configured. Add them with --trace-target HOST or TraceTargets in ~/.netwatch"
+            },
+            Style::default().fg(state.theme.muted_color),
+        )));
+    }
+
+    // One target at a time, chosen with ←/→
+    let target_count = results.len();
+    let selected = state.trace_target_index.min(target_count.saturating_sub(1));
+    if let Some(result) = results.get(selected) {
+        let status = match &result.status {
+            crate::active_diagnostics::ConnectivityStatus::Error(e) => e.clone(),
+            status => format!("{status:?}"),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("→ {} ", result.target),
+                Style::default()
+                    .fg(state.theme.accent_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "({} hops, {status}, traced {} ago) [{}/{target_count}]",
+                    result.total_hops,
+                    format_age(result.last_test.elapsed()),
+                    selected + 1
+                ),
+                Style::default().fg(state.theme.muted_color),
+            ),
+        ]));
+        if !result.changed_ttls.is_empty() {
+            let ttls: Vec<String> = result.changed_ttls.iter().map(u8::to_string).collect();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "⚠ Path changed since the previous trace at hop {}",
+                    ttls.join(", ")
+                ),
+                Style::default()
+                    .fg(state.theme.warning_color)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        // The worst hop is where the most latency is added over the hop before it
+        let mut worst = None;
+        let mut previous_rtt = 0.0;
+        let mut worst_added = 0.0;
+        for hop in &result.hops {
+            let added = hop.avg_rtt_ms - previous_rtt;
+            if added > worst_added {
+                worst_added = added;
+                worst = Some(hop.ttl);
+            }
+            previous_rtt = hop.avg_rtt_ms;
+        }
+
+        // Bars are scaled to the slowest hop of this trace
+        let max_rtt = result
+            .hops
+            .iter()
+            .map(|h| h.avg_rtt_ms)
+            .fold(0.0_f64, f64::max)
+            .max(1.0);
+        let mut expected_ttl = 1;
+        for hop in &result.hops {
+            // Routers that never answered leave gaps in the TTL sequence
+            for silent in expected_ttl..hop.ttl {
+                lines.push(Line::from(Span::styled(
+                    format!("  {silent:>2}  *"),
+                    Style::default().fg(state.theme.muted_color),
+                )));
+            }
+            expected_ttl = hop.ttl.saturating_add(1);
+
+            let color = if hop.packet_loss > 50.0 || hop.avg_rtt_ms >= 150.0 {
+                state.theme.critical_color
+            } else if hop.packet_loss > 0.0 || hop.avg_rtt_ms >= 50.0 {
+                state.theme.warning_color
+            } else {
+                state.theme.ok_color
+            };
+            let router = match &hop.hostname {
+                Some(hostname) => format!("{hostname} ({})", hop.addr),
+                None => hop.addr.to_string(),
+            };
+            let router: String = router.chars().take(39).collect();
+            let bar_len = ((hop.avg_rtt_ms / max_rtt) * BAR_WIDTH as f64).round() as usize;
+            let is_worst = worst == Some(hop.ttl);
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        "  {:>2}  {router:<39} {:>7.1}ms {:>3.0}% ",
+                        hop.ttl, hop.avg_rtt_ms, hop.packet_loss
+                    ),
+                    if is_worst {
+                        Style::default().add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    },
+                ),
+                Span::styled("█".repeat(bar_len.max(1)), Style::default().fg(color)),
+            ];
+            if is_worst {
+                spans.push(Span::styled(
+                    format!(" ◀ worst (+{worst_added:.1}ms)"),
+                    Style::default()
+                        .fg(state.theme.critical_color)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if result.changed_ttls.contains(&hop.ttl) {
+                spans.push(Span::styled(
+                    " ⟳ new router",
+                    Style::default().fg(state.theme.warning_color),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    let scroll = state
+        .diagnostics_scroll
+        .min(lines.len().saturating_sub(1) as u16);
+    let title = if engine.traceroute_in_progress() {
+        "🛣️  Traceroute (tracing..., ←/→ target, ↑/↓ scroll)"
+    } else {
+        "🛣️  Traceroute (←/→ target, ↑/↓ scroll)"
+    };
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_alerts_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(10)])
         .split(area);
 
-    let title = Paragraph::new("Network Alerts & Anomaly Detection - SRE Monitoring")
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Network Alerts"),
-        )
-        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+    let title =
+        Paragraph::new("Network Alerts & Anomaly Detection - SRE Monitoring (h: active/history)")
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Network Alerts"),
+            )
+            .style(
+                Style::default()
+                    .fg(state.theme.critical_color)
+                    .add_modifier(Modifier::BOLD),
+            );
     f.render_widget(title, chunks[0]);
 
+    if state.alerts_show_history {
+        draw_alert_history(f, chunks[1], state);
+        return;
+    }
+
+    let conn_stats = state.connection_monitor.get_connection_stats();
+
     let mut alerts = Vec::new();
     let mut critical_count = 0;
     let mut warning_count = 0;
 
-    for (device_name, calculator) in stats_calculators {
-        let (max_in, max_out) = calculator.max_speed();
-        let (current_in, _current_out) = calculator.current_speed();
-
-        if max_in > 100_000_000 {
-            alerts.push(ListItem::new(format!(
-                "🔥 CRITICAL: {} high inbound traffic: {}/s",
-                device_name,
-                format_bytes(max_in)
-            )));
-            critical_count += 1;
-        }
-
-        if max_out > 100_000_000 {
-            alerts.push(ListItem::new(format!(
-                "🔥 CRITICAL: {} high outbound traffic: {}/s",
-                device_name,
-                format_bytes(max_out)
-            )));
-            critical_count += 1;
-        }
-
-        if current_in > 50_000_000 {
-            alerts.push(ListItem::new(format!(
-                "⚠️  WARNING: {} sustained high traffic: {}/s",
-                device_name,
-                format_bytes(current_in)
-            )));
-            warning_count += 1;
+    // Evaluated once per refresh, the same list --webhook-url notifies on
+    for alert in &state.current_alerts {
+        let (prefix, color) = match (alert.kind, alert.severity) {
+            (AlertKind::Spike, _) => ("⚡ SPIKE:", state.theme.warning_color),
+            (_, AlertSeverity::Critical) => ("🔥 CRITICAL:", state.theme.critical_color),
+            (_, AlertSeverity::Warning) => ("⚠️  WARNING:", state.theme.warning_color),
+            (_, AlertSeverity::Info) => ("ℹ️  INFO:", state.theme.ok_color),
+        };
+        match alert.severity {
+            AlertSeverity::Critical => critical_count += 1,
+            AlertSeverity::Warning => warning_count += 1,
+            AlertSeverity::Info => {}
         }
-    }
-
-    let connection_count = state.connection_monitor.get_connections().len();
-    if connection_count > 1000 {
-        alerts.push(ListItem::new(format!(
-            "🔥 CRITICAL: High connection count: {connection_count} active"
-        )));
-        critical_count += 1;
-    } else if connection_count > 500 {
-        alerts.push(ListItem::new(format!(
-            "⚠️  WARNING: Elevated connections: {connection_count} active"
-        )));
-        warning_count += 1;
+        alerts.push(
+            ListItem::new(format!("{prefix} {}", alert.message)).style(Style::default().fg(color)),
+        );
     }
 
     if alerts.is_empty() {
@@ -3966,6 +7588,14 @@ fn draw_alerts_panel(
         alerts.push(ListItem::new(
             "📊 Thresholds: >100MB/s traffic, >1000 connections, >10k pps",
         ));
+        alerts.push(ListItem::new(format!(
+            "🔌 Socket states: {} TIME_WAIT, {} CLOSE_WAIT, {} FIN_WAIT, {} SYN_SENT",
+            conn_stats.time_wait, conn_stats.close_wait, conn_stats.fin_wait, conn_stats.syn_sent
+        )));
+        alerts.push(ListItem::new(format!(
+            "🔄 Connection churn: {}",
+            churn_summary(&conn_stats)
+        )));
     } else {
         alerts.insert(
             0,
@@ -3973,7 +7603,14 @@ fn draw_alerts_panel(
                 "📊 Alert Summary: {critical_count} critical, {warning_count} warnings"
             )),
         );
-        alerts.insert(1, ListItem::new(""));
+        alerts.insert(
+            1,
+            ListItem::new(format!(
+                "🔄 Connection churn: {}",
+                churn_summary(&conn_stats)
+            )),
+        );
+        alerts.insert(2, ListItem::new(""));
     }
 
     let alerts_list = List::new(alerts)
@@ -3982,36 +7619,73 @@ fn draw_alerts_panel(
                 .borders(Borders::ALL)
                 .title("Active Alerts"),
         )
-        .style(Style::default().fg(Color::White))
-        .highlight_style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(state.theme.text_color))
+        .highlight_style(Style::default().fg(state.theme.critical_color));
 
     f.render_widget(alerts_list, chunks[1]);
 }
 
+/// Alert events newest first, scrolled with j/k
+fn draw_alert_history(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let events = state.listed_alert_events();
+    let title = format!(
+        "Alert History ({} of last {})",
+        events.len(),
+        alerts::ALERT_HISTORY_LEN
+    );
+
+    let items: Vec<ListItem> = if events.is_empty() {
+        vec![ListItem::new(
+            "No alerts raised yet; warnings, critical alerts and their clearing are kept here",
+        )
+        .style(Style::default().fg(state.theme.muted_color))]
+    } else {
+        let scroll = state.alert_history_scroll.min(events.len() - 1);
+        events
+            .into_iter()
+            .skip(scroll)
+            .map(|event| {
+                let color = match event.level {
+                    AlertSeverity::Critical => state.theme.critical_color,
+                    AlertSeverity::Warning => state.theme.warning_color,
+                    AlertSeverity::Info => state.theme.ok_color,
+                };
+                ListItem::new(format!(
+                    "{}  {:<8}  {}",
+                    event.timestamp.format("%H:%M:%S"),
+                    event.level.as_str().to_uppercase(),
+                    event.message
+                ))
+                .style(Style::default().fg(color))
+            })
+            .collect()
+    };
+
+    let history = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(state.theme.text_color));
+    f.render_widget(history, area);
+}
+
 fn draw_forensics_panel(f: &mut Frame, area: Rect, state: &mut DashboardState) {
     // Advanced Network Security Forensics Panel with AI-powered threat detection
 
     let now = std::time::Instant::now();
 
-    // Skip expensive operations in high performance mode or if updated recently
-    let should_skip_expensive = state
-        .config
-        .as_ref()
-        .map(|c| c.high_performance)
-        .unwrap_or(false)
-        || state
-            .last_forensics_update
-            .map(|last| now.duration_since(last) < Duration::from_secs(2))
-            .unwrap_or(false);
-
-    if should_skip_expensive {
-        // Show simplified forensics in high performance mode or when throttled
-        draw_simplified_forensics(f, area, state);
-        return;
-    }
+    // Analysis is expensive: rerun it only every ForensicsInterval seconds (twice
+    // that in high performance mode) and redraw the previous pass in between
+    let interval = state.config.as_ref().map_or(Duration::from_secs(2), |c| {
+        let multiplier = if c.high_performance { 2 } else { 1 };
+        Duration::from_secs(c.forensics_interval_secs.max(1) * multiplier)
+    });
+    let analysis_due = state
+        .last_forensics_update
+        .map_or(true, |last| now.duration_since(last) >= interval);
 
-    // Update the last forensics update time
-    state.last_forensics_update = Some(now);
+    if analysis_due {
+        refresh_forensics_intel(state);
+        state.last_forensics_update = Some(now);
+    }
 
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -4027,7 +7701,7 @@ fn draw_forensics_panel(f: &mut Frame, area: Rect, state: &mut DashboardState) {
     }))
     .is_err()
     {
-        draw_forensics_error(f, main_chunks[0]);
+        draw_forensics_error(f, main_chunks[0], &state.theme);
     }
 
     // Right side: Port scan detection and security anomalies - with panic protection
@@ -4036,43 +7710,36 @@ fn draw_forensics_panel(f: &mut Frame, area: Rect, state: &mut DashboardState) {
     }))
     .is_err()
     {
-        draw_forensics_error(f, main_chunks[1]);
+        draw_forensics_error(f, main_chunks[1], &state.theme);
     }
 }
 
-fn draw_simplified_forensics(f: &mut Frame, area: Rect, _state: &mut DashboardState) {
-    let block = Block::default()
-        .title("🔍 Security Forensics (High Performance Mode)")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
-
-    let paragraph = Paragraph::new(vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "⚡ High Performance Mode Active",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "• Forensics analysis disabled for optimal performance",
-            Style::default().fg(Color::White),
-        )]),
-        Line::from(vec![Span::styled(
-            "• Use regular mode for full security analysis",
-            Style::default().fg(Color::White),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "  Switch to regular mode: remove --high-perf flag",
-            Style::default().fg(Color::Gray),
-        )]),
-    ])
-    .block(block)
-    .alignment(Alignment::Center);
+/// Analyze the first connections for the Forensics panel's GeoIP and threat view
+fn refresh_forensics_intel(state: &mut DashboardState) {
+    // Safely get connections; keep the previous pass if that panics
+    let Ok(connections) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        // Limit to 2 connections to prevent performance issues
+        state
+            .connection_monitor
+            .get_connections()
+            .iter()
+            .take(2)
+            .cloned()
+            .collect::<Vec<_>>()
+    })) else {
+        return;
+    };
 
-    f.render_widget(paragraph, area);
+    // Skip any connection whose analysis panics
+    state.forensics_intel = connections
+        .iter()
+        .filter_map(|connection| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                state.network_intelligence.analyze_connection(connection)
+            }))
+            .ok()
+        })
+        .collect();
 }
 
 fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut DashboardState) {
@@ -4084,45 +7751,12 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
         ])
         .split(area);
 
-    // Safely get connections with error handling to prevent crashes
-    let connections = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        state.connection_monitor.get_connections()
-    })) {
-        Ok(conns) => {
-            // Limit to maximum 2 connections to prevent performance issues
-            conns.iter().take(2).cloned().collect::<Vec<_>>()
-        }
-        Err(_) => {
-            // If getting connections panics, show safe fallback UI
-            draw_forensics_error(f, area);
-            return;
-        }
-    };
-
     let mut threat_data = Vec::new();
     let mut geo_stats = std::collections::HashMap::new();
     let mut suspicious_count = 0;
 
-    // Safely analyze connections with panic protection
-    let analysis_results: Vec<_> = connections
-        .iter()
-        .take(2) // Further reduce to 2 for stability
-        .filter_map(|connection| {
-            // Wrap analysis in panic catch to prevent crashes
-            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                state.network_intelligence.analyze_connection(connection)
-            })) {
-                Ok(connection_intel) => Some((connection, connection_intel)),
-                Err(_) => {
-                    // Skip this connection if analysis panics
-                    None
-                }
-            }
-        })
-        .collect();
-
     // Process results
-    for (_connection, connection_intel) in analysis_results {
+    for connection_intel in &state.forensics_intel {
         // GeoIP analysis
         if let Some(ref geo) = connection_intel.geo_info {
             if !geo.is_internal {
@@ -4149,30 +7783,38 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     let mut geo_content = vec![
         Line::from(vec![Span::styled(
             "🌍 GEOLOCATION INTELLIGENCE",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
     ];
 
     let connection_stats = state.network_intelligence.get_connection_stats();
     geo_content.push(Line::from(vec![
-        Span::styled("📊 Global Connections: ", Style::default().fg(Color::White)),
+        Span::styled(
+            "📊 Global Connections: ",
+            Style::default().fg(state.theme.text_color),
+        ),
         Span::styled(
             format!("{} countries", connection_stats.unique_countries),
             Style::default().fg(if connection_stats.unique_countries > 10 {
-                Color::Red
+                state.theme.critical_color
             } else {
-                Color::Green
+                state.theme.ok_color
             }),
         ),
         Span::styled(
             format!(" | {} external", connection_stats.external_connections),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         ),
     ]));
 
     geo_content.push(Line::from(vec![
-        Span::styled("🚨 Threat Level: ", Style::default().fg(Color::White)),
+        Span::styled(
+            "🚨 Threat Level: ",
+            Style::default().fg(state.theme.text_color),
+        ),
         Span::styled(
             if suspicious_count > 5 {
                 "🔴 HIGH"
@@ -4185,13 +7827,13 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
             },
             Style::default()
                 .fg(if suspicious_count > 5 {
-                    Color::Red
+                    state.theme.critical_color
                 } else if suspicious_count > 2 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else if suspicious_count > 0 {
-                    Color::Magenta
+                    state.theme.highlight_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 })
                 .add_modifier(Modifier::BOLD),
         ),
@@ -4201,7 +7843,7 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     geo_content.push(Line::from(vec![Span::styled(
         "🌐 TOP COUNTRIES:",
         Style::default()
-            .fg(Color::Cyan)
+            .fg(state.theme.accent_color)
             .add_modifier(Modifier::BOLD),
     )]));
 
@@ -4218,16 +7860,19 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
         geo_content.push(Line::from(vec![
             Span::styled(
                 format!("  {threat_indicator} {country}: "),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
+            ),
+            Span::styled(
+                format!("{count} conn"),
+                Style::default().fg(state.theme.accent_color),
             ),
-            Span::styled(format!("{count} conn"), Style::default().fg(Color::Cyan)),
         ]));
     }
 
     let geo_block = Block::default()
         .title("🌍 GeoIP Threat Intelligence")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(state.theme.critical_color));
 
     let geo_paragraph = Paragraph::new(geo_content)
         .block(geo_block)
@@ -4238,7 +7883,9 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     let mut threat_content = vec![
         Line::from(vec![Span::styled(
             "🛡️  ACTIVE THREATS DETECTED",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
     ];
@@ -4246,24 +7893,24 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     if threat_data.is_empty() {
         threat_content.push(Line::from(vec![Span::styled(
             "✅ No active threats detected",
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]));
         threat_content.push(Line::from(vec![Span::styled(
             "   All connections appear legitimate",
-            Style::default().fg(Color::White),
+            Style::default().fg(state.theme.text_color),
         )]));
     } else {
         for threat in threat_data.iter().take(8) {
             threat_content.push(Line::from(vec![Span::styled(
                 threat,
-                Style::default().fg(Color::Red),
+                Style::default().fg(state.theme.critical_color),
             )]));
         }
 
         if threat_data.len() > 8 {
             threat_content.push(Line::from(vec![Span::styled(
                 format!("  ... and {} more threats", threat_data.len() - 8),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             )]));
         }
     }
@@ -4271,7 +7918,7 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     let threat_block = Block::default()
         .title("🚨 Threat Intelligence Feed")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(state.theme.critical_color));
 
     let threat_paragraph = Paragraph::new(threat_content)
         .block(threat_block)
@@ -4279,6 +7926,15 @@ fn draw_geo_threat_intelligence(f: &mut Frame, area: Rect, state: &mut Dashboard
     f.render_widget(threat_paragraph, chunks[1]);
 }
 
+/// Up to 8 ports, comma separated, then how many more
+fn port_list(ports: &std::collections::BTreeSet<u16>) -> String {
+    let shown: Vec<String> = ports.iter().take(8).map(u16::to_string).collect();
+    match ports.len().saturating_sub(shown.len()) {
+        0 => shown.join(","),
+        more => format!("{} +{more}", shown.join(",")),
+    }
+}
+
 fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -4289,24 +7945,14 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
         ])
         .split(area);
 
-    // Port Scan Detection Panel - with panic protection and throttling
-    let port_scan_alerts = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        if let Ok(last_update) = state.parallel_data.last_update.lock() {
-            // Only update port scan data every 10 seconds to improve stability
-            if last_update.elapsed().as_secs() > 10 {
-                state.network_intelligence.get_port_scan_alerts()
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
-        }
-    }))
-    .unwrap_or_default();
+    // Port Scan Detection Panel, fed from each connection snapshot
+    let port_scan_alerts = state.network_intelligence.get_port_scan_alerts();
     let mut scan_content = vec![
         Line::from(vec![Span::styled(
             "🎯 PORT SCAN DETECTION",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
     ];
@@ -4314,41 +7960,56 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
     if port_scan_alerts.is_empty() {
         scan_content.push(Line::from(vec![Span::styled(
             "✅ No port scanning detected",
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]));
         scan_content.push(Line::from(vec![Span::styled(
             "   Network appears secure from scan attempts",
-            Style::default().fg(Color::White),
+            Style::default().fg(state.theme.text_color),
         )]));
     } else {
         scan_content.push(Line::from(vec![Span::styled(
             format!("🚨 {} ACTIVE SCANS DETECTED", port_scan_alerts.len()),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]));
         scan_content.push(Line::from(""));
 
         for (i, scan) in port_scan_alerts.iter().take(4).enumerate() {
             scan_content.push(Line::from(vec![
-                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{}. ", i + 1),
+                    Style::default().fg(state.theme.text_color),
+                ),
                 Span::styled(
                     format!("{}", scan.scanner_ip),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(state.theme.critical_color),
                 ),
                 Span::styled(
                     format!(" → {} ports", scan.ports_scanned.len()),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.theme.warning_color),
                 ),
                 Span::styled(
                     format!(" ({:.1}/s)", scan.scan_rate),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(state.theme.accent_color),
                 ),
             ]));
             scan_content.push(Line::from(vec![Span::styled(
-                format!("   Confidence: {:.0}%", scan.confidence * 100.0),
+                format!(
+                    "   ports {} · first {} ago, last {} ago · confidence {:.0}% · {} established",
+                    port_list(&scan.ports_scanned),
+                    format_age(scan.first_seen.elapsed().unwrap_or_default()),
+                    format_age(scan.last_seen.elapsed().unwrap_or_default()),
+                    scan.confidence * 100.0,
+                    state
+                        .connection_monitor
+                        .get_established_to_remote(scan.scanner_ip)
+                        .len()
+                ),
                 Style::default().fg(if scan.confidence > 0.8 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Yellow
+                    state.theme.warning_color
                 }),
             )]));
         }
@@ -4357,7 +8018,7 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
     let scan_block = Block::default()
         .title("🎯 Port Scan Detection Engine")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(state.theme.critical_color));
 
     let scan_paragraph = Paragraph::new(scan_content)
         .block(scan_block)
@@ -4370,7 +8031,7 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
         Line::from(vec![Span::styled(
             "⚠️  SECURITY ALERTS",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -4379,16 +8040,16 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
     if anomalies.is_empty() {
         alert_content.push(Line::from(vec![Span::styled(
             "✅ No security anomalies detected",
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]));
     } else {
         for anomaly in anomalies {
             let severity_color = match anomaly.severity {
-                Severity::Critical => Color::Red,
-                Severity::High => Color::Magenta,
-                Severity::Medium => Color::Yellow,
-                Severity::Low => Color::Blue,
-                Severity::Info => Color::White,
+                Severity::Critical => state.theme.critical_color,
+                Severity::High => state.theme.highlight_color,
+                Severity::Medium => state.theme.warning_color,
+                Severity::Low => state.theme.info_color,
+                Severity::Info => state.theme.text_color,
             };
 
             alert_content.push(Line::from(vec![
@@ -4396,7 +8057,10 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
                     format!("{:?}: ", anomaly.severity),
                     Style::default().fg(severity_color),
                 ),
-                Span::styled(&anomaly.description, Style::default().fg(Color::White)),
+                Span::styled(
+                    &anomaly.description,
+                    Style::default().fg(state.theme.text_color),
+                ),
             ]));
         }
     }
@@ -4404,7 +8068,7 @@ fn draw_security_anomalies(f: &mut Frame, area: Rect, state: &mut DashboardState
     let alert_block = Block::default()
         .title("⚠️ Security Alert System")
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(state.theme.warning_color));
 
     let alert_paragraph = Paragraph::new(alert_content)
         .block(alert_block)
@@ -4431,16 +8095,17 @@ fn draw_connection_forensics_table(f: &mut Frame, area: Rect, state: &mut Dashbo
 
     // Header row
     let header = Row::new(vec![
-        Cell::from("Remote IP"),
+        Cell::from("Remote"),
         Cell::from("Port"),
         Cell::from("Service"),
         Cell::from("Country"),
         Cell::from("Threat"),
+        Cell::from("CC"),
         Cell::from("Process"),
     ])
     .style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(state.theme.header_color)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -4474,10 +8139,19 @@ fn draw_connection_forensics_table(f: &mut Frame, area: Rect, state: &mut Dashbo
             "✅"
         };
 
-        let service = if connection_intel.service_name.len() > 12 {
-            format!("{}...", &connection_intel.service_name[..9])
+        // With --geoip-db the remote network's owner follows the service name
+        let service = match connection_intel
+            .geo_info
+            .as_ref()
+            .filter(|geo| geo.asn != 0)
+        {
+            Some(geo) => format!("{} {}", connection_intel.service_name, geo.organization),
+            None => connection_intel.service_name.clone(),
+        };
+        let service = if service.chars().count() > 28 {
+            format!("{}...", service.chars().take(25).collect::<String>())
         } else {
-            connection_intel.service_name.clone()
+            service
         };
 
         let process = connection
@@ -4493,11 +8167,23 @@ fn draw_connection_forensics_table(f: &mut Frame, area: Rect, state: &mut Dashbo
             .unwrap_or_else(|| "?".to_string());
 
         rows.push(Row::new(vec![
-            Cell::from(connection_intel.remote_ip.to_string()),
+            Cell::from(
+                state
+                    .connection_monitor
+                    .hostname(connection_intel.remote_ip)
+                    .unwrap_or_else(|| connection_intel.remote_ip.to_string()),
+            ),
             Cell::from(connection_intel.remote_port.to_string()),
             Cell::from(service),
             Cell::from(country),
             Cell::from(threat_level),
+            Cell::from(
+                connection
+                    .socket_info
+                    .cc_algo
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
             Cell::from(process),
         ]));
     }
@@ -4507,9 +8193,10 @@ fn draw_connection_forensics_table(f: &mut Frame, area: Rect, state: &mut Dashbo
         [
             Constraint::Length(15), // IP
             Constraint::Length(6),  // Port
-            Constraint::Length(12), // Service
+            Constraint::Min(12),    // Service and organization
             Constraint::Length(7),  // Country
             Constraint::Length(7),  // Threat
+            Constraint::Length(9),  // Congestion control
             Constraint::Length(12), // Process
         ],
     )
@@ -4518,64 +8205,90 @@ fn draw_connection_forensics_table(f: &mut Frame, area: Rect, state: &mut Dashbo
         Block::default()
             .title("🔍 Real-time Connection Forensics")
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Cyan)),
+            .style(Style::default().fg(state.theme.accent_color)),
     )
     .column_spacing(1);
 
     f.render_widget(table, area);
 }
 
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
-    }
-
-    format!("{:.1}{}", size, UNITS[unit_index])
-}
-
 fn draw_settings_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let settings_text = vec![
+    let mut settings_text = vec![
         Line::from(vec![Span::styled(
             "Settings Panel",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
+    ];
+
+    // Editable rows show the running config, with the units as currently displayed
+    if let Some(config) = state.config.as_deref() {
+        let config = state.editable_config(config);
+        let selected = state.selected_setting();
+        for field in SettingField::ALL {
+            let is_selected = state.active_panel == DashboardPanel::Settings && field == selected;
+            let value = match &state.settings_input {
+                Some(input) if is_selected => format!("{input}▏"),
+                _ => field.value(&config),
+            };
+            let row_style = if is_selected {
+                Style::default().bg(state.theme.selected_row_bg)
+            } else {
+                Style::default()
+            };
+            settings_text.push(
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{}{:<18}",
+                            if is_selected { "▶ " } else { "  " },
+                            field.label()
+                        ),
+                        Style::default().fg(state.theme.accent_color),
+                    ),
+                    Span::styled(value, Style::default().fg(state.theme.text_color)),
+                ])
+                .style(row_style),
+            );
+        }
+        settings_text.push(Line::from(""));
+    }
+
+    settings_text.extend([
         Line::from(vec![
-            Span::styled("Traffic Unit: ", Style::default().fg(Color::Cyan)),
             Span::styled(
-                format!("{:?}", state.traffic_unit),
-                Style::default().fg(Color::White),
+                "Zoom Level: ",
+                Style::default().fg(state.theme.accent_color),
             ),
-        ]),
-        Line::from(vec![
-            Span::styled("Data Unit: ", Style::default().fg(Color::Cyan)),
             Span::styled(
-                format!("{:?}", state.data_unit),
-                Style::default().fg(Color::White),
+                format!("{:.1}x", state.zoom_level),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Zoom Level: ", Style::default().fg(Color::Cyan)),
             Span::styled(
-                format!("{:.1}x", state.zoom_level),
-                Style::default().fg(Color::White),
+                "Graph Scale: ",
+                Style::default().fg(state.theme.accent_color),
+            ),
+            Span::styled(
+                if state.log_scale {
+                    "Logarithmic"
+                } else {
+                    "Linear"
+                },
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Status: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
                 if state.paused { "PAUSED" } else { "RUNNING" },
                 Style::default().fg(if state.paused {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
@@ -4583,74 +8296,134 @@ fn draw_settings_panel(f: &mut Frame, area: Rect, state: &DashboardState) {
         Line::from(vec![Span::styled(
             "Controls:",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
+        Line::from("↑/↓ - Select setting, ←/→ or Enter - Change it"),
+        Line::from("Enter on a number - Type a value, Enter applies, Esc cancels"),
         Line::from("F5 - Save settings"),
         Line::from("F6 - Reload settings"),
         Line::from("Space - Pause/Resume"),
         Line::from("u - Toggle traffic units"),
-        Line::from("+/- - Zoom graphs"),
-    ];
+        Line::from("+/- ←/→ - Zoom/pan graph time (Graphs panel)"),
+        Line::from("L - Toggle log scale (Graphs panel)"),
+    ]);
 
     let settings = Paragraph::new(settings_text)
         .block(Block::default().borders(Borders::ALL).title("Settings"))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(settings, area);
 }
 
 fn draw_footer(f: &mut Frame, area: Rect, state: &DashboardState) {
+    if let Some(status) = state.current_status() {
+        let footer = Paragraph::new(status).style(Style::default().fg(state.theme.warning_color));
+        let footer = if state.is_compact() {
+            footer
+        } else {
+            footer.block(Block::default().borders(Borders::ALL))
+        };
+        f.render_widget(footer, area);
+        return;
+    }
+
+    // Every collector is stopped while paused, so say so before the key hints
+    let frozen = state.paused.then(|| {
+        Span::styled(
+            "FROZEN | ",
+            Style::default()
+                .fg(state.theme.warning_color)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+
+    if state.is_compact() {
+        let footer = Paragraph::new(Line::from_iter(frozen.into_iter().chain([Span::raw(
+            "Tab: Panel | Space: Pause | c: Full | F2: Help | q: Quit",
+        )])))
+        .style(Style::default().fg(state.theme.accent_color));
+        f.render_widget(footer, area);
+        return;
+    }
+
     let help_text = if state.show_help {
-        "Press F2 to hide help"
+        "Press F2 to hide help".to_string()
     } else {
-        "Tab/Shift+Tab: Switch panels | Enter: Select | Space: Pause | F2: Help | q: Quit"
+        let refresh_ms = state
+            .config
+            .as_ref()
+            .map_or(1000, |config| config.refresh_interval);
+        format!(
+            "Tab/Shift+Tab: Switch panels | Enter: Select | Space: Pause | [/]: Refresh {refresh_ms}ms | F2: Help | q: Quit"
+        )
     };
 
-    let footer = Paragraph::new(help_text)
-        .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Cyan));
+    let footer = Paragraph::new(Line::from_iter(
+        frozen.into_iter().chain([Span::raw(help_text)]),
+    ))
+    .block(Block::default().borders(Borders::ALL))
+    .style(Style::default().fg(state.theme.accent_color));
 
     f.render_widget(footer, area);
 }
 
-fn draw_help_overlay(f: &mut Frame) {
+fn draw_help_overlay(f: &mut Frame, state: &DashboardState) {
+    if state.is_compact() {
+        draw_compact_help_overlay(f, &state.theme);
+        return;
+    }
+
     let area = centered_rect(60, 70, f.area());
 
     let help_text = vec![
         Line::from(vec![Span::styled(
             "netwatch Help",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Navigation:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from("  Tab / Shift+Tab  - Switch between panels"),
         Line::from("  ←/→ or h/l       - Previous/Next panel"),
         Line::from("  ↑/↓ or j/k       - Navigate within panel"),
         Line::from("  Enter            - Select item"),
+        Line::from("  Mouse            - Click a tab; wheel scrolls tables"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Controls:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from("  Space            - Pause/Resume monitoring"),
         Line::from("  r                - Reset statistics"),
         Line::from("  u                - Toggle traffic units"),
-        Line::from("  +/-              - Zoom graphs"),
+        Line::from("  +/-              - Zoom graph time axis (Graphs panel)"),
+        Line::from("  ←/→              - Pan zoomed graph (Graphs panel)"),
+        Line::from("  L                - Toggle log scale (Graphs panel)"),
+        Line::from("  m                - Side-by-side interface graphs (Graphs panel)"),
+        Line::from("  c                - Toggle compact layout"),
+        Line::from("  K                - Kill selected process (Processes panel)"),
+        Line::from("  g                - Group traffic by container (Processes panel)"),
+        Line::from("  o                - Listening sockets and their exposure (Connections panel)"),
+        Line::from("  h                - Alert history, j/k to scroll (Alerts panel)"),
+        Line::from("  Enter            - Fold ports, slaves and VLANs away (Interfaces panel)"),
+        Line::from("  /                - Filter the list; Enter keeps the filter, Esc clears it"),
+        Line::from("  Enter / Esc      - Open / close a process's connections (Processes panel)"),
+        Line::from("  ←/→ Enter        - Change selected setting (Settings panel)"),
+        Line::from("  [ / ]            - Faster/Slower refresh (100ms-10s)"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Settings:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from("  F5               - Save current settings"),
@@ -4659,7 +8432,7 @@ fn draw_help_overlay(f: &mut Frame) {
         Line::from(vec![Span::styled(
             "Other:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from("  F2               - Toggle this help"),
@@ -4668,7 +8441,92 @@ fn draw_help_overlay(f: &mut Frame) {
 
     let help = Paragraph::new(help_text)
         .block(Block::default().borders(Borders::ALL).title("Help"))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
+
+    f.render_widget(Clear, area);
+    f.render_widget(help, area);
+}
+
+/// y/n confirmation for signalling a process, clamped to the terminal
+fn draw_kill_prompt(f: &mut Frame, prompt: &KillPrompt, theme: &Theme) {
+    let screen = f.area();
+    let width = 50.min(screen.width);
+    let height = 6.min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    let (signal, title) = if prompt.force {
+        ("SIGKILL", "Kill Process")
+    } else {
+        ("SIGTERM", "Terminate Process")
+    };
+    let text = vec![
+        Line::from(vec![
+            Span::raw(format!("Send {signal} to ")),
+            Span::styled(
+                format!("{} ({})", prompt.pid, prompt.name),
+                Style::default()
+                    .fg(theme.critical_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("?"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "y/Enter: confirm    any other key: cancel",
+            Style::default().fg(theme.muted_color),
+        )]),
+    ];
+
+    let popup = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(theme.text_color))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// 40x15 help popup for compact mode, clamped to the terminal
+fn draw_compact_help_overlay(f: &mut Frame, theme: &Theme) {
+    let screen = f.area();
+    let width = 40.min(screen.width);
+    let height = 15.min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    let help_text = vec![
+        Line::from(vec![Span::styled(
+            "netwatch Help",
+            Style::default()
+                .fg(theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from("Tab/S-Tab  Switch panels"),
+        Line::from("j/k        Navigate within panel"),
+        Line::from("Enter      Select item"),
+        Line::from("Space      Pause/Resume"),
+        Line::from("r          Reset statistics"),
+        Line::from("u          Toggle traffic units"),
+        Line::from("+/- ←/→ L  Zoom / pan / log scale"),
+        Line::from("m          Side-by-side graphs"),
+        Line::from("[ / ]      Faster/Slower refresh"),
+        Line::from("c          Toggle compact layout"),
+        Line::from("F5/F6      Save/Reload settings"),
+        Line::from("F2         Toggle help  q: Quit"),
+    ];
+
+    let help = Paragraph::new(help_text)
+        .block(Block::default().borders(Borders::ALL).title("Help"))
+        .style(Style::default().fg(theme.text_color));
 
     f.render_widget(Clear, area);
     f.render_widget(help, area);
@@ -4806,107 +8664,130 @@ fn draw_network_overview(
     let overview_text = vec![
         Line::from(vec![Span::styled(
             "███ ULTRA ENHANCED VERSION ███",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(state.theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
             "████████████████████████████████",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
             "NETWORK INTELLIGENCE OVERVIEW",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "📊 Traffic Summary:",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]),
         Line::from(vec![
-            Span::styled("  ↓ In:  ", Style::default().fg(Color::Green)),
+            Span::styled("  ↓ In:  ", Style::default().fg(state.theme.ok_color)),
             Span::styled(
-                format!("{}/s", format_bytes(total_in)),
+                format_rate(total_in, &state.traffic_unit),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  🌐 BW: ", Style::default().fg(Color::Cyan)),
+            Span::styled("  🌐 BW: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
-                format_bandwidth(total_bandwidth),
-                Style::default().fg(Color::White),
+                format_bandwidth(total_bandwidth, &state.traffic_unit),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  ↑ Out: ", Style::default().fg(Color::Red)),
+            Span::styled("  ↑ Out: ", Style::default().fg(state.theme.critical_color)),
             Span::styled(
-                format!("{}/s", format_bytes(total_out)),
+                format_rate(total_out, &state.traffic_unit),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  ⚡ RTT: ", Style::default().fg(Color::Magenta)),
+            Span::styled(
+                "  ⚡ RTT: ",
+                Style::default().fg(state.theme.highlight_color),
+            ),
             Span::styled(
                 if avg_rtt > 0.0 {
                     format!("{avg_rtt:.1}ms")
                 } else {
                     "N/A".to_string()
                 },
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🔗 CONNECTION INTELLIGENCE (ENHANCED!):",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  🔥 NEW FEATURE: Total: ", Style::default().fg(Color::Red)),
+            Span::styled(
+                "  🔥 NEW FEATURE: Total: ",
+                Style::default().fg(state.theme.critical_color),
+            ),
             Span::styled(
                 format!("{}", conn_stats.total),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Active: ", Style::default().fg(Color::Green)),
+            Span::styled("  Active: ", Style::default().fg(state.theme.ok_color)),
             Span::styled(
                 format!("{}", conn_stats.established),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(state.theme.ok_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Listen: ", Style::default().fg(Color::Blue)),
+            Span::styled("  Listen: ", Style::default().fg(state.theme.info_color)),
             Span::styled(
                 format!("{}", conn_stats.listening),
                 Style::default()
-                    .fg(Color::Blue)
+                    .fg(state.theme.info_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  🟢 Fast: ", Style::default().fg(Color::Green)),
-            Span::styled(format!("{high_quality}"), Style::default().fg(Color::Green)),
-            Span::styled("  🔴 Slow: ", Style::default().fg(Color::Red)),
-            Span::styled(format!("{poor_quality}"), Style::default().fg(Color::Red)),
-            Span::styled("  ⚠️ Retrans: ", Style::default().fg(Color::Yellow)),
+            Span::styled("  🟢 Fast: ", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                format!("{high_quality}"),
+                Style::default().fg(state.theme.ok_color),
+            ),
+            Span::styled(
+                "  🔴 Slow: ",
+                Style::default().fg(state.theme.critical_color),
+            ),
+            Span::styled(
+                format!("{poor_quality}"),
+                Style::default().fg(state.theme.critical_color),
+            ),
+            Span::styled(
+                "  ⚠️ Retrans: ",
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{total_retrans}"),
                 Style::default().fg(if total_retrans > 0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  📶 Interfaces: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "  📶 Interfaces: ",
+                Style::default().fg(state.theme.accent_color),
+            ),
             Span::styled(
                 format!("{active_interfaces}"),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
             Span::styled(
                 if error_count > 0 {
@@ -4915,9 +8796,9 @@ fn draw_network_overview(
                     " (✓ healthy)".to_string()
                 },
                 Style::default().fg(if error_count > 0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
@@ -4925,7 +8806,7 @@ fn draw_network_overview(
 
     let overview = Paragraph::new(overview_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(overview, area);
 }
@@ -4934,7 +8815,7 @@ fn draw_network_overview(
 fn draw_top_interfaces(
     f: &mut Frame,
     area: Rect,
-    _state: &DashboardState,
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
     // Find top interfaces by current traffic
@@ -4946,14 +8827,14 @@ fn draw_top_interfaces(
         })
         .collect();
 
-    interface_traffic.sort_by(|a, b| b.1.cmp(&a.1));
+    interface_traffic.sort_by_key(|b| std::cmp::Reverse(b.1));
     interface_traffic.truncate(3); // Top 3
 
     let mut top_text = vec![
         Line::from(vec![Span::styled(
             "🔥 TOP INTERFACES",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -4968,17 +8849,20 @@ fn draw_top_interfaces(
         };
 
         top_text.push(Line::from(vec![
-            Span::styled(format!("{icon} {name}: "), Style::default().fg(Color::Cyan)),
             Span::styled(
-                format!("{}/s", format_bytes(*traffic)),
-                Style::default().fg(Color::White),
+                format!("{icon} {name}: "),
+                Style::default().fg(state.theme.accent_color),
+            ),
+            Span::styled(
+                format_rate(*traffic, &state.traffic_unit),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
     }
 
     let top_interfaces = Paragraph::new(top_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(top_interfaces, area);
 }
@@ -5025,23 +8909,23 @@ fn draw_network_health(
     }
 
     let health_status = if total_errors == 0 && total_drops == 0 && connection_issues == 0 {
-        ("🟢 EXCELLENT", Color::Green)
+        ("🟢 EXCELLENT", state.theme.ok_color)
     } else if total_errors < 10 && total_drops < 10 && connection_issues < 5 {
-        ("🟡 GOOD", Color::Yellow)
+        ("🟡 GOOD", state.theme.warning_color)
     } else {
-        ("🔴 ISSUES", Color::Red)
+        ("🔴 ISSUES", state.theme.critical_color)
     };
 
     let health_text = vec![
         Line::from(vec![Span::styled(
             "⚕️ INTELLIGENT HEALTH",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Status: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
                 health_status.0,
                 Style::default()
@@ -5050,49 +8934,55 @@ fn draw_network_health(
             ),
         ]),
         Line::from(vec![
-            Span::styled("📡 Errors: ", Style::default().fg(Color::Cyan)),
+            Span::styled("📡 Errors: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
                 format!("{total_errors}"),
                 Style::default().fg(if total_errors > 0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
-            Span::styled(" Drops: ", Style::default().fg(Color::Cyan)),
+            Span::styled(" Drops: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
                 format!("{total_drops}"),
                 Style::default().fg(if total_drops > 0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("🔗 Conn Issues: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "🔗 Conn Issues: ",
+                Style::default().fg(state.theme.accent_color),
+            ),
             Span::styled(
                 format!("{connection_issues}"),
                 Style::default().fg(if connection_issues > 0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("🐌 Slow RTT: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "🐌 Slow RTT: ",
+                Style::default().fg(state.theme.accent_color),
+            ),
             Span::styled(
                 format!("{slow_connections}"),
                 Style::default().fg(if slow_connections > 0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Mode: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Mode: ", Style::default().fg(state.theme.accent_color)),
             Span::styled(
                 if state.paused {
                     "⏸️ PAUSED"
@@ -5101,9 +8991,9 @@ fn draw_network_health(
                 },
                 Style::default()
                     .fg(if state.paused {
-                        Color::Yellow
+                        state.theme.warning_color
                     } else {
-                        Color::Green
+                        state.theme.ok_color
                     })
                     .add_modifier(Modifier::BOLD),
             ),
@@ -5112,7 +9002,7 @@ fn draw_network_health(
 
     let health = Paragraph::new(health_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(health, area);
 }
@@ -5121,14 +9011,14 @@ fn draw_network_health(
 fn draw_system_alerts(
     f: &mut Frame,
     area: Rect,
-    _state: &DashboardState,
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
     let mut alerts = vec![
         Line::from(vec![Span::styled(
             "🚨 ALERTS & INFO",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -5145,10 +9035,10 @@ fn draw_system_alerts(
         // Alert if current traffic is 5x higher than average
         if avg_in > 0 && current_in > avg_in * 5 {
             alerts.push(Line::from(vec![
-                Span::styled("⚡ ", Style::default().fg(Color::Red)),
+                Span::styled("⚡ ", Style::default().fg(state.theme.critical_color)),
                 Span::styled(
                     format!("{name}: Traffic spike IN"),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.theme.warning_color),
                 ),
             ]));
             has_alerts = true;
@@ -5156,10 +9046,10 @@ fn draw_system_alerts(
 
         if avg_out > 0 && current_out > avg_out * 5 {
             alerts.push(Line::from(vec![
-                Span::styled("⚡ ", Style::default().fg(Color::Red)),
+                Span::styled("⚡ ", Style::default().fg(state.theme.critical_color)),
                 Span::styled(
                     format!("{name}: Traffic spike OUT"),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.theme.warning_color),
                 ),
             ]));
             has_alerts = true;
@@ -5175,22 +9065,25 @@ fn draw_system_alerts(
     if !has_alerts {
         alerts.push(Line::from(vec![Span::styled(
             "✅ No active alerts",
-            Style::default().fg(Color::Green),
+            Style::default().fg(state.theme.ok_color),
         )]));
     }
 
     alerts.push(Line::from(""));
     alerts.push(Line::from(vec![
-        Span::styled("📅 Session: ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            "📅 Session: ",
+            Style::default().fg(state.theme.accent_color),
+        ),
         Span::styled(
             format!("{}s", now % 3600),
-            Style::default().fg(Color::White),
+            Style::default().fg(state.theme.text_color),
         ),
     ]));
 
     let system_info = Paragraph::new(alerts)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(system_info, area);
 }
@@ -5221,7 +9114,7 @@ fn draw_activity_graphs(
 fn draw_combined_traffic_graph(
     f: &mut Frame,
     area: Rect,
-    _state: &DashboardState,
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
     // Create ASCII art traffic visualization
@@ -5229,7 +9122,7 @@ fn draw_combined_traffic_graph(
         Line::from(vec![Span::styled(
             "📈 REAL-TIME TRAFFIC",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -5257,24 +9150,30 @@ fn draw_combined_traffic_graph(
 
         traffic_lines.push(Line::from(vec![Span::styled(
             format!("{name:>8}: "),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]));
 
         traffic_lines.push(Line::from(vec![
-            Span::styled("  ↓ ", Style::default().fg(Color::Green)),
-            Span::styled(format!("{in_bar:<20}"), Style::default().fg(Color::Green)),
+            Span::styled("  ↓ ", Style::default().fg(state.theme.ok_color)),
+            Span::styled(
+                format!("{in_bar:<20}"),
+                Style::default().fg(state.theme.ok_color),
+            ),
             Span::styled(
-                format!(" {}/s", format_bytes(current_in)),
-                Style::default().fg(Color::White),
+                format!(" {}", format_rate(current_in, &state.traffic_unit)),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
 
         traffic_lines.push(Line::from(vec![
-            Span::styled("  ↑ ", Style::default().fg(Color::Red)),
-            Span::styled(format!("{out_bar:<20}"), Style::default().fg(Color::Red)),
+            Span::styled("  ↑ ", Style::default().fg(state.theme.critical_color)),
+            Span::styled(
+                format!("{out_bar:<20}"),
+                Style::default().fg(state.theme.critical_color),
+            ),
             Span::styled(
-                format!(" {}/s", format_bytes(current_out)),
-                Style::default().fg(Color::White),
+                format!(" {}", format_rate(current_out, &state.traffic_unit)),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
 
@@ -5283,7 +9182,7 @@ fn draw_combined_traffic_graph(
 
     let traffic_graph = Paragraph::new(traffic_lines)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(traffic_graph, area);
 }
@@ -5292,14 +9191,14 @@ fn draw_combined_traffic_graph(
 fn draw_interface_sparklines(
     f: &mut Frame,
     area: Rect,
-    _state: &DashboardState,
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
     let mut sparkline_text = vec![
         Line::from(vec![Span::styled(
             "⚡ INTERFACE ACTIVITY",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -5335,17 +9234,25 @@ fn draw_interface_sparklines(
 
         sparkline_text.push(Line::from(vec![Span::styled(
             format!("{activity_level} {name:>10}"),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]));
 
         sparkline_text.push(Line::from(vec![Span::styled(
-            format!("   ↓{} {:>8}/s", in_trend, format_bytes(current_in)),
-            Style::default().fg(Color::Green),
+            format!(
+                "   ↓{} {:>10}",
+                in_trend,
+                format_rate(current_in, &state.traffic_unit)
+            ),
+            Style::default().fg(state.theme.ok_color),
         )]));
 
         sparkline_text.push(Line::from(vec![Span::styled(
-            format!("   ↑{} {:>8}/s", out_trend, format_bytes(current_out)),
-            Style::default().fg(Color::Red),
+            format!(
+                "   ↑{} {:>10}",
+                out_trend,
+                format_rate(current_out, &state.traffic_unit)
+            ),
+            Style::default().fg(state.theme.critical_color),
         )]));
 
         sparkline_text.push(Line::from(""));
@@ -5353,7 +9260,7 @@ fn draw_interface_sparklines(
 
     let sparklines = Paragraph::new(sparkline_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(sparklines, area);
 }
@@ -5366,7 +9273,7 @@ fn draw_top_connections_preview(f: &mut Frame, area: Rect, state: &DashboardStat
         Line::from(vec![Span::styled(
             "🔗 TOP CONNECTIONS",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -5402,11 +9309,11 @@ fn draw_top_connections_preview(f: &mut Frame, area: Rect, state: &DashboardStat
         preview_text.push(Line::from(vec![
             Span::styled(
                 format!("{}. {} ", i + 1, quality),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(state.theme.accent_color),
             ),
             Span::styled(
                 format!("{remote_short:<15}"),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
 
@@ -5423,7 +9330,7 @@ fn draw_top_connections_preview(f: &mut Frame, area: Rect, state: &DashboardStat
                 rtt_display,
                 process_short
             ),
-            Style::default().fg(Color::Gray),
+            Style::default().fg(state.theme.muted_color),
         )]));
 
         if i < 5 {
@@ -5434,22 +9341,22 @@ fn draw_top_connections_preview(f: &mut Frame, area: Rect, state: &DashboardStat
     if connections.is_empty() {
         preview_text.push(Line::from(vec![Span::styled(
             "   No active connections",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(state.theme.muted_color),
         )]));
         preview_text.push(Line::from(""));
         preview_text.push(Line::from(vec![Span::styled(
             "   💡 Press Tab → Connections",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]));
         preview_text.push(Line::from(vec![Span::styled(
             "      for full details",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(state.theme.warning_color),
         )]));
     }
 
     let preview = Paragraph::new(preview_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(preview, area);
 }
@@ -5480,7 +9387,7 @@ fn draw_network_details(
 fn draw_enhanced_interface_table(
     f: &mut Frame,
     area: Rect,
-    _state: &DashboardState,
+    state: &DashboardState,
     stats_calculators: &HashMap<String, StatsCalculator>,
 ) {
     let rows: Vec<Row> = stats_calculators
@@ -5490,9 +9397,15 @@ fn draw_enhanced_interface_table(
             let (avg_in, avg_out) = calculator.average_speed();
             let (_max_in, _max_out) = calculator.max_speed();
 
-            // Calculate utilization percentage (assuming 1Gbps = 125MB/s baseline)
-            let baseline_capacity = 125_000_000; // 1 Gbps in bytes/s
-            let utilization = ((current_in + current_out) * 100 / baseline_capacity).min(100);
+            // Share of the link speed, or of 1 Gbit/s where the interface reports none
+            let capacity = state
+                .devices
+                .iter()
+                .find(|device| &device.name == name)
+                .map_or(crate::device::DeviceInfo::default().capacity(), |device| {
+                    device.info.capacity()
+                });
+            let utilization = ((current_in + current_out) * 100 / capacity).min(100);
 
             let status = if current_in > 0 || current_out > 0 {
                 if utilization > 80 {
@@ -5508,10 +9421,10 @@ fn draw_enhanced_interface_table(
 
             Row::new(vec![
                 name.clone(),
-                format!("{}/s", format_bytes(current_in)),
-                format!("{}/s", format_bytes(current_out)),
-                format!("{}/s", format_bytes(avg_in)),
-                format!("{}/s", format_bytes(avg_out)),
+                format_rate(current_in, &state.traffic_unit),
+                format_rate(current_out, &state.traffic_unit),
+                format_rate(avg_in, &state.traffic_unit),
+                format_rate(avg_out, &state.traffic_unit),
                 format!("{}%", utilization),
                 status.to_string(),
             ])
@@ -5542,7 +9455,7 @@ fn draw_enhanced_interface_table(
         ])
         .style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     )
@@ -5586,81 +9499,90 @@ fn draw_network_diagnostics(
         Line::from(vec![Span::styled(
             "🔍 DIAGNOSTICS",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "📦 Packet Stats:",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]),
         Line::from(vec![
-            Span::styled("  Total In:  ", Style::default().fg(Color::Green)),
+            Span::styled("  Total In:  ", Style::default().fg(state.theme.ok_color)),
             Span::styled(
                 format_number(total_packets_in).to_string(),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Total Out: ", Style::default().fg(Color::Red)),
+            Span::styled(
+                "  Total Out: ",
+                Style::default().fg(state.theme.critical_color),
+            ),
             Span::styled(
                 format_number(total_packets_out).to_string(),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "⚠️ Error Analysis:",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]),
         Line::from(vec![
-            Span::styled("  Errors: ", Style::default().fg(Color::Yellow)),
+            Span::styled("  Errors: ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("{total_errors}"),
                 Style::default().fg(if total_errors > 0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Drops:  ", Style::default().fg(Color::Yellow)),
+            Span::styled("  Drops:  ", Style::default().fg(state.theme.warning_color)),
             Span::styled(
                 format!("{total_drops}"),
                 Style::default().fg(if total_drops > 0 {
-                    Color::Red
+                    state.theme.critical_color
                 } else {
-                    Color::Green
+                    state.theme.ok_color
                 }),
             ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "🌐 Bandwidth Total:",
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(state.theme.accent_color),
         )]),
         Line::from(vec![
-            Span::styled("  Combined: ", Style::default().fg(Color::Magenta)),
             Span::styled(
-                format!(
-                    "{}/s",
-                    format_bytes(total_bandwidth_in + total_bandwidth_out)
+                "  Combined: ",
+                Style::default().fg(state.theme.highlight_color),
+            ),
+            Span::styled(
+                format_rate(
+                    total_bandwidth_in + total_bandwidth_out,
+                    &state.traffic_unit,
                 ),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Peak Est: ", Style::default().fg(Color::Magenta)),
-            Span::styled("~1 Gbps", Style::default().fg(Color::Gray)),
+            Span::styled(
+                "  Peak Est: ",
+                Style::default().fg(state.theme.highlight_color),
+            ),
+            Span::styled("~1 Gbps", Style::default().fg(state.theme.muted_color)),
         ]),
     ];
 
     let diagnostics = Paragraph::new(diagnostics_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(diagnostics, area);
 }
@@ -5679,23 +9601,23 @@ fn format_number(num: u64) -> String {
 }
 
 fn draw_connections_list(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let connections = state.connection_monitor.get_connections();
+    let connections = state.listed_connections();
 
     // If no connections, show helpful message
-    if connections.is_empty() {
+    if state.panel_connections().is_empty() {
         let empty_content = vec![
             Line::from(vec![Span::styled(
                 "🔗 Network Connections",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(state.theme.accent_color)
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("📊 Status: ", Style::default().fg(Color::White)),
+                Span::styled("📊 Status: ", Style::default().fg(state.theme.text_color)),
                 Span::styled(
                     "Scanning for connections...",
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.theme.warning_color),
                 ),
             ]),
             Line::from(""),
@@ -5707,10 +9629,10 @@ fn draw_connections_list(f: &mut Frame, area: Rect, state: &DashboardState) {
             Line::from("• Ensure 'ss' command is available"),
             Line::from(""),
             Line::from(vec![
-                Span::styled("💡 Tip: ", Style::default().fg(Color::Green)),
+                Span::styled("💡 Tip: ", Style::default().fg(state.theme.ok_color)),
                 Span::styled(
                     "Open a browser or make network requests to see connections",
-                    Style::default().fg(Color::White),
+                    Style::default().fg(state.theme.text_color),
                 ),
             ]),
         ];
@@ -5724,13 +9646,108 @@ fn draw_connections_list(f: &mut Frame, area: Rect, state: &DashboardState) {
         return;
     }
 
+    let title = match &state.process_focus {
+        Some(focus) => format!("CONNECTIONS OF PID {} (o: listening)", focus.pid),
+        None => "CONNECTION INTELLIGENCE (o: listening)".to_string(),
+    };
+    draw_connection_table(f, area, state, &connections, &title);
+}
+
+/// Listening sockets audit: bind address, owner and exposure of every port, world
+/// exposed ones in red and often attacked ports flagged
+fn draw_listening_sockets(f: &mut Frame, area: Rect, state: &DashboardState) {
+    let sockets = state.listed_listening_sockets();
+    let exposed = sockets
+        .iter()
+        .filter(|socket| socket.exposure.is_world_exposed())
+        .count();
+    let title = format!(
+        "🎧 Listening Sockets: {} ({exposed} world-exposed) (o: connections)",
+        sockets.len()
+    );
+
+    if sockets.is_empty() {
+        let empty = Paragraph::new("No listening sockets detected")
+            .style(Style::default().fg(state.theme.muted_color))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let rows: Vec<Row> = sockets
+        .iter()
+        .map(|socket| {
+            let port = match socket.risk() {
+                Some(service) => format!("⚠ {} {service}", socket.local_addr.port()),
+                None => format!("  {}", socket.local_addr.port()),
+            };
+            let owner = match (&socket.process_name, socket.pid) {
+                (Some(name), Some(pid)) => format!("{name} ({pid})"),
+                (Some(name), None) => name.clone(),
+                (None, Some(pid)) => format!("PID {pid}"),
+                (None, None) => "unknown".to_string(),
+            };
+            let color = if socket.exposure.is_world_exposed() {
+                state.theme.critical_color
+            } else if socket.risk().is_some() {
+                state.theme.warning_color
+            } else if socket.exposure == connections::Exposure::Loopback {
+                state.theme.muted_color
+            } else {
+                state.theme.text_color
+            };
+
+            Row::new(vec![
+                port,
+                socket.protocol.as_str().to_string(),
+                socket.local_addr.ip().to_string(),
+                socket.exposure.as_str().to_string(),
+                owner,
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(18), // Port and risky service
+            Constraint::Length(5),  // Protocol
+            Constraint::Length(22), // Bind address
+            Constraint::Length(9),  // Exposure
+            Constraint::Min(12),    // Process
+        ],
+    )
+    .header(
+        Row::new(vec!["Port", "Proto", "Bind", "Exposure", "Process"]).style(
+            Style::default()
+                .fg(state.theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(table, area);
+}
+
+/// The Connections panel's table, also used for one process's connections
+fn draw_connection_table(
+    f: &mut Frame,
+    area: Rect,
+    state: &DashboardState,
+    connections: &[&NetworkConnection],
+    title: &str,
+) {
+    // Compact mode drops the Queue column so the table fits 80 columns
+    const QUEUE_COLUMN: usize = 7;
+    let compact = state.is_compact();
+
     let rows: Vec<Row> = connections
         .iter()
-        .take(15)
+        .take(CONNECTION_LIST_ROWS)
         .map(|conn| {
             let process_name = conn.process_name.as_deref().unwrap_or("unknown");
             let local_addr = format!("{}:{}", conn.local_addr.ip(), conn.local_addr.port());
-            let remote_addr = format!("{}:{}", conn.remote_addr.ip(), conn.remote_addr.port());
+            let remote_addr = state.connection_monitor.remote_display(conn);
 
             // Quality indicators based on socket info
             let quality_indicator = if let Some(rtt) = conn.socket_info.rtt {
@@ -5754,7 +9771,7 @@ fn draw_connections_list(f: &mut Frame, area: Rect, state: &DashboardState) {
             let bandwidth_display = conn
                 .socket_info
                 .bandwidth
-                .map(format_bandwidth)
+                .map(|bw| format_bandwidth(bw, &state.traffic_unit))
                 .unwrap_or_else(|| "-".to_string());
 
             let queue_info = if conn.socket_info.send_queue > 0 || conn.socket_info.recv_queue > 0 {
@@ -5766,67 +9783,79 @@ fn draw_connections_list(f: &mut Frame, area: Rect, state: &DashboardState) {
                 "-".to_string()
             };
 
-            Row::new(vec![
+            let age_display = conn
+                .age()
+                .map(format_age)
+                .unwrap_or_else(|| "-".to_string());
+
+            let mut cells = vec![
                 format!("{} {}", quality_indicator, conn.protocol.as_str()),
                 local_addr,
                 remote_addr,
                 conn.state.as_str().to_string(),
+                age_display,
                 rtt_display,
                 bandwidth_display,
                 queue_info,
                 process_name.to_string(),
-            ])
-            .style(Style::default().fg(conn.state.color()))
+            ];
+            if compact {
+                cells.remove(QUEUE_COLUMN);
+            }
+            Row::new(cells).style(Style::default().fg(conn.state.color()))
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(8),  // Protocol + Quality
-            Constraint::Length(18), // Local Address
-            Constraint::Length(18), // Remote Address
-            Constraint::Length(10), // State
-            Constraint::Length(8),  // RTT
-            Constraint::Length(10), // Bandwidth
-            Constraint::Length(8),  // Queue
-            Constraint::Min(12),    // Process
-        ],
-    )
-    .header(
-        Row::new(vec![
-            "Proto", "Local", "Remote", "State", "RTT", "BW", "Queue", "Process",
-        ])
-        .style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-    )
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("CONNECTION INTELLIGENCE"),
-    );
+    let mut widths = vec![
+        Constraint::Length(8),  // Protocol + Quality
+        Constraint::Length(18), // Local Address
+        Constraint::Length(18), // Remote Address
+        Constraint::Length(10), // State
+        Constraint::Length(7),  // Age
+        Constraint::Length(8),  // RTT
+        Constraint::Length(12), // Bandwidth
+        Constraint::Length(8),  // Queue
+        Constraint::Min(12),    // Process
+    ];
+    let mut headers = vec![
+        "Proto", "Local", "Remote", "State", "Age", "RTT", "BW", "Queue", "Process",
+    ];
+    if compact {
+        widths.remove(QUEUE_COLUMN);
+        headers.remove(QUEUE_COLUMN);
+    }
 
-    f.render_widget(table, area);
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(headers).style(
+                Style::default()
+                    .fg(state.theme.header_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(Style::default().bg(state.theme.selected_row_bg));
+
+    let mut table_state =
+        TableState::default().with_selected(state.table_state.selected().map(|row| {
+            row.min(
+                connections
+                    .len()
+                    .min(CONNECTION_LIST_ROWS)
+                    .saturating_sub(1),
+            )
+        }));
+    f.render_stateful_widget(table, area, &mut table_state);
 }
 
-fn format_bandwidth(bw: u64) -> String {
-    if bw >= 1_000_000_000 {
-        format!("{:.1}G", bw as f64 / 1_000_000_000.0)
-    } else if bw >= 1_000_000 {
-        format!("{:.0}M", bw as f64 / 1_000_000.0)
-    } else if bw >= 1_000 {
-        format!("{:.0}K", bw as f64 / 1_000.0)
-    } else {
-        format!("{bw}b")
-    }
+// Socket bandwidth estimates are in bits/s; show them like every other rate
+fn format_bandwidth(bits_per_sec: u64, unit: &TrafficUnit) -> String {
+    format_rate(bits_per_sec / 8, unit)
 }
 
 fn draw_connection_stats(f: &mut Frame, area: Rect, dashboard_state: &DashboardState) {
-    let connections = dashboard_state.connection_monitor.get_connections();
-    let connection_stats = dashboard_state.connection_monitor.get_connection_stats();
+    let connections = dashboard_state.panel_connections();
+    let connection_stats = dashboard_state.panel_connection_stats();
 
     // Calculate macOS-appropriate network intelligence metrics
     let mut _local_connections = 0;
@@ -5890,18 +9919,21 @@ fn draw_connection_stats(f: &mut Frame, area: Rect, dashboard_state: &DashboardS
         Line::from(vec![Span::styled(
             "⚡ NETWORK INTELLIGENCE",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(dashboard_state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "📈 Performance:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(dashboard_state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Avg RTT: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "  Avg RTT: ",
+                Style::default().fg(dashboard_state.theme.accent_color),
+            ),
             Span::styled(
                 if rtt_count > 0 {
                     format!("{avg_rtt:.1}ms")
@@ -5910,21 +9942,24 @@ fn draw_connection_stats(f: &mut Frame, area: Rect, dashboard_state: &DashboardS
                 },
                 Style::default()
                     .fg(if avg_rtt < 20.0 {
-                        Color::Green
+                        dashboard_state.theme.ok_color
                     } else if avg_rtt < 100.0 {
-                        Color::Yellow
+                        dashboard_state.theme.warning_color
                     } else {
-                        Color::Red
+                        dashboard_state.theme.critical_color
                     })
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Total BW: ", Style::default().fg(Color::Cyan)),
             Span::styled(
-                format_bandwidth(total_bandwidth),
+                "  Total BW: ",
+                Style::default().fg(dashboard_state.theme.accent_color),
+            ),
+            Span::styled(
+                format_bandwidth(total_bandwidth, &dashboard_state.traffic_unit),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(dashboard_state.theme.text_color)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -5932,70 +9967,97 @@ fn draw_connection_stats(f: &mut Frame, area: Rect, dashboard_state: &DashboardS
         Line::from(vec![Span::styled(
             "🎯 Quality Distribution:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(dashboard_state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  🟢 Excellent: ", Style::default().fg(Color::Green)),
+            Span::styled(
+                "  🟢 Excellent: ",
+                Style::default().fg(dashboard_state.theme.ok_color),
+            ),
             Span::styled(
                 format!("{high_quality_connections}"),
-                Style::default().fg(Color::White),
+                Style::default().fg(dashboard_state.theme.text_color),
+            ),
+            Span::styled(
+                " (<10ms)",
+                Style::default().fg(dashboard_state.theme.muted_color),
             ),
-            Span::styled(" (<10ms)", Style::default().fg(Color::Gray)),
         ]),
         Line::from(vec![
-            Span::styled("  🟡 Good: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  🟡 Good: ",
+                Style::default().fg(dashboard_state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{medium_quality_connections}"),
-                Style::default().fg(Color::White),
+                Style::default().fg(dashboard_state.theme.text_color),
+            ),
+            Span::styled(
+                " (10-50ms)",
+                Style::default().fg(dashboard_state.theme.muted_color),
             ),
-            Span::styled(" (10-50ms)", Style::default().fg(Color::Gray)),
         ]),
         Line::from(vec![
-            Span::styled("  🔴 Poor: ", Style::default().fg(Color::Red)),
+            Span::styled(
+                "  🔴 Poor: ",
+                Style::default().fg(dashboard_state.theme.critical_color),
+            ),
             Span::styled(
                 format!("{poor_quality_connections}"),
-                Style::default().fg(Color::White),
+                Style::default().fg(dashboard_state.theme.text_color),
+            ),
+            Span::styled(
+                " (>50ms)",
+                Style::default().fg(dashboard_state.theme.muted_color),
             ),
-            Span::styled(" (>50ms)", Style::default().fg(Color::Gray)),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "⚠️ Reliability:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(dashboard_state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Retrans: ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "  Retrans: ",
+                Style::default().fg(dashboard_state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{total_retrans}"),
                 Style::default().fg(if total_retrans == 0 {
-                    Color::Green
+                    dashboard_state.theme.ok_color
                 } else {
-                    Color::Yellow
+                    dashboard_state.theme.warning_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Lost: ", Style::default().fg(Color::Red)),
+            Span::styled(
+                "  Lost: ",
+                Style::default().fg(dashboard_state.theme.critical_color),
+            ),
             Span::styled(
                 format!("{total_lost}"),
                 Style::default().fg(if total_lost == 0 {
-                    Color::Green
+                    dashboard_state.theme.ok_color
                 } else {
-                    Color::Red
+                    dashboard_state.theme.critical_color
                 }),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Congested: ", Style::default().fg(Color::Magenta)),
+            Span::styled(
+                "  Congested: ",
+                Style::default().fg(dashboard_state.theme.highlight_color),
+            ),
             Span::styled(
                 format!("{congested_connections}"),
                 Style::default().fg(if congested_connections == 0 {
-                    Color::Green
+                    dashboard_state.theme.ok_color
                 } else {
-                    Color::Red
+                    dashboard_state.theme.critical_color
                 }),
             ),
         ]),
@@ -6003,31 +10065,40 @@ fn draw_connection_stats(f: &mut Frame, area: Rect, dashboard_state: &DashboardS
         Line::from(vec![Span::styled(
             "🌐 Network Overview:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(dashboard_state.theme.accent_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Interfaces: ", Style::default().fg(Color::Blue)),
-            Span::styled(format!("{interfaces}"), Style::default().fg(Color::White)),
+            Span::styled(
+                "  Interfaces: ",
+                Style::default().fg(dashboard_state.theme.info_color),
+            ),
+            Span::styled(
+                format!("{interfaces}"),
+                Style::default().fg(dashboard_state.theme.text_color),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("  TCP/UDP: ", Style::default().fg(Color::Green)),
+            Span::styled(
+                "  TCP/UDP: ",
+                Style::default().fg(dashboard_state.theme.ok_color),
+            ),
             Span::styled(
                 format!("{}/{}", connection_stats.tcp, connection_stats.udp),
-                Style::default().fg(Color::White),
+                Style::default().fg(dashboard_state.theme.text_color),
             ),
         ]),
     ];
 
     let stats_widget = Paragraph::new(stats_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(dashboard_state.theme.text_color));
 
     f.render_widget(stats_widget, area);
 }
 
 fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let connections = state.connection_monitor.get_connections();
+    let connections = state.panel_connections();
 
     // Build rich host analytics
     let mut host_analytics: std::collections::HashMap<IpAddr, HostMetrics> =
@@ -6078,7 +10149,7 @@ fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
         Line::from(vec![Span::styled(
             "🌐 REMOTE HOST INTELLIGENCE",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -6112,25 +10183,28 @@ fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
         let geo_hint = get_geographic_hint(**ip);
 
         hosts_text.push(Line::from(vec![
-            Span::styled(format!("{icon} "), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{icon} "),
+                Style::default().fg(state.theme.warning_color),
+            ),
             Span::styled(
                 format!("{quality_indicator} "),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
             Span::styled(
                 format!("{ip} "),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(state.theme.accent_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(geo_hint, Style::default().fg(Color::Gray)),
+            Span::styled(geo_hint, Style::default().fg(state.theme.muted_color)),
         ]));
 
         hosts_text.push(Line::from(vec![
             Span::styled("     ", Style::default()),
             Span::styled(
                 format!("{}conn ", metrics.connection_count),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
             Span::styled(
                 if avg_rtt > 0.0 {
@@ -6139,16 +10213,19 @@ fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
                     "".to_string()
                 },
                 Style::default().fg(if avg_rtt < 20.0 {
-                    Color::Green
+                    state.theme.ok_color
                 } else if avg_rtt < 100.0 {
-                    Color::Yellow
+                    state.theme.warning_color
                 } else {
-                    Color::Red
+                    state.theme.critical_color
                 }),
             ),
             Span::styled(
-                format!("{}BW", format_bandwidth(metrics.total_bandwidth)),
-                Style::default().fg(Color::Magenta),
+                format!(
+                    "{} BW",
+                    format_bandwidth(metrics.total_bandwidth, &state.traffic_unit)
+                ),
+                Style::default().fg(state.theme.highlight_color),
             ),
         ]));
 
@@ -6157,7 +10234,7 @@ fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
                 Span::styled("     ", Style::default()),
                 Span::styled(
                     format!("⚠️ {}ret {}lost", metrics.total_retrans, metrics.total_lost),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(state.theme.critical_color),
                 ),
             ]));
         }
@@ -6168,13 +10245,13 @@ fn draw_top_remote_hosts(f: &mut Frame, area: Rect, state: &DashboardState) {
     if sorted_hosts.is_empty() {
         hosts_text.push(Line::from(vec![Span::styled(
             "No remote connections detected",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(state.theme.muted_color),
         )]));
     }
 
     let hosts_widget = Paragraph::new(hosts_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(hosts_widget, area);
 }
@@ -6220,14 +10297,24 @@ fn get_geographic_hint(ip: IpAddr) -> String {
 }
 
 fn draw_process_list(f: &mut Frame, area: Rect, state: &DashboardState) {
-    let processes = state.process_monitor.get_top_network_processes(15);
+    if let Some(drilldown) = &state.process_drilldown {
+        draw_process_drilldown(f, area, state, drilldown);
+        return;
+    }
+
+    let processes = state.listed_processes();
+    let bandwidth: HashMap<u32, (u64, u64)> = state
+        .process_bandwidth
+        .iter()
+        .map(|(proc, bw_in, bw_out)| (proc.pid, (*bw_in, *bw_out)))
+        .collect();
 
     // Safety check - ensure we have valid processes
     if processes.is_empty() {
         let empty_text = vec![
             Line::from(vec![Span::styled(
                 "No network processes found",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(state.theme.warning_color),
             )]),
             Line::from(""),
             Line::from("Processes are being monitored..."),
@@ -6242,19 +10329,38 @@ fn draw_process_list(f: &mut Frame, area: Rect, state: &DashboardState) {
         return;
     }
 
+    let measured = state.process_monitor.measures_traffic();
+    if state.processes_by_container {
+        draw_container_list(f, area, state, measured);
+        return;
+    }
+
+    // Attributed totals, to compare with the interfaces: UDP, loopback and headers
+    // aren't attributed, so they come out somewhat lower
+    let title = if measured {
+        let totals = state.process_monitor.get_process_stats();
+        format!(
+            "⚡ Network Process Activity: ↓ {} ↑ {} attributed (Enter: connections, K: kill, g: containers)",
+            format_rate(totals.bytes_received, &state.traffic_unit),
+            format_rate(totals.bytes_sent, &state.traffic_unit)
+        )
+    } else {
+        "⚡ Network Process Activity (Enter: connections, K: kill, g: containers)".to_string()
+    };
+
     let rows: Vec<Row> = processes
         .iter()
-        .filter_map(|proc| {
-            // Safety check - ensure process fields are valid
-            if proc.name.is_empty() && proc.command.is_empty() {
-                return None; // Skip invalid processes
-            }
-
-            let command_display = if proc.command.chars().count() > 25 {
-                let truncated: String = proc.command.chars().take(22).collect();
+        .map(|proc| {
+            // Unreadable command lines are bracketed like ps shows kernel threads
+            let command = proc
+                .cmdline
+                .clone()
+                .unwrap_or_else(|| format!("[{}]", proc.name));
+            let command_display = if command.chars().count() > 60 {
+                let truncated: String = command.chars().take(57).collect();
                 format!("{truncated}...")
             } else {
-                proc.command.clone()
+                command
             };
 
             // Ensure process name is not too long and contains valid characters
@@ -6264,67 +10370,245 @@ fn draw_process_list(f: &mut Frame, area: Rect, state: &DashboardState) {
                 proc.name.clone()
             };
 
-            Some(Row::new(vec![
+            // Measured socket traffic where available, else the connection estimate
+            let (bw_in, bw_out) = if measured {
+                (proc.bytes_received, proc.bytes_sent)
+            } else {
+                bandwidth.get(&proc.pid).copied().unwrap_or((0, 0))
+            };
+
+            Row::new(vec![
                 format!("{}", proc.pid),
                 safe_name,
+                proc.container.clone().unwrap_or_else(|| "-".to_string()),
                 command_display,
                 format!("{}", proc.connections),
-                format!("{}/s", format_bytes(proc.bytes_sent)),
-                format!("{}/s", format_bytes(proc.bytes_received)),
-                format!("{}/s", format_bytes(proc.total_bytes())),
-            ]))
+                format_rate(bw_in, &state.traffic_unit),
+                format_rate(bw_out, &state.traffic_unit),
+                format_rate(bw_in + bw_out, &state.traffic_unit),
+            ])
         })
         .collect();
 
-    // Safety check - ensure we have valid rows after filtering
-    if rows.is_empty() {
-        let empty_text = vec![
-            Line::from(vec![Span::styled(
-                "No valid network processes",
-                Style::default().fg(Color::Yellow),
-            )]),
-            Line::from(""),
-            Line::from("Process data is being collected..."),
-        ];
-
-        let paragraph = Paragraph::new(empty_text).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("⚡ Network Process Activity"),
-        );
-        f.render_widget(paragraph, area);
-        return;
-    }
-
     let table = Table::new(
         rows,
         [
             Constraint::Length(8),  // PID
             Constraint::Length(15), // Name
-            Constraint::Length(25), // Command
+            Constraint::Length(12), // Container
+            Constraint::Min(25),    // Command
             Constraint::Length(8),  // Connections
-            Constraint::Length(12), // Sent
-            Constraint::Length(12), // Received
+            Constraint::Length(12), // In
+            Constraint::Length(12), // Out
             Constraint::Length(12), // Total
         ],
     )
     .header(
         Row::new(vec![
-            "PID", "Name", "Command", "Conn", "Sent", "Recv", "Total",
+            "PID",
+            "Name",
+            "Container",
+            "Command",
+            "Conn",
+            "↓ In",
+            "↑ Out",
+            "Total",
         ])
         .style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .row_highlight_style(Style::default().bg(state.theme.selected_row_bg));
+
+    // Same clamping as DashboardState::selected_process, so K acts on the highlighted row
+    let mut table_state = TableState::default().with_selected(
+        state
+            .table_state
+            .selected()
+            .map(|row| row.min(processes.len() - 1)),
+    );
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// Enter on a process: its totals above its connections. The view stays open when
+/// the process exits, so its last connections can still be read.
+fn draw_process_drilldown(
+    f: &mut Frame,
+    area: Rect,
+    state: &DashboardState,
+    drilldown: &ProcessDrilldown,
+) {
+    let connections = state.drilldown_connections();
+    let process = state.process_monitor.get_process(drilldown.pid);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    let mut listening: Vec<u16> = connections
+        .iter()
+        .filter(|conn| conn.state == connections::ConnectionState::Listen)
+        .map(|conn| conn.local_addr.port())
+        .collect();
+    listening.sort_unstable();
+    listening.dedup();
+    let listening = if listening.is_empty() {
+        "none".to_string()
+    } else {
+        listening
+            .iter()
+            .map(u16::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    // Same source as the process list: measured rates, else the connection estimate
+    let (bw_in, bw_out) = match process {
+        Some(process) if state.process_monitor.measures_traffic() => {
+            (process.bytes_received, process.bytes_sent)
+        }
+        _ => state
+            .process_bandwidth
+            .iter()
+            .find(|(proc, _, _)| proc.pid == drilldown.pid)
+            .map_or((0, 0), |(_, bw_in, bw_out)| (*bw_in, *bw_out)),
+    };
+
+    let status = if process.is_some() {
+        Span::styled("running", Style::default().fg(state.theme.ok_color))
+    } else {
+        Span::styled(
+            "exited",
+            Style::default()
+                .fg(state.theme.warning_color)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let header = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", drilldown.name),
+                Style::default()
+                    .fg(state.theme.text_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("PID {} ", drilldown.pid),
+                Style::default().fg(state.theme.accent_color),
+            ),
+            status,
+        ]),
+        Line::from(format!(
+            "Connections: {}   Listening: {}   ↓ {} ↑ {}",
+            connections.len(),
+            listening,
+            format_rate(bw_in, &state.traffic_unit),
+            format_rate(bw_out, &state.traffic_unit)
+        )),
+    ];
+    f.render_widget(
+        Paragraph::new(header)
+            .style(Style::default().fg(state.theme.text_color))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("⚡ Process Connections (Esc: back, K: kill)"),
+            ),
+        chunks[0],
+    );
+
+    if connections.is_empty() {
+        let message = if process.is_some() {
+            "No connections open"
+        } else {
+            "The process has exited; press Esc to return to the list"
+        };
+        f.render_widget(
+            Paragraph::new(message)
+                .style(Style::default().fg(state.theme.warning_color))
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        return;
+    }
+    draw_connection_table(f, chunks[1], state, &connections, "Connections");
+}
+
+/// Processes panel grouped with g: one row per container, host processes under "-"
+fn draw_container_list(f: &mut Frame, area: Rect, state: &DashboardState, measured: bool) {
+    let containers = state.listed_containers();
+
+    // Without measured rates, sum the connection estimate over each container instead
+    let mut estimated: HashMap<&str, (u64, u64)> = HashMap::new();
+    if !measured {
+        for (proc, bw_in, bw_out) in &state.process_bandwidth {
+            let entry = estimated
+                .entry(proc.container.as_deref().unwrap_or("-"))
+                .or_insert((0, 0));
+            entry.0 += bw_in;
+            entry.1 += bw_out;
+        }
+    }
+
+    let rows: Vec<Row> = containers
+        .iter()
+        .map(|container| {
+            let (bw_in, bw_out) = if measured {
+                (container.bytes_received, container.bytes_sent)
+            } else {
+                estimated
+                    .get(container.container.as_str())
+                    .copied()
+                    .unwrap_or((0, 0))
+            };
+
+            Row::new(vec![
+                container.container.clone(),
+                format!("{}", container.processes),
+                format!("{}", container.connections),
+                format_rate(bw_in, &state.traffic_unit),
+                format_rate(bw_out, &state.traffic_unit),
+                format_rate(bw_in + bw_out, &state.traffic_unit),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(14),    // Container
+            Constraint::Length(8),  // Processes
+            Constraint::Length(8),  // Connections
+            Constraint::Length(12), // In
+            Constraint::Length(12), // Out
+            Constraint::Length(12), // Total
+        ],
+    )
+    .header(
+        Row::new(vec!["Container", "Procs", "Conn", "↓ In", "↑ Out", "Total"]).style(
+            Style::default()
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         ),
     )
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title("⚡ Network Process Activity"),
-    );
+            .title("⚡ Network Activity by Container (g: processes)"),
+    )
+    .row_highlight_style(Style::default().bg(state.theme.selected_row_bg));
 
-    f.render_widget(table, area);
+    let mut table_state = TableState::default().with_selected(
+        state
+            .table_state
+            .selected()
+            .map(|row| row.min(containers.len().saturating_sub(1))),
+    );
+    f.render_stateful_widget(table, area, &mut table_state);
 }
 
 fn draw_top_processes_by_connections(f: &mut Frame, area: Rect, state: &DashboardState) {
@@ -6340,7 +10624,7 @@ fn draw_top_processes_by_connections(f: &mut Frame, area: Rect, state: &Dashboar
         Line::from(vec![Span::styled(
             "🔥 TOP BY CONNECTIONS",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -6355,22 +10639,31 @@ fn draw_top_processes_by_connections(f: &mut Frame, area: Rect, state: &Dashboar
         };
 
         process_text.push(Line::from(vec![
-            Span::styled(format!("{icon} "), Style::default().fg(Color::Yellow)),
-            Span::styled(format!("{name}: "), Style::default().fg(Color::Cyan)),
-            Span::styled(format!("{count} conn"), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("{icon} "),
+                Style::default().fg(state.theme.warning_color),
+            ),
+            Span::styled(
+                format!("{name}: "),
+                Style::default().fg(state.theme.accent_color),
+            ),
+            Span::styled(
+                format!("{count} conn"),
+                Style::default().fg(state.theme.text_color),
+            ),
         ]));
     }
 
     if top_processes.is_empty() {
         process_text.push(Line::from(vec![Span::styled(
             "No processes with connections",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(state.theme.muted_color),
         )]));
     }
 
     let process_widget = Paragraph::new(process_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(process_widget, area);
 }
@@ -6382,7 +10675,7 @@ fn draw_listening_services(f: &mut Frame, area: Rect, state: &DashboardState) {
         Line::from(vec![Span::styled(
             "🔊 LISTENING SERVICES",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(state.theme.header_color)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -6399,11 +10692,17 @@ fn draw_listening_services(f: &mut Frame, area: Rect, state: &DashboardState) {
         };
 
         services_text.push(Line::from(vec![
-            Span::styled(format!("{service_icon} "), Style::default().fg(Color::Blue)),
-            Span::styled(format!("{}: ", proc.name), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{service_icon} "),
+                Style::default().fg(state.theme.info_color),
+            ),
+            Span::styled(
+                format!("{}: ", proc.name),
+                Style::default().fg(state.theme.accent_color),
+            ),
             Span::styled(
                 format!("{} ports", proc.listening_ports),
-                Style::default().fg(Color::White),
+                Style::default().fg(state.theme.text_color),
             ),
         ]));
     }
@@ -6411,42 +10710,44 @@ fn draw_listening_services(f: &mut Frame, area: Rect, state: &DashboardState) {
     if listening_processes.is_empty() {
         services_text.push(Line::from(vec![Span::styled(
             "No listening services detected",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(state.theme.muted_color),
         )]));
     }
 
     let services_widget = Paragraph::new(services_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(state.theme.text_color));
 
     f.render_widget(services_widget, area);
 }
 
-fn draw_forensics_error(f: &mut Frame, area: Rect) {
+fn draw_forensics_error(f: &mut Frame, area: Rect, theme: &Theme) {
     let block = Block::default()
         .title("🔍 Security Forensics (Error Recovery)")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red));
+        .border_style(Style::default().fg(theme.critical_color));
 
     let paragraph = Paragraph::new(vec![
         Line::from(""),
         Line::from(vec![Span::styled(
             "⚠️ Forensics Analysis Error",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.critical_color)
+                .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "• Connection monitoring experienced an issue",
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text_color),
         )]),
         Line::from(vec![Span::styled(
             "• Forensics disabled for stability",
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text_color),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "  System will recover automatically",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.muted_color),
         )]),
     ])
     .block(block)
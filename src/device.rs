@@ -1,7 +1,10 @@
-use crate::error::Result;
-use std::time::SystemTime;
+use crate::error::{NetwatchError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::time::{Duration, Instant, SystemTime};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkStats {
     pub timestamp: SystemTime,
     pub bytes_in: u64,
@@ -36,10 +39,236 @@ impl NetworkStats {
     }
 }
 
+/// How often the selected interface's driver counters are read again
+pub const HW_STATS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Name fragments of driver counters that count errors or losses rather than traffic,
+/// e.g. rx_crc_errors, tx_carrier_errors, rx_fifo_errors, rx_missed, rx0_drops
+const HW_ERROR_WORDS: &[&str] = &[
+    "err",
+    "drop",
+    "crc",
+    "fifo",
+    "miss",
+    "discard",
+    "carrier",
+    "collision",
+    "overrun",
+    "fail",
+    "timeout",
+    "abort",
+    "jabber",
+    "fragment",
+    "undersize",
+    "oversize",
+    "no_buf",
+];
+
+/// Name of the synthetic device that sums every physical interface (`ShowTotalDevice`).
+/// Interface names cannot contain spaces, so no real interface is called this.
+pub const TOTAL_DEVICE: &str = "All interfaces";
+
+/// How often each interface's link metadata is read again
+pub const DEVICE_INFO_INTERVAL: Duration = Duration::from_secs(10);
+/// Link speed assumed for utilization where the interface reports none: 1 Gbit/s
+pub const DEFAULT_LINK_SPEED_MBPS: u64 = 1000;
+
+/// What kind of link an interface is, as far as the platform tells
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterfaceKind {
+    Loopback,
+    Ethernet,
+    Wifi,
+    Bridge,
+    Bond,
+    Vlan,
+    Tun,
+    #[default]
+    Unknown,
+}
+
+impl InterfaceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Loopback => "loopback",
+            Self::Ethernet => "ethernet",
+            Self::Wifi => "wifi",
+            Self::Bridge => "bridge",
+            Self::Bond => "bond",
+            Self::Vlan => "vlan",
+            Self::Tun => "tun",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Link metadata of an interface; None wherever the platform or driver does not say
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceInfo {
+    /// Negotiated speed in Mbit/s; virtual interfaces and links that are down have none
+    pub speed_mbps: Option<u64>,
+    /// "full" or "half"
+    pub duplex: Option<String>,
+    pub mtu: Option<u32>,
+    pub mac: Option<String>,
+    /// "up", "down", "dormant", ...
+    pub operstate: Option<String>,
+    pub carrier: Option<bool>,
+    pub kind: InterfaceKind,
+    /// Bridge or bond the interface is a port of, or the link a VLAN sits on
+    pub parent: Option<String>,
+    /// Backed by a hardware device rather than a bridge, bond, VLAN or tunnel
+    pub physical: Option<bool>,
+}
+
+/// Radio metrics of a wireless interface; None wherever the driver or tool does not say
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WirelessInfo {
+    /// Received signal strength in dBm
+    pub rssi_dbm: Option<i32>,
+    /// Noise floor in dBm
+    pub noise_dbm: Option<i32>,
+    /// Rate of the last transmitted frame in Mbit/s
+    pub tx_rate_mbps: Option<f64>,
+    pub channel: Option<u32>,
+}
+
+impl WirelessInfo {
+    /// Signal-to-noise ratio in dB, when both signal and noise are known
+    pub fn snr_db(&self) -> Option<i32> {
+        Some(self.rssi_dbm? - self.noise_dbm?)
+    }
+}
+
+impl DeviceInfo {
+    /// Link capacity in bytes/s, for utilization; the default link speed where unknown
+    pub fn capacity(&self) -> u64 {
+        self.speed_mbps.unwrap_or(DEFAULT_LINK_SPEED_MBPS) * 1_000_000 / 8
+    }
+}
+
 pub trait NetworkReader: Send + Sync {
     fn list_devices(&self) -> Result<Vec<String>>;
     fn read_stats(&self, device: &str) -> Result<NetworkStats>;
     fn is_available(&self) -> bool;
+
+    /// Speed, duplex, MTU, MAC, state and kind of `device`, where the platform has them
+    fn read_info(&self, _device: &str) -> DeviceInfo {
+        DeviceInfo::default()
+    }
+
+    /// Signal, noise, rate and channel of `device` if it is a wireless interface the
+    /// platform can describe
+    fn read_wireless(&self, _device: &str) -> Option<WirelessInfo> {
+        None
+    }
+
+    /// Addresses assigned to each interface, where they describe the interfaces whose
+    /// counters this reader returns; None for readers of another host or a recording
+    fn read_addresses(&self) -> Option<HashMap<String, BTreeSet<IpAddr>>> {
+        None
+    }
+
+    /// Whether `device` is a software interface (bridge, veth, tunnel, ...) rather than
+    /// hardware; None where the reader cannot tell, and [`InterfaceFilter`] goes by name
+    fn is_virtual(&self, _device: &str) -> Option<bool> {
+        None
+    }
+
+    /// Samples of `device` as the reader produces them, for readers fed by something
+    /// that pushes (an embedding application, a collector). None means poll
+    /// [`read_stats`](Self::read_stats). No built-in reader pushes: neither Linux
+    /// netlink nor the BSD routing socket announces counter changes, only link state
+    /// changes, which the dashboard already follows.
+    #[cfg(feature = "push-reader")]
+    fn subscribe(&self, _device: &str) -> Option<std::sync::mpsc::Receiver<NetworkStats>> {
+        None
+    }
+}
+
+/// Each subscribed interface's receiver with the newest sample it delivered
+#[cfg(feature = "push-reader")]
+type Subscriptions = HashMap<
+    String,
+    (
+        std::sync::mpsc::Receiver<NetworkStats>,
+        Option<NetworkStats>,
+    ),
+>;
+
+/// Wraps a reader so interfaces it offers a subscription for are served their latest
+/// pushed sample, and the rest are polled as before
+#[cfg(feature = "push-reader")]
+pub struct SubscribedReader {
+    inner: Box<dyn NetworkReader>,
+    subscriptions: std::sync::Mutex<Subscriptions>,
+}
+
+#[cfg(feature = "push-reader")]
+impl SubscribedReader {
+    pub fn new(inner: Box<dyn NetworkReader>, devices: &[String]) -> Self {
+        let subscriptions = devices
+            .iter()
+            .filter_map(|device| {
+                let receiver = inner.subscribe(device)?;
+                Some((device.clone(), (receiver, None)))
+            })
+            .collect();
+        Self {
+            inner,
+            subscriptions: std::sync::Mutex::new(subscriptions),
+        }
+    }
+}
+
+#[cfg(feature = "push-reader")]
+impl NetworkReader for SubscribedReader {
+    fn list_devices(&self) -> Result<Vec<String>> {
+        self.inner.list_devices()
+    }
+
+    fn read_stats(&self, device: &str) -> Result<NetworkStats> {
+        use std::sync::mpsc::TryRecvError;
+
+        let mut subscriptions = self.subscriptions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((receiver, latest)) = subscriptions.get_mut(device) {
+            let disconnected = loop {
+                match receiver.try_recv() {
+                    Ok(stats) => *latest = Some(stats),
+                    Err(TryRecvError::Empty) => break false,
+                    Err(TryRecvError::Disconnected) => break true,
+                }
+            };
+            if disconnected {
+                // The pusher gave up; poll from now on
+                subscriptions.remove(device);
+            } else if let Some(stats) = latest {
+                return Ok(stats.clone());
+            }
+        }
+        drop(subscriptions);
+        self.inner.read_stats(device)
+    }
+
+    fn is_available(&self) -> bool {
+        self.inner.is_available()
+    }
+
+    fn read_info(&self, device: &str) -> DeviceInfo {
+        self.inner.read_info(device)
+    }
+
+    fn read_wireless(&self, device: &str) -> Option<WirelessInfo> {
+        self.inner.read_wireless(device)
+    }
+
+    fn read_addresses(&self) -> Option<HashMap<String, BTreeSet<IpAddr>>> {
+        self.inner.read_addresses()
+    }
+
+    fn is_virtual(&self, device: &str) -> Option<bool> {
+        self.inner.is_virtual(device)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +276,19 @@ pub struct Device {
     pub name: String,
     pub stats: NetworkStats,
     pub is_active: bool,
+    /// Docker container or network behind the interface, shown next to its name
+    pub label: Option<String>,
+    /// Driver counters (`ethtool -S`), read while the interface is selected; empty when
+    /// the driver has none
+    pub hw_stats: HashMap<String, u64>,
+    // The sample before hw_stats, to tell which counters grew
+    hw_stats_previous: HashMap<String, u64>,
+    hw_stats_updated: Option<Instant>,
+    /// Link metadata, read every DEVICE_INFO_INTERVAL
+    pub info: DeviceInfo,
+    info_updated: Option<Instant>,
+    /// Radio metrics, read on every refresh while `info` says the interface is Wi-Fi
+    pub wireless: Option<WirelessInfo>,
 }
 
 impl Device {
@@ -55,6 +297,77 @@ impl Device {
             name,
             stats: NetworkStats::new(),
             is_active: false,
+            label: None,
+            hw_stats: HashMap::new(),
+            hw_stats_previous: HashMap::new(),
+            hw_stats_updated: None,
+            info: DeviceInfo::default(),
+            info_updated: None,
+            wireless: None,
+        }
+    }
+
+    /// Re-read the link metadata once DEVICE_INFO_INTERVAL has passed since the last read
+    pub fn refresh_info(&mut self, reader: &dyn NetworkReader) {
+        if self
+            .info_updated
+            .is_some_and(|updated| updated.elapsed() < DEVICE_INFO_INTERVAL)
+        {
+            return;
+        }
+        self.info_updated = Some(Instant::now());
+        self.info = reader.read_info(&self.name);
+    }
+
+    /// Re-read the radio metrics of a Wi-Fi interface; other kinds have none
+    pub fn refresh_wireless(&mut self, reader: &dyn NetworkReader) {
+        self.wireless = if self.info.kind == InterfaceKind::Wifi {
+            reader.read_wireless(&self.name)
+        } else {
+            None
+        };
+    }
+
+    /// Re-read the driver counters once HW_STATS_INTERVAL has passed since the last read
+    pub fn refresh_hw_stats(&mut self) {
+        if self
+            .hw_stats_updated
+            .is_some_and(|updated| updated.elapsed() < HW_STATS_INTERVAL)
+        {
+            return;
+        }
+        self.hw_stats_updated = Some(Instant::now());
+        let current = crate::platform::ethtool_stats(&self.name);
+        self.hw_stats_previous = std::mem::replace(&mut self.hw_stats, current);
+    }
+
+    /// The non-zero error and loss counters among the driver counters, largest first and
+    /// at most `limit`, each with whether it grew since the previous read
+    pub fn hw_error_counters(&self, limit: usize) -> Vec<(&str, u64, bool)> {
+        let mut counters: Vec<(&str, u64, bool)> = self
+            .hw_stats
+            .iter()
+            .filter(|(name, &value)| {
+                value > 0 && HW_ERROR_WORDS.iter().any(|word| name.contains(word))
+            })
+            .map(|(name, &value)| {
+                let grew = self
+                    .hw_stats_previous
+                    .get(name)
+                    .is_some_and(|&previous| value > previous);
+                (name.as_str(), value, grew)
+            })
+            .collect();
+        counters.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counters.truncate(limit);
+        counters
+    }
+
+    /// The name with its label in parentheses, e.g. `veth3a2b4c (nginx)`
+    pub fn display_name(&self) -> String {
+        match self.label {
+            Some(ref label) => format!("{} ({label})", self.name),
+            None => self.name.clone(),
         }
     }
 
@@ -72,3 +385,301 @@ impl Device {
         }
     }
 }
+
+/// Interface selector for `--match`: a glob (`eth*`, `wlan?`, `en[0-3]`) or, when the
+/// pattern uses regex syntax, a regex (`en.*`, `(eth|wlan)\d+`). `--interfaces-regex`
+/// and `--interfaces-regex-exclude` take a regex only. Always matches the whole
+/// interface name.
+#[derive(Debug, Clone)]
+pub struct InterfacePattern {
+    regex: regex::Regex,
+}
+
+impl InterfacePattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let is_regex = pattern.contains(['.', '+', '^', '$', '\\', '|', '(', ')', '{', '}']);
+        if is_regex {
+            Self::regex(pattern)
+        } else {
+            Self::compile(pattern, &glob_to_regex(pattern))
+        }
+    }
+
+    /// A regex, whatever it looks like; `eth*` means `et` followed by any number of `h`s
+    pub fn regex(pattern: &str) -> Result<Self> {
+        Self::compile(pattern, pattern)
+    }
+
+    fn compile(pattern: &str, body: &str) -> Result<Self> {
+        if pattern.is_empty() || pattern.len() > 256 {
+            return Err(NetwatchError::Config(format!(
+                "Invalid interface pattern length: {pattern:?}"
+            )));
+        }
+
+        let regex = regex::Regex::new(&format!("^(?:{body})$")).map_err(|e| {
+            // Syntax errors span several lines pointing into the pattern; the last one
+            // says what is wrong
+            let reason = match e {
+                regex::Error::Syntax(ref message) => message
+                    .lines()
+                    .last()
+                    .unwrap_or(message)
+                    .trim_start_matches("error: ")
+                    .to_string(),
+                e => e.to_string(),
+            };
+            NetwatchError::Config(format!("Invalid interface pattern {pattern:?}: {reason}"))
+        })?;
+        Ok(Self { regex })
+    }
+
+    pub fn is_match(&self, interface: &str) -> bool {
+        self.regex.is_match(interface)
+    }
+}
+
+/// Name prefixes of the software interfaces container runtimes, hypervisors and VPNs
+/// create, for readers that cannot say which interfaces are virtual
+const VIRTUAL_PREFIXES: [&str; 11] = [
+    "docker", "veth", "br-", "virbr", "vnet", "tun", "tap", "cni", "flannel", "cali", "vxlan",
+];
+
+/// What `Devices = "all"` leaves out: loopback (`ExcludeLoopback`), software interfaces
+/// (`ExcludeVirtual`) and names matching `ExcludePatterns`
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceFilter {
+    pub loopback: bool,
+    pub virtual_interfaces: bool,
+    patterns: Vec<(String, InterfacePattern)>,
+}
+
+impl InterfaceFilter {
+    pub fn new(loopback: bool, virtual_interfaces: bool, patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| Ok((pattern.clone(), InterfacePattern::parse(pattern)?)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            loopback,
+            virtual_interfaces,
+            patterns,
+        })
+    }
+
+    /// Why `device` is left out, or None when it is monitored
+    pub fn exclusion(&self, device: &str, reader: &dyn NetworkReader) -> Option<String> {
+        if self.loopback && is_loopback_name(device) {
+            return Some("loopback (ExcludeLoopback)".to_string());
+        }
+        if self.virtual_interfaces {
+            match reader.is_virtual(device) {
+                Some(true) => return Some("virtual (ExcludeVirtual)".to_string()),
+                Some(false) => {}
+                None if VIRTUAL_PREFIXES
+                    .iter()
+                    .any(|prefix| device.starts_with(prefix)) =>
+                {
+                    return Some("virtual by its name (ExcludeVirtual)".to_string());
+                }
+                None => {}
+            }
+        }
+        self.patterns
+            .iter()
+            .find(|(_, pattern)| pattern.is_match(device))
+            .map(|(pattern, _)| format!("matches `{pattern}` (ExcludePatterns)"))
+    }
+}
+
+/// `lo` on Linux, `lo0` on macOS and the BSDs
+fn is_loopback_name(device: &str) -> bool {
+    device
+        .strip_prefix("lo")
+        .is_some_and(|unit| unit.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::new();
+    let mut in_class = false;
+    for c in glob.chars() {
+        match c {
+            '*' if !in_class => regex.push_str(".*"),
+            '?' if !in_class => regex.push('.'),
+            '[' if !in_class => {
+                in_class = true;
+                regex.push('[');
+            }
+            ']' if in_class => {
+                in_class = false;
+                regex.push(']');
+            }
+            // Shell-style negated class
+            '!' if in_class && regex.ends_with('[') => regex.push('^'),
+            // Class contents keep their meaning, so ranges like [0-3] work
+            c if in_class && c != '\\' && c != '[' => regex.push(c),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interface_filter_reasons() {
+        struct Reader(Option<bool>);
+        impl NetworkReader for Reader {
+            fn list_devices(&self) -> Result<Vec<String>> {
+                Ok(Vec::new())
+            }
+            fn read_stats(&self, _device: &str) -> Result<NetworkStats> {
+                Ok(NetworkStats::new())
+            }
+            fn is_available(&self) -> bool {
+                true
+            }
+            fn is_virtual(&self, device: &str) -> Option<bool> {
+                self.0.map(|known| known && device != "eth0")
+            }
+        }
+
+        let filter = InterfaceFilter::new(true, true, &["wlan*".to_string()]).unwrap();
+        let sysfs = Reader(Some(true));
+        assert!(filter.exclusion("lo", &sysfs).unwrap().contains("loopback"));
+        assert!(filter
+            .exclusion("lo0", &sysfs)
+            .unwrap()
+            .contains("loopback"));
+        assert!(filter.exclusion("br0", &sysfs).unwrap().contains("virtual"));
+        assert_eq!(filter.exclusion("eth0", &sysfs), None);
+
+        // A remote host's interfaces are judged by name
+        let remote = Reader(None);
+        assert!(filter
+            .exclusion("veth1a2b", &remote)
+            .unwrap()
+            .contains("name"));
+        assert_eq!(filter.exclusion("br0", &remote), None);
+        assert!(filter
+            .exclusion("wlan0", &remote)
+            .unwrap()
+            .contains("`wlan*`"));
+        assert_eq!(filter.exclusion("lowpan0", &remote), None);
+
+        let keep_all = InterfaceFilter::new(false, false, &[]).unwrap();
+        assert_eq!(keep_all.exclusion("lo", &sysfs), None);
+        assert_eq!(keep_all.exclusion("veth1a2b", &remote), None);
+        assert!(InterfaceFilter::new(true, true, &["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_hw_error_counters_skip_traffic_and_flag_growth() {
+        let mut device = Device::new("eth0".to_string());
+        device.hw_stats_previous = HashMap::from([
+            ("rx_crc_errors".to_string(), 4),
+            ("tx_carrier_errors".to_string(), 9),
+        ]);
+        device.hw_stats = HashMap::from([
+            ("rx_packets".to_string(), 1_000_000),
+            ("rx_crc_errors".to_string(), 7),
+            ("tx_carrier_errors".to_string(), 9),
+            ("rx_fifo_errors".to_string(), 0),
+            ("rx_missed_errors".to_string(), 12),
+        ]);
+
+        assert_eq!(
+            device.hw_error_counters(10),
+            [
+                ("rx_missed_errors", 12, false),
+                ("tx_carrier_errors", 9, false),
+                ("rx_crc_errors", 7, true),
+            ]
+        );
+        assert_eq!(device.hw_error_counters(1).len(), 1);
+    }
+
+    #[cfg(feature = "push-reader")]
+    #[test]
+    fn test_subscribed_reader_prefers_pushed_samples() {
+        use std::sync::mpsc::{channel, Sender};
+        use std::sync::Mutex;
+
+        // Pushes eth0 and polls everything else at 1 byte in
+        struct PushingReader(Mutex<Option<std::sync::mpsc::Receiver<NetworkStats>>>);
+        impl NetworkReader for PushingReader {
+            fn list_devices(&self) -> Result<Vec<String>> {
+                Ok(vec!["eth0".to_string(), "eth1".to_string()])
+            }
+            fn read_stats(&self, _device: &str) -> Result<NetworkStats> {
+                Ok(NetworkStats {
+                    bytes_in: 1,
+                    ..NetworkStats::new()
+                })
+            }
+            fn is_available(&self) -> bool {
+                true
+            }
+            fn subscribe(&self, device: &str) -> Option<std::sync::mpsc::Receiver<NetworkStats>> {
+                (device == "eth0").then(|| self.0.lock().unwrap().take())?
+            }
+        }
+
+        let (sender, receiver): (Sender<NetworkStats>, _) = channel();
+        let inner = PushingReader(Mutex::new(Some(receiver)));
+        let devices = inner.list_devices().unwrap();
+        let reader = SubscribedReader::new(Box::new(inner), &devices);
+
+        // Nothing pushed yet, so eth0 is polled too
+        assert_eq!(reader.read_stats("eth0").unwrap().bytes_in, 1);
+        for bytes_in in [500, 900] {
+            sender
+                .send(NetworkStats {
+                    bytes_in,
+                    ..NetworkStats::new()
+                })
+                .unwrap();
+        }
+        assert_eq!(reader.read_stats("eth0").unwrap().bytes_in, 900);
+        assert_eq!(reader.read_stats("eth0").unwrap().bytes_in, 900);
+        assert_eq!(reader.read_stats("eth1").unwrap().bytes_in, 1);
+
+        drop(sender);
+        assert_eq!(reader.read_stats("eth0").unwrap().bytes_in, 1);
+    }
+
+    #[test]
+    fn test_interface_pattern_glob_and_regex() {
+        let glob = InterfacePattern::parse("eth*").unwrap();
+        assert!(glob.is_match("eth0"));
+        assert!(glob.is_match("eth"));
+        assert!(!glob.is_match("veth0")); // Anchored to the whole name
+
+        let class = InterfacePattern::parse("en[0-3]").unwrap();
+        assert!(class.is_match("en2"));
+        assert!(!class.is_match("en5"));
+        assert!(InterfacePattern::parse("wlan?").unwrap().is_match("wlan1"));
+        assert!(!InterfacePattern::parse("en[!0]").unwrap().is_match("en0"));
+
+        let regex = InterfacePattern::parse("en.*").unwrap();
+        assert!(regex.is_match("en0"));
+        assert!(regex.is_match("enp3s0"));
+        assert!(!regex.is_match("lo"));
+        assert!(InterfacePattern::parse("(eth|wlan)\\d+")
+            .unwrap()
+            .is_match("wlan0"));
+
+        // Taken as a regex, a glob-looking pattern is not a prefix match
+        let exact = InterfacePattern::regex("veth").unwrap();
+        assert!(exact.is_match("veth"));
+        assert!(!exact.is_match("veth0"));
+        let err = InterfacePattern::regex("veth[0-9").unwrap_err().to_string();
+        assert!(err.ends_with("unclosed character class"), "{err}");
+
+        assert!(InterfacePattern::parse("").is_err());
+        assert!(InterfacePattern::parse("(eth").is_err());
+        assert!(InterfacePattern::parse("en[0-3").is_err());
+    }
+}
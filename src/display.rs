@@ -5,6 +5,7 @@ use crate::{
     input::InputEvent,
     logger::TrafficLogger,
     stats::StatsCalculator,
+    units::{format_rate, format_volume},
 };
 use anyhow::Result;
 use crossterm::event::{self, Event};
@@ -32,6 +33,7 @@ pub struct DisplayState {
     pub max_incoming: u64, // 0 = auto-scale
     pub max_outgoing: u64, // 0 = auto-scale
     pub zoom_level: f64,   // Graph zoom multiplier
+    pub log_scale: bool,   // Plot graphs on a log10 Y axis
     pub show_options: bool,
     pub settings_message: Option<String>,
 }
@@ -51,6 +53,7 @@ impl DisplayState {
             max_incoming: config.max_incoming,
             max_outgoing: config.max_outgoing,
             zoom_level: 1.0,
+            log_scale: config.log_scale,
             show_options: false,
             settings_message: None,
         }
@@ -69,17 +72,17 @@ pub fn run_ui(
     let mut state = DisplayState::new(interfaces, &config);
     let mut stats_calculators: HashMap<String, StatsCalculator> = HashMap::new();
     let mut logger = if log_file.is_some() {
-        Some(TrafficLogger::new(log_file)?)
+        Some(TrafficLogger::new(log_file, config.log_compress)?)
     } else {
         None
     };
 
     // Initialize stats calculators for each device
     for device in &state.devices {
-        stats_calculators.insert(
-            device.name.clone(),
-            StatsCalculator::new(Duration::from_secs(config.average_window as u64)),
-        );
+        let average_window = config.for_interface(&device.name).average_window;
+        let mut calculator = StatsCalculator::new(Duration::from_secs(average_window as u64));
+        calculator.set_ema_alpha(config.ema_alpha);
+        stats_calculators.insert(device.name.clone(), calculator);
     }
 
     let refresh_interval = Duration::from_millis(config.refresh_interval);
@@ -200,6 +203,10 @@ fn handle_input(
                 state.zoom_level = (state.zoom_level / 1.5).max(0.1);
                 return Ok(false);
             }
+            InputEvent::ToggleLogScale => {
+                state.log_scale = !state.log_scale;
+                return Ok(false);
+            }
             InputEvent::SaveSettings => {
                 // Update config with current state values
                 config.traffic_format = state.traffic_unit.to_string().to_string();
@@ -207,6 +214,7 @@ fn handle_input(
                 config.multiple_devices = state.show_multiple;
                 config.max_incoming = state.max_incoming;
                 config.max_outgoing = state.max_outgoing;
+                config.log_scale = state.log_scale;
 
                 // Save to file
                 match config.save() {
@@ -218,11 +226,12 @@ fn handle_input(
                 }
                 return Ok(false);
             }
-            InputEvent::IncreaseRefresh => {
-                config.refresh_interval = (config.refresh_interval.saturating_sub(50)).max(50); // Min 50ms
+            InputEvent::IncreaseRefresh | InputEvent::DecreaseInterval => {
+                config.refresh_interval = (config.refresh_interval.saturating_sub(50))
+                    .max(crate::validation::MIN_REFRESH_INTERVAL);
                 return Ok(false);
             }
-            InputEvent::DecreaseRefresh => {
+            InputEvent::DecreaseRefresh | InputEvent::IncreaseInterval => {
                 config.refresh_interval = (config.refresh_interval + 50).min(2000); // Max 2000ms
                 return Ok(false);
             }
@@ -245,6 +254,7 @@ fn handle_input(
                         state.show_multiple = config.multiple_devices;
                         state.max_incoming = config.max_incoming;
                         state.max_outgoing = config.max_outgoing;
+                        state.log_scale = config.log_scale;
                         state.settings_message =
                             Some("✅ Settings reloaded from ~/.netwatch".to_string());
                     }
@@ -317,6 +327,10 @@ fn handle_input(
             state.zoom_level = (state.zoom_level / 1.5).max(0.1);
         }
 
+        InputEvent::ToggleLogScale => {
+            state.log_scale = !state.log_scale;
+        }
+
         InputEvent::ShowOptions => {
             state.show_options = !state.show_options;
         }
@@ -328,6 +342,7 @@ fn handle_input(
             config.multiple_devices = state.show_multiple;
             config.max_incoming = state.max_incoming;
             config.max_outgoing = state.max_outgoing;
+            config.log_scale = state.log_scale;
 
             // Save to file
             if let Err(e) = config.save() {
@@ -345,11 +360,14 @@ fn handle_input(
                 state.show_multiple = config.multiple_devices;
                 state.max_incoming = config.max_incoming;
                 state.max_outgoing = config.max_outgoing;
+                state.log_scale = config.log_scale;
             }
         }
 
         InputEvent::IncreaseRefresh
         | InputEvent::DecreaseRefresh
+        | InputEvent::DecreaseInterval
+        | InputEvent::IncreaseInterval
         | InputEvent::IncreaseAverage
         | InputEvent::DecreaseAverage => {
             // These are only handled in options window
@@ -358,7 +376,12 @@ fn handle_input(
         InputEvent::NextPanel
         | InputEvent::PrevPanel
         | InputEvent::NextItem
-        | InputEvent::PrevItem => {
+        | InputEvent::PrevItem
+        | InputEvent::ToggleCompact
+        | InputEvent::KillProcess
+        | InputEvent::ToggleGraphsView
+        | InputEvent::ToggleListening
+        | InputEvent::Search => {
             // These are dashboard-specific, already handled above
         }
 
@@ -492,15 +515,15 @@ fn draw_devices_table(
             let (total_in, total_out) = calculator.total_bytes();
 
             table_content.push_str(&format!(
-                "│{} {:13} │ {:>11}/s │ {:>11}/s │ {:>11}/s │ {:>11}/s │ {:>7}/{:<7} │\n",
+                "│{} {:13} │ {:>13} │ {:>13} │ {:>13} │ {:>13} │ {:>7}/{:<7} │\n",
                 prefix,
                 truncate_device_name(&device.name, 13),
-                format_bytes_short(current_in),
-                format_bytes_short(current_out),
-                format_bytes_short(avg_in),
-                format_bytes_short(avg_out),
-                format_bytes_short(total_in),
-                format_bytes_short(total_out)
+                format_rate(current_in, &state.traffic_unit),
+                format_rate(current_out, &state.traffic_unit),
+                format_rate(avg_in, &state.traffic_unit),
+                format_rate(avg_out, &state.traffic_unit),
+                format_volume(total_in, &state.data_unit),
+                format_volume(total_out, &state.data_unit)
             ));
         } else {
             table_content.push_str(&format!(
@@ -609,7 +632,7 @@ fn draw_placeholder_graphs(
             .split(area);
 
         // Draw statistics summary
-        draw_stats_summary(f, chunks[0], device, calculator);
+        draw_stats_summary(f, chunks[0], device, calculator, &state.traffic_unit);
 
         // Draw the actual graphs
         draw_traffic_graphs_internal(f, chunks[1], calculator, state);
@@ -630,6 +653,7 @@ fn draw_stats_summary(
     area: ratatui::layout::Rect,
     device: &Device,
     calculator: &StatsCalculator,
+    unit: &TrafficUnit,
 ) {
     let (current_in, current_out) = calculator.current_speed();
     let (avg_in, avg_out) = calculator.average_speed();
@@ -637,14 +661,14 @@ fn draw_stats_summary(
     let (max_in, max_out) = calculator.max_speed();
 
     let stats_text = format!(
-        "📶 Device: {}     Current Traffic: 📥 {}/s down  📤 {}/s up\nAverages: 📊 {}/s down  📊 {}/s up     Peak: 📈 {}/s down  📈 {}/s up",
+        "📶 Device: {}     Current Traffic: 📥 {} down  📤 {} up\nAverages: 📊 {} down  📊 {} up     Peak: 📈 {} down  📈 {} up",
         device.name,
-        format_bytes(current_in),
-        format_bytes(current_out),
-        format_bytes(avg_in),
-        format_bytes(avg_out),
-        format_bytes(max_in),
-        format_bytes(max_out)
+        format_rate(current_in, unit),
+        format_rate(current_out, unit),
+        format_rate(avg_in, unit),
+        format_rate(avg_out, unit),
+        format_rate(max_in, unit),
+        format_rate(max_out, unit)
     );
 
     let stats_widget = Paragraph::new(stats_text)
@@ -662,6 +686,7 @@ pub fn draw_traffic_graphs(
     dashboard_state: &crate::dashboard::DashboardState,
 ) {
     // Create a compatibility DisplayState for the existing function
+    let (max_incoming, max_outgoing) = dashboard_state.bar_max(device_name);
     let state = DisplayState {
         current_device_index: dashboard_state.current_device_index,
         devices: dashboard_state.devices.clone(),
@@ -670,14 +695,23 @@ pub fn draw_traffic_graphs(
         paused: dashboard_state.paused,
         traffic_unit: dashboard_state.traffic_unit.clone(),
         data_unit: dashboard_state.data_unit.clone(),
-        max_incoming: dashboard_state.max_incoming,
-        max_outgoing: dashboard_state.max_outgoing,
+        max_incoming,
+        max_outgoing,
         zoom_level: dashboard_state.zoom_level,
+        log_scale: dashboard_state.log_scale,
         show_options: false,
         settings_message: None,
     };
 
-    draw_traffic_graphs_with_device_name(f, area, device_name, calculator, &state);
+    draw_traffic_graphs_with_device_name(
+        f,
+        area,
+        device_name,
+        calculator,
+        dashboard_state.graph_window(calculator.graph_len()),
+        &state,
+        &dashboard_state.theme,
+    );
 }
 
 fn draw_traffic_graphs_with_device_name(
@@ -685,7 +719,9 @@ fn draw_traffic_graphs_with_device_name(
     area: ratatui::layout::Rect,
     device_name: &str,
     calculator: &StatsCalculator,
+    window: (usize, usize),
     state: &DisplayState,
+    theme: &crate::theme::Theme,
 ) {
     // Split into incoming and outgoing graph areas
     let chunks = Layout::default()
@@ -693,17 +729,17 @@ fn draw_traffic_graphs_with_device_name(
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    // Get graph data
-    let graph_data_in = calculator.graph_data_in();
-    let graph_data_out = calculator.graph_data_out();
+    // Get the visible slice of the graph data
+    let graph_data_in = calculator.graph_data_in(window);
+    let graph_data_out = calculator.graph_data_out(window);
 
     // Draw incoming traffic graph with device name
     draw_single_graph_with_device(
         f,
         chunks[0],
         &format!("{device_name} - Incoming"),
-        graph_data_in,
-        Color::Green,
+        &graph_data_in,
+        theme.speed_in_color,
         calculator.max_speed().0, // max incoming
         state,
     );
@@ -713,13 +749,120 @@ fn draw_traffic_graphs_with_device_name(
         f,
         chunks[1],
         &format!("{device_name} - Outgoing"),
-        graph_data_out,
-        Color::Red,
+        &graph_data_out,
+        theme.speed_out_color,
         calculator.max_speed().1, // max outgoing
         state,
     );
 }
 
+/// One interface's incoming and outgoing traffic in a single small chart, for the Graphs
+/// panel's side-by-side view. The Y axis scales to this interface alone.
+pub fn draw_mini_traffic_graph(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    device_name: &str,
+    calculator: Option<&StatsCalculator>,
+    dashboard_state: &crate::dashboard::DashboardState,
+    focused: bool,
+) {
+    let theme = &dashboard_state.theme;
+    let border_style = if focused {
+        Style::default()
+            .fg(theme.highlight_color)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.muted_color)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(device_name.to_string());
+
+    let window = calculator.map_or((0, 0), |calc| {
+        dashboard_state.graph_window(calc.graph_len())
+    });
+    let (data_in, data_out) = match calculator {
+        Some(calc) if window.1 - window.0 >= 2 => {
+            (calc.graph_data_in(window), calc.graph_data_out(window))
+        }
+        _ => {
+            let waiting = Paragraph::new("Collecting data...")
+                .block(block)
+                .style(Style::default().fg(theme.warning_color));
+            f.render_widget(waiting, area);
+            return;
+        }
+    };
+
+    let zoomed = dashboard_state.zoom_level > 1.0;
+    let (min_x, max_x, _) = time_axis(&data_in, zoomed);
+    let data_max = data_in
+        .iter()
+        .chain(&data_out)
+        .map(|(_, y)| *y)
+        .filter(|y| y.is_finite() && *y >= 0.0)
+        .fold(0.0, f64::max);
+    let max_y = graph_top((data_max as u64).max(1024), zoomed);
+    let unit = &dashboard_state.traffic_unit;
+
+    // Same log mapping as the full graph; linear gets just the ends, columns are narrow
+    let (data_in, data_out, y_bounds, y_labels) = if dashboard_state.log_scale {
+        let factor = log_scale_factor(unit);
+        let (min_exp, max_exp) = log_scale_exponents(max_y * factor);
+        let to_log = |points: Vec<(f64, f64)>| -> Vec<(f64, f64)> {
+            points
+                .into_iter()
+                .map(|(x, y)| (x, log_scale_value(y * factor, min_exp)))
+                .collect()
+        };
+        let labels = create_log_y_labels(min_exp, max_exp, unit);
+        let ends = vec![labels[0].clone(), labels[labels.len() - 1].clone()];
+        (
+            to_log(data_in),
+            to_log(data_out),
+            [min_exp as f64, max_exp as f64],
+            ends,
+        )
+    } else {
+        let labels = vec![
+            format_rate(0, unit).into(),
+            format_rate(max_y as u64, unit).into(),
+        ];
+        (data_in, data_out, [0.0, max_y], labels)
+    };
+
+    let datasets = vec![
+        Dataset::default()
+            .name("In")
+            .marker(ratatui::symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.speed_in_color))
+            .data(&data_in),
+        Dataset::default()
+            .name("Out")
+            .marker(ratatui::symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.speed_out_color))
+            .data(&data_out),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted_color))
+                .bounds([min_x, max_x]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted_color))
+                .bounds(y_bounds)
+                .labels(y_labels),
+        );
+    f.render_widget(chart, area);
+}
+
 fn draw_traffic_graphs_internal(
     f: &mut Frame,
     area: ratatui::layout::Rect,
@@ -733,15 +876,16 @@ fn draw_traffic_graphs_internal(
         .split(area);
 
     // Get graph data
-    let graph_data_in = calculator.graph_data_in();
-    let graph_data_out = calculator.graph_data_out();
+    let window = (0, calculator.graph_len());
+    let graph_data_in = calculator.graph_data_in(window);
+    let graph_data_out = calculator.graph_data_out(window);
 
     // Draw incoming traffic graph
     draw_single_graph(
         f,
         chunks[0],
         "Incoming Traffic",
-        graph_data_in,
+        &graph_data_in,
         Color::Green,
         calculator.max_speed().0, // max incoming
         state,
@@ -752,7 +896,7 @@ fn draw_traffic_graphs_internal(
         f,
         chunks[1],
         "Outgoing Traffic",
-        graph_data_out,
+        &graph_data_out,
         Color::Red,
         calculator.max_speed().1, // max outgoing
         state,
@@ -763,7 +907,7 @@ fn draw_single_graph_with_device(
     f: &mut Frame,
     area: ratatui::layout::Rect,
     title: &str,
-    data: &std::collections::VecDeque<(f64, f64)>,
+    data: &[(f64, f64)],
     color: Color,
     max_value: u64,
     state: &DisplayState,
@@ -784,9 +928,9 @@ fn draw_single_graph_with_device(
         return;
     }
 
-    // Calculate bounds with smart scaling first
-    let min_x = 0.0; // Left side starts at "now" (time 0)
-    let max_x = 60.0; // Right side goes to "60 seconds ago"
+    // zoom_level zooms the time axis here: data is already the visible window
+    let zoomed = state.zoom_level > 1.0;
+    let (min_x, max_x, x_labels) = time_axis(data, zoomed);
 
     // Calculate Y-axis bounds based on network capacity tiers
     let data_max = data
@@ -802,13 +946,7 @@ fn draw_single_graph_with_device(
         1024 // 1KB minimum
     };
 
-    // Use network capacity scale for graph bounds, adjusted by zoom level
-    let base_max_y = get_network_capacity_scale(actual_max) as f64;
-    let max_y = if state.zoom_level > 0.0 && state.zoom_level.is_finite() {
-        base_max_y / state.zoom_level // Higher zoom = smaller Y range = "zoomed in"
-    } else {
-        base_max_y // Fallback if zoom_level is invalid
-    };
+    let max_y = graph_top(actual_max, zoomed);
 
     // Convert data to chart format and sort by time (newest to oldest for proper line drawing)
     let chart_data: Vec<(f64, f64)> = data
@@ -829,6 +967,27 @@ fn draw_single_graph_with_device(
 
     chart_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)); // Sort by time, safe fallback
 
+    // Map onto the Y axis - log mode only transforms the plotted copy, never the stats
+    let (chart_data, y_bounds, y_labels) = if state.log_scale {
+        let factor = log_scale_factor(&state.traffic_unit);
+        let (min_exp, max_exp) = log_scale_exponents(max_y * factor);
+        let log_data: Vec<(f64, f64)> = chart_data
+            .into_iter()
+            .map(|(x, y)| (x, log_scale_value(y * factor, min_exp)))
+            .collect();
+        (
+            log_data,
+            [min_exp as f64, max_exp as f64],
+            create_log_y_labels(min_exp, max_exp, &state.traffic_unit),
+        )
+    } else {
+        (
+            chart_data,
+            [0.0, max_y],
+            create_smart_y_labels(max_y, &state.traffic_unit),
+        )
+    };
+
     // Create dataset
     let dataset = Dataset::default()
         .name(title)
@@ -840,28 +999,34 @@ fn draw_single_graph_with_device(
     // Try to create chart, fallback to ASCII if it fails
     let chart = Chart::new(vec![dataset])
         .block(Block::default().borders(Borders::ALL).title(format!(
-            "{} (Max: {}) - Use ↑/↓ to switch devices",
+            "{} (Max: {}){}{} - Use ↑/↓ to switch devices",
             title,
-            format_bytes(max_value)
+            format_rate(max_value, &state.traffic_unit),
+            if zoomed {
+                format!(" [{}x]", state.zoom_level)
+            } else {
+                String::new()
+            },
+            if state.log_scale { " [log]" } else { "" }
         )))
         .x_axis(
             Axis::default()
                 .title("Time")
                 .style(Style::default().fg(Color::Gray))
                 .bounds([min_x, max_x])
-                .labels(vec!["Now", "30s ago", "1 min ago"]),
+                .labels(x_labels),
         )
         .y_axis(
             Axis::default()
                 .title("Speed")
                 .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, max_y])
-                .labels(create_smart_y_labels(max_y)),
+                .bounds(y_bounds)
+                .labels(y_labels),
         );
 
     // If chart rendering fails, use ASCII fallback
     if area.width < 20 || area.height < 8 {
-        draw_ascii_graph_with_device(f, area, title, data, color, max_value);
+        draw_ascii_graph_with_device(f, area, title, data, color, max_value, &state.traffic_unit);
     } else {
         f.render_widget(chart, area);
     }
@@ -871,7 +1036,7 @@ fn draw_single_graph(
     f: &mut Frame,
     area: ratatui::layout::Rect,
     title: &str,
-    data: &std::collections::VecDeque<(f64, f64)>,
+    data: &[(f64, f64)],
     color: Color,
     max_value: u64,
     state: &DisplayState,
@@ -937,6 +1102,27 @@ fn draw_single_graph(
 
     chart_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)); // Sort by time, safe fallback
 
+    // Map onto the Y axis - log mode only transforms the plotted copy, never the stats
+    let (chart_data, y_bounds, y_labels) = if state.log_scale {
+        let factor = log_scale_factor(&state.traffic_unit);
+        let (min_exp, max_exp) = log_scale_exponents(max_y * factor);
+        let log_data: Vec<(f64, f64)> = chart_data
+            .into_iter()
+            .map(|(x, y)| (x, log_scale_value(y * factor, min_exp)))
+            .collect();
+        (
+            log_data,
+            [min_exp as f64, max_exp as f64],
+            create_log_y_labels(min_exp, max_exp, &state.traffic_unit),
+        )
+    } else {
+        (
+            chart_data,
+            [0.0, max_y],
+            create_smart_y_labels(max_y, &state.traffic_unit),
+        )
+    };
+
     // Create dataset
     let dataset = Dataset::default()
         .name(title)
@@ -948,9 +1134,10 @@ fn draw_single_graph(
     // Try to create chart, fallback to ASCII if it fails
     let chart = Chart::new(vec![dataset])
         .block(Block::default().borders(Borders::ALL).title(format!(
-            "{} (Max: {}) - Use ↑/↓ to switch devices",
+            "{} (Max: {}){} - Use ↑/↓ to switch devices",
             title,
-            format_bytes(max_value)
+            format_rate(max_value, &state.traffic_unit),
+            if state.log_scale { " [log]" } else { "" }
         )))
         .x_axis(
             Axis::default()
@@ -963,13 +1150,13 @@ fn draw_single_graph(
             Axis::default()
                 .title("Speed")
                 .style(Style::default().fg(Color::Gray))
-                .bounds([0.0, max_y])
-                .labels(create_smart_y_labels(max_y)),
+                .bounds(y_bounds)
+                .labels(y_labels),
         );
 
     // If chart rendering fails, use ASCII fallback
     if area.width < 20 || area.height < 8 {
-        draw_ascii_graph(f, area, title, data, color, max_value);
+        draw_ascii_graph(f, area, title, data, color, max_value, &state.traffic_unit);
     } else {
         f.render_widget(chart, area);
     }
@@ -979,9 +1166,10 @@ fn draw_ascii_graph_with_device(
     f: &mut Frame,
     area: ratatui::layout::Rect,
     title: &str,
-    data: &std::collections::VecDeque<(f64, f64)>,
+    data: &[(f64, f64)],
     color: Color,
     max_value: u64,
+    unit: &TrafficUnit,
 ) {
     if data.is_empty() {
         let no_data = Paragraph::new("No data available")
@@ -1058,11 +1246,11 @@ fn draw_ascii_graph_with_device(
     }
 
     // Add current value and max info
-    let current_val = data.back().map(|(_, v)| *v).unwrap_or(0.0);
+    let current_val = data.last().map(|(_, v)| *v).unwrap_or(0.0);
     let info_line = format!(
-        "Current: {}/s | Max: {}/s",
-        format_bytes(current_val as u64),
-        format_bytes(scale_max as u64)
+        "Current: {} | Max: {}",
+        format_rate(current_val as u64, unit),
+        format_rate(scale_max as u64, unit)
     );
 
     // Combine all lines
@@ -1095,9 +1283,10 @@ fn draw_ascii_graph(
     f: &mut Frame,
     area: ratatui::layout::Rect,
     title: &str,
-    data: &std::collections::VecDeque<(f64, f64)>,
+    data: &[(f64, f64)],
     color: Color,
     max_value: u64,
+    unit: &TrafficUnit,
 ) {
     if data.is_empty() {
         let no_data = Paragraph::new("No data available")
@@ -1174,11 +1363,11 @@ fn draw_ascii_graph(
     }
 
     // Add current value and max info
-    let current_val = data.back().map(|(_, v)| *v).unwrap_or(0.0);
+    let current_val = data.last().map(|(_, v)| *v).unwrap_or(0.0);
     let info_line = format!(
-        "Current: {}/s | Max: {}/s",
-        format_bytes(current_val as u64),
-        format_bytes(scale_max as u64)
+        "Current: {} | Max: {}",
+        format_rate(current_val as u64, unit),
+        format_rate(scale_max as u64, unit)
     );
 
     // Combine all lines
@@ -1258,10 +1447,10 @@ fn draw_detailed_stats_table(
         ┌─────────────────────────────┬──────────────────┬──────────────────┐\n\
         │         Statistic           │    Incoming      │    Outgoing      │\n\
         ├─────────────────────────────┼──────────────────┼──────────────────┤\n\
-        │ Current Speed               │ {:>15}/s │ {:>15}/s │\n\
-        │ Average Speed               │ {:>15}/s │ {:>15}/s │\n\
-        │ Minimum Speed               │ {:>15}/s │ {:>15}/s │\n\
-        │ Maximum Speed               │ {:>15}/s │ {:>15}/s │\n\
+        │ Current Speed               │ {:>16} │ {:>16} │\n\
+        │ Average Speed               │ {:>16} │ {:>16} │\n\
+        │ Minimum Speed               │ {:>16} │ {:>16} │\n\
+        │ Maximum Speed               │ {:>16} │ {:>16} │\n\
         ├─────────────────────────────┼──────────────────┼──────────────────┤\n\
         │ Total Bytes                 │ {:>16} │ {:>16} │\n\
         │ Total Packets               │ {:>16} │ {:>16} │\n\
@@ -1269,16 +1458,16 @@ fn draw_detailed_stats_table(
         \n\
         Network Interface Statistics - Press 'g' to toggle back to graphs",
         device.name,
-        format_bytes_with_unit(current_in, traffic_unit),
-        format_bytes_with_unit(current_out, traffic_unit),
-        format_bytes_with_unit(avg_in, traffic_unit),
-        format_bytes_with_unit(avg_out, traffic_unit),
-        format_bytes_with_unit(min_in, traffic_unit),
-        format_bytes_with_unit(min_out, traffic_unit),
-        format_bytes_with_unit(max_in, traffic_unit),
-        format_bytes_with_unit(max_out, traffic_unit),
-        format_bytes_with_unit(total_bytes_in, data_unit),
-        format_bytes_with_unit(total_bytes_out, data_unit),
+        format_rate(current_in, traffic_unit),
+        format_rate(current_out, traffic_unit),
+        format_rate(avg_in, traffic_unit),
+        format_rate(avg_out, traffic_unit),
+        format_rate(min_in, traffic_unit),
+        format_rate(min_out, traffic_unit),
+        format_rate(max_in, traffic_unit),
+        format_rate(max_out, traffic_unit),
+        format_volume(total_bytes_in, data_unit),
+        format_volume(total_bytes_out, data_unit),
         format_number(total_packets_in),
         format_number(total_packets_out),
     );
@@ -1334,50 +1523,6 @@ fn draw_status_line(f: &mut Frame, area: ratatui::layout::Rect, _state: &Display
     f.render_widget(help, area);
 }
 
-// Helper function for formatting bytes
-fn format_bytes(bytes: u64) -> String {
-    format_bytes_with_unit(bytes, &TrafficUnit::HumanByte)
-}
-
-// Helper function for formatting bytes with specific unit
-fn format_bytes_with_unit(bytes: u64, unit: &TrafficUnit) -> String {
-    match unit {
-        TrafficUnit::HumanBit => {
-            let bits = bytes * 8;
-            format_human_readable(bits, &["bit", "Kbit", "Mbit", "Gbit", "Tbit"], 1000.0)
-        }
-        TrafficUnit::HumanByte => {
-            format_human_readable(bytes, &["B", "KB", "MB", "GB", "TB"], 1024.0)
-        }
-        TrafficUnit::Bit => format!("{} bit", bytes * 8),
-        TrafficUnit::Byte => format!("{bytes} B"),
-        TrafficUnit::KiloBit => format!("{:.2} kbit", (bytes * 8) as f64 / 1000.0),
-        TrafficUnit::KiloByte => format!("{:.2} KB", bytes as f64 / 1024.0),
-        TrafficUnit::MegaBit => format!("{:.2} Mbit", (bytes * 8) as f64 / 1_000_000.0),
-        TrafficUnit::MegaByte => format!("{:.2} MB", bytes as f64 / 1_048_576.0),
-        TrafficUnit::GigaBit => format!("{:.2} Gbit", (bytes * 8) as f64 / 1_000_000_000.0),
-        TrafficUnit::GigaByte => format!("{:.2} GB", bytes as f64 / 1_073_741_824.0),
-    }
-}
-
-fn format_human_readable(value: u64, units: &[&str], divisor: f64) -> String {
-    let mut size = value as f64;
-    let mut unit_index = 0;
-
-    while size >= divisor && unit_index < units.len() - 1 {
-        size /= divisor;
-        unit_index += 1;
-    }
-
-    if size >= 100.0 {
-        format!("{:.0} {}", size, units[unit_index])
-    } else if size >= 10.0 {
-        format!("{:.1} {}", size, units[unit_index])
-    } else {
-        format!("{:.2} {}", size, units[unit_index])
-    }
-}
-
 // Helper function for formatting large numbers with commas
 fn format_number(num: u64) -> String {
     let num_str = num.to_string();
@@ -1394,32 +1539,48 @@ fn format_number(num: u64) -> String {
     result
 }
 
-// Helper function for formatting bytes in a shorter format (for tables)
-fn format_bytes_short(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "K", "M", "G", "T"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
+// Helper function to truncate device names for table display
+fn truncate_device_name(name: &str, max_len: usize) -> String {
+    if name.len() <= max_len {
+        name.to_string()
+    } else {
+        format!("{}...", &name[..max_len.saturating_sub(3)])
+    }
+}
 
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
+// Time axis bounds and labels: the whole minute, or when zoomed just the visible window
+fn time_axis(data: &[(f64, f64)], zoomed: bool) -> (f64, f64, Vec<String>) {
+    if zoomed {
+        let newest = data.last().map_or(0.0, |(x, _)| *x);
+        let oldest = data.first().map_or(60.0, |(x, _)| *x).max(newest + 1.0);
+        let labels = [newest, (newest + oldest) / 2.0, oldest]
+            .iter()
+            .map(|secs| format_seconds_ago(*secs))
+            .collect();
+        (newest, oldest, labels)
+    } else {
+        // Left side starts at "now" (time 0), right side goes to "60 seconds ago"
+        let labels = vec!["Now".to_string(), "30s ago".into(), "1 min ago".into()];
+        (0.0, 60.0, labels)
     }
+}
 
-    if size >= 100.0 {
-        format!("{:.0}{}", size, UNITS[unit_index])
-    } else if size >= 10.0 {
-        format!("{:.1}{}", size, UNITS[unit_index])
+// Top of a linear Y axis. The full minute uses network capacity tiers; a zoomed window
+// fits its own peak so a quiet stretch isn't flattened against the bottom
+fn graph_top(actual_max: u64, zoomed: bool) -> f64 {
+    if zoomed {
+        (actual_max as f64 * 1.25).max(1024.0)
     } else {
-        format!("{:.2}{}", size, UNITS[unit_index])
+        get_network_capacity_scale(actual_max) as f64
     }
 }
 
-// Helper function to truncate device names for table display
-fn truncate_device_name(name: &str, max_len: usize) -> String {
-    if name.len() <= max_len {
-        name.to_string()
+// Time axis label for a point `secs` seconds in the past
+fn format_seconds_ago(secs: f64) -> String {
+    if secs < 0.5 {
+        "Now".to_string()
     } else {
-        format!("{}...", &name[..max_len.saturating_sub(3)])
+        format!("{secs:.0}s ago")
     }
 }
 
@@ -1451,22 +1612,75 @@ fn get_network_capacity_scale(actual_max: u64) -> u64 {
 }
 
 // Create network-capacity-aware Y-axis labels for bounds [0.0, max_y]
-fn create_smart_y_labels(max_y: f64) -> Vec<ratatui::text::Span<'static>> {
+fn create_smart_y_labels(max_y: f64, unit: &TrafficUnit) -> Vec<ratatui::text::Span<'static>> {
     let capacity_scale = max_y as u64; // max_y is already the capacity scale
 
     // Labels for Y-axis bounds [0.0, max_y]
     // First label = 0.0 (bottom), Last label = max_y (top)
     let labels = vec![
-        "0 B/s".into(),                                               // 0.0 (bottom)
-        format!("{}/s", format_bytes(capacity_scale / 4)).into(),     // 25% (lower)
-        format!("{}/s", format_bytes(capacity_scale / 2)).into(),     // 50% (middle)
-        format!("{}/s", format_bytes(capacity_scale * 3 / 4)).into(), // 75% (upper)
-        format!("{}/s", format_bytes(capacity_scale)).into(),         // max_y (top)
+        format_rate(0, unit).into(),                      // 0.0 (bottom)
+        format_rate(capacity_scale / 4, unit).into(),     // 25% (lower)
+        format_rate(capacity_scale / 2, unit).into(),     // 50% (middle)
+        format_rate(capacity_scale * 3 / 4, unit).into(), // 75% (upper)
+        format_rate(capacity_scale, unit).into(),         // max_y (top)
     ];
 
     labels
 }
 
+// Lowest decade shown on a log scale graph (10^3 = 1 KB/s or 1 Kbit/s); anything below sits on the floor
+const LOG_SCALE_MIN_EXP: i32 = 3;
+
+// Decade exponents bounding a log10 Y axis whose linear top would be max_y.
+// The top is rounded up to a whole decade so every tick label lands on 1, 10, 100...
+fn log_scale_exponents(max_y: f64) -> (i32, i32) {
+    let max_exp = if max_y.is_finite() && max_y > 0.0 {
+        max_y.log10().ceil() as i32
+    } else {
+        LOG_SCALE_MIN_EXP + 1
+    };
+
+    (LOG_SCALE_MIN_EXP, max_exp.max(LOG_SCALE_MIN_EXP + 1))
+}
+
+// Convert a bytes/s value to its log10 position, clamping zero and tiny values to the floor
+fn log_scale_value(value: f64, min_exp: i32) -> f64 {
+    let floor = 10f64.powi(min_exp);
+    if value.is_finite() && value > floor {
+        value.log10()
+    } else {
+        min_exp as f64
+    }
+}
+
+// Multiplier from bytes/s to the log axis' base unit, so bit units get decades of bits
+fn log_scale_factor(unit: &TrafficUnit) -> f64 {
+    if unit.is_bits() {
+        8.0
+    } else {
+        1.0
+    }
+}
+
+// One label per decade - ratatui spaces labels evenly, which matches decade spacing in log space
+fn create_log_y_labels(
+    min_exp: i32,
+    max_exp: i32,
+    unit: &TrafficUnit,
+) -> Vec<ratatui::text::Span<'static>> {
+    const SUFFIXES: [&str; 5] = ["", "K", "M", "G", "T"];
+    let base = if unit.is_bits() { "bit/s" } else { "B/s" };
+
+    (min_exp..=max_exp)
+        .map(|exp| {
+            let exp = exp.max(0) as u32;
+            let unit = (exp / 3).min(SUFFIXES.len() as u32 - 1);
+            let mantissa = 10u64.pow(exp - unit * 3);
+            format!("{mantissa}{}{base}", SUFFIXES[unit as usize]).into()
+        })
+        .collect()
+}
+
 fn draw_options_overlay(
     f: &mut Frame,
     area: ratatui::layout::Rect,
@@ -1507,7 +1721,8 @@ fn draw_options_overlay(
         • Space - Pause/resume monitoring\n\
         • '+/-' - Zoom graph scale\n\
         • '</>' - Slower/Faster refresh rate\n\
-        • '[/]' - Shorter/Longer average window\n\
+        • '[/]' - Shorter/Longer refresh interval\n\
+        • '{{/}}' - Shorter/Longer average window\n\
         • F5 - Save current settings to file\n\
         • F6 - Reload settings from file\n\
         \n\
@@ -1571,3 +1786,63 @@ fn centered_rect(
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_scale_clamps_zero_and_labels_decades() {
+        let (min_exp, max_exp) = log_scale_exponents(125_000_000.0);
+        assert_eq!((min_exp, max_exp), (3, 9));
+
+        // Zero and sub-floor values land on the bottom of the axis instead of -inf
+        assert_eq!(log_scale_value(0.0, min_exp), 3.0);
+        assert_eq!(log_scale_value(12.0, min_exp), 3.0);
+        assert!((log_scale_value(1_000_000.0, min_exp) - 6.0).abs() < f64::EPSILON);
+
+        let labels: Vec<String> = create_log_y_labels(min_exp, max_exp, &TrafficUnit::HumanByte)
+            .into_iter()
+            .map(|span| span.content.into_owned())
+            .collect();
+        assert_eq!(
+            labels,
+            ["1KB/s", "10KB/s", "100KB/s", "1MB/s", "10MB/s", "100MB/s", "1GB/s"]
+        );
+
+        // Zooming in far still keeps at least one decade of range
+        assert_eq!(log_scale_exponents(1.0), (3, 4));
+    }
+
+    #[test]
+    fn test_speed_formatting_follows_traffic_unit() {
+        // 1.5 MB/s is 12 Mbit/s
+        assert_eq!(
+            format_rate(1_500_000, &TrafficUnit::MegaBit),
+            "12.00 Mbit/s"
+        );
+        assert_eq!(
+            format_rate(1_500_000, &TrafficUnit::HumanBit),
+            "12.0 Mbit/s"
+        );
+        assert_eq!(format_rate(1_048_576, &TrafficUnit::MegaByte), "1.00 MiB/s");
+
+        let labels: Vec<String> = create_smart_y_labels(125_000_000.0, &TrafficUnit::GigaBit)
+            .into_iter()
+            .map(|span| span.content.into_owned())
+            .collect();
+        assert_eq!(labels[0], "0.00 Gbit/s");
+        assert_eq!(labels[4], "1.00 Gbit/s");
+
+        // Log axes for bit units count decades of bits
+        let factor = log_scale_factor(&TrafficUnit::HumanBit);
+        let (min_exp, max_exp) = log_scale_exponents(125_000_000.0 * factor);
+        assert_eq!((min_exp, max_exp), (3, 9));
+        let labels: Vec<String> = create_log_y_labels(min_exp, max_exp, &TrafficUnit::HumanBit)
+            .into_iter()
+            .map(|span| span.content.into_owned())
+            .collect();
+        assert_eq!(labels.first().map(String::as_str), Some("1Kbit/s"));
+        assert_eq!(labels.last().map(String::as_str), Some("1Gbit/s"));
+    }
+}
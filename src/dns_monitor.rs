@@ -0,0 +1,650 @@
+//! DNS activity for the Diagnostics panel: which names are being looked up, how many
+//! fail and how slowly they resolve
+//!
+//! With CAP_NET_RAW on Linux, a background thread reads every UDP packet to or from
+//! port 53 (and 5353, mDNS) off an `AF_PACKET` socket and pairs queries with their
+//! responses by client address and query ID. Queries left unanswered for
+//! [`QUERY_TIMEOUT`] count as timeouts, so an outage shows up as a wall of them.
+//! Without the capability, systemd-resolved's counters from `resolvectl statistics`
+//! are shown instead: transactions and cache hits, but no names or response codes.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How far back the summary looks
+pub const DNS_WINDOW: Duration = Duration::from_secs(300);
+/// A query with no response after this long counts as a timeout
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Entries in the top domains and slowest resolutions lists
+pub const TOP_ENTRIES: usize = 5;
+
+/// Events kept before the oldest are dropped, whatever the window
+const MAX_EVENTS: usize = 5000;
+/// Queries awaiting a response before new ones are ignored
+const MAX_PENDING: usize = 2048;
+
+const DNS_PORT: u16 = 53;
+const MDNS_PORT: u16 = 5353;
+const IPPROTO_UDP: u8 = 17;
+
+pub const RCODE_NOERROR: u8 = 0;
+pub const RCODE_SERVFAIL: u8 = 2;
+pub const RCODE_NXDOMAIN: u8 = 3;
+
+/// Where the Diagnostics panel's DNS figures come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum DnsSource {
+    /// Not started yet: the first [`DnsMonitor::update`] picks a source
+    Pending,
+    /// Live packet capture, see [`DnsSummary`]
+    Capture,
+    /// systemd-resolved counters, for hosts where capture isn't permitted
+    Resolved(ResolvedStats),
+    /// Neither is available; the reason is shown in the panel
+    Unavailable(String),
+}
+
+/// How one captured query ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnsOutcome {
+    Answered {
+        rcode: u8,
+        latency: Duration,
+    },
+    TimedOut,
+    /// mDNS answers go to the multicast group, so they aren't paired with queries
+    Multicast,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsEvent {
+    pub name: String,
+    pub outcome: DnsOutcome,
+    pub at: Instant,
+}
+
+/// Captured DNS activity over the last [`DNS_WINDOW`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DnsSummary {
+    pub queries: usize,
+    pub answered: usize,
+    pub nxdomain: usize,
+    pub servfail: usize,
+    pub timeouts: usize,
+    pub avg_latency: Option<Duration>,
+    /// Most queried names with their query counts
+    pub top_domains: Vec<(String, usize)>,
+    /// Slowest answered queries, slowest first
+    pub slowest: Vec<(String, Duration)>,
+}
+
+impl DnsSummary {
+    /// Share of answered queries that came back NXDOMAIN
+    pub fn nxdomain_rate(&self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            self.nxdomain as f64 / self.answered as f64
+        }
+    }
+
+    /// Most unicast queries are going unanswered: resolvers are down or unreachable
+    pub fn looks_like_outage(&self) -> bool {
+        self.timeouts >= 5 && self.timeouts > self.answered
+    }
+}
+
+/// Counters from `resolvectl statistics`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolvedStats {
+    pub current_transactions: u64,
+    pub total_transactions: u64,
+    pub cache_size: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+impl ResolvedStats {
+    pub fn cache_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Parse `resolvectl statistics` output, whose counters are `Name: value` lines
+pub fn parse_resolvectl_statistics(output: &str) -> Option<ResolvedStats> {
+    let mut stats = ResolvedStats::default();
+    let mut found = false;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<u64>() else {
+            continue;
+        };
+        let field = match key.trim() {
+            "Current Transactions" => &mut stats.current_transactions,
+            "Total Transactions" => &mut stats.total_transactions,
+            "Current Cache Size" => &mut stats.cache_size,
+            "Cache Hits" => &mut stats.cache_hits,
+            "Cache Misses" => &mut stats.cache_misses,
+            _ => continue,
+        };
+        *field = value;
+        found = true;
+    }
+    found.then_some(stats)
+}
+
+/// Captured queries and responses, shared between the capture thread and the monitor
+#[derive(Debug, Default)]
+pub struct DnsLog {
+    /// (client address, client port, query ID) → (name, sent at)
+    pending: HashMap<(IpAddr, u16, u16), (String, Instant)>,
+    events: VecDeque<DnsEvent>,
+}
+
+impl DnsLog {
+    /// Record one IP packet (IPv4 or IPv6, no link-layer header) if it carries DNS
+    pub fn observe_packet(&mut self, packet: &[u8], now: Instant) {
+        self.expire(now);
+        let Some((src, dst, payload)) = udp_datagram(packet) else {
+            return;
+        };
+        let Some(message) = DnsMessage::parse(payload) else {
+            return;
+        };
+
+        if !message.is_response && (dst.1 == DNS_PORT || dst.1 == MDNS_PORT) {
+            if dst.1 == MDNS_PORT {
+                self.push(message.name, DnsOutcome::Multicast, now);
+                return;
+            }
+            let key = (src.0, src.1, message.id);
+            // The same packet is seen twice on loopback; keep the first sighting
+            if self.pending.len() < MAX_PENDING {
+                self.pending.entry(key).or_insert((message.name, now));
+            }
+        } else if message.is_response && src.1 == DNS_PORT {
+            let key = (dst.0, dst.1, message.id);
+            if let Some((name, sent)) = self.pending.remove(&key) {
+                let outcome = DnsOutcome::Answered {
+                    rcode: message.rcode,
+                    latency: now.saturating_duration_since(sent),
+                };
+                self.push(name, outcome, now);
+            }
+        }
+    }
+
+    /// Turn queries older than [`QUERY_TIMEOUT`] into timeouts and forget events
+    /// older than [`DNS_WINDOW`]
+    pub fn expire(&mut self, now: Instant) {
+        let mut timed_out = Vec::new();
+        self.pending.retain(|_, (name, sent)| {
+            if now.saturating_duration_since(*sent) < QUERY_TIMEOUT {
+                true
+            } else {
+                timed_out.push((std::mem::take(name), *sent));
+                false
+            }
+        });
+        timed_out.sort_by_key(|(_, sent)| *sent);
+        for (name, sent) in timed_out {
+            self.push(name, DnsOutcome::TimedOut, sent + QUERY_TIMEOUT);
+        }
+
+        while self
+            .events
+            .front()
+            .is_some_and(|event| now.saturating_duration_since(event.at) > DNS_WINDOW)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    fn push(&mut self, name: String, outcome: DnsOutcome, at: Instant) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(DnsEvent { name, outcome, at });
+    }
+
+    pub fn summary(&mut self, now: Instant) -> DnsSummary {
+        self.expire(now);
+
+        let mut summary = DnsSummary {
+            queries: self.events.len(),
+            ..DnsSummary::default()
+        };
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut latencies = Vec::new();
+        for event in &self.events {
+            *counts.entry(event.name.as_str()).or_default() += 1;
+            match event.outcome {
+                DnsOutcome::Answered { rcode, latency } => {
+                    summary.answered += 1;
+                    match rcode {
+                        RCODE_NXDOMAIN => summary.nxdomain += 1,
+                        RCODE_SERVFAIL => summary.servfail += 1,
+                        _ => {}
+                    }
+                    latencies.push((event.name.as_str(), latency));
+                }
+                DnsOutcome::TimedOut => summary.timeouts += 1,
+                DnsOutcome::Multicast => {}
+            }
+        }
+
+        if !latencies.is_empty() {
+            let total: Duration = latencies.iter().map(|(_, latency)| *latency).sum();
+            summary.avg_latency = Some(total / latencies.len() as u32);
+        }
+        latencies.sort_by_key(|(_, latency)| std::cmp::Reverse(*latency));
+        summary.slowest = latencies
+            .into_iter()
+            .take(TOP_ENTRIES)
+            .map(|(name, latency)| (name.to_string(), latency))
+            .collect();
+
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        summary.top_domains = counts
+            .into_iter()
+            .take(TOP_ENTRIES)
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+
+        summary
+    }
+}
+
+/// The parts of a DNS message the monitor needs: header fields and the first question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnsMessage {
+    pub id: u16,
+    pub is_response: bool,
+    pub rcode: u8,
+    /// First question's name, lowercase, without the trailing dot
+    pub name: String,
+}
+
+impl DnsMessage {
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        if payload.len() < 12 {
+            return None;
+        }
+        let id = u16::from_be_bytes([payload[0], payload[1]]);
+        let flags = u16::from_be_bytes([payload[2], payload[3]]);
+        let question_count = u16::from_be_bytes([payload[4], payload[5]]);
+        if question_count == 0 {
+            return None;
+        }
+
+        // The first question's name follows the header uncompressed
+        let mut labels = Vec::new();
+        let mut offset = 12;
+        loop {
+            let len = usize::from(*payload.get(offset)?);
+            if len == 0 {
+                break;
+            }
+            // Compression pointers and reserved label types don't belong here
+            if len & 0xC0 != 0 {
+                return None;
+            }
+            let label = payload.get(offset + 1..offset + 1 + len)?;
+            labels.push(String::from_utf8_lossy(label).to_ascii_lowercase());
+            offset += 1 + len;
+        }
+
+        Some(Self {
+            id,
+            is_response: flags & 0x8000 != 0,
+            rcode: (flags & 0x000F) as u8,
+            name: if labels.is_empty() {
+                ".".to_string()
+            } else {
+                labels.join(".")
+            },
+        })
+    }
+}
+
+type Endpoint = (IpAddr, u16);
+
+/// Source and destination endpoints and payload of a UDP packet
+fn udp_datagram(packet: &[u8]) -> Option<(Endpoint, Endpoint, &[u8])> {
+    let version = packet.first()? >> 4;
+    let (src, dst, udp) = match version {
+        4 => {
+            let header_len = usize::from(packet[0] & 0x0F) * 4;
+            let fragment_offset = u16::from_be_bytes([*packet.get(6)?, *packet.get(7)?]) & 0x1FFF;
+            if *packet.get(9)? != IPPROTO_UDP || fragment_offset != 0 {
+                return None;
+            }
+            let src: [u8; 4] = packet.get(12..16)?.try_into().ok()?;
+            let dst: [u8; 4] = packet.get(16..20)?.try_into().ok()?;
+            (
+                IpAddr::V4(Ipv4Addr::from(src)),
+                IpAddr::V4(Ipv4Addr::from(dst)),
+                packet.get(header_len..)?,
+            )
+        }
+        // Extension headers are rare on DNS traffic and skipped
+        6 => {
+            if *packet.get(6)? != IPPROTO_UDP {
+                return None;
+            }
+            let src: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+            let dst: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+            (
+                IpAddr::V6(Ipv6Addr::from(src)),
+                IpAddr::V6(Ipv6Addr::from(dst)),
+                packet.get(40..)?,
+            )
+        }
+        _ => return None,
+    };
+
+    let src_port = u16::from_be_bytes([*udp.first()?, *udp.get(1)?]);
+    let dst_port = u16::from_be_bytes([*udp.get(2)?, *udp.get(3)?]);
+    Some(((src, src_port), (dst, dst_port), udp.get(8..)?))
+}
+
+/// DNS activity from live capture, or systemd-resolved's counters as a fallback.
+/// Clones share the capture, which stops once the last clone is dropped.
+#[derive(Clone)]
+pub struct DnsMonitor {
+    log: Arc<Mutex<DnsLog>>,
+    source: DnsSource,
+}
+
+impl Default for DnsMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DnsMonitor {
+    pub fn new() -> Self {
+        Self {
+            log: Arc::new(Mutex::new(DnsLog::default())),
+            source: DnsSource::Pending,
+        }
+    }
+
+    pub fn source(&self) -> &DnsSource {
+        &self.source
+    }
+
+    /// Start capturing on the first call when permitted; otherwise re-read the
+    /// systemd-resolved counters
+    pub fn update(&mut self) {
+        if self.source == DnsSource::Capture {
+            return;
+        }
+        if self.source == DnsSource::Pending {
+            match start_capture(Arc::clone(&self.log)) {
+                Ok(()) => {
+                    self.source = DnsSource::Capture;
+                    return;
+                }
+                Err(reason) => self.source = DnsSource::Unavailable(reason),
+            }
+        }
+
+        if let Some(stats) = Command::new("resolvectl")
+            .arg("statistics")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                parse_resolvectl_statistics(&String::from_utf8_lossy(&output.stdout))
+            })
+        {
+            self.source = DnsSource::Resolved(stats);
+        }
+    }
+
+    /// Captured activity over the last [`DNS_WINDOW`]; empty unless capturing
+    pub fn summary(&self) -> DnsSummary {
+        self.log
+            .lock()
+            .map(|mut log| log.summary(Instant::now()))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn start_capture(log: Arc<Mutex<DnsLog>>) -> Result<(), String> {
+    if !crate::traceroute::has_cap_net_raw() {
+        return Err("live capture needs CAP_NET_RAW".to_string());
+    }
+    let socket = PacketSocket::open().map_err(|e| format!("packet socket: {e}"))?;
+
+    std::thread::spawn(move || {
+        let mut buffer = vec![0u8; 65536];
+        // Exits once every DnsMonitor sharing the log has been dropped
+        while Arc::strong_count(&log) > 1 {
+            let Ok(Some(len)) = socket.recv(&mut buffer, Duration::from_millis(500)) else {
+                continue;
+            };
+            if let Ok(mut log) = log.lock() {
+                log.observe_packet(&buffer[..len], Instant::now());
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_capture(_log: Arc<Mutex<DnsLog>>) -> Result<(), String> {
+    Err("live capture is only supported on Linux".to_string())
+}
+
+/// `AF_PACKET` datagram socket receiving every IP packet on every interface, closed on drop
+#[cfg(target_os = "linux")]
+struct PacketSocket {
+    fd: libc::c_int,
+}
+
+#[cfg(target_os = "linux")]
+impl PacketSocket {
+    fn open() -> std::io::Result<Self> {
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        // SAFETY: plain socket(2) call; the result is checked before use
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_DGRAM,
+                libc::c_int::from(protocol),
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self { fd })
+    }
+
+    /// Wait up to `timeout` for one packet; None when nothing arrived
+    fn recv(&self, buffer: &mut [u8], timeout: Duration) -> std::io::Result<Option<usize>> {
+        let mut poll_fd = libc::pollfd {
+            fd: self.fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: poll_fd is a single valid pollfd
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) };
+        if ready < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if ready == 0 {
+            return Ok(None);
+        }
+
+        // SAFETY: buffer is valid for its length
+        let received = unsafe {
+            libc::recv(
+                self.fd,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+                0,
+            )
+        };
+        if received < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Some(received as usize))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for PacketSocket {
+    fn drop(&mut self) {
+        // SAFETY: fd was opened by this socket and is closed exactly once
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dns_payload(id: u16, response: bool, rcode: u8, name: &str) -> Vec<u8> {
+        let flags: u16 = if response {
+            0x8180 | u16::from(rcode)
+        } else {
+            0x0100
+        };
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&id.to_be_bytes());
+        payload.extend_from_slice(&flags.to_be_bytes());
+        payload.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+        for label in name.split('.') {
+            payload.push(label.len() as u8);
+            payload.extend_from_slice(label.as_bytes());
+        }
+        payload.extend_from_slice(&[0, 0, 1, 0, 1]);
+        payload
+    }
+
+    fn ipv4_udp(src: ([u8; 4], u16), dst: ([u8; 4], u16), payload: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0x45, 0, 0, 0, 0, 0, 0, 0, 64, IPPROTO_UDP, 0, 0];
+        packet.extend_from_slice(&src.0);
+        packet.extend_from_slice(&dst.0);
+        packet.extend_from_slice(&src.1.to_be_bytes());
+        packet.extend_from_slice(&dst.1.to_be_bytes());
+        packet.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    const CLIENT: [u8; 4] = [10, 0, 0, 5];
+    const SERVER: [u8; 4] = [10, 0, 0, 1];
+
+    fn query(log: &mut DnsLog, id: u16, name: &str, at: Instant) {
+        let payload = dns_payload(id, false, 0, name);
+        log.observe_packet(&ipv4_udp((CLIENT, 40000), (SERVER, 53), &payload), at);
+    }
+
+    fn answer(log: &mut DnsLog, id: u16, rcode: u8, name: &str, at: Instant) {
+        let payload = dns_payload(id, true, rcode, name);
+        log.observe_packet(&ipv4_udp((SERVER, 53), (CLIENT, 40000), &payload), at);
+    }
+
+    #[test]
+    fn test_queries_paired_with_responses() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut log = DnsLog::default();
+
+        query(&mut log, 1, "Example.com", start);
+        // Loopback shows every packet twice; the duplicate must not restart the clock
+        query(&mut log, 1, "example.com", start + ms(5));
+        answer(&mut log, 1, RCODE_NOERROR, "example.com", start + ms(40));
+        answer(&mut log, 1, RCODE_NOERROR, "example.com", start + ms(41));
+        query(&mut log, 2, "example.com", start + ms(100));
+        answer(&mut log, 2, RCODE_NOERROR, "example.com", start + ms(120));
+        query(&mut log, 3, "typo.example", start + ms(200));
+        answer(&mut log, 3, RCODE_NXDOMAIN, "typo.example", start + ms(700));
+        // Never answered
+        query(&mut log, 4, "down.example", start + ms(300));
+        // A response nobody asked for is ignored
+        answer(
+            &mut log,
+            9,
+            RCODE_SERVFAIL,
+            "stray.example",
+            start + ms(400),
+        );
+
+        let summary = log.summary(start + Duration::from_secs(6));
+        assert_eq!(summary.queries, 4);
+        assert_eq!(summary.answered, 3);
+        assert_eq!(summary.nxdomain, 1);
+        assert_eq!(summary.servfail, 0);
+        assert_eq!(summary.timeouts, 1);
+        assert_eq!(summary.nxdomain_rate(), 1.0 / 3.0);
+        assert_eq!(summary.avg_latency, Some(ms(560) / 3));
+        assert_eq!(summary.top_domains[0], ("example.com".to_string(), 2));
+        assert_eq!(summary.slowest[0], ("typo.example".to_string(), ms(500)));
+        assert!(!summary.looks_like_outage());
+
+        // Everything ages out of the window
+        assert_eq!(log.summary(start + Duration::from_secs(400)).queries, 0);
+    }
+
+    #[test]
+    fn test_unanswered_queries_look_like_an_outage() {
+        let start = Instant::now();
+        let mut log = DnsLog::default();
+        for id in 0..6 {
+            query(&mut log, id, "example.com", start);
+        }
+        // Still within the timeout: not yet counted
+        assert_eq!(log.summary(start + Duration::from_secs(1)).timeouts, 0);
+        let summary = log.summary(start + QUERY_TIMEOUT);
+        assert_eq!(summary.timeouts, 6);
+        assert!(summary.looks_like_outage());
+
+        // Truncated and non-DNS packets are ignored
+        log.observe_packet(&[0x45, 0, 0], start);
+        log.observe_packet(&ipv4_udp((CLIENT, 1), (SERVER, 53), &[1, 2, 3]), start);
+        assert_eq!(log.summary(start + QUERY_TIMEOUT).queries, 6);
+    }
+
+    #[test]
+    fn test_resolvectl_statistics_parsing() {
+        let output = "DNSSEC supported by current servers: no
+
+Transactions
+Current Transactions: 1
+  Total Transactions: 8812
+
+Cache
+  Current Cache Size: 143
+          Cache Hits: 6000
+        Cache Misses: 2000
+
+DNSSEC Verdicts
+              Secure: 0
+";
+        let stats = parse_resolvectl_statistics(output).unwrap();
+        assert_eq!(stats.total_transactions, 8812);
+        assert_eq!(stats.current_transactions, 1);
+        assert_eq!(stats.cache_size, 143);
+        assert_eq!(stats.cache_hit_rate(), 0.75);
+        assert_eq!(parse_resolvectl_statistics("Failed to connect"), None);
+    }
+}
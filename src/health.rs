@@ -0,0 +1,200 @@
+//! The "System Status" health assessment shown by the Diagnostics panel
+//!
+//! [`assess`] grades connection quality (retransmissions, RTT, congested sockets, the
+//! connection count) and interface errors into HEALTHY, WARNING or CRITICAL. The
+//! dashboard runs it once per refresh, whichever panel is open, so the panel and the
+//! `NotifyOnCritical` notifier always agree on one set of thresholds.
+
+use crate::connections::NetworkConnection;
+use crate::device::Device;
+
+/// Retransmissions summed over all connections above which health is critical
+pub const CRITICAL_RETRANSMISSIONS: u32 = 100;
+/// Retransmissions summed over all connections above which health is a warning
+pub const WARNING_RETRANSMISSIONS: u32 = 25;
+/// Average RTT (ms) above which health is critical
+pub const CRITICAL_RTT_MS: f64 = 2000.0;
+/// Average RTT (ms) above which health is a warning
+pub const WARNING_RTT_MS: f64 = 500.0;
+/// Congested connections above which health is critical
+pub const CRITICAL_CONGESTED: usize = 5;
+/// Congested connections above which health is a warning
+pub const WARNING_CONGESTED: usize = 1;
+/// Open connections above which health is a warning
+pub const WARNING_CONNECTIONS: u32 = 2000;
+/// Interface errors, in and out, above which health is critical
+pub const CRITICAL_INTERFACE_ERRORS: u64 = 50;
+/// Interface drops, in and out, above which health is critical
+pub const CRITICAL_INTERFACE_DROPS: u64 = 100;
+
+// A connection is congested when its queue passes this many bytes, or when it is both
+// slow and retransmitting
+const CONGESTED_QUEUE_BYTES: u32 = 65536;
+const CONGESTED_RTT_MS: f64 = 500.0;
+const CONGESTED_RETRANSMISSIONS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthStatus {
+    #[default]
+    Healthy,
+    Warning,
+    Critical,
+}
+
+impl HealthStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Healthy => "🟢 HEALTHY",
+            Self::Warning => "🟡 WARNING",
+            Self::Critical => "🔴 CRITICAL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthAssessment {
+    pub status: HealthStatus,
+    /// What made the status critical, e.g. "🚨 SEVERE LATENCY"
+    pub critical_issues: Vec<&'static str>,
+    pub warnings: Vec<&'static str>,
+}
+
+impl HealthAssessment {
+    fn critical(&mut self, issue: &'static str) {
+        self.critical_issues.push(issue);
+        self.status = HealthStatus::Critical;
+    }
+
+    fn warning(&mut self, issue: &'static str) {
+        self.warnings.push(issue);
+        self.status = self.status.max(HealthStatus::Warning);
+    }
+}
+
+/// Grade the current connections (`connection_total` of them, all states) and the
+/// interfaces' error and drop counters
+pub fn assess(
+    connections: &[NetworkConnection],
+    connection_total: u32,
+    devices: &[Device],
+) -> HealthAssessment {
+    let mut total_retrans = 0u32;
+    let mut rtt_sum = 0.0;
+    let mut rtt_count = 0;
+    let mut congested = 0usize;
+
+    for conn in connections {
+        total_retrans = total_retrans.saturating_add(conn.socket_info.retrans);
+
+        if let Some(rtt) = conn.socket_info.rtt {
+            rtt_sum += rtt;
+            rtt_count += 1;
+            if rtt > CONGESTED_RTT_MS && conn.socket_info.retrans > CONGESTED_RETRANSMISSIONS {
+                congested += 1;
+            }
+        }
+
+        if conn.socket_info.send_queue > CONGESTED_QUEUE_BYTES
+            || conn.socket_info.recv_queue > CONGESTED_QUEUE_BYTES
+        {
+            congested += 1;
+        }
+    }
+    let avg_rtt = if rtt_count > 0 {
+        rtt_sum / f64::from(rtt_count)
+    } else {
+        0.0
+    };
+
+    let mut health = HealthAssessment::default();
+
+    if total_retrans > CRITICAL_RETRANSMISSIONS {
+        health.critical("🚨 MASSIVE RETRANSMISSIONS");
+    } else if total_retrans > WARNING_RETRANSMISSIONS {
+        health.warning("⚠️ HIGH RETRANS RATE");
+    }
+
+    if avg_rtt > CRITICAL_RTT_MS {
+        health.critical("🚨 SEVERE LATENCY");
+    } else if avg_rtt > WARNING_RTT_MS {
+        health.warning("⚠️ HIGH LATENCY");
+    }
+
+    if congested > CRITICAL_CONGESTED {
+        health.critical("🚨 NETWORK CONGESTION");
+    } else if congested > WARNING_CONGESTED {
+        health.warning("⚠️ CONGESTION DETECTED");
+    }
+
+    if connection_total > WARNING_CONNECTIONS {
+        health.warning("⚠️ CONNECTION FLOOD");
+    }
+
+    let errors: u64 = devices
+        .iter()
+        .map(|device| device.stats.errors_in + device.stats.errors_out)
+        .sum();
+    let drops: u64 = devices
+        .iter()
+        .map(|device| device.stats.drops_in + device.stats.drops_out)
+        .sum();
+    if errors > CRITICAL_INTERFACE_ERRORS {
+        health.critical("🚨 INTERFACE ERRORS");
+    }
+    if drops > CRITICAL_INTERFACE_DROPS {
+        health.critical("🚨 PACKET DROPS");
+    }
+
+    health
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connections::{ConnectionState, Protocol, SocketInfo};
+
+    fn connection(rtt: Option<f64>, retrans: u32) -> NetworkConnection {
+        NetworkConnection {
+            local_addr: "10.0.0.1:40000".parse().unwrap(),
+            remote_addr: "10.0.0.2:443".parse().unwrap(),
+            state: ConnectionState::Established,
+            protocol: Protocol::Tcp,
+            pid: None,
+            process_name: None,
+            process_cmdline: None,
+            bytes_sent: 0,
+            bytes_received: 0,
+            socket_info: SocketInfo {
+                rtt,
+                retrans,
+                ..SocketInfo::default()
+            },
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
+        }
+    }
+
+    #[test]
+    fn test_latency_and_retransmissions_grade_health() {
+        assert_eq!(assess(&[], 0, &[]), HealthAssessment::default());
+
+        let health = assess(&[connection(Some(20.0), 30)], 1, &[]);
+        assert_eq!(health.status, HealthStatus::Warning);
+        assert_eq!(health.warnings, ["⚠️ HIGH RETRANS RATE"]);
+
+        // RTT alone takes health to critical, with no alert involved
+        let health = assess(&[connection(Some(2500.0), 0)], 1, &[]);
+        assert_eq!(health.status, HealthStatus::Critical);
+        assert_eq!(health.critical_issues, ["🚨 SEVERE LATENCY"]);
+    }
+
+    #[test]
+    fn test_interface_errors_are_critical() {
+        let mut device = Device::new("eth0".to_string());
+        device.stats.errors_in = CRITICAL_INTERFACE_ERRORS + 1;
+        let health = assess(&[], 0, &[device]);
+        assert_eq!(health.status, HealthStatus::Critical);
+        assert_eq!(health.critical_issues, ["🚨 INTERFACE ERRORS"]);
+    }
+}
@@ -0,0 +1,234 @@
+//! HTTP health checks for the Diagnostics panel (`http-checks` feature)
+//!
+//! Requests go through ureq with a connector chain that timestamps each step: the
+//! resolver is wrapped to time DNS, and pass-through connectors after the TCP and TLS
+//! connectors mark when each finished. A fresh agent is used per check so every check
+//! pays the full DNS/connect/TLS cost instead of reusing a pooled connection.
+
+use crate::active_diagnostics::HttpCheckResult;
+use std::time::{Duration, Instant};
+
+/// Responses slower than this are shown as degraded even with a 2xx status
+pub const SLOW_RESPONSE_MS: f64 = 1000.0;
+
+#[cfg(feature = "http-checks")]
+pub fn check(url: &str, follow_redirects: bool, timeout: Duration) -> HttpCheckResult {
+    use std::sync::{Arc, Mutex};
+    use ureq::unversioned::resolver::DefaultResolver;
+    use ureq::unversioned::transport::{Connector, RustlsConnector, TcpConnector};
+
+    let phases = Arc::new(Mutex::new(Phases::default()));
+    let connector =
+        ().chain(TcpConnector::default())
+            .chain(PhaseMark::new(Step::Connected, &phases))
+            .chain(RustlsConnector::default())
+            .chain(PhaseMark::new(Step::TlsDone, &phases));
+    let resolver = TimedResolver {
+        inner: DefaultResolver::default(),
+        phases: Arc::clone(&phases),
+    };
+
+    let config = ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .http_status_as_error(false)
+        .max_redirects(if follow_redirects { 10 } else { 0 })
+        // With redirects off, a 3xx is a result to show, not an error
+        .max_redirects_will_error(false)
+        .user_agent(concat!("netwatch/", env!("CARGO_PKG_VERSION")))
+        .build();
+    let agent = ureq::Agent::with_parts(config, connector, resolver);
+
+    let start = Instant::now();
+    let response = agent.get(url).call();
+    let headers_received = Instant::now();
+
+    let (status_code, error, timed_out) = match response {
+        Ok(mut response) => {
+            // Drain a bounded amount of body so total covers the transfer
+            let _ = response
+                .body_mut()
+                .with_config()
+                .limit(1024 * 1024)
+                .read_to_vec();
+            (response.status().as_u16(), None, false)
+        }
+        Err(e) => (0, Some(e.to_string()), matches!(e, ureq::Error::Timeout(_))),
+    };
+    let total_ms = millis(start.elapsed());
+
+    let phases = phases.lock().map(|p| p.clone()).unwrap_or_default();
+    let is_https = url.starts_with("https://");
+    let connect_start = phases.resolved.unwrap_or(start);
+    HttpCheckResult {
+        url: url.to_string(),
+        status_code,
+        total_ms,
+        dns_ms: phases.dns.map(millis),
+        connect_ms: phases.connected.map(|t| millis(t - connect_start)),
+        tls_ms: match (is_https, phases.connected, phases.tls_done) {
+            (true, Some(connected), Some(tls_done)) => Some(millis(tls_done - connected)),
+            _ => None,
+        },
+        ttfb_ms: (status_code != 0).then(|| millis(headers_received - start)),
+        // The TLS connector only returns after the handshake, including certificate
+        // verification, succeeded
+        tls_valid: is_https && phases.tls_done.is_some(),
+        timed_out,
+        error,
+        last_test: start,
+    }
+}
+
+#[cfg(not(feature = "http-checks"))]
+pub fn check(url: &str, _follow_redirects: bool, _timeout: Duration) -> HttpCheckResult {
+    HttpCheckResult {
+        url: url.to_string(),
+        status_code: 0,
+        total_ms: 0.0,
+        dns_ms: None,
+        connect_ms: None,
+        tls_ms: None,
+        ttfb_ms: None,
+        tls_valid: false,
+        timed_out: false,
+        error: Some("netwatch was built without the http-checks feature".to_string()),
+        last_test: Instant::now(),
+    }
+}
+
+#[cfg(feature = "http-checks")]
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// When each step of the first connection finished. Later connections (redirects)
+/// leave these alone.
+#[cfg(feature = "http-checks")]
+#[derive(Debug, Clone, Default)]
+struct Phases {
+    dns: Option<Duration>,
+    resolved: Option<Instant>,
+    connected: Option<Instant>,
+    tls_done: Option<Instant>,
+}
+
+#[cfg(feature = "http-checks")]
+#[derive(Debug)]
+struct TimedResolver {
+    inner: ureq::unversioned::resolver::DefaultResolver,
+    phases: std::sync::Arc<std::sync::Mutex<Phases>>,
+}
+
+#[cfg(feature = "http-checks")]
+impl ureq::unversioned::resolver::Resolver for TimedResolver {
+    fn resolve(
+        &self,
+        uri: &ureq::http::Uri,
+        config: &ureq::config::Config,
+        timeout: ureq::unversioned::transport::NextTimeout,
+    ) -> Result<ureq::unversioned::resolver::ResolvedSocketAddrs, ureq::Error> {
+        let start = Instant::now();
+        let addrs = self.inner.resolve(uri, config, timeout);
+        if let Ok(mut phases) = self.phases.lock() {
+            if phases.dns.is_none() {
+                phases.dns = Some(start.elapsed());
+                phases.resolved = Some(Instant::now());
+            }
+        }
+        addrs
+    }
+}
+
+#[cfg(feature = "http-checks")]
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Connected,
+    TlsDone,
+}
+
+/// Pass-through connector that records when the connector before it produced a transport
+#[cfg(feature = "http-checks")]
+#[derive(Debug)]
+struct PhaseMark {
+    step: Step,
+    phases: std::sync::Arc<std::sync::Mutex<Phases>>,
+}
+
+#[cfg(feature = "http-checks")]
+impl PhaseMark {
+    fn new(step: Step, phases: &std::sync::Arc<std::sync::Mutex<Phases>>) -> Self {
+        Self {
+            step,
+            phases: std::sync::Arc::clone(phases),
+        }
+    }
+}
+
+#[cfg(feature = "http-checks")]
+impl<In: ureq::unversioned::transport::Transport> ureq::unversioned::transport::Connector<In>
+    for PhaseMark
+{
+    type Out = In;
+
+    fn connect(
+        &self,
+        _details: &ureq::unversioned::transport::ConnectionDetails,
+        chained: Option<In>,
+    ) -> Result<Option<In>, ureq::Error> {
+        if chained.is_some() {
+            if let Ok(mut phases) = self.phases.lock() {
+                let mark = match self.step {
+                    Step::Connected => &mut phases.connected,
+                    Step::TlsDone => &mut phases.tls_done,
+                };
+                mark.get_or_insert_with(Instant::now);
+            }
+        }
+        Ok(chained)
+    }
+}
+
+#[cfg(all(test, feature = "http-checks"))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn serve_once(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{addr}/health")
+    }
+
+    #[test]
+    fn test_plain_http_check_times_phases() {
+        let url = serve_once("HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n");
+        let result = check(&url, false, Duration::from_secs(5));
+
+        assert_eq!(result.status_code, 204);
+        assert!(result.error.is_none());
+        assert!(result.dns_ms.is_some());
+        assert!(result.connect_ms.is_some());
+        assert!(result.ttfb_ms.is_some());
+        assert_eq!(result.tls_ms, None);
+        assert!(!result.tls_valid);
+    }
+
+    #[test]
+    fn test_redirect_is_reported_when_not_following() {
+        let url = serve_once(
+            "HTTP/1.1 301 Moved Permanently\r\nLocation: http://127.0.0.1:9/\r\nContent-Length: 0\r\n\r\n",
+        );
+        let result = check(&url, false, Duration::from_secs(5));
+
+        assert_eq!(result.status_code, 301);
+        assert!(result.error.is_none());
+    }
+}
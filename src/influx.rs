@@ -0,0 +1,352 @@
+//! InfluxDB line-protocol exporter (`--influx-url`)
+//!
+//! Measurements are built on the collector thread and handed to a background sender
+//! through a bounded channel. When InfluxDB is slow or unreachable the queue fills up
+//! and new batches are dropped, so the collector never waits on the network. Failures
+//! are not printed (the dashboard owns the screen); callers pick them up with
+//! [`InfluxExporter::take_status`].
+
+use crate::connections::ConnectionStats;
+use crate::error::{NetwatchError, Result};
+use crate::stats::StatsCalculator;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Batches waiting to be sent before new ones are dropped
+const QUEUE_DEPTH: usize = 16;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to POST line protocol, parsed from an `http://host[:port]/path?query` URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluxEndpoint {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+    /// InfluxDB 2.x API token, read from `INFLUX_TOKEN` so it stays out of `ps`
+    pub token: Option<String>,
+}
+
+impl InfluxEndpoint {
+    /// Only plain `http://` is supported; put a local proxy in front for TLS
+    pub fn parse(url: &str) -> Result<Self> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            NetwatchError::Config(format!(
+                "Influx URL must start with http:// (https is not supported): {url}"
+            ))
+        })?;
+
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/api/v2/write"),
+        };
+        // Bracketed IPv6 literals carry colons of their own
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(bracketed) => bracketed
+                .split_once(']')
+                .map_or((authority, ""), |(host, rest)| {
+                    (host, rest.strip_prefix(':').unwrap_or(rest))
+                }),
+            None => authority.rsplit_once(':').unwrap_or((authority, "")),
+        };
+        let port = if port.is_empty() {
+            8086
+        } else {
+            port.parse::<u16>()
+                .map_err(|_| NetwatchError::Config(format!("Invalid port in Influx URL: {url}")))?
+        };
+
+        crate::validation::validate_host(host)?;
+        if path.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(NetwatchError::Config(format!(
+                "Invalid path in Influx URL: {url}"
+            )));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            token: std::env::var("INFLUX_TOKEN").ok().filter(|t| !t.is_empty()),
+        })
+    }
+}
+
+/// Sends line-protocol batches to InfluxDB from a background thread
+pub struct InfluxExporter {
+    sender: Option<SyncSender<String>>,
+    worker: Option<JoinHandle<()>>,
+    // Failures, recoveries and drops waiting for take_status
+    status_sender: Sender<String>,
+    status: Receiver<String>,
+    dropped: u64,
+}
+
+impl InfluxExporter {
+    pub fn new(url: &str) -> Result<Self> {
+        let endpoint = InfluxEndpoint::parse(url)?;
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_DEPTH);
+        let (status_sender, status) = mpsc::channel();
+        let worker_status = status_sender.clone();
+        let worker = thread::spawn(move || send_loop(endpoint, receiver, worker_status));
+
+        Ok(Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            status_sender,
+            status,
+            dropped: 0,
+        })
+    }
+
+    /// The newest failure or recovery message since the last call, for the caller to
+    /// show. Never blocks.
+    pub fn take_status(&mut self) -> Option<String> {
+        self.status.try_iter().last()
+    }
+
+    /// Queue one refresh worth of measurements. Never blocks.
+    pub fn export(
+        &mut self,
+        stats_calculators: &HashMap<String, StatsCalculator>,
+        conn_stats: Option<&ConnectionStats>,
+    ) {
+        let payload = line_protocol(stats_calculators, conn_stats, timestamp_ns());
+        if payload.is_empty() {
+            return;
+        }
+
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        match sender.try_send(payload) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                // Warn on the first drop and then occasionally, not every refresh
+                if self.dropped == 1 || self.dropped % 100 == 0 {
+                    let _ = self.status_sender.send(format!(
+                        "InfluxDB export is falling behind, {} batches dropped",
+                        self.dropped
+                    ));
+                }
+            }
+            Err(TrySendError::Disconnected(_)) => self.sender = None,
+        }
+    }
+}
+
+impl Drop for InfluxExporter {
+    fn drop(&mut self) {
+        // Closing the channel lets the worker flush what is queued and exit
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn send_loop(endpoint: InfluxEndpoint, receiver: Receiver<String>, status: Sender<String>) {
+    let mut failing = false;
+
+    for payload in receiver {
+        match post(&endpoint, &payload) {
+            Ok(()) if failing => {
+                let _ = status.send("InfluxDB export recovered".to_string());
+                failing = false;
+            }
+            Ok(()) => {}
+            Err(e) => {
+                // Only report the transition so an outage doesn't flood the status line
+                if !failing {
+                    let _ = status.send(format!("InfluxDB export failed: {e}"));
+                    failing = true;
+                }
+            }
+        }
+    }
+}
+
+fn post(endpoint: &InfluxEndpoint, payload: &str) -> std::io::Result<()> {
+    let addr = (endpoint.host.as_str(), endpoint.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("cannot resolve {}", endpoint.host),
+            )
+        })?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let authorization = endpoint
+        .token
+        .as_ref()
+        .map(|token| format!("Authorization: Token {token}\r\n"))
+        .unwrap_or_default();
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\n{authorization}Content-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        endpoint.path,
+        endpoint.host,
+        endpoint.port,
+        payload.len(),
+        payload
+    );
+    stream.write_all(request.as_bytes())?;
+
+    // The status line is all we need: InfluxDB answers 204 on success
+    let mut response = [0u8; 64];
+    let read = stream.read(&mut response)?;
+    let status_line = String::from_utf8_lossy(&response[..read]);
+    let status = status_line.split_whitespace().nth(1).unwrap_or("");
+    if status.starts_with('2') {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "HTTP {}",
+                status_line.lines().next().unwrap_or("(no response)")
+            ),
+        ))
+    }
+}
+
+fn timestamp_ns() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Build `netwatch_traffic` (one line per interface) and `netwatch_connections` lines
+pub fn line_protocol(
+    stats_calculators: &HashMap<String, StatsCalculator>,
+    conn_stats: Option<&ConnectionStats>,
+    timestamp_ns: u128,
+) -> String {
+    let mut interfaces: Vec<_> = stats_calculators.iter().collect();
+    interfaces.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut lines = String::new();
+    for (interface, calculator) in interfaces {
+        let (rx_bps, tx_bps) = calculator.current_speed();
+        let (rx_avg_bps, tx_avg_bps) = calculator.average_speed();
+        let (rx_bytes, tx_bytes) = calculator.total_bytes();
+        lines.push_str(&format!(
+            "netwatch_traffic,interface={} rx_bps={rx_bps}i,tx_bps={tx_bps}i,rx_avg_bps={rx_avg_bps}i,tx_avg_bps={tx_avg_bps}i,rx_bytes={rx_bytes}i,tx_bytes={tx_bytes}i {timestamp_ns}\n",
+            escape_tag(interface)
+        ));
+    }
+
+    if let Some(stats) = conn_stats {
+        lines.push_str(&format!(
+            "netwatch_connections total={}i,established={}i,listening={}i,time_wait={}i,close_wait={}i,fin_wait={}i,syn_sent={}i,tcp={}i,udp={}i {timestamp_ns}\n",
+            stats.total,
+            stats.established,
+            stats.listening,
+            stats.time_wait,
+            stats.close_wait,
+            stats.fin_wait,
+            stats.syn_sent,
+            stats.tcp,
+            stats.udp
+        ));
+    }
+
+    lines
+}
+
+/// Tag values must escape commas, equals signs and spaces
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_parsing() {
+        let endpoint =
+            InfluxEndpoint::parse("http://localhost:8086/api/v2/write?org=o&bucket=b").unwrap();
+        assert_eq!(endpoint.host, "localhost");
+        assert_eq!(endpoint.port, 8086);
+        assert_eq!(endpoint.path, "/api/v2/write?org=o&bucket=b");
+
+        let endpoint = InfluxEndpoint::parse("http://10.0.0.5").unwrap();
+        assert_eq!(endpoint.port, 8086);
+        assert_eq!(endpoint.path, "/api/v2/write");
+
+        let endpoint = InfluxEndpoint::parse("http://[::1]:9999/write").unwrap();
+        assert_eq!((endpoint.host.as_str(), endpoint.port), ("::1", 9999));
+
+        assert!(InfluxEndpoint::parse("https://influx.example.com").is_err());
+        assert!(InfluxEndpoint::parse("http://host:notaport/write").is_err());
+        assert!(InfluxEndpoint::parse("http://bad host/write").is_err());
+    }
+
+    #[test]
+    fn test_line_protocol_payload() {
+        let mut calculators = HashMap::new();
+        calculators.insert(
+            "eth 0".to_string(),
+            StatsCalculator::new(Duration::from_secs(300)),
+        );
+        let conn_stats = ConnectionStats {
+            total: 3,
+            established: 2,
+            listening: 1,
+            tcp: 3,
+            ..Default::default()
+        };
+
+        let payload = line_protocol(&calculators, Some(&conn_stats), 42);
+        let lines: Vec<&str> = payload.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("netwatch_traffic,interface=eth\\ 0 rx_bps=0i,"));
+        assert!(lines[0].ends_with(" 42"));
+        assert!(lines[1].starts_with("netwatch_connections total=3i,established=2i,"));
+
+        assert!(line_protocol(&HashMap::new(), None, 42).is_empty());
+    }
+
+    #[test]
+    fn test_send_failure_reported_through_status() {
+        // A port nothing listens on, so the worker's post is refused
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut exporter = InfluxExporter::new(&format!("http://127.0.0.1:{port}/write")).unwrap();
+        assert!(exporter.take_status().is_none());
+
+        let conn_stats = ConnectionStats::default();
+        exporter.export(&HashMap::new(), Some(&conn_stats));
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let status = loop {
+            if let Some(status) = exporter.take_status() {
+                break status;
+            }
+            assert!(std::time::Instant::now() < deadline, "no status reported");
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert!(status.starts_with("InfluxDB export failed"));
+    }
+}
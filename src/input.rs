@@ -27,12 +27,20 @@ pub enum InputEvent {
     ToggleMultiple,     // Enter - Toggle between single/multiple device view
     ZoomIn,             // '+' - Zoom graph scale
     ZoomOut,            // '-' - Zoom graph scale
+    ToggleLogScale,     // 'L' - Toggle logarithmic graph scale
+    ToggleCompact,      // 'c' - Toggle compact layout for small terminals
+    KillProcess,        // 'K' - Signal the selected process (Processes panel)
+    ToggleGraphsView,   // 'm' - Single or side-by-side interface graphs (Graphs panel)
+    ToggleListening,    // 'o' - Listening sockets instead of connections (Connections panel)
+    DecreaseInterval,   // '[' - Sample faster (shorter refresh interval)
+    IncreaseInterval,   // ']' - Sample slower (longer refresh interval)
+    Search,             // '/' - Filter the list panels
 
     // Config adjustments (for F2 options)
     IncreaseRefresh, // '>' - Increase refresh rate (decrease interval)
     DecreaseRefresh, // '<' - Decrease refresh rate (increase interval)
-    IncreaseAverage, // ']' - Increase average window
-    DecreaseAverage, // '[' - Decrease average window
+    IncreaseAverage, // '}' - Increase average window
+    DecreaseAverage, // '{' - Decrease average window
 
     // Unknown/unhandled
     Unknown,
@@ -41,6 +49,9 @@ pub enum InputEvent {
 impl InputEvent {
     pub fn from_key_event(key_event: KeyEvent) -> Self {
         match (key_event.code, key_event.modifiers) {
+            // Raw mode delivers Ctrl+C as a key press rather than SIGINT
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => Self::Quit,
+
             (KeyCode::Tab, KeyModifiers::NONE) => Self::NextPanel,
             (KeyCode::Tab, KeyModifiers::SHIFT) => Self::PrevPanel,
             (KeyCode::BackTab, _) => Self::PrevPanel,
@@ -64,10 +75,18 @@ impl InputEvent {
             (KeyCode::Char('g'), _) => Self::ToggleGraphs,
             (KeyCode::Char('+'), _) => Self::ZoomIn,
             (KeyCode::Char('-'), _) => Self::ZoomOut,
+            (KeyCode::Char('L'), _) => Self::ToggleLogScale,
+            (KeyCode::Char('c'), _) => Self::ToggleCompact,
+            (KeyCode::Char('K'), _) => Self::KillProcess,
+            (KeyCode::Char('m'), _) => Self::ToggleGraphsView,
+            (KeyCode::Char('o'), _) => Self::ToggleListening,
             (KeyCode::Char('>'), _) => Self::IncreaseRefresh,
             (KeyCode::Char('<'), _) => Self::DecreaseRefresh,
-            (KeyCode::Char(']'), _) => Self::IncreaseAverage,
-            (KeyCode::Char('['), _) => Self::DecreaseAverage,
+            (KeyCode::Char('['), _) => Self::DecreaseInterval,
+            (KeyCode::Char(']'), _) => Self::IncreaseInterval,
+            (KeyCode::Char('/'), _) => Self::Search,
+            (KeyCode::Char('}'), _) => Self::IncreaseAverage,
+            (KeyCode::Char('{'), _) => Self::DecreaseAverage,
 
             (KeyCode::Esc, _) => Self::Quit,
 
@@ -75,3 +94,57 @@ impl InputEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> InputEvent {
+        InputEvent::from_key_event(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_navigation_keys() {
+        assert_eq!(key(KeyCode::Tab, KeyModifiers::NONE), InputEvent::NextPanel);
+        // Terminals send Shift+Tab as BackTab, usually with SHIFT still set
+        assert_eq!(
+            key(KeyCode::BackTab, KeyModifiers::NONE),
+            InputEvent::PrevPanel
+        );
+        assert_eq!(
+            key(KeyCode::BackTab, KeyModifiers::SHIFT),
+            InputEvent::PrevPanel
+        );
+        assert_eq!(
+            key(KeyCode::Tab, KeyModifiers::SHIFT),
+            InputEvent::PrevPanel
+        );
+
+        for (code, event) in [
+            (KeyCode::Down, InputEvent::NextItem),
+            (KeyCode::Char('j'), InputEvent::NextItem),
+            (KeyCode::Up, InputEvent::PrevItem),
+            (KeyCode::Char('k'), InputEvent::PrevItem),
+            (KeyCode::Right, InputEvent::NextDevice),
+            (KeyCode::Char('l'), InputEvent::NextDevice),
+            (KeyCode::Left, InputEvent::PrevDevice),
+            (KeyCode::Char('h'), InputEvent::PrevDevice),
+        ] {
+            assert_eq!(key(code, KeyModifiers::NONE), event, "{code:?}");
+        }
+
+        // Ctrl+C quits; a plain c toggles the compact layout
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            InputEvent::Quit
+        );
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::NONE),
+            InputEvent::ToggleCompact
+        );
+        assert_eq!(
+            key(KeyCode::Char('/'), KeyModifiers::NONE),
+            InputEvent::Search
+        );
+    }
+}
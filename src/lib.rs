@@ -27,28 +27,49 @@
 //! ```
 
 pub mod active_diagnostics;
+pub mod alerts;
+pub mod check;
 pub mod cli;
+pub mod collectors;
 pub mod config;
+pub mod config_check;
 pub mod connections;
 pub mod dashboard;
 pub mod device;
 pub mod display;
+pub mod dns_monitor;
 pub mod error;
+pub mod health;
+pub mod http_check;
+pub mod influx;
 pub mod input;
 pub mod logger;
+pub mod neighbors;
 pub mod network_intelligence;
+pub mod ping;
 pub mod platform;
+pub mod port_check;
 pub mod processes;
+pub mod quota;
+pub mod remote;
+pub mod replay;
+pub mod resolver;
 pub mod safe_system;
 pub mod security;
+pub mod session;
+pub mod shutdown;
 pub mod simple_overview;
+pub mod snapshot;
 pub mod stats;
 pub mod system;
+pub mod theme;
+pub mod traceroute;
+pub mod units;
 pub mod validation;
 
 use anyhow::Result;
 use cli::Args;
-use crossterm::{execute, terminal::*};
+use crossterm::execute;
 use std::collections::HashMap;
 
 /// Main entry point for the netwatch application.
@@ -76,20 +97,43 @@ pub fn run(args: Args) -> Result<()> {
     // Initialize security monitoring
     security::init_security_monitor();
 
+    // Monitoring plugins report every failure, bad arguments included, as UNKNOWN (3)
+    if args.check {
+        let (status, line) = run_check(&args).unwrap_or_else(|e| check::unknown(&e));
+        println!("{line}");
+        std::process::exit(status.exit_code());
+    }
+
     // Validate all arguments for security
     args.validate().map_err(|e| anyhow::anyhow!(e))?;
+    enter_netns(&args)?;
 
     // Handle simple commands first
+    if args.check_config {
+        let report = config_check::check_config_file();
+        print!("{}", report.render());
+        if !report.is_ok() {
+            anyhow::bail!("{} invalid config key(s)", report.errors.len());
+        }
+        return Ok(());
+    }
+
+    if args.migrate_config {
+        let path = config::Config::migrate_nload()?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
+
     if args.list {
-        return list_interfaces();
+        return list_interfaces(&args);
     }
 
     if args.test {
-        return test_interface_stats(&args.devices);
+        return test_interface_stats(&args);
     }
 
     if args.debug_dashboard {
-        return debug_dashboard_data();
+        return debug_dashboard_data(&selected_traffic_unit(&args)?);
     }
 
     if args.show_comparison {
@@ -97,7 +141,7 @@ pub fn run(args: Args) -> Result<()> {
     }
 
     if args.show_overview {
-        return show_overview_data();
+        return show_overview_data(&selected_traffic_unit(&args)?);
     }
 
     if args.force_terminal {
@@ -105,80 +149,362 @@ pub fn run(args: Args) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(seconds) = args.learn_baseline {
+        let config = load_config(&args)?;
+        let reader = create_reader(&args)?;
+        let interfaces = select_interfaces(&args, &config, reader.as_ref())?;
+
+        shutdown::install_signal_handlers()?;
+        return learn_baseline(
+            &interfaces,
+            reader.as_ref(),
+            &config,
+            std::time::Duration::from_secs(seconds),
+        );
+    }
+
+    if args.once {
+        // Scripted snapshot: no signal handlers or terminal setup, output goes to a pipe
+        let config = load_config(&args)?;
+        let reader = create_reader(&args)?;
+        let interfaces = select_interfaces(&args, &config, reader.as_ref())?;
+
+        let interval = std::time::Duration::from_millis(config.refresh_interval);
+        let report = snapshot::take(&interfaces, reader.as_ref(), interval)?;
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print!("{}", report.render_text());
+        }
+        return Ok(());
+    }
+
     if args.sre_terminal {
         // Load configuration and determine interfaces
-        let mut config = config::Config::load()?;
-        config.apply_args(&args);
-        let reader = platform::create_reader()?;
-        let interfaces = if args.devices.is_empty() {
-            if config.devices == "all" {
-                reader.list_devices()?
-            } else {
-                config
-                    .devices
-                    .split_whitespace()
-                    .map(String::from)
-                    .collect()
-            }
-        } else {
-            args.devices.clone()
+        let config = load_config(&args)?;
+        let reader = create_reader(&args)?;
+        let interfaces = select_interfaces(&args, &config, reader.as_ref())?;
+
+        let log_file = config.log_file.clone();
+        // Ctrl+C/SIGTERM end the refresh loop instead of killing it mid-report
+        shutdown::install_signal_handlers()?;
+        let limit = TerminalRunLimit {
+            iterations: args.iterations,
+            duration: args.run_duration(),
         };
+        return run_enhanced_terminal_mode(interfaces, reader, config, log_file, limit);
+    }
+
+    // Load configuration, with command line arguments taking precedence
+    let mut config = load_config(&args)?;
+
+    // Initialize platform-specific network reader, play back a recording or watch
+    // another machine; connections come from the same place
+    let replay = args
+        .replay
+        .as_deref()
+        .map(replay::Replay::load)
+        .transpose()?;
+    let remote = args
+        .remote
+        .as_deref()
+        .map(remote::RemoteHost::connect)
+        .transpose()?
+        .map(std::sync::Arc::new);
+    let (reader, source): (Box<dyn device::NetworkReader>, _) = match (replay, remote) {
+        (Some(replay), _) => (
+            Box::new(replay::ReplayReader::new(std::sync::Arc::clone(&replay))),
+            connections::ConnectionSource::Replay(replay),
+        ),
+        (None, Some(host)) => (
+            Box::new(remote::RemoteReader::new(std::sync::Arc::clone(&host))),
+            connections::ConnectionSource::Remote(host),
+        ),
+        (None, None) => (create_reader(&args)?, connections::ConnectionSource::Local),
+    };
+    let recorder = args
+        .record
+        .as_deref()
+        .map(replay::Recorder::create)
+        .transpose()?;
+
+    let interfaces = select_interfaces(&args, &config, reader.as_ref())?;
+    let debug_log = logger::DebugLogger::new(args.debug_log.as_deref())?;
+
+    // Report a bad theme or [Colors] while stderr is still readable; the dashboard then
+    // uses the default colors
+    if let Err(e) = theme::Theme::from_config(&config) {
+        eprintln!("{e}; using the default theme");
+        config.theme = None;
+        config.colors.clear();
+    }
+
+    let terminal_limit = TerminalRunLimit {
+        iterations: args.iterations,
+        duration: args.run_duration(),
+    };
 
-        // Validate interface names for security
-        for interface in &interfaces {
-            validation::validate_interface_name(interface)?;
+    // Signals and panics must restore the terminal, so hook them before raw mode
+    shutdown::install_signal_handlers()?;
+    shutdown::install_panic_hook();
+
+    // Initialize display with comprehensive error handling and multiple fallback strategies
+    let tui_result = initialize_enhanced_tui(config.use_mouse);
+
+    let log_file = config.log_file.clone();
+
+    match tui_result {
+        Ok(_stdout) => {
+            println!("Starting SRE Network Forensics Dashboard...");
+            let process_focus = args
+                .monitor_pid
+                .map(|pid| dashboard::ProcessFocus::new(pid, args.exit_on_process_death));
+            let result = dashboard::run_dashboard(
+                interfaces,
+                reader,
+                config,
+                recorder,
+                source,
+                debug_log,
+                process_focus,
+                args.run_duration(),
+            );
+
+            // Cleanup
+            shutdown::restore_terminal();
+            result
         }
+        Err(e) => {
+            eprintln!("⚠️  TUI initialization failed: {e}");
+            eprintln!("🛠️  Attempting enhanced terminal mode with SRE forensics...");
+            run_enhanced_terminal_mode(interfaces, reader, config, log_file, terminal_limit)
+        }
+    }
+}
 
-        // Validate that provided interfaces exist
-        let available_interfaces = reader.list_devices()?;
-        for interface in &interfaces {
-            if !available_interfaces.contains(interface) {
-                anyhow::bail!(
-                    "Interface '{}' not found. Available interfaces: {}",
+/// `--learn-baseline`: observe traffic for `window`, then save the baseline that the
+/// dashboard scores anomalies against
+fn learn_baseline(
+    interfaces: &[String],
+    reader: &dyn device::NetworkReader,
+    config: &config::Config,
+    window: std::time::Duration,
+) -> Result<()> {
+    use network_intelligence::{connection_metrics, NetworkIntelligenceEngine};
+    use std::time::{Duration, Instant};
+
+    let refresh_interval = Duration::from_millis(config.refresh_interval);
+    let mut calculators: HashMap<String, stats::StatsCalculator> = interfaces
+        .iter()
+        .map(|name| {
+            let average_window = config.for_interface(name).average_window;
+            let mut calculator =
+                stats::StatsCalculator::new(Duration::from_secs(average_window as u64));
+            calculator.set_ema_alpha(config.ema_alpha);
+            (name.clone(), calculator)
+        })
+        .collect();
+    let mut conn_monitor = connections::ConnectionMonitor::new();
+    let mut engine = NetworkIntelligenceEngine::new();
+
+    println!(
+        "Learning traffic baseline for {} over {}s (Ctrl+C to abort)...",
+        interfaces.join(", "),
+        window.as_secs()
+    );
+    engine.learn_baseline(window);
+    let started = Instant::now();
+
+    while engine.is_learning() {
+        if shutdown::requested() {
+            anyhow::bail!("Baseline learning interrupted; nothing was saved");
+        }
+        // Learning ends on an observation, so unreadable interfaces would never finish it
+        if started.elapsed() > window + refresh_interval * 3 {
+            anyhow::bail!("Could not read interface statistics; no baseline was learned");
+        }
+
+        if let Err(e) = conn_monitor.update() {
+            eprintln!("Warning: Failed to read connections: {e}");
+        }
+        let (connection_count, unique_remote_ips) =
+            connection_metrics(conn_monitor.get_connections());
+
+        for interface in interfaces {
+            let Some(calculator) = calculators.get_mut(interface) else {
+                continue;
+            };
+            if let Ok(stats) = reader.read_stats(interface) {
+                calculator.add_sample(stats);
+            }
+            // The first sample has no rate yet
+            if calculator.sample_count() >= 2 {
+                let (speed_in, speed_out) = calculator.current_speed();
+                engine.observe_traffic(
                     interface,
-                    available_interfaces.join(", ")
+                    speed_in + speed_out,
+                    connection_count,
+                    unique_remote_ips,
                 );
             }
         }
 
-        return run_enhanced_terminal_mode(interfaces, reader, config, args.log_file);
+        let next_refresh = Instant::now() + refresh_interval;
+        while engine.is_learning() && !shutdown::requested() && Instant::now() < next_refresh {
+            std::thread::sleep((next_refresh - Instant::now()).min(Duration::from_millis(100)));
+        }
     }
 
-    // Load configuration
-    let mut config = config::Config::load()?;
+    let baseline = engine
+        .baseline()
+        .ok_or_else(|| anyhow::anyhow!("No baseline was learned"))?;
+    baseline.save()?;
 
-    // Override config with command line arguments
-    config.apply_args(&args);
+    println!(
+        "{:<16} {:>8} {:>14} {:>14} {:>10} {:>10} {:>10} {:>10}",
+        "Interface", "Samples", "Mean B/s", "Std B/s", "Conns", "Std", "Remote IPs", "Std"
+    );
+    for (interface, learned) in &baseline.interfaces {
+        println!(
+            "{:<16} {:>8} {:>14.0} {:>14.0} {:>10.1} {:>10.1} {:>10.1} {:>10.1}",
+            interface,
+            learned.samples,
+            learned.bytes_per_sec.mean,
+            learned.bytes_per_sec.std_dev,
+            learned.connections.mean,
+            learned.connections.std_dev,
+            learned.unique_remote_ips.mean,
+            learned.unique_remote_ips.std_dev
+        );
+    }
+    if let Some(path) = network_intelligence::Baseline::path() {
+        println!("Baseline saved to {}", path.display());
+    }
 
-    // Initialize platform-specific network reader
-    let reader = platform::create_reader()?;
+    Ok(())
+}
 
-    // Determine which interfaces to monitor
-    let interfaces = if args.devices.is_empty() {
-        if config.devices == "all" {
-            reader.list_devices()?
-        } else {
-            config
-                .devices
-                .split_whitespace()
-                .map(String::from)
+/// `--check`: sample once and evaluate the `--warn`/`--crit` thresholds
+fn run_check(args: &Args) -> Result<(check::CheckStatus, String)> {
+    args.validate()?;
+    enter_netns(args)?;
+    let warn = check::parse_thresholds(&args.warn)?;
+    let crit = check::parse_thresholds(&args.crit)?;
+
+    let config = load_config(args)?;
+    let reader = create_reader(args)?;
+    let interfaces = select_interfaces(args, &config, reader.as_ref())?;
+
+    let interval = std::time::Duration::from_millis(config.refresh_interval);
+    let report = snapshot::take(&interfaces, reader.as_ref(), interval)?;
+    Ok(check::evaluate(
+        &warn,
+        &crit,
+        &check::MetricValues::from_report(&report),
+    ))
+}
+
+/// Interfaces to monitor: the positional devices plus everything `--match` selects, or
+/// the config's `Devices` ("all" or a list) when neither is given
+fn select_interfaces(
+    args: &Args,
+    config: &config::Config,
+    reader: &dyn device::NetworkReader,
+) -> Result<Vec<String>> {
+    let available_interfaces = reader.list_devices()?;
+
+    let exclude = args
+        .interfaces_regex_exclude
+        .as_deref()
+        .map(device::InterfacePattern::regex)
+        .transpose()?;
+    let excluded = |name: &str| exclude.as_ref().is_some_and(|e| e.is_match(name));
+
+    let mut selectors = Vec::new();
+    if let Some(ref pattern) = args.interface_pattern {
+        selectors.push((
+            "--match",
+            pattern,
+            device::InterfacePattern::parse(pattern)?,
+        ));
+    }
+    if let Some(ref pattern) = args.interfaces_regex {
+        selectors.push((
+            "--interfaces-regex",
+            pattern,
+            device::InterfacePattern::regex(pattern)?,
+        ));
+    }
+
+    // Names typed on the command line were validated with the rest of the arguments, and
+    // names from list_devices come from the OS; only the config's list is checked here
+    let interfaces = if !selectors.is_empty() {
+        let mut interfaces = args.devices.clone();
+        for (flag, pattern, matcher) in &selectors {
+            let matched: Vec<&String> = available_interfaces
+                .iter()
+                .filter(|name| matcher.is_match(name) && !excluded(name))
+                .collect();
+            if matched.is_empty() {
+                anyhow::bail!(
+                    "{} '{}' matched no interfaces. Available interfaces: {}",
+                    flag,
+                    pattern,
+                    available_interfaces.join(", ")
+                );
+            }
+
+            for name in matched {
+                if !interfaces.contains(name) {
+                    interfaces.push(name.clone());
+                }
+            }
+        }
+        interfaces
+    } else if !args.devices.is_empty() {
+        args.devices.clone()
+    } else if config.devices == "all" {
+        let filter = config.interface_filter()?;
+        let kept: Vec<String> = available_interfaces
+            .iter()
+            .filter(|name| !excluded(name) && filter.exclusion(name, reader).is_none())
+            .cloned()
+            .collect();
+        if kept.is_empty() {
+            // A container may have nothing but a veth; better it than nothing
+            eprintln!(
+                "Warning: ExcludeLoopback, ExcludeVirtual and ExcludePatterns leave no interfaces; monitoring all of them (see --list --verbose)"
+            );
+            available_interfaces
+                .iter()
+                .filter(|name| !excluded(name))
+                .cloned()
                 .collect()
+        } else {
+            kept
         }
     } else {
-        args.devices
+        let named: Vec<String> = config
+            .devices
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        for name in &named {
+            validation::validate_interface_name(name)?;
+        }
+        named
     };
 
     if interfaces.is_empty() {
         anyhow::bail!("No network interfaces found");
     }
 
-    // Validate interface names for security
-    for interface in &interfaces {
-        validation::validate_interface_name(interface)?;
+    // A shared config may describe interfaces this host doesn't have
+    for name in config.unknown_interfaces(&available_interfaces) {
+        eprintln!("Warning: ignoring [Interfaces.{name}] in config, no such interface");
     }
 
     // Validate that provided interfaces exist
-    let available_interfaces = reader.list_devices()?;
     for interface in &interfaces {
         if !available_interfaces.contains(interface) {
             anyhow::bail!(
@@ -189,45 +515,35 @@ pub fn run(args: Args) -> Result<()> {
         }
     }
 
-    // Initialize display with comprehensive error handling and multiple fallback strategies
-    let tui_result = initialize_enhanced_tui();
-
-    match tui_result {
-        Ok(mut stdout) => {
-            println!("Starting SRE Network Forensics Dashboard...");
-            let result = dashboard::run_dashboard(interfaces, reader, config, args.log_file);
-
-            // Cleanup
-            let _ = disable_raw_mode();
-            let _ = execute!(stdout, LeaveAlternateScreen);
-            result
-        }
-        Err(e) => {
-            eprintln!("⚠️  TUI initialization failed: {e}");
-            eprintln!("🛠️  Attempting enhanced terminal mode with SRE forensics...");
-            run_enhanced_terminal_mode(interfaces, reader, config, args.log_file)
-        }
-    }
+    Ok(interfaces)
 }
 
-fn list_interfaces() -> Result<()> {
-    let reader = platform::create_reader()?;
+/// `--list`: the interfaces `Devices = "all"` monitors; with `--verbose`, every
+/// interface with the reason it is left out, to tune ExcludePatterns by
+fn list_interfaces(args: &Args) -> Result<()> {
+    let reader = create_reader(args)?;
+    let filter = load_config(args)?.interface_filter()?;
     let interfaces = reader.list_devices()?;
 
     for interface in interfaces {
-        println!("{interface}");
+        match filter.exclusion(&interface, reader.as_ref()) {
+            None if args.verbose => println!("{interface:<16} monitored"),
+            None => println!("{interface}"),
+            Some(reason) if args.verbose => println!("{interface:<16} excluded: {reason}"),
+            Some(_) => {}
+        }
     }
 
     Ok(())
 }
 
-fn test_interface_stats(devices: &[String]) -> Result<()> {
-    let reader = platform::create_reader()?;
+fn test_interface_stats(args: &Args) -> Result<()> {
+    let reader = create_reader(args)?;
 
-    let interfaces = if devices.is_empty() {
+    let interfaces = if args.devices.is_empty() {
         vec!["en0".to_string()] // Default to en0 for testing
     } else {
-        devices.to_vec()
+        args.devices.clone()
     };
 
     for interface in interfaces {
@@ -255,7 +571,44 @@ fn test_interface_stats(devices: &[String]) -> Result<()> {
     Ok(())
 }
 
-fn debug_dashboard_data() -> Result<()> {
+/// `--netns`: switch namespaces before any reader exists or collector thread starts,
+/// since setns(2) only moves the calling thread
+fn enter_netns(args: &Args) -> Result<()> {
+    if let Some(ref path) = args.netns {
+        platform::enter_network_namespace(&platform::netns_path(path))?;
+    }
+    Ok(())
+}
+
+/// The platform's counter reader, or one reading `--proc-net-dev` instead
+fn create_reader(args: &Args) -> Result<Box<dyn device::NetworkReader>> {
+    if let Some(ref target) = args.remote {
+        let host = remote::RemoteHost::connect(target)?;
+        return Ok(Box::new(remote::RemoteReader::new(std::sync::Arc::new(
+            host,
+        ))));
+    }
+    Ok(match args.proc_net_dev {
+        Some(ref path) => platform::create_reader_from(path)?,
+        None => platform::create_reader()?,
+    })
+}
+
+/// The config file with `args` applied and validated, its `UnitBase` made current
+fn load_config(args: &Args) -> Result<config::Config> {
+    let mut config = config::Config::load()?;
+    config.apply_args(args);
+    config.validate()?;
+    units::set_unit_base(config.get_unit_base());
+    Ok(config)
+}
+
+/// `--unit`, else the config file's `TrafficFormat`, for the printouts that don't load config
+fn selected_traffic_unit(args: &Args) -> Result<cli::TrafficUnit> {
+    Ok(load_config(args)?.get_traffic_unit())
+}
+
+fn debug_dashboard_data(unit: &cli::TrafficUnit) -> Result<()> {
     use connections::ConnectionMonitor;
     use processes::ProcessMonitor;
 
@@ -304,7 +657,11 @@ fn debug_dashboard_data() -> Result<()> {
             println!("     📊 RTT: {rtt:.1}ms");
         }
         if let Some(bandwidth) = conn.socket_info.bandwidth {
-            println!("     Bandwidth: {:.0} Mbps", bandwidth as f64 / 1_000_000.0);
+            // Socket estimates are bits/s
+            println!(
+                "     Bandwidth: {}",
+                units::format_rate(bandwidth / 8, unit)
+            );
         }
         if conn.socket_info.retrans > 0 || conn.socket_info.lost > 0 {
             println!(
@@ -349,13 +706,13 @@ fn debug_dashboard_data() -> Result<()> {
     println!("Found {} processes with network activity:", processes.len());
     for (i, proc) in processes.iter().enumerate() {
         println!(
-            "  {}. PID {} ({}): {} connections, {}/s sent, {}/s received",
+            "  {}. PID {} ({}): {} connections, {} sent, {} received",
             i + 1,
             proc.pid,
             proc.name,
             proc.connections,
-            format_debug_bytes(proc.bytes_sent),
-            format_debug_bytes(proc.bytes_received)
+            units::format_rate(proc.bytes_sent, unit),
+            units::format_rate(proc.bytes_received, unit)
         );
     }
 
@@ -368,6 +725,28 @@ fn debug_dashboard_data() -> Result<()> {
         );
     }
 
+    // Every socket, one per line: protocol, bind address, exposure, owner, risky service
+    let listening_sockets = conn_monitor.get_listening_sockets();
+    println!("\nListening Sockets ({}):", listening_sockets.len());
+    for socket in &listening_sockets {
+        let owner = match (&socket.process_name, socket.pid) {
+            (Some(name), Some(pid)) => format!("{name} (PID {pid})"),
+            (Some(name), None) => name.clone(),
+            (None, Some(pid)) => format!("PID {pid}"),
+            (None, None) => "unknown".to_string(),
+        };
+        let risk = socket
+            .risk()
+            .map(|service| format!(" ⚠️ {service}"))
+            .unwrap_or_default();
+        println!(
+            "  {:<5} {:<45} {:<8} {owner}{risk}",
+            socket.protocol.as_str(),
+            socket.local_addr,
+            socket.exposure.as_str()
+        );
+    }
+
     println!("\n🎯 Dashboard modules are working! You should see this data in the TUI.");
     println!("   Run 'netwatch' (without --debug-dashboard) to see the full dashboard.");
 
@@ -470,16 +849,6 @@ fn show_dashboard_comparison() -> Result<()> {
     Ok(())
 }
 
-fn format_debug_bytes(bytes: u64) -> String {
-    if bytes >= 1_000_000 {
-        format!("{:.1}M", bytes as f64 / 1_000_000.0)
-    } else if bytes >= 1_000 {
-        format!("{:.1}K", bytes as f64 / 1_000.0)
-    } else {
-        format!("{bytes}")
-    }
-}
-
 fn simulate_connections_panel(connections: &[crate::connections::NetworkConnection]) {
     println!("\n┌─ CONNECTION INTELLIGENCE ─────────────────────────────────────────────┐");
     println!("│ Proto │ Local          │ Remote               │ State │ RTT    │ BW   │ Process │");
@@ -630,7 +999,7 @@ fn simulate_host_intelligence(connections: &[crate::connections::NetworkConnecti
     println!("└────────────────────────────────────────────────────────────────────┘");
 }
 
-fn show_overview_data() -> Result<()> {
+fn show_overview_data(unit: &cli::TrafficUnit) -> Result<()> {
     use connections::ConnectionMonitor;
     use processes::ProcessMonitor;
 
@@ -681,7 +1050,10 @@ fn show_overview_data() -> Result<()> {
 
     println!("=== NETWORK INTELLIGENCE OVERVIEW ===");
     println!("📊 Traffic Summary:");
-    println!("  🌐 Total Bandwidth: {} Mbps", total_bandwidth / 1_000_000);
+    println!(
+        "  🌐 Total Bandwidth: {}",
+        units::format_rate(total_bandwidth / 8, unit)
+    );
     println!("  ⚡ Average RTT: {avg_rtt:.1}ms");
     println!();
     println!("🔗 Connection Intelligence:");
@@ -723,7 +1095,7 @@ fn show_overview_data() -> Result<()> {
             println!("   RTT: {rtt:.1}ms");
         }
         if let Some(bw) = conn.socket_info.bandwidth {
-            println!("   BW: {} Mbps", bw / 1_000_000);
+            println!("   BW: {}", units::format_rate(bw / 8, unit));
         }
         if let Some(process) = &conn.process_name {
             println!("   Process: {process}");
@@ -737,7 +1109,7 @@ fn show_overview_data() -> Result<()> {
     Ok(())
 }
 
-fn initialize_enhanced_tui() -> Result<std::io::Stdout> {
+fn initialize_enhanced_tui(use_mouse: bool) -> Result<std::io::Stdout> {
     use crossterm::terminal::*;
     use std::io;
 
@@ -748,7 +1120,15 @@ fn initialize_enhanced_tui() -> Result<std::io::Stdout> {
         Ok(_) => {
             let mut stdout = io::stdout();
             match execute!(stdout, EnterAlternateScreen) {
-                Ok(_) => return Ok(stdout),
+                Ok(_) => {
+                    // Released again by shutdown::restore_terminal
+                    if use_mouse {
+                        if let Err(e) = execute!(stdout, crossterm::event::EnableMouseCapture) {
+                            eprintln!("Warning: Mouse capture failed: {e}");
+                        }
+                    }
+                    return Ok(stdout);
+                }
                 Err(e) => {
                     let _ = disable_raw_mode();
                     eprintln!("⚠️  Alternate screen failed: {e}");
@@ -783,17 +1163,32 @@ fn initialize_enhanced_tui() -> Result<std::io::Stdout> {
     Err(anyhow::anyhow!("Failed all TUI initialization strategies"))
 }
 
+/// Optional bounds on an enhanced terminal mode run (`--iterations`, `--duration`)
+#[derive(Debug, Clone, Copy, Default)]
+struct TerminalRunLimit {
+    iterations: Option<u64>,
+    duration: Option<std::time::Duration>,
+}
+
+/// Refreshes the terminal renderers every `RefreshInterval` until Ctrl+C, SIGTERM or
+/// `limit` is reached, then prints a min/avg/max traffic summary for the session.
 fn run_enhanced_terminal_mode(
     interfaces: Vec<String>,
     reader: Box<dyn crate::device::NetworkReader>,
-    _config: crate::config::Config,
+    config: crate::config::Config,
     _log_file: Option<String>,
+    limit: TerminalRunLimit,
 ) -> Result<()> {
     use crate::stats::StatsCalculator;
     use connections::ConnectionMonitor;
     use processes::ProcessMonitor;
     use std::thread;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
+
+    let refresh_interval = Duration::from_millis(config.refresh_interval);
+    let unit = config.get_traffic_unit();
+    let started = Instant::now();
+    let deadline = limit.duration.map(|duration| started + duration);
 
     println!("🛡️  SRE NETWORK FORENSICS - Enhanced Terminal Mode 🛡️");
     println!("📊 Comprehensive network diagnostics in text format");
@@ -803,6 +1198,17 @@ fn run_enhanced_terminal_mode(
     let mut proc_monitor = ProcessMonitor::new();
     let mut safe_system_monitor = crate::safe_system::SafeSystemMonitor::new();
     let mut stats_calculators: HashMap<String, StatsCalculator> = HashMap::new();
+    let mut alert_logger = crate::logger::AlertLogger::new(config.alert_log.clone())?;
+    let mut influx = config
+        .influx_url
+        .as_deref()
+        .map(crate::influx::InfluxExporter::new)
+        .transpose()?;
+    let mut webhook = config
+        .webhook_url
+        .as_deref()
+        .map(crate::alerts::WebhookNotifier::new)
+        .transpose()?;
 
     // Initialize stats calculators for interfaces
     for interface in &interfaces {
@@ -812,7 +1218,12 @@ fn run_enhanced_terminal_mode(
         );
     }
 
-    for iteration in 1..=20 {
+    // Totals at the first pass, so the summary averages over this session only
+    let mut session_start: Option<SessionBaseline> = None;
+
+    let mut iteration = 0;
+    while !shutdown::requested() {
+        iteration += 1;
         // Clear screen for better display
         print!("\x1B[2J\x1B[1;1H"); // ANSI escape codes to clear screen and move cursor to top
 
@@ -823,70 +1234,246 @@ fn run_enhanced_terminal_mode(
             "=".repeat(80)
         );
 
-        // Update monitors
-        if let Err(e) = conn_monitor.update() {
-            println!("⚠️  Connection monitor error: {e}");
+        render_terminal_pass(
+            &interfaces,
+            reader.as_ref(),
+            &mut conn_monitor,
+            &mut proc_monitor,
+            &mut safe_system_monitor,
+            &mut stats_calculators,
+            &config,
+        );
+
+        if session_start.is_none() {
+            let totals = stats_calculators
+                .iter()
+                .map(|(name, calculator)| (name.clone(), calculator.total_bytes()))
+                .collect();
+            session_start = Some(SessionBaseline {
+                started: Instant::now(),
+                totals,
+            });
         }
 
-        if let Err(e) = proc_monitor.update() {
-            println!("⚠️  Process monitor error: {e}");
+        if let Some(ref mut influx) = influx {
+            influx.export(
+                &stats_calculators,
+                Some(&conn_monitor.get_connection_stats()),
+            );
+            if let Some(status) = influx.take_status() {
+                eprintln!("Warning: {status}");
+            }
         }
 
-        // Update interface stats
+        // Spike events go to --alert-log (or stderr) so cron/headless runs can pick them up
         for interface in &interfaces {
-            if let Ok(stats) = reader.read_stats(interface) {
-                if let Some(calculator) = stats_calculators.get_mut(interface) {
-                    calculator.add_sample(stats);
+            if let Some(calculator) = stats_calculators.get_mut(interface) {
+                let spike_multiplier = config.for_interface(interface).spike_multiplier;
+                if calculator.update_spike_state(spike_multiplier) {
+                    if let Err(e) = alert_logger.log_spike(interface, calculator, spike_multiplier)
+                    {
+                        eprintln!("Warning: Failed to write alert event: {e}");
+                    }
                 }
             }
         }
 
-        let connections = conn_monitor.get_connections();
-        let conn_stats = conn_monitor.get_connection_stats();
+        if let Some(ref mut webhook) = webhook {
+            let mut alerts = crate::alerts::evaluate(
+                &stats_calculators,
+                &conn_monitor.get_connection_stats(),
+                config.time_wait_threshold,
+                config.close_wait_threshold,
+            );
+            alerts.extend(crate::alerts::evaluate_connections(
+                &conn_monitor,
+                safe_system_monitor
+                    .socket_stats()
+                    .map(|sockets| sockets.orphan_tcp),
+            ));
+            webhook.notify(&alerts);
+            if let Some(status) = webhook.take_status() {
+                eprintln!("Warning: {status}");
+            }
+        }
 
-        // Get system stats using safe monitor
-        let safe_stats = safe_system_monitor.get_current_stats();
-        let system_info = safe_system_monitor.get_system_info();
+        if limit.iterations.is_some_and(|n| iteration >= n)
+            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            break;
+        }
 
-        // === SYSTEM INFORMATION ===
-        render_terminal_system_info_safe(system_info, &safe_stats);
+        println!("\n{}", "=".repeat(80));
+        println!("💡 This is the COMPREHENSIVE SRE data from the multi-panel dashboard!");
+        println!(
+            "⏱️  Updating every {}ms... (Ctrl+C to exit)",
+            config.refresh_interval
+        );
+        println!("{}", "=".repeat(80));
 
-        println!();
+        // Sleep in short slices so a signal or the --duration deadline ends the loop promptly
+        let mut next_refresh = Instant::now() + refresh_interval;
+        if let Some(deadline) = deadline {
+            next_refresh = next_refresh.min(deadline);
+        }
+        while !shutdown::requested() && Instant::now() < next_refresh {
+            thread::sleep((next_refresh - Instant::now()).min(Duration::from_millis(100)));
+        }
+    }
 
-        // === SYSTEM HEALTH ASSESSMENT ===
-        render_terminal_system_health(connections, &conn_stats, &stats_calculators, &interfaces);
+    print_terminal_session_summary(
+        &interfaces,
+        &stats_calculators,
+        session_start,
+        iteration,
+        started.elapsed(),
+        &unit,
+    );
 
-        println!();
+    Ok(())
+}
 
-        // === CONNECTION FORENSICS ===
-        render_terminal_connection_forensics(connections);
+/// Byte totals at the first terminal pass, so the summary averages over the session only
+struct SessionBaseline {
+    started: std::time::Instant,
+    totals: HashMap<String, (u64, u64)>,
+}
 
-        println!();
+/// Min/avg/max traffic per interface for an enhanced terminal mode run
+fn print_terminal_session_summary(
+    interfaces: &[String],
+    stats_calculators: &HashMap<String, crate::stats::StatsCalculator>,
+    session_start: Option<SessionBaseline>,
+    updates: u64,
+    elapsed: std::time::Duration,
+    unit: &cli::TrafficUnit,
+) {
+    let secs = elapsed.as_secs();
+    println!(
+        "\n{}\nSESSION SUMMARY - {updates} updates over {}h {:02}m {:02}s\n{}",
+        "=".repeat(80),
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        "=".repeat(80)
+    );
+    println!(
+        "{:<16} {:>12} {:>12} {:>12}   {:>12} {:>12} {:>12}",
+        "Interface", "Min In", "Avg In", "Max In", "Min Out", "Avg Out", "Max Out"
+    );
 
-        // === REAL-TIME DIAGNOSTICS ===
-        render_terminal_diagnostics(connections, &conn_stats);
+    let rate = |bytes_per_sec: u64| units::format_rate(bytes_per_sec, unit);
+    for interface in interfaces {
+        let Some(calculator) = stats_calculators.get(interface) else {
+            continue;
+        };
+        let (min_in, min_out) = calculator.min_speed();
+        let (max_in, max_out) = calculator.max_speed();
+
+        // Average over the whole session, not the rolling window
+        let (avg_in, avg_out) = session_start
+            .as_ref()
+            .and_then(|baseline| {
+                let (start_in, start_out) = baseline.totals.get(interface)?;
+                let (total_in, total_out) = calculator.total_bytes();
+                let secs = baseline.started.elapsed().as_secs_f64();
+                (secs > 0.0).then(|| {
+                    (
+                        (total_in.saturating_sub(*start_in) as f64 / secs) as u64,
+                        (total_out.saturating_sub(*start_out) as f64 / secs) as u64,
+                    )
+                })
+            })
+            .unwrap_or((0, 0));
 
-        println!();
+        println!(
+            "{:<16} {:>12} {:>12} {:>12}   {:>12} {:>12} {:>12}",
+            interface,
+            rate(min_in),
+            rate(avg_in),
+            rate(max_in),
+            rate(min_out),
+            rate(avg_out),
+            rate(max_out)
+        );
+    }
+}
 
-        // === PERFORMANCE METRICS ===
-        render_terminal_performance_metrics(connections, &stats_calculators, &interfaces);
+/// One collection pass: refresh every monitor, then print each report section
+fn render_terminal_pass(
+    interfaces: &[String],
+    reader: &dyn crate::device::NetworkReader,
+    conn_monitor: &mut connections::ConnectionMonitor,
+    proc_monitor: &mut processes::ProcessMonitor,
+    safe_system_monitor: &mut crate::safe_system::SafeSystemMonitor,
+    stats_calculators: &mut HashMap<String, crate::stats::StatsCalculator>,
+    config: &crate::config::Config,
+) {
+    let unit = &config.get_traffic_unit();
 
-        println!("\n{}", "=".repeat(80));
-        println!("💡 This is the COMPREHENSIVE SRE data from the multi-panel dashboard!");
-        println!("⏱️  Updating every 2 seconds... (Ctrl+C to exit)");
-        println!("{}", "=".repeat(80));
+    // Update monitors
+    if let Err(e) = conn_monitor.update() {
+        println!("⚠️  Connection monitor error: {e}");
+    }
 
-        thread::sleep(Duration::from_secs(2));
+    if let Err(e) = proc_monitor.update() {
+        println!("⚠️  Process monitor error: {e}");
     }
 
-    Ok(())
+    // Update interface stats
+    for interface in interfaces {
+        if let Ok(stats) = reader.read_stats(interface) {
+            if let Some(calculator) = stats_calculators.get_mut(interface) {
+                calculator.add_sample(stats);
+            }
+        }
+    }
+
+    let connections = conn_monitor.get_connections();
+    let conn_stats = conn_monitor.get_connection_stats();
+
+    // Get system stats using safe monitor
+    let safe_stats = safe_system_monitor.get_current_stats();
+    let system_info = safe_system_monitor.get_system_info();
+
+    // === SYSTEM INFORMATION ===
+    render_terminal_system_info_safe(system_info, &safe_stats);
+
+    println!();
+
+    // === SYSTEM HEALTH ASSESSMENT ===
+    render_terminal_system_health(
+        connections,
+        &conn_stats,
+        config.close_wait_threshold,
+        stats_calculators,
+        interfaces,
+        unit,
+    );
+
+    println!();
+
+    // === CONNECTION FORENSICS ===
+    render_terminal_connection_forensics(connections);
+
+    println!();
+
+    // === REAL-TIME DIAGNOSTICS ===
+    render_terminal_diagnostics(connections, &conn_stats);
+
+    println!();
+
+    // === PERFORMANCE METRICS ===
+    render_terminal_performance_metrics(connections, stats_calculators, interfaces, unit);
 }
 
 fn render_terminal_system_health(
     connections: &[crate::connections::NetworkConnection],
     conn_stats: &crate::connections::ConnectionStats,
+    close_wait_threshold: u32,
     stats_calculators: &HashMap<String, crate::stats::StatsCalculator>,
     interfaces: &[String],
+    unit: &cli::TrafficUnit,
 ) {
     println!("🩺 SYSTEM HEALTH ASSESSMENT");
     println!("{}", "-".repeat(50));
@@ -916,7 +1503,7 @@ fn render_terminal_system_health(
         critical_issues.push("🚨 MASSIVE RETRANSMISSIONS");
         system_status = "🔴 CRITICAL";
     } else if total_retrans > 25 {
-        warnings.push("⚠️  HIGH RETRANS RATE");
+        warnings.push("⚠️  HIGH RETRANS RATE".to_string());
         system_status = "🟡 WARNING";
     }
 
@@ -924,14 +1511,21 @@ fn render_terminal_system_health(
         critical_issues.push("🚨 SEVERE LATENCY");
         system_status = "🔴 CRITICAL";
     } else if avg_rtt > 500.0 {
-        warnings.push("⚠️  HIGH LATENCY");
+        warnings.push("⚠️  HIGH LATENCY".to_string());
         if system_status == "🟢 HEALTHY" {
             system_status = "🟡 WARNING";
         }
     }
 
     if conn_stats.total > 1000 {
-        warnings.push("⚠️  HIGH CONNECTION COUNT");
+        warnings.push("⚠️  HIGH CONNECTION COUNT".to_string());
+        if system_status == "🟢 HEALTHY" {
+            system_status = "🟡 WARNING";
+        }
+    }
+
+    for pressure in conn_stats.pressure_warnings(close_wait_threshold) {
+        warnings.push(format!("⚠️  {pressure}"));
         if system_status == "🟢 HEALTHY" {
             system_status = "🟡 WARNING";
         }
@@ -950,9 +1544,9 @@ fn render_terminal_system_health(
 
     println!("🌟 System Status: {system_status}");
     println!(
-        "📊 Network Traffic: ↓{}/s ↑{}/s",
-        format_bytes(total_in),
-        format_bytes(total_out)
+        "📊 Network Traffic: ↓{} ↑{}",
+        units::format_rate(total_in, unit),
+        units::format_rate(total_out, unit)
     );
     println!(
         "🔗 Connections: {} total, {} active, {} listening",
@@ -1110,6 +1704,7 @@ fn render_terminal_performance_metrics(
     connections: &[crate::connections::NetworkConnection],
     stats_calculators: &HashMap<String, crate::stats::StatsCalculator>,
     interfaces: &[String],
+    unit: &cli::TrafficUnit,
 ) {
     println!("📈 PERFORMANCE METRICS");
     println!("{}", "-".repeat(50));
@@ -1158,12 +1753,15 @@ fn render_terminal_performance_metrics(
 
     println!("⚡ Performance Summary:");
     println!("  Avg RTT: {avg_rtt:.0}ms");
-    println!("  Bandwidth: {} Mbps", total_bandwidth / 1_000_000);
+    println!(
+        "  Bandwidth: {}",
+        units::format_rate(total_bandwidth / 8, unit)
+    );
     println!("  Retrans Rate: {retrans_rate:.2}%");
     println!(
-        "  Interface Traffic: ↓{}/s ↑{}/s",
-        format_bytes(total_in),
-        format_bytes(total_out)
+        "  Interface Traffic: ↓{} ↑{}",
+        units::format_rate(total_in, unit),
+        units::format_rate(total_out, unit)
     );
 
     // Bottleneck detection
@@ -1253,7 +1851,7 @@ fn render_terminal_system_info_safe(
         );
         println!(
             "💾 Memory: {} | Uptime: {}",
-            crate::safe_system::SafeSystemMonitor::format_bytes(info.total_memory),
+            units::format_bytes(info.total_memory),
             crate::safe_system::SafeSystemMonitor::format_uptime(info.uptime)
         );
     } else {
@@ -1295,8 +1893,8 @@ fn render_terminal_system_info_safe(
         "   {} Memory: {:.1}% ({} used / {} available)",
         mem_status,
         safe_stats.memory_usage_percent,
-        crate::safe_system::SafeSystemMonitor::format_bytes(safe_stats.memory_used),
-        crate::safe_system::SafeSystemMonitor::format_bytes(safe_stats.memory_available)
+        units::format_bytes(safe_stats.memory_used),
+        units::format_bytes(safe_stats.memory_available)
     );
 
     // Top processes preview
@@ -1333,8 +1931,8 @@ fn render_terminal_system_info_safe(
                 disk_status,
                 mount,
                 usage.usage_percent,
-                crate::safe_system::SafeSystemMonitor::format_bytes(usage.used),
-                crate::safe_system::SafeSystemMonitor::format_bytes(usage.total)
+                units::format_bytes(usage.used),
+                units::format_bytes(usage.total)
             );
         }
     }
@@ -1343,24 +1941,3 @@ fn render_terminal_system_info_safe(
 fn run_terminal_mode() {
     println!("⚠️  Deprecated: This function is replaced by run_enhanced_terminal_mode");
 }
-
-fn format_bytes(bytes: u64) -> String {
-    if bytes >= 1_000_000_000 {
-        // Note: Precision loss acceptable for display formatting
-        #[allow(clippy::cast_precision_loss)]
-        let gb = bytes as f64 / 1_000_000_000.0;
-        format!("{gb:.1}GB")
-    } else if bytes >= 1_000_000 {
-        // Note: Precision loss acceptable for display formatting
-        #[allow(clippy::cast_precision_loss)]
-        let mb = bytes as f64 / 1_000_000.0;
-        format!("{mb:.1}MB")
-    } else if bytes >= 1_000 {
-        // Note: Precision loss acceptable for display formatting
-        #[allow(clippy::cast_precision_loss)]
-        let kb = bytes as f64 / 1_000.0;
-        format!("{kb:.1}KB")
-    } else {
-        format!("{bytes}B")
-    }
-}
@@ -1,45 +1,113 @@
 use crate::stats::StatsCalculator;
 use crate::validation;
 use chrono::Local;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+
+/// The traffic log file, written as is or through gzip (`--log-compress`)
+enum LogWriter {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "log-compress")]
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "log-compress")]
+            Self::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            #[cfg(feature = "log-compress")]
+            Self::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
 
 pub struct TrafficLogger {
-    file: Option<std::fs::File>,
+    file: Option<LogWriter>,
     use_stdout: bool,
 }
 
 impl TrafficLogger {
-    pub fn new(path: Option<String>) -> anyhow::Result<Self> {
-        let (file, use_stdout) = if let Some(path) = path {
+    /// Log to `path`, or stdout for "-". With `compress` the file gets a `.gz` suffix
+    /// (`traffic.log` becomes `traffic.log.gz`) and is written gzip-compressed; stdout is
+    /// never compressed. Gzip cannot append to a finished stream, so each run adds a new
+    /// gzip member to an existing file. Such multi-member files are valid gzip, and
+    /// `zcat` or `GzDecoder`-style readers return all runs' rows in order.
+    pub fn new(path: Option<String>, compress: bool) -> anyhow::Result<Self> {
+        let (file, use_stdout, is_new) = if let Some(path) = path {
             if path == "-" {
-                (None, true) // stdout logging
+                (None, true, true) // stdout logging
             } else {
                 // Validate log file path for security
                 validation::validate_file_path(&path, Some("log"))?;
-                let f = OpenOptions::new().create(true).append(true).open(path)?;
-                (Some(f), false)
+                let (writer, is_new) = Self::open(&path, compress)?;
+                (Some(writer), false, is_new)
             }
         } else {
-            (None, false)
+            (None, false, false)
         };
 
         let mut logger = Self { file, use_stdout };
 
         // Write header if file is new or empty
-        if let Some(ref mut f) = logger.file {
-            // Check if file is empty (new)
-            let metadata = f.metadata()?;
-            if metadata.len() == 0 {
-                logger.write_header()?;
-            }
-        } else if logger.use_stdout {
+        if is_new {
             logger.write_header()?;
         }
 
         Ok(logger)
     }
 
+    /// Open the log for appending, and whether it is new or empty
+    fn open(path: &str, compress: bool) -> anyhow::Result<(LogWriter, bool)> {
+        #[cfg(not(feature = "log-compress"))]
+        if compress {
+            anyhow::bail!("--log-compress requires netwatch built with `--features log-compress`");
+        }
+
+        let path = if compress {
+            format!("{path}.gz")
+        } else {
+            path.to_string()
+        };
+        let f = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_new = f.metadata()?.len() == 0;
+        let writer = BufWriter::new(f);
+
+        #[cfg(feature = "log-compress")]
+        if compress {
+            let encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+            return Ok((LogWriter::Gzip(encoder), is_new));
+        }
+        Ok((LogWriter::Plain(writer), is_new))
+    }
+
+    /// Write out everything buffered and sync the file to disk. A compressed log's gzip
+    /// stream is finished here, so call this before exiting: without it the file ends in a
+    /// truncated member.
+    pub fn flush_and_sync(self) -> anyhow::Result<()> {
+        match self.file {
+            Some(LogWriter::Plain(writer)) => {
+                let file = writer.into_inner().map_err(|e| e.into_error())?;
+                file.sync_all()?;
+            }
+            #[cfg(feature = "log-compress")]
+            Some(LogWriter::Gzip(encoder)) => {
+                let writer = encoder.finish()?;
+                let file = writer.into_inner().map_err(|e| e.into_error())?;
+                file.sync_all()?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
     fn write_header(&mut self) -> anyhow::Result<()> {
         let header = "Date Time DeviceName DataInTotal DataOutTotal DataInPerSecond DataOutPerSecond DataInAverage DataOutAverage DataInMin DataOutMin DataInMax DataOutMax TimeSeconds TimeMicroSeconds\n";
 
@@ -86,14 +154,175 @@ impl TrafficLogger {
         );
 
         match (&mut self.file, self.use_stdout) {
-            (Some(f), _) => {
+            (Some(LogWriter::Plain(f)), _) => {
                 f.write_all(log_line.as_bytes())?;
                 f.flush()?;
             }
+            // Flushing a deflate stream after every row would cost most of the
+            // compression, so rows reach the file in compressed chunks
+            #[cfg(feature = "log-compress")]
+            (Some(f @ LogWriter::Gzip(_)), _) => f.write_all(log_line.as_bytes())?,
             (None, true) => print!("{log_line}"),
             _ => {} // No output
         }
 
         Ok(())
     }
+
+    /// Record something that happened to `device` (such as an address change) as a
+    /// timestamped `#` line, which column-oriented readers of the log skip as a comment
+    pub fn log_event(&mut self, device: &str, message: &str) -> anyhow::Result<()> {
+        validation::validate_interface_name(device)?;
+
+        let now = Local::now();
+        let line = format!(
+            "# {} {} {message}\n",
+            now.format("%Y-%m-%d"),
+            now.format("%H:%M:%S")
+        );
+        match (&mut self.file, self.use_stdout) {
+            (Some(f), _) => {
+                f.write_all(line.as_bytes())?;
+                f.flush()?;
+            }
+            (None, true) => print!("{line}"),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Writes alert events as JSON lines to a file, or stderr when no file is given
+pub struct AlertLogger {
+    file: Option<std::fs::File>,
+}
+
+impl AlertLogger {
+    pub fn new(path: Option<String>) -> anyhow::Result<Self> {
+        let file = match path {
+            Some(path) => {
+                validation::validate_file_path(&path, None)?;
+                Some(OpenOptions::new().create(true).append(true).open(path)?)
+            }
+            None => None,
+        };
+
+        Ok(Self { file })
+    }
+
+    pub fn log_spike(
+        &mut self,
+        device: &str,
+        stats: &StatsCalculator,
+        multiplier: f64,
+    ) -> anyhow::Result<()> {
+        let (current_in, current_out) = stats.current_speed();
+        let (avg_in, avg_out) = stats.average_speed();
+
+        let event = format!(
+            "{{\"timestamp\":\"{}\",\"event\":\"spike\",\"device\":\"{}\",\"multiplier\":{},\"current_in\":{},\"current_out\":{},\"average_in\":{},\"average_out\":{}}}\n",
+            Local::now().to_rfc3339(),
+            json_escape(device),
+            multiplier,
+            current_in,
+            current_out,
+            avg_in,
+            avg_out
+        );
+
+        match &mut self.file {
+            Some(f) => {
+                f.write_all(event.as_bytes())?;
+                f.flush()?;
+            }
+            None => eprint!("{event}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Panel navigation and key event trace for `--debug-log`. Without a path nothing is
+/// opened or written, so the input loop pays only for the `None` check.
+#[derive(Default)]
+pub struct DebugLogger {
+    file: Option<std::fs::File>,
+}
+
+impl DebugLogger {
+    pub fn new(path: Option<&str>) -> anyhow::Result<Self> {
+        let file = match path {
+            Some(path) => {
+                validation::validate_file_path(path, None)?;
+                let mut options = OpenOptions::new();
+                options.create(true).append(true);
+                // Key events are logged, so keep the file private to the user
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+                Some(options.open(path)?)
+            }
+            None => None,
+        };
+
+        Ok(Self { file })
+    }
+
+    /// Append one timestamped line; write errors are ignored so tracing never
+    /// interrupts the dashboard
+    pub fn log(&mut self, message: std::fmt::Arguments<'_>) {
+        if let Some(f) = &mut self.file {
+            let _ = writeln!(f, "{} {message}", Local::now().format("%H:%M:%S%.3f"));
+        }
+    }
+}
+
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, feature = "log-compress"))]
+mod tests {
+    use super::*;
+    use crate::device::NetworkStats;
+    use std::io::Read;
+    use std::time::Duration;
+
+    #[test]
+    fn test_compressed_log_reads_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traffic.log");
+        let path = path.to_str().unwrap().to_string();
+        let mut stats = StatsCalculator::new(Duration::from_secs(10));
+        stats.add_sample(NetworkStats::new());
+
+        let mut logger = TrafficLogger::new(Some(path.clone()), true).unwrap();
+        for _ in 0..1000 {
+            logger.log_traffic("eth0", &stats).unwrap();
+        }
+        logger.flush_and_sync().unwrap();
+        assert!(!dir.path().join("traffic.log").exists());
+
+        // A second run appends another gzip member, without a second header
+        let mut logger = TrafficLogger::new(Some(path.clone()), true).unwrap();
+        logger.log_traffic("eth0", &stats).unwrap();
+        logger.flush_and_sync().unwrap();
+
+        let mut content = String::new();
+        flate2::read::MultiGzDecoder::new(File::open(format!("{path}.gz")).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 1 + 1001);
+        assert!(lines[0].starts_with("Date Time DeviceName"));
+        assert!(lines[1..].iter().all(|line| line.contains(" eth0 ")));
+    }
 }
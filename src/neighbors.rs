@@ -0,0 +1,330 @@
+//! IPv4 neighbor (ARP) cache for the Neighbors panel: which MAC answers for which
+//! address on the local segments
+//!
+//! Linux entries come from `/proc/net/arp`, which only says whether an entry is
+//! complete; `ip -4 neigh` supplies the finer reachability state when it is installed.
+//! macOS entries come from `arp -an`.
+
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// `/proc/net/arp` flag: the hardware address is known
+pub const ATF_COM: u8 = 0x02;
+/// `/proc/net/arp` flag: static entry that never expires
+pub const ATF_PERM: u8 = 0x04;
+
+/// A 48-bit hardware address
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 6]
+    }
+
+    /// Randomized (phones, containers) or otherwise locally assigned, so not in the
+    /// IEEE registry
+    pub fn is_locally_administered(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+
+    /// Manufacturer registered for the first three bytes, from [`OUI_VENDORS`]
+    pub fn vendor_prefix(&self) -> Option<&'static str> {
+        let prefix = [self.0[0], self.0[1], self.0[2]];
+        OUI_VENDORS
+            .binary_search_by(|(oui, _)| oui.cmp(&prefix))
+            .ok()
+            .map(|index| OUI_VENDORS[index].1)
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = String;
+
+    /// Colon-separated hex, one or two digits per byte (`arp -an` drops leading zeros)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 6];
+        let mut parts = s.split(':');
+        for byte in &mut bytes {
+            let part = parts
+                .next()
+                .filter(|part| (1..=2).contains(&part.len()))
+                .ok_or_else(|| format!("Invalid MAC address '{s}'"))?;
+            *byte =
+                u8::from_str_radix(part, 16).map_err(|_| format!("Invalid MAC address '{s}'"))?;
+        }
+        if parts.next().is_some() {
+            return Err(format!("Invalid MAC address '{s}'"));
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// Whether a neighbor is answering, as the kernel last saw it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeighborState {
+    /// Recently confirmed, or static
+    Reachable,
+    /// Known but not confirmed lately; checked again on next use
+    Stale,
+    /// Resolution failed or is still pending
+    Failed,
+}
+
+impl NeighborState {
+    /// From an `ip neigh` NUD state such as `REACHABLE` or `STALE`
+    pub fn from_nud(state: &str) -> Option<Self> {
+        match state {
+            "REACHABLE" | "PERMANENT" | "NOARP" => Some(Self::Reachable),
+            "STALE" | "DELAY" | "PROBE" => Some(Self::Stale),
+            "FAILED" | "INCOMPLETE" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Reachable => "Reachable",
+            Self::Stale => "Stale",
+            Self::Failed => "Failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArpEntry {
+    pub ip: Ipv4Addr,
+    /// ARP hardware type, 1 for Ethernet
+    pub hw_type: u16,
+    /// `ATF_*` flags
+    pub flags: u8,
+    pub mac: MacAddr,
+    pub interface: String,
+    pub state: NeighborState,
+}
+
+/// Entries of `/proc/net/arp`, state from the flags alone
+pub fn parse_proc_net_arp(content: &str) -> Vec<ArpEntry> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                return None;
+            }
+            let flags = u8::from_str_radix(fields[2].trim_start_matches("0x"), 16).ok()?;
+            Some(ArpEntry {
+                ip: fields[0].parse().ok()?,
+                hw_type: u16::from_str_radix(fields[1].trim_start_matches("0x"), 16).ok()?,
+                flags,
+                mac: fields[3].parse().ok()?,
+                interface: fields[5].to_string(),
+                state: if flags & ATF_COM != 0 {
+                    NeighborState::Reachable
+                } else {
+                    NeighborState::Failed
+                },
+            })
+        })
+        .collect()
+}
+
+/// Take each entry's state from `ip -4 neigh show` output, where one is listed
+pub fn apply_ip_neigh_states(entries: &mut [ArpEntry], ip_neigh: &str) {
+    for line in ip_neigh.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (Some(ip), Some(state)) = (fields.first(), fields.last()) else {
+            continue;
+        };
+        let (Ok(ip), Some(state)) = (ip.parse::<Ipv4Addr>(), NeighborState::from_nud(state)) else {
+            continue;
+        };
+        let interface = fields
+            .windows(2)
+            .find(|pair| pair[0] == "dev")
+            .map(|pair| pair[1]);
+        for entry in entries.iter_mut() {
+            if entry.ip == ip && interface.map_or(true, |dev| dev == entry.interface) {
+                entry.state = state;
+            }
+        }
+    }
+}
+
+/// Entries of macOS `arp -an`, e.g.
+/// `? (192.168.1.1) at 0:11:22:33:44:55 on en0 ifscope [ethernet]`
+pub fn parse_arp_an(output: &str) -> Vec<ArpEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let ip = fields
+                .get(1)?
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .parse()
+                .ok()?;
+            let after = |word: &str| {
+                fields
+                    .windows(2)
+                    .find(|pair| pair[0] == word)
+                    .map(|pair| pair[1])
+            };
+            let interface = after("on")?.to_string();
+            // "(incomplete)" while resolution is pending or has failed
+            let mac = after("at").and_then(|mac| mac.parse::<MacAddr>().ok());
+            let permanent = fields.contains(&"permanent");
+
+            Some(ArpEntry {
+                ip,
+                hw_type: 1,
+                flags: match (mac.is_some(), permanent) {
+                    (true, true) => ATF_COM | ATF_PERM,
+                    (true, false) => ATF_COM,
+                    (false, _) => 0,
+                },
+                mac: mac.unwrap_or_default(),
+                interface,
+                state: if mac.is_some() {
+                    NeighborState::Reachable
+                } else {
+                    NeighborState::Failed
+                },
+            })
+        })
+        .collect()
+}
+
+/// A small built-in subset of the IEEE MA-L registry: virtualization, common network
+/// gear and single-board computers, sorted by prefix for binary search
+pub static OUI_VENDORS: &[([u8; 3], &str)] = &[
+    ([0x00, 0x00, 0x0c], "Cisco"),
+    ([0x00, 0x02, 0xb3], "Intel"),
+    ([0x00, 0x03, 0x93], "Apple"),
+    ([0x00, 0x04, 0x4b], "NVIDIA"),
+    ([0x00, 0x05, 0x69], "VMware"),
+    ([0x00, 0x05, 0x85], "Juniper Networks"),
+    ([0x00, 0x09, 0x0f], "Fortinet"),
+    ([0x00, 0x09, 0x5b], "Netgear"),
+    ([0x00, 0x0a, 0x95], "Apple"),
+    ([0x00, 0x0b, 0x86], "Aruba Networks"),
+    ([0x00, 0x0c, 0x29], "VMware"),
+    ([0x00, 0x0c, 0x42], "MikroTik"),
+    ([0x00, 0x0d, 0xb9], "PC Engines"),
+    ([0x00, 0x10, 0x18], "Broadcom"),
+    ([0x00, 0x10, 0xdb], "Juniper Networks"),
+    ([0x00, 0x11, 0x32], "Synology"),
+    ([0x00, 0x14, 0x6c], "Netgear"),
+    ([0x00, 0x15, 0x17], "Intel"),
+    ([0x00, 0x15, 0x5d], "Microsoft Hyper-V"),
+    ([0x00, 0x15, 0x6d], "Ubiquiti"),
+    ([0x00, 0x16, 0x3e], "Xen"),
+    ([0x00, 0x17, 0x88], "Philips Lighting"),
+    ([0x00, 0x17, 0xf2], "Apple"),
+    ([0x00, 0x18, 0x0a], "Cisco Meraki"),
+    ([0x00, 0x1a, 0x11], "Google"),
+    ([0x00, 0x1b, 0x17], "Palo Alto Networks"),
+    ([0x00, 0x1b, 0x21], "Intel"),
+    ([0x00, 0x1b, 0x63], "Apple"),
+    ([0x00, 0x1c, 0x14], "VMware"),
+    ([0x00, 0x1c, 0x73], "Arista Networks"),
+    ([0x00, 0x1d, 0xaa], "DrayTek"),
+    ([0x00, 0x1e, 0x67], "Intel"),
+    ([0x00, 0x1e, 0xc2], "Apple"),
+    ([0x00, 0x25, 0x00], "Apple"),
+    ([0x00, 0x25, 0x90], "Super Micro"),
+    ([0x00, 0x26, 0xbb], "Apple"),
+    ([0x00, 0x50, 0x56], "VMware"),
+    ([0x00, 0x50, 0xf2], "Microsoft"),
+    ([0x00, 0xe0, 0x4c], "Realtek"),
+    ([0x04, 0x18, 0xd6], "Ubiquiti"),
+    ([0x08, 0x00, 0x27], "VirtualBox"),
+    ([0x18, 0xb4, 0x30], "Nest Labs"),
+    ([0x24, 0xa4, 0x3c], "Ubiquiti"),
+    ([0x3c, 0x22, 0xfb], "Apple"),
+    ([0x3c, 0x5a, 0xb4], "Google"),
+    ([0x3c, 0xd9, 0x2b], "Hewlett Packard"),
+    ([0x3c, 0xfd, 0xfe], "Intel"),
+    ([0x44, 0x4c, 0xa8], "Arista Networks"),
+    ([0x4c, 0x5e, 0x0c], "MikroTik"),
+    ([0x50, 0xc7, 0xbf], "TP-Link"),
+    ([0x52, 0x54, 0x00], "QEMU/KVM"),
+    ([0x7c, 0xd1, 0xc3], "Apple"),
+    ([0xa0, 0x36, 0x9f], "Intel"),
+    ([0xa4, 0x83, 0xe7], "Apple"),
+    ([0xac, 0x1f, 0x6b], "Super Micro"),
+    ([0xb4, 0xfb, 0xe4], "Ubiquiti"),
+    ([0xb8, 0x27, 0xeb], "Raspberry Pi"),
+    ([0xd4, 0xca, 0x6d], "MikroTik"),
+    ([0xd8, 0x3a, 0xdd], "Raspberry Pi"),
+    ([0xdc, 0xa6, 0x32], "Raspberry Pi"),
+    ([0xe4, 0x5f, 0x01], "Raspberry Pi"),
+    ([0xf0, 0x18, 0x98], "Apple"),
+    ([0xf4, 0xf5, 0xd8], "Google"),
+    ([0xfc, 0xec, 0xda], "Ubiquiti"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbor_tables_parse_on_linux_and_macos() {
+        let proc_net_arp = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         b8:27:eb:12:34:56     *        eth0
+192.168.1.77     0x1         0x0         00:00:00:00:00:00     *        eth0
+10.8.0.2         0x1         0x6         52:54:00:ab:cd:ef     *        br0
+";
+        let mut entries = parse_proc_net_arp(proc_net_arp);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].ip, Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(entries[0].hw_type, 1);
+        assert_eq!(entries[0].mac.to_string(), "b8:27:eb:12:34:56");
+        assert_eq!(entries[0].mac.vendor_prefix(), Some("Raspberry Pi"));
+        assert_eq!(entries[1].state, NeighborState::Failed);
+        assert!(entries[1].mac.is_zero());
+        assert_eq!(entries[2].flags, ATF_COM | ATF_PERM);
+        assert_eq!(entries[2].mac.vendor_prefix(), Some("QEMU/KVM"));
+
+        apply_ip_neigh_states(
+            &mut entries,
+            "192.168.1.1 dev eth0 lladdr b8:27:eb:12:34:56 STALE\n\
+             192.168.1.77 dev eth0 FAILED\n\
+             10.8.0.2 dev br0 lladdr 52:54:00:ab:cd:ef PERMANENT\n",
+        );
+        assert_eq!(entries[0].state, NeighborState::Stale);
+        assert_eq!(entries[2].state, NeighborState::Reachable);
+
+        let arp_an = "\
+? (192.168.1.1) at 0:11:32:a:b:c on en0 ifscope [ethernet]
+? (192.168.1.9) at (incomplete) on en0 ifscope [ethernet]
+? (224.0.0.251) at 1:0:5e:0:0:fb on en0 ifscope permanent [ethernet]
+";
+        let entries = parse_arp_an(arp_an);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].mac.to_string(), "00:11:32:0a:0b:0c");
+        assert_eq!(entries[0].mac.vendor_prefix(), Some("Synology"));
+        assert_eq!(entries[0].interface, "en0");
+        assert_eq!(entries[1].state, NeighborState::Failed);
+        assert_eq!(entries[2].flags, ATF_COM | ATF_PERM);
+        assert!(entries[2].mac.vendor_prefix().is_none());
+
+        assert!("02:42:ac:11:00:02"
+            .parse::<MacAddr>()
+            .unwrap()
+            .is_locally_administered());
+        assert!("00:11:22:33:44".parse::<MacAddr>().is_err());
+        assert!("00:11:22:33:44:55:66".parse::<MacAddr>().is_err());
+        assert!(OUI_VENDORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
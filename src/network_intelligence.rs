@@ -1,8 +1,27 @@
+use crate::cli::{DataUnit, TrafficUnit};
+use crate::connections::{ConnectionState, NetworkConnection, Protocol};
+use crate::units::{format_rate, format_volume};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
+/// Bytes/s treated as high bandwidth when no baseline has been learned
+const HIGH_BANDWIDTH_BPS: u64 = 10_000_000;
+/// Connection count treated as a flood when no baseline has been learned
+const HIGH_CONNECTION_COUNT: usize = 1000;
+/// Anomalies kept for `get_recent_anomalies`
+const MAX_ANOMALIES: usize = 1000;
+/// Throughput, in bytes/s, a connection must reach before a jump counts as a burst
+const BURST_MIN_RATE: f64 = 256.0 * 1024.0;
+/// Send plus receive queue, in bytes, a connection must reach before growth counts as a burst
+const BURST_MIN_QUEUED: u64 = 64 * 1024;
+/// Default jump factor between snapshots that counts as a burst (`BurstMultiplier`)
+pub const DEFAULT_BURST_MULTIPLIER: f64 = 10.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeoIpInfo {
     pub country: String,
@@ -12,7 +31,9 @@ pub struct GeoIpInfo {
     pub is_internal: bool,
     pub is_suspicious: bool,
     pub threat_level: ThreatLevel,
+    /// Network owner from the `--geoip-db` ASN database, "Unknown" without one
     pub organization: String,
+    /// Autonomous system number, 0 when unknown
     pub asn: u32,
 }
 
@@ -72,16 +93,42 @@ pub enum ThreatIndicator {
     },
 }
 
+/// A remote address probing more local ports than [`PortScanThresholds`] allow
 #[derive(Debug, Clone)]
 pub struct PortScanDetection {
     pub scanner_ip: IpAddr,
-    pub ports_scanned: HashSet<u16>,
-    pub scan_start_time: SystemTime,
-    pub scan_duration: Duration,
+    pub ports_scanned: BTreeSet<u16>, // Local ports probed within the window
+    pub first_seen: SystemTime,
+    pub last_seen: SystemTime,
     pub scan_rate: f64,  // ports per second
     pub confidence: f64, // 0.0 to 1.0
 }
 
+impl PortScanDetection {
+    pub fn scan_duration(&self) -> Duration {
+        self.last_seen
+            .duration_since(self.first_seen)
+            .unwrap_or_default()
+    }
+}
+
+/// A remote address touching more than `ports` distinct local ports within `window`
+/// is reported as a port scan (`PortScanPorts`, `PortScanWindow`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortScanThresholds {
+    pub ports: usize,
+    pub window: Duration,
+}
+
+impl Default for PortScanThresholds {
+    fn default() -> Self {
+        Self {
+            ports: 15,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NetworkAnomaly {
     pub anomaly_type: AnomalyType,
@@ -119,21 +166,174 @@ pub struct NetworkIntelligenceEngine {
     connection_history: VecDeque<ConnectionIntelligence>,
     geo_cache: HashMap<IpAddr, GeoIpInfo>,
     port_scan_detectors: HashMap<IpAddr, PortScanDetection>,
+    port_scan_thresholds: PortScanThresholds,
+    // Per remote IP, (when, local port) of each probe seen within the scan window
+    port_probes: HashMap<IpAddr, VecDeque<(SystemTime, u16)>>,
+    // Inbound TCP sockets in the last connection snapshot, with how many snapshots
+    // each has been in
+    inbound_sockets: HashMap<(SocketAddr, SocketAddr), u32>,
+    // Byte counters and queues of every socket in the last connection snapshot
+    connection_samples: HashMap<(SocketAddr, SocketAddr), ConnectionSample>,
+    burst_multiplier: f64,
     anomalies: VecDeque<NetworkAnomaly>,
-    #[allow(dead_code)]
-    traffic_baselines: HashMap<String, TrafficBaseline>,
+    baseline: Option<Baseline>,
+    baseline_sigma: f64,
+    learning: Option<BaselineLearner>,
+    // (interface, metric) pairs currently out of range, so each excursion is reported once
+    active_anomalies: HashSet<(String, &'static str)>,
     known_services: HashMap<u16, String>,
     suspicious_ips: HashSet<IpAddr>,
     internal_networks: Vec<(IpAddr, u8)>, // CIDR notation
+    // Offline MaxMind ASN database from --geoip-db
+    asn_database: Option<maxminddb::Reader<Vec<u8>>>,
+}
+
+/// One connection's counters at a snapshot, for burst detection
+#[derive(Debug, Clone, Copy)]
+struct ConnectionSample {
+    at: SystemTime,
+    bytes: u64,
+    // Bytes/s since the snapshot before; None on the first sighting or without counters
+    rate: Option<f64>,
+    queued: u64,
+}
+
+/// Mean and standard deviation of one metric over the learning window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricBaseline {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl MetricBaseline {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+
+    /// Standard deviations between `value` and the mean. A flat metric uses 10% of its
+    /// mean (at least 1) as the spread, so any change isn't an infinite score.
+    pub fn sigmas(&self, value: f64) -> f64 {
+        let spread = if self.std_dev > f64::EPSILON {
+            self.std_dev
+        } else {
+            (self.mean.abs() * 0.1).max(1.0)
+        };
+        (value - self.mean) / spread
+    }
+
+    /// [`sigmas`](Self::sigmas) of a counter or rate sample
+    pub fn z_score(&self, current: u64) -> f64 {
+        self.sigmas(current as f64)
+    }
 }
 
+/// Learned baseline for one interface. Connection metrics are system-wide, since
+/// sockets aren't reliably attributed to an interface.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceBaseline {
+    pub bytes_per_sec: MetricBaseline,
+    pub connections: MetricBaseline,
+    pub unique_remote_ips: MetricBaseline,
+    pub samples: usize,
+}
+
+/// Traffic baseline learned with `--learn-baseline`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Unix timestamp (seconds) when learning finished
+    pub learned_at: i64,
+    pub window_secs: u64,
+    pub interfaces: BTreeMap<String, InterfaceBaseline>,
+}
+
+impl Baseline {
+    /// `<data dir>/netwatch/baseline.json` (`~/.local/share/netwatch` on Linux)
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("netwatch").join("baseline.json"))
+    }
+
+    /// Load the saved baseline, if any. A missing file is not an error.
+    pub fn load() -> Result<Option<Self>> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Self::load_from(&path).map(Some)
+    }
+
+    /// Load a baseline saved by `--learn-baseline` from `path` (`--compare-baseline`)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// How far `current` is above (positive) or below (negative) `baseline_mean`, in
+    /// percent. Anything above a zero mean is infinitely far.
+    pub fn deviation_percent(current: u64, baseline_mean: u64) -> f64 {
+        if baseline_mean == 0 {
+            return if current == 0 { 0.0 } else { f64::INFINITY };
+        }
+        (current as f64 - baseline_mean as f64) / baseline_mean as f64 * 100.0
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Observations collected while a baseline is being learned
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct TrafficBaseline {
-    average_bps: f64,
-    std_deviation: f64,
-    samples: VecDeque<(SystemTime, u64)>,
-    last_updated: SystemTime,
+struct BaselineLearner {
+    started: SystemTime,
+    window: Duration,
+    // Per interface: (bytes/s, connections, unique remote IPs) for each observation
+    samples: BTreeMap<String, Vec<(f64, f64, f64)>>,
+}
+
+impl BaselineLearner {
+    fn finish(self) -> Baseline {
+        let interfaces = self
+            .samples
+            .into_iter()
+            .map(|(interface, samples)| {
+                let column = |pick: fn(&(f64, f64, f64)) -> f64| {
+                    MetricBaseline::from_samples(&samples.iter().map(pick).collect::<Vec<_>>())
+                };
+                let baseline = InterfaceBaseline {
+                    bytes_per_sec: column(|s| s.0),
+                    connections: column(|s| s.1),
+                    unique_remote_ips: column(|s| s.2),
+                    samples: samples.len(),
+                };
+                (interface, baseline)
+            })
+            .collect();
+
+        Baseline {
+            learned_at: chrono::Utc::now().timestamp(),
+            window_secs: self.window.as_secs(),
+            interfaces,
+        }
+    }
 }
 
 impl NetworkIntelligenceEngine {
@@ -142,11 +342,20 @@ impl NetworkIntelligenceEngine {
             connection_history: VecDeque::with_capacity(10000),
             geo_cache: HashMap::new(),
             port_scan_detectors: HashMap::new(),
-            anomalies: VecDeque::with_capacity(1000),
-            traffic_baselines: HashMap::new(),
+            port_scan_thresholds: PortScanThresholds::default(),
+            port_probes: HashMap::new(),
+            inbound_sockets: HashMap::new(),
+            connection_samples: HashMap::new(),
+            burst_multiplier: DEFAULT_BURST_MULTIPLIER,
+            anomalies: VecDeque::with_capacity(MAX_ANOMALIES),
+            baseline: None,
+            baseline_sigma: 3.0,
+            learning: None,
+            active_anomalies: HashSet::new(),
             known_services: Self::initialize_known_services(),
             suspicious_ips: HashSet::new(),
             internal_networks: Self::initialize_internal_networks(),
+            asn_database: None,
         };
 
         // Pre-populate with some threat intelligence
@@ -206,10 +415,7 @@ impl NetworkIntelligenceEngine {
         // For now, keep empty - no fake data
     }
 
-    pub fn analyze_connection(
-        &mut self,
-        connection: &crate::connections::NetworkConnection,
-    ) -> ConnectionIntelligence {
+    pub fn analyze_connection(&mut self, connection: &NetworkConnection) -> ConnectionIntelligence {
         let remote_ip = connection.remote_addr.ip();
         let local_port = connection.local_addr.port();
         let remote_port = connection.remote_addr.port();
@@ -239,10 +445,10 @@ impl NetworkIntelligenceEngine {
         let mut threat_indicators = Vec::new();
 
         // Check for port scanning
-        if let Some(scan_detection) = self.detect_port_scan(&remote_ip, remote_port) {
+        if let Some(scan_detection) = self.port_scan_detectors.get(&remote_ip) {
             threat_indicators.push(ThreatIndicator::PortScanAttempt {
                 ports_scanned: scan_detection.ports_scanned.len() as u16,
-                time_window: scan_detection.scan_duration,
+                time_window: scan_detection.scan_duration(),
             });
         }
 
@@ -261,11 +467,10 @@ impl NetworkIntelligenceEngine {
             0
         };
 
-        if bytes_per_second > 10_000_000 {
-            // >10MB/s threshold
+        if bytes_per_second > HIGH_BANDWIDTH_BPS {
             threat_indicators.push(ThreatIndicator::HighBandwidthUsage {
                 bandwidth: bytes_per_second,
-                threshold: 10_000_000,
+                threshold: HIGH_BANDWIDTH_BPS,
             });
         }
 
@@ -320,7 +525,11 @@ impl NetworkIntelligenceEngine {
         }
 
         // Simplified GeoIP lookup (in real implementation, use MaxMind GeoIP2 or similar)
-        let geo_info = self.mock_geo_lookup(ip);
+        let mut geo_info = self.mock_geo_lookup(ip);
+        if let Some((asn, organization)) = self.lookup_asn(ip) {
+            geo_info.asn = asn;
+            geo_info.organization = organization;
+        }
         self.geo_cache.insert(*ip, geo_info.clone());
         Some(geo_info)
     }
@@ -428,56 +637,229 @@ impl NetworkIntelligenceEngine {
         (protocol.to_uppercase(), service_name)
     }
 
-    fn detect_port_scan(&mut self, ip: &IpAddr, port: u16) -> Option<PortScanDetection> {
-        let now = SystemTime::now();
+    pub fn set_port_scan_thresholds(&mut self, thresholds: PortScanThresholds) {
+        self.port_scan_thresholds = thresholds;
+    }
+
+    pub fn set_burst_multiplier(&mut self, multiplier: f64) {
+        self.burst_multiplier = multiplier;
+    }
+
+    /// Feed one connection snapshot to the burst and port scan detectors. Probes are inbound TCP
+    /// sockets that are half open (SYN_RECV, as a SYN scan leaves them) or short lived
+    /// (gone after a single snapshot, as a connect() scan leaves them). Probes of closed
+    /// ports never reach the socket table, so only open ports are counted.
+    pub fn observe_connections(&mut self, connections: &[NetworkConnection]) {
+        self.observe_connections_at(SystemTime::now(), connections);
+    }
 
-        // Check if we already have a detector for this IP
-        let mut updated_detector = if let Some(existing) = self.port_scan_detectors.get(ip) {
-            let mut detector = existing.clone();
-            detector.ports_scanned.insert(port);
-            detector.scan_duration = now
-                .duration_since(detector.scan_start_time)
-                .unwrap_or_default();
+    fn observe_connections_at(&mut self, now: SystemTime, connections: &[NetworkConnection]) {
+        self.detect_bursts(now, connections);
 
-            // Calculate scan rate
-            if detector.scan_duration.as_secs() > 0 {
-                detector.scan_rate =
-                    detector.ports_scanned.len() as f64 / detector.scan_duration.as_secs_f64();
+        let tcp = connections
+            .iter()
+            .filter(|conn| matches!(conn.protocol, Protocol::Tcp | Protocol::Tcp6));
+        let listening: HashSet<u16> = tcp
+            .clone()
+            .filter(|conn| conn.state == ConnectionState::Listen)
+            .map(|conn| conn.local_addr.port())
+            .collect();
+
+        let mut inbound = HashMap::new();
+        for conn in tcp {
+            let half_open = conn.state == ConnectionState::SynReceived;
+            let accepted = conn.state != ConnectionState::Listen
+                && listening.contains(&conn.local_addr.port());
+            // This host talking to itself is not a scan
+            if !(half_open || accepted) || conn.remote_addr.ip().is_loopback() {
+                continue;
             }
 
-            detector
-        } else {
-            // Create new detector
-            let mut detector = PortScanDetection {
-                scanner_ip: *ip,
-                ports_scanned: HashSet::new(),
-                scan_start_time: now,
-                scan_duration: Duration::from_secs(0),
-                scan_rate: 0.0,
-                confidence: 0.0,
+            let key = (conn.local_addr, conn.remote_addr);
+            let snapshots = self.inbound_sockets.get(&key).map_or(1, |seen| seen + 1);
+            inbound.insert(key, snapshots);
+            if half_open {
+                self.record_probe(now, conn.remote_addr.ip(), conn.local_addr.port());
+            }
+        }
+
+        let short_lived: Vec<(SocketAddr, SocketAddr)> = self
+            .inbound_sockets
+            .iter()
+            .filter(|(key, &snapshots)| snapshots == 1 && !inbound.contains_key(*key))
+            .map(|(key, _)| *key)
+            .collect();
+        for (local, remote) in short_lived {
+            self.record_probe(now, remote.ip(), local.port());
+        }
+        self.inbound_sockets = inbound;
+
+        self.update_port_scans(now);
+    }
+
+    /// Report connections whose throughput or queued bytes grew more than
+    /// `burst_multiplier` times since the last snapshot, as an exfiltration would.
+    /// Throughput comes from the `ss -i` byte counters, so it is TCP only; queues
+    /// cover UDP too.
+    fn detect_bursts(&mut self, now: SystemTime, connections: &[NetworkConnection]) {
+        let multiplier = self.burst_multiplier;
+        let mut samples = HashMap::with_capacity(connections.len());
+        for conn in connections {
+            if conn.state == ConnectionState::Listen {
+                continue;
+            }
+            let key = (conn.local_addr, conn.remote_addr);
+            let bytes = conn.bytes_sent.saturating_add(conn.bytes_received);
+            let queued =
+                u64::from(conn.socket_info.send_queue) + u64::from(conn.socket_info.recv_queue);
+            let previous = self.connection_samples.get(&key).copied();
+            let rate = previous.and_then(|prev| {
+                let elapsed = now.duration_since(prev.at).ok()?.as_secs_f64();
+                (elapsed > 0.0 && bytes > 0 && bytes >= prev.bytes)
+                    .then(|| (bytes - prev.bytes) as f64 / elapsed)
+            });
+            samples.insert(
+                key,
+                ConnectionSample {
+                    at: now,
+                    bytes,
+                    rate,
+                    queued,
+                },
+            );
+
+            let Some(prev) = previous else {
+                continue;
+            };
+            let burst = match (prev.rate, rate) {
+                (Some(before), Some(after))
+                    if after >= BURST_MIN_RATE && after > before * multiplier =>
+                {
+                    let format = |rate: f64| format_rate(rate as u64, &TrafficUnit::HumanByte);
+                    Some(("throughput", before, after, format(before), format(after)))
+                }
+                _ if queued >= BURST_MIN_QUEUED
+                    && queued as f64 > prev.queued as f64 * multiplier =>
+                {
+                    let format = |bytes: u64| format_volume(bytes, &DataUnit::HumanByte);
+                    Some((
+                        "queued",
+                        prev.queued as f64,
+                        queued as f64,
+                        format(prev.queued),
+                        format(queued),
+                    ))
+                }
+                _ => None,
+            };
+            let Some((metric, before, after, before_text, after_text)) = burst else {
+                continue;
             };
-            detector.ports_scanned.insert(port);
-            detector
-        };
 
-        // Calculate confidence
-        updated_detector.confidence = self.calculate_port_scan_confidence(&updated_detector);
+            let factor = if before > 0.0 {
+                format!(" ({:.0}x)", after / before)
+            } else {
+                String::new()
+            };
+            self.record_anomaly(NetworkAnomaly {
+                anomaly_type: AnomalyType::TrafficSpike,
+                severity: Severity::Medium,
+                description: format!(
+                    "{} {} -> {} burst: {metric} {before_text} -> {after_text}{factor}",
+                    conn.protocol.as_str(),
+                    conn.local_addr,
+                    conn.remote_addr
+                ),
+                affected_ip: Some(conn.remote_addr.ip()),
+                affected_port: Some(conn.remote_addr.port()),
+                detected_at: now,
+                confidence: 0.6,
+                metrics: HashMap::from([
+                    ("before".to_string(), before),
+                    ("after".to_string(), after),
+                    ("multiplier".to_string(), multiplier),
+                ]),
+            });
+        }
+        self.connection_samples = samples;
+    }
 
-        // Update the detector in the map
-        self.port_scan_detectors
-            .insert(*ip, updated_detector.clone());
+    fn record_probe(&mut self, at: SystemTime, ip: IpAddr, port: u16) {
+        self.port_probes
+            .entry(ip)
+            .or_default()
+            .push_back((at, port));
+    }
 
-        // Clean up old detectors (older than 5 minutes)
-        let cutoff = now - Duration::from_secs(300);
-        self.port_scan_detectors
-            .retain(|_, detector| detector.scan_start_time > cutoff);
+    /// Drop probes older than the window, then report each address that touched more
+    /// ports than allowed within it. A scan is recorded as an anomaly when it starts
+    /// and decays once its probes have aged out of the window.
+    fn update_port_scans(&mut self, now: SystemTime) {
+        let thresholds = self.port_scan_thresholds;
+        let cutoff = now.checked_sub(thresholds.window).unwrap_or(now);
+        self.port_probes.retain(|_, probes| {
+            while probes.front().is_some_and(|(at, _)| *at < cutoff) {
+                probes.pop_front();
+            }
+            !probes.is_empty()
+        });
+
+        let mut detections = HashMap::new();
+        for (ip, probes) in &self.port_probes {
+            let ports_scanned: BTreeSet<u16> = probes.iter().map(|(_, port)| *port).collect();
+            if ports_scanned.len() <= thresholds.ports {
+                continue;
+            }
+            let (Some(&(first_seen, _)), Some(&(last_seen, _))) = (probes.front(), probes.back())
+            else {
+                continue;
+            };
 
-        // Return detector if confidence is high enough
-        if updated_detector.confidence > 0.7 {
-            Some(updated_detector)
-        } else {
-            None
+            let mut detection = PortScanDetection {
+                scanner_ip: *ip,
+                first_seen,
+                last_seen,
+                scan_rate: 0.0,
+                confidence: 0.0,
+                ports_scanned,
+            };
+            // Probes in one snapshot share a timestamp; count them as a second apart
+            detection.scan_rate = detection.ports_scanned.len() as f64
+                / detection.scan_duration().as_secs_f64().max(1.0);
+            detection.confidence = self.calculate_port_scan_confidence(&detection);
+            detections.insert(*ip, detection);
         }
+
+        let started: Vec<PortScanDetection> = detections
+            .values()
+            .filter(|detection| !self.port_scan_detectors.contains_key(&detection.scanner_ip))
+            .cloned()
+            .collect();
+        for detection in started {
+            let ports = detection.ports_scanned.len();
+            self.record_anomaly(NetworkAnomaly {
+                anomaly_type: AnomalyType::PortScan,
+                severity: if detection.confidence > 0.8 {
+                    Severity::High
+                } else {
+                    Severity::Medium
+                },
+                description: format!(
+                    "{} probed {ports} ports in {}s",
+                    detection.scanner_ip,
+                    detection.scan_duration().as_secs()
+                ),
+                affected_ip: Some(detection.scanner_ip),
+                affected_port: None,
+                detected_at: now,
+                confidence: detection.confidence,
+                metrics: HashMap::from([
+                    ("ports".to_string(), ports as f64),
+                    ("rate".to_string(), detection.scan_rate),
+                ]),
+            });
+        }
+        self.port_scan_detectors = detections;
     }
 
     fn calculate_port_scan_confidence(&self, detector: &PortScanDetection) -> f64 {
@@ -495,22 +877,17 @@ impl NetworkIntelligenceEngine {
         }
 
         // Sequential port scanning pattern
-        let mut ports: Vec<u16> = detector.ports_scanned.iter().cloned().collect();
-        ports.sort();
+        let ports: Vec<u16> = detector.ports_scanned.iter().cloned().collect();
         let sequential_count = self.count_sequential_ports(&ports);
         if sequential_count > 5 {
             confidence += 0.2;
         }
 
         // Common port scan targets
-        let common_scan_ports: HashSet<u16> =
-            [22, 23, 80, 443, 21, 25, 53, 110, 143, 993, 995, 3389]
-                .iter()
-                .cloned()
-                .collect();
-        let scan_common_count = detector
-            .ports_scanned
-            .intersection(&common_scan_ports)
+        let common_scan_ports = [22, 23, 80, 443, 21, 25, 53, 110, 143, 993, 995, 3389];
+        let scan_common_count = common_scan_ports
+            .iter()
+            .filter(|port| detector.ports_scanned.contains(port))
             .count();
         if scan_common_count > 3 {
             confidence += 0.1;
@@ -544,16 +921,260 @@ impl NetworkIntelligenceEngine {
         matches!(port, 1337 | 31337 | 12345 | 54321 | 6667 | 6668 | 6669)
     }
 
+    /// Learn a new baseline from the observations made during the next `window`. It
+    /// replaces the current baseline when the window ends; nothing is scored meanwhile.
+    pub fn learn_baseline(&mut self, window: Duration) {
+        self.learning = Some(BaselineLearner {
+            started: SystemTime::now(),
+            window,
+            samples: BTreeMap::new(),
+        });
+        self.active_anomalies.clear();
+    }
+
+    pub fn is_learning(&self) -> bool {
+        self.learning.is_some()
+    }
+
+    pub fn baseline(&self) -> Option<&Baseline> {
+        self.baseline.as_ref()
+    }
+
+    /// Look up external addresses' AS number and organization in a MaxMind ASN
+    /// database (GeoLite2-ASN, or GeoIP2-ISP which has the same fields)
+    pub fn load_asn_database(&mut self, path: &str) -> Result<()> {
+        let reader = maxminddb::Reader::open_readfile(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open GeoIP database {path}: {e}"))?;
+        let database_type = &reader.metadata.database_type;
+        if !database_type.contains("ASN") && !database_type.contains("ISP") {
+            anyhow::bail!("{path} is a {database_type} database; --geoip-db needs an ASN one such as GeoLite2-ASN.mmdb");
+        }
+        self.asn_database = Some(reader);
+        self.geo_cache.clear();
+        Ok(())
+    }
+
+    /// (AS number, organization) of `ip`; None without a database or for addresses
+    /// it doesn't cover
+    fn lookup_asn(&self, ip: &IpAddr) -> Option<(u32, String)> {
+        let asn: maxminddb::geoip2::Asn = self.asn_database.as_ref()?.lookup(*ip).ok()?;
+        Some((
+            asn.autonomous_system_number?,
+            asn.autonomous_system_organization?.to_string(),
+        ))
+    }
+
+    pub fn set_baseline(&mut self, baseline: Option<Baseline>) {
+        self.baseline = baseline;
+        self.active_anomalies.clear();
+    }
+
+    /// Deviation (in standard deviations) at which a metric counts as anomalous
+    pub fn set_baseline_sigma(&mut self, sigma: f64) {
+        self.baseline_sigma = sigma;
+    }
+
+    /// Record one refresh worth of traffic for `interface`. While learning this feeds
+    /// the baseline; afterwards it is scored against the baseline, or against fixed
+    /// thresholds when no baseline covers the interface.
+    pub fn observe_traffic(
+        &mut self,
+        interface: &str,
+        bytes_per_sec: u64,
+        connections: usize,
+        unique_remote_ips: usize,
+    ) {
+        self.observe_traffic_at(
+            SystemTime::now(),
+            interface,
+            bytes_per_sec,
+            connections,
+            unique_remote_ips,
+        );
+    }
+
+    fn observe_traffic_at(
+        &mut self,
+        now: SystemTime,
+        interface: &str,
+        bytes_per_sec: u64,
+        connections: usize,
+        unique_remote_ips: usize,
+    ) {
+        let bytes_per_sec = bytes_per_sec as f64;
+        let connections = connections as f64;
+        let unique_remote_ips = unique_remote_ips as f64;
+
+        if let Some(learner) = self.learning.as_mut() {
+            learner
+                .samples
+                .entry(interface.to_string())
+                .or_default()
+                .push((bytes_per_sec, connections, unique_remote_ips));
+
+            let elapsed = now.duration_since(learner.started).unwrap_or_default();
+            if elapsed >= learner.window {
+                if let Some(learner) = self.learning.take() {
+                    self.baseline = Some(learner.finish());
+                }
+            }
+            return;
+        }
+
+        let learned = self
+            .baseline
+            .as_ref()
+            .and_then(|baseline| baseline.interfaces.get(interface))
+            .cloned();
+        match learned {
+            Some(learned) => {
+                let checks = [
+                    (
+                        "bytes/s",
+                        bytes_per_sec,
+                        learned.bytes_per_sec,
+                        AnomalyType::BandwidthAnomaly,
+                    ),
+                    (
+                        "connections",
+                        connections,
+                        learned.connections,
+                        AnomalyType::ConnectionFlood,
+                    ),
+                    (
+                        "remote IPs",
+                        unique_remote_ips,
+                        learned.unique_remote_ips,
+                        AnomalyType::ConnectionFlood,
+                    ),
+                ];
+                for (metric, value, baseline, anomaly_type) in checks {
+                    self.score_against_baseline(interface, metric, value, baseline, anomaly_type);
+                }
+            }
+            None => {
+                self.check_fixed_threshold(
+                    interface,
+                    "bytes/s",
+                    bytes_per_sec,
+                    HIGH_BANDWIDTH_BPS as f64,
+                    AnomalyType::BandwidthAnomaly,
+                );
+                self.check_fixed_threshold(
+                    interface,
+                    "connections",
+                    connections,
+                    HIGH_CONNECTION_COUNT as f64,
+                    AnomalyType::ConnectionFlood,
+                );
+            }
+        }
+    }
+
+    fn score_against_baseline(
+        &mut self,
+        interface: &str,
+        metric: &'static str,
+        value: f64,
+        baseline: MetricBaseline,
+        anomaly_type: AnomalyType,
+    ) {
+        let sigmas = baseline.sigmas(value);
+        let sigma = self.baseline_sigma;
+        if !self.track_excursion(interface, metric, sigmas.abs() >= sigma) {
+            return;
+        }
+
+        let score = sigmas.abs();
+        let severity = if score >= sigma * 3.0 {
+            Severity::Critical
+        } else if score >= sigma * 2.0 {
+            Severity::High
+        } else {
+            Severity::Medium
+        };
+        let direction = if sigmas > 0.0 { "above" } else { "below" };
+
+        self.record_anomaly(NetworkAnomaly {
+            anomaly_type,
+            severity,
+            description: format!(
+                "{interface}: {metric} {score:.1}σ {direction} baseline ({value:.0} vs mean {:.0})",
+                baseline.mean
+            ),
+            affected_ip: None,
+            affected_port: None,
+            detected_at: SystemTime::now(),
+            confidence: (score / (sigma * 2.0)).min(1.0),
+            metrics: HashMap::from([
+                ("value".to_string(), value),
+                ("mean".to_string(), baseline.mean),
+                ("std_dev".to_string(), baseline.std_dev),
+                ("sigmas".to_string(), sigmas),
+            ]),
+        });
+    }
+
+    fn check_fixed_threshold(
+        &mut self,
+        interface: &str,
+        metric: &'static str,
+        value: f64,
+        threshold: f64,
+        anomaly_type: AnomalyType,
+    ) {
+        if !self.track_excursion(interface, metric, value > threshold) {
+            return;
+        }
+
+        self.record_anomaly(NetworkAnomaly {
+            anomaly_type,
+            severity: Severity::Medium,
+            description: format!("{interface}: {metric} {value:.0} above {threshold:.0}"),
+            affected_ip: None,
+            affected_port: None,
+            detected_at: SystemTime::now(),
+            confidence: 0.5,
+            metrics: HashMap::from([
+                ("value".to_string(), value),
+                ("threshold".to_string(), threshold),
+            ]),
+        });
+    }
+
+    /// True when (interface, metric) has just gone out of range
+    fn track_excursion(
+        &mut self,
+        interface: &str,
+        metric: &'static str,
+        out_of_range: bool,
+    ) -> bool {
+        let key = (interface.to_string(), metric);
+        if out_of_range {
+            self.active_anomalies.insert(key)
+        } else {
+            self.active_anomalies.remove(&key);
+            false
+        }
+    }
+
+    fn record_anomaly(&mut self, anomaly: NetworkAnomaly) {
+        if self.anomalies.len() >= MAX_ANOMALIES {
+            self.anomalies.pop_front();
+        }
+        self.anomalies.push_back(anomaly);
+    }
+
     pub fn get_recent_anomalies(&self, limit: usize) -> Vec<&NetworkAnomaly> {
         self.anomalies.iter().rev().take(limit).collect()
     }
 
+    /// Scans still within the window, most recently active first
     pub fn get_port_scan_alerts(&self) -> Vec<PortScanDetection> {
-        self.port_scan_detectors
-            .values()
-            .filter(|detector| detector.confidence > 0.7)
-            .cloned()
-            .collect()
+        let mut alerts: Vec<PortScanDetection> =
+            self.port_scan_detectors.values().cloned().collect();
+        alerts.sort_by_key(|alert| std::cmp::Reverse(alert.last_seen));
+        alerts
     }
 
     pub fn get_connection_stats(&self) -> ConnectionStats {
@@ -603,6 +1224,17 @@ impl Default for NetworkIntelligenceEngine {
     }
 }
 
+/// Connection count and distinct remote IPs (listening sockets excluded), the
+/// connection metrics a baseline is learned for
+pub fn connection_metrics(connections: &[NetworkConnection]) -> (usize, usize) {
+    let remote_ips: HashSet<IpAddr> = connections
+        .iter()
+        .map(|conn| conn.remote_addr.ip())
+        .filter(|ip| !ip.is_unspecified())
+        .collect();
+    (connections.len(), remote_ips.len())
+}
+
 // Helper function to parse duration strings from ss command output
 fn parse_duration(duration_str: &str) -> Option<Duration> {
     // Parse duration strings like "1h30m", "45m", "30s", etc.
@@ -631,3 +1263,370 @@ fn parse_duration(duration_str: &str) -> Option<Duration> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // MaxMind DB encoding: a control byte holding the type (top 3 bits) and the size,
+    // then the payload. Types above 7 and sizes from 29 on take an extra byte.
+    fn mmdb_string(value: &str) -> Vec<u8> {
+        let mut out = match value.len() {
+            len @ 0..=28 => vec![0x40 | len as u8],
+            len => vec![0x40 | 29, (len - 29) as u8],
+        };
+        out.extend_from_slice(value.as_bytes());
+        out
+    }
+
+    fn mmdb_uint(type_bits: u8, value: u16) -> Vec<u8> {
+        let mut out = vec![type_bits | 2];
+        out.extend_from_slice(&value.to_be_bytes());
+        out
+    }
+
+    fn mmdb_map(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut out = vec![0xE0 | entries.len() as u8];
+        for (key, value) in entries {
+            out.extend(mmdb_string(key));
+            out.extend_from_slice(value);
+        }
+        out
+    }
+
+    /// One-node IPv4 ASN database: 0.0.0.0/1 is AS13335, 128.0.0.0/1 isn't covered
+    fn asn_database(database_type: &str) -> Vec<u8> {
+        let uint16 = |value| mmdb_uint(0xA0, value);
+        let uint32 = |value| mmdb_uint(0xC0, value);
+        // Left record points at data offset 0 (node count + 16 + offset), right at
+        // the node count, meaning no data
+        let mut db = vec![0, 0, 17, 0, 0, 1];
+        db.extend([0; 16]);
+        db.extend(mmdb_map(&[
+            ("autonomous_system_number", uint32(13335)),
+            (
+                "autonomous_system_organization",
+                mmdb_string("CLOUDFLARENET"),
+            ),
+        ]));
+        db.extend(b"\xAB\xCD\xEFMaxMind.com");
+        db.extend(mmdb_map(&[
+            ("binary_format_major_version", uint16(2)),
+            ("binary_format_minor_version", uint16(0)),
+            ("build_epoch", vec![0x01, 0x02, 0x01]), // uint64 (type 9), 1 byte
+            ("database_type", mmdb_string(database_type)),
+            ("description", mmdb_map(&[])),
+            ("ip_version", uint16(4)),
+            ("languages", vec![0x00, 0x04]), // empty array (type 11)
+            ("node_count", uint32(1)),
+            ("record_size", uint16(24)),
+        ]));
+        db
+    }
+
+    #[test]
+    fn test_asn_database_fills_organization() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("GeoLite2-ASN.mmdb");
+        fs::write(&path, asn_database("GeoLite2-ASN")).unwrap();
+
+        let mut engine = NetworkIntelligenceEngine::new();
+        let cloudflare: IpAddr = "1.1.1.1".parse().unwrap();
+        assert_eq!(engine.get_geo_info(&cloudflare).unwrap().asn, 0);
+
+        // Loading drops what was cached without it
+        engine.load_asn_database(path.to_str().unwrap()).unwrap();
+        let geo = engine.get_geo_info(&cloudflare).unwrap();
+        assert_eq!(geo.asn, 13335);
+        assert_eq!(geo.organization, "CLOUDFLARENET");
+
+        let uncovered = engine.get_geo_info(&"200.1.1.1".parse().unwrap()).unwrap();
+        assert_eq!(
+            (uncovered.asn, uncovered.organization.as_str()),
+            (0, "Unknown")
+        );
+        let internal = engine.get_geo_info(&"10.0.0.1".parse().unwrap()).unwrap();
+        assert_eq!(internal.organization, "Internal Network");
+
+        // A City database has no ASN fields
+        fs::write(&path, asn_database("GeoLite2-City")).unwrap();
+        let error = engine
+            .load_asn_database(path.to_str().unwrap())
+            .unwrap_err();
+        assert!(error.to_string().contains("GeoLite2-City database"));
+        assert!(engine
+            .load_asn_database(dir.path().join("missing.mmdb").to_str().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_learned_baseline_scores_in_sigmas() {
+        let mut engine = NetworkIntelligenceEngine::new();
+        engine.learn_baseline(Duration::from_secs(60));
+        let started = engine.learning.as_ref().unwrap().started;
+
+        for (i, bytes) in [900, 1000, 1100, 1000].into_iter().enumerate() {
+            let at = started + Duration::from_secs(20 * i as u64);
+            engine.observe_traffic_at(at, "eth0", bytes, 10, 4);
+        }
+        assert!(!engine.is_learning());
+
+        let baseline = engine.baseline().unwrap().interfaces["eth0"].clone();
+        assert_eq!(baseline.samples, 4);
+        assert_eq!(baseline.bytes_per_sec.mean, 1000.0);
+        assert!((baseline.bytes_per_sec.std_dev - 70.71).abs() < 0.01);
+        assert!(engine.get_recent_anomalies(10).is_empty());
+
+        // Within 3 sigma: nothing; far above: reported once until it settles
+        engine.observe_traffic("eth0", 1150, 10, 4);
+        assert!(engine.get_recent_anomalies(10).is_empty());
+        engine.observe_traffic("eth0", 5000, 10, 4);
+        engine.observe_traffic("eth0", 5000, 10, 4);
+        let anomalies = engine.get_recent_anomalies(10);
+        assert_eq!(anomalies.len(), 1);
+        assert!(matches!(anomalies[0].severity, Severity::Critical));
+        assert!(anomalies[0].description.starts_with("eth0: bytes/s"));
+
+        // A flat baseline (std dev 0) still scores sensibly
+        engine.observe_traffic("eth0", 1000, 30, 4);
+        assert_eq!(engine.get_recent_anomalies(10).len(), 2);
+
+        // Interfaces without a baseline fall back to fixed thresholds
+        engine.observe_traffic("eth1", HIGH_BANDWIDTH_BPS + 1, 10, 4);
+        assert_eq!(engine.get_recent_anomalies(10).len(), 3);
+    }
+
+    fn tcp_socket(local: &str, remote: &str, state: ConnectionState) -> NetworkConnection {
+        NetworkConnection {
+            local_addr: local.parse().unwrap(),
+            remote_addr: remote.parse().unwrap(),
+            state,
+            protocol: Protocol::Tcp,
+            pid: None,
+            process_name: None,
+            process_cmdline: None,
+            bytes_sent: 0,
+            bytes_received: 0,
+            socket_info: crate::connections::SocketInfo::default(),
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
+        }
+    }
+
+    /// This host listening on 22, 80, 443 and 8000-8049
+    fn listeners() -> Vec<NetworkConnection> {
+        [22, 80, 443]
+            .into_iter()
+            .chain(8000..8050)
+            .map(|port| {
+                tcp_socket(
+                    &format!("0.0.0.0:{port}"),
+                    "0.0.0.0:0",
+                    ConnectionState::Listen,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_port_scan_detected_and_decays() {
+        let mut engine = NetworkIntelligenceEngine::new();
+        let start = SystemTime::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let scanner: IpAddr = "203.0.113.9".parse().unwrap();
+
+        // A SYN scan leaves half-open sockets: 10 ports per snapshot, 4s apart
+        for (i, ports) in [8000..8010, 8010..8020].into_iter().enumerate() {
+            let mut snapshot = listeners();
+            snapshot.extend(ports.map(|port| {
+                tcp_socket(
+                    &format!("192.0.2.1:{port}"),
+                    "203.0.113.9:51000",
+                    ConnectionState::SynReceived,
+                )
+            }));
+            engine.observe_connections_at(at(4 * i as u64), &snapshot);
+        }
+
+        let alerts = engine.get_port_scan_alerts();
+        assert_eq!(alerts.len(), 1);
+        let scan = &alerts[0];
+        assert_eq!(scan.scanner_ip, scanner);
+        assert_eq!(scan.ports_scanned.len(), 20);
+        assert_eq!(scan.ports_scanned.first(), Some(&8000));
+        assert_eq!((scan.first_seen, scan.last_seen), (at(0), at(4)));
+        assert_eq!(scan.scan_rate, 5.0);
+        let anomalies = engine.get_recent_anomalies(10);
+        assert_eq!(anomalies.len(), 1);
+        assert!(matches!(anomalies[0].anomaly_type, AnomalyType::PortScan));
+        assert_eq!(anomalies[0].affected_ip, Some(scanner));
+
+        // A connect() scan: accepted sockets gone by the next snapshot
+        let mut snapshot = listeners();
+        snapshot.extend((8020..8030).map(|port| {
+            tcp_socket(
+                &format!("192.0.2.1:{port}"),
+                "203.0.113.9:52000",
+                ConnectionState::Established,
+            )
+        }));
+        engine.observe_connections_at(at(8), &snapshot);
+        engine.observe_connections_at(at(12), &listeners());
+        let scan = &engine.get_port_scan_alerts()[0];
+        assert_eq!(scan.ports_scanned.len(), 30);
+        assert_eq!(scan.last_seen, at(12));
+        // Still the same scan, so still one anomaly
+        assert_eq!(engine.get_recent_anomalies(10).len(), 1);
+
+        // Once the window passes without probes the alert decays
+        engine.observe_connections_at(at(60), &listeners());
+        assert_eq!(engine.get_port_scan_alerts().len(), 1);
+        engine.observe_connections_at(at(73), &listeners());
+        assert!(engine.get_port_scan_alerts().is_empty());
+        assert!(engine.port_probes.is_empty());
+
+        // Thresholds are configurable
+        engine.set_port_scan_thresholds(PortScanThresholds {
+            ports: 5,
+            window: Duration::from_secs(10),
+        });
+        let mut snapshot = listeners();
+        snapshot.extend((8040..8046).map(|port| {
+            tcp_socket(
+                &format!("192.0.2.1:{port}"),
+                "198.51.100.7:40000",
+                ConnectionState::SynReceived,
+            )
+        }));
+        engine.observe_connections_at(at(80), &snapshot);
+        assert_eq!(engine.get_port_scan_alerts().len(), 1);
+    }
+
+    #[test]
+    fn test_busy_load_balancer_is_not_a_port_scan() {
+        let mut engine = NetworkIntelligenceEngine::new();
+        let start = SystemTime::now();
+
+        // A load balancer health-checks and reconnects to 443 and 8080 from new
+        // ephemeral ports every snapshot, sometimes faster than they are accepted
+        for i in 0..30u16 {
+            let mut snapshot = listeners();
+            for j in 0..20u16 {
+                let remote = format!("10.0.0.5:{}", 30000 + i * 20 + j);
+                let (port, state) = if j % 5 == 0 {
+                    (8080, ConnectionState::SynReceived)
+                } else {
+                    (443, ConnectionState::Established)
+                };
+                snapshot.push(tcp_socket(&format!("10.0.0.1:{port}"), &remote, state));
+            }
+            // A long-lived admin session and outbound connections to many ports
+            snapshot.push(tcp_socket(
+                "10.0.0.1:22",
+                "10.0.0.9:50022",
+                ConnectionState::Established,
+            ));
+            snapshot.extend((0..20u16).map(|port| {
+                tcp_socket(
+                    &format!("10.0.0.1:{}", 40000 + port),
+                    &format!("10.0.0.7:{}", 9000 + port),
+                    ConnectionState::Established,
+                )
+            }));
+            engine.observe_connections_at(start + Duration::from_secs(4 * i as u64), &snapshot);
+        }
+
+        assert!(engine.get_port_scan_alerts().is_empty());
+        assert!(engine.get_recent_anomalies(10).is_empty());
+        let probed: HashSet<u16> = engine.port_probes[&"10.0.0.5".parse().unwrap()]
+            .iter()
+            .map(|(_, port)| *port)
+            .collect();
+        assert_eq!(probed, HashSet::from([443, 8080]));
+        assert!(!engine
+            .port_probes
+            .contains_key(&"10.0.0.9".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_connection_burst_reported_when_it_starts() {
+        let mut engine = NetworkIntelligenceEngine::new();
+        let start = SystemTime::now();
+        let upload = |bytes_sent| NetworkConnection {
+            bytes_sent,
+            ..tcp_socket(
+                "10.0.0.5:40000",
+                "198.51.100.7:443",
+                ConnectionState::Established,
+            )
+        };
+
+        // First sighting has no rate; then a steady 10 kB/s
+        for (secs, bytes) in [(0, 1_000), (1, 11_000), (2, 21_000)] {
+            engine.observe_connections_at(start + Duration::from_secs(secs), &[upload(bytes)]);
+        }
+        assert!(engine.get_recent_anomalies(10).is_empty());
+
+        // 2 MB in the next second is 200x; staying at that rate is not a new burst
+        engine.observe_connections_at(start + Duration::from_secs(3), &[upload(2_021_000)]);
+        engine.observe_connections_at(start + Duration::from_secs(4), &[upload(4_021_000)]);
+        let anomalies = engine.get_recent_anomalies(10);
+        assert_eq!(anomalies.len(), 1);
+        assert!(matches!(anomalies[0].severity, Severity::Medium));
+        assert!(anomalies[0]
+            .description
+            .starts_with("TCP 10.0.0.5:40000 -> 198.51.100.7:443 burst: throughput"));
+        assert!(anomalies[0].description.ends_with("(200x)"));
+        assert_eq!(anomalies[0].metrics["after"], 2_000_000.0);
+
+        // UDP has no byte counters, but a send queue filling up counts too
+        let mut queued = tcp_socket(
+            "10.0.0.5:5000",
+            "198.51.100.8:53",
+            ConnectionState::Established,
+        );
+        queued.protocol = Protocol::Udp;
+        queued.socket_info.send_queue = 1_000;
+        engine.observe_connections_at(start + Duration::from_secs(5), &[queued.clone()]);
+        queued.socket_info.send_queue = 100_000;
+        engine.observe_connections_at(start + Duration::from_secs(6), &[queued]);
+        let anomalies = engine.get_recent_anomalies(10);
+        assert_eq!(anomalies.len(), 2);
+        assert!(anomalies[0].description.contains("burst: queued"));
+    }
+
+    #[test]
+    fn test_baseline_round_trips_through_json() {
+        let baseline = Baseline {
+            learned_at: 1_700_000_000,
+            window_secs: 600,
+            interfaces: BTreeMap::from([(
+                "eth0".to_string(),
+                InterfaceBaseline {
+                    bytes_per_sec: MetricBaseline::from_samples(&[1.0, 3.0]),
+                    samples: 2,
+                    ..InterfaceBaseline::default()
+                },
+            )]),
+        };
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+        assert_eq!(baseline.interfaces["eth0"].bytes_per_sec.std_dev, 1.0);
+    }
+
+    #[test]
+    fn test_deviation_from_baseline() {
+        assert_eq!(Baseline::deviation_percent(185, 100), 85.0);
+        assert_eq!(Baseline::deviation_percent(50, 100), -50.0);
+        assert_eq!(Baseline::deviation_percent(0, 0), 0.0);
+        assert!(Baseline::deviation_percent(1, 0).is_infinite());
+
+        let metric = MetricBaseline {
+            mean: 1000.0,
+            std_dev: 100.0,
+        };
+        assert_eq!(metric.z_score(1250), 2.5);
+        assert_eq!(metric.z_score(900), -1.0);
+    }
+}
@@ -0,0 +1,328 @@
+//! Native ICMP echo for the Diagnostics panel's ping results
+//!
+//! Linux allows unprivileged ICMP on datagram sockets for the groups in
+//! `net.ipv4.ping_group_range`, and macOS always does. Otherwise a raw socket is used
+//! when CAP_NET_RAW allows. When neither can be opened [`echo`] returns
+//! the error and the caller falls back to the `ping` binary.
+
+use crate::traceroute::{echo_request, has_cap_net_raw, ip_header_len};
+use std::collections::VecDeque;
+use std::io;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Probes kept per target for loss and RTT statistics
+pub const PING_WINDOW: usize = 20;
+
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMP6_ECHO_REQUEST: u8 = 128;
+const ICMP6_ECHO_REPLY: u8 = 129;
+
+/// Send one echo request to `addr` and wait up to `timeout` for its reply.
+/// Ok(Some(rtt in ms)) on a reply, Ok(None) when it timed out, Err when no ICMP
+/// socket could be opened or used.
+pub fn echo(addr: IpAddr, seq: u16, timeout: Duration) -> io::Result<Option<f64>> {
+    let socket = IcmpSocket::open(addr)?;
+    let ident = (std::process::id() & 0xffff) as u16;
+    let sent = Instant::now();
+    socket.send_echo(addr, ident, seq)?;
+    Ok(socket
+        .wait_reply(ident, seq, sent + timeout)?
+        .then(|| sent.elapsed().as_secs_f64() * 1000.0))
+}
+
+/// Loss and RTT statistics over the last [`PING_WINDOW`] probes of one target
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PingSummary {
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f32,
+    pub min_rtt: f32,
+    pub avg_rtt: f32,
+    pub max_rtt: f32,
+    pub stddev_rtt: f32,
+    /// Whether the most recent probe was answered
+    pub last_answered: bool,
+}
+
+/// Sequence numbers and recent results for one ping target
+#[derive(Debug, Clone, Default)]
+pub struct PingWindow {
+    // RTT in ms of each recent probe, None where it was lost; oldest first
+    probes: VecDeque<Option<f64>>,
+    next_seq: u16,
+}
+
+impl PingWindow {
+    /// Sequence number for the next probe, so a late reply to an earlier one is not
+    /// mistaken for it
+    pub fn next_seq(&mut self) -> u16 {
+        let seq = self.next_seq;
+        self.next_seq = seq.wrapping_add(1);
+        seq
+    }
+
+    pub fn record(&mut self, rtt_ms: Option<f64>) {
+        if self.probes.len() >= PING_WINDOW {
+            self.probes.pop_front();
+        }
+        self.probes.push_back(rtt_ms);
+    }
+
+    pub fn summary(&self) -> PingSummary {
+        let rtts: Vec<f64> = self.probes.iter().flatten().copied().collect();
+        let sent = self.probes.len();
+        let mut summary = PingSummary {
+            sent: sent as u32,
+            received: rtts.len() as u32,
+            last_answered: self.probes.back().is_some_and(Option::is_some),
+            ..PingSummary::default()
+        };
+        if sent > 0 {
+            summary.loss_percent = (sent - rtts.len()) as f32 / sent as f32 * 100.0;
+        }
+        if !rtts.is_empty() {
+            let avg = rtts.iter().sum::<f64>() / rtts.len() as f64;
+            let variance =
+                rtts.iter().map(|rtt| (rtt - avg).powi(2)).sum::<f64>() / rtts.len() as f64;
+            summary.min_rtt = rtts.iter().copied().fold(f64::INFINITY, f64::min) as f32;
+            summary.max_rtt = rtts.iter().copied().fold(0.0, f64::max) as f32;
+            summary.avg_rtt = avg as f32;
+            summary.stddev_rtt = variance.sqrt() as f32;
+        }
+        summary
+    }
+}
+
+/// ICMP or ICMPv6 echo socket, closed on drop
+struct IcmpSocket {
+    fd: libc::c_int,
+    // Raw sockets see every ICMP packet for the host, so replies are matched on the
+    // identifier too. Datagram sockets only get their own, and Linux rewrites the
+    // identifier to the socket's port.
+    raw: bool,
+}
+
+impl IcmpSocket {
+    fn open(addr: IpAddr) -> io::Result<Self> {
+        let (domain, protocol) = match addr {
+            IpAddr::V4(_) => (libc::AF_INET, libc::IPPROTO_ICMP),
+            IpAddr::V6(_) => (libc::AF_INET6, libc::IPPROTO_ICMPV6),
+        };
+        // SAFETY: plain socket(2) call; the result is checked before use
+        let fd = unsafe { libc::socket(domain, libc::SOCK_DGRAM, protocol) };
+        if fd >= 0 {
+            return Ok(Self { fd, raw: false });
+        }
+        let err = io::Error::last_os_error();
+
+        if has_cap_net_raw() {
+            // SAFETY: as above
+            let fd = unsafe { libc::socket(domain, libc::SOCK_RAW, protocol) };
+            if fd >= 0 {
+                return Ok(Self { fd, raw: true });
+            }
+        }
+        Err(err)
+    }
+
+    fn send_echo(&self, dest: IpAddr, ident: u16, seq: u16) -> io::Result<()> {
+        let mut packet = echo_request(ident, seq);
+
+        let sent = match dest {
+            IpAddr::V4(v4) => {
+                // SAFETY: sockaddr_in is plain old data; all-zero is a valid starting value
+                let mut addr: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+                addr.sin_family = libc::AF_INET as libc::sa_family_t;
+                addr.sin_addr = libc::in_addr {
+                    s_addr: u32::from_ne_bytes(v4.octets()),
+                };
+                #[cfg(target_os = "macos")]
+                {
+                    addr.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+                }
+                // SAFETY: packet and addr are valid for the lengths passed
+                unsafe {
+                    libc::sendto(
+                        self.fd,
+                        packet.as_ptr() as *const libc::c_void,
+                        packet.len(),
+                        0,
+                        &addr as *const libc::sockaddr_in as *const libc::sockaddr,
+                        std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    )
+                }
+            }
+            IpAddr::V6(v6) => {
+                // The kernel fills in the ICMPv6 checksum, which covers a pseudo-header
+                packet[0] = ICMP6_ECHO_REQUEST;
+                packet[2..4].copy_from_slice(&[0, 0]);
+
+                // SAFETY: sockaddr_in6 is plain old data; all-zero is a valid starting value
+                let mut addr: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+                addr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                addr.sin6_addr = libc::in6_addr {
+                    s6_addr: v6.octets(),
+                };
+                #[cfg(target_os = "macos")]
+                {
+                    addr.sin6_len = std::mem::size_of::<libc::sockaddr_in6>() as u8;
+                }
+                // SAFETY: packet and addr are valid for the lengths passed
+                unsafe {
+                    libc::sendto(
+                        self.fd,
+                        packet.as_ptr() as *const libc::c_void,
+                        packet.len(),
+                        0,
+                        &addr as *const libc::sockaddr_in6 as *const libc::sockaddr,
+                        std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    )
+                }
+            }
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Wait until `deadline` for the reply to probe (ident, seq)
+    fn wait_reply(&self, ident: u16, seq: u16, deadline: Instant) -> io::Result<bool> {
+        let mut buffer = [0u8; 512];
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+
+            let mut poll_fd = libc::pollfd {
+                fd: self.fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: poll_fd is a single valid pollfd
+            let ready =
+                unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as libc::c_int) };
+            if ready < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            if ready == 0 {
+                return Ok(false);
+            }
+
+            // SAFETY: buffer is valid for its length
+            let received = unsafe {
+                libc::recv(
+                    self.fd,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    0,
+                )
+            };
+            if received < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let ident = self.raw.then_some(ident);
+            if is_echo_reply(&buffer[..received as usize], ident, seq) {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl Drop for IcmpSocket {
+    fn drop(&mut self) {
+        // SAFETY: fd was opened by this socket and is closed exactly once
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Whether a received packet is the echo reply to `seq` (and `ident`, when given).
+/// IPv4 packets from raw sockets, and from datagram sockets on macOS, still carry
+/// their IP header.
+fn is_echo_reply(packet: &[u8], ident: Option<u16>, seq: u16) -> bool {
+    let icmp = match packet.first() {
+        Some(byte) if byte >> 4 == 4 => {
+            match ip_header_len(packet).and_then(|len| packet.get(len..)) {
+                Some(icmp) => icmp,
+                None => return false,
+            }
+        }
+        _ => packet,
+    };
+    if icmp.len() < 8 || !matches!(icmp[0], ICMP_ECHO_REPLY | ICMP6_ECHO_REPLY) {
+        return false;
+    }
+    ident.map_or(true, |ident| {
+        u16::from_be_bytes([icmp[4], icmp[5]]) == ident
+    }) && u16::from_be_bytes([icmp[6], icmp[7]]) == seq
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_loss_and_rtt_over_last_probes() {
+        let mut window = PingWindow::default();
+        assert_eq!(window.next_seq(), 0);
+        assert_eq!(window.next_seq(), 1);
+        assert_eq!(window.summary().sent, 0);
+
+        for rtt in [Some(10.0), None, Some(20.0), Some(30.0)] {
+            window.record(rtt);
+        }
+        let summary = window.summary();
+        assert_eq!(summary.sent, 4);
+        assert_eq!(summary.received, 3);
+        assert_eq!(summary.loss_percent, 25.0);
+        assert_eq!(summary.min_rtt, 10.0);
+        assert_eq!(summary.avg_rtt, 20.0);
+        assert_eq!(summary.max_rtt, 30.0);
+        assert!((summary.stddev_rtt - (200.0_f32 / 3.0).sqrt()).abs() < 1e-4);
+        assert!(summary.last_answered);
+
+        // Only the last PING_WINDOW probes count: the early loss slides out
+        for _ in 0..PING_WINDOW - 1 {
+            window.record(Some(5.0));
+        }
+        window.record(None);
+        let summary = window.summary();
+        assert_eq!(summary.sent, PING_WINDOW as u32);
+        assert_eq!(summary.received, PING_WINDOW as u32 - 1);
+        assert!(!summary.last_answered);
+    }
+
+    #[test]
+    fn test_echo_reply_matching() {
+        let mut reply = echo_request(0x1234, 7);
+        reply[0] = ICMP_ECHO_REPLY;
+
+        // Linux datagram socket: bare ICMP, identifier rewritten by the kernel
+        assert!(is_echo_reply(&reply, None, 7));
+        assert!(!is_echo_reply(&reply, None, 8));
+
+        // Raw socket: IPv4 header first, and the identifier must match
+        let mut with_header = vec![0x45];
+        with_header.extend_from_slice(&[0; 19]);
+        with_header.extend_from_slice(&reply);
+        assert!(is_echo_reply(&with_header, Some(0x1234), 7));
+        assert!(!is_echo_reply(&with_header, Some(0x4321), 7));
+
+        // Our own request looped back is not a reply
+        assert!(!is_echo_reply(&echo_request(0x1234, 7), None, 7));
+
+        let mut reply6 = reply.clone();
+        reply6[0] = ICMP6_ECHO_REPLY;
+        assert!(is_echo_reply(&reply6, None, 7));
+    }
+}
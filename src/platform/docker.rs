@@ -0,0 +1,206 @@
+//! Container names for Docker's host-side interfaces
+//!
+//! `docker0` and `br-<id>` bridges are labelled with their Docker network's name and
+//! each `veth*` with the container whose interface is its peer. Everything comes from
+//! the Engine API on `/var/run/docker.sock`, which is only spoken with
+//! `--features docker`; without it, or when the socket is missing or unreadable,
+//! interfaces are simply left unlabelled.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Engine API version requested; supported by Docker 20.10 and later
+#[cfg(feature = "docker")]
+const API_VERSION: &str = "v1.41";
+
+#[cfg(feature = "docker")]
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Each API call gets this long before enrichment is abandoned
+#[cfg(feature = "docker")]
+const SOCKET_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Option holding a bridge's interface name when it isn't `br-` plus the network ID
+const BRIDGE_NAME_OPTION: &str = "com.docker.network.bridge.name";
+
+/// Interfaces Docker creates: its default bridge, user-defined bridges and veth pairs
+pub fn is_docker_interface(interface: &str) -> bool {
+    interface == "docker0" || interface.starts_with("br-") || interface.starts_with("veth")
+}
+
+/// Container (for `veth*`) or network (for bridges) name behind a Docker interface
+pub fn resolve_container_name(interface: &str) -> Option<String> {
+    resolve_container_names(&[interface.to_string()])
+        .pop()
+        .flatten()
+}
+
+/// [`resolve_container_name`] for several interfaces, querying Docker only once
+pub fn resolve_container_names(interfaces: &[String]) -> Vec<Option<String>> {
+    if !interfaces.iter().any(|name| is_docker_interface(name)) {
+        return vec![None; interfaces.len()];
+    }
+
+    let bridges = api_get("/networks")
+        .map(|networks| bridge_names(&networks))
+        .unwrap_or_default();
+    let containers = if interfaces.iter().any(|name| name.starts_with("veth")) {
+        containers_by_ifindex()
+    } else {
+        HashMap::new()
+    };
+
+    interfaces
+        .iter()
+        .map(|name| {
+            if name.starts_with("veth") {
+                peer_ifindex(name).and_then(|index| containers.get(&index).cloned())
+            } else {
+                bridges.get(name).cloned()
+            }
+        })
+        .collect()
+}
+
+/// Bridge interface name to Docker network name, from a `GET /networks` response
+fn bridge_names(networks: &Value) -> HashMap<String, String> {
+    let Some(networks) = networks.as_array() else {
+        return HashMap::new();
+    };
+
+    networks
+        .iter()
+        .filter(|network| network["Driver"] == "bridge")
+        .filter_map(|network| {
+            let name = network["Name"].as_str()?;
+            let bridge = match network["Options"][BRIDGE_NAME_OPTION].as_str() {
+                Some(bridge) => bridge.to_string(),
+                None => format!("br-{}", network["Id"].as_str()?.get(..12)?),
+            };
+            Some((bridge, name.to_string()))
+        })
+        .collect()
+}
+
+/// (ID, name) of each running container, from a `GET /containers/json` response
+fn container_names(containers: &Value) -> Vec<(String, String)> {
+    let Some(containers) = containers.as_array() else {
+        return Vec::new();
+    };
+
+    containers
+        .iter()
+        .filter_map(|container| {
+            let id = container["Id"].as_str()?;
+            let name = container["Names"][0].as_str()?;
+            Some((id.to_string(), name.trim_start_matches('/').to_string()))
+        })
+        .collect()
+}
+
+/// Container name for the ifindex of every interface inside a running container.
+/// Reading another network namespace's sysfs through /proc/PID/root needs root.
+fn containers_by_ifindex() -> HashMap<u32, String> {
+    let mut by_ifindex = HashMap::new();
+    let Some(containers) = api_get("/containers/json") else {
+        return by_ifindex;
+    };
+
+    for (id, name) in container_names(&containers) {
+        let Some(pid) = api_get(&format!("/containers/{id}/json"))
+            .and_then(|inspect| inspect["State"]["Pid"].as_u64())
+            .filter(|&pid| pid > 0)
+        else {
+            continue;
+        };
+        let Ok(entries) = std::fs::read_dir(format!("/proc/{pid}/root/sys/class/net")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Some(index) = read_index(&entry.path().join("ifindex")) {
+                by_ifindex.insert(index, name.clone());
+            }
+        }
+    }
+    by_ifindex
+}
+
+/// ifindex of the other end of a host veth
+fn peer_ifindex(interface: &str) -> Option<u32> {
+    read_index(
+        &std::path::Path::new("/sys/class/net")
+            .join(interface)
+            .join("iflink"),
+    )
+}
+
+fn read_index(path: &std::path::Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Body of a complete HTTP/1.0 response, if the status was 200
+#[cfg(any(feature = "docker", test))]
+fn response_body(response: &str) -> Option<&str> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?;
+    (status == "200").then_some(body)
+}
+
+/// GET an Engine API path over the Docker socket and parse the JSON reply
+#[cfg(feature = "docker")]
+fn api_get(path: &str) -> Option<Value> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(DOCKER_SOCKET).ok()?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT)).ok()?;
+    // HTTP/1.0 gets a plain body terminated by the server closing the connection
+    write!(
+        stream,
+        "GET /{API_VERSION}{path} HTTP/1.0\r\nHost: docker\r\n\r\n"
+    )
+    .ok()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    serde_json::from_str(response_body(&response)?).ok()
+}
+
+#[cfg(not(feature = "docker"))]
+fn api_get(_path: &str) -> Option<Value> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridges_and_containers_from_api_responses() {
+        let response = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n[\
+            {\"Name\":\"bridge\",\"Id\":\"5f3c\",\"Driver\":\"bridge\",\
+             \"Options\":{\"com.docker.network.bridge.name\":\"docker0\"}},\
+            {\"Name\":\"web\",\"Id\":\"abc123def4567890\",\"Driver\":\"bridge\",\"Options\":{}},\
+            {\"Name\":\"host\",\"Id\":\"0e1f\",\"Driver\":\"host\",\"Options\":{}}]";
+        let networks: Value = serde_json::from_str(response_body(response).unwrap()).unwrap();
+        let bridges = bridge_names(&networks);
+        assert_eq!(bridges.len(), 2);
+        assert_eq!(bridges["docker0"], "bridge");
+        assert_eq!(bridges["br-abc123def456"], "web");
+
+        assert_eq!(response_body("HTTP/1.0 404 Not Found\r\n\r\n{}"), None);
+        assert_eq!(response_body("HTTP/1.0 200 OK\r\n"), None);
+
+        let containers: Value =
+            serde_json::from_str(r#"[{"Id":"c0ffee","Names":["/nginx"]},{"Id":"bad"}]"#).unwrap();
+        assert_eq!(
+            container_names(&containers),
+            vec![("c0ffee".to_string(), "nginx".to_string())]
+        );
+
+        assert!(is_docker_interface("veth3a2b4c"));
+        assert!(!is_docker_interface("eth0"));
+        assert_eq!(resolve_container_names(&["eth0".to_string()]), vec![None]);
+    }
+}
@@ -1,114 +1,874 @@
+use super::{parse_proc_net_dev, proc_net_dev_devices, InterfaceEvent};
 use crate::{
-    device::{NetworkReader, NetworkStats},
+    device::{DeviceInfo, InterfaceKind, NetworkReader, NetworkStats, WirelessInfo},
     error::{NetwatchError, Result},
 };
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::fs;
-use std::time::SystemTime;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
-pub struct LinuxReader;
+const PROC_NET_DEV: &str = "/proc/net/dev";
+const SYS_CLASS_NET: &str = "/sys/class/net";
+const PROC_NET_WIRELESS: &str = "/proc/net/wireless";
+const PROC_NET_VLAN_CONFIG: &str = "/proc/net/vlan/config";
+const SYS_DEVICES_VIRTUAL_NET: &str = "/sys/devices/virtual/net";
 
-impl Default for LinuxReader {
-    fn default() -> Self {
-        Self::new()
+// ARPHRD_* link types in /sys/class/net/*/type
+const ARPHRD_ETHER: u32 = 1;
+const ARPHRD_LOOPBACK: u32 = 772;
+const ARPHRD_NONE: u32 = 65534;
+
+// SIOCETHTOOL sub-commands and the string set `ethtool -S` reads
+const ETHTOOL_GSTRINGS: u32 = 0x1b;
+const ETHTOOL_GSTATS: u32 = 0x1d;
+const ETHTOOL_GSSET_INFO: u32 = 0x37;
+const ETH_SS_STATS: u32 = 1;
+const ETH_GSTRING_LEN: usize = 32;
+
+/// Interface events kept for the dashboard before the oldest are dropped
+pub const MAX_QUEUED_EVENTS: usize = 256;
+/// How long the netlink listener waits in recv(2) before checking whether to stop
+const NETLINK_POLL: Duration = Duration::from_secs(1);
+const NLMSG_HEADER_LEN: usize = std::mem::size_of::<libc::nlmsghdr>();
+const IFINFOMSG_LEN: usize = std::mem::size_of::<libc::ifinfomsg>();
+
+/// Move this thread into the network namespace at `path` (`--netns`). Threads started
+/// afterwards inherit it, and /proc/net/* then describes that namespace.
+pub fn enter_network_namespace(path: &str) -> Result<()> {
+    let file = fs::File::open(path)?;
+    // SAFETY: setns(2) only reads the fd, which stays open for the call
+    if unsafe { libc::setns(file.as_raw_fd(), libc::CLONE_NEWNET) } == 0 {
+        return Ok(());
     }
+
+    let err = std::io::Error::last_os_error();
+    Err(match err.raw_os_error() {
+        Some(libc::EPERM) => NetwatchError::PermissionDenied(format!(
+            "entering network namespace {path} needs CAP_SYS_ADMIN; run netwatch as root"
+        )),
+        _ => NetwatchError::Platform(format!("Cannot enter network namespace {path}: {err}")),
+    })
 }
 
-impl LinuxReader {
-    pub fn new() -> Self {
-        Self
+/// The driver's `ethtool -S` counters for `interface`, by name. Empty where the driver
+/// has none (most virtual interfaces) or the ioctl is refused.
+pub fn ethtool_stats(interface: &str) -> HashMap<String, u64> {
+    read_ethtool_stats(interface).unwrap_or_default()
+}
+
+fn read_ethtool_stats(interface: &str) -> io::Result<HashMap<String, u64>> {
+    // SAFETY: plain socket(2) call; the result is checked before use
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fd was just opened and nothing else owns it
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // struct ethtool_sset_info asking for the size of the stats string set
+    #[repr(C)]
+    struct SsetInfo {
+        cmd: u32,
+        reserved: u32,
+        sset_mask: u64,
+        count: u32,
+    }
+    let mut info = SsetInfo {
+        cmd: ETHTOOL_GSSET_INFO,
+        reserved: 0,
+        sset_mask: 1 << ETH_SS_STATS,
+        count: 0,
+    };
+    ethtool_ioctl(
+        &socket,
+        interface,
+        &mut info as *mut SsetInfo as *mut libc::c_char,
+    )?;
+    // The kernel clears the bit when the driver has no such set
+    let count = if info.sset_mask == 0 {
+        0
+    } else {
+        info.count as usize
+    };
+    if count == 0 {
+        return Ok(HashMap::new());
+    }
+
+    // struct ethtool_gstrings: cmd, string_set, len, then len fixed-size names
+    let mut strings = vec![0u32; 3 + count * ETH_GSTRING_LEN / 4];
+    strings[..3].copy_from_slice(&[ETHTOOL_GSTRINGS, ETH_SS_STATS, count as u32]);
+    ethtool_ioctl(
+        &socket,
+        interface,
+        strings.as_mut_ptr() as *mut libc::c_char,
+    )?;
+    let names: Vec<u8> = strings[3..]
+        .iter()
+        .flat_map(|word| word.to_ne_bytes())
+        .collect();
+
+    // struct ethtool_stats: cmd and n_stats packed in the first word, then the values
+    let mut values = vec![0u64; 1 + count];
+    let mut header = [0u8; 8];
+    header[..4].copy_from_slice(&ETHTOOL_GSTATS.to_ne_bytes());
+    header[4..].copy_from_slice(&(count as u32).to_ne_bytes());
+    values[0] = u64::from_ne_bytes(header);
+    ethtool_ioctl(&socket, interface, values.as_mut_ptr() as *mut libc::c_char)?;
+
+    Ok(gstring_names(&names)
+        .into_iter()
+        .zip(values[1..].iter().copied())
+        .collect())
+}
+
+/// One SIOCETHTOOL request for `interface` with `data` as its command buffer
+fn ethtool_ioctl(socket: &OwnedFd, interface: &str, data: *mut libc::c_char) -> io::Result<()> {
+    if interface.len() >= libc::IFNAMSIZ {
+        return Err(io::Error::from(io::ErrorKind::InvalidInput));
+    }
+    // SAFETY: ifreq is plain old data; all-zero is a valid starting value
+    let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in request.ifr_name.iter_mut().zip(interface.bytes()) {
+        *dst = src as libc::c_char;
     }
+    request.ifr_ifru.ifru_data = data;
 
-    fn parse_proc_net_dev(&self, content: &str, device: &str) -> Result<NetworkStats> {
-        for line in content.lines().skip(2) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.is_empty() {
-                continue;
+    // SAFETY: the name is NUL-terminated and data points at a buffer the caller sized
+    // for the command in it
+    if unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCETHTOOL as _, &mut request) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Counter names from an ETHTOOL_GSTRINGS reply: NUL-padded ETH_GSTRING_LEN-byte slots
+fn gstring_names(data: &[u8]) -> Vec<String> {
+    data.chunks(ETH_GSTRING_LEN)
+        .map(|slot| {
+            let len = slot.iter().position(|&b| b == 0).unwrap_or(slot.len());
+            String::from_utf8_lossy(&slot[..len]).into_owned()
+        })
+        .collect()
+}
+
+/// Listens on the RTMGRP_LINK netlink group and queues an [`InterfaceEvent`] whenever an
+/// interface appears, disappears or goes up or down. The listening thread stops within
+/// [`NETLINK_POLL`] once this is dropped.
+pub struct InterfaceEventWatcher {
+    stop: Arc<AtomicBool>,
+}
+
+impl InterfaceEventWatcher {
+    /// Subscribe and start listening; `queue` is never grown past [`MAX_QUEUED_EVENTS`]
+    pub fn spawn(queue: Arc<Mutex<VecDeque<InterfaceEvent>>>) -> io::Result<Self> {
+        let socket = open_link_socket()?;
+        // The current interfaces, so only changes from here on become events
+        request_link_dump(&socket)?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        thread::Builder::new()
+            .name("netlink-link".to_string())
+            .spawn(move || watch_links(&socket, &queue, &stopped))?;
+        Ok(Self { stop })
+    }
+}
+
+impl Drop for InterfaceEventWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn open_link_socket() -> io::Result<OwnedFd> {
+    // SAFETY: plain socket(2) call; the result is checked before use
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            libc::NETLINK_ROUTE,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fd was just opened and nothing else owns it
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // SAFETY: sockaddr_nl is plain old data; all-zero is a valid starting value
+    let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    addr.nl_groups = libc::RTMGRP_LINK as u32;
+    // SAFETY: addr is valid for the length passed
+    let bound = unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if bound < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // A receive timeout lets the thread notice the watcher was dropped
+    let timeout = libc::timeval {
+        tv_sec: NETLINK_POLL.as_secs() as libc::time_t,
+        tv_usec: 0,
+    };
+    // SAFETY: the option value is a timeval valid for the length passed
+    let set = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            &timeout as *const libc::timeval as *const libc::c_void,
+            std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+        )
+    };
+    if set < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(socket)
+}
+
+/// Ask for every interface as RTM_NEWLINK messages, ending with NLMSG_DONE
+fn request_link_dump(socket: &OwnedFd) -> io::Result<()> {
+    // struct nlmsghdr followed by struct rtgenmsg, padded to NLMSG_ALIGNTO
+    let length = NLMSG_HEADER_LEN + 4;
+    let mut request = Vec::with_capacity(length);
+    request.extend_from_slice(&(length as u32).to_ne_bytes());
+    request.extend_from_slice(&libc::RTM_GETLINK.to_ne_bytes());
+    request.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    request.extend_from_slice(&1u32.to_ne_bytes());
+    request.extend_from_slice(&0u32.to_ne_bytes());
+    request.extend_from_slice(&[libc::AF_UNSPEC as u8, 0, 0, 0]);
+
+    // SAFETY: request is valid for the length passed
+    let sent = unsafe {
+        libc::send(
+            socket.as_raw_fd(),
+            request.as_ptr() as *const libc::c_void,
+            request.len(),
+            0,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn watch_links(socket: &OwnedFd, queue: &Mutex<VecDeque<InterfaceEvent>>, stop: &AtomicBool) {
+    let mut links = LinkStates::default();
+    let mut buffer = vec![0u8; 32 * 1024];
+    while !stop.load(Ordering::Relaxed) {
+        // SAFETY: buffer is valid for the length passed
+        let length = unsafe {
+            libc::recv(
+                socket.as_raw_fd(),
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+                0,
+            )
+        };
+        if length < 0 {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::EAGAIN | libc::EINTR) => continue,
+                // The kernel overflowed the socket and notifications were lost; a fresh
+                // dump reports the links that appeared or changed state in the meantime
+                Some(libc::ENOBUFS) if request_link_dump(socket).is_ok() => continue,
+                _ => return,
             }
+        }
 
-            let iface_name = parts[0].trim_end_matches(':');
-            if iface_name == device {
-                return Ok(NetworkStats {
-                    timestamp: SystemTime::now(),
-                    bytes_in: parts.get(1).unwrap_or(&"0").parse().unwrap_or(0),
-                    packets_in: parts.get(2).unwrap_or(&"0").parse().unwrap_or(0),
-                    errors_in: parts.get(3).unwrap_or(&"0").parse().unwrap_or(0),
-                    drops_in: parts.get(4).unwrap_or(&"0").parse().unwrap_or(0),
-                    bytes_out: parts.get(9).unwrap_or(&"0").parse().unwrap_or(0),
-                    packets_out: parts.get(10).unwrap_or(&"0").parse().unwrap_or(0),
-                    errors_out: parts.get(11).unwrap_or(&"0").parse().unwrap_or(0),
-                    drops_out: parts.get(12).unwrap_or(&"0").parse().unwrap_or(0),
-                });
+        let events: Vec<InterfaceEvent> = parse_link_messages(&buffer[..length as usize])
+            .into_iter()
+            .flat_map(|message| links.apply(message))
+            .collect();
+        if events.is_empty() {
+            continue;
+        }
+        let mut queue = queue.lock().unwrap_or_else(|e| e.into_inner());
+        for event in events {
+            if queue.len() >= MAX_QUEUED_EVENTS {
+                queue.pop_front();
             }
+            queue.push_back(event);
+        }
+    }
+}
+
+/// One decoded netlink message about links
+#[derive(Debug, Clone, PartialEq)]
+enum LinkMessage {
+    /// RTM_NEWLINK, or RTM_DELLINK when `deleted`
+    Link {
+        index: i32,
+        name: String,
+        up: bool,
+        deleted: bool,
+    },
+    /// NLMSG_DONE: the end of a dump
+    DumpDone,
+}
+
+/// The link messages in one datagram from a NETLINK_ROUTE socket; anything else, and
+/// anything truncated, is skipped
+fn parse_link_messages(data: &[u8]) -> Vec<LinkMessage> {
+    let mut messages = Vec::new();
+    let mut offset = 0;
+    while data.len() - offset >= NLMSG_HEADER_LEN {
+        let message = &data[offset..];
+        let length = u32::from_ne_bytes([message[0], message[1], message[2], message[3]]) as usize;
+        if length < NLMSG_HEADER_LEN || length > message.len() {
+            break;
         }
+        let kind = u16::from_ne_bytes([message[4], message[5]]);
+        let payload = &message[NLMSG_HEADER_LEN..length];
 
-        Err(NetwatchError::DeviceNotFound(device.to_string()))
+        if kind == libc::NLMSG_DONE as u16 {
+            messages.push(LinkMessage::DumpDone);
+        } else if kind == libc::RTM_NEWLINK || kind == libc::RTM_DELLINK {
+            messages.extend(parse_link(payload, kind == libc::RTM_DELLINK));
+        }
+        offset += netlink_align(length).min(message.len());
     }
+    messages
 }
 
-impl NetworkReader for LinuxReader {
-    fn list_devices(&self) -> Result<Vec<String>> {
-        let content = fs::read_to_string("/proc/net/dev")?;
-        let mut devices = Vec::new();
-
-        for line in content.lines().skip(2) {
-            if let Some(device_part) = line.split(':').next() {
-                let device_name = device_part.trim().to_string();
-                if !device_name.is_empty() {
-                    devices.push(device_name);
+/// struct ifinfomsg and the IFLA_IFNAME and IFLA_OPERSTATE attributes after it
+fn parse_link(payload: &[u8], deleted: bool) -> Option<LinkMessage> {
+    let info = payload.get(..IFINFOMSG_LEN)?;
+    let index = i32::from_ne_bytes([info[4], info[5], info[6], info[7]]);
+    let flags = u32::from_ne_bytes([info[8], info[9], info[10], info[11]]);
+
+    let mut name = None;
+    let mut operstate = libc::IF_OPER_UNKNOWN as u8;
+    let mut attributes = &payload[netlink_align(IFINFOMSG_LEN).min(payload.len())..];
+    while attributes.len() >= 4 {
+        let length = u16::from_ne_bytes([attributes[0], attributes[1]]) as usize;
+        if length < 4 || length > attributes.len() {
+            break;
+        }
+        let kind = u16::from_ne_bytes([attributes[2], attributes[3]]);
+        let value = &attributes[4..length];
+        if kind == libc::IFLA_IFNAME {
+            let end = value.iter().position(|&b| b == 0).unwrap_or(value.len());
+            name = Some(String::from_utf8_lossy(&value[..end]).into_owned());
+        } else if kind == libc::IFLA_OPERSTATE {
+            operstate = value.first().copied().unwrap_or(operstate);
+        }
+        attributes = &attributes[netlink_align(length).min(attributes.len())..];
+    }
+
+    // Drivers that do not track carrier (lo, tun) report UNKNOWN while up and working
+    let up = operstate == libc::IF_OPER_UP as u8
+        || (operstate == libc::IF_OPER_UNKNOWN as u8 && flags & libc::IFF_UP as u32 != 0);
+    Some(LinkMessage::Link {
+        index,
+        name: name?,
+        up,
+        deleted,
+    })
+}
+
+/// Netlink messages and attributes start on 4-byte boundaries
+fn netlink_align(length: usize) -> usize {
+    (length + 3) & !3
+}
+
+/// Each interface's last known name and state, to turn link messages into events
+#[derive(Debug, Default)]
+struct LinkStates {
+    // Name and whether it is up, by interface index
+    links: HashMap<i32, (String, bool)>,
+    // Whether the startup dump is complete; the links it lists are not events
+    seeded: bool,
+}
+
+impl LinkStates {
+    fn apply(&mut self, message: LinkMessage) -> Vec<InterfaceEvent> {
+        let LinkMessage::Link {
+            index,
+            name,
+            up,
+            deleted,
+        } = message
+        else {
+            self.seeded = true;
+            return Vec::new();
+        };
+
+        let previous = if deleted {
+            self.links.remove(&index)
+        } else {
+            self.links.insert(index, (name.clone(), up))
+        };
+        if !self.seeded {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        match previous {
+            _ if deleted => events.push(InterfaceEvent::Removed(name)),
+            None => events.push(InterfaceEvent::Added(name)),
+            Some((previous_name, was_up)) => {
+                // A rename looks like the old name going away and the new one appearing
+                if previous_name != name {
+                    events.push(InterfaceEvent::Removed(previous_name));
+                    events.push(InterfaceEvent::Added(name.clone()));
+                }
+                if up != was_up {
+                    events.push(if up {
+                        InterfaceEvent::Up(name)
+                    } else {
+                        InterfaceEvent::Down(name)
+                    });
                 }
             }
         }
+        events
+    }
+}
 
-        // Filter out loopback and virtual interfaces by default
-        devices.retain(|name| {
-            !name.starts_with("lo")
-                && !name.starts_with("docker")
-                && !name.starts_with("veth")
-                && !name.starts_with("br-")
-        });
+pub struct LinuxReader {
+    // Counters file in /proc/net/dev format; another one for `--proc-net-dev`
+    proc_net_dev: PathBuf,
+}
 
-        Ok(devices)
+impl Default for LinuxReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinuxReader {
+    pub fn new() -> Self {
+        Self::with_proc_net_dev(PROC_NET_DEV)
+    }
+
+    /// Read counters from `path` instead of /proc/net/dev, e.g. /proc/PID/net/dev to see
+    /// the network namespace PID is in without entering it
+    pub fn with_proc_net_dev(path: impl Into<PathBuf>) -> Self {
+        Self {
+            proc_net_dev: path.into(),
+        }
+    }
+}
+
+impl NetworkReader for LinuxReader {
+    fn list_devices(&self) -> Result<Vec<String>> {
+        let content = fs::read_to_string(&self.proc_net_dev)?;
+        Ok(proc_net_dev_devices(&content))
     }
 
     fn read_stats(&self, device: &str) -> Result<NetworkStats> {
-        let content = fs::read_to_string("/proc/net/dev")?;
-        self.parse_proc_net_dev(&content, device)
+        let content = fs::read_to_string(&self.proc_net_dev)?;
+        parse_proc_net_dev(&content, device)
     }
 
     fn is_available(&self) -> bool {
-        std::path::Path::new("/proc/net/dev").exists()
+        self.proc_net_dev.exists()
+    }
+
+    fn read_info(&self, device: &str) -> DeviceInfo {
+        if device.contains('/') || device.contains("..") {
+            return DeviceInfo::default();
+        }
+        let mut info = read_link_info(&Path::new(SYS_CLASS_NET).join(device));
+        if info.parent.is_none() {
+            // VLANs not named LINK.VID; 8021q lists them all, but only to root
+            if let Some(link) = fs::read_to_string(PROC_NET_VLAN_CONFIG)
+                .ok()
+                .and_then(|config| parse_proc_net_vlan(&config, device))
+            {
+                info.parent = Some(link);
+                info.kind = InterfaceKind::Vlan;
+            }
+        }
+        info
+    }
+
+    fn read_wireless(&self, device: &str) -> Option<WirelessInfo> {
+        parse_proc_net_wireless(&fs::read_to_string(PROC_NET_WIRELESS).ok()?, device)
+    }
+
+    fn read_addresses(&self) -> Option<HashMap<String, BTreeSet<std::net::IpAddr>>> {
+        // A --proc-net-dev file may describe another namespace's interfaces
+        if self.proc_net_dev != Path::new(PROC_NET_DEV) {
+            return None;
+        }
+        super::interface_addresses().ok()
     }
+
+    fn is_virtual(&self, device: &str) -> Option<bool> {
+        if self.proc_net_dev != Path::new(PROC_NET_DEV)
+            || device.contains('/')
+            || device.contains("..")
+        {
+            return None;
+        }
+        // Software interfaces have no bus device, so the kernel files them here
+        Some(Path::new(SYS_DEVICES_VIRTUAL_NET).join(device).exists())
+    }
+}
+
+/// Signal and noise of `device` from /proc/net/wireless, which has a line per wireless
+/// interface: `wlan0: 0000   70.  -40.  -256  ...` (status, link quality, level, noise).
+/// It has no rate or channel; those need nl80211 and stay None.
+fn parse_proc_net_wireless(content: &str, device: &str) -> Option<WirelessInfo> {
+    let fields = content.lines().skip(2).find_map(|line| {
+        let (name, fields) = line.split_once(':')?;
+        (name.trim() == device).then_some(fields)
+    })?;
+    // A trailing '.' marks a value updated since the last read
+    let mut values = fields
+        .split_whitespace()
+        .skip(2)
+        .map(|value| value.trim_end_matches('.').parse::<i32>().ok());
+    let level = values.next().flatten();
+    let noise = values.next().flatten();
+
+    // Old drivers report dBm as an unsigned byte. 0 means not associated, and cfg80211
+    // reports a noise of -256 when the driver has no figure.
+    let dbm = |value: Option<i32>| {
+        value
+            .map(|value| if value > 0 { value - 256 } else { value })
+            .filter(|value| (-255..0).contains(value))
+    };
+    dbm(level).map(|rssi_dbm| WirelessInfo {
+        rssi_dbm: Some(rssi_dbm),
+        noise_dbm: dbm(noise),
+        ..WirelessInfo::default()
+    })
+}
+
+/// Link metadata from an interface's /sys/class/net directory. Reading speed, duplex or
+/// carrier fails while the link is down, so each attribute is optional on its own.
+fn read_link_info(dir: &Path) -> DeviceInfo {
+    let attribute = |name: &str| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    // Virtual interfaces report -1, or fail the read
+    let speed_mbps = attribute("speed")
+        .and_then(|speed| speed.parse::<i64>().ok())
+        .and_then(|speed| u64::try_from(speed).ok())
+        .filter(|&speed| speed > 0);
+    let duplex = attribute("duplex").filter(|duplex| duplex == "full" || duplex == "half");
+    let mac = attribute("address").filter(|mac| mac.bytes().any(|b| b != b'0' && b != b':'));
+
+    // Bridge ports and bond slaves link to their master; VLANs are named LINK.VID
+    let master = fs::read_link(dir.join("master"))
+        .ok()
+        .and_then(|master| Some(master.file_name()?.to_str()?.to_string()));
+    let vlan_link = dir
+        .file_name()
+        .and_then(|name| name.to_str()?.rsplit_once('.'))
+        .filter(|(link, vid)| vid.parse::<u16>().is_ok() && dir.with_file_name(link).exists())
+        .map(|(link, _)| link.to_string());
+
+    let kind = match attribute("type").and_then(|kind| kind.parse().ok()) {
+        Some(ARPHRD_LOOPBACK) => InterfaceKind::Loopback,
+        Some(ARPHRD_NONE) => InterfaceKind::Tun,
+        // Wi-Fi, bridges and taps all present themselves as Ethernet
+        Some(ARPHRD_ETHER) if dir.join("wireless").exists() || dir.join("phy80211").exists() => {
+            InterfaceKind::Wifi
+        }
+        Some(ARPHRD_ETHER) if dir.join("bridge").exists() => InterfaceKind::Bridge,
+        Some(ARPHRD_ETHER) if dir.join("bonding").exists() => InterfaceKind::Bond,
+        Some(ARPHRD_ETHER) if vlan_link.is_some() => InterfaceKind::Vlan,
+        Some(ARPHRD_ETHER) if dir.join("tun_flags").exists() => InterfaceKind::Tun,
+        Some(ARPHRD_ETHER) => InterfaceKind::Ethernet,
+        _ => InterfaceKind::Unknown,
+    };
+
+    DeviceInfo {
+        speed_mbps,
+        duplex,
+        mtu: attribute("mtu").and_then(|mtu| mtu.parse().ok()),
+        mac,
+        operstate: attribute("operstate"),
+        carrier: attribute("carrier").map(|carrier| carrier == "1"),
+        kind,
+        parent: master.or(vlan_link),
+        // Only interfaces backed by a bus device have a `device` link
+        physical: Some(dir.join("device").exists()),
+    }
+}
+
+/// Link `device` sits on, from /proc/net/vlan/config's `NAME | VID | LINK` rows
+fn parse_proc_net_vlan(content: &str, device: &str) -> Option<String> {
+    content.lines().skip(2).find_map(|line| {
+        let mut fields = line.split('|').map(str::trim);
+        if fields.next()? != device {
+            return None;
+        }
+        fields
+            .nth(1)
+            .filter(|link| !link.is_empty())
+            .map(str::to_string)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_gstring_names() {
+        let mut data = vec![0u8; 2 * ETH_GSTRING_LEN];
+        data[..13].copy_from_slice(b"rx_crc_errors");
+        data[ETH_GSTRING_LEN..][..17].copy_from_slice(b"tx_carrier_errors");
+        assert_eq!(gstring_names(&data), ["rx_crc_errors", "tx_carrier_errors"]);
+
+        // lo has no driver statistics
+        assert!(ethtool_stats("lo").is_empty());
+    }
+
+    /// An RTM_NEWLINK or RTM_DELLINK message as the kernel sends it
+    fn link_message(kind: u16, index: i32, name: &str, operstate: u8, flags: u32) -> Vec<u8> {
+        let mut attributes = Vec::new();
+        let mut ifname = name.as_bytes().to_vec();
+        ifname.push(0);
+        for (kind, value) in [
+            (libc::IFLA_IFNAME, ifname),
+            (libc::IFLA_OPERSTATE, vec![operstate]),
+        ] {
+            attributes.extend_from_slice(&((4 + value.len()) as u16).to_ne_bytes());
+            attributes.extend_from_slice(&kind.to_ne_bytes());
+            attributes.extend_from_slice(&value);
+            attributes.resize(netlink_align(attributes.len()), 0);
+        }
+
+        let length = NLMSG_HEADER_LEN + IFINFOMSG_LEN + attributes.len();
+        let mut message = Vec::new();
+        message.extend_from_slice(&(length as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.resize(NLMSG_HEADER_LEN + 4, 0);
+        message.extend_from_slice(&index.to_ne_bytes());
+        message.extend_from_slice(&flags.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&attributes);
+        message
+    }
+
+    #[test]
+    fn test_link_messages_become_interface_events() {
+        let up = libc::IF_OPER_UP as u8;
+        let down = libc::IF_OPER_DOWN as u8;
+        let iff_up = libc::IFF_UP as u32;
+
+        // The startup dump: eth0 up, lo up with no carrier tracking
+        let mut dump = link_message(libc::RTM_NEWLINK, 2, "eth0", up, iff_up);
+        dump.extend(link_message(libc::RTM_NEWLINK, 1, "lo", 0, iff_up));
+        let mut done = vec![0u8; NLMSG_HEADER_LEN + 4];
+        done[..4].copy_from_slice(&((NLMSG_HEADER_LEN + 4) as u32).to_ne_bytes());
+        done[4..6].copy_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        dump.extend(done);
+
+        let messages = parse_link_messages(&dump);
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(&messages[1], LinkMessage::Link { name, up: true, .. } if name == "lo"));
+
+        let mut links = LinkStates::default();
+        let events: Vec<_> = messages.into_iter().flat_map(|m| links.apply(m)).collect();
+        assert!(events.is_empty());
+
+        let mut apply = |message: Vec<u8>| -> Vec<InterfaceEvent> {
+            parse_link_messages(&message)
+                .into_iter()
+                .flat_map(|m| links.apply(m))
+                .collect()
+        };
+        assert_eq!(
+            apply(link_message(libc::RTM_NEWLINK, 2, "eth0", down, iff_up)),
+            [InterfaceEvent::Down("eth0".to_string())]
+        );
+        // Other attribute changes of a link in the same state are not events
+        assert!(apply(link_message(libc::RTM_NEWLINK, 2, "eth0", down, 0)).is_empty());
+        assert_eq!(
+            apply(link_message(libc::RTM_NEWLINK, 7, "veth1", down, 0)),
+            [InterfaceEvent::Added("veth1".to_string())]
+        );
+        assert_eq!(
+            apply(link_message(libc::RTM_DELLINK, 7, "veth1", down, 0)),
+            [InterfaceEvent::Removed("veth1".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_read_link_info() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, value) in [
+            ("speed", "10000\n"),
+            ("duplex", "full\n"),
+            ("mtu", "9000\n"),
+            ("address", "52:54:00:12:34:56\n"),
+            ("operstate", "up\n"),
+            ("carrier", "1\n"),
+            ("type", "1\n"),
+        ] {
+            fs::write(dir.path().join(name), value).unwrap();
+        }
+        let info = read_link_info(dir.path());
+        assert_eq!(info.speed_mbps, Some(10000));
+        assert_eq!(info.duplex.as_deref(), Some("full"));
+        assert_eq!(info.mtu, Some(9000));
+        assert_eq!(info.mac.as_deref(), Some("52:54:00:12:34:56"));
+        assert_eq!(info.carrier, Some(true));
+        assert_eq!(info.kind, InterfaceKind::Ethernet);
+        assert_eq!(info.capacity(), 1_250_000_000);
+
+        // A bridge reports no speed and falls back to the default link speed
+        fs::write(dir.path().join("speed"), "-1\n").unwrap();
+        fs::write(dir.path().join("duplex"), "unknown\n").unwrap();
+        fs::create_dir(dir.path().join("bridge")).unwrap();
+        let info = read_link_info(dir.path());
+        assert_eq!((info.speed_mbps, info.duplex.as_deref()), (None, None));
+        assert_eq!(info.kind, InterfaceKind::Bridge);
+        assert_eq!(info.capacity(), 125_000_000);
+
+        let lo = LinuxReader::new().read_info("lo");
+        assert_eq!(lo.kind, InterfaceKind::Loopback);
+        assert_eq!(lo.mac, None);
+    }
+
+    #[test]
+    fn test_interface_hierarchy() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["eth0", "eth1", "eth1.100", "bond0"] {
+            fs::create_dir(root.path().join(name)).unwrap();
+            fs::write(root.path().join(name).join("type"), "1\n").unwrap();
+        }
+        fs::create_dir(root.path().join("eth0/device")).unwrap();
+        fs::create_dir(root.path().join("bond0/bonding")).unwrap();
+        std::os::unix::fs::symlink("../../virtual/net/bond0", root.path().join("eth0/master"))
+            .unwrap();
+
+        let eth0 = read_link_info(&root.path().join("eth0"));
+        assert_eq!(eth0.parent.as_deref(), Some("bond0"));
+        assert_eq!(
+            (eth0.kind, eth0.physical),
+            (InterfaceKind::Ethernet, Some(true))
+        );
+        let bond0 = read_link_info(&root.path().join("bond0"));
+        assert_eq!(bond0.parent, None);
+        assert_eq!(
+            (bond0.kind, bond0.physical),
+            (InterfaceKind::Bond, Some(false))
+        );
+        let vlan = read_link_info(&root.path().join("eth1.100"));
+        assert_eq!(vlan.parent.as_deref(), Some("eth1"));
+        assert_eq!(vlan.kind, InterfaceKind::Vlan);
+
+        let config = "VLAN Dev name    | VLAN ID\n\
+                      Name-Type: VLAN_NAME_TYPE_RAW_PLUS_VID_NO_PAD\n\
+                      eth1.100       | 100  | eth1\n\
+                      vlan200        | 200  | bond0\n";
+        assert_eq!(
+            parse_proc_net_vlan(config, "vlan200").as_deref(),
+            Some("bond0")
+        );
+        assert_eq!(parse_proc_net_vlan(config, "eth1"), None);
+    }
+
+    #[test]
+    fn test_interface_addresses() {
+        let addresses = super::super::interface_addresses().unwrap();
+        assert!(addresses
+            .get("lo")
+            .is_some_and(|lo| lo.contains(&std::net::IpAddr::from([127, 0, 0, 1]))));
+    }
+
+    #[test]
+    fn test_parse_proc_net_wireless() {
+        let content = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+wlp2s0: 0000   54.  -56.  -256        0      0      0     12     41        0
+ wlan1: 0000   70.  216.  161.        0      0      0      0      0        0
+ wlan2: 0000    0     0     0         0      0      0      0      0        0
+";
+        let info = parse_proc_net_wireless(content, "wlp2s0").unwrap();
+        assert_eq!(info.rssi_dbm, Some(-56));
+        assert_eq!(info.noise_dbm, None);
+        assert_eq!(info.snr_db(), None);
+        assert_eq!(info.channel, None);
+
+        // Unsigned byte encoding of an older driver
+        let info = parse_proc_net_wireless(content, "wlan1").unwrap();
+        assert_eq!((info.rssi_dbm, info.noise_dbm), (Some(-40), Some(-95)));
+        assert_eq!(info.snr_db(), Some(55));
+
+        // Not associated, or not wireless at all
+        assert_eq!(parse_proc_net_wireless(content, "wlan2"), None);
+        assert_eq!(parse_proc_net_wireless(content, "eth0"), None);
+    }
+
     #[test]
     fn test_parse_proc_net_dev() {
-        let reader = LinuxReader::new();
         let sample_data = r#"Inter-|   Receive                                                |  Transmit
  face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
     lo: 1234567      100    0    0    0     0          0         0  1234567      100    0    0    0     0       0          0
   eth0: 9876543210   5000    0    0    0     0          0         0  1234567890   3000    0    0    0     0       0          0
 "#;
 
-        let stats = reader.parse_proc_net_dev(sample_data, "eth0").unwrap();
+        let stats = parse_proc_net_dev(sample_data, "eth0").unwrap();
         assert_eq!(stats.bytes_in, 9876543210);
         assert_eq!(stats.bytes_out, 1234567890);
         assert_eq!(stats.packets_in, 5000);
         assert_eq!(stats.packets_out, 3000);
     }
 
+    #[test]
+    fn test_reader_uses_configured_path() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo: 1234567      100    0    0    0     0          0         0  1234567      100    0    0    0     0       0          0
+ veth0:     100        1    0    0    0     0          0         0      200        2    0    0    0     0       0          0
+  ens5:     4096      32    0    0    0     0          0         0     8192       64    0    0    0     0       0          0
+"
+        )
+        .unwrap();
+
+        let reader = LinuxReader::with_proc_net_dev(file.path());
+        assert!(reader.is_available());
+        // Loopback and virtual interfaces are listed; InterfaceFilter leaves them out
+        assert_eq!(reader.list_devices().unwrap(), ["lo", "veth0", "ens5"]);
+        assert_eq!(reader.is_virtual("veth0"), None);
+        let stats = reader.read_stats("ens5").unwrap();
+        assert_eq!((stats.bytes_in, stats.bytes_out), (4096, 8192));
+
+        let missing = LinuxReader::with_proc_net_dev("/nonexistent/net/dev");
+        assert!(!missing.is_available());
+        assert!(missing.list_devices().is_err());
+    }
+
     #[test]
     fn test_device_not_found() {
-        let reader = LinuxReader::new();
         let sample_data = r#"Inter-|   Receive                                                |  Transmit
  face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
     lo: 1234567      100    0    0    0     0          0         0  1234567      100    0    0    0     0       0          0
 "#;
 
-        let result = reader.parse_proc_net_dev(sample_data, "nonexistent");
+        let result = parse_proc_net_dev(sample_data, "nonexistent");
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
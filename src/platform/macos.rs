@@ -1,11 +1,18 @@
 use crate::{
-    device::{NetworkReader, NetworkStats},
+    device::{DeviceInfo, InterfaceKind, NetworkReader, NetworkStats, WirelessInfo},
     error::{NetwatchError, Result},
 };
+use std::collections::{BTreeSet, HashMap};
 use std::ffi::CStr;
+use std::net::IpAddr;
 use std::ptr;
 use std::time::SystemTime;
 
+/// Apple's Wi-Fi diagnostics tool; removed in macOS 14.4, where wireless info is simply
+/// not shown
+const AIRPORT: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
 pub struct MacOSReader;
 
 impl Default for MacOSReader {
@@ -40,8 +47,9 @@ impl NetworkReader for MacOSReader {
                 if !ifa.ifa_name.is_null() {
                     let name = CStr::from_ptr(ifa.ifa_name).to_string_lossy().to_string();
 
-                    // Only include unique interfaces and filter out virtual/loopback interfaces
-                    if !devices.contains(&name) && !name.starts_with("lo") {
+                    // getifaddrs has an entry per address; InterfaceFilter decides what
+                    // "all" leaves out
+                    if !devices.contains(&name) {
                         devices.push(name);
                     }
                 }
@@ -128,4 +136,169 @@ impl NetworkReader for MacOSReader {
         // Always available on macOS
         true
     }
+
+    fn read_info(&self, device: &str) -> DeviceInfo {
+        use std::process::Command;
+
+        match Command::new("ifconfig").arg(device).output() {
+            Ok(output) if output.status.success() => {
+                parse_ifconfig(device, &String::from_utf8_lossy(&output.stdout))
+            }
+            _ => DeviceInfo::default(),
+        }
+    }
+
+    fn read_addresses(&self) -> Option<HashMap<String, BTreeSet<IpAddr>>> {
+        super::interface_addresses().ok()
+    }
+
+    fn is_virtual(&self, device: &str) -> Option<bool> {
+        // There is no sysfs: go by ifconfig's media type and name
+        self.read_info(device).physical.map(|physical| !physical)
+    }
+
+    fn read_wireless(&self, _device: &str) -> Option<WirelessInfo> {
+        use std::process::Command;
+
+        // airport describes the primary Wi-Fi interface, the only one a Mac has
+        let output = Command::new(AIRPORT).arg("-I").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_airport(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Radio metrics from `airport -I`, which prints `key: value` lines such as
+/// `agrCtlRSSI: -55`, `lastTxRate: 867` and `channel: 36,80`. None while Wi-Fi is off or
+/// not associated, when the RSSI reads 0 or is missing.
+fn parse_airport(output: &str) -> Option<WirelessInfo> {
+    let mut info = WirelessInfo::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "agrCtlRSSI" => info.rssi_dbm = value.parse().ok().filter(|&rssi| rssi < 0),
+            "agrCtlNoise" => info.noise_dbm = value.parse().ok().filter(|&noise| noise < 0),
+            "lastTxRate" => info.tx_rate_mbps = value.parse().ok(),
+            // The primary channel, before the width or secondary channel
+            "channel" => {
+                info.channel = value
+                    .split(',')
+                    .next()
+                    .and_then(|channel| channel.trim().parse().ok())
+            }
+            _ => {}
+        }
+    }
+    info.rssi_dbm.is_some().then_some(info)
+}
+
+/// Link metadata from `ifconfig NAME`, which reports the SIOCGIFMEDIA media word as e.g.
+/// `media: autoselect (1000baseT <full-duplex>)` and `status: active`
+fn parse_ifconfig(device: &str, output: &str) -> DeviceInfo {
+    let mut info = DeviceInfo::default();
+    for line in output.lines().map(str::trim) {
+        if let Some((_, mtu)) = line.split_once(" mtu ") {
+            info.mtu = mtu
+                .split_whitespace()
+                .next()
+                .and_then(|mtu| mtu.parse().ok());
+        } else if let Some((_, link)) = line.split_once("parent interface: ") {
+            // `vlan: 100 parent interface: en0`
+            info.parent = Some(link.trim().to_string()).filter(|link| link != "<none>");
+        } else if let Some(mac) = line.strip_prefix("ether ") {
+            info.mac = mac.split_whitespace().next().map(str::to_string);
+        } else if let Some(status) = line.strip_prefix("status: ") {
+            let active = status == "active";
+            info.carrier = Some(active);
+            info.operstate = Some(if active { "up" } else { "down" }.to_string());
+        } else if let Some(media) = line.strip_prefix("media: ") {
+            // The active media is in parentheses after autoselect
+            let active = media
+                .split_once('(')
+                .map_or(media, |(_, active)| active.trim_end_matches(')'));
+            let digits: String = active.chars().take_while(char::is_ascii_digit).collect();
+            info.speed_mbps = digits.parse::<u64>().ok().map(|speed| {
+                if active[digits.len()..].starts_with('G') {
+                    speed * 1000
+                } else {
+                    speed
+                }
+            });
+            if active.contains("full-duplex") {
+                info.duplex = Some("full".to_string());
+            } else if active.contains("half-duplex") {
+                info.duplex = Some("half".to_string());
+            }
+        }
+    }
+
+    info.kind = if device.starts_with("lo") {
+        InterfaceKind::Loopback
+    } else if device.starts_with("bridge") {
+        InterfaceKind::Bridge
+    } else if device.starts_with("bond") {
+        InterfaceKind::Bond
+    } else if device.starts_with("vlan") {
+        InterfaceKind::Vlan
+    } else if device.starts_with("utun") || device.starts_with("tun") {
+        InterfaceKind::Tun
+    } else if output
+        .lines()
+        .any(|line| line.trim() == "media: autoselect")
+    {
+        // Wi-Fi reports plain autoselect, without a wired media type in parentheses
+        InterfaceKind::Wifi
+    } else if info.mac.is_some() {
+        InterfaceKind::Ethernet
+    } else {
+        InterfaceKind::Unknown
+    };
+    // AWDL, Apple-private and hotspot interfaces have MACs too, but only enN is hardware
+    info.physical = Some(
+        device.starts_with("en")
+            && matches!(info.kind, InterfaceKind::Ethernet | InterfaceKind::Wifi),
+    );
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_airport() {
+        let output = "     agrCtlRSSI: -58
+     agrExtRSSI: 0
+    agrCtlNoise: -92
+    agrExtNoise: 0
+          state: running
+        op mode: station
+     lastTxRate: 866
+        maxRate: 867
+lastAssocStatus: 0
+    802.11 auth: open
+      link auth: wpa2-psk
+          BSSID: 3c:28:6d:11:22:33
+           SSID: home
+            MCS: 9
+  guardInterval: 800
+            NSS: 2
+        channel: 36,80
+";
+        let info = parse_airport(output).unwrap();
+        assert_eq!(info.rssi_dbm, Some(-58));
+        assert_eq!(info.noise_dbm, Some(-92));
+        assert_eq!(info.snr_db(), Some(34));
+        assert_eq!(info.tx_rate_mbps, Some(866.0));
+        assert_eq!(info.channel, Some(36));
+
+        // Wi-Fi turned off
+        assert_eq!(parse_airport("AirPort: Off\n"), None);
+        let idle = "     agrCtlRSSI: 0\n    agrCtlNoise: 0\n          state: init\n";
+        assert_eq!(parse_airport(idle), None);
+    }
 }
@@ -1,16 +1,185 @@
-use crate::{device::NetworkReader, error::Result};
+use crate::{
+    device::{NetworkReader, NetworkStats},
+    error::{NetwatchError, Result},
+};
+use std::collections::{BTreeSet, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::SystemTime;
+
+pub mod docker;
+pub mod wireguard;
 
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "linux")]
-pub use linux::LinuxReader;
+pub use linux::{enter_network_namespace, ethtool_stats, InterfaceEventWatcher, LinuxReader};
+
+#[cfg(all(target_os = "linux", feature = "af-packet"))]
+mod linux_afpacket;
+#[cfg(all(target_os = "linux", feature = "af-packet"))]
+pub use linux_afpacket::AfPacketReader;
 
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "macos")]
 pub use macos::MacOSReader;
 
+/// Where `ip netns add NAME` bind-mounts named network namespaces
+const NETNS_RUN_DIR: &str = "/var/run/netns";
+
+/// A `--netns` value as a path: a bare NAME is the namespace `ip netns add NAME` made,
+/// anything with a slash (such as /proc/PID/ns/net) is used as given
+pub fn netns_path(name_or_path: &str) -> String {
+    if name_or_path.contains('/') {
+        name_or_path.to_string()
+    } else {
+        format!("{NETNS_RUN_DIR}/{name_or_path}")
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn enter_network_namespace(_path: &str) -> Result<()> {
+    Err(crate::error::NetwatchError::Platform(
+        "--netns needs Linux network namespaces".to_string(),
+    ))
+}
+
+/// Driver counters like `ethtool -S`, which only Linux has
+#[cfg(not(target_os = "linux"))]
+pub fn ethtool_stats(_interface: &str) -> std::collections::HashMap<String, u64> {
+    std::collections::HashMap::new()
+}
+
+/// An interface appearing, disappearing or changing state while netwatch runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterfaceEvent {
+    Up(String),
+    Down(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Interface events need Linux's netlink; elsewhere the device list stays as it started
+#[cfg(not(target_os = "linux"))]
+pub struct InterfaceEventWatcher;
+
+#[cfg(not(target_os = "linux"))]
+impl InterfaceEventWatcher {
+    pub fn spawn(
+        _queue: std::sync::Arc<std::sync::Mutex<std::collections::VecDeque<InterfaceEvent>>>,
+    ) -> std::io::Result<Self> {
+        Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+    }
+}
+
+/// IPv4 and IPv6 addresses of every interface from getifaddrs(3), which both Linux and
+/// macOS have; interfaces without an address are left out
+pub fn interface_addresses() -> std::io::Result<HashMap<String, BTreeSet<IpAddr>>> {
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: on success ifap holds a list that is freed below
+    if unsafe { libc::getifaddrs(&mut ifap) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut addresses: HashMap<String, BTreeSet<IpAddr>> = HashMap::new();
+    let mut current = ifap;
+    while !current.is_null() {
+        // SAFETY: current is a node of the list getifaddrs returned, not yet freed
+        let ifa = unsafe { &*current };
+        current = ifa.ifa_next;
+        if ifa.ifa_name.is_null() || ifa.ifa_addr.is_null() {
+            continue;
+        }
+
+        // SAFETY: ifa_addr points to a sockaddr of the family it names
+        let ip = unsafe {
+            match i32::from((*ifa.ifa_addr).sa_family) {
+                libc::AF_INET => {
+                    let sin = &*(ifa.ifa_addr as *const libc::sockaddr_in);
+                    IpAddr::V4(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)))
+                }
+                libc::AF_INET6 => {
+                    let sin6 = &*(ifa.ifa_addr as *const libc::sockaddr_in6);
+                    IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.s6_addr))
+                }
+                _ => continue,
+            }
+        };
+        // SAFETY: ifa_name is a NUL-terminated string owned by the list
+        let name = unsafe { std::ffi::CStr::from_ptr(ifa.ifa_name) };
+        addresses
+            .entry(name.to_string_lossy().into_owned())
+            .or_default()
+            .insert(ip);
+    }
+
+    // SAFETY: ifap came from getifaddrs and is freed exactly once
+    unsafe { libc::freeifaddrs(ifap) };
+    Ok(addresses)
+}
+
+/// Interfaces listed in /proc/net/dev-format `content`, loopback and virtual ones
+/// included; [`InterfaceFilter`](crate::device::InterfaceFilter) decides what "all" means
+pub(crate) fn proc_net_dev_devices(content: &str) -> Vec<String> {
+    let mut devices = Vec::new();
+
+    for line in content.lines().skip(2) {
+        if let Some(device_part) = line.split(':').next() {
+            let device_name = device_part.trim().to_string();
+            if !device_name.is_empty() {
+                devices.push(device_name);
+            }
+        }
+    }
+
+    devices
+}
+
+/// `device`'s counters from /proc/net/dev-format `content`, whichever host it came from
+pub(crate) fn parse_proc_net_dev(content: &str, device: &str) -> Result<NetworkStats> {
+    for line in content.lines().skip(2) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let iface_name = parts[0].trim_end_matches(':');
+        if iface_name == device {
+            return Ok(NetworkStats {
+                timestamp: SystemTime::now(),
+                bytes_in: parts.get(1).unwrap_or(&"0").parse().unwrap_or(0),
+                packets_in: parts.get(2).unwrap_or(&"0").parse().unwrap_or(0),
+                errors_in: parts.get(3).unwrap_or(&"0").parse().unwrap_or(0),
+                drops_in: parts.get(4).unwrap_or(&"0").parse().unwrap_or(0),
+                bytes_out: parts.get(9).unwrap_or(&"0").parse().unwrap_or(0),
+                packets_out: parts.get(10).unwrap_or(&"0").parse().unwrap_or(0),
+                errors_out: parts.get(11).unwrap_or(&"0").parse().unwrap_or(0),
+                drops_out: parts.get(12).unwrap_or(&"0").parse().unwrap_or(0),
+            });
+        }
+    }
+
+    Err(NetwatchError::DeviceNotFound(device.to_string()))
+}
+
+/// A reader for `--proc-net-dev`: interface counters from a /proc/net/dev-format file
+pub fn create_reader_from(proc_net_dev: &str) -> Result<Box<dyn NetworkReader>> {
+    #[cfg(target_os = "linux")]
+    return Ok(Box::new(LinuxReader::with_proc_net_dev(proc_net_dev)));
+
+    #[cfg(not(target_os = "linux"))]
+    return Err(crate::error::NetwatchError::Platform(format!(
+        "--proc-net-dev {proc_net_dev}: only Linux reads /proc/net/dev"
+    )));
+}
+
 pub fn create_reader() -> Result<Box<dyn NetworkReader>> {
+    // Opening packet sockets needs CAP_NET_RAW; without it /proc/net/dev is all there is
+    #[cfg(all(target_os = "linux", feature = "af-packet"))]
+    if crate::traceroute::has_cap_net_raw() {
+        return Ok(Box::new(AfPacketReader::new()));
+    }
+
     #[cfg(target_os = "linux")]
     return Ok(Box::new(LinuxReader::new()));
 
@@ -0,0 +1,518 @@
+//! Per-peer statistics for WireGuard interfaces
+//!
+//! Kernel WireGuard devices are asked over generic netlink (`WG_CMD_GET_DEVICE`), which
+//! needs CAP_NET_ADMIN. Userspace implementations (wireguard-go, boringtun) answer the
+//! same question on their UAPI socket, `/var/run/wireguard/IFNAME.sock`, which only
+//! needs permission on the socket file; it is also tried when netlink is refused.
+//! Both are only spoken with `--features wireguard`; without it, or when neither
+//! answers, the interface is shown like any other.
+
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime};
+
+/// Directory holding the UAPI sockets of userspace WireGuard implementations
+#[cfg(feature = "wireguard")]
+const UAPI_DIR: &str = "/var/run/wireguard";
+
+/// Each query gets this long before the interface is shown without peers
+#[cfg(feature = "wireguard")]
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Peers of one WireGuard interface
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WireGuardInfo {
+    pub peers: Vec<WgPeer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WgPeer {
+    /// Base64, as `wg show` prints it
+    pub public_key: String,
+    /// Where the peer was last heard from; None until it has been
+    pub endpoint: Option<SocketAddr>,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    /// None when no handshake has completed
+    pub last_handshake_time: Option<SystemTime>,
+}
+
+/// Peer statistics for `interface`, or None when it isn't a WireGuard interface we
+/// can query
+pub fn read_info(interface: &str) -> Option<WireGuardInfo> {
+    #[cfg(all(feature = "wireguard", target_os = "linux"))]
+    if is_kernel_device(interface) {
+        if let Some(info) = netlink::get_device(interface) {
+            return Some(info);
+        }
+    }
+
+    read_uapi(interface)
+}
+
+/// Whether sysfs reports `interface` as a kernel WireGuard device
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+fn is_kernel_device(interface: &str) -> bool {
+    std::fs::read_to_string(
+        std::path::Path::new("/sys/class/net")
+            .join(interface)
+            .join("uevent"),
+    )
+    .is_ok_and(|uevent| uevent.lines().any(|line| line == "DEVTYPE=wireguard"))
+}
+
+#[cfg(feature = "wireguard")]
+fn read_uapi(interface: &str) -> Option<WireGuardInfo> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    // The name ends up in a path, so only accept what an interface name can be
+    if interface.is_empty() || interface.contains(['/', '\0']) || interface.starts_with('.') {
+        return None;
+    }
+    let mut stream = UnixStream::connect(format!("{UAPI_DIR}/{interface}.sock")).ok()?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT)).ok()?;
+    stream.write_all(b"get=1\n\n").ok()?;
+
+    // The reply ends with a blank line; the server may keep the connection open
+    let mut reply = Vec::new();
+    let mut buffer = [0u8; 4096];
+    while !reply.ends_with(b"\n\n") {
+        let read = stream.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        reply.extend_from_slice(&buffer[..read]);
+    }
+    parse_uapi(&String::from_utf8_lossy(&reply))
+}
+
+#[cfg(not(feature = "wireguard"))]
+fn read_uapi(_interface: &str) -> Option<WireGuardInfo> {
+    None
+}
+
+/// Peers from a UAPI `get=1` reply; None when it reports an error
+#[cfg_attr(not(any(feature = "wireguard", test)), allow(dead_code))]
+fn parse_uapi(reply: &str) -> Option<WireGuardInfo> {
+    let mut info = WireGuardInfo::default();
+    let mut handshake = (0u64, 0u32);
+
+    for line in reply.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key == "public_key" {
+            // Each peer starts with its key
+            finish_handshake(&mut info, handshake);
+            handshake = (0, 0);
+            info.peers.push(WgPeer {
+                public_key: base64_encode(&decode_hex(value)?),
+                endpoint: None,
+                bytes_received: 0,
+                bytes_sent: 0,
+                last_handshake_time: None,
+            });
+            continue;
+        }
+        if key == "errno" {
+            if value != "0" {
+                return None;
+            }
+            continue;
+        }
+
+        // Anything before the first public_key belongs to the interface itself
+        let Some(peer) = info.peers.last_mut() else {
+            continue;
+        };
+        match key {
+            "endpoint" => peer.endpoint = value.parse().ok(),
+            "rx_bytes" => peer.bytes_received = value.parse().unwrap_or(0),
+            "tx_bytes" => peer.bytes_sent = value.parse().unwrap_or(0),
+            "last_handshake_time_sec" => handshake.0 = value.parse().unwrap_or(0),
+            "last_handshake_time_nsec" => handshake.1 = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    finish_handshake(&mut info, handshake);
+
+    Some(info)
+}
+
+/// Set the last peer's handshake time; (0, 0) means it never completed one
+fn finish_handshake(info: &mut WireGuardInfo, (secs, nanos): (u64, u32)) {
+    if let Some(peer) = info.peers.last_mut() {
+        peer.last_handshake_time = handshake_time(secs, nanos);
+    }
+}
+
+fn handshake_time(secs: u64, nanos: u32) -> Option<SystemTime> {
+    (secs != 0 || nanos != 0)
+        .then(|| SystemTime::UNIX_EPOCH + Duration::new(secs, nanos.min(999_999_999)))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | u32::from(byte) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                out.push(ALPHABET[(group >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Generic netlink client for the kernel's `wireguard` family
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+mod netlink {
+    use super::{base64_encode, handshake_time, WgPeer, WireGuardInfo, SOCKET_TIMEOUT};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    const GENL_ID_CTRL: u16 = 0x10;
+    const CTRL_CMD_GETFAMILY: u8 = 3;
+    const CTRL_ATTR_FAMILY_ID: u16 = 1;
+    const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+    const WG_CMD_GET_DEVICE: u8 = 0;
+    const WG_GENL_VERSION: u8 = 1;
+    const WGDEVICE_A_IFNAME: u16 = 2;
+    const WGDEVICE_A_PEERS: u16 = 8;
+    const WGPEER_A_PUBLIC_KEY: u16 = 1;
+    const WGPEER_A_ENDPOINT: u16 = 4;
+    const WGPEER_A_LAST_HANDSHAKE_TIME: u16 = 6;
+    const WGPEER_A_RX_BYTES: u16 = 7;
+    const WGPEER_A_TX_BYTES: u16 = 8;
+
+    const NLM_F_REQUEST: u16 = 0x1;
+    const NLM_F_ACK: u16 = 0x4;
+    const NLM_F_DUMP: u16 = 0x300;
+    const NLMSG_ERROR: u16 = 2;
+    const NLMSG_DONE: u16 = 3;
+    const NLA_TYPE_MASK: u16 = 0x3fff;
+    const HEADER_LEN: usize = 16 + 4; // nlmsghdr + genlmsghdr
+
+    /// Peers of a kernel WireGuard device; None if it isn't one or we may not ask
+    pub fn get_device(interface: &str) -> Option<WireGuardInfo> {
+        let socket = open()?;
+        let family = resolve_family(&socket)?;
+
+        let mut ifname = interface.as_bytes().to_vec();
+        ifname.push(0);
+        let request = message(
+            family,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_DUMP,
+            WG_CMD_GET_DEVICE,
+            WG_GENL_VERSION,
+            &attribute(WGDEVICE_A_IFNAME, &ifname),
+        );
+
+        // Devices with many peers are split over several messages
+        let mut info = WireGuardInfo::default();
+        for payload in exchange(&socket, &request)? {
+            for (kind, value) in attributes(&payload) {
+                if kind == WGDEVICE_A_PEERS {
+                    info.peers
+                        .extend(attributes(value).filter_map(|(_, peer)| parse_peer(peer)));
+                }
+            }
+        }
+        Some(info)
+    }
+
+    /// Numeric ID the kernel gave the `wireguard` family
+    fn resolve_family(socket: &OwnedFd) -> Option<u16> {
+        let request = message(
+            GENL_ID_CTRL,
+            NLM_F_REQUEST | NLM_F_ACK,
+            CTRL_CMD_GETFAMILY,
+            1,
+            &attribute(CTRL_ATTR_FAMILY_NAME, b"wireguard\0"),
+        );
+        exchange(socket, &request)?.iter().find_map(|payload| {
+            attributes(payload)
+                .find(|&(kind, value)| kind == CTRL_ATTR_FAMILY_ID && value.len() >= 2)
+                .map(|(_, value)| u16::from_ne_bytes([value[0], value[1]]))
+        })
+    }
+
+    pub(super) fn parse_peer(attrs: &[u8]) -> Option<WgPeer> {
+        let mut peer = WgPeer {
+            public_key: String::new(),
+            endpoint: None,
+            bytes_received: 0,
+            bytes_sent: 0,
+            last_handshake_time: None,
+        };
+        for (kind, value) in attributes(attrs) {
+            match kind {
+                WGPEER_A_PUBLIC_KEY => peer.public_key = base64_encode(value),
+                WGPEER_A_ENDPOINT => peer.endpoint = parse_sockaddr(value),
+                WGPEER_A_RX_BYTES => peer.bytes_received = read_u64(value)?,
+                WGPEER_A_TX_BYTES => peer.bytes_sent = read_u64(value)?,
+                // struct __kernel_timespec: i64 seconds, i64 nanoseconds
+                WGPEER_A_LAST_HANDSHAKE_TIME if value.len() >= 16 => {
+                    let secs = read_u64(&value[..8])?;
+                    let nanos = read_u64(&value[8..16])?;
+                    peer.last_handshake_time =
+                        handshake_time(secs, u32::try_from(nanos).unwrap_or(0));
+                }
+                _ => {}
+            }
+        }
+        (!peer.public_key.is_empty()).then_some(peer)
+    }
+
+    /// struct sockaddr_in or sockaddr_in6: native family, then port and address in
+    /// network order
+    fn parse_sockaddr(value: &[u8]) -> Option<SocketAddr> {
+        let family = i32::from(u16::from_ne_bytes([*value.first()?, *value.get(1)?]));
+        let port = u16::from_be_bytes([*value.get(2)?, *value.get(3)?]);
+        let ip = if family == libc::AF_INET {
+            let octets: [u8; 4] = value.get(4..8)?.try_into().ok()?;
+            IpAddr::V4(Ipv4Addr::from(octets))
+        } else if family == libc::AF_INET6 {
+            let octets: [u8; 16] = value.get(8..24)?.try_into().ok()?;
+            IpAddr::V6(Ipv6Addr::from(octets))
+        } else {
+            return None;
+        };
+        Some(SocketAddr::new(ip, port))
+    }
+
+    fn read_u64(value: &[u8]) -> Option<u64> {
+        Some(u64::from_ne_bytes(value.get(..8)?.try_into().ok()?))
+    }
+
+    /// (type, payload) of each attribute in `data`, nested flag stripped
+    pub(super) fn attributes(data: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+        let mut rest = data;
+        std::iter::from_fn(move || {
+            if rest.len() < 4 {
+                return None;
+            }
+            let len = usize::from(u16::from_ne_bytes([rest[0], rest[1]]));
+            let kind = u16::from_ne_bytes([rest[2], rest[3]]) & NLA_TYPE_MASK;
+            if len < 4 || len > rest.len() {
+                return None;
+            }
+            let value = &rest[4..len];
+            rest = rest.get(align(len)..).unwrap_or_default();
+            Some((kind, value))
+        })
+    }
+
+    pub(super) fn attribute(kind: u16, value: &[u8]) -> Vec<u8> {
+        let len = 4 + value.len();
+        let mut out = Vec::with_capacity(align(len));
+        out.extend_from_slice(&(len as u16).to_ne_bytes());
+        out.extend_from_slice(&kind.to_ne_bytes());
+        out.extend_from_slice(value);
+        out.resize(align(len), 0);
+        out
+    }
+
+    fn align(len: usize) -> usize {
+        (len + 3) & !3
+    }
+
+    fn message(family: u16, flags: u16, command: u8, version: u8, attrs: &[u8]) -> Vec<u8> {
+        let len = HEADER_LEN + attrs.len();
+        let mut out = Vec::with_capacity(len);
+        out.extend_from_slice(&(len as u32).to_ne_bytes());
+        out.extend_from_slice(&family.to_ne_bytes());
+        out.extend_from_slice(&flags.to_ne_bytes());
+        out.extend_from_slice(&1u32.to_ne_bytes()); // sequence number
+        out.extend_from_slice(&0u32.to_ne_bytes()); // port ID: the kernel assigns ours
+        out.extend_from_slice(&[command, version, 0, 0]);
+        out.extend_from_slice(attrs);
+        out
+    }
+
+    fn open() -> Option<OwnedFd> {
+        // SAFETY: plain socket(2); the descriptor is owned from here on
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_GENERIC,
+            )
+        };
+        if fd < 0 {
+            return None;
+        }
+        // SAFETY: fd was just returned by socket(2) and nothing else owns it
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let timeout = libc::timeval {
+            tv_sec: SOCKET_TIMEOUT.as_secs() as libc::time_t,
+            tv_usec: 0,
+        };
+        // SAFETY: timeout outlives the call and its size is passed alongside
+        let result = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                (&timeout as *const libc::timeval).cast(),
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        (result == 0).then_some(socket)
+    }
+
+    /// Send `request` and collect the generic netlink payload (after the genl header)
+    /// of every reply until the kernel acknowledges or finishes the dump. None on any
+    /// error, including EPERM without CAP_NET_ADMIN and ENODEV for other interfaces.
+    fn exchange(socket: &OwnedFd, request: &[u8]) -> Option<Vec<Vec<u8>>> {
+        // SAFETY: request is a valid buffer of the given length
+        let sent = unsafe {
+            libc::send(
+                socket.as_raw_fd(),
+                request.as_ptr().cast(),
+                request.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            return None;
+        }
+
+        let mut payloads = Vec::new();
+        let mut buffer = vec![0u8; 32 * 1024];
+        loop {
+            // SAFETY: buffer is writable for its full length
+            let received = unsafe {
+                libc::recv(
+                    socket.as_raw_fd(),
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                    0,
+                )
+            };
+            let mut rest = buffer.get(..usize::try_from(received).ok()?)?;
+            if rest.is_empty() {
+                return None;
+            }
+
+            while rest.len() >= 16 {
+                let len = u32::from_ne_bytes(rest[..4].try_into().ok()?) as usize;
+                let kind = u16::from_ne_bytes([rest[4], rest[5]]);
+                if len < 16 || len > rest.len() {
+                    return None;
+                }
+                match kind {
+                    NLMSG_DONE => return Some(payloads),
+                    NLMSG_ERROR => {
+                        // An error code of 0 is the acknowledgement
+                        let code = i32::from_ne_bytes(rest.get(16..20)?.try_into().ok()?);
+                        return (code == 0).then_some(payloads);
+                    }
+                    _ => payloads.push(rest.get(HEADER_LEN..len)?.to_vec()),
+                }
+                rest = rest.get(align(len)..).unwrap_or_default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uapi_reply_parses_peers() {
+        let reply =
+            "private_key=e84b5a6d2717c1003a13b431570353dbaca9146cf150c5f8575680feba52027a\n\
+            listen_port=51820\n\
+            public_key=b85996fecc9c7f1fc6d2572a76eda11d59bcd20be8e543b15ce4bd85a8e75a33\n\
+            endpoint=192.0.2.10:51820\n\
+            last_handshake_time_sec=1700000000\n\
+            last_handshake_time_nsec=500\n\
+            tx_bytes=38333\n\
+            rx_bytes=2224\n\
+            allowed_ip=10.0.0.2/32\n\
+            public_key=58402e695ba1772b1cc9309755f043251ea77fdcf10fbe63989ceb7e19321376\n\
+            last_handshake_time_sec=0\n\
+            last_handshake_time_nsec=0\n\
+            tx_bytes=0\n\
+            rx_bytes=0\n\
+            errno=0\n\n";
+        let info = parse_uapi(reply).unwrap();
+        assert_eq!(info.peers.len(), 2);
+
+        let peer = &info.peers[0];
+        assert_eq!(
+            peer.public_key,
+            "uFmW/sycfx/G0lcqdu2hHVm80gvo5UOxXOS9hajnWjM="
+        );
+        assert_eq!(peer.endpoint, Some("192.0.2.10:51820".parse().unwrap()));
+        assert_eq!((peer.bytes_received, peer.bytes_sent), (2224, 38333));
+        assert_eq!(
+            peer.last_handshake_time,
+            Some(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 500))
+        );
+
+        // A peer that never completed a handshake has no endpoint or handshake time
+        assert_eq!(info.peers[1].endpoint, None);
+        assert_eq!(info.peers[1].last_handshake_time, None);
+
+        assert_eq!(parse_uapi("errno=19\n\n"), None);
+        assert_eq!(parse_uapi("public_key=zz\n"), None);
+        assert_eq!(base64_encode(b"wg"), "d2c=");
+        assert_eq!(read_info("definitely-not-wg0"), None);
+    }
+
+    #[cfg(all(feature = "wireguard", target_os = "linux"))]
+    #[test]
+    fn test_netlink_peer_attributes() {
+        let mut endpoint = vec![0u8; 16];
+        endpoint[..2].copy_from_slice(&(libc::AF_INET as u16).to_ne_bytes());
+        endpoint[2..4].copy_from_slice(&51820u16.to_be_bytes());
+        endpoint[4..8].copy_from_slice(&[192, 0, 2, 10]);
+        let mut handshake = 1_700_000_000u64.to_ne_bytes().to_vec();
+        handshake.extend(0u64.to_ne_bytes());
+
+        let attrs = [
+            netlink::attribute(1, &[0u8; 32]),
+            netlink::attribute(4, &endpoint),
+            netlink::attribute(6, &handshake),
+            netlink::attribute(7, &2224u64.to_ne_bytes()),
+            netlink::attribute(8, &38333u64.to_ne_bytes()),
+        ]
+        .concat();
+        let peer = netlink::parse_peer(&attrs).unwrap();
+        assert_eq!(
+            peer.public_key,
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+        );
+        assert_eq!(peer.endpoint, Some("192.0.2.10:51820".parse().unwrap()));
+        assert_eq!((peer.bytes_received, peer.bytes_sent), (2224, 38333));
+        assert_eq!(
+            peer.last_handshake_time,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+    }
+}
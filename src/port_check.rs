@@ -0,0 +1,268 @@
+//! TCP connect checks for the Diagnostics panel's `PortChecks`
+//!
+//! Every configured `host:port` gets one connect attempt per round. A completed handshake
+//! is Open, a RST is Closed and silence until the timeout is Filtered. Rounds run on a
+//! background thread with at most [`MAX_PARALLEL_CHECKS`] connects in flight, and host
+//! names are resolved once per [`RESOLVE_TTL`] rather than on every attempt.
+
+use crate::active_diagnostics::{PortScanResult, PortStatus};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long one connect attempt may take before the port counts as filtered
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Connect attempts in flight at once, however many checks are configured
+pub const MAX_PARALLEL_CHECKS: usize = 8;
+/// Attempts kept per check for the reachability sparkline and percentiles
+pub const PORT_CHECK_HISTORY: usize = 30;
+/// How long a resolved address is reused before the host is looked up again
+pub const RESOLVE_TTL: Duration = Duration::from_secs(300);
+
+/// Split `host:port` or `[v6addr]:port`; None without a valid port
+pub fn split_host_port(target: &str) -> Option<(&str, u16)> {
+    let (host, port) = target.rsplit_once(':')?;
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.strip_suffix(']')?,
+        // A bare IPv6 address would be ambiguous
+        None if host.contains(':') => return None,
+        None => host,
+    };
+    let port = port.parse().ok().filter(|&port| port != 0)?;
+    (!host.is_empty()).then_some((host, port))
+}
+
+/// Recent attempts of one check, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct PortCheckHistory {
+    // Status of each attempt and its connect time in ms when the handshake completed
+    attempts: VecDeque<(PortStatus, Option<f64>)>,
+}
+
+impl PortCheckHistory {
+    pub fn record(&mut self, status: PortStatus, connect_ms: Option<f64>) {
+        if self.attempts.len() >= PORT_CHECK_HISTORY {
+            self.attempts.pop_front();
+        }
+        self.attempts.push_back((status, connect_ms));
+    }
+
+    /// 1 for each attempt that connected and 0 for each that did not, for a sparkline
+    pub fn reachability(&self) -> Vec<u64> {
+        self.attempts
+            .iter()
+            .map(|(status, _)| u64::from(*status == PortStatus::Open))
+            .collect()
+    }
+
+    /// Percent of recent attempts that connected
+    pub fn success_rate(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        let open = self.reachability().iter().sum::<u64>();
+        open as f64 / self.attempts.len() as f64 * 100.0
+    }
+
+    /// Nearest-rank percentile (0-100) of the connect times of attempts that connected
+    pub fn connect_percentile(&self, percentile: f64) -> Option<f64> {
+        let mut times: Vec<f64> = self.attempts.iter().filter_map(|(_, ms)| *ms).collect();
+        if times.is_empty() {
+            return None;
+        }
+        times.sort_by(f64::total_cmp);
+        let rank = (percentile / 100.0 * times.len() as f64).ceil() as usize;
+        Some(times[rank.clamp(1, times.len()) - 1])
+    }
+}
+
+/// Resolved address of each host, with when it was looked up
+type ResolveCache = HashMap<String, (IpAddr, Instant)>;
+
+/// The configured checks and their results, shared with the thread running a round
+pub struct PortChecker {
+    targets: Vec<String>,
+    resolved: Mutex<ResolveCache>,
+    results: Mutex<HashMap<String, PortScanResult>>,
+    histories: Mutex<HashMap<String, PortCheckHistory>>,
+    running: AtomicBool,
+}
+
+impl PortChecker {
+    /// Checks for each valid `host:port`; the rest were rejected by config validation
+    pub fn new(targets: &[String]) -> Self {
+        Self {
+            targets: targets
+                .iter()
+                .filter(|target| split_host_port(target).is_some())
+                .cloned()
+                .collect(),
+            resolved: Mutex::new(HashMap::new()),
+            results: Mutex::new(HashMap::new()),
+            histories: Mutex::new(HashMap::new()),
+            running: AtomicBool::new(false),
+        }
+    }
+
+    /// Start a round on a background thread unless one is still running
+    pub fn start_round(self: &Arc<Self>) {
+        if self.targets.is_empty() || self.running.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let checker = Arc::clone(self);
+        thread::spawn(move || {
+            checker.run_round();
+            checker.running.store(false, Ordering::Release);
+        });
+    }
+
+    /// Latest result of each check, keyed by `host:port`
+    pub fn results(&self) -> HashMap<String, PortScanResult> {
+        self.results.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+
+    /// Recent attempts of each check, keyed by `host:port`
+    pub fn histories(&self) -> HashMap<String, PortCheckHistory> {
+        self.histories.lock().map(|h| h.clone()).unwrap_or_default()
+    }
+
+    /// Check every target, MAX_PARALLEL_CHECKS at a time, publishing each as it finishes
+    fn run_round(&self) {
+        let next = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..MAX_PARALLEL_CHECKS.min(self.targets.len()) {
+                scope.spawn(|| {
+                    while let Some(target) = self.targets.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let result = self.check(target);
+                        if let Ok(mut histories) = self.histories.lock() {
+                            histories
+                                .entry(target.clone())
+                                .or_default()
+                                .record(result.status.clone(), result.response_time.map(f64::from));
+                        }
+                        if let Ok(mut results) = self.results.lock() {
+                            results.insert(target.clone(), result);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    fn check(&self, target: &str) -> PortScanResult {
+        let started = Instant::now();
+        let (host, port) = split_host_port(target).unwrap_or((target, 0));
+        let (status, response_time) = match self.resolve(host, port) {
+            Ok(addr) => connect(addr, CONNECT_TIMEOUT),
+            Err(_) => (PortStatus::Error, None),
+        };
+        PortScanResult {
+            target: host.to_string(),
+            port,
+            protocol: "TCP".to_string(),
+            status,
+            response_time,
+            service_banner: None,
+            last_test: started,
+        }
+    }
+
+    /// The cached address of `host`, looking it up again once RESOLVE_TTL has passed.
+    /// Failures are not cached, so a name that starts resolving is picked up next round.
+    fn resolve(&self, host: &str, port: u16) -> io::Result<SocketAddr> {
+        let cached = self
+            .resolved
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(host).copied());
+        if let Some((ip, at)) = cached {
+            if at.elapsed() < RESOLVE_TTL {
+                return Ok(SocketAddr::new(ip, port));
+            }
+        }
+
+        let addr = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no addresses"))?;
+        if let Ok(mut cache) = self.resolved.lock() {
+            cache.insert(host.to_string(), (addr.ip(), Instant::now()));
+        }
+        Ok(addr)
+    }
+}
+
+/// One connect attempt: Open with the handshake time, Closed on a RST, Filtered when
+/// nothing answers before `timeout`, Error for anything else (e.g. no route)
+pub fn connect(addr: SocketAddr, timeout: Duration) -> (PortStatus, Option<f32>) {
+    let started = Instant::now();
+    match TcpStream::connect_timeout(&addr, timeout) {
+        Ok(_) => (
+            PortStatus::Open,
+            Some(started.elapsed().as_secs_f32() * 1000.0),
+        ),
+        Err(e) => match e.kind() {
+            io::ErrorKind::ConnectionRefused => (PortStatus::Closed, None),
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => (PortStatus::Filtered, None),
+            _ => (PortStatus::Error, None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(
+            split_host_port("db.internal:5432"),
+            Some(("db.internal", 5432))
+        );
+        assert_eq!(split_host_port("[::1]:22"), Some(("::1", 22)));
+        assert_eq!(split_host_port("::1:22"), None);
+        assert_eq!(split_host_port("db.internal"), None);
+        assert_eq!(split_host_port("db.internal:0"), None);
+        assert_eq!(split_host_port(":80"), None);
+    }
+
+    #[test]
+    fn test_history_reachability_and_percentiles() {
+        let mut history = PortCheckHistory::default();
+        assert_eq!(history.connect_percentile(50.0), None);
+
+        for ms in [10.0, 20.0, 30.0, 40.0] {
+            history.record(PortStatus::Open, Some(ms));
+        }
+        history.record(PortStatus::Filtered, None);
+        assert_eq!(history.reachability(), vec![1, 1, 1, 1, 0]);
+        assert_eq!(history.success_rate(), 80.0);
+        assert_eq!(history.connect_percentile(50.0), Some(20.0));
+        assert_eq!(history.connect_percentile(95.0), Some(40.0));
+
+        for _ in 0..PORT_CHECK_HISTORY {
+            history.record(PortStatus::Closed, None);
+        }
+        assert_eq!(history.reachability().len(), PORT_CHECK_HISTORY);
+        assert_eq!(history.connect_percentile(50.0), None);
+    }
+
+    #[test]
+    fn test_connect_tells_open_from_closed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = listener.local_addr().unwrap();
+        let (status, connect_ms) = connect(open, CONNECT_TIMEOUT);
+        assert_eq!(status, PortStatus::Open);
+        assert!(connect_ms.is_some());
+
+        // Nothing listens once the listener is gone, so the kernel answers with a RST
+        drop(listener);
+        assert_eq!(connect(open, CONNECT_TIMEOUT), (PortStatus::Closed, None));
+    }
+}
@@ -1,16 +1,42 @@
+//! Per-process network activity
+//!
+//! On Linux traffic is measured, not estimated: every TCP socket's `bytes_acked` and
+//! `bytes_received` counters (from `ss -tinHe`) are diffed between updates and charged
+//! to the process holding that socket's inode in /proc/PID/fd. Processes in another
+//! network namespace (containers) own sockets `ss` can't list from here, so that
+//! namespace's /proc/PID/net/dev deltas, minus `lo`, go to its lowest PID instead.
+//!
+//! The per-process rates therefore add up to a little less than the interface totals.
+//! Not attributed: UDP and raw sockets (no byte counters), loopback connections (never
+//! on a monitored interface), TCP/IP headers and retransmissions, and whatever a socket
+//! sent between the last update and closing. On other platforms processes come from
+//! lsof/ps without byte counts; see [`ProcessMonitor::bandwidth_by_process`].
+
+use crate::connections::NetworkConnection;
 use std::collections::HashMap;
 use std::fs;
+use std::net::IpAddr;
 use std::path::Path;
-use std::time::{Duration, SystemTime};
+use std::process::Command;
+use std::time::SystemTime;
 
 #[derive(Debug, Clone)]
 pub struct ProcessNetworkInfo {
     pub pid: u32,
     pub name: String,
-    pub command: String,
+    /// Full command line, arguments separated by spaces. None when it can't be read
+    /// (another user's process without privileges, already exited) or is empty
+    /// (kernel threads, zombies).
+    pub cmdline: Option<String>,
+    /// Short (12 character) ID of the Docker, containerd, CRI-O or Podman container
+    /// the process runs in, from /proc/PID/cgroup. None on the host and off Linux.
+    pub container: Option<String>,
     pub connections: u32,
+    /// Measured send rate in bytes/s since the previous update (zero on the first)
     pub bytes_sent: u64,
+    /// Measured receive rate in bytes/s since the previous update
     pub bytes_received: u64,
+    /// TCP segments (or packets, for a namespace's interfaces) per second
     pub packets_sent: u64,
     pub packets_received: u64,
     pub established_connections: u32,
@@ -28,27 +54,80 @@ impl ProcessNetworkInfo {
     }
 }
 
+#[derive(Clone)]
 pub struct ProcessMonitor {
     processes: HashMap<u32, ProcessNetworkInfo>,
-    previous_stats: HashMap<u32, ProcessNetworkStats>,
-    last_update: SystemTime,
+    // Cumulative counters from the previous update, by socket inode and by namespace
+    previous_sockets: HashMap<u64, TrafficCounters>,
+    previous_namespaces: HashMap<String, TrafficCounters>,
+    last_update: Option<SystemTime>,
+    measured: bool,
 }
 
+/// Measured rates summed over every process, to set against the interface totals
 #[derive(Debug, Clone)]
 pub struct ProcessNetworkStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub timestamp: SystemTime,
+}
+
+/// Processes, connections and measured rates summed over one container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerNetworkInfo {
+    /// Short container ID, or "-" for everything running on the host
+    pub container: String,
+    pub processes: u32,
+    pub connections: u32,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+impl ContainerNetworkInfo {
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_sent + self.bytes_received
+    }
+}
+
+/// Cumulative byte and packet counters of one socket or one network namespace
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TrafficCounters {
     bytes_sent: u64,
     bytes_received: u64,
     packets_sent: u64,
     packets_received: u64,
-    timestamp: SystemTime,
+}
+
+impl TrafficCounters {
+    fn since(&self, earlier: &Self) -> Self {
+        Self {
+            bytes_sent: self.bytes_sent.saturating_sub(earlier.bytes_sent),
+            bytes_received: self.bytes_received.saturating_sub(earlier.bytes_received),
+            packets_sent: self.packets_sent.saturating_sub(earlier.packets_sent),
+            packets_received: self
+                .packets_received
+                .saturating_sub(earlier.packets_received),
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.bytes_sent = self.bytes_sent.saturating_add(other.bytes_sent);
+        self.bytes_received = self.bytes_received.saturating_add(other.bytes_received);
+        self.packets_sent = self.packets_sent.saturating_add(other.packets_sent);
+        self.packets_received = self.packets_received.saturating_add(other.packets_received);
+    }
 }
 
 impl ProcessMonitor {
     pub fn new() -> Self {
         Self {
             processes: HashMap::new(),
-            previous_stats: HashMap::new(),
-            last_update: SystemTime::now(),
+            previous_sockets: HashMap::new(),
+            previous_namespaces: HashMap::new(),
+            last_update: None,
+            measured: false,
         }
     }
 
@@ -58,25 +137,33 @@ impl ProcessMonitor {
 
         let now = SystemTime::now();
 
-        // Read all process network information
+        // Read all process names and command lines
         self.scan_processes()?;
 
-        // Update connection counts
-        self.update_connection_counts()?;
+        // Which process holds each socket, for both connection counts and traffic
+        let owners = self.socket_owners_by_inode();
+        self.update_connection_counts(&owners);
 
-        // Calculate network I/O rates
-        self.calculate_rates(now)?;
+        let sockets = read_socket_counters();
+        self.measured = sockets.is_some();
+        let namespaces = self.namespace_counters();
+        self.attribute_traffic(&owners, sockets.unwrap_or_default(), namespaces, now);
 
-        self.last_update = now;
         Ok(())
     }
 
+    /// Whether byte rates are measured per socket. When false they stay zero, and
+    /// [`Self::bandwidth_by_process`] is the best available estimate.
+    pub fn measures_traffic(&self) -> bool {
+        self.measured
+    }
+
     fn scan_processes(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Ok(entries) = fs::read_dir("/proc") {
             for entry in entries.flatten() {
                 if let Ok(file_name) = entry.file_name().into_string() {
                     if let Ok(pid) = file_name.parse::<u32>() {
-                        if let Some(process_info) = self.read_process_info(pid)? {
+                        if let Some(process_info) = self.read_process_info(pid) {
                             self.processes.insert(pid, process_info);
                         }
                     }
@@ -89,15 +176,12 @@ impl ProcessMonitor {
         Ok(())
     }
 
-    fn read_process_info(
-        &mut self,
-        pid: u32,
-    ) -> Result<Option<ProcessNetworkInfo>, Box<dyn std::error::Error>> {
+    fn read_process_info(&self, pid: u32) -> Option<ProcessNetworkInfo> {
         let proc_path = format!("/proc/{pid}");
 
         // Check if process directory exists and is accessible
         if !Path::new(&proc_path).exists() {
-            return Ok(None);
+            return None;
         }
 
         // Read process name
@@ -107,164 +191,59 @@ impl ProcessMonitor {
             .trim()
             .to_string();
 
-        // Read command line
-        let cmdline_path = format!("{proc_path}/cmdline");
-        let command = fs::read_to_string(cmdline_path)
-            .unwrap_or_else(|_| name.clone())
-            .replace('\0', " ")
-            .trim()
-            .to_string();
-
-        // Read network I/O statistics from /proc/pid/net/dev if available
-        let (bytes_sent, bytes_received, packets_sent, packets_received) =
-            self.read_process_network_stats(pid).unwrap_or((0, 0, 0, 0));
-
-        let process_info = ProcessNetworkInfo {
+        Some(ProcessNetworkInfo {
             pid,
             name,
-            command,
+            cmdline: read_proc_cmdline(pid),
+            container: fs::read_to_string(format!("{proc_path}/cgroup"))
+                .ok()
+                .and_then(|cgroup| parse_cgroup_container(&cgroup)),
             connections: 0, // Will be updated later
-            bytes_sent,
-            bytes_received,
-            packets_sent,
-            packets_received,
+            bytes_sent: 0,
+            bytes_received: 0,
+            packets_sent: 0,
+            packets_received: 0,
             established_connections: 0,
             listening_ports: 0,
             last_updated: SystemTime::now(),
-        };
-
-        Ok(Some(process_info))
+        })
     }
 
-    fn read_process_network_stats(
-        &self,
-        pid: u32,
-    ) -> Result<(u64, u64, u64, u64), Box<dyn std::error::Error>> {
-        // Try to read network statistics from various sources
-
-        // Method 1: Try /proc/pid/net/dev (process-specific network stats)
-        let net_dev_path = format!("/proc/{pid}/net/dev");
-        if let Ok(content) = fs::read_to_string(net_dev_path) {
-            if let Some((bytes_rx, bytes_tx, packets_rx, packets_tx)) = self.parse_net_dev(&content)
-            {
-                return Ok((bytes_tx, bytes_rx, packets_tx, packets_rx));
-            }
-        }
-
-        // Method 2: Try /proc/pid/io (process I/O stats)
-        let io_path = format!("/proc/{pid}/io");
-        if let Ok(content) = fs::read_to_string(io_path) {
-            if let Some((read_bytes, write_bytes)) = self.parse_io_stats(&content) {
-                // Estimate network I/O as a fraction of total I/O
-                // This is a rough approximation
-                return Ok((write_bytes / 4, read_bytes / 4, 0, 0));
-            }
-        }
-
-        // Method 3: Use system-wide stats as fallback
-        // Read from /proc/net/netstat for system-wide network stats
-        if let Ok(content) = fs::read_to_string("/proc/net/netstat") {
-            if let Some((bytes_sent, bytes_received)) = self.parse_netstat(&content) {
-                // Distribute proportionally based on number of connections
-                // This is a very rough estimate
-                return Ok((bytes_sent / 100, bytes_received / 100, 0, 0));
-            }
-        }
-
-        Ok((0, 0, 0, 0))
-    }
-
-    fn parse_net_dev(&self, content: &str) -> Option<(u64, u64, u64, u64)> {
-        let mut total_bytes_rx = 0;
-        let mut total_bytes_tx = 0;
-        let mut total_packets_rx = 0;
-        let mut total_packets_tx = 0;
-
-        for line in content.lines().skip(2) {
-            // Skip header lines
-            let fields: Vec<&str> = line.split_whitespace().collect();
-            if fields.len() >= 10 {
-                if let (Ok(bytes_rx), Ok(packets_rx), Ok(bytes_tx), Ok(packets_tx)) = (
-                    fields[1].parse::<u64>(),
-                    fields[2].parse::<u64>(),
-                    fields[9].parse::<u64>(),
-                    fields[10].parse::<u64>(),
-                ) {
-                    total_bytes_rx += bytes_rx;
-                    total_bytes_tx += bytes_tx;
-                    total_packets_rx += packets_rx;
-                    total_packets_tx += packets_tx;
-                }
+    /// Owning pid of every socket inode found in a /proc/PID/fd we may read
+    fn socket_owners_by_inode(&self) -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+        for &pid in self.processes.keys() {
+            for inode in socket_inodes(pid) {
+                owners.insert(inode, pid);
             }
         }
-
-        if total_bytes_rx > 0 || total_bytes_tx > 0 {
-            Some((
-                total_bytes_rx,
-                total_bytes_tx,
-                total_packets_rx,
-                total_packets_tx,
-            ))
-        } else {
-            None
-        }
+        owners
     }
 
-    fn parse_io_stats(&self, content: &str) -> Option<(u64, u64)> {
-        let mut read_bytes = 0;
-        let mut write_bytes = 0;
-
-        for line in content.lines() {
-            if line.starts_with("read_bytes:") {
-                if let Some(value) = line.split_whitespace().nth(1) {
-                    read_bytes = value.parse().unwrap_or(0);
-                }
-            } else if line.starts_with("write_bytes:") {
-                if let Some(value) = line.split_whitespace().nth(1) {
-                    write_bytes = value.parse().unwrap_or(0);
-                }
-            }
-        }
-
-        if read_bytes > 0 || write_bytes > 0 {
-            Some((read_bytes, write_bytes))
-        } else {
-            None
-        }
-    }
+    fn update_connection_counts(&mut self, owners: &HashMap<u64, u32>) {
+        // (total, established, listening) per pid, from this namespace's socket tables
+        let mut pid_connections: HashMap<u32, (u32, u32, u32)> = HashMap::new();
 
-    fn parse_netstat(&self, content: &str) -> Option<(u64, u64)> {
-        // Parse system-wide network statistics
-        for line in content.lines() {
-            if line.starts_with("IpExt:") {
-                let fields: Vec<&str> = line.split_whitespace().collect();
-                if fields.len() > 6 {
-                    if let (Ok(bytes_sent), Ok(bytes_received)) =
-                        (fields[5].parse::<u64>(), fields[6].parse::<u64>())
-                    {
-                        return Some((bytes_sent, bytes_received));
-                    }
+        for table in ["tcp", "tcp6", "udp", "udp6"] {
+            let Ok(content) = fs::read_to_string(format!("/proc/net/{table}")) else {
+                continue;
+            };
+            for (state, inode) in parse_proc_net_socket_states(&content) {
+                let Some(pid) = owners.get(&inode) else {
+                    continue;
+                };
+                let (total, established, listening) =
+                    pid_connections.entry(*pid).or_insert((0, 0, 0));
+                *total += 1;
+                match state {
+                    0x01 => *established += 1,
+                    // LISTEN in the TCP tables; UDP sockets never use this state
+                    0x0A => *listening += 1,
+                    _ => {}
                 }
             }
         }
-        None
-    }
-
-    fn update_connection_counts(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Count connections per process by parsing /proc/net/tcp and /proc/net/udp
-        let mut pid_connections: HashMap<u32, (u32, u32, u32)> = HashMap::new(); // (total, established, listening)
-
-        // Parse TCP connections
-        if let Ok(content) = fs::read_to_string("/proc/net/tcp") {
-            self.count_connections_in_file(&content, &mut pid_connections)?;
-        }
-
-        // Parse UDP connections
-        if let Ok(content) = fs::read_to_string("/proc/net/udp") {
-            self.count_connections_in_file(&content, &mut pid_connections)?;
-        }
 
-        // Update process information
         for (pid, (total, established, listening)) in pid_connections {
             if let Some(process) = self.processes.get_mut(&pid) {
                 process.connections = total;
@@ -272,110 +251,147 @@ impl ProcessMonitor {
                 process.listening_ports = listening;
             }
         }
-
-        Ok(())
     }
 
-    fn count_connections_in_file(
-        &self,
-        content: &str,
-        pid_connections: &mut HashMap<u32, (u32, u32, u32)>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        for line in content.lines().skip(1) {
-            // Skip header
-            let fields: Vec<&str> = line.split_whitespace().collect();
-            if fields.len() >= 8 {
-                // Try to extract PID from inode (this is complex and may not always work)
-                // For now, we'll use a simplified approach
-                if let Ok(inode) = fields[9].parse::<u64>() {
-                    if let Some(pid) = self.find_pid_by_inode(inode) {
-                        let (total, established, listening) =
-                            pid_connections.entry(pid).or_insert((0, 0, 0));
-                        *total += 1;
-
-                        // Check connection state
-                        if fields[3] == "01" {
-                            // ESTABLISHED
-                            *established += 1;
-                        } else if fields[3] == "0A" {
-                            // LISTEN
-                            *listening += 1;
-                        }
-                    }
-                }
+    /// Interface counters of each network namespace other than ours, keyed by the
+    /// namespace and charged to the lowest pid inside it
+    fn namespace_counters(&self) -> HashMap<String, (u32, TrafficCounters)> {
+        let mut namespaces = HashMap::new();
+        let Ok(own) = fs::read_link("/proc/self/ns/net") else {
+            return namespaces;
+        };
+
+        let mut pids: Vec<u32> = self.processes.keys().copied().collect();
+        pids.sort_unstable();
+        for pid in pids {
+            let Ok(namespace) = fs::read_link(format!("/proc/{pid}/ns/net")) else {
+                continue; // Not ours to inspect without privileges
+            };
+            let namespace = namespace.to_string_lossy().into_owned();
+            if Path::new(&namespace) == own || namespaces.contains_key(&namespace) {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(format!("/proc/{pid}/net/dev")) {
+                namespaces.insert(namespace, (pid, parse_net_dev(&content)));
             }
         }
-        Ok(())
+        namespaces
     }
 
-    fn find_pid_by_inode(&self, _inode: u64) -> Option<u32> {
-        // This is a simplified implementation
-        // In reality, we'd need to scan /proc/*/fd/* to find which process owns this inode
-        // For now, return None to avoid complex filesystem scanning
-        None
-    }
-
-    fn calculate_rates(&mut self, now: SystemTime) -> Result<(), Box<dyn std::error::Error>> {
-        // Calculate network I/O rates by comparing with previous measurements
-        for (pid, process) in &mut self.processes {
-            if let Some(prev_stats) = self.previous_stats.get(pid) {
-                let time_diff = now
-                    .duration_since(prev_stats.timestamp)
-                    .unwrap_or(Duration::from_secs(1));
-                let time_secs = time_diff.as_secs_f64();
-
-                if time_secs > 0.0 {
-                    // Calculate rates (bytes per second)
-                    let bytes_sent_rate =
-                        ((process.bytes_sent.saturating_sub(prev_stats.bytes_sent)) as f64
-                            / time_secs) as u64;
-                    let bytes_received_rate = ((process
-                        .bytes_received
-                        .saturating_sub(prev_stats.bytes_received))
-                        as f64
-                        / time_secs) as u64;
-
-                    // Update with calculated rates
-                    process.bytes_sent = bytes_sent_rate;
-                    process.bytes_received = bytes_received_rate;
+    /// Turn counter deltas since the previous update into per-process rates, then keep
+    /// the counters for the next one. Sockets opened since then count from zero.
+    fn attribute_traffic(
+        &mut self,
+        owners: &HashMap<u64, u32>,
+        sockets: HashMap<u64, TrafficCounters>,
+        namespaces: HashMap<String, (u32, TrafficCounters)>,
+        now: SystemTime,
+    ) {
+        let elapsed = self
+            .last_update
+            .and_then(|last| now.duration_since(last).ok())
+            .map(|elapsed| elapsed.as_secs_f64())
+            .filter(|&secs| secs > 0.0);
+
+        if let Some(secs) = elapsed {
+            let mut deltas: HashMap<u32, TrafficCounters> = HashMap::new();
+            for (inode, counters) in &sockets {
+                let Some(pid) = owners.get(inode) else {
+                    continue;
+                };
+                let previous = self.previous_sockets.get(inode).copied();
+                deltas
+                    .entry(*pid)
+                    .or_default()
+                    .add(&counters.since(&previous.unwrap_or_default()));
+            }
+            for (namespace, (pid, counters)) in &namespaces {
+                // A namespace first seen now has no baseline yet
+                if let Some(previous) = self.previous_namespaces.get(namespace) {
+                    deltas
+                        .entry(*pid)
+                        .or_default()
+                        .add(&counters.since(previous));
                 }
             }
 
-            // Store current stats for next calculation
-            self.previous_stats.insert(
-                *pid,
-                ProcessNetworkStats {
-                    bytes_sent: process.bytes_sent,
-                    bytes_received: process.bytes_received,
-                    packets_sent: process.packets_sent,
-                    packets_received: process.packets_received,
-                    timestamp: now,
-                },
-            );
+            let per_second = |count: u64| (count as f64 / secs) as u64;
+            for (pid, delta) in deltas {
+                if let Some(process) = self.processes.get_mut(&pid) {
+                    process.bytes_sent = per_second(delta.bytes_sent);
+                    process.bytes_received = per_second(delta.bytes_received);
+                    process.packets_sent = per_second(delta.packets_sent);
+                    process.packets_received = per_second(delta.packets_received);
+                }
+            }
         }
 
-        Ok(())
+        self.previous_sockets = sockets;
+        self.previous_namespaces = namespaces
+            .into_iter()
+            .map(|(namespace, (_, counters))| (namespace, counters))
+            .collect();
+        self.last_update = Some(now);
     }
 
     pub fn get_processes(&self) -> Vec<&ProcessNetworkInfo> {
         let mut processes: Vec<&ProcessNetworkInfo> = self.processes.values().collect();
 
-        // Sort by total network activity (bytes sent + received)
-        processes.sort_by(|a, b| {
-            let a_total = a.total_bytes();
-            let b_total = b.total_bytes();
-            b_total.cmp(&a_total)
+        // Busiest first by measured traffic, then by connections, so idle servers
+        // still rank above processes without sockets
+        processes.sort_by_key(|p| {
+            (
+                std::cmp::Reverse(p.total_bytes()),
+                std::cmp::Reverse(p.connections),
+                p.pid,
+            )
         });
 
         processes
     }
 
+    /// None once the process has exited (as of the last update)
+    pub fn get_process(&self, pid: u32) -> Option<&ProcessNetworkInfo> {
+        self.processes.get(&pid)
+    }
+
     pub fn get_top_network_processes(&self, limit: usize) -> Vec<&ProcessNetworkInfo> {
         let mut processes = self.get_processes();
         processes.truncate(limit);
         processes
     }
 
+    /// [`get_processes`](Self::get_processes) grouped by container, busiest first;
+    /// host processes are one group named "-"
+    pub fn get_container_totals(&self) -> Vec<ContainerNetworkInfo> {
+        let mut groups: HashMap<&str, ContainerNetworkInfo> = HashMap::new();
+        for process in self.processes.values() {
+            let container = process.container.as_deref().unwrap_or("-");
+            let group = groups
+                .entry(container)
+                .or_insert_with(|| ContainerNetworkInfo {
+                    container: container.to_string(),
+                    processes: 0,
+                    connections: 0,
+                    bytes_sent: 0,
+                    bytes_received: 0,
+                });
+            group.processes += 1;
+            group.connections += process.connections;
+            group.bytes_sent += process.bytes_sent;
+            group.bytes_received += process.bytes_received;
+        }
+
+        let mut groups: Vec<ContainerNetworkInfo> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.total_bytes()
+                .cmp(&a.total_bytes())
+                .then(b.connections.cmp(&a.connections))
+                .then_with(|| a.container.cmp(&b.container))
+        });
+        groups
+    }
+
     pub fn get_process_stats(&self) -> ProcessNetworkStats {
         let mut stats = ProcessNetworkStats {
             bytes_sent: 0,
@@ -395,6 +411,118 @@ impl ProcessMonitor {
         stats
     }
 
+    /// Per-process (info, inbound, outbound) rates in bytes/s built from the given
+    /// connections, sorted by total bandwidth descending.
+    pub fn bandwidth_by_process(
+        &self,
+        connections: &[NetworkConnection],
+    ) -> Vec<(ProcessNetworkInfo, u64, u64)> {
+        let pid_by_name = self.pids_by_name();
+
+        // Sockets that ss didn't attribute are resolved through each process's fd table
+        let unattributed = connections
+            .iter()
+            .any(|c| c.pid.is_none() && c.process_name.is_none());
+        let socket_owners = if unattributed {
+            self.socket_owners_by_ports()
+        } else {
+            HashMap::new()
+        };
+
+        let mut totals: HashMap<u32, (u64, u64)> = HashMap::new();
+        for conn in connections {
+            let pid = self.attributed_pid(conn, &pid_by_name).or_else(|| {
+                socket_owners
+                    .get(&(conn.local_addr.port(), conn.remote_addr.port()))
+                    .copied()
+            });
+
+            let Some(pid) = pid else {
+                continue;
+            };
+
+            // Prefer rates measured from the byte counters; until a socket has two updates
+            // (or without counters), ss's bandwidth estimate (bits/s) counts as outbound
+            let (inbound, outbound) = match (conn.received_per_sec, conn.sent_per_sec) {
+                (Some(received), Some(sent)) => (received, sent),
+                _ => (0, conn.socket_info.bandwidth.unwrap_or(0) / 8),
+            };
+
+            let entry = totals.entry(pid).or_insert((0, 0));
+            entry.0 += inbound;
+            entry.1 += outbound;
+        }
+
+        let mut result: Vec<(ProcessNetworkInfo, u64, u64)> = totals
+            .into_iter()
+            .filter_map(|(pid, (inbound, outbound))| {
+                self.processes
+                    .get(&pid)
+                    .map(|p| (p.clone(), inbound, outbound))
+            })
+            .collect();
+
+        result.sort_by_key(|(_, inbound, outbound)| std::cmp::Reverse(inbound + outbound));
+        result
+    }
+
+    /// The connections [`bandwidth_by_process`](Self::bandwidth_by_process) charges to
+    /// `pid`, in their original order
+    pub fn connections_of<'a>(
+        &self,
+        pid: u32,
+        connections: &'a [NetworkConnection],
+    ) -> Vec<&'a NetworkConnection> {
+        let pid_by_name = self.pids_by_name();
+        let unattributed = connections
+            .iter()
+            .any(|c| c.pid.is_none() && c.process_name.is_none());
+        let ports = if unattributed {
+            socket_ports(pid)
+        } else {
+            Vec::new()
+        };
+
+        connections
+            .iter()
+            .filter(|conn| match self.attributed_pid(conn, &pid_by_name) {
+                Some(owner) => owner == pid,
+                None => ports.contains(&(conn.local_addr.port(), conn.remote_addr.port())),
+            })
+            .collect()
+    }
+
+    fn pids_by_name(&self) -> HashMap<&str, u32> {
+        self.processes
+            .values()
+            .map(|p| (p.name.as_str(), p.pid))
+            .collect()
+    }
+
+    /// Owner of a connection going by what ss reported: its pid, else its process name
+    fn attributed_pid(
+        &self,
+        conn: &NetworkConnection,
+        pid_by_name: &HashMap<&str, u32>,
+    ) -> Option<u32> {
+        conn.pid
+            .filter(|pid| self.processes.contains_key(pid))
+            .or_else(|| {
+                conn.process_name
+                    .as_deref()
+                    .and_then(|name| pid_by_name.get(name).copied())
+            })
+    }
+
+    // Map (local port, remote port) of each TCP socket to its owning pid by matching
+    // socket inodes in /proc/PID/fd against /proc/PID/net/tcp{,6}
+    fn socket_owners_by_ports(&self) -> HashMap<(u16, u16), u32> {
+        self.processes
+            .keys()
+            .flat_map(|&pid| socket_ports(pid).into_iter().map(move |ports| (ports, pid)))
+            .collect()
+    }
+
     pub fn get_listening_processes(&self) -> Vec<&ProcessNetworkInfo> {
         let mut processes: Vec<&ProcessNetworkInfo> = self
             .processes
@@ -402,13 +530,11 @@ impl ProcessMonitor {
             .filter(|p| p.listening_ports > 0)
             .collect();
 
-        processes.sort_by(|a, b| b.listening_ports.cmp(&a.listening_ports));
+        processes.sort_by_key(|p| std::cmp::Reverse(p.listening_ports));
         processes
     }
 
     fn get_real_processes_from_system(&mut self) {
-        use std::process::Command;
-
         // Use ps and lsof to get real process data with network activity
         if let Ok(output) = Command::new("lsof").args(["-i", "-n", "-P"]).output() {
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -420,10 +546,16 @@ impl ProcessMonitor {
                 self.parse_ps_processes(&stdout);
             }
         }
+
+        // Neither lsof nor `ps -o comm` gives arguments, so look them up in one ps call
+        let mut cmdlines = read_cmdlines(self.processes.keys().copied());
+        for (pid, process) in &mut self.processes {
+            process.cmdline = cmdlines.remove(pid);
+        }
     }
 
     fn parse_lsof_processes(&mut self, output: &str) {
-        let mut process_map: HashMap<u32, (String, String)> = HashMap::new(); // pid -> (name, command)
+        let mut process_map: HashMap<u32, String> = HashMap::new(); // pid -> name
         let mut process_connections: HashMap<u32, u32> = HashMap::new(); // pid -> total connections
         let mut process_listening: HashMap<u32, u32> = HashMap::new(); // pid -> listening ports
         let mut process_established: HashMap<u32, u32> = HashMap::new(); // pid -> established connections
@@ -450,23 +582,21 @@ impl ProcessMonitor {
                 }
 
                 // Store process info if not already seen
-                process_map.entry(pid).or_insert_with(|| {
-                    let command = process_name.to_string(); // lsof doesn't give full command
-                    (process_name, command)
-                });
+                process_map.entry(pid).or_insert(process_name);
             }
         }
 
         // Convert to ProcessNetworkInfo
         for (pid, total_connections) in process_connections {
-            if let Some((name, command)) = process_map.get(&pid) {
+            if let Some(name) = process_map.get(&pid) {
                 let listening_ports = process_listening.get(&pid).copied().unwrap_or(0);
                 let established_connections = process_established.get(&pid).copied().unwrap_or(0);
 
                 let process_info = ProcessNetworkInfo {
                     pid,
                     name: name.clone(),
-                    command: command.clone(),
+                    cmdline: None, // Filled in from ps afterwards
+                    container: None,
                     connections: total_connections,
                     bytes_sent: 0, // lsof doesn't provide byte counts
                     bytes_received: 0,
@@ -494,8 +624,9 @@ impl ProcessMonitor {
                 let name = parts[1].to_string();
                 let process_info = ProcessNetworkInfo {
                     pid,
-                    name: name.clone(),
-                    command: name,
+                    name,
+                    cmdline: None, // Filled in from ps afterwards
+                    container: None,
                     connections: 0,
                     bytes_sent: 0,
                     bytes_received: 0,
@@ -511,8 +642,645 @@ impl ProcessMonitor {
     }
 }
 
+/// (local port, remote port) of each TCP socket `pid` holds; empty off Linux
+#[cfg(target_os = "linux")]
+fn socket_ports(pid: u32) -> Vec<(u16, u16)> {
+    let inodes = socket_inodes(pid);
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ports = Vec::new();
+    for table in ["tcp", "tcp6"] {
+        if let Ok(content) = fs::read_to_string(format!("/proc/{pid}/net/{table}")) {
+            ports.extend(
+                parse_proc_net_socket_ports(&content)
+                    .into_iter()
+                    .filter(|(_, inode)| inodes.contains(inode))
+                    .map(|(ports, _)| ports),
+            );
+        }
+    }
+    ports
+}
+
+#[cfg(not(target_os = "linux"))]
+fn socket_ports(_pid: u32) -> Vec<(u16, u16)> {
+    Vec::new()
+}
+
+/// Inodes of the sockets among /proc/PID/fd; empty when we may not read another
+/// user's fd table
+pub(crate) fn socket_inodes(pid: u32) -> Vec<u64> {
+    let Ok(fds) = fs::read_dir(format!("/proc/{pid}/fd")) else {
+        return Vec::new();
+    };
+
+    fds.flatten()
+        .filter_map(|fd| fs::read_link(fd.path()).ok())
+        .filter_map(|target| {
+            target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok())
+        })
+        .collect()
+}
+
+/// Per-socket TCP counters by inode from `ss`, or None when ss can't be run
+fn read_socket_counters() -> Option<HashMap<u64, TrafficCounters>> {
+    // Without -a only connected sockets are listed: listeners never carry data
+    let output = Command::new("ss").arg("-tinHe").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_ss_socket_counters(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+// Parse `ss -tinHe`: a line per socket with its local address and `ino:N`, followed by
+// an indented line of `key:value` TCP info. Loopback sockets and inode 0 (sockets
+// already released by their process) are skipped.
+fn parse_ss_socket_counters(output: &str) -> HashMap<u64, TrafficCounters> {
+    let mut sockets = HashMap::new();
+    let mut inode = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let loopback = fields
+                .get(3)
+                .is_some_and(|local| is_loopback_address(local));
+            inode = fields
+                .iter()
+                .find_map(|field| field.strip_prefix("ino:"))
+                .and_then(|inode| inode.parse::<u64>().ok())
+                .filter(|&inode| inode != 0 && !loopback);
+            continue;
+        }
+
+        let Some(socket) = inode.take() else {
+            continue;
+        };
+        let mut counters = TrafficCounters::default();
+        for (key, value) in line.split_whitespace().filter_map(|f| f.split_once(':')) {
+            let Ok(value) = value.parse() else {
+                continue;
+            };
+            match key {
+                // Acknowledged rather than sent, so retransmissions aren't counted twice
+                "bytes_acked" => counters.bytes_sent = value,
+                "bytes_received" => counters.bytes_received = value,
+                "segs_out" => counters.packets_sent = value,
+                "segs_in" => counters.packets_received = value,
+                _ => {}
+            }
+        }
+        sockets.insert(socket, counters);
+    }
+
+    sockets
+}
+
+// `127.0.0.1:80`, `127.0.0.53%lo:53`, `[::1]:22` or `[::ffff:127.0.0.1]:8080`
+fn is_loopback_address(address: &str) -> bool {
+    let Some((ip, _port)) = address.rsplit_once(':') else {
+        return false;
+    };
+    let ip = ip.split('%').next().unwrap_or(ip);
+    match ip
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(IpAddr::V4(ip)) => ip.is_loopback(),
+        Ok(IpAddr::V6(ip)) => {
+            ip.is_loopback() || ip.to_ipv4_mapped().is_some_and(|ip| ip.is_loopback())
+        }
+        Err(_) => false,
+    }
+}
+
+/// Interface counters summed over a /proc/net/dev, leaving out `lo`
+fn parse_net_dev(content: &str) -> TrafficCounters {
+    let mut counters = TrafficCounters::default();
+
+    for line in content.lines().skip(2) {
+        // "  eth0: rx_bytes rx_packets ... (8 receive fields) tx_bytes tx_packets ..."
+        let Some((name, fields)) = line.split_once(':') else {
+            continue;
+        };
+        if name.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = fields
+            .split_whitespace()
+            .filter_map(|field| field.parse().ok())
+            .collect();
+        if fields.len() >= 10 {
+            counters.add(&TrafficCounters {
+                bytes_received: fields[0],
+                packets_received: fields[1],
+                bytes_sent: fields[8],
+                packets_sent: fields[9],
+            });
+        }
+    }
+
+    counters
+}
+
+// Extract (state, inode) from /proc/net/{tcp,udp}-style tables
+fn parse_proc_net_socket_states(content: &str) -> Vec<(u8, u64)> {
+    content
+        .lines()
+        .skip(1) // Skip header
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let state = u8::from_str_radix(fields[3], 16).ok()?;
+            let inode = fields[9].parse().ok()?;
+            Some((state, inode))
+        })
+        .collect()
+}
+
+// Extract ((local port, remote port), inode) from /proc/net/tcp-style tables
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_net_socket_ports(content: &str) -> Vec<((u16, u16), u64)> {
+    let port_of = |addr: &str| {
+        addr.rsplit(':')
+            .next()
+            .and_then(|port| u16::from_str_radix(port, 16).ok())
+    };
+
+    content
+        .lines()
+        .skip(1) // Skip header
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let local_port = port_of(fields[1])?;
+            let remote_port = port_of(fields[2])?;
+            let inode = fields[9].parse().ok()?;
+            Some(((local_port, remote_port), inode))
+        })
+        .collect()
+}
+
+/// Short container ID from a /proc/PID/cgroup file. Runtimes put the 64-hex-digit
+/// ID in the cgroup path, bare (`/docker/<id>`, `/kubepods/burstable/pod<uid>/<id>`)
+/// or as a systemd scope (`docker-<id>.scope`, `cri-containerd-<id>.scope`,
+/// `crio-<id>.scope`, `libpod-<id>.scope`).
+fn parse_cgroup_container(content: &str) -> Option<String> {
+    content
+        .lines()
+        // "hierarchy-ID:controllers:path", cgroup v2 being the single "0::path"
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .flat_map(|path| path.split('/').rev())
+        .find_map(|component| {
+            let id = component.strip_suffix(".scope").unwrap_or(component);
+            let id = id.rsplit('-').next()?;
+            (id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit()))
+                .then(|| id[..12].to_string())
+        })
+}
+
+/// Command lines for `pids`, as in [`ProcessNetworkInfo::cmdline`]. Processes whose
+/// command line can't be read are left out.
+pub fn read_cmdlines(pids: impl IntoIterator<Item = u32>) -> HashMap<u32, String> {
+    #[cfg(target_os = "linux")]
+    {
+        pids.into_iter()
+            .filter_map(|pid| read_proc_cmdline(pid).map(|cmdline| (pid, cmdline)))
+            .collect()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut pids: Vec<String> = pids.into_iter().map(|pid| pid.to_string()).collect();
+        if pids.is_empty() {
+            return HashMap::new();
+        }
+        pids.sort();
+        pids.dedup();
+        // One ps call for all pids; pids that exited in the meantime are simply missing
+        match Command::new("ps")
+            .args(["-o", "pid=,command=", "-p", &pids.join(",")])
+            .output()
+        {
+            Ok(output) => parse_ps_cmdlines(&String::from_utf8_lossy(&output.stdout)),
+            Err(_) => HashMap::new(),
+        }
+    }
+}
+
+/// /proc/PID/cmdline with its NUL separators turned into spaces
+fn read_proc_cmdline(pid: u32) -> Option<String> {
+    // Permission denied (hidepid mounts) and vanished processes both read as None
+    let raw = fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    parse_proc_cmdline(&raw)
+}
+
+fn parse_proc_cmdline(raw: &[u8]) -> Option<String> {
+    let args: Vec<String> = raw
+        .split(|&byte| byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    (!args.is_empty()).then(|| args.join(" "))
+}
+
+// Parse `ps -o pid=,command=` output: the pid, then the command line to the end of the line
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_ps_cmdlines(output: &str) -> HashMap<u32, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, cmdline) = line.trim_start().split_once(char::is_whitespace)?;
+            let cmdline = cmdline.trim();
+            if cmdline.is_empty() {
+                return None;
+            }
+            Some((pid.parse().ok()?, cmdline.to_string()))
+        })
+        .collect()
+}
+
+/// Whether process `pid` exists and this user can see it (`--pid`)
+pub fn is_running(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    return Path::new(&format!("/proc/{pid}")).exists();
+
+    // kill(2) with signal 0 only checks the process exists and may be signalled
+    #[cfg(not(target_os = "linux"))]
+    return libc::pid_t::try_from(pid)
+        .is_ok_and(|target| target > 0 && unsafe { libc::kill(target, 0) } == 0);
+}
+
+/// The connections `pid` owns (`--pid`): those ss attributed to it, plus unattributed
+/// TCP sockets found in its /proc/PID/fd table
+pub fn connections_of_pid(pid: u32, connections: &[NetworkConnection]) -> Vec<NetworkConnection> {
+    let unattributed = connections.iter().any(|c| c.process_name.is_none());
+    let ports = if unattributed {
+        socket_ports(pid)
+    } else {
+        Vec::new()
+    };
+
+    connections
+        .iter()
+        .filter(|conn| match conn.process_name {
+            // ss names the owner along with its pid; without a name the pid column
+            // isn't reliable (the /proc fallback has only the uid)
+            Some(_) => conn.pid == Some(pid),
+            None => ports.contains(&(conn.local_addr.port(), conn.remote_addr.port())),
+        })
+        .cloned()
+        .collect()
+}
+
+/// Send SIGTERM to `pid`, or SIGKILL when `force` is set. PID 0 and 1 and netwatch
+/// itself are refused: signalling 0 would hit our whole process group.
+pub fn signal_process(pid: u32, force: bool) -> std::io::Result<()> {
+    let target = match libc::pid_t::try_from(pid) {
+        Ok(target) if target > 1 && pid != std::process::id() => target,
+        _ => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("refusing to signal PID {pid}"),
+            ))
+        }
+    };
+
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+    // SAFETY: kill(2) takes plain integers and has no memory side effects
+    if unsafe { libc::kill(target, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
 impl Default for ProcessMonitor {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connections::{ConnectionState, Protocol, SocketInfo};
+    use std::time::Duration;
+
+    fn process(pid: u32, name: &str) -> ProcessNetworkInfo {
+        ProcessNetworkInfo {
+            pid,
+            name: name.to_string(),
+            cmdline: None,
+            container: None,
+            connections: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            packets_sent: 0,
+            packets_received: 0,
+            established_connections: 0,
+            listening_ports: 0,
+            last_updated: SystemTime::now(),
+        }
+    }
+
+    // `rates` is (sent, received) bytes/s, as measured on a socket's second update
+    fn connection(
+        pid: Option<u32>,
+        name: Option<&str>,
+        rates: Option<(u64, u64)>,
+        bandwidth: Option<u64>,
+    ) -> NetworkConnection {
+        NetworkConnection {
+            local_addr: "10.0.0.1:40000".parse().unwrap(),
+            remote_addr: "10.0.0.2:443".parse().unwrap(),
+            state: ConnectionState::Established,
+            protocol: Protocol::Tcp,
+            pid,
+            process_name: name.map(str::to_string),
+            process_cmdline: None,
+            // Cumulative counters, far larger than any rate
+            bytes_sent: 1_000_000,
+            bytes_received: 9_000_000,
+            socket_info: SocketInfo {
+                bandwidth,
+                ..SocketInfo::default()
+            },
+            established_at: None,
+            sent_per_sec: rates.map(|(sent, _)| sent),
+            received_per_sec: rates.map(|(_, received)| received),
+        }
+    }
+
+    #[test]
+    fn test_bandwidth_by_process_sums_and_sorts() {
+        let mut monitor = ProcessMonitor::new();
+        monitor.processes.insert(10, process(10, "curl"));
+        monitor.processes.insert(20, process(20, "firefox"));
+
+        let connections = vec![
+            connection(Some(10), Some("curl"), Some((100, 200)), None),
+            connection(None, Some("firefox"), Some((1_000, 5_000)), Some(48_000)),
+            // Not measured yet: the 8 kbit/s estimate is 1000 B/s out, never the totals
+            connection(Some(20), None, None, Some(8_000)),
+            connection(Some(99), Some("unknown"), Some((50, 50)), None),
+        ];
+
+        let result = monitor.bandwidth_by_process(&connections);
+        let summary: Vec<(u32, u64, u64)> = result
+            .iter()
+            .map(|(p, inbound, outbound)| (p.pid, *inbound, *outbound))
+            .collect();
+
+        assert_eq!(summary, vec![(20, 5_000, 2_000), (10, 200, 100)]);
+
+        // The drill-down lists the same connections the totals were built from
+        let firefox: Vec<Option<u64>> = monitor
+            .connections_of(20, &connections)
+            .iter()
+            .map(|conn| conn.received_per_sec)
+            .collect();
+        assert_eq!(firefox, vec![Some(5_000), None]);
+        assert_eq!(monitor.connections_of(10, &connections).len(), 1);
+        assert!(monitor.connections_of(99, &connections).is_empty());
+    }
+
+    #[test]
+    fn test_socket_counters_attribute_measured_rates() {
+        let ss = "\
+ESTAB 0 0 10.0.0.1:40000 93.184.216.34:443 uid:1000 ino:5001 sk:1 cgroup:/ <->
+\t cubic rtt:12.5/3 bytes_sent:4200 bytes_acked:4000 bytes_received:90000 segs_out:40 segs_in:70
+ESTAB 0 0 [::ffff:127.0.0.1]:8080 [::ffff:127.0.0.1]:51000 ino:5002 sk:2 <->
+\t cubic bytes_acked:777 bytes_received:777
+ESTAB 0 0 127.0.0.53%lo:53 127.0.0.1:41000 ino:5004 sk:4 <->
+\t cubic bytes_acked:555 bytes_received:555
+ESTAB 0 0 10.0.0.1:40001 10.0.0.9:22 ino:5003 sk:3 <->
+\t cubic bytes_acked:100 bytes_received:300 segs_out:2 segs_in:3
+FIN-WAIT-2 0 0 10.0.0.1:40002 10.0.0.9:80 ino:0 sk:5 <->
+\t cubic bytes_acked:999 bytes_received:999
+";
+        let sockets = parse_ss_socket_counters(ss);
+        assert_eq!(
+            sockets.len(),
+            2,
+            "loopback and released sockets are skipped"
+        );
+        assert_eq!(
+            sockets[&5001],
+            TrafficCounters {
+                bytes_sent: 4000,
+                bytes_received: 90000,
+                packets_sent: 40,
+                packets_received: 70,
+            }
+        );
+
+        let net_dev = "Inter-|   Receive                            |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:    5000      50    0    0    0     0          0         0     5000      50    0    0    0     0       0          0
+  eth0:10000 100 0 0 0 0 0 0 2000 20 0 0 0 0 0 0
+";
+        let namespace = parse_net_dev(net_dev);
+        assert_eq!(
+            (namespace.bytes_received, namespace.bytes_sent),
+            (10_000, 2_000)
+        );
+
+        let mut monitor = ProcessMonitor::new();
+        for (pid, name) in [(10, "curl"), (20, "sshd"), (30, "nginx")] {
+            monitor.processes.insert(pid, process(pid, name));
+        }
+        let owners = HashMap::from([(5001, 10), (5003, 20)]);
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let container = |counters| HashMap::from([("net:[4026532]".to_string(), (30, counters))]);
+
+        // The first update only records a baseline
+        monitor.attribute_traffic(&owners, sockets, container(namespace), start);
+        assert_eq!(monitor.processes[&10].total_bytes(), 0);
+
+        // Two seconds later: curl's socket grew, sshd's closed and a new one opened
+        let sockets = HashMap::from([
+            (
+                5001,
+                TrafficCounters {
+                    bytes_sent: 6000,
+                    bytes_received: 190_000,
+                    packets_sent: 50,
+                    packets_received: 140,
+                },
+            ),
+            (
+                5005,
+                TrafficCounters {
+                    bytes_sent: 800,
+                    bytes_received: 400,
+                    ..TrafficCounters::default()
+                },
+            ),
+        ]);
+        let owners = HashMap::from([(5001, 10), (5005, 20)]);
+        let namespace = TrafficCounters {
+            bytes_sent: 6_000,
+            bytes_received: 30_000,
+            ..namespace
+        };
+        monitor.attribute_traffic(
+            &owners,
+            sockets,
+            container(namespace),
+            start + Duration::from_secs(2),
+        );
+
+        let rates = |pid| {
+            let process = &monitor.processes[&pid];
+            (process.bytes_received, process.bytes_sent)
+        };
+        assert_eq!(rates(10), (50_000, 1_000));
+        assert_eq!(monitor.processes[&10].packets_received, 35);
+        assert_eq!(rates(20), (200, 400));
+        assert_eq!(rates(30), (10_000, 2_000));
+        assert_eq!(monitor.get_process_stats().bytes_received, 60_200);
+        assert_eq!(
+            monitor
+                .get_top_network_processes(2)
+                .iter()
+                .map(|process| process.pid)
+                .collect::<Vec<_>>(),
+            vec![10, 30]
+        );
+    }
+
+    #[test]
+    fn test_parse_cmdlines() {
+        assert_eq!(
+            parse_proc_cmdline(b"java\0-Xmx2g\0-jar\0app.jar\0").as_deref(),
+            Some("java -Xmx2g -jar app.jar")
+        );
+        // Kernel threads have an empty cmdline
+        assert_eq!(parse_proc_cmdline(b""), None);
+
+        let cmdlines = parse_ps_cmdlines(
+            "  412 /usr/bin/python3 -m http.server 8000\n 9001 \n88 /Applications/Foo.app/Contents/MacOS/Foo\n",
+        );
+        assert_eq!(cmdlines[&412], "/usr/bin/python3 -m http.server 8000");
+        assert_eq!(cmdlines[&88], "/Applications/Foo.app/Contents/MacOS/Foo");
+        assert!(!cmdlines.contains_key(&9001));
+    }
+
+    #[test]
+    fn test_cgroup_container_and_grouping() {
+        let id = "4f1c2d9e8b7a65432100fedcba9876543210abcdef0123456789abcdef012345";
+        for cgroup in [
+            format!("12:pids:/docker/{id}\n11:memory:/docker/{id}\n"),
+            format!("0::/system.slice/docker-{id}.scope\n"),
+            format!("0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1a2b.slice/cri-containerd-{id}.scope\n"),
+            format!("3:cpu:/kubepods/besteffort/pod8c1d7e3a-0f6b-4d2c-9a55-2b7c1e0d4f3a/{id}\n"),
+            format!("0::/machine.slice/libpod-{id}.scope/container\n"),
+        ] {
+            assert_eq!(
+                parse_cgroup_container(&cgroup).as_deref(),
+                Some("4f1c2d9e8b7a"),
+                "{cgroup}"
+            );
+        }
+        assert_eq!(
+            parse_cgroup_container("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+        assert_eq!(parse_cgroup_container("0::/init.scope\n"), None);
+
+        let mut monitor = ProcessMonitor::new();
+        for (pid, container, sent, connections) in [
+            (1, None, 100, 3),
+            (2, Some("4f1c2d9e8b7a"), 500, 1),
+            (3, Some("4f1c2d9e8b7a"), 250, 2),
+            (4, None, 0, 1),
+        ] {
+            let mut info = process(pid, "proc");
+            info.container = container.map(str::to_string);
+            info.bytes_sent = sent;
+            info.connections = connections;
+            monitor.processes.insert(pid, info);
+        }
+        let groups = monitor.get_container_totals();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].container, "4f1c2d9e8b7a");
+        assert_eq!(groups[0].processes, 2);
+        assert_eq!(groups[0].connections, 3);
+        assert_eq!(groups[0].total_bytes(), 750);
+        assert_eq!(groups[1].container, "-");
+        assert_eq!(groups[1].processes, 2);
+        assert_eq!(groups[1].bytes_sent, 100);
+    }
+
+    #[test]
+    fn test_parse_proc_net_socket_ports() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 12345 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:9C40 0200000A:01BB 01 00000000:00000000 00:00000000 00000000  1000        0 67890 1 0000000000000000 20 4 30 10 -1";
+
+        assert_eq!(
+            parse_proc_net_socket_ports(content),
+            vec![((8080, 0), 12345), ((40000, 443), 67890)]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_connections_of_pid_reads_fd_table() {
+        // A real socket of ours, which the /proc/PID/fd lookup has to find
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let ours = std::process::id();
+
+        let mut unattributed = connection(None, None, None, None);
+        unattributed.local_addr = stream.local_addr().unwrap();
+        unattributed.remote_addr = listener.local_addr().unwrap();
+        let mut stranger = unattributed.clone();
+        stranger.remote_addr.set_port(1);
+        let connections = vec![
+            unattributed,
+            stranger,
+            connection(Some(ours), Some("netwatch"), None, None),
+            connection(Some(ours + 1), Some("nginx"), None, None),
+        ];
+
+        let owned = connections_of_pid(ours, &connections);
+        assert_eq!(owned.len(), 2);
+        assert_eq!(owned[0].local_addr, stream.local_addr().unwrap());
+        assert_eq!(owned[1].process_name.as_deref(), Some("netwatch"));
+
+        assert!(is_running(ours));
+        assert!(!is_running(u32::MAX));
+    }
+
+    #[test]
+    fn test_signal_process_guards_and_terminates() {
+        for pid in [0, 1, std::process::id(), u32::MAX] {
+            let err = signal_process(pid, true).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        }
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        signal_process(child.id(), false).unwrap();
+        let status = child.wait().unwrap();
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+    }
+}
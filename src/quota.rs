@@ -0,0 +1,349 @@
+//! Traffic quotas for ISP data caps and cloud egress billing
+//!
+//! [`QuotaTracker`] counts the bytes (in plus out) an interface moves today, this week
+//! and this month, and keeps the counts in `<data dir>/netwatch/quota/INTERFACE.json`
+//! so they carry across runs. Periods are UTC: days roll over at midnight, weeks on
+//! Monday at 00:00 and months on the 1st. Only traffic seen while netwatch runs is
+//! counted. [`evaluate`] raises a warning at 80% of a `--quota-*-gb` limit and a
+//! critical alert once it is used up.
+
+use crate::alerts::{Alert, AlertKind, AlertSeverity};
+use crate::config::Config;
+use crate::stats::StatsCalculator;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Days, Months, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Bytes in a GB of the `--quota-*-gb` flags: decimal, as ISPs and clouds bill
+pub const BYTES_PER_GB: f64 = 1_000_000_000.0;
+/// Share of a quota at which it is a warning; using it all up is critical
+pub const QUOTA_WARNING: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QuotaPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl QuotaPeriod {
+    pub const ALL: [Self; 3] = [Self::Daily, Self::Weekly, Self::Monthly];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    /// When the period holding `now` began
+    pub fn start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.date_naive();
+        let first_day = match self {
+            Self::Daily => today,
+            Self::Weekly => today - Days::new(today.weekday().num_days_from_monday().into()),
+            Self::Monthly => today - Days::new(today.day0().into()),
+        };
+        first_day.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// When the period holding `now` ends and the next one begins
+    pub fn next_reset(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let start = self.start(now);
+        match self {
+            Self::Daily => start + Days::new(1),
+            Self::Weekly => start + Days::new(7),
+            Self::Monthly => start + Months::new(1),
+        }
+    }
+}
+
+/// Bytes counted in one period, and when that period began (Unix seconds)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeriodTotal {
+    pub start: i64,
+    pub bytes: u64,
+}
+
+/// What is saved per interface
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaTotals {
+    pub daily: PeriodTotal,
+    pub weekly: PeriodTotal,
+    pub monthly: PeriodTotal,
+}
+
+impl QuotaTotals {
+    pub fn get(&self, period: QuotaPeriod) -> PeriodTotal {
+        match period {
+            QuotaPeriod::Daily => self.daily,
+            QuotaPeriod::Weekly => self.weekly,
+            QuotaPeriod::Monthly => self.monthly,
+        }
+    }
+
+    fn get_mut(&mut self, period: QuotaPeriod) -> &mut PeriodTotal {
+        match period {
+            QuotaPeriod::Daily => &mut self.daily,
+            QuotaPeriod::Weekly => &mut self.weekly,
+            QuotaPeriod::Monthly => &mut self.monthly,
+        }
+    }
+}
+
+/// Quotas in bytes, from `--quota-daily-gb`, `--quota-weekly-gb` and `--quota-monthly-gb`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuotaLimits {
+    pub daily: Option<u64>,
+    pub weekly: Option<u64>,
+    pub monthly: Option<u64>,
+}
+
+impl QuotaLimits {
+    pub fn from_config(config: &Config) -> Self {
+        let bytes = |gb: Option<f64>| gb.map(|gb| (gb * BYTES_PER_GB) as u64);
+        Self {
+            daily: bytes(config.quota_daily_gb),
+            weekly: bytes(config.quota_weekly_gb),
+            monthly: bytes(config.quota_monthly_gb),
+        }
+    }
+
+    pub fn get(&self, period: QuotaPeriod) -> Option<u64> {
+        match period {
+            QuotaPeriod::Daily => self.daily,
+            QuotaPeriod::Weekly => self.weekly,
+            QuotaPeriod::Monthly => self.monthly,
+        }
+    }
+
+    /// No quota is set, so nothing needs tracking
+    pub fn is_empty(&self) -> bool {
+        QuotaPeriod::ALL
+            .iter()
+            .all(|&period| self.get(period).is_none())
+    }
+}
+
+/// Daily, weekly and monthly byte totals of one interface
+#[derive(Debug, Clone)]
+pub struct QuotaTracker {
+    interface: String,
+    totals: QuotaTotals,
+    // The calculator's in+out total at the previous update; None until the first
+    last_total: Option<u64>,
+}
+
+impl QuotaTracker {
+    pub fn new(interface: &str) -> Self {
+        Self {
+            interface: interface.to_string(),
+            totals: QuotaTotals::default(),
+            last_total: None,
+        }
+    }
+
+    /// `<data dir>/netwatch/quota/INTERFACE.json` (`~/.local/share/netwatch` on Linux)
+    pub fn path(interface: &str) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| {
+            dir.join("netwatch")
+                .join("quota")
+                .join(format!("{}.json", interface.replace('/', "_")))
+        })
+    }
+
+    /// The interface's saved totals. A missing file starts from nothing.
+    pub fn load(interface: &str) -> Result<Self> {
+        let mut tracker = Self::new(interface);
+        if let Some(path) = Self::path(interface).filter(|path| path.exists()) {
+            tracker.totals = serde_json::from_str(&fs::read_to_string(path)?)?;
+        }
+        Ok(tracker)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path(&self.interface)
+            .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(&self.totals)?)?;
+        Ok(())
+    }
+
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// Count what the calculator's total grew by since the previous call; call it
+    /// once per sample. The first call only notes where the total stands.
+    pub fn update(&mut self, calculator: &StatsCalculator) {
+        if calculator.sample_count() == 0 {
+            return;
+        }
+        let (total_in, total_out) = calculator.total_bytes();
+        self.add_total_at(total_in.saturating_add(total_out), Utc::now());
+    }
+
+    /// [`update`](Self::update) with the calculator's in+out total and the time given
+    pub fn add_total_at(&mut self, total: u64, now: DateTime<Utc>) {
+        // A fresh calculator (the interface came back) starts lower; count from there
+        let grew = self.last_total.map_or(0, |last| total.saturating_sub(last));
+        self.last_total = Some(total);
+
+        for period in QuotaPeriod::ALL {
+            let start = period.start(now).timestamp();
+            let entry = self.totals.get_mut(period);
+            if entry.start != start {
+                *entry = PeriodTotal { start, bytes: 0 };
+            }
+            entry.bytes = entry.bytes.saturating_add(grew);
+        }
+    }
+
+    /// Bytes counted in the period holding `now`
+    pub fn used(&self, period: QuotaPeriod, now: DateTime<Utc>) -> u64 {
+        let entry = self.totals.get(period);
+        if entry.start == period.start(now).timestamp() {
+            entry.bytes
+        } else {
+            0
+        }
+    }
+}
+
+/// Share of `limit` used, 1.0 being all of it
+pub fn used_fraction(used: u64, limit: u64) -> f64 {
+    if limit == 0 {
+        return 1.0;
+    }
+    used as f64 / limit as f64
+}
+
+/// Quota alerts for every tracked interface, in name order
+pub fn evaluate(
+    trackers: &HashMap<String, QuotaTracker>,
+    limits: &QuotaLimits,
+    now: DateTime<Utc>,
+) -> Vec<Alert> {
+    let mut trackers: Vec<&QuotaTracker> = trackers.values().collect();
+    trackers.sort_by(|a, b| a.interface.cmp(&b.interface));
+
+    let mut alerts = Vec::new();
+    for tracker in trackers {
+        for period in QuotaPeriod::ALL {
+            let Some(limit) = limits.get(period) else {
+                continue;
+            };
+            let used = tracker.used(period, now);
+            let fraction = used_fraction(used, limit);
+            let severity = if fraction >= 1.0 {
+                AlertSeverity::Critical
+            } else if fraction >= QUOTA_WARNING {
+                AlertSeverity::Warning
+            } else {
+                continue;
+            };
+            alerts.push(Alert {
+                kind: AlertKind::Quota(period),
+                severity,
+                interface: Some(tracker.interface.clone()),
+                value: used,
+                message: format!(
+                    "{} {} quota {:.0}% used: {:.2} of {:.2} GB",
+                    tracker.interface,
+                    period.as_str(),
+                    fraction * 100.0,
+                    used as f64 / BYTES_PER_GB,
+                    limit as f64 / BYTES_PER_GB
+                ),
+            });
+        }
+    }
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_periods_roll_over_in_utc() {
+        // Wednesday 2026-12-30 15:00
+        let now = at(2026, 12, 30, 15);
+        assert_eq!(QuotaPeriod::Daily.start(now), at(2026, 12, 30, 0));
+        assert_eq!(QuotaPeriod::Daily.next_reset(now), at(2026, 12, 31, 0));
+        assert_eq!(QuotaPeriod::Weekly.start(now), at(2026, 12, 28, 0));
+        assert_eq!(QuotaPeriod::Weekly.next_reset(now), at(2027, 1, 4, 0));
+        assert_eq!(QuotaPeriod::Monthly.start(now), at(2026, 12, 1, 0));
+        assert_eq!(QuotaPeriod::Monthly.next_reset(now), at(2027, 1, 1, 0));
+
+        // Midnight itself already belongs to the new day
+        assert_eq!(
+            QuotaPeriod::Daily.start(at(2027, 1, 1, 0)),
+            at(2027, 1, 1, 0)
+        );
+    }
+
+    #[test]
+    fn test_tracker_counts_growth_and_resets_each_period() {
+        let mut tracker = QuotaTracker::new("eth0");
+        // The first sample only seeds: whatever the counters held before isn't counted
+        tracker.add_total_at(5_000_000, at(2026, 12, 31, 22));
+        tracker.add_total_at(5_600_000, at(2026, 12, 31, 23));
+        assert_eq!(
+            tracker.used(QuotaPeriod::Daily, at(2026, 12, 31, 23)),
+            600_000
+        );
+
+        // New day, week (Monday 2026-12-28 already began it) and month
+        tracker.add_total_at(5_700_000, at(2027, 1, 1, 1));
+        let now = at(2027, 1, 1, 1);
+        assert_eq!(tracker.used(QuotaPeriod::Daily, now), 100_000);
+        assert_eq!(tracker.used(QuotaPeriod::Weekly, now), 700_000);
+        assert_eq!(tracker.used(QuotaPeriod::Monthly, now), 100_000);
+
+        // A restarted calculator counts on from its own total
+        tracker.add_total_at(1_000, now);
+        tracker.add_total_at(3_000, now);
+        assert_eq!(tracker.used(QuotaPeriod::Daily, now), 102_000);
+
+        // Nothing recorded for a day that has since passed
+        assert_eq!(tracker.used(QuotaPeriod::Daily, at(2027, 1, 2, 0)), 0);
+    }
+
+    #[test]
+    fn test_alerts_at_80_and_100_percent() {
+        let now = at(2026, 10, 17, 12);
+        let limits = QuotaLimits {
+            daily: Some(1_000),
+            monthly: Some(100_000),
+            ..QuotaLimits::default()
+        };
+        let mut eth0 = QuotaTracker::new("eth0");
+        eth0.add_total_at(0, now);
+        eth0.add_total_at(850, now);
+        let mut wlan0 = QuotaTracker::new("wlan0");
+        wlan0.add_total_at(0, now);
+        wlan0.add_total_at(1_000, now);
+        let trackers = HashMap::from([("wlan0".to_string(), wlan0), ("eth0".to_string(), eth0)]);
+
+        let alerts = evaluate(&trackers, &limits, now);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].kind, AlertKind::Quota(QuotaPeriod::Daily));
+        assert_eq!(alerts[0].severity, AlertSeverity::Warning);
+        assert_eq!(alerts[0].interface.as_deref(), Some("eth0"));
+        assert_eq!(alerts[1].severity, AlertSeverity::Critical);
+        assert!(alerts[1].message.starts_with("wlan0 daily quota 100% used"));
+    }
+}
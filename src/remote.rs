@@ -0,0 +1,134 @@
+//! `--remote [user@]host`: interface counters and connections of another Linux machine,
+//! read over the system `ssh`
+//!
+//! Every command goes through one multiplexed connection (`ControlMaster`), so a refresh
+//! costs a round trip rather than a handshake. The master is started when the host is
+//! opened and lingers for [`CONTROL_PERSIST`] after the last command. Authentication must
+//! work without prompting (keys or an agent): the dashboard owns the terminal.
+//!
+//! Only counters and sockets come from the remote host. Process, system and diagnostics
+//! panels still describe the machine netwatch runs on.
+
+use crate::{
+    device::{NetworkReader, NetworkStats},
+    error::{NetwatchError, Result},
+    platform::{parse_proc_net_dev, proc_net_dev_devices},
+};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long the shared SSH connection stays open after the last command
+pub const CONTROL_PERSIST: &str = "60s";
+/// Seconds ssh may take to set up the connection before giving up
+const CONNECT_TIMEOUT_SECS: u32 = 10;
+/// How long one fetch of /proc/net/dev serves reads, so the per-interface reads of one
+/// refresh share a round trip
+const PROC_NET_DEV_TTL: Duration = Duration::from_millis(250);
+
+/// An SSH destination with its multiplexed connection
+#[derive(Debug)]
+pub struct RemoteHost {
+    target: String,
+    control_path: PathBuf,
+}
+
+impl RemoteHost {
+    /// Start the shared connection to `target`, failing early when ssh cannot log in
+    pub fn connect(target: &str) -> Result<Self> {
+        crate::validation::validate_ssh_target(target)?;
+
+        // The control socket grants use of the connection, so only this user may reach it
+        // SAFETY: getuid(2) cannot fail
+        let uid = unsafe { libc::getuid() };
+        let control_dir = std::env::temp_dir().join(format!("netwatch-ssh-{uid}"));
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&control_dir)?;
+
+        let host = Self {
+            target: target.to_string(),
+            // %C is a hash of the connection parameters, short enough for a socket path
+            control_path: control_dir.join("%C"),
+        };
+        host.run("true")?;
+        Ok(host)
+    }
+
+    /// Run `command` on the remote host and return its stdout
+    pub fn run(&self, command: &str) -> Result<String> {
+        let output = Command::new("ssh")
+            .arg("-o")
+            .arg("BatchMode=yes")
+            .arg("-o")
+            .arg(format!("ConnectTimeout={CONNECT_TIMEOUT_SECS}"))
+            .arg("-o")
+            .arg("ControlMaster=auto")
+            .arg("-o")
+            .arg(format!("ControlPath={}", self.control_path.display()))
+            .arg("-o")
+            .arg(format!("ControlPersist={CONTROL_PERSIST}"))
+            .arg("--")
+            .arg(&self.target)
+            .arg(command)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| NetwatchError::Platform(format!("Cannot run ssh: {e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(NetwatchError::Platform(format!(
+                "ssh {} {command:?} failed: {}",
+                self.target,
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Interface counters from the remote host's /proc/net/dev
+pub struct RemoteReader {
+    host: Arc<RemoteHost>,
+    // The last fetch and when it was made
+    cached: Mutex<Option<(Instant, String)>>,
+}
+
+impl RemoteReader {
+    pub fn new(host: Arc<RemoteHost>) -> Self {
+        Self {
+            host,
+            cached: Mutex::new(None),
+        }
+    }
+
+    fn proc_net_dev(&self) -> Result<String> {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((fetched, ref content)) = *cached {
+            if fetched.elapsed() < PROC_NET_DEV_TTL {
+                return Ok(content.clone());
+            }
+        }
+
+        let content = self.host.run("cat /proc/net/dev")?;
+        *cached = Some((Instant::now(), content.clone()));
+        Ok(content)
+    }
+}
+
+impl NetworkReader for RemoteReader {
+    fn list_devices(&self) -> Result<Vec<String>> {
+        Ok(proc_net_dev_devices(&self.proc_net_dev()?))
+    }
+
+    fn read_stats(&self, device: &str) -> Result<NetworkStats> {
+        parse_proc_net_dev(&self.proc_net_dev()?, device)
+    }
+
+    fn is_available(&self) -> bool {
+        self.proc_net_dev().is_ok()
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::CStr;
+use std::net::IpAddr;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Hostnames remembered before the least recently used entry is evicted
+pub const DEFAULT_CACHE_SIZE: usize = 1024;
+
+// NI_MAXHOST from <netdb.h>
+const MAX_HOSTNAME_LEN: usize = 1025;
+
+/// Reverse-DNS lookups performed on a background thread so callers never block.
+/// Both hostnames and failed lookups are cached; clones share the cache and worker.
+#[derive(Clone)]
+pub struct ReverseDnsResolver {
+    cache: Arc<Mutex<LruCache>>,
+    pending: Arc<Mutex<HashSet<IpAddr>>>,
+    sender: Sender<IpAddr>,
+}
+
+impl ReverseDnsResolver {
+    pub fn new(cache_size: usize) -> Self {
+        let cache = Arc::new(Mutex::new(LruCache::new(cache_size)));
+        let pending = Arc::new(Mutex::new(HashSet::new()));
+        let (sender, receiver) = mpsc::channel::<IpAddr>();
+
+        let worker_cache = Arc::clone(&cache);
+        let worker_pending = Arc::clone(&pending);
+        thread::spawn(move || {
+            // Exits once every clone of the resolver (and its sender) is dropped
+            for ip in receiver {
+                let hostname = reverse_lookup(ip);
+                if let Ok(mut cache) = worker_cache.lock() {
+                    cache.insert(ip, hostname);
+                }
+                if let Ok(mut pending) = worker_pending.lock() {
+                    pending.remove(&ip);
+                }
+            }
+        });
+
+        Self {
+            cache,
+            pending,
+            sender,
+        }
+    }
+
+    /// Cached hostname for `ip`. On a miss the lookup is queued and None is returned.
+    pub fn lookup(&self, ip: IpAddr) -> Option<String> {
+        if ip.is_unspecified() || ip.is_loopback() {
+            return None;
+        }
+
+        if let Ok(mut cache) = self.cache.lock() {
+            if let Some(hostname) = cache.get(&ip) {
+                return hostname;
+            }
+        }
+
+        if let Ok(mut pending) = self.pending.lock() {
+            if pending.insert(ip) && self.sender.send(ip).is_err() {
+                pending.remove(&ip);
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for ReverseDnsResolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_SIZE)
+    }
+}
+
+/// Bounded map from IP to hostname (None = lookup failed), evicting the least recently used entry
+struct LruCache {
+    capacity: usize,
+    entries: HashMap<IpAddr, (Option<String>, u64)>,
+    tick: u64,
+}
+
+impl LruCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn get(&mut self, ip: &IpAddr) -> Option<Option<String>> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(ip).map(|(hostname, last_used)| {
+            *last_used = tick;
+            hostname.clone()
+        })
+    }
+
+    fn insert(&mut self, ip: IpAddr, hostname: Option<String>) {
+        if !self.entries.contains_key(&ip) && self.entries.len() >= self.capacity {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(ip, _)| *ip)
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.tick += 1;
+        self.entries.insert(ip, (hostname, self.tick));
+    }
+}
+
+/// Blocking getnameinfo(3) lookup of `ip`'s hostname
+pub(crate) fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    let mut host = [0 as libc::c_char; MAX_HOSTNAME_LEN];
+
+    let result = match ip {
+        IpAddr::V4(v4) => {
+            // SAFETY: sockaddr_in is plain old data; all-zero is a valid starting value
+            let mut addr: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            addr.sin_family = libc::AF_INET as libc::sa_family_t;
+            addr.sin_addr = libc::in_addr {
+                s_addr: u32::from_ne_bytes(v4.octets()),
+            };
+            #[cfg(target_os = "macos")]
+            {
+                addr.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+            }
+
+            // SAFETY: addr and host are valid for the lengths passed
+            unsafe {
+                libc::getnameinfo(
+                    &addr as *const libc::sockaddr_in as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    host.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+        IpAddr::V6(v6) => {
+            // SAFETY: sockaddr_in6 is plain old data; all-zero is a valid starting value
+            let mut addr: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+            addr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            addr.sin6_addr = libc::in6_addr {
+                s6_addr: v6.octets(),
+            };
+            #[cfg(target_os = "macos")]
+            {
+                addr.sin6_len = std::mem::size_of::<libc::sockaddr_in6>() as u8;
+            }
+
+            // SAFETY: addr and host are valid for the lengths passed
+            unsafe {
+                libc::getnameinfo(
+                    &addr as *const libc::sockaddr_in6 as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    host.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+    };
+
+    if result != 0 {
+        return None;
+    }
+
+    // SAFETY: getnameinfo succeeded, so host holds a NUL-terminated string
+    let hostname = unsafe { CStr::from_ptr(host.as_ptr()) };
+    hostname.to_str().ok().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([192, 0, 2, last])
+    }
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(ip(1), Some("one.example".to_string()));
+        cache.insert(ip(2), None); // Negative results are cached too
+
+        // Touch 1 so 2 becomes the eviction candidate
+        assert_eq!(cache.get(&ip(1)), Some(Some("one.example".to_string())));
+        cache.insert(ip(3), Some("three.example".to_string()));
+
+        assert_eq!(cache.get(&ip(2)), None);
+        assert_eq!(cache.get(&ip(1)), Some(Some("one.example".to_string())));
+        assert_eq!(cache.get(&ip(3)), Some(Some("three.example".to_string())));
+        assert_eq!(cache.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_resolver_never_blocks_and_caches_negative_results() {
+        let resolver = ReverseDnsResolver::new(8);
+        if let Ok(mut cache) = resolver.cache.lock() {
+            cache.insert(ip(7), None);
+            cache.insert(ip(8), Some("eight.example".to_string()));
+        }
+
+        assert_eq!(resolver.lookup(ip(7)), None);
+        assert_eq!(resolver.lookup(ip(8)), Some("eight.example".to_string()));
+        // Loopback and unspecified addresses are never queued
+        assert_eq!(resolver.lookup(IpAddr::from([127, 0, 0, 1])), None);
+        assert!(resolver.pending.lock().unwrap().is_empty());
+    }
+}
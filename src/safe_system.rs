@@ -1,3 +1,4 @@
+use crate::neighbors::ArpEntry;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -31,6 +32,78 @@ pub struct SafeSystemStats {
     pub top_processes: Vec<SafeProcessInfo>,
     pub timestamp: SystemTime,
     pub errors: Vec<String>,
+    /// Kernel socket totals; None where the platform doesn't expose them
+    pub socket_stats: Option<SocketStats>,
+}
+
+/// Orphaned TCP sockets (closed by the application, still held by the kernel) above
+/// which the Alerts panel reports a likely connection leak
+pub const ORPHAN_TCP_WARNING: u32 = 100;
+
+/// Socket counts and memory from /proc/net/sockstat and sockstat6. On macOS only
+/// `tcp_sockets` is filled in, from the TCP control block count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketStats {
+    /// In-use TCP sockets, IPv4 and IPv6
+    pub tcp_sockets: u32,
+    /// Pages of memory held by TCP socket buffers (shared by IPv4 and IPv6)
+    pub tcp_memory_pages: u32,
+    /// In-use UDP sockets, IPv4 and IPv6
+    pub udp_sockets: u32,
+    pub udp_memory_pages: u32,
+    /// In-use raw sockets, IPv4 and IPv6
+    pub raw_sockets: u32,
+    pub orphan_tcp: u32,
+}
+
+impl SocketStats {
+    /// Parse the contents of /proc/net/sockstat and /proc/net/sockstat6, whose lines
+    /// look like `TCP: inuse 4 orphan 0 tw 0 alloc 4 mem 0`. sockstat6 only adds in-use
+    /// counts; memory and orphans are already totals in sockstat.
+    pub fn parse(sockstat: &str, sockstat6: &str) -> Self {
+        let mut stats = Self::default();
+        for line in sockstat.lines().chain(sockstat6.lines()) {
+            let Some((protocol, fields)) = line.split_once(':') else {
+                continue;
+            };
+            let field = |name: &str| -> u32 {
+                let mut words = fields.split_whitespace();
+                while let Some(word) = words.next() {
+                    if word == name {
+                        return words.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+                    }
+                }
+                0
+            };
+            match protocol {
+                "TCP" => {
+                    stats.tcp_sockets += field("inuse");
+                    stats.tcp_memory_pages = field("mem");
+                    stats.orphan_tcp = field("orphan");
+                }
+                "TCP6" => stats.tcp_sockets += field("inuse"),
+                "UDP" => {
+                    stats.udp_sockets += field("inuse");
+                    stats.udp_memory_pages = field("mem");
+                }
+                "UDP6" => stats.udp_sockets += field("inuse"),
+                "RAW" | "RAW6" => stats.raw_sockets += field("inuse"),
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    /// Bytes held by `pages` pages of socket memory
+    pub fn memory_bytes(pages: u32) -> u64 {
+        u64::from(pages) * page_size()
+    }
+}
+
+fn page_size() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(size).unwrap_or(4096)
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +128,7 @@ pub struct SafeProcessInfo {
     pub state: String,
 }
 
+#[derive(Clone)]
 pub struct SafeSystemMonitor {
     last_cpu_stats: Option<SafeCpuStats>,
     last_update: SystemTime,
@@ -181,6 +255,58 @@ impl SafeSystemMonitor {
             top_processes,
             timestamp: now,
             errors,
+            socket_stats: self.socket_stats(),
+        }
+    }
+
+    /// Socket counts and memory for the whole host, see [`SocketStats`]
+    pub fn socket_stats(&self) -> Option<SocketStats> {
+        #[cfg(target_os = "linux")]
+        {
+            let sockstat = std::fs::read_to_string("/proc/net/sockstat").ok()?;
+            // No IPv6 support in the kernel just means no IPv6 sockets
+            let sockstat6 = std::fs::read_to_string("/proc/net/sockstat6").unwrap_or_default();
+            Some(SocketStats::parse(&sockstat, &sockstat6))
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            // Rough equivalent: TCP control blocks, in use or not yet reclaimed
+            let count = Self::safe_command("sysctl", &["-n", "net.inet.tcp.pcbcount"])?;
+            Some(SocketStats {
+                tcp_sockets: count.trim().parse().ok()?,
+                ..SocketStats::default()
+            })
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        {
+            None
+        }
+    }
+
+    /// IPv4 neighbor (ARP) cache, see [`crate::neighbors`]
+    pub fn neighbors(&self) -> Option<Vec<ArpEntry>> {
+        #[cfg(target_os = "linux")]
+        {
+            let content = std::fs::read_to_string("/proc/net/arp").ok()?;
+            let mut entries = crate::neighbors::parse_proc_net_arp(&content);
+            // Without iproute2, states come from the flags alone
+            if let Some(ip_neigh) = Self::safe_command("ip", &["-4", "neigh", "show"]) {
+                crate::neighbors::apply_ip_neigh_states(&mut entries, &ip_neigh);
+            }
+            Some(entries)
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let output = Self::safe_command("arp", &["-an"])?;
+            Some(crate::neighbors::parse_arp_an(&output))
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        {
+            None
         }
     }
 
@@ -732,27 +858,6 @@ impl SafeSystemMonitor {
         Ok(processes)
     }
 
-    pub fn format_bytes(bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        let mut size = bytes as f64;
-        let mut unit_index = 0;
-
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
-        }
-
-        let unit = UNITS.get(unit_index).unwrap_or(&"B");
-
-        if size >= 100.0 {
-            format!("{size:.0} {unit}")
-        } else if size >= 10.0 {
-            format!("{size:.1} {unit}")
-        } else {
-            format!("{size:.2} {unit}")
-        }
-    }
-
     pub fn format_uptime(duration: Duration) -> String {
         let total_secs = duration.as_secs();
         let days = total_secs / 86400;
@@ -774,3 +879,45 @@ impl Default for SafeSystemMonitor {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_stats_sum_ipv4_and_ipv6() {
+        let sockstat = "sockets: used 1893
+TCP: inuse 112 orphan 140 tw 37 alloc 130 mem 58
+UDP: inuse 9 mem 4
+UDPLITE: inuse 0
+RAW: inuse 1
+FRAG: inuse 0 memory 0
+";
+        let sockstat6 = "TCP6: inuse 20
+UDP6: inuse 3
+UDPLITE6: inuse 0
+RAW6: inuse 2
+FRAG6: inuse 0 memory 0
+";
+        let stats = SocketStats::parse(sockstat, sockstat6);
+        assert_eq!(
+            stats,
+            SocketStats {
+                tcp_sockets: 132,
+                tcp_memory_pages: 58,
+                udp_sockets: 12,
+                udp_memory_pages: 4,
+                raw_sockets: 3,
+                orphan_tcp: 140,
+            }
+        );
+        assert!(stats.orphan_tcp > ORPHAN_TCP_WARNING);
+
+        // A kernel without IPv6 has no sockstat6; garbage lines are skipped
+        let stats = SocketStats::parse("TCP: inuse 4 orphan 0 tw 0 alloc 4 mem\nnonsense\n", "");
+        assert_eq!(stats.tcp_sockets, 4);
+        assert_eq!(stats.tcp_memory_pages, 0);
+        assert_eq!(stats.udp_sockets, 0);
+        assert_eq!(SocketStats::memory_bytes(0), 0);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::device::NetworkStats;
+use crate::stats::StatsCalculator;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Totals and peaks for one interface as they stood when the state was saved
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceSession {
+    pub total_bytes_in: u64,
+    pub total_bytes_out: u64,
+    pub total_packets_in: u64,
+    pub total_packets_out: u64,
+    pub max_speed_in: u64,
+    pub max_speed_out: u64,
+
+    // Raw interface counters at save time, used to detect counter resets on reload
+    pub raw_bytes_in: u64,
+    pub raw_bytes_out: u64,
+    pub raw_packets_in: u64,
+    pub raw_packets_out: u64,
+}
+
+/// Session state persisted between runs with `--continue-session`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Unix timestamp (seconds) of the last save
+    pub saved_at: i64,
+    pub interfaces: BTreeMap<String, InterfaceSession>,
+}
+
+impl SessionState {
+    /// Location of the state file: `<config dir>/netwatch/session.toml`
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("netwatch").join("session.toml"))
+    }
+
+    /// Load the saved state, if any. A missing file is not an error.
+    pub fn load() -> Result<Option<Self>> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(toml::from_str(&content)?))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Snapshot every calculator that has seen at least one sample
+    pub fn capture(calculators: &HashMap<String, StatsCalculator>) -> Self {
+        let interfaces = calculators
+            .iter()
+            .filter_map(|(name, calc)| calc.session_snapshot().map(|s| (name.clone(), s)))
+            .collect();
+
+        Self {
+            saved_at: chrono::Utc::now().timestamp(),
+            interfaces,
+        }
+    }
+
+    /// Carry saved totals and maximums into matching calculators
+    pub fn seed(&self, calculators: &mut HashMap<String, StatsCalculator>) {
+        for (name, saved) in &self.interfaces {
+            if let Some(calculator) = calculators.get_mut(name) {
+                calculator.seed_session(saved.clone());
+            }
+        }
+    }
+}
+
+/// Where the dashboard was left: written on exit and, unless `--no-restore-session`,
+/// applied before the next start's first draw. Missing fields keep their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DashboardSnapshot {
+    /// Unix timestamp (seconds) of the save
+    pub saved_at: i64,
+    /// Short name of the active panel, as for `--panel`; its tab position is worked out
+    /// again on restore, since `EnabledPanels` may have changed
+    pub panel: String,
+    /// Cursor in the Interfaces panel
+    pub selected_item: usize,
+    /// Selected row of the table-based panels
+    pub selected_row: Option<usize>,
+    /// First traceroute line shown in the Diagnostics panel
+    pub scroll: u16,
+    pub zoom_level: f64,
+    /// Traffic unit as for `TrafficFormat`, e.g. "k"
+    pub traffic_unit: String,
+    /// The `/` filter in effect on the panel
+    pub filter: String,
+    /// Each interface's counters as last read
+    pub interfaces: BTreeMap<String, NetworkStats>,
+}
+
+impl Default for DashboardSnapshot {
+    fn default() -> Self {
+        Self {
+            saved_at: 0,
+            panel: "overview".to_string(),
+            selected_item: 0,
+            selected_row: None,
+            scroll: 0,
+            zoom_level: 1.0,
+            traffic_unit: String::new(),
+            filter: String::new(),
+            interfaces: BTreeMap::new(),
+        }
+    }
+}
+
+impl DashboardSnapshot {
+    /// Location of the snapshot: `<data dir>/netwatch/session.json`
+    /// (`~/.local/share/netwatch/session.json` on Linux)
+    pub fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("netwatch").join("session.json"))
+    }
+
+    /// Load the saved snapshot, if any. A missing file is not an error.
+    pub fn load() -> Result<Option<Self>> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Per-interface rolling window file: `<data dir>/netwatch/stats/INTERFACE.json`
+/// (`~/.local/share/netwatch/stats` on Linux)
+pub fn history_path(interface: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join("netwatch")
+            .join("stats")
+            .join(format!("{interface}.json"))
+    })
+}
+
+/// Write every calculator's rolling window to its history file
+pub fn save_history(calculators: &HashMap<String, StatsCalculator>) -> Result<()> {
+    for (interface, calculator) in calculators {
+        if calculator.newest_sample_time().is_none() {
+            continue; // Nothing worth keeping
+        }
+
+        let path = history_path(interface)
+            .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, calculator.to_json()?)?;
+    }
+
+    Ok(())
+}
+
+/// Replace calculators with their saved windows when the saved data is recent enough.
+/// Each keeps its own window size: samples older than it are dropped, and files whose
+/// newest sample is already outside the window are ignored entirely. Unreadable files
+/// are skipped silently.
+pub fn restore_history(calculators: &mut HashMap<String, StatsCalculator>) {
+    let now = SystemTime::now();
+
+    for (interface, calculator) in calculators.iter_mut() {
+        let Some(path) = history_path(interface) else {
+            continue;
+        };
+        let Ok(json) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Some(restored) = restore_window(&json, calculator.window_size(), now) {
+            *calculator = restored;
+        }
+    }
+}
+
+/// A saved window trimmed to `average_window`, or None when it is unreadable or its
+/// newest sample is older than the window (its averages and graph would then describe
+/// traffic the trimmed history no longer holds)
+fn restore_window(
+    json: &str,
+    average_window: std::time::Duration,
+    now: SystemTime,
+) -> Option<StatsCalculator> {
+    let mut restored = StatsCalculator::from_json(json).ok()?;
+    let newest = restored.newest_sample_time()?;
+    if now.duration_since(newest).unwrap_or_default() >= average_window {
+        return None;
+    }
+
+    restored.rewindow(average_window, now);
+    Some(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn saved_window(newest: SystemTime) -> String {
+        let mut calculator = StatsCalculator::new(Duration::from_secs(300));
+        for (offset, bytes) in [(20, 0), (10, 1_000_000), (0, 2_000_000)] {
+            calculator.add_sample(NetworkStats {
+                timestamp: newest - Duration::from_secs(offset),
+                bytes_in: bytes,
+                bytes_out: bytes,
+                ..NetworkStats::default()
+            });
+        }
+        calculator.to_json().unwrap()
+    }
+
+    #[test]
+    fn test_restore_only_windows_younger_than_average_window() {
+        let now = SystemTime::now();
+        let window = Duration::from_secs(300);
+
+        let recent = restore_window(&saved_window(now - Duration::from_secs(60)), window, now)
+            .expect("a window saved a minute ago is restored");
+        assert_eq!(recent.sample_count(), 3);
+        assert_eq!(recent.window_size(), window);
+
+        // Between one and two windows old: every sample would be trimmed, leaving averages
+        // and graph data with no history behind them
+        let stale = saved_window(now - Duration::from_secs(450));
+        assert!(restore_window(&stale, window, now).is_none());
+        assert!(restore_window(&saved_window(now - window), window, now).is_none());
+
+        assert!(restore_window("not json", window, now).is_none());
+    }
+}
@@ -0,0 +1,102 @@
+//! Clean shutdown on signals and panics
+//!
+//! SIGINT, SIGTERM, SIGHUP and SIGQUIT only set a flag: the dashboard and the enhanced
+//! terminal loop check [`requested`] and return through their normal cleanup, so history
+//! and the session are saved and the terminal is restored. A second signal means the
+//! loop is stuck, so the handler restores the terminal itself and exits immediately.
+
+use crossterm::{cursor, event, execute, terminal};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+const SIGNALS: [libc::c_int; 4] = [libc::SIGINT, libc::SIGTERM, libc::SIGHUP, libc::SIGQUIT];
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Terminal settings from before raw mode, for the forced exit path
+static SAVED_TERMIOS: OnceLock<libc::termios> = OnceLock::new();
+
+/// True once a shutdown signal has been received
+pub fn requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Route shutdown signals to [`requested`]. Call before entering raw mode.
+pub fn install_signal_handlers() -> io::Result<()> {
+    let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: tcgetattr fully initializes `termios` when it returns 0
+    if unsafe { libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) } == 0 {
+        let _ = SAVED_TERMIOS.set(unsafe { termios.assume_init() });
+    }
+
+    for signal in SIGNALS {
+        // SAFETY: the handler only touches atomics and calls async-signal-safe libc functions
+        unsafe { signal_hook::low_level::register(signal, move || on_signal(signal))? };
+    }
+    Ok(())
+}
+
+fn on_signal(signal: libc::c_int) {
+    if SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst) {
+        force_restore_terminal();
+        // SAFETY: _exit is async-signal-safe and skips destructors that might block
+        unsafe { libc::_exit(128 + signal) };
+    }
+}
+
+/// Signal-safe restore: cooked mode from the saved termios, main screen, visible cursor
+fn force_restore_terminal() {
+    if let Some(termios) = SAVED_TERMIOS.get() {
+        // SAFETY: tcsetattr and write are async-signal-safe
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, termios) };
+    }
+    let sequence = b"\x1b[?1049l\x1b[?25h";
+    unsafe {
+        libc::write(
+            libc::STDOUT_FILENO,
+            sequence.as_ptr().cast(),
+            sequence.len(),
+        )
+    };
+}
+
+/// Leave raw mode and the alternate screen. Safe to call when neither is active.
+pub fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        event::DisableMouseCapture,
+        terminal::LeaveAlternateScreen,
+        cursor::Show
+    );
+}
+
+/// Restore the terminal before the panic message is printed, so it is readable
+/// and the shell is usable afterwards
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // With panic=abort any thread takes the process down; otherwise only a panic
+        // on the UI thread ends the session and a collector panic leaves the UI running
+        if cfg!(panic = "abort") || std::thread::current().name() == Some("main") {
+            restore_terminal();
+        }
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_signal_requests_shutdown() {
+        install_signal_handlers().unwrap();
+        assert!(!requested());
+
+        // SAFETY: raising a signal we have a handler for
+        unsafe { libc::raise(libc::SIGTERM) };
+        assert!(requested());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::dashboard::DashboardState;
 use crate::stats::StatsCalculator;
+use crate::units::format_rate;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
@@ -70,13 +71,7 @@ pub fn draw_simple_interface_summary(
             let (speed_in, speed_out) = calculator.current_speed();
             let combined_speed = speed_in + speed_out;
 
-            let speed_text = if combined_speed > 1024 * 1024 {
-                format!("{:.1}MB/s", combined_speed as f64 / 1024.0 / 1024.0)
-            } else if combined_speed > 1024 {
-                format!("{:.0}KB/s", combined_speed as f64 / 1024.0)
-            } else {
-                format!("{combined_speed}B/s")
-            };
+            let speed_text = format_rate(combined_speed, &state.traffic_unit);
 
             let errors = device.stats.errors_in + device.stats.errors_out;
             let error_text = if errors > 0 {
@@ -116,7 +111,7 @@ pub fn draw_simple_interface_summary(
                 }),
             ),
             Span::styled(
-                format!("{:<10}", status.1),
+                format!("{:<12} ", status.1),
                 Style::default().fg(Color::White),
             ),
             Span::styled(
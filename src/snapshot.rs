@@ -0,0 +1,372 @@
+//! One-shot snapshot for scripts (`--once`, `--json`)
+//!
+//! Each interface is sampled at the start and end of one refresh interval, so speeds are
+//! real rates rather than zeros. Nothing here touches the terminal: no raw mode and no
+//! alternate screen, so it works from cron, pipes and CI jobs.
+
+use crate::connections::{ConnectionMonitor, NetworkConnection};
+use crate::device::{NetworkReader, NetworkStats};
+use crate::processes::ProcessMonitor;
+use crate::stats::{StatsCalculator, DEFAULT_EMA_ALPHA};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Processes listed under "Top talkers"
+const TOP_TALKERS: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotReport {
+    /// RFC 3339 time the sample finished
+    pub timestamp: String,
+    pub interval_ms: u64,
+    pub interfaces: Vec<InterfaceSnapshot>,
+    pub connections: ConnectionSnapshot,
+    pub top_talkers: Vec<TalkerSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceSnapshot {
+    pub name: String,
+    pub rx_bytes_per_sec: u64,
+    pub tx_bytes_per_sec: u64,
+    pub rx_bytes_total: u64,
+    pub tx_bytes_total: u64,
+    /// Simple and exponential moving averages, as in the dashboard. A snapshot has only
+    /// two samples, so both equal the rate above.
+    pub avg_speed_in: u64,
+    pub avg_speed_out: u64,
+    pub ema_speed_in: u64,
+    pub ema_speed_out: u64,
+    /// Errors and drops counted during the sample interval
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_drops: u64,
+    pub tx_drops: u64,
+}
+
+/// Connection counts over the sample interval. There is no connection age here:
+/// [`NetworkConnection::age`] counts from when netwatch first saw a connection
+/// ESTABLISHED, so in a one-shot run every age would be at most `interval_ms` and a
+/// long-lived count always zero. Age is in the dashboard's Connections and Alerts panels.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectionSnapshot {
+    pub total: u32,
+    pub established: u32,
+    pub listening: u32,
+    pub time_wait: u32,
+    pub close_wait: u32,
+    pub tcp: u32,
+    pub udp: u32,
+    /// Mean RTT over connections that report one
+    pub avg_rtt_ms: Option<f64>,
+    pub retransmits: u64,
+    /// TCP connections per congestion control algorithm, where `ss -i` reports one
+    pub congestion_control: BTreeMap<String, u32>,
+    /// Connections opened and closed per second during the sample interval
+    pub new_per_sec: f64,
+    pub closed_per_sec: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TalkerSnapshot {
+    pub pid: u32,
+    pub name: String,
+    pub cmdline: Option<String>,
+    pub rx_bytes_per_sec: u64,
+    pub tx_bytes_per_sec: u64,
+}
+
+/// Sample `interfaces` twice, `interval` apart, and collect connections and processes
+pub fn take(
+    interfaces: &[String],
+    reader: &dyn NetworkReader,
+    interval: Duration,
+) -> crate::error::Result<SnapshotReport> {
+    let mut before = Vec::with_capacity(interfaces.len());
+    for interface in interfaces {
+        before.push(reader.read_stats(interface)?);
+    }
+    // Process traffic and connection churn are measured over the same interval as the
+    // interfaces
+    let mut conn_monitor = ConnectionMonitor::new();
+    if let Err(e) = conn_monitor.update() {
+        eprintln!("Warning: Failed to read connections: {e}");
+    }
+    let mut proc_monitor = ProcessMonitor::new();
+    if let Err(e) = proc_monitor.update() {
+        eprintln!("Warning: Failed to read processes: {e}");
+    }
+
+    std::thread::sleep(interval);
+
+    let mut interface_snapshots = Vec::with_capacity(interfaces.len());
+    for (interface, first) in interfaces.iter().zip(before) {
+        let second = reader.read_stats(interface)?;
+        interface_snapshots.push(interface_snapshot(interface, first, second));
+    }
+
+    if let Err(e) = proc_monitor.update() {
+        eprintln!("Warning: Failed to read processes: {e}");
+    }
+
+    if let Err(e) = conn_monitor.update() {
+        eprintln!("Warning: Failed to read connections: {e}");
+    }
+
+    let connections = conn_monitor.get_connections();
+    let stats = conn_monitor.get_connection_stats();
+    let (avg_rtt_ms, retransmits) = rtt_and_retransmits(connections);
+
+    let talkers = if proc_monitor.measures_traffic() {
+        proc_monitor
+            .get_top_network_processes(TOP_TALKERS)
+            .into_iter()
+            .filter(|process| process.total_bytes() > 0)
+            .map(|process| (process.clone(), process.bytes_received, process.bytes_sent))
+            .collect()
+    } else {
+        proc_monitor.bandwidth_by_process(connections)
+    };
+    let top_talkers = talkers
+        .into_iter()
+        .take(TOP_TALKERS)
+        .map(|(process, rx, tx)| TalkerSnapshot {
+            pid: process.pid,
+            name: process.name,
+            cmdline: process.cmdline,
+            rx_bytes_per_sec: rx,
+            tx_bytes_per_sec: tx,
+        })
+        .collect();
+
+    Ok(SnapshotReport {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        interval_ms: u64::try_from(interval.as_millis()).unwrap_or(u64::MAX),
+        interfaces: interface_snapshots,
+        connections: ConnectionSnapshot {
+            total: stats.total,
+            established: stats.established,
+            listening: stats.listening,
+            time_wait: stats.time_wait,
+            close_wait: stats.close_wait,
+            tcp: stats.tcp,
+            udp: stats.udp,
+            avg_rtt_ms,
+            retransmits,
+            congestion_control: congestion_control_counts(connections),
+            new_per_sec: stats.new_per_sec,
+            closed_per_sec: stats.closed_per_sec,
+        },
+        top_talkers,
+    })
+}
+
+fn interface_snapshot(name: &str, first: NetworkStats, second: NetworkStats) -> InterfaceSnapshot {
+    // Counters that went backwards (interface reset) count as zero new events
+    let rx_errors = second.errors_in.saturating_sub(first.errors_in);
+    let tx_errors = second.errors_out.saturating_sub(first.errors_out);
+    let rx_drops = second.drops_in.saturating_sub(first.drops_in);
+    let tx_drops = second.drops_out.saturating_sub(first.drops_out);
+
+    let mut calculator = StatsCalculator::new(Duration::from_secs(300));
+    calculator.add_sample(first);
+    calculator.add_sample(second);
+    let (rx_bytes_per_sec, tx_bytes_per_sec) = calculator.current_speed();
+    let (rx_bytes_total, tx_bytes_total) = calculator.total_bytes();
+    let (avg_speed_in, avg_speed_out) = calculator.average_speed();
+    let (ema_speed_in, ema_speed_out) = calculator.ema_speed(DEFAULT_EMA_ALPHA);
+
+    InterfaceSnapshot {
+        name: name.to_string(),
+        rx_bytes_per_sec,
+        tx_bytes_per_sec,
+        rx_bytes_total,
+        tx_bytes_total,
+        avg_speed_in,
+        avg_speed_out,
+        ema_speed_in,
+        ema_speed_out,
+        rx_errors,
+        tx_errors,
+        rx_drops,
+        tx_drops,
+    }
+}
+
+/// Mean RTT (None when no connection reports one) and total retransmits
+pub fn rtt_and_retransmits(connections: &[NetworkConnection]) -> (Option<f64>, u64) {
+    let rtts: Vec<f64> = connections
+        .iter()
+        .filter_map(|conn| conn.socket_info.rtt)
+        .collect();
+    let avg_rtt = (!rtts.is_empty()).then(|| rtts.iter().sum::<f64>() / rtts.len() as f64);
+    let retransmits = connections
+        .iter()
+        .map(|conn| u64::from(conn.socket_info.retrans))
+        .sum();
+    (avg_rtt, retransmits)
+}
+
+/// Connections per congestion control algorithm; those without one are left out
+pub fn congestion_control_counts(connections: &[NetworkConnection]) -> BTreeMap<String, u32> {
+    let mut counts = BTreeMap::new();
+    for algo in connections
+        .iter()
+        .filter_map(|conn| conn.socket_info.cc_algo.as_ref())
+    {
+        *counts.entry(algo.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+impl SnapshotReport {
+    /// Plain text, one section per heading, no colors or emoji
+    pub fn render_text(&self) -> String {
+        let rate = |bytes_per_sec: u64| format!("{}/s", crate::units::format_bytes(bytes_per_sec));
+        let mut out = format!(
+            "netwatch snapshot {} ({}ms sample)\n\nInterfaces\n",
+            self.timestamp, self.interval_ms
+        );
+
+        out.push_str(&format!(
+            "  {:<16} {:>12} {:>12} {:>10} {:>10} {:>8} {:>8}\n",
+            "Interface", "In", "Out", "Total In", "Total Out", "Errors", "Drops"
+        ));
+        for interface in &self.interfaces {
+            out.push_str(&format!(
+                "  {:<16} {:>12} {:>12} {:>10} {:>10} {:>8} {:>8}\n",
+                interface.name,
+                rate(interface.rx_bytes_per_sec),
+                rate(interface.tx_bytes_per_sec),
+                crate::units::format_bytes(interface.rx_bytes_total),
+                crate::units::format_bytes(interface.tx_bytes_total),
+                interface.rx_errors + interface.tx_errors,
+                interface.rx_drops + interface.tx_drops
+            ));
+        }
+
+        let conns = &self.connections;
+        out.push_str(&format!(
+            "\nConnections\n  {} total, {} established, {} listening, {} time_wait, {} close_wait ({} tcp, {} udp)\n",
+            conns.total,
+            conns.established,
+            conns.listening,
+            conns.time_wait,
+            conns.close_wait,
+            conns.tcp,
+            conns.udp
+        ));
+        let rtt = conns
+            .avg_rtt_ms
+            .map_or_else(|| "n/a".to_string(), |rtt| format!("{rtt:.1}ms"));
+        out.push_str(&format!(
+            "  avg RTT {rtt}, {} retransmits\n",
+            conns.retransmits
+        ));
+        out.push_str(&format!(
+            "  {:.1} new/s, {:.1} closed/s\n",
+            conns.new_per_sec, conns.closed_per_sec
+        ));
+        if !conns.congestion_control.is_empty() {
+            let algos: Vec<String> = conns
+                .congestion_control
+                .iter()
+                .map(|(algo, count)| format!("{algo} {count}"))
+                .collect();
+            out.push_str(&format!("  congestion control: {}\n", algos.join(", ")));
+        }
+
+        out.push_str("\nTop talkers\n");
+        if self.top_talkers.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for talker in &self.top_talkers {
+            out.push_str(&format!(
+                "  {:<8} {:<20} in {:>12}  out {:>12}\n",
+                talker.pid,
+                talker.name,
+                rate(talker.rx_bytes_per_sec),
+                rate(talker.tx_bytes_per_sec)
+            ));
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connections::{ConnectionState, Protocol, SocketInfo};
+    use std::time::UNIX_EPOCH;
+
+    fn sample(secs: u64, bytes_in: u64, errors_in: u64) -> NetworkStats {
+        NetworkStats {
+            timestamp: UNIX_EPOCH + Duration::from_secs(secs),
+            bytes_in,
+            errors_in,
+            ..NetworkStats::new()
+        }
+    }
+
+    #[test]
+    fn test_interface_snapshot_rates_and_interval_errors() {
+        let snapshot = interface_snapshot("eth0", sample(100, 1_000, 5), sample(102, 5_000, 7));
+
+        assert_eq!(snapshot.rx_bytes_per_sec, 2_000);
+        assert_eq!(snapshot.rx_bytes_total, 5_000);
+        assert_eq!(snapshot.rx_errors, 2);
+        assert_eq!(snapshot.tx_errors, 0);
+
+        let report = SnapshotReport {
+            timestamp: "2024-01-01T00:00:00+00:00".to_string(),
+            interval_ms: 2000,
+            interfaces: vec![snapshot],
+            connections: ConnectionSnapshot::default(),
+            top_talkers: Vec::new(),
+        };
+        let text = report.render_text();
+        assert!(text.contains("eth0"));
+        assert!(text.contains("1.95 KiB/s"));
+        assert!(text.contains("avg RTT n/a"));
+
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["interfaces"][0]["rx_bytes_per_sec"], 2_000);
+        assert_eq!(json["interfaces"][0]["avg_speed_in"], 2_000);
+        assert_eq!(json["interfaces"][0]["ema_speed_in"], 2_000);
+        assert!(json["connections"]["avg_rtt_ms"].is_null());
+        assert!(!text.contains("congestion control"));
+    }
+
+    #[test]
+    fn test_congestion_control_counts() {
+        let with_algo = |algo: Option<&str>| NetworkConnection {
+            local_addr: "10.0.0.1:40000".parse().unwrap(),
+            remote_addr: "10.0.0.2:443".parse().unwrap(),
+            state: ConnectionState::Established,
+            protocol: Protocol::Tcp,
+            pid: None,
+            process_name: None,
+            process_cmdline: None,
+            bytes_sent: 0,
+            bytes_received: 0,
+            socket_info: SocketInfo {
+                cc_algo: algo.map(str::to_string),
+                ..SocketInfo::default()
+            },
+            established_at: None,
+            sent_per_sec: None,
+            received_per_sec: None,
+        };
+        let counts = congestion_control_counts(&[
+            with_algo(Some("cubic")),
+            with_algo(Some("bbr")),
+            with_algo(Some("cubic")),
+            with_algo(None),
+        ]);
+        assert_eq!(counts.get("cubic"), Some(&2));
+        assert_eq!(counts.get("bbr"), Some(&1));
+        assert_eq!(counts.len(), 2);
+    }
+}
@@ -1,9 +1,19 @@
-use crate::device::NetworkStats;
-use std::collections::VecDeque;
-use std::time::Duration;
-#[cfg(test)]
-use std::time::SystemTime;
+use crate::device::{Device, NetworkStats, TOTAL_DEVICE};
+use crate::session::InterfaceSession;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime};
+
+/// Errors and drops per second between the last two samples
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ErrorRate {
+    pub errors_in: f64,
+    pub errors_out: f64,
+    pub drops_in: f64,
+    pub drops_out: f64,
+}
 
+#[derive(Serialize, Deserialize)]
 pub struct StatsCalculator {
     // Data storage
     history: VecDeque<NetworkStats>,
@@ -19,6 +29,21 @@ pub struct StatsCalculator {
     max_speed_in: u64,
     max_speed_out: u64,
 
+    // Raw byte deltas between the last two samples and the time between them
+    #[serde(default)]
+    last_delta: (u64, u64),
+    #[serde(default)]
+    sample_interval: Duration,
+
+    // Exponential moving average of the per-sample speeds, weighting the newest by
+    // ema_alpha; restarted from the first rate after a reset
+    #[serde(default)]
+    ema_in: f64,
+    #[serde(default)]
+    ema_out: f64,
+    #[serde(default = "default_ema_alpha")]
+    ema_alpha: f64,
+
     // Graph data for display
     graph_data_in: VecDeque<(f64, f64)>, // (time, value) pairs
     graph_data_out: VecDeque<(f64, f64)>,
@@ -29,6 +54,14 @@ pub struct StatsCalculator {
     total_packets_in: u64,
     total_packets_out: u64,
 
+    // Added to raw counters so totals continue across previous sessions, counter
+    // wraps and interface resets (bytes in, bytes out, packets in, packets out)
+    carried_totals: [u64; 4],
+    pending_session: Option<InterfaceSession>,
+
+    // Spike hysteresis: set when a spike is flagged, cleared once traffic settles
+    spike_active: bool,
+
     // First sample flag for initialization
     first_sample: bool,
 }
@@ -46,38 +79,119 @@ impl StatsCalculator {
             min_speed_out: 0,
             max_speed_in: 0,
             max_speed_out: 0,
+            last_delta: (0, 0),
+            sample_interval: Duration::ZERO,
+            ema_in: 0.0,
+            ema_out: 0.0,
+            ema_alpha: DEFAULT_EMA_ALPHA,
             graph_data_in: VecDeque::new(),
             graph_data_out: VecDeque::new(),
             total_bytes_in: 0,
             total_bytes_out: 0,
             total_packets_in: 0,
             total_packets_out: 0,
+            carried_totals: [0; 4],
+            pending_session: None,
+            spike_active: false,
             first_sample: true,
         }
     }
 
     pub fn add_sample(&mut self, stats: NetworkStats) {
+        // Resolve a seeded session against the first raw counters we see
+        if let Some(saved) = self.pending_session.take() {
+            self.carried_totals = [
+                carried_offset(saved.total_bytes_in, saved.raw_bytes_in, stats.bytes_in),
+                carried_offset(saved.total_bytes_out, saved.raw_bytes_out, stats.bytes_out),
+                carried_offset(
+                    saved.total_packets_in,
+                    saved.raw_packets_in,
+                    stats.packets_in,
+                ),
+                carried_offset(
+                    saved.total_packets_out,
+                    saved.raw_packets_out,
+                    stats.packets_out,
+                ),
+            ];
+        }
+
+        // Compare raw counters with the previous sample to spot wraps and resets
+        let mut counter_reset = false;
+        if let Some(previous) = self.history.back() {
+            let counters = [
+                (stats.bytes_in, previous.bytes_in),
+                (stats.bytes_out, previous.bytes_out),
+                (stats.packets_in, previous.packets_in),
+                (stats.packets_out, previous.packets_out),
+            ]
+            .map(|(current, previous)| (current, previous, self.calculate_diff(current, previous)));
+
+            for (index, (carried, (current, previous, diff))) in
+                self.carried_totals.iter_mut().zip(counters).enumerate()
+            {
+                match diff {
+                    // Non-zero only when the counter wrapped, keeping totals monotonic
+                    Some(diff) => {
+                        *carried = carried.saturating_add(previous.saturating_add(diff) - current)
+                    }
+                    None => {
+                        *carried = carried.saturating_add(previous);
+                        // Rates come from the byte counters, so only they force a rebaseline
+                        counter_reset |= index < 2;
+                    }
+                }
+            }
+        }
+
+        if counter_reset {
+            // Interface bounced: drop the stale baseline rather than computing a bogus delta
+            self.history.clear();
+            self.current_speed_in = 0;
+            self.current_speed_out = 0;
+            self.last_delta = (0, 0);
+            self.sample_interval = Duration::ZERO;
+        }
+
         // Update totals
-        self.total_bytes_in = stats.bytes_in;
-        self.total_bytes_out = stats.bytes_out;
-        self.total_packets_in = stats.packets_in;
-        self.total_packets_out = stats.packets_out;
+        self.total_bytes_in = stats.bytes_in.saturating_add(self.carried_totals[0]);
+        self.total_bytes_out = stats.bytes_out.saturating_add(self.carried_totals[1]);
+        self.total_packets_in = stats.packets_in.saturating_add(self.carried_totals[2]);
+        self.total_packets_out = stats.packets_out.saturating_add(self.carried_totals[3]);
 
         // Calculate current speed if we have previous data
         if let Some(previous) = self.history.back() {
-            let time_diff = stats
+            let interval = stats
                 .timestamp
                 .duration_since(previous.timestamp)
-                .unwrap_or_default()
-                .as_secs_f64();
+                .unwrap_or_default();
+            let time_diff = interval.as_secs_f64();
 
             if time_diff > 0.0 {
-                // Handle counter overflow (32-bit counters can wrap)
-                let bytes_in_diff = self.calculate_diff(stats.bytes_in, previous.bytes_in);
-                let bytes_out_diff = self.calculate_diff(stats.bytes_out, previous.bytes_out);
+                // Resets were handled above, so only plain deltas and wraps remain here
+                let bytes_in_diff = self
+                    .calculate_diff(stats.bytes_in, previous.bytes_in)
+                    .unwrap_or(0);
+                let bytes_out_diff = self
+                    .calculate_diff(stats.bytes_out, previous.bytes_out)
+                    .unwrap_or(0);
 
-                self.current_speed_in = (bytes_in_diff as f64 / time_diff) as u64;
-                self.current_speed_out = (bytes_out_diff as f64 / time_diff) as u64;
+                // No interval yet means this is the first rate since a (counter) reset
+                let first_rate = self.sample_interval.is_zero();
+                self.last_delta = (bytes_in_diff, bytes_out_diff);
+                self.sample_interval = interval;
+                let speed_in = bytes_in_diff as f64 / time_diff;
+                let speed_out = bytes_out_diff as f64 / time_diff;
+                self.current_speed_in = speed_in as u64;
+                self.current_speed_out = speed_out as u64;
+                (self.ema_in, self.ema_out) = if first_rate {
+                    (speed_in, speed_out)
+                } else {
+                    (
+                        ema_step(self.ema_in, speed_in, self.ema_alpha),
+                        ema_step(self.ema_out, speed_out, self.ema_alpha),
+                    )
+                };
 
                 // Update min/max (skip first few samples for stability)
                 if !self.first_sample {
@@ -85,7 +199,7 @@ impl StatsCalculator {
                 }
 
                 // Add to graph data
-                self.add_graph_data(&stats);
+                self.add_graph_data(time_diff);
             }
         }
 
@@ -98,21 +212,25 @@ impl StatsCalculator {
         }
     }
 
-    fn calculate_diff(&self, current: u64, previous: u64) -> u64 {
+    /// Delta between two raw counter readings, or None when the counter was reset.
+    /// A decrease is treated as a wrap only when the previous value sat close to the
+    /// 32-bit or 64-bit wrap point; anything else is an interface reset.
+    fn calculate_diff(&self, current: u64, previous: u64) -> Option<u64> {
         if current >= previous {
-            current - previous
+            return Some(current - previous);
+        }
+
+        let u32_wrap = u32::MAX as u64 + 1;
+        if previous < u32_wrap {
+            // 32-bit counter: wrapped delta must be small relative to the counter range
+            let diff = u32_wrap - previous + current;
+            (diff <= MAX_WRAP_DELTA_32).then_some(diff)
         } else {
-            // Counter wrapped, assume 32-bit or 64-bit counter
-            // Try 32-bit first, then 64-bit
-            let diff_32 = (u32::MAX as u64) - previous + current + 1;
-            let diff_64 = (u64::MAX) - previous + current + 1;
-
-            // Choose the smaller, more reasonable difference
-            if diff_32 < diff_64 / 1000 {
-                diff_32
-            } else {
-                diff_64
-            }
+            // 64-bit counter: only a wrap if we were right at the top of the range
+            let diff = (u64::MAX - previous)
+                .saturating_add(current)
+                .saturating_add(1);
+            (diff <= MAX_WRAP_DELTA_64).then_some(diff)
         }
     }
 
@@ -131,13 +249,13 @@ impl StatsCalculator {
         }
     }
 
-    fn add_graph_data(&mut self, _stats: &NetworkStats) {
-        // First, shift all existing points forward in time (age them)
+    fn add_graph_data(&mut self, elapsed: f64) {
+        // First, shift all existing points back by the real time since the last sample
         for (time, _) in self.graph_data_in.iter_mut() {
-            *time += 0.5; // Assuming ~500ms refresh rate
+            *time += elapsed;
         }
         for (time, _) in self.graph_data_out.iter_mut() {
-            *time += 0.5; // Assuming ~500ms refresh rate
+            *time += elapsed;
         }
 
         // Remove data older than 60 seconds
@@ -151,10 +269,10 @@ impl StatsCalculator {
             .push_back((0.0, self.current_speed_out as f64));
 
         // Limit to reasonable number of points
-        while self.graph_data_in.len() > 120 {
+        while self.graph_data_in.len() > MAX_GRAPH_POINTS {
             self.graph_data_in.pop_front();
         }
-        while self.graph_data_out.len() > 120 {
+        while self.graph_data_out.len() > MAX_GRAPH_POINTS {
             self.graph_data_out.pop_front();
         }
     }
@@ -187,8 +305,24 @@ impl StatsCalculator {
             .as_secs_f64();
 
         if time_span > 0.0 {
-            let bytes_in_diff = self.calculate_diff(last.bytes_in, first.bytes_in);
-            let bytes_out_diff = self.calculate_diff(last.bytes_out, first.bytes_out);
+            // Sum sample-to-sample deltas: a 32-bit counter can wrap several times within
+            // the window, which a single first-to-last difference cannot see
+            let (bytes_in_diff, bytes_out_diff) =
+                self.history.iter().zip(self.history.iter().skip(1)).fold(
+                    (0u64, 0u64),
+                    |(total_in, total_out), (previous, current)| {
+                        (
+                            total_in.saturating_add(
+                                self.calculate_diff(current.bytes_in, previous.bytes_in)
+                                    .unwrap_or(0),
+                            ),
+                            total_out.saturating_add(
+                                self.calculate_diff(current.bytes_out, previous.bytes_out)
+                                    .unwrap_or(0),
+                            ),
+                        )
+                    },
+                );
 
             self.avg_speed_in = (bytes_in_diff as f64 / time_span) as u64;
             self.avg_speed_out = (bytes_out_diff as f64 / time_span) as u64;
@@ -200,10 +334,73 @@ impl StatsCalculator {
         (self.current_speed_in, self.current_speed_out)
     }
 
+    /// Bytes in/out between the previous sample and the latest one, with counter wraps
+    /// already accounted for. Divide by [`Self::sample_interval`] for a rate of your own;
+    /// both are zero until two samples have been seen and again after a counter reset.
+    pub fn last_delta(&self) -> (u64, u64) {
+        self.last_delta
+    }
+
+    /// Time between the two samples behind [`Self::last_delta`]
+    pub fn sample_interval(&self) -> Duration {
+        self.sample_interval
+    }
+
+    /// Simple mean speed over the averaging window
     pub fn average_speed(&self) -> (u64, u64) {
         (self.avg_speed_in, self.avg_speed_out)
     }
 
+    /// Exponential moving average of the speed; `alpha` in (0, 1] is the weight of the
+    /// newest sample, so higher follows changes faster. The EMA for the alpha set with
+    /// [`Self::set_ema_alpha`] runs over every sample since the last reset; any other
+    /// alpha is worked out from the samples still in the window.
+    pub fn ema_speed(&self, alpha: f64) -> (u64, u64) {
+        let (ema_in, ema_out) = if alpha == self.ema_alpha {
+            (self.ema_in, self.ema_out)
+        } else {
+            self.window_ema(alpha)
+        };
+        (ema_in as u64, ema_out as u64)
+    }
+
+    /// Weight of the newest sample in the running EMA (the `EmaAlpha` setting). The
+    /// EMA restarts from the samples in the window.
+    pub fn set_ema_alpha(&mut self, alpha: f64) {
+        if alpha != self.ema_alpha {
+            self.ema_alpha = alpha;
+            (self.ema_in, self.ema_out) = self.window_ema(alpha);
+        }
+    }
+
+    /// EMA over the sample-to-sample speeds in the window, seeded with the oldest
+    fn window_ema(&self, alpha: f64) -> (f64, f64) {
+        let mut ema: Option<(f64, f64)> = None;
+        for (previous, current) in self.history.iter().zip(self.history.iter().skip(1)) {
+            let elapsed = current
+                .timestamp
+                .duration_since(previous.timestamp)
+                .unwrap_or_default()
+                .as_secs_f64();
+            if elapsed <= 0.0 {
+                continue;
+            }
+            let speed = |current, previous| {
+                self.calculate_diff(current, previous).unwrap_or(0) as f64 / elapsed
+            };
+            let speed_in = speed(current.bytes_in, previous.bytes_in);
+            let speed_out = speed(current.bytes_out, previous.bytes_out);
+            ema = Some(match ema {
+                None => (speed_in, speed_out),
+                Some((ema_in, ema_out)) => (
+                    ema_step(ema_in, speed_in, alpha),
+                    ema_step(ema_out, speed_out, alpha),
+                ),
+            });
+        }
+        ema.unwrap_or_default()
+    }
+
     pub fn min_speed(&self) -> (u64, u64) {
         (self.min_speed_in, self.min_speed_out)
     }
@@ -220,18 +417,150 @@ impl StatsCalculator {
         (self.total_packets_in, self.total_packets_out)
     }
 
-    pub fn graph_data_in(&self) -> &VecDeque<(f64, f64)> {
-        &self.graph_data_in
+    /// The previous and latest samples with the seconds between them, once there are two
+    fn last_two_samples(&self) -> Option<(&NetworkStats, &NetworkStats, f64)> {
+        let latest = self.history.back()?;
+        let previous = self.history.iter().rev().nth(1)?;
+        let seconds = latest
+            .timestamp
+            .duration_since(previous.timestamp)
+            .ok()?
+            .as_secs_f64();
+        (seconds > 0.0).then_some((previous, latest, seconds))
+    }
+
+    /// Packets per second in/out between the last two samples, zero until there are two
+    pub fn current_pps(&self) -> (f64, f64) {
+        let Some((previous, latest, seconds)) = self.last_two_samples() else {
+            return (0.0, 0.0);
+        };
+        let rate = |current, previous| {
+            self.calculate_diff(current, previous).unwrap_or(0) as f64 / seconds
+        };
+        (
+            rate(latest.packets_in, previous.packets_in),
+            rate(latest.packets_out, previous.packets_out),
+        )
+    }
+
+    /// Errors and drops per second between the last two samples. A counter that went
+    /// backwards was reset and counts as no new errors.
+    pub fn current_error_rate(&self) -> ErrorRate {
+        let Some((previous, latest, seconds)) = self.last_two_samples() else {
+            return ErrorRate::default();
+        };
+        let rate = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / seconds;
+        ErrorRate {
+            errors_in: rate(latest.errors_in, previous.errors_in),
+            errors_out: rate(latest.errors_out, previous.errors_out),
+            drops_in: rate(latest.drops_in, previous.drops_in),
+            drops_out: rate(latest.drops_out, previous.drops_out),
+        }
+    }
+
+    /// Graph points in `start..end`, oldest first; see [`graph_window`] for picking the range
+    pub fn graph_data_in(&self, window: (usize, usize)) -> Vec<(f64, f64)> {
+        graph_slice(&self.graph_data_in, window)
     }
 
-    pub fn graph_data_out(&self) -> &VecDeque<(f64, f64)> {
-        &self.graph_data_out
+    pub fn graph_data_out(&self, window: (usize, usize)) -> Vec<(f64, f64)> {
+        graph_slice(&self.graph_data_out, window)
+    }
+
+    /// Number of graph points currently held (both directions get one per sample)
+    pub fn graph_len(&self) -> usize {
+        self.graph_data_in.len()
     }
 
     pub fn sample_count(&self) -> usize {
         self.history.len()
     }
 
+    /// Enough samples in the window for averages to be meaningful
+    pub fn is_warmed_up(&self) -> bool {
+        self.history.len() >= SPIKE_WARMUP_SAMPLES
+    }
+
+    /// True when the current speed in either direction exceeds `multiplier` times its average
+    pub fn is_spike(&self, multiplier: f64) -> bool {
+        self.is_warmed_up()
+            && (exceeds(self.current_speed_in, self.avg_speed_in, multiplier)
+                || exceeds(self.current_speed_out, self.avg_speed_out, multiplier))
+    }
+
+    /// Advance the spike state after a sample. Returns true only when a new spike is
+    /// flagged; it isn't flagged again until speed falls below 80% of the threshold.
+    pub fn update_spike_state(&mut self, multiplier: f64) -> bool {
+        if self.spike_active {
+            if !self.is_spike(multiplier * 0.8) {
+                self.spike_active = false;
+            }
+            false
+        } else if self.is_spike(multiplier) {
+            self.spike_active = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn spike_active(&self) -> bool {
+        self.spike_active
+    }
+
+    /// Current totals, peaks and raw counters for persisting; None before the first sample
+    pub fn session_snapshot(&self) -> Option<InterfaceSession> {
+        let latest = self.history.back()?;
+        Some(InterfaceSession {
+            total_bytes_in: self.total_bytes_in,
+            total_bytes_out: self.total_bytes_out,
+            total_packets_in: self.total_packets_in,
+            total_packets_out: self.total_packets_out,
+            max_speed_in: self.max_speed_in,
+            max_speed_out: self.max_speed_out,
+            raw_bytes_in: latest.bytes_in,
+            raw_bytes_out: latest.bytes_out,
+            raw_packets_in: latest.packets_in,
+            raw_packets_out: latest.packets_out,
+        })
+    }
+
+    /// Continue totals and maximums from a saved session. Totals are reconciled
+    /// with the interface counters on the next sample.
+    pub fn seed_session(&mut self, saved: InterfaceSession) {
+        self.max_speed_in = self.max_speed_in.max(saved.max_speed_in);
+        self.max_speed_out = self.max_speed_out.max(saved.max_speed_out);
+        self.pending_session = Some(saved);
+    }
+
+    /// Serialize the rolling window and derived values for restoring on the next start
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Timestamp of the most recent sample in the window
+    pub fn newest_sample_time(&self) -> Option<SystemTime> {
+        self.history.back().map(|stats| stats.timestamp)
+    }
+
+    pub fn window_size(&self) -> Duration {
+        self.window_size
+    }
+
+    /// Adopt a new window size and drop samples that fall outside it as of `now`
+    pub fn rewindow(&mut self, window_size: Duration, now: SystemTime) {
+        self.window_size = window_size;
+        let cutoff = now
+            .checked_sub(window_size)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        self.history.retain(|stats| stats.timestamp >= cutoff);
+        self.calculate_averages();
+    }
+
     pub fn reset(&mut self) {
         self.history.clear();
         self.graph_data_in.clear();
@@ -244,14 +573,192 @@ impl StatsCalculator {
         self.min_speed_out = 0;
         self.max_speed_in = 0;
         self.max_speed_out = 0;
+        self.last_delta = (0, 0);
+        self.sample_interval = Duration::ZERO;
+        self.ema_in = 0.0;
+        self.ema_out = 0.0;
+        self.spike_active = false;
         self.first_sample = true;
     }
 }
 
+/// Default `EmaAlpha`: each new sample carries 20% of the weight
+pub const DEFAULT_EMA_ALPHA: f64 = 0.2;
+
+fn default_ema_alpha() -> f64 {
+    DEFAULT_EMA_ALPHA
+}
+
+fn ema_step(previous: f64, sample: f64, alpha: f64) -> f64 {
+    alpha * sample + (1.0 - alpha) * previous
+}
+
+// Largest delta accepted as a counter wrap: half the 32-bit range, and 2^40 bytes
+// (~1 TB, far beyond one refresh interval) for 64-bit counters
+const MAX_WRAP_DELTA_32: u64 = 1 << 31;
+const MAX_WRAP_DELTA_64: u64 = 1 << 40;
+
+// Enough graph points for 60 seconds at the fastest (100ms) refresh interval
+const MAX_GRAPH_POINTS: usize = 600;
+
+/// Points shown when a graph of `len` points is zoomed to `zoom_level` (1.0 = everything)
+pub fn visible_graph_points(len: usize, zoom_level: f64) -> usize {
+    let zoom = if zoom_level.is_finite() && zoom_level > 1.0 {
+        zoom_level
+    } else {
+        1.0
+    };
+    // Keep at least two points so there is still a line to draw
+    ((len as f64 / zoom).ceil() as usize).clamp(len.min(2), len)
+}
+
+/// Furthest a zoomed graph can be panned back from the newest point
+pub fn max_graph_offset(len: usize, zoom_level: f64) -> usize {
+    len - visible_graph_points(len, zoom_level)
+}
+
+/// Index range `(start, end)` of the visible graph points when zoomed to `zoom_level`
+/// and panned `offset` points back from the newest one. Offsets past the oldest data clamp.
+pub fn graph_window(len: usize, zoom_level: f64, offset: usize) -> (usize, usize) {
+    let visible = visible_graph_points(len, zoom_level);
+    let end = len - offset.min(len - visible);
+    (end - visible, end)
+}
+
+fn graph_slice(points: &VecDeque<(f64, f64)>, (start, end): (usize, usize)) -> Vec<(f64, f64)> {
+    let end = end.min(points.len());
+    points.range(start.min(end)..end).copied().collect()
+}
+
+// Samples required in the window before spike detection fires
+const SPIKE_WARMUP_SAMPLES: usize = 10;
+
+fn exceeds(current: u64, average: u64, multiplier: f64) -> bool {
+    average > 0 && current as f64 > average as f64 * multiplier
+}
+
+// Amount to add to a raw counter so totals continue from a saved session.
+// A counter below its saved value means the interface was reset (reboot, driver
+// reload), so the saved total carries over whole instead of a huge wrapped delta.
+fn carried_offset(saved_total: u64, saved_raw: u64, raw: u64) -> u64 {
+    if raw >= saved_raw {
+        saved_total.saturating_sub(saved_raw)
+    } else {
+        saved_total
+    }
+}
+
+/// The synthetic "all interfaces" device: a calculator fed with the sum of the
+/// counters of every interface read that tick, so whole-host throughput graphs like
+/// any interface's. Each interface adds what its counters grew by since it last
+/// counted, so one joining, leaving or resetting doesn't move the sum.
+pub struct TotalDevice {
+    /// Carries the summed counters in `stats`, for anything that lists devices
+    pub device: Device,
+    calculator: StatsCalculator,
+    // Raw counters of each interface when it last counted towards the sum
+    previous: HashMap<String, NetworkStats>,
+    // A tick was skipped for interfaces that failed to read, to see if they come back
+    waited: bool,
+}
+
+impl TotalDevice {
+    pub fn new(window_size: Duration) -> Self {
+        Self {
+            device: Device::new(TOTAL_DEVICE.to_string()),
+            calculator: StatsCalculator::new(window_size),
+            previous: HashMap::new(),
+            waited: false,
+        }
+    }
+
+    pub fn calculator(&self) -> &StatsCalculator {
+        &self.calculator
+    }
+
+    pub fn calculator_mut(&mut self) -> &mut StatsCalculator {
+        &mut self.calculator
+    }
+
+    /// Add one tick: the counters of each interface whose read succeeded. When one that
+    /// counted before is missing the tick is skipped, once, so a transient read failure
+    /// doesn't show as a traffic drop; the next tick covers both intervals. Still
+    /// missing then, it stops counting until it is back.
+    pub fn add_samples(&mut self, samples: &[(String, NetworkStats)]) {
+        let missing: Vec<String> = self
+            .previous
+            .keys()
+            .filter(|name| !samples.iter().any(|(sampled, _)| sampled == *name))
+            .cloned()
+            .collect();
+        if !missing.is_empty() && !self.waited {
+            self.waited = true;
+            return;
+        }
+        self.waited = false;
+        for name in missing {
+            self.previous.remove(&name);
+        }
+        if samples.is_empty() {
+            self.device.is_active = false;
+            return;
+        }
+
+        let mut sum = self.device.stats.clone();
+        for (name, stats) in samples {
+            if let Some(previous) = self.previous.get(name) {
+                // A reset counter adds nothing this tick and counts again from the next
+                let grew = |current: u64, previous: u64| {
+                    self.calculator
+                        .calculate_diff(current, previous)
+                        .unwrap_or(0)
+                };
+                sum.bytes_in += grew(stats.bytes_in, previous.bytes_in);
+                sum.bytes_out += grew(stats.bytes_out, previous.bytes_out);
+                sum.packets_in += grew(stats.packets_in, previous.packets_in);
+                sum.packets_out += grew(stats.packets_out, previous.packets_out);
+                sum.errors_in += grew(stats.errors_in, previous.errors_in);
+                sum.errors_out += grew(stats.errors_out, previous.errors_out);
+                sum.drops_in += grew(stats.drops_in, previous.drops_in);
+                sum.drops_out += grew(stats.drops_out, previous.drops_out);
+            }
+            self.previous.insert(name.clone(), stats.clone());
+        }
+        if let Some(newest) = samples.iter().map(|(_, stats)| stats.timestamp).max() {
+            sum.timestamp = newest;
+        }
+
+        self.device.stats = sum.clone();
+        self.device.is_active = true;
+        self.calculator.add_sample(sum);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_packet_and_error_rates() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        calc.add_sample(sample(0, 0, 100));
+        assert_eq!(calc.current_pps(), (0.0, 0.0));
+
+        let mut later = sample(100_000, 50_000, 102);
+        later.errors_in = 6;
+        later.drops_out = 3;
+        calc.add_sample(later);
+        assert_eq!(calc.current_pps(), (500.0, 250.0));
+        let rate = calc.current_error_rate();
+        assert_eq!((rate.errors_in, rate.errors_out), (3.0, 0.0));
+        assert_eq!((rate.drops_in, rate.drops_out), (0.0, 1.5));
+
+        // The driver cleared its error counters: no new errors rather than a huge rate
+        calc.add_sample(sample(200_000, 100_000, 103));
+        assert_eq!(calc.current_error_rate(), ErrorRate::default());
+        assert_eq!(calc.current_pps(), (1000.0, 500.0));
+    }
+
     #[test]
     fn test_stats_calculation() {
         let mut calc = StatsCalculator::new(Duration::from_secs(60));
@@ -300,6 +807,287 @@ mod tests {
 
         // Test 32-bit counter overflow
         let diff = calc.calculate_diff(100, u32::MAX as u64 - 50);
-        assert_eq!(diff, 151); // (u32::MAX - (u32::MAX - 50)) + 100 + 1
+        assert_eq!(diff, Some(151)); // (u32::MAX - (u32::MAX - 50)) + 100 + 1
+    }
+
+    fn sample(bytes_in: u64, bytes_out: u64, secs: u64) -> NetworkStats {
+        NetworkStats {
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            bytes_in,
+            bytes_out,
+            packets_in: bytes_in / 100,
+            packets_out: bytes_out / 100,
+            errors_in: 0,
+            errors_out: 0,
+            drops_in: 0,
+            drops_out: 0,
+        }
+    }
+
+    #[test]
+    fn test_last_delta_matches_known_samples() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        calc.add_sample(sample(10_000, 4_000, 100));
+        assert_eq!(calc.last_delta(), (0, 0));
+        assert_eq!(calc.sample_interval(), Duration::ZERO);
+
+        let mut later = sample(13_000, 5_500, 102);
+        later.timestamp += Duration::from_millis(500);
+        calc.add_sample(later);
+        assert_eq!(calc.last_delta(), (3_000, 1_500));
+        assert_eq!(calc.sample_interval(), Duration::from_millis(2_500));
+        assert_eq!(calc.current_speed(), (1_200, 600));
+
+        // A 32-bit wrap still yields the true delta
+        calc.add_sample(sample(u32::MAX as u64 - 99, 5_600, 103));
+        calc.add_sample(sample(100, 5_700, 104));
+        assert_eq!(calc.last_delta(), (200, 100));
+        assert_eq!(calc.sample_interval(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_ema_follows_step_change_faster_than_average() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        for secs in 0..=10 {
+            calc.add_sample(sample(secs * 1_000, 0, secs));
+        }
+        assert_eq!(calc.average_speed().0, 1_000);
+        assert_eq!(calc.ema_speed(DEFAULT_EMA_ALPHA).0, 1_000);
+
+        // Traffic jumps from 1000 to 11000 B/s
+        let mut bytes = 10_000;
+        for secs in 11..=13 {
+            bytes += 11_000;
+            calc.add_sample(sample(bytes, 0, secs));
+        }
+        let (sma, _) = calc.average_speed();
+        let (ema, _) = calc.ema_speed(DEFAULT_EMA_ALPHA);
+        assert_eq!(sma, 3_307); // 43000 bytes over 13s
+        assert_eq!(ema, 5_880); // 11000 - 10000 * 0.8^3
+        assert!(ema > sma);
+
+        // A higher alpha reacts faster still, computed from the window
+        let (fast, _) = calc.ema_speed(0.5);
+        assert_eq!(fast, 9_750); // 11000 - 10000 * 0.5^3
+        calc.set_ema_alpha(0.5);
+        assert_eq!(calc.ema_speed(0.5).0, 9_750);
+
+        calc.reset();
+        assert_eq!(calc.ema_speed(0.5), (0, 0));
+    }
+
+    #[test]
+    fn test_session_seed_continues_totals() {
+        let mut first = StatsCalculator::new(Duration::from_secs(60));
+        first.add_sample(sample(10_000, 5_000, 100));
+        first.add_sample(sample(20_000, 6_000, 101));
+        first.add_sample(sample(50_000, 8_000, 102));
+        let saved = first.session_snapshot().unwrap();
+        assert_eq!(saved.max_speed_in, 30_000);
+
+        // Same boot: counters kept growing, so totals simply follow them
+        let mut resumed = StatsCalculator::new(Duration::from_secs(60));
+        resumed.seed_session(saved.clone());
+        resumed.add_sample(sample(60_000, 9_000, 200));
+        assert_eq!(resumed.total_bytes(), (60_000, 9_000));
+        assert_eq!(resumed.max_speed(), (30_000, 2_000));
+
+        // After a reboot the counters restart near zero; no negative delta is applied
+        let mut rebooted = StatsCalculator::new(Duration::from_secs(60));
+        rebooted.seed_session(saved);
+        rebooted.add_sample(sample(1_000, 500, 300));
+        assert_eq!(rebooted.total_bytes(), (51_000, 8_500));
+        assert_eq!(rebooted.total_packets(), (510, 85));
+    }
+
+    #[test]
+    fn test_spike_detection_with_warmup_and_hysteresis() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(300));
+        let mut bytes = 0;
+        let mut secs = 0;
+        let mut step = |calc: &mut StatsCalculator, rate: u64| {
+            bytes += rate;
+            secs += 1;
+            calc.add_sample(sample(bytes, 0, secs));
+            calc.update_spike_state(5.0)
+        };
+
+        // A burst during warm-up is ignored
+        for _ in 0..3 {
+            assert!(!step(&mut calc, 1_000));
+        }
+        assert!(!calc.is_warmed_up());
+        assert!(!step(&mut calc, 100_000));
+
+        for _ in 0..20 {
+            step(&mut calc, 1_000);
+        }
+        assert!(calc.is_warmed_up());
+        assert!(!calc.spike_active());
+
+        // Sustained spike flags once, then stays quiet while elevated
+        assert!(step(&mut calc, 200_000));
+        assert!(calc.spike_active());
+        assert!(!step(&mut calc, 200_000));
+
+        // Back to baseline clears it so the next spike flags again
+        assert!(!step(&mut calc, 1_000));
+        assert!(!calc.spike_active());
+        assert!(step(&mut calc, 500_000));
+    }
+
+    #[test]
+    fn test_counter_wrap_and_reset_in_add_sample() {
+        // 32-bit wrap: delta computed across the wrap point, totals keep climbing
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        calc.add_sample(sample(u32::MAX as u64 - 999, 0, 1));
+        calc.add_sample(sample(1_000, 0, 2));
+        assert_eq!(calc.current_speed().0, 2_000);
+        assert_eq!(calc.total_bytes().0, u32::MAX as u64 + 1_001);
+
+        // Reset: counter drops far from the wrap point, sample is discarded as a delta
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        calc.add_sample(sample(1_000_000_000, 0, 1));
+        calc.add_sample(sample(1_000_100_000, 0, 2));
+        calc.add_sample(sample(5_000, 0, 3));
+        assert_eq!(calc.current_speed().0, 0);
+        assert_eq!(calc.max_speed().0, 100_000);
+        assert_eq!(calc.total_bytes().0, 1_000_105_000);
+        assert_eq!(calc.sample_count(), 1);
+
+        // Rates resume from the new baseline
+        calc.add_sample(sample(15_000, 0, 4));
+        assert_eq!(calc.current_speed().0, 10_000);
+
+        // 64-bit wrap
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        calc.add_sample(sample(u64::MAX - 499, 0, 1));
+        calc.add_sample(sample(500, 0, 2));
+        assert_eq!(calc.current_speed().0, 1_000);
+        assert_eq!(calc.calculate_diff(10, u64::MAX / 2), None);
+    }
+
+    #[test]
+    fn test_average_spans_counter_wraps_and_resets() {
+        // 32-bit counter wrapping every few samples at 1 GB/s: each step is a clean wrap,
+        // but first-to-last across the window is not
+        let step = 1_000_000_000;
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        for secs in 0..10 {
+            calc.add_sample(sample((secs * step) % (u32::MAX as u64 + 1), 0, secs + 1));
+        }
+        assert_eq!(calc.current_speed().0, step);
+        assert_eq!(calc.average_speed().0, step);
+
+        // After a reset the average restarts from the new baseline instead of going to 0
+        // or spiking
+        calc.add_sample(sample(1_000, 0, 11));
+        assert_eq!(calc.average_speed().0, step);
+        calc.add_sample(sample(5_000, 0, 12));
+        calc.add_sample(sample(9_000, 0, 13));
+        assert_eq!(calc.current_speed().0, 4_000);
+        assert_eq!(calc.average_speed().0, 4_000);
+    }
+
+    #[test]
+    fn test_json_round_trip_and_rewindow() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        for secs in 1..=5 {
+            calc.add_sample(sample(secs * 1_000, secs * 500, secs));
+        }
+
+        let mut restored = StatsCalculator::from_json(&calc.to_json().unwrap()).unwrap();
+        assert_eq!(restored.sample_count(), 5);
+        assert_eq!(restored.total_bytes(), calc.total_bytes());
+        assert_eq!(restored.max_speed(), calc.max_speed());
+        assert_eq!(
+            restored.newest_sample_time(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(5))
+        );
+
+        // Only samples inside the new window survive
+        restored.rewindow(
+            Duration::from_secs(2),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(5),
+        );
+        assert_eq!(restored.sample_count(), 3);
+
+        // Restored history keeps producing rates from where it left off
+        restored.add_sample(sample(7_000, 3_500, 7));
+        assert_eq!(restored.current_speed(), (1_000, 500));
+    }
+
+    #[test]
+    fn test_graph_points_age_by_actual_interval() {
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        for tick in 0..=700u64 {
+            let mut stats = sample(tick * 1_000, tick * 100, 0);
+            stats.timestamp = SystemTime::UNIX_EPOCH + Duration::from_millis(tick * 100);
+            calc.add_sample(stats);
+        }
+
+        // 100ms samples: the full 60 second span fits, oldest point is one minute back
+        let points = calc.graph_data_in((0, calc.graph_len()));
+        assert_eq!(points.len(), MAX_GRAPH_POINTS);
+        assert_eq!(points.last().unwrap().0, 0.0);
+        assert!((points.first().unwrap().0 - 59.9).abs() < 1e-6);
+        assert_eq!(points.last().unwrap().1, 10_000.0);
+    }
+
+    #[test]
+    fn test_graph_window_zooms_and_pans() {
+        // 1.0 shows everything, whatever the offset
+        assert_eq!(graph_window(60, 1.0, 0), (0, 60));
+        assert_eq!(graph_window(60, 1.0, 25), (0, 60));
+
+        // 2.0 shows the newest half, panning back until it meets the oldest point
+        assert_eq!(graph_window(60, 2.0, 0), (30, 60));
+        assert_eq!(graph_window(60, 2.0, 10), (20, 50));
+        assert_eq!(max_graph_offset(60, 2.0), 30);
+        assert_eq!(graph_window(60, 2.0, 500), (0, 30));
+
+        // Zooming never goes below a drawable line, and empty graphs stay empty
+        assert_eq!(visible_graph_points(5, 16.0), 2);
+        assert_eq!(graph_window(0, 4.0, 3), (0, 0));
+
+        let mut calc = StatsCalculator::new(Duration::from_secs(60));
+        for tick in 0..=4u64 {
+            calc.add_sample(sample(tick * 1_000, 0, tick));
+        }
+        let window = graph_window(calc.graph_len(), 2.0, 0);
+        let speeds: Vec<f64> = calc.graph_data_in(window).iter().map(|p| p.1).collect();
+        assert_eq!(speeds, [1_000.0, 1_000.0]);
+    }
+
+    #[test]
+    fn test_total_device_sums_and_rides_out_read_failures() {
+        let tick = |eth0: Option<u64>, wlan0: Option<u64>, secs: u64| {
+            [("eth0", eth0), ("wlan0", wlan0)]
+                .into_iter()
+                .filter_map(|(name, bytes)| Some((name.to_string(), sample(bytes?, 0, secs))))
+                .collect::<Vec<_>>()
+        };
+        let mut total = TotalDevice::new(Duration::from_secs(60));
+        total.add_samples(&tick(Some(50_000), Some(7_000), 0));
+        total.add_samples(&tick(Some(51_000), Some(7_500), 1));
+        assert_eq!(total.calculator().current_speed(), (1_500, 0));
+
+        // wlan0 fails one read: the tick waits, and the next one spans both intervals
+        total.add_samples(&tick(Some(52_000), None, 2));
+        assert_eq!(total.calculator().sample_count(), 2);
+        total.add_samples(&tick(Some(53_000), Some(8_500), 3));
+        assert_eq!(total.calculator().current_speed(), (1_500, 0));
+        assert_eq!(total.calculator().total_bytes().0, 4_500);
+
+        // Still gone after the wait: eth0 carries on alone, and wlan0 rejoins without
+        // its counters since it left, or a reset, counting as traffic
+        total.add_samples(&tick(Some(54_000), None, 4));
+        total.add_samples(&tick(Some(55_000), None, 5));
+        assert_eq!(total.calculator().current_speed(), (1_000, 0));
+        total.add_samples(&tick(Some(56_000), Some(100), 6));
+        assert_eq!(total.calculator().current_speed(), (1_000, 0));
+        total.add_samples(&tick(Some(57_000), Some(600), 7));
+        assert_eq!(total.calculator().current_speed(), (1_500, 0));
+        assert_eq!(total.device.name, TOTAL_DEVICE);
     }
 }
@@ -725,28 +725,6 @@ impl SystemMonitor {
         Ok(processes)
     }
 
-    pub fn format_bytes(bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        let mut size = bytes as f64;
-        let mut unit_index = 0;
-
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
-        }
-
-        // Safe array access with bounds checking
-        let unit = UNITS.get(unit_index).unwrap_or(&"B");
-
-        if size >= 100.0 {
-            format!("{size:.0} {unit}")
-        } else if size >= 10.0 {
-            format!("{size:.1} {unit}")
-        } else {
-            format!("{size:.2} {unit}")
-        }
-    }
-
     pub fn format_uptime(duration: Duration) -> String {
         let total_secs = duration.as_secs();
         let days = total_secs / 86400;
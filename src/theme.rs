@@ -0,0 +1,229 @@
+//! Dashboard color themes (`--theme NAME`)
+//!
+//! A theme names colors by role rather than by hue, so the dashboard asks for
+//! `critical_color` instead of red. Themes are TOML files in
+//! `~/.config/netwatch/themes/NAME.toml`; keys left out keep the default palette.
+//! Built in are `default` (the original colors, also called `dark`), `light` for light
+//! terminal backgrounds, and `solarized`. The config file's `[Colors]` table takes the
+//! same keys and is layered over whichever theme is selected.
+
+use crate::config::Config;
+use crate::error::{NetwatchError, Result};
+use ratatui::style::Color;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Themes that need no file
+pub const BUILT_IN: [&str; 4] = ["default", "dark", "light", "solarized"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Incoming traffic graph
+    pub speed_in_color: Color,
+    /// Outgoing traffic graph
+    pub speed_out_color: Color,
+    /// Failures, critical alerts, unhealthy values
+    pub critical_color: Color,
+    /// Degraded values and warnings
+    pub warning_color: Color,
+    /// Healthy values
+    pub ok_color: Color,
+    /// Table headers and panel headings
+    pub header_color: Color,
+    /// Labels and section titles
+    pub accent_color: Color,
+    /// Plain values
+    pub text_color: Color,
+    /// Secondary text and placeholders
+    pub muted_color: Color,
+    /// Informational values: listening sockets, services, low severity
+    pub info_color: Color,
+    /// Latency figures and high severity
+    pub highlight_color: Color,
+    /// Background of the selected table row
+    pub selected_row_bg: Color,
+}
+
+impl Default for Theme {
+    /// The palette the dashboard has always used
+    fn default() -> Self {
+        Self {
+            speed_in_color: Color::Green,
+            speed_out_color: Color::Red,
+            critical_color: Color::Red,
+            warning_color: Color::Yellow,
+            ok_color: Color::Green,
+            header_color: Color::Yellow,
+            accent_color: Color::Cyan,
+            text_color: Color::White,
+            muted_color: Color::Gray,
+            info_color: Color::Blue,
+            highlight_color: Color::Magenta,
+            selected_row_bg: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Dark text and deeper accents for terminals with a light background
+    pub fn light() -> Self {
+        Self {
+            speed_in_color: Color::Rgb(0x1a, 0x7f, 0x37),
+            speed_out_color: Color::Rgb(0xcf, 0x22, 0x2e),
+            critical_color: Color::Rgb(0xcf, 0x22, 0x2e),
+            warning_color: Color::Rgb(0x9a, 0x67, 0x00),
+            ok_color: Color::Rgb(0x1a, 0x7f, 0x37),
+            header_color: Color::Rgb(0x09, 0x69, 0xda),
+            accent_color: Color::Rgb(0x1b, 0x7c, 0x83),
+            text_color: Color::Black,
+            muted_color: Color::Rgb(0x57, 0x60, 0x6a),
+            info_color: Color::Rgb(0x09, 0x69, 0xda),
+            highlight_color: Color::Rgb(0x82, 0x50, 0xdf),
+            selected_row_bg: Color::Rgb(0xd0, 0xd7, 0xde),
+        }
+    }
+
+    /// Ethan Schoonover's Solarized accent colors, for dark terminals
+    pub fn solarized() -> Self {
+        Self {
+            speed_in_color: Color::Rgb(0x85, 0x99, 0x00),  // green
+            speed_out_color: Color::Rgb(0xcb, 0x4b, 0x16), // orange
+            critical_color: Color::Rgb(0xdc, 0x32, 0x2f),  // red
+            warning_color: Color::Rgb(0xb5, 0x89, 0x00),   // yellow
+            ok_color: Color::Rgb(0x85, 0x99, 0x00),        // green
+            header_color: Color::Rgb(0x26, 0x8b, 0xd2),    // blue
+            accent_color: Color::Rgb(0x2a, 0xa1, 0x98),    // cyan
+            text_color: Color::Rgb(0x93, 0xa1, 0xa1),      // base1
+            muted_color: Color::Rgb(0x58, 0x6e, 0x75),     // base01
+            info_color: Color::Rgb(0x6c, 0x71, 0xc4),      // violet
+            highlight_color: Color::Rgb(0xd3, 0x36, 0x82), // magenta
+            selected_row_bg: Color::Rgb(0x07, 0x36, 0x42), // base02
+        }
+    }
+
+    /// Load `NAME.toml` from the themes directory, or a built-in theme of that name
+    pub fn load(name: &str) -> Result<Self> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(NetwatchError::Config(format!(
+                "Invalid theme name {name:?} (letters, digits, - and _ only)"
+            )));
+        }
+
+        // A file shadows the built-in theme of the same name
+        if let Some(path) = themes_dir().map(|dir| dir.join(format!("{name}.toml"))) {
+            if path.exists() {
+                let content = std::fs::read_to_string(&path)?;
+                return Self::parse(&content).map_err(|e| {
+                    NetwatchError::Config(format!("Invalid theme {}: {e}", path.display()))
+                });
+            }
+        }
+
+        match name {
+            "default" | "dark" => Ok(Self::default()),
+            "light" => Ok(Self::light()),
+            "solarized" => Ok(Self::solarized()),
+            _ => Err(NetwatchError::Config(format!(
+                "Theme {name:?} not found (built-in: {}, or add {name}.toml to {})",
+                BUILT_IN.join(", "),
+                themes_dir().map_or_else(
+                    || "~/.config/netwatch/themes".to_string(),
+                    |dir| dir.display().to_string()
+                )
+            ))),
+        }
+    }
+
+    /// The theme `config` selects (`Theme`, `--theme`) with its `[Colors]` applied
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let theme = match config.theme {
+            Some(ref name) => Self::load(name)?,
+            None => Self::default(),
+        };
+        theme
+            .with_colors(&config.colors)
+            .map_err(|e| NetwatchError::Config(format!("[Colors]: {e}")))
+    }
+
+    /// Theme file contents; keys left out keep the default palette
+    pub fn parse(content: &str) -> std::result::Result<Self, String> {
+        let colors: BTreeMap<String, String> =
+            toml::from_str(content).map_err(|e| e.message().to_string())?;
+        Self::default().with_colors(&colors)
+    }
+
+    /// This theme with some roles recolored, e.g. `header_color = "blue"`. Color values
+    /// are names ("green", "lightblue"), "#rrggbb" or a 0-255 palette index.
+    pub fn with_colors(
+        mut self,
+        colors: &BTreeMap<String, String>,
+    ) -> std::result::Result<Self, String> {
+        for (role, value) in colors {
+            let slot = self
+                .role_mut(role)
+                .ok_or_else(|| format!("unknown color role `{role}`"))?;
+            *slot = Color::from_str(value)
+                .map_err(|_| format!("unknown color {value:?} for `{role}`"))?;
+        }
+        Ok(self)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "speed_in_color" => &mut self.speed_in_color,
+            "speed_out_color" => &mut self.speed_out_color,
+            "critical_color" => &mut self.critical_color,
+            "warning_color" => &mut self.warning_color,
+            "ok_color" => &mut self.ok_color,
+            "header_color" => &mut self.header_color,
+            "accent_color" => &mut self.accent_color,
+            "text_color" => &mut self.text_color,
+            "muted_color" => &mut self.muted_color,
+            "info_color" => &mut self.info_color,
+            "highlight_color" => &mut self.highlight_color,
+            "selected_row_bg" => &mut self.selected_row_bg,
+            _ => return None,
+        })
+    }
+}
+
+/// `~/.config/netwatch/themes` (or the platform's config directory)
+pub fn themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("netwatch").join("themes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_file_overrides_defaults() {
+        let theme =
+            Theme::parse("critical_color = \"#ff0000\"\nselected_row_bg = \"blue\"\n").unwrap();
+        assert_eq!(theme.critical_color, Color::Rgb(0xff, 0, 0));
+        assert_eq!(theme.selected_row_bg, Color::Blue);
+        assert_eq!(theme.ok_color, Theme::default().ok_color);
+
+        assert!(Theme::parse("ok_color = \"not-a-color\"").is_err());
+        assert!(Theme::parse("ok_colour = \"green\"").is_err());
+
+        assert_eq!(Theme::load("solarized").unwrap(), Theme::solarized());
+        assert_eq!(Theme::load("dark").unwrap(), Theme::default());
+        assert!(Theme::load("../../etc/passwd").is_err());
+
+        // [Colors] in the config recolors the selected theme
+        let config = Config {
+            theme: Some("light".to_string()),
+            colors: BTreeMap::from([("header_color".to_string(), "magenta".to_string())]),
+            ..Config::default()
+        };
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.header_color, Color::Magenta);
+        assert_eq!(theme.text_color, Theme::light().text_color);
+    }
+}
@@ -0,0 +1,472 @@
+use crate::active_diagnostics::TracerouteHop;
+use anyhow::{anyhow, Result};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+pub const MAX_HOPS: u8 = 30;
+pub const PROBES_PER_HOP: usize = 3;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMP_DEST_UNREACHABLE: u8 = 3;
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_TIME_EXCEEDED: u8 = 11;
+const ECHO_PAYLOAD_LEN: usize = 24;
+
+// Bit number of CAP_NET_RAW in the capability sets of /proc/PID/status
+#[cfg(target_os = "linux")]
+const CAP_NET_RAW: u32 = 13;
+
+/// Trace the route to `target`, calling `on_hop` with the hops found so far after each
+/// TTL. Uses raw ICMP Echo probes when the process may open raw sockets (IPv4 only),
+/// otherwise falls back to the system `traceroute` command.
+pub fn trace(target: &str, mut on_hop: impl FnMut(&[TracerouteHop])) -> Result<Vec<TracerouteHop>> {
+    let addr = resolve(target)?;
+
+    if let IpAddr::V4(v4) = addr {
+        if has_cap_net_raw() {
+            // Raw socket can still be refused (e.g. seccomp); the subprocess may cope
+            if let Ok(hops) = icmp_trace(v4, &mut on_hop) {
+                return Ok(hops);
+            }
+        }
+    }
+
+    let hops = subprocess_trace(&addr.to_string())?;
+    on_hop(&hops);
+    Ok(hops)
+}
+
+pub(crate) fn resolve(target: &str) -> Result<IpAddr> {
+    let addrs: Vec<IpAddr> = (target, 0)
+        .to_socket_addrs()
+        .map_err(|e| anyhow!("Could not resolve {target}: {e}"))?
+        .map(|addr| addr.ip())
+        .collect();
+
+    // Prefer IPv4, which the raw ICMP path supports
+    addrs
+        .iter()
+        .find(|ip| ip.is_ipv4())
+        .or_else(|| addrs.first())
+        .copied()
+        .ok_or_else(|| anyhow!("No addresses found for {target}"))
+}
+
+/// Whether raw ICMP sockets are permitted: CAP_NET_RAW on Linux, root elsewhere
+pub fn has_cap_net_raw() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| {
+                status
+                    .lines()
+                    .find_map(|line| line.strip_prefix("CapEff:"))
+                    .and_then(|caps| u64::from_str_radix(caps.trim(), 16).ok())
+            })
+            .is_some_and(|caps| caps & (1 << CAP_NET_RAW) != 0)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        unsafe { libc::geteuid() == 0 }
+    }
+}
+
+fn icmp_trace(
+    dest: Ipv4Addr,
+    on_hop: &mut impl FnMut(&[TracerouteHop]),
+) -> io::Result<Vec<TracerouteHop>> {
+    let socket = RawIcmpSocket::open()?;
+    let ident = (std::process::id() & 0xffff) as u16;
+    let mut hops = Vec::new();
+
+    for ttl in 1..=MAX_HOPS {
+        socket.set_ttl(ttl)?;
+
+        let mut rtts = Vec::with_capacity(PROBES_PER_HOP);
+        let mut hop_addr = None;
+        let mut finished = false;
+
+        for probe in 0..PROBES_PER_HOP {
+            let seq = u16::from(ttl) * PROBES_PER_HOP as u16 + probe as u16;
+            let sent = Instant::now();
+            socket.send_echo(dest, ident, seq)?;
+
+            if let Some((from, is_final)) = socket.wait_reply(ident, seq, sent + PROBE_TIMEOUT)? {
+                rtts.push(sent.elapsed().as_secs_f64() * 1000.0);
+                hop_addr = Some(from);
+                finished |= is_final;
+            }
+        }
+
+        if let Some(addr) = hop_addr {
+            hops.push(hop_from_probes(
+                ttl,
+                IpAddr::V4(addr),
+                &rtts,
+                PROBES_PER_HOP,
+            ));
+            on_hop(&hops);
+        }
+        if finished {
+            break;
+        }
+    }
+
+    Ok(hops)
+}
+
+fn hop_from_probes(ttl: u8, addr: IpAddr, rtts: &[f64], probes: usize) -> TracerouteHop {
+    let avg_rtt_ms = if rtts.is_empty() {
+        0.0
+    } else {
+        rtts.iter().sum::<f64>() / rtts.len() as f64
+    };
+    let lost = probes.saturating_sub(rtts.len());
+
+    TracerouteHop {
+        ttl,
+        addr,
+        hostname: None,
+        avg_rtt_ms,
+        packet_loss: lost as f32 / probes.max(1) as f32 * 100.0,
+    }
+}
+
+/// Raw IPv4 ICMP socket, closed on drop
+struct RawIcmpSocket {
+    fd: libc::c_int,
+}
+
+impl RawIcmpSocket {
+    fn open() -> io::Result<Self> {
+        // SAFETY: plain socket(2) call; the result is checked before use
+        let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_RAW, libc::IPPROTO_ICMP) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { fd })
+    }
+
+    fn set_ttl(&self, ttl: u8) -> io::Result<()> {
+        let ttl = libc::c_int::from(ttl);
+        // SAFETY: ttl outlives the call and the length matches its type
+        let result = unsafe {
+            libc::setsockopt(
+                self.fd,
+                libc::IPPROTO_IP,
+                libc::IP_TTL,
+                &ttl as *const libc::c_int as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn send_echo(&self, dest: Ipv4Addr, ident: u16, seq: u16) -> io::Result<()> {
+        let packet = echo_request(ident, seq);
+
+        // SAFETY: sockaddr_in is plain old data; all-zero is a valid starting value
+        let mut addr: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+        addr.sin_family = libc::AF_INET as libc::sa_family_t;
+        addr.sin_addr = libc::in_addr {
+            s_addr: u32::from_ne_bytes(dest.octets()),
+        };
+        #[cfg(target_os = "macos")]
+        {
+            addr.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+        }
+
+        // SAFETY: packet and addr are valid for the lengths passed
+        let sent = unsafe {
+            libc::sendto(
+                self.fd,
+                packet.as_ptr() as *const libc::c_void,
+                packet.len(),
+                0,
+                &addr as *const libc::sockaddr_in as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Wait until `deadline` for the reply to probe (ident, seq). Returns the responding
+    /// router and whether the trace is finished (echo reply or destination unreachable).
+    fn wait_reply(
+        &self,
+        ident: u16,
+        seq: u16,
+        deadline: Instant,
+    ) -> io::Result<Option<(Ipv4Addr, bool)>> {
+        let mut buffer = [0u8; 512];
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            let mut poll_fd = libc::pollfd {
+                fd: self.fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: poll_fd is a single valid pollfd
+            let ready =
+                unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as libc::c_int) };
+            if ready < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            if ready == 0 {
+                return Ok(None);
+            }
+
+            // SAFETY: sockaddr_in is plain old data; all-zero is a valid starting value
+            let mut from: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            let mut from_len = std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
+            // SAFETY: buffer and from are valid for the lengths passed
+            let received = unsafe {
+                libc::recvfrom(
+                    self.fd,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    0,
+                    &mut from as *mut libc::sockaddr_in as *mut libc::sockaddr,
+                    &mut from_len,
+                )
+            };
+            if received < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // Raw sockets see every ICMP packet for the host; skip ones for other probes
+            if let Some(is_final) = parse_reply(&buffer[..received as usize], ident, seq) {
+                let addr = Ipv4Addr::from(from.sin_addr.s_addr.to_ne_bytes());
+                return Ok(Some((addr, is_final)));
+            }
+        }
+    }
+}
+
+impl Drop for RawIcmpSocket {
+    fn drop(&mut self) {
+        // SAFETY: fd was opened by this socket and is closed exactly once
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+pub(crate) fn echo_request(ident: u16, seq: u16) -> Vec<u8> {
+    let mut packet = vec![0u8; 8 + ECHO_PAYLOAD_LEN];
+    packet[0] = ICMP_ECHO_REQUEST;
+    packet[4..6].copy_from_slice(&ident.to_be_bytes());
+    packet[6..8].copy_from_slice(&seq.to_be_bytes());
+    for (i, byte) in packet[8..].iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    let checksum = internet_checksum(&packet);
+    packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    packet
+}
+
+/// RFC 1071 ones' complement checksum
+fn internet_checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|pair| u32::from(u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)])))
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// Match an IPv4 packet from a raw ICMP socket against our probe. Some(true) means the
+/// trace is finished; Some(false) is an intermediate router; None is someone else's packet.
+fn parse_reply(packet: &[u8], ident: u16, seq: u16) -> Option<bool> {
+    let icmp = packet.get(ip_header_len(packet)?..)?;
+    let matches_probe = |echo: &[u8]| {
+        echo.len() >= 8
+            && u16::from_be_bytes([echo[4], echo[5]]) == ident
+            && u16::from_be_bytes([echo[6], echo[7]]) == seq
+    };
+
+    match *icmp.first()? {
+        ICMP_ECHO_REPLY => matches_probe(icmp).then_some(true),
+        kind @ (ICMP_TIME_EXCEEDED | ICMP_DEST_UNREACHABLE) => {
+            // Error messages quote the original IP header plus the first 8 bytes of our echo
+            let quoted = icmp.get(8..)?;
+            let original = quoted.get(ip_header_len(quoted)?..)?;
+            (original.first() == Some(&ICMP_ECHO_REQUEST) && matches_probe(original))
+                .then_some(kind == ICMP_DEST_UNREACHABLE)
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn ip_header_len(packet: &[u8]) -> Option<usize> {
+    let len = usize::from(packet.first()? & 0x0f) * 4;
+    (len >= 20).then_some(len)
+}
+
+fn subprocess_trace(target: &str) -> Result<Vec<TracerouteHop>> {
+    let output = Command::new("traceroute")
+        .args([
+            "-n",
+            "-q",
+            &PROBES_PER_HOP.to_string(),
+            "-w",
+            &PROBE_TIMEOUT.as_secs().to_string(),
+            "-m",
+            &MAX_HOPS.to_string(),
+            target,
+        ])
+        .output()
+        .map_err(|e| anyhow!("traceroute unavailable: {e}"))?;
+
+    Ok(parse_traceroute_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// TTLs that both traces got an answer for, but from different routers. Hops that only
+/// one of them heard from are not counted, since routers often drop some probes.
+pub fn changed_ttls(previous: &[TracerouteHop], current: &[TracerouteHop]) -> Vec<u8> {
+    current
+        .iter()
+        .filter(|hop| {
+            previous
+                .iter()
+                .any(|before| before.ttl == hop.ttl && before.addr != hop.addr)
+        })
+        .map(|hop| hop.ttl)
+        .collect()
+}
+
+/// Parse `traceroute -n` output. Hops where every probe timed out are omitted.
+pub fn parse_traceroute_output(output: &str) -> Vec<TracerouteHop> {
+    let mut hops = Vec::new();
+
+    for line in output.lines() {
+        let mut tokens = line.split_whitespace().peekable();
+        let Some(ttl) = tokens.next().and_then(|t| t.parse::<u8>().ok()) else {
+            continue; // Header or continuation line
+        };
+
+        let mut addr = None;
+        let mut rtts = Vec::new();
+        let mut probes = 0;
+        while let Some(token) = tokens.next() {
+            if token == "*" {
+                probes += 1;
+            } else if let Ok(ip) = token.parse::<IpAddr>() {
+                // With load balancing a hop can list several routers; keep the first
+                addr.get_or_insert(ip);
+            } else if let Ok(rtt) = token.parse::<f64>() {
+                if tokens.peek() == Some(&"ms") {
+                    tokens.next();
+                    rtts.push(rtt);
+                    probes += 1;
+                }
+            }
+        }
+
+        if let Some(addr) = addr {
+            hops.push(hop_from_probes(ttl, addr, &rtts, probes));
+        }
+    }
+
+    hops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_traceroute_output() {
+        let output = "\
+traceroute to 1.1.1.1 (1.1.1.1), 30 hops max, 60 byte packets
+ 1  192.168.1.1  0.512 ms  0.480 ms  0.470 ms
+ 2  * * *
+ 3  10.0.0.1  5.000 ms *  7.000 ms
+ 4  10.0.0.2  9.000 ms 10.0.0.3  11.000 ms  10.000 ms
+ 5  1.1.1.1  12.000 ms !H  12.000 ms  12.000 ms
+";
+        let hops = parse_traceroute_output(output);
+        assert_eq!(hops.len(), 4);
+
+        assert_eq!(hops[0].ttl, 1);
+        assert_eq!(hops[0].addr, IpAddr::from([192, 168, 1, 1]));
+        assert_eq!(hops[0].packet_loss, 0.0);
+
+        // Silent hop 2 is skipped; hop 3 lost one probe of three
+        assert_eq!(hops[1].ttl, 3);
+        assert!((hops[1].avg_rtt_ms - 6.0).abs() < 1e-9);
+        assert!((hops[1].packet_loss - 100.0 / 3.0).abs() < 1e-4);
+
+        assert_eq!(hops[2].addr, IpAddr::from([10, 0, 0, 2]));
+        assert!((hops[2].avg_rtt_ms - 10.0).abs() < 1e-9);
+        assert_eq!(hops[3].ttl, 5);
+    }
+
+    #[test]
+    fn test_changed_ttls_compare_hops_answered_in_both_traces() {
+        let hop =
+            |ttl, last_octet| hop_from_probes(ttl, IpAddr::from([10, 0, 0, last_octet]), &[1.0], 1);
+        let previous = [hop(1, 1), hop(2, 2), hop(4, 4)];
+
+        assert!(changed_ttls(&[], &previous).is_empty());
+        assert!(changed_ttls(&previous, &previous).is_empty());
+
+        // Hop 2 moved to another router; hop 3 answering now and hop 4 not is not a change
+        let current = [hop(1, 1), hop(2, 9), hop(3, 3)];
+        assert_eq!(changed_ttls(&previous, &current), [2]);
+    }
+
+    #[test]
+    fn test_echo_request_and_reply_matching() {
+        let request = echo_request(0x1234, 7);
+        // A correct checksum makes the whole packet sum to zero
+        assert_eq!(internet_checksum(&request), 0);
+
+        let ip_header = {
+            let mut header = [0u8; 20];
+            header[0] = 0x45; // IPv4, 5 words
+            header
+        };
+
+        let mut reply = ip_header.to_vec();
+        let mut echo = request.clone();
+        echo[0] = ICMP_ECHO_REPLY;
+        reply.extend_from_slice(&echo);
+        assert_eq!(parse_reply(&reply, 0x1234, 7), Some(true));
+        assert_eq!(parse_reply(&reply, 0x1234, 8), None);
+
+        // Time exceeded quotes the original header and echo request
+        let mut exceeded = ip_header.to_vec();
+        exceeded.extend_from_slice(&[ICMP_TIME_EXCEEDED, 0, 0, 0, 0, 0, 0, 0]);
+        exceeded.extend_from_slice(&ip_header);
+        exceeded.extend_from_slice(&request[..8]);
+        assert_eq!(parse_reply(&exceeded, 0x1234, 7), Some(false));
+        assert_eq!(parse_reply(&exceeded, 0x4321, 7), None);
+    }
+}
@@ -0,0 +1,261 @@
+//! Formatting for transfer rates and data volumes in the user's selected units
+//!
+//! Every panel and text mode goes through [`format_rate`] and [`format_volume`], so
+//! cycling `u`/`U` or passing `--unit`/`--data-unit` changes all of them at once.
+//! Whether a K is 1000 or 1024 follows the config file's `UnitBase`, set once at
+//! startup with [`set_unit_base`]; 1024-based amounts are labelled KiB/Kibit.
+
+use crate::cli::{DataUnit, TrafficUnit};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+/// Whether unit prefixes step by 1000 (SI) or 1024 (IEC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitBase {
+    /// nload's convention, used when `UnitBase` isn't set: bits by 1000, bytes by 1024
+    #[default]
+    Mixed,
+    /// `UnitBase = "si"`: everything by 1000 (kbit, KB)
+    Si,
+    /// `UnitBase = "iec"`: everything by 1024 (Kibit, KiB)
+    Iec,
+}
+
+impl UnitBase {
+    /// The config file spelling, "si" or "iec"
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "si" => Some(Self::Si),
+            "iec" => Some(Self::Iec),
+            _ => None,
+        }
+    }
+
+    fn binary(self, bits: bool) -> bool {
+        match self {
+            Self::Mixed => !bits,
+            Self::Si => false,
+            Self::Iec => true,
+        }
+    }
+}
+
+static UNIT_BASE: AtomicU8 = AtomicU8::new(0);
+
+/// Use `base` for all formatting from now on
+pub fn set_unit_base(base: UnitBase) {
+    let value = match base {
+        UnitBase::Mixed => 0,
+        UnitBase::Si => 1,
+        UnitBase::Iec => 2,
+    };
+    UNIT_BASE.store(value, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn unit_base() -> UnitBase {
+    match UNIT_BASE.load(Ordering::Relaxed) {
+        1 => UnitBase::Si,
+        2 => UnitBase::Iec,
+        _ => UnitBase::Mixed,
+    }
+}
+
+/// A bytes-per-second rate in the given unit, e.g. "12.5 Mbit/s"
+#[must_use]
+pub fn format_rate(bytes_per_sec: u64, unit: &TrafficUnit) -> String {
+    format!("{}/s", format_amount(bytes_per_sec, unit, unit_base()))
+}
+
+/// A byte count in the given unit, e.g. "1.50 GiB"
+#[must_use]
+pub fn format_volume(bytes: u64, unit: &DataUnit) -> String {
+    format_amount(bytes, unit, unit_base())
+}
+
+/// A byte count with an automatic prefix, for amounts that don't follow the
+/// unit selection (memory, disk, `--once`/`--check` output)
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    format_volume(bytes, &DataUnit::HumanByte)
+}
+
+/// Compact connection age: 45s, 12m34s, 3h04m, 2d05h
+#[must_use]
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs >= 86_400 {
+        format!("{}d{:02}h", secs / 86_400, secs % 86_400 / 3600)
+    } else if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn format_amount(bytes: u64, unit: &TrafficUnit, base: UnitBase) -> String {
+    let bits = unit.is_bits();
+    let value = if bits { bytes.saturating_mul(8) } else { bytes };
+    let binary = base.binary(bits);
+    let divisor = if binary { 1024.0 } else { 1000.0 };
+    let prefixes = match (bits, binary) {
+        (true, false) => ["bit", "kbit", "Mbit", "Gbit", "Tbit"],
+        (true, true) => ["bit", "Kibit", "Mibit", "Gibit", "Tibit"],
+        (false, false) => ["B", "KB", "MB", "GB", "TB"],
+        (false, true) => ["B", "KiB", "MiB", "GiB", "TiB"],
+    };
+
+    let power = match unit {
+        TrafficUnit::HumanBit | TrafficUnit::HumanByte => {
+            return format_human_readable(value, &prefixes, divisor)
+        }
+        TrafficUnit::Bit | TrafficUnit::Byte => return format!("{value} {}", prefixes[0]),
+        TrafficUnit::KiloBit | TrafficUnit::KiloByte => 1,
+        TrafficUnit::MegaBit | TrafficUnit::MegaByte => 2,
+        TrafficUnit::GigaBit | TrafficUnit::GigaByte => 3,
+    };
+    format!(
+        "{:.2} {}",
+        value as f64 / f64::powi(divisor, power),
+        prefixes[power as usize]
+    )
+}
+
+// Largest prefix that keeps the number below `divisor`, with 3 significant digits
+fn format_human_readable(value: u64, prefixes: &[&str], divisor: f64) -> String {
+    let mut size = value as f64;
+    let mut index = 0;
+
+    // Step up early when rounding would print e.g. "1024 KiB" instead of "1.00 MiB"
+    while size >= divisor - 0.5 && index < prefixes.len() - 1 {
+        size /= divisor;
+        index += 1;
+    }
+
+    if size >= 100.0 {
+        format!("{:.0} {}", size, prefixes[index])
+    } else if size >= 10.0 {
+        format!("{:.1} {}", size, prefixes[index])
+    } else {
+        format!("{:.2} {}", size, prefixes[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The global base stays at its default here; other bases are passed explicitly
+    fn rate(bytes_per_sec: u64, unit: &TrafficUnit, base: UnitBase) -> String {
+        format!("{}/s", format_amount(bytes_per_sec, unit, base))
+    }
+
+    #[test]
+    fn test_human_units_pick_prefix_at_boundaries() {
+        // Bytes scale by 1024 by default: 1000 B is still bytes
+        assert_eq!(format_volume(999, &DataUnit::HumanByte), "999 B");
+        assert_eq!(format_volume(1000, &DataUnit::HumanByte), "1000 B");
+        assert_eq!(format_volume(1023, &DataUnit::HumanByte), "1023 B");
+        assert_eq!(format_volume(1024, &DataUnit::HumanByte), "1.00 KiB");
+        assert_eq!(
+            format_volume(1024 * 1024 - 1, &DataUnit::HumanByte),
+            "1.00 MiB"
+        );
+
+        // Bits scale by 1000, after multiplying by 8
+        assert_eq!(format_rate(0, &TrafficUnit::HumanBit), "0.00 bit/s");
+        assert_eq!(format_rate(124, &TrafficUnit::HumanBit), "992 bit/s");
+        assert_eq!(format_rate(125, &TrafficUnit::HumanBit), "1.00 kbit/s");
+        assert_eq!(format_rate(128, &TrafficUnit::HumanBit), "1.02 kbit/s");
+        assert_eq!(format_rate(124_990, &TrafficUnit::HumanBit), "1.00 Mbit/s");
+        assert_eq!(format_rate(125_000, &TrafficUnit::HumanBit), "1.00 Mbit/s");
+    }
+
+    #[test]
+    fn test_byte_boundaries_follow_unit_base() {
+        let volume = |bytes, base| format_amount(bytes, &DataUnit::HumanByte, base);
+        assert_eq!(format_bytes(1023), volume(1023, UnitBase::Mixed));
+
+        for (base, expected) in [
+            (UnitBase::Mixed, ["999 B", "1000 B", "1023 B", "1.00 KiB"]),
+            (UnitBase::Si, ["999 B", "1.00 KB", "1.02 KB", "1.02 KB"]),
+            (UnitBase::Iec, ["999 B", "1000 B", "1023 B", "1.00 KiB"]),
+        ] {
+            for (bytes, expected) in [999, 1000, 1023, 1024].into_iter().zip(expected) {
+                assert_eq!(volume(bytes, base), expected, "{bytes} B with {base:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_units_convert_bits_and_bytes() {
+        assert_eq!(format_rate(125, &TrafficUnit::Bit), "1000 bit/s");
+        assert_eq!(format_rate(1000, &TrafficUnit::Byte), "1000 B/s");
+        assert_eq!(format_rate(125, &TrafficUnit::KiloBit), "1.00 kbit/s");
+        assert_eq!(format_rate(1024, &TrafficUnit::KiloByte), "1.00 KiB/s");
+        assert_eq!(format_rate(1000, &TrafficUnit::KiloByte), "0.98 KiB/s");
+        assert_eq!(format_rate(125_000, &TrafficUnit::MegaBit), "1.00 Mbit/s");
+        assert_eq!(format_volume(1_048_576, &DataUnit::MegaByte), "1.00 MiB");
+        assert_eq!(
+            format_rate(125_000_000, &TrafficUnit::GigaBit),
+            "1.00 Gbit/s"
+        );
+        assert_eq!(
+            format_volume(1_073_741_824, &DataUnit::GigaByte),
+            "1.00 GiB"
+        );
+
+        // Bit conversion saturates instead of overflowing
+        assert_eq!(
+            format_rate(u64::MAX, &TrafficUnit::Bit),
+            format!("{} bit/s", u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_unit_base_sets_divisor_and_label() {
+        assert_eq!(UnitBase::parse("SI"), Some(UnitBase::Si));
+        assert_eq!(UnitBase::parse("iec"), Some(UnitBase::Iec));
+        assert_eq!(UnitBase::parse("binary"), None);
+
+        // SI: 1000 bytes is a KB, and no "i" anywhere
+        assert_eq!(rate(999, &TrafficUnit::HumanByte, UnitBase::Si), "999 B/s");
+        assert_eq!(
+            rate(1000, &TrafficUnit::HumanByte, UnitBase::Si),
+            "1.00 KB/s"
+        );
+        assert_eq!(
+            rate(1024, &TrafficUnit::HumanByte, UnitBase::Si),
+            "1.02 KB/s"
+        );
+        assert_eq!(
+            rate(1_000_000, &TrafficUnit::MegaByte, UnitBase::Si),
+            "1.00 MB/s"
+        );
+        assert_eq!(
+            rate(125, &TrafficUnit::HumanBit, UnitBase::Si),
+            "1.00 kbit/s"
+        );
+
+        // IEC: bits scale by 1024 too
+        assert_eq!(
+            rate(125, &TrafficUnit::HumanBit, UnitBase::Iec),
+            "1000 bit/s"
+        );
+        assert_eq!(
+            rate(128, &TrafficUnit::HumanBit, UnitBase::Iec),
+            "1.00 Kibit/s"
+        );
+        assert_eq!(
+            rate(131_072, &TrafficUnit::MegaBit, UnitBase::Iec),
+            "1.00 Mibit/s"
+        );
+        assert_eq!(
+            rate(1024, &TrafficUnit::HumanByte, UnitBase::Iec),
+            "1.00 KiB/s"
+        );
+    }
+}
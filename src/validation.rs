@@ -14,10 +14,13 @@ const MAX_INTERFACE_NAME_LEN: usize = 16;
 const MAX_PATH_LEN: usize = 4096;
 
 /// Maximum allowed refresh interval in milliseconds
-const MAX_REFRESH_INTERVAL: u64 = 60_000; // 1 minute
+pub const MAX_REFRESH_INTERVAL: u64 = 60_000; // 1 minute
 
 /// Minimum allowed refresh interval in milliseconds
-const MIN_REFRESH_INTERVAL: u64 = 100; // 0.1 seconds
+pub const MIN_REFRESH_INTERVAL: u64 = 100; // 0.1 seconds
+
+/// Minimum interval for the expensive collectors (connections, processes)
+const MIN_COLLECTOR_INTERVAL: u64 = 500;
 
 /// Validates network interface names to prevent path traversal and injection
 ///
@@ -223,6 +226,20 @@ pub fn validate_refresh_interval(interval_ms: u64) -> Result<()> {
     Ok(())
 }
 
+/// Validates update intervals of the expensive collectors (connection and process scans)
+///
+/// These walk /proc or spawn `ss`/`lsof`, so they get a higher floor than the
+/// interface stats refresh.
+pub fn validate_collector_interval(interval_ms: u64, name: &str) -> Result<()> {
+    if !(MIN_COLLECTOR_INTERVAL..=MAX_REFRESH_INTERVAL).contains(&interval_ms) {
+        return Err(NetwatchError::Config(format!(
+            "{name} must be between {MIN_COLLECTOR_INTERVAL} and {MAX_REFRESH_INTERVAL} ms"
+        )));
+    }
+
+    Ok(())
+}
+
 /// Validates bandwidth values to prevent overflow and unrealistic values
 ///
 /// # Security Considerations
@@ -242,6 +259,286 @@ pub fn validate_bandwidth(bandwidth_kbps: u64) -> Result<()> {
     Ok(())
 }
 
+/// Validates the traffic spike multiplier
+///
+/// A multiplier of 1.0 or less would flag ordinary traffic as a spike.
+pub fn validate_spike_multiplier(multiplier: f64) -> Result<()> {
+    if !multiplier.is_finite() || multiplier <= 1.0 || multiplier > 1000.0 {
+        return Err(NetwatchError::Config(
+            "Spike multiplier must be greater than 1.0 and at most 1000".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates the per-connection burst multiplier (`BurstMultiplier`)
+pub fn validate_burst_multiplier(multiplier: f64) -> Result<()> {
+    if !multiplier.is_finite() || multiplier <= 1.0 || multiplier > 1000.0 {
+        return Err(NetwatchError::Config(
+            "Burst multiplier must be greater than 1.0 and at most 1000".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates the anomaly sensitivity for a learned baseline (`--baseline-sigma`)
+pub fn validate_baseline_sigma(sigma: f64) -> Result<()> {
+    if !sigma.is_finite() || sigma <= 0.0 || sigma > 100.0 {
+        return Err(NetwatchError::Config(
+            "Baseline sigma must be greater than 0 and at most 100".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates a traffic quota in GB (`--quota-daily-gb` and the like)
+pub fn validate_quota_gb(gb: f64) -> Result<()> {
+    if !gb.is_finite() || gb <= 0.0 || gb > 1_000_000.0 {
+        return Err(NetwatchError::Config(
+            "Quota must be greater than 0 and at most 1000000 GB".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_ema_alpha(alpha: f64) -> Result<()> {
+    if !alpha.is_finite() || alpha <= 0.0 || alpha > 1.0 {
+        return Err(NetwatchError::Config(
+            "EMA alpha must be greater than 0 and at most 1".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_unit_base(base: &str) -> Result<()> {
+    if crate::units::UnitBase::parse(base).is_none() {
+        return Err(NetwatchError::Config(format!(
+            "Unit base must be \"si\" or \"iec\", got \"{base}\""
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks that `path` is a readable file in /proc/net/dev format for `--proc-net-dev`.
+/// Unlike other file arguments it may be under /proc, where /proc/PID/net/dev lives;
+/// it is only ever read.
+pub fn validate_proc_net_dev_path(path: &str) -> Result<()> {
+    if path.is_empty() || path.len() > MAX_PATH_LEN {
+        return Err(NetwatchError::Config(format!(
+            "Counters file path must be 1-{MAX_PATH_LEN} characters"
+        )));
+    }
+    if path.chars().any(|c| c.is_control()) {
+        return Err(NetwatchError::Config(
+            "Control characters not allowed in counters file path".to_string(),
+        ));
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            NetwatchError::Config(format!("Counters file {path} does not exist"))
+        }
+        std::io::ErrorKind::PermissionDenied => {
+            NetwatchError::PermissionDenied(format!("cannot read counters file {path}"))
+        }
+        _ => NetwatchError::Config(format!("Cannot read counters file {path}: {e}")),
+    })?;
+    // Both header lines of /proc/net/dev, e.g. " face |bytes    packets errs drop ..."
+    let mut lines = content.lines();
+    if !lines.next().is_some_and(|line| line.starts_with("Inter-|"))
+        || !lines.next().is_some_and(|line| line.contains("|bytes"))
+    {
+        return Err(NetwatchError::Config(format!(
+            "{path} is not in /proc/net/dev format"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that `--pid` names a running process this user can see: /proc/PID on
+/// Linux, `kill(PID, 0)` elsewhere
+pub fn validate_pid(pid: u32) -> Result<()> {
+    if pid == 0 {
+        return Err(NetwatchError::Config("PID must be at least 1".to_string()));
+    }
+    if !crate::processes::is_running(pid) {
+        return Err(NetwatchError::Config(format!(
+            "No process with PID {pid} is running (or it is not visible to this user)"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that `path` names a network namespace for `--netns`: a bind mount such as
+/// `ip netns add` creates under /var/run/netns, or /proc/PID/ns/net
+pub fn validate_netns_path(path: &str) -> Result<()> {
+    if path.is_empty() || path.len() > MAX_PATH_LEN {
+        return Err(NetwatchError::Config(format!(
+            "Network namespace path must be 1-{MAX_PATH_LEN} characters"
+        )));
+    }
+    if path.chars().any(|c| c.is_control()) {
+        return Err(NetwatchError::Config(
+            "Control characters not allowed in network namespace path".to_string(),
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::fd::AsRawFd;
+
+        // NS_GET_NSTYPE (Linux 4.11+): the CLONE_NEW* type of an nsfs file
+        const NS_GET_NSTYPE: u32 = 0xb703;
+
+        let file = std::fs::File::open(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                NetwatchError::Config(format!("Network namespace {path} does not exist"))
+            }
+            std::io::ErrorKind::PermissionDenied => NetwatchError::PermissionDenied(format!(
+                "cannot open network namespace {path}; run netwatch as root"
+            )),
+            _ => NetwatchError::Config(format!("Cannot open network namespace {path}: {e}")),
+        })?;
+        // SAFETY: NS_GET_NSTYPE takes no argument; other files just fail with ENOTTY
+        let kind = unsafe { libc::ioctl(file.as_raw_fd(), NS_GET_NSTYPE as _) };
+        if kind != libc::CLONE_NEWNET {
+            return Err(NetwatchError::Config(format!(
+                "{path} is not a network namespace"
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    Err(NetwatchError::Platform(
+        "--netns needs Linux network namespaces".to_string(),
+    ))
+}
+
+/// Parses a run length such as `90`, `90s`, `10m`, `2h` or `1d` (bare numbers are seconds).
+/// Zero is accepted and means no limit.
+///
+/// Used as a clap value parser for `--duration`.
+pub fn parse_duration(value: &str) -> Result<std::time::Duration> {
+    let value = value.trim();
+    let (number, unit_secs) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 3_600),
+        Some((i, 'd')) => (&value[..i], 86_400),
+        _ => (value, 1),
+    };
+
+    let secs = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit_secs))
+        .ok_or_else(|| {
+            NetwatchError::Config(format!(
+                "Invalid duration {value:?}: expected a number with an optional s/m/h/d suffix"
+            ))
+        })?;
+
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Validates a hostname or IP address passed to diagnostic tools
+///
+/// # Security Considerations
+/// - Hosts reach subprocess arguments (`traceroute`), so a leading `-` is rejected
+///   to prevent option injection
+/// - Only hostname and IP literal characters are allowed
+pub fn validate_host(host: &str) -> Result<()> {
+    if host.is_empty() || host.len() > 253 {
+        return Err(NetwatchError::Config(format!(
+            "Invalid host length: {host:?}"
+        )));
+    }
+
+    if host.starts_with('-')
+        || !host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '_'))
+    {
+        return Err(NetwatchError::Config(format!("Invalid host: {host:?}")));
+    }
+
+    Ok(())
+}
+
+/// Validates a `--remote` ssh destination: `host` or `user@host`
+///
+/// # Security Considerations
+/// - The destination reaches ssh's command line, so a leading `-` is rejected even
+///   though it is passed after `--`
+/// - The user part is limited to characters valid in login names
+pub fn validate_ssh_target(target: &str) -> Result<()> {
+    let (user, host) = match target.split_once('@') {
+        Some((user, host)) => (Some(user), host),
+        None => (None, target),
+    };
+    if let Some(user) = user {
+        if user.is_empty()
+            || user.len() > 32
+            || user.starts_with('-')
+            || !user
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        {
+            return Err(NetwatchError::Config(format!(
+                "Invalid ssh user in {target:?}"
+            )));
+        }
+    }
+    validate_host(host)
+}
+
+/// Validates a `host:port` or `[v6addr]:port` TCP connect check target
+pub fn validate_port_check(target: &str) -> Result<()> {
+    let Some((host, _)) = crate::port_check::split_host_port(target) else {
+        return Err(NetwatchError::Config(format!(
+            "Invalid port check {target:?}: expected HOST:PORT"
+        )));
+    };
+    validate_host(host)
+}
+
+/// Validates an `http://` or `https://` URL used for HTTP health checks
+///
+/// # Security Considerations
+/// - Only the two web schemes are accepted, so `file://` and friends cannot be probed
+/// - The host must pass [`validate_host`]; whitespace and control characters are rejected
+pub fn validate_http_url(url: &str) -> Result<()> {
+    if url.len() > 2048 {
+        return Err(NetwatchError::Config(format!("URL too long: {url:?}")));
+    }
+
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| {
+            NetwatchError::Config(format!("URL must start with http:// or https://: {url:?}"))
+        })?;
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(NetwatchError::Config(format!("Invalid URL: {url:?}")));
+    }
+
+    // Host is everything up to the path/query, minus credentials and port
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host_port.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host_port.split(':').next().unwrap_or_default(),
+    };
+    validate_host(host)
+}
+
 /// Validates configuration strings for injection attacks
 ///
 /// # Security Considerations
@@ -342,6 +639,83 @@ mod tests {
         assert!(validate_file_path("/tmp/file.txt", Some("log")).is_err()); // Wrong extension
     }
 
+    #[test]
+    fn test_host_validation() {
+        assert!(validate_host("1.1.1.1").is_ok());
+        assert!(validate_host("example.com").is_ok());
+        assert!(validate_host("2606:4700:4700::1111").is_ok());
+
+        assert!(validate_host("").is_err());
+        assert!(validate_host("-n").is_err()); // Option injection
+        assert!(validate_host("example.com; rm -rf /").is_err());
+    }
+
+    #[test]
+    fn test_http_url_validation() {
+        assert!(validate_http_url("https://example.com/health").is_ok());
+        assert!(validate_http_url("http://10.0.0.1:8080/status?verbose=1").is_ok());
+        assert!(validate_http_url("https://[::1]:8443/").is_ok());
+
+        assert!(validate_http_url("file:///etc/passwd").is_err());
+        assert!(validate_http_url("https://").is_err());
+        assert!(validate_http_url("https://exa mple.com/").is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pid_validation() {
+        assert!(validate_pid(std::process::id()).is_ok());
+        assert!(validate_pid(0).is_err());
+        // Above the kernel's pid_max (at most 2^22)
+        assert!(validate_pid(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_proc_net_dev_path_validation() {
+        assert!(validate_proc_net_dev_path("/proc/self/net/dev").is_ok());
+        assert!(validate_proc_net_dev_path("/proc/self/status").is_err());
+        assert!(validate_proc_net_dev_path("/nonexistent/net/dev").is_err());
+        assert!(validate_proc_net_dev_path("").is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_netns_path_validation() {
+        assert!(validate_netns_path("/proc/self/ns/net").is_ok());
+
+        // Other namespace types and ordinary files are refused
+        assert!(validate_netns_path("/proc/self/ns/uts").is_err());
+        assert!(validate_netns_path("/proc/self/status").is_err());
+        assert!(validate_netns_path("/var/run/netns/does-not-exist").is_err());
+        assert!(validate_netns_path("").is_err());
+
+        // Bare names are looked up where `ip netns add` puts them
+        assert_eq!(crate::platform::netns_path("vpn"), "/var/run/netns/vpn");
+        assert_eq!(
+            crate::platform::netns_path("/proc/1/ns/net"),
+            "/proc/1/ns/net"
+        );
+        assert!(validate_netns_path(&crate::platform::netns_path("does-not-exist")).is_err());
+    }
+
+    #[test]
+    fn test_duration_parsing() {
+        use std::time::Duration;
+
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7_200));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86_400));
+
+        assert_eq!(parse_duration("0").unwrap(), Duration::ZERO);
+        assert_eq!(parse_duration("0m").unwrap(), Duration::ZERO);
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("-5s").is_err());
+    }
+
     #[test]
     fn test_refresh_interval_validation() {
         // Valid intervals
@@ -352,6 +726,13 @@ mod tests {
         // Invalid intervals
         assert!(validate_refresh_interval(50).is_err()); // Too small
         assert!(validate_refresh_interval(120000).is_err()); // Too large
+
+        // Fastest supported interface refresh
+        assert!(validate_refresh_interval(100).is_ok());
+
+        // Collectors have their own, higher floor
+        assert!(validate_collector_interval(4000, "ConnectionInterval").is_ok());
+        assert!(validate_collector_interval(100, "ConnectionInterval").is_err());
     }
 
     #[test]
@@ -366,6 +747,20 @@ mod tests {
         assert!(validate_bandwidth(2_000_000_000).is_err()); // Unrealistic
     }
 
+    #[test]
+    fn test_ssh_target_validation() {
+        assert!(validate_ssh_target("db1.internal").is_ok());
+        assert!(validate_ssh_target("ops@10.0.0.7").is_ok());
+        assert!(validate_ssh_target("deploy_bot@fe80::1").is_ok());
+
+        assert!(validate_ssh_target("-oProxyCommand=sh").is_err());
+        assert!(validate_ssh_target("-x@host").is_err());
+        assert!(validate_ssh_target("@host").is_err());
+        assert!(validate_ssh_target("ops@").is_err());
+        assert!(validate_ssh_target("ops@host;reboot").is_err());
+        assert!(validate_ssh_target("o ps@host").is_err());
+    }
+
     #[test]
     fn test_config_string_validation() {
         // Valid config strings
@@ -30,6 +30,19 @@ fn test_list_flag() {
     );
 }
 
+#[test]
+fn test_list_verbose_explains_exclusions() {
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.args(["--list", "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("excluded: loopback"));
+
+    // --verbose only qualifies --list
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.arg("--verbose").assert().failure();
+}
+
 #[test]
 fn test_invalid_argument() {
     let mut cmd = Command::cargo_bin("netwatch").unwrap();
@@ -98,3 +111,42 @@ fn test_bandwidth_scale_options() {
         .assert()
         .success();
 }
+
+#[test]
+fn test_once_flag_documented() {
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--once"));
+}
+
+#[test]
+fn test_no_restore_session_flag_documented() {
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--no-restore-session"));
+}
+
+#[test]
+fn test_json_requires_once() {
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.arg("--json").assert().failure();
+}
+
+#[test]
+fn test_spike_multiplier_validation() {
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.args(["--spike-multiplier", "3.5"])
+        .arg("--list")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("netwatch").unwrap();
+    cmd.args(["--spike-multiplier", "0.5"])
+        .arg("--list")
+        .assert()
+        .failure();
+}